@@ -1,17 +1,37 @@
 mod parsers;
 
-use crate::api::parsers::{parse_account_to_proto, parse_to_domain_account_type};
+use crate::api::parsers::{
+    parse_account_to_proto, parse_to_domain_account_status, parse_to_domain_account_type,
+    parse_to_domain_filter,
+};
+use crate::domain::account::AccountError;
 use crate::repo::AccountRepository;
 use crate::service::AccountsService;
 
 use accounts_proto::accounts_v1;
 
 use async_trait::async_trait;
+use common::kafka::Publisher;
+
+/// parse_error_to_status maps account errors to their grpc codes, anything else is internal.
+fn parse_error_to_status(e: anyhow::Error, message: &str) -> tonic::Status {
+    match e.downcast_ref::<AccountError>() {
+        Some(error @ AccountError::NotFound(_)) => tonic::Status::not_found(error.to_string()),
+        Some(error @ AccountError::InvalidId(_)) => {
+            tonic::Status::invalid_argument(error.to_string())
+        }
+        Some(error @ AccountError::Closed(_)) => {
+            tonic::Status::failed_precondition(error.to_string())
+        }
+        None => tonic::Status::internal(format!("{message}: {e}")),
+    }
+}
 
 #[async_trait]
-impl<R> accounts_v1::accounts_server::Accounts for AccountsService<R>
+impl<R, P> accounts_v1::accounts_server::Accounts for AccountsService<R, P>
 where
     R: AccountRepository,
+    P: Publisher,
 {
     async fn health_check(
         &self,
@@ -53,9 +73,21 @@ where
 
     async fn get_accounts(
         &self,
-        _request: tonic::Request<accounts_v1::GetAccountsRequest>,
+        request: tonic::Request<accounts_v1::GetAccountsRequest>,
     ) -> Result<tonic::Response<accounts_v1::GetAccountsResponse>, tonic::Status> {
-        todo!()
+        let filter = match parse_to_domain_filter(request.into_inner().filter) {
+            Ok(filter) => filter,
+            Err(e) => return Err(tonic::Status::invalid_argument(e.to_string())),
+        };
+
+        let accounts = match self.get_accounts(&filter).await {
+            Ok(accounts) => accounts.into_iter().map(parse_account_to_proto).collect(),
+            Err(e) => return Err(parse_error_to_status(e, "failed to get accounts")),
+        };
+
+        Ok(tonic::Response::new(accounts_v1::GetAccountsResponse {
+            accounts,
+        }))
     }
 
     async fn get_account(
@@ -65,16 +97,80 @@ where
         let request = request.into_inner();
         let account = match self.get_account_by_id(request.account_id.as_str()).await {
             Ok(account) => parse_account_to_proto(account),
-            Err(e) => {
-                return Err(tonic::Status::new(
-                    tonic::Code::Internal,
-                    format!("failed to get account: {}", e),
-                ));
-            }
+            Err(e) => return Err(parse_error_to_status(e, "failed to get account")),
         };
 
         Ok(tonic::Response::new(accounts_v1::GetAccountResponse {
             account: Some(account),
         }))
     }
+
+    async fn update_account_status(
+        &self,
+        request: tonic::Request<accounts_v1::UpdateAccountStatusRequest>,
+    ) -> Result<tonic::Response<accounts_v1::UpdateAccountStatusResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let status = match parse_to_domain_account_status(request.status) {
+            Ok(status) => status,
+            Err(e) => return Err(tonic::Status::invalid_argument(e.to_string())),
+        };
+
+        let account = match self
+            .update_account_status(request.account_id.as_str(), status)
+            .await
+        {
+            Ok(account) => parse_account_to_proto(account),
+            Err(e) => return Err(parse_error_to_status(e, "failed to update account status")),
+        };
+
+        Ok(tonic::Response::new(
+            accounts_v1::UpdateAccountStatusResponse {
+                account: Some(account),
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_to_status() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            error: anyhow::Error,
+            expected: tonic::Code,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "not found when account does not exist",
+                error: anyhow::Error::from(AccountError::NotFound("id".to_string()))
+                    .context("Failed to get_account_by_id"),
+                expected: tonic::Code::NotFound,
+            },
+            TestCase {
+                name: "invalid argument when account id is not a uuid",
+                error: AccountError::InvalidId("id".to_string()).into(),
+                expected: tonic::Code::InvalidArgument,
+            },
+            TestCase {
+                name: "failed precondition when account is closed",
+                error: AccountError::Closed("id".to_string()).into(),
+                expected: tonic::Code::FailedPrecondition,
+            },
+            TestCase {
+                name: "internal for any other error",
+                error: anyhow::anyhow!("connection reset"),
+                expected: tonic::Code::Internal,
+            },
+        ];
+
+        for test_case in test_cases {
+            let status = parse_error_to_status(test_case.error, "failed");
+            assert_eq!(status.code(), test_case.expected, "{}", test_case.name);
+        }
+    }
 }
@@ -14,8 +14,8 @@ pub fn parse_account_to_proto(account: domain::account::Account) -> accounts_v1:
         },
         status: match account.account_status {
             domain::account::Status::Active => accounts_v1::AccountStatus::Active as i32,
-            domain::account::Status::Frozen => accounts_v1::AccountStatus::Active as i32,
-            domain::account::Status::Closed => accounts_v1::AccountStatus::Active as i32,
+            domain::account::Status::Frozen => accounts_v1::AccountStatus::Frozen as i32,
+            domain::account::Status::Closed => accounts_v1::AccountStatus::Closed as i32,
         },
         created_by: account.created_by,
         created_at: Some(Timestamp {
@@ -39,12 +39,50 @@ pub fn parse_to_domain_account_type(account_type: i32) -> anyhow::Result<domain:
     }
 }
 
+pub fn parse_to_domain_account_status(
+    account_status: i32,
+) -> anyhow::Result<domain::account::Status> {
+    match account_status {
+        1 => Ok(domain::account::Status::Active),
+        2 => Ok(domain::account::Status::Frozen),
+        3 => Ok(domain::account::Status::Closed),
+        _ => Err(anyhow::anyhow!("Unspecified account status")),
+    }
+}
+
+/// parse_to_domain_filter treats unspecified type and status as no filter on them.
+pub fn parse_to_domain_filter(
+    filter: Option<accounts_v1::get_accounts_request::Filter>,
+) -> anyhow::Result<domain::account::Filter> {
+    let filter = filter.unwrap_or_default();
+    let mut ids = Vec::with_capacity(filter.account_ids.len());
+    for id in &filter.account_ids {
+        match uuid::Uuid::parse_str(id) {
+            Ok(id) => ids.push(id),
+            Err(_) => return Err(domain::account::AccountError::InvalidId(id.to_string()).into()),
+        }
+    }
+
+    Ok(domain::account::Filter {
+        ids,
+        account_type: match filter.account_type {
+            0 => None,
+            account_type => Some(parse_to_domain_account_type(account_type)?),
+        },
+        account_status: match filter.account_status {
+            0 => None,
+            account_status => Some(parse_to_domain_account_status(account_status)?),
+        },
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::domain;
     use crate::domain::account::{Account, Status, Type};
-    use accounts_proto::accounts_v1::AccountType;
+    use accounts_proto::accounts_v1::get_accounts_request::Filter;
+    use accounts_proto::accounts_v1::{AccountStatus, AccountType};
 
     #[test]
     fn test_parse_to_domain_account_type() {
@@ -112,4 +150,71 @@ mod tests {
         assert_eq!(account_proto.r#type, AccountType::Customer as i32);
         assert_eq!(account_proto.status, Status::Active as i32);
     }
+
+    #[test]
+    fn test_parse_account_status_to_proto() {
+        let test_cases = vec![
+            (Status::Active, AccountStatus::Active),
+            (Status::Frozen, AccountStatus::Frozen),
+            (Status::Closed, AccountStatus::Closed),
+        ];
+
+        for (status, expected) in test_cases {
+            let account = Account::new("test", Type::Customer, status.clone(), "test");
+            let account_proto = parse_account_to_proto(account);
+            assert_eq!(account_proto.status, expected as i32, "{status:?}");
+            assert_eq!(
+                parse_to_domain_account_status(account_proto.status).unwrap(),
+                status
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_to_domain_filter() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            filter: Option<Filter>,
+            expected: Option<domain::account::Filter>,
+        }
+
+        let id = uuid::Uuid::new_v4();
+        let test_cases = vec![
+            TestCase {
+                name: "empty filter when none is given",
+                filter: None,
+                expected: Some(domain::account::Filter::default()),
+            },
+            TestCase {
+                name: "successfully parse ids, type and status",
+                filter: Some(Filter {
+                    account_ids: vec![id.to_string()],
+                    account_type: AccountType::Merchant as i32,
+                    account_status: AccountStatus::Frozen as i32,
+                }),
+                expected: Some(domain::account::Filter {
+                    ids: vec![id],
+                    account_type: Some(Type::Merchant),
+                    account_status: Some(Status::Frozen),
+                }),
+            },
+            TestCase {
+                name: "error when an account id is not a uuid",
+                filter: Some(Filter {
+                    account_ids: vec!["not-a-uuid".to_string()],
+                    ..Filter::default()
+                }),
+                expected: None,
+            },
+        ];
+
+        for test_case in test_cases {
+            let resp = parse_to_domain_filter(test_case.filter);
+            match test_case.expected {
+                Some(expected) => assert_eq!(resp.unwrap(), expected, "{}", test_case.name),
+                None => assert!(resp.is_err(), "{}", test_case.name),
+            }
+        }
+    }
 }
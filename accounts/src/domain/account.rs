@@ -1,3 +1,5 @@
+use std::fmt;
+
 #[derive(Debug, Clone, PartialEq, sqlx::Type)]
 #[sqlx(type_name = "account_status", rename_all = "lowercase")]
 pub enum Status {
@@ -74,4 +76,37 @@ impl Account {
     pub fn set_status(&mut self, status: Status) {
         self.account_status = status;
     }
+
+    /// can_transition_to tells whether the account may move to `status`, closed accounts
+    /// cannot be reopened or frozen.
+    pub fn can_transition_to(&self, status: &Status) -> bool {
+        self.account_status != Status::Closed || *status == Status::Closed
+    }
+}
+
+/// Filter narrows the accounts returned by get_accounts, unset fields match every account.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Filter {
+    pub ids: Vec<uuid::Uuid>,
+    pub account_type: Option<Type>,
+    pub account_status: Option<Status>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccountError {
+    NotFound(String),
+    InvalidId(String),
+    Closed(String),
 }
+
+impl fmt::Display for AccountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccountError::NotFound(id) => write!(f, "account {id} not found"),
+            AccountError::InvalidId(id) => write!(f, "invalid account_id {id}"),
+            AccountError::Closed(id) => write!(f, "account {id} is closed"),
+        }
+    }
+}
+
+impl std::error::Error for AccountError {}
@@ -2,7 +2,7 @@ use accounts::repo;
 use accounts::service::AccountsService;
 use accounts::{DEFAULT_READER_MAX_CONN, DEFAULT_TIMEOUT_SECONDS, DEFAULT_WRITER_MAX_CONN};
 use accounts_proto::accounts_v1::{FILE_DESCRIPTOR_SET, accounts_server};
-use common::{database, shutdown};
+use common::{database, kafka, shutdown};
 use std::env;
 use tonic::codegen::tokio_stream::wrappers::TcpListenerStream;
 use tonic::transport::Server;
@@ -24,8 +24,15 @@ async fn main() -> anyhow::Result<()> {
     // setup repo layer
     let repo = repo::PgAccountRepository::new(db);
 
+    // setup kafka, the ledger learns about accounts from their events
+    let kafka_config = kafka::Config {
+        brokers: env::var("KAFKA_BROKERS").expect("KAFKA_BROKERS must be set"),
+        group_id: env::var("KAFKA_GROUP_ID").unwrap_or("accounts".to_string()),
+    };
+    let publisher = kafka::KafkaPublisher::new(&kafka_config)?;
+
     // setup service
-    let service = AccountsService::new(repo);
+    let service = AccountsService::new(repo, publisher);
 
     // add reflection
     let reflection_service = tonic_reflection::server::Builder::configure()
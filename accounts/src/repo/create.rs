@@ -1,4 +1,4 @@
-use crate::domain::account::Account;
+use crate::domain::account::{Account, AccountError, Status};
 use crate::repo::{AccountWriter, PgAccountRepository};
 use async_trait::async_trait;
 use chrono;
@@ -29,6 +29,31 @@ impl AccountWriter for PgAccountRepository {
             Err(e) => anyhow::bail!("Failed to insert into database: {e}"),
         }
     }
+
+    async fn update_account_status(
+        &self,
+        id: uuid::Uuid,
+        status: Status,
+    ) -> anyhow::Result<Account> {
+        let result = sqlx::query_as::<_, Account>(
+            r#"
+            UPDATE accounts
+            SET account_status = $2, updated_at = now()
+            WHERE id = $1
+            RETURNING id, name, account_type, account_status, created_by, created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(status)
+        .fetch_one(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(account) => Ok(account),
+            Err(sqlx::Error::RowNotFound) => Err(AccountError::NotFound(id.to_string()).into()),
+            Err(e) => anyhow::bail!("Failed to update account status: {e}"),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -87,4 +112,42 @@ mod tests {
             account.updated_at.with_nanosecond(0).unwrap()
         );
     }
+
+    #[sqlx::test(migrations = "../migrations/accounts")]
+    async fn successfully_update_account_status(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgAccountRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let account = repo
+            .create_account(&Account::new(
+                "test account",
+                domain::account::Type::Customer,
+                Status::Active,
+                "test creator",
+            ))
+            .await
+            .unwrap();
+
+        // act
+        let updated = repo
+            .update_account_status(account.id, Status::Frozen)
+            .await
+            .unwrap();
+        let missing = repo
+            .update_account_status(uuid::Uuid::new_v4(), Status::Frozen)
+            .await;
+
+        // assert
+        assert_eq!(updated.account_status, Status::Frozen);
+        assert!(updated.updated_at >= account.updated_at);
+        let fetched = repo
+            .get_account_by_id(account.id.to_string().as_str())
+            .await
+            .unwrap();
+        assert_eq!(fetched.account_status, Status::Frozen);
+        assert!(matches!(
+            missing.unwrap_err().downcast_ref::<AccountError>(),
+            Some(AccountError::NotFound(_))
+        ));
+    }
 }
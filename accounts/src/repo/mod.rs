@@ -1,4 +1,4 @@
-use crate::domain::account::{Account, Status, Type};
+use crate::domain::account::{Account, Filter, Status, Type};
 use async_trait::async_trait;
 use common::database::Database;
 
@@ -24,11 +24,18 @@ pub trait AccountReader: 'static + Sync + Send {
     async fn get_account_by_id(&self, id: &str) -> anyhow::Result<Account>;
     async fn get_accounts_by_type(&self, account_type: Type) -> anyhow::Result<Vec<Account>>;
     async fn get_accounts_by_status(&self, status: Status) -> anyhow::Result<Vec<Account>>;
+    /// get_accounts returns the accounts matching every set field of the filter, oldest first.
+    async fn get_accounts(&self, filter: &Filter) -> anyhow::Result<Vec<Account>>;
 }
 
 #[async_trait]
 pub trait AccountWriter: 'static + Sync + Send {
     async fn create_account(&self, account: &Account) -> anyhow::Result<Account>;
+    async fn update_account_status(
+        &self,
+        id: uuid::Uuid,
+        status: Status,
+    ) -> anyhow::Result<Account>;
 }
 
 impl AccountRepository for PgAccountRepository {}
@@ -1,4 +1,4 @@
-use crate::domain::account::{Account, Status, Type};
+use crate::domain::account::{Account, AccountError, Filter, Status, Type};
 use crate::repo::{AccountReader, PgAccountRepository};
 use anyhow;
 use async_trait::async_trait;
@@ -9,7 +9,7 @@ impl AccountReader for PgAccountRepository {
     async fn get_account_by_id(&self, id: &str) -> anyhow::Result<Account> {
         let account_id = match Uuid::parse_str(id) {
             Ok(account_id) => account_id,
-            Err(e) => anyhow::bail!("failed to parse account_id to str: {}", e),
+            Err(_) => return Err(AccountError::InvalidId(id.to_string()).into()),
         };
 
         let result = sqlx::query_as::<_, Account>(
@@ -25,9 +25,7 @@ impl AccountReader for PgAccountRepository {
 
         match result {
             Ok(account) => Ok(account),
-            Err(sqlx::Error::RowNotFound) => {
-                anyhow::bail!("Account {id} not found")
-            }
+            Err(sqlx::Error::RowNotFound) => Err(AccountError::NotFound(id.to_string()).into()),
             Err(e) => {
                 anyhow::bail!("Failed to get_account_by_id: {e}")
             }
@@ -79,6 +77,31 @@ impl AccountReader for PgAccountRepository {
             }
         }
     }
+
+    async fn get_accounts(&self, filter: &Filter) -> anyhow::Result<Vec<Account>> {
+        let result = sqlx::query_as::<_, Account>(
+            r#"
+                SELECT id, name, account_type, account_status, created_by, created_at, updated_at
+                FROM accounts
+                WHERE (cardinality($1::UUID[]) = 0 OR id = ANY($1))
+                  AND ($2::account_type IS NULL OR account_type = $2)
+                  AND ($3::account_status IS NULL OR account_status = $3)
+                ORDER BY created_at, id
+                "#,
+        )
+        .bind(&filter.ids)
+        .bind(filter.account_type.clone())
+        .bind(filter.account_status.clone())
+        .fetch_all(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(accounts) => Ok(accounts),
+            Err(e) => {
+                anyhow::bail!("Failed to get_accounts: {e}")
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -252,4 +275,70 @@ mod tests {
 
         // todo: add assertions on account level
     }
+
+    #[sqlx::test(migrations = "../migrations/accounts")]
+    async fn test_get_accounts_by_filter(pool: sqlx::PgPool) {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            filter: Filter,
+            expected: Vec<&'static str>,
+        }
+
+        // arrange - setup repo, insert accounts
+        let repo =
+            PgAccountRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let mut ids = Vec::new();
+        for (name, account_type, account_status) in [
+            ("customer active", Type::Customer, Status::Active),
+            ("customer frozen", Type::Customer, Status::Frozen),
+            ("merchant active", Type::Merchant, Status::Active),
+        ] {
+            let account = Account::new(name, account_type, account_status, "test creator");
+            ids.push(repo.create_account(&account).await.unwrap().id);
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "every account when the filter is empty",
+                filter: Filter::default(),
+                expected: vec!["customer active", "customer frozen", "merchant active"],
+            },
+            TestCase {
+                name: "accounts of the given type and status",
+                filter: Filter {
+                    account_type: Some(Type::Customer),
+                    account_status: Some(Status::Active),
+                    ..Filter::default()
+                },
+                expected: vec!["customer active"],
+            },
+            TestCase {
+                name: "accounts with the given ids",
+                filter: Filter {
+                    ids: vec![ids[2], ids[1], uuid::Uuid::new_v4()],
+                    ..Filter::default()
+                },
+                expected: vec!["customer frozen", "merchant active"],
+            },
+            TestCase {
+                name: "no accounts when nothing matches",
+                filter: Filter {
+                    ids: vec![ids[0]],
+                    account_status: Some(Status::Closed),
+                    ..Filter::default()
+                },
+                expected: vec![],
+            },
+        ];
+
+        for test_case in test_cases {
+            // act
+            let accounts = repo.get_accounts(&test_case.filter).await.unwrap();
+
+            // assert
+            let names: Vec<&str> = accounts.iter().map(|a| a.name.as_str()).collect();
+            assert_eq!(names, test_case.expected, "{}", test_case.name);
+        }
+    }
 }
@@ -1,22 +1,31 @@
-use crate::domain::account::{Account, Status, Type};
+use crate::domain::account::{Account, AccountError, Filter, Status, Type};
 use crate::repo::AccountRepository;
+use anyhow::Context;
+use common::kafka::{ACCOUNTS_EVENTS_TOPIC, Publisher};
+use events_proto::events_v1;
+use prost::Message;
+use prost_types::Timestamp;
 
-#[derive(Debug, Clone)]
-pub struct AccountsService<R>
+pub struct AccountsService<R, P>
 where
     R: AccountRepository,
+    P: Publisher,
 {
     repo: R,
+    publisher: P,
 }
 
-impl<R> AccountsService<R>
+impl<R, P> AccountsService<R, P>
 where
     R: AccountRepository,
+    P: Publisher,
 {
-    pub fn new(repo: R) -> Self {
-        Self { repo }
+    pub fn new(repo: R, publisher: P) -> Self {
+        Self { repo, publisher }
     }
 
+    /// create_account stores the account and publishes it to `accounts_events`, which is how
+    /// the ledger learns about it.
     pub async fn create_account(
         &self,
         name: impl Into<String>,
@@ -25,21 +34,24 @@ where
     ) -> anyhow::Result<Account> {
         let account = Account::new(name.into(), account_type, Status::Active, created_by.into());
 
-        match self.repo.create_account(&account).await {
-            Ok(account) => Ok(account),
+        let created = match self.repo.create_account(&account).await {
+            Ok(account) => account,
             Err(e) => {
                 anyhow::bail!("Failed to create_account: {:?}", e);
             }
-        }
+        };
+        self.publish_account(&created).await?;
+
+        Ok(created)
     }
 
+    /// get_account_by_id keeps account errors downcastable, so callers can tell a missing
+    /// account from a failure.
     pub async fn get_account_by_id(&self, id: &str) -> anyhow::Result<Account> {
-        match self.repo.get_account_by_id(id).await {
-            Ok(account) => Ok(account),
-            Err(e) => {
-                anyhow::bail!("Failed to get_account_by_id: {:?}", e);
-            }
-        }
+        self.repo
+            .get_account_by_id(id)
+            .await
+            .context("Failed to get_account_by_id")
     }
 
     pub async fn get_accounts_by_type(&self, account_type: Type) -> anyhow::Result<Vec<Account>> {
@@ -59,6 +71,67 @@ where
             }
         }
     }
+
+    pub async fn get_accounts(&self, filter: &Filter) -> anyhow::Result<Vec<Account>> {
+        match self.repo.get_accounts(filter).await {
+            Ok(accounts) => Ok(accounts),
+            Err(e) => {
+                anyhow::bail!("Failed to get_accounts: {:?}", e);
+            }
+        }
+    }
+
+    /// update_account_status moves the account to `status`, failing with AccountError::Closed
+    /// when a closed account would be reopened or frozen.
+    pub async fn update_account_status(&self, id: &str, status: Status) -> anyhow::Result<Account> {
+        let account = self.get_account_by_id(id).await?;
+        if !account.can_transition_to(&status) {
+            return Err(AccountError::Closed(id.to_string()).into());
+        }
+
+        let updated = self
+            .repo
+            .update_account_status(account.id, status)
+            .await
+            .context("Failed to update_account_status")?;
+        self.publish_account(&updated).await?;
+
+        Ok(updated)
+    }
+
+    async fn publish_account(&self, account: &Account) -> anyhow::Result<()> {
+        let event = events_v1::Account {
+            id: account.id.to_string(),
+            name: account.name.clone(),
+            r#type: match account.account_type {
+                Type::Customer => events_v1::AccountType::Customer,
+                Type::Merchant => events_v1::AccountType::Merchant,
+                Type::System => events_v1::AccountType::System,
+            } as i32,
+            status: match account.account_status {
+                Status::Active => events_v1::AccountStatus::Active,
+                Status::Frozen => events_v1::AccountStatus::Frozen,
+                Status::Closed => events_v1::AccountStatus::Closed,
+            } as i32,
+            created_by: account.created_by.clone(),
+            created_at: Some(Timestamp {
+                seconds: account.created_at.timestamp(),
+                nanos: account.created_at.timestamp_subsec_nanos() as i32,
+            }),
+            updated_at: Some(Timestamp {
+                seconds: account.updated_at.timestamp(),
+                nanos: account.updated_at.timestamp_subsec_nanos() as i32,
+            }),
+        };
+
+        self.publisher
+            .publish(
+                ACCOUNTS_EVENTS_TOPIC,
+                account.id.to_string().as_str(),
+                event.encode_to_vec(),
+            )
+            .await
+    }
 }
 
 #[cfg(test)]
@@ -66,7 +139,25 @@ mod tests {
     use super::*;
     use crate::repo;
     use crate::repo::PgAccountRepository;
+    use async_trait::async_trait;
     use common::database;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    pub(crate) struct InMemoryPublisher {
+        pub messages: Mutex<Vec<(String, String, Vec<u8>)>>,
+    }
+
+    #[async_trait]
+    impl Publisher for InMemoryPublisher {
+        async fn publish(&self, topic: &str, key: &str, payload: Vec<u8>) -> anyhow::Result<()> {
+            self.messages
+                .lock()
+                .unwrap()
+                .push((topic.to_string(), key.to_string(), payload));
+            Ok(())
+        }
+    }
 
     async fn setup_database(repo: &impl AccountRepository) -> anyhow::Result<()> {
         let accounts: Vec<Account> = vec![
@@ -113,7 +204,7 @@ mod tests {
         let repo = repo::PgAccountRepository {
             db: database::Database::from_pool(pool).await.unwrap(),
         };
-        let account_service = AccountsService::new(repo);
+        let account_service = AccountsService::new(repo, InMemoryPublisher::default());
 
         // act - we are also testing the create account here as well
         let result = account_service
@@ -136,7 +227,7 @@ mod tests {
         // arrange
         let repo =
             PgAccountRepository::new(common::database::Database::from_pool(pool).await.unwrap());
-        let account_service = AccountsService::new(repo.clone());
+        let account_service = AccountsService::new(repo.clone(), InMemoryPublisher::default());
         setup_database(&repo).await.unwrap();
 
         // act
@@ -154,7 +245,7 @@ mod tests {
         let repo = repo::PgAccountRepository {
             db: database::Database::from_pool(pool).await.unwrap(),
         };
-        let account_service = AccountsService::new(repo.clone());
+        let account_service = AccountsService::new(repo.clone(), InMemoryPublisher::default());
         setup_database(&repo).await.unwrap();
 
         // act
@@ -165,4 +256,103 @@ mod tests {
         let accounts = result.unwrap();
         assert_eq!(accounts.len(), 1);
     }
+
+    #[sqlx::test(migrations = "../migrations/accounts")]
+    async fn test_update_account_status(pool: sqlx::PgPool) {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            status: Status,
+            expected: Option<Status>,
+        }
+
+        // arrange
+        let repo =
+            PgAccountRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let account_service = AccountsService::new(repo, InMemoryPublisher::default());
+        let account = account_service
+            .create_account("test account", Type::Customer, "test user")
+            .await
+            .unwrap();
+        let id = account.id.to_string();
+
+        let test_cases = vec![
+            TestCase {
+                name: "successfully freeze an active account",
+                status: Status::Frozen,
+                expected: Some(Status::Frozen),
+            },
+            TestCase {
+                name: "successfully unfreeze a frozen account",
+                status: Status::Active,
+                expected: Some(Status::Active),
+            },
+            TestCase {
+                name: "successfully close an active account",
+                status: Status::Closed,
+                expected: Some(Status::Closed),
+            },
+            TestCase {
+                name: "error when reopening a closed account",
+                status: Status::Active,
+                expected: None,
+            },
+            TestCase {
+                name: "error when freezing a closed account",
+                status: Status::Frozen,
+                expected: None,
+            },
+        ];
+
+        for test_case in test_cases {
+            // act
+            let result = account_service
+                .update_account_status(id.as_str(), test_case.status)
+                .await;
+
+            // assert
+            match test_case.expected {
+                Some(status) => {
+                    assert_eq!(result.unwrap().account_status, status, "{}", test_case.name)
+                }
+                None => assert_eq!(
+                    result.unwrap_err().downcast_ref::<AccountError>(),
+                    Some(&AccountError::Closed(id.clone())),
+                    "{}",
+                    test_case.name
+                ),
+            }
+        }
+    }
+
+    #[sqlx::test(migrations = "../migrations/accounts")]
+    async fn successfully_publish_created_and_updated_accounts(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgAccountRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let account_service = AccountsService::new(repo, InMemoryPublisher::default());
+
+        // act
+        let account = account_service
+            .create_account("test account", Type::Merchant, "test user")
+            .await
+            .unwrap();
+        account_service
+            .update_account_status(account.id.to_string().as_str(), Status::Frozen)
+            .await
+            .unwrap();
+
+        // assert
+        let messages = account_service.publisher.messages.lock().unwrap();
+        assert_eq!(messages.len(), 2);
+        for (topic, key, _) in messages.iter() {
+            assert_eq!(topic, ACCOUNTS_EVENTS_TOPIC);
+            assert_eq!(key, &account.id.to_string());
+        }
+        let created = events_v1::Account::decode(messages[0].2.as_slice()).unwrap();
+        assert_eq!(created.r#type(), events_v1::AccountType::Merchant);
+        assert_eq!(created.status(), events_v1::AccountStatus::Active);
+        let updated = events_v1::Account::decode(messages[1].2.as_slice()).unwrap();
+        assert_eq!(updated.status(), events_v1::AccountStatus::Frozen);
+    }
 }
@@ -1,5 +1,9 @@
 use crate::helpers;
-use accounts_proto::accounts_v1::{CreateAccountRequest, GetAccountRequest};
+use accounts_proto::accounts_v1::get_accounts_request::Filter;
+use accounts_proto::accounts_v1::{
+    AccountStatus, CreateAccountRequest, GetAccountRequest, GetAccountsRequest,
+    UpdateAccountStatusRequest,
+};
 use tokio::net::TcpListener;
 use tonic::codegen::tokio_stream::wrappers::TcpListenerStream;
 
@@ -43,3 +47,75 @@ async fn successfully_calls_the_create_account_rpc_and_retrieve_account_using_ge
     assert_eq!(get_account.r#type, account.r#type);
     assert_eq!(get_account.id, account.id);
 }
+
+#[tokio::test]
+async fn successfully_freeze_and_close_an_account_and_list_it_by_status() {
+    // arrange
+    let listener = TcpListener::bind("0.0.0.0:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let test_server = helpers::accounts_grpc_test_server().await;
+
+    tokio::spawn(async move {
+        test_server
+            .serve_with_incoming(TcpListenerStream::new(listener))
+            .await
+            .unwrap()
+    });
+
+    let mut client = helpers::grpc_client_stub(addr.to_string()).await;
+    let account = client
+        .create_account(CreateAccountRequest {
+            name: "test".to_string(),
+            r#type: 1,
+            created_by: "test".to_string(),
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .account
+        .unwrap();
+    let update = |status: AccountStatus| UpdateAccountStatusRequest {
+        account_id: account.id.clone(),
+        status: status as i32,
+    };
+
+    // act
+    let frozen = client
+        .update_account_status(update(AccountStatus::Frozen))
+        .await;
+    let closed = client
+        .update_account_status(update(AccountStatus::Closed))
+        .await;
+    let reopened = client
+        .update_account_status(update(AccountStatus::Active))
+        .await;
+
+    // assert
+    assert_eq!(
+        frozen.unwrap().into_inner().account.unwrap().status,
+        AccountStatus::Frozen as i32
+    );
+    assert_eq!(
+        closed.unwrap().into_inner().account.unwrap().status,
+        AccountStatus::Closed as i32
+    );
+    assert_eq!(
+        reopened.unwrap_err().code(),
+        tonic::Code::FailedPrecondition
+    );
+
+    let listed = client
+        .get_accounts(GetAccountsRequest {
+            filter: Some(Filter {
+                account_ids: vec![account.id.clone()],
+                account_type: 0,
+                account_status: AccountStatus::Closed as i32,
+            }),
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .accounts;
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].id, account.id);
+}
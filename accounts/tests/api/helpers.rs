@@ -2,12 +2,24 @@ use accounts::repo;
 use accounts::service::AccountsService;
 use accounts_proto::accounts_v1::accounts_client::AccountsClient;
 use accounts_proto::accounts_v1::accounts_server;
+use async_trait::async_trait;
 use common::database;
+use common::kafka::Publisher;
 use std::env;
 use std::time::Duration;
 use tonic::transport::server::Router;
 use tonic::transport::{Channel, Server};
 
+/// DiscardPublisher drops account events, the tests do not run kafka.
+pub struct DiscardPublisher;
+
+#[async_trait]
+impl Publisher for DiscardPublisher {
+    async fn publish(&self, _topic: &str, _key: &str, _payload: Vec<u8>) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
 pub async fn accounts_grpc_test_server() -> Router {
     // setup database
     let database_config = database::Config {
@@ -27,7 +39,7 @@ pub async fn accounts_grpc_test_server() -> Router {
     let repo = repo::PgAccountRepository::new(db);
 
     // setup service
-    let service = AccountsService::new(repo);
+    let service = AccountsService::new(repo, DiscardPublisher);
 
     Server::builder().add_service(accounts_server::AccountsServer::new(service))
 }
@@ -0,0 +1,443 @@
+use async_trait::async_trait;
+use rdkafka::consumer::{BaseConsumer, CommitMode, Consumer, StreamConsumer};
+use rdkafka::message::{BorrowedMessage, Header, Headers, Message, OwnedHeaders};
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use rdkafka::{ClientConfig, Offset, TopicPartitionList};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub const TRANSACTION_EVENTS_TOPIC: &str = "transaction_events";
+pub const ACCOUNTS_EVENTS_TOPIC: &str = "accounts_events";
+pub const SETTLEMENT_RESULT_EVENTS_TOPIC: &str = "settlement_result_events";
+pub const FRAUD_DETECTED_EVENTS_TOPIC: &str = "fraud_detected_events";
+pub const RECONCILIATION_EVENTS_TOPIC: &str = "reconciliation_events";
+pub const REFUND_EVENTS_TOPIC: &str = "refund_events";
+pub const ANALYTICS_EVENTS_TOPIC: &str = "analytics_events";
+
+const DEFAULT_PUBLISH_TIMEOUT_SECONDS: u64 = 5;
+
+/// ORIGINAL_TOPIC_HEADER carries the topic a dead letter was consumed from.
+pub const ORIGINAL_TOPIC_HEADER: &str = "original_topic";
+/// ERROR_HEADER carries the handler error which dead-lettered the message.
+pub const ERROR_HEADER: &str = "error";
+
+/// dead_letter_topic is where a consumer group parks the messages its handler failed on. Every
+/// group has its own, so replaying a message does not redeliver it to groups which handled it.
+pub fn dead_letter_topic(group_id: &str) -> String {
+    format!("{group_id}_dlq")
+}
+
+/// retry_topic is where dead letters are replayed to. Workers consuming with dead letters
+/// subscribe to it next to their own topics.
+pub fn retry_topic(group_id: &str) -> String {
+    format!("{group_id}_retry")
+}
+
+pub struct Config {
+    pub brokers: String,
+    pub group_id: String,
+}
+
+/// Publisher sends encoded events to a topic. It is a trait so that services can be tested
+/// without a running kafka cluster.
+#[async_trait]
+pub trait Publisher: 'static + Send + Sync {
+    async fn publish(&self, topic: &str, key: &str, payload: Vec<u8>) -> anyhow::Result<()>;
+}
+
+/// MessageHandler processes a single message consumed from a topic.
+/// Returning an error does not stop the consumer.
+#[async_trait]
+pub trait MessageHandler: 'static + Send + Sync {
+    async fn handle(&self, topic: &str, payload: &[u8]) -> anyhow::Result<()>;
+}
+
+#[derive(Clone)]
+pub struct KafkaPublisher {
+    producer: FutureProducer,
+}
+
+impl KafkaPublisher {
+    pub fn new(config: &Config) -> anyhow::Result<Self> {
+        let producer: FutureProducer = ClientConfig::new()
+            .set("bootstrap.servers", config.brokers.as_str())
+            .set("enable.idempotence", "true")
+            .set("acks", "all")
+            .create()?;
+
+        Ok(Self { producer })
+    }
+}
+
+#[async_trait]
+impl Publisher for KafkaPublisher {
+    async fn publish(&self, topic: &str, key: &str, payload: Vec<u8>) -> anyhow::Result<()> {
+        let record = FutureRecord::to(topic).key(key).payload(&payload);
+        match self
+            .producer
+            .send(record, Duration::from_secs(DEFAULT_PUBLISH_TIMEOUT_SECONDS))
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err((e, _)) => anyhow::bail!("failed to publish to {topic}: {e}"),
+        }
+    }
+}
+
+/// DeadLetterQueue publishes the messages a consumer group failed to handle to the group's
+/// dead letter topic, keeping their key and original topic.
+#[derive(Clone)]
+pub struct DeadLetterQueue {
+    producer: FutureProducer,
+    topic: String,
+    retry_topic: String,
+}
+
+impl DeadLetterQueue {
+    pub fn new(config: &Config) -> anyhow::Result<Self> {
+        let publisher = KafkaPublisher::new(config)?;
+
+        Ok(Self {
+            producer: publisher.producer,
+            topic: dead_letter_topic(config.group_id.as_str()),
+            retry_topic: retry_topic(config.group_id.as_str()),
+        })
+    }
+
+    async fn publish(
+        &self,
+        message: &BorrowedMessage<'_>,
+        topic: &str,
+        error: &anyhow::Error,
+    ) -> anyhow::Result<()> {
+        let error = format!("{error:#}");
+        let headers = OwnedHeaders::new()
+            .insert(Header {
+                key: ORIGINAL_TOPIC_HEADER,
+                value: Some(topic),
+            })
+            .insert(Header {
+                key: ERROR_HEADER,
+                value: Some(error.as_str()),
+            });
+        let mut record = FutureRecord::<[u8], [u8]>::to(self.topic.as_str()).headers(headers);
+        if let Some(key) = message.key() {
+            record = record.key(key);
+        }
+        if let Some(payload) = message.payload() {
+            record = record.payload(payload);
+        }
+
+        match self
+            .producer
+            .send(record, Duration::from_secs(DEFAULT_PUBLISH_TIMEOUT_SECONDS))
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err((e, _)) => anyhow::bail!("failed to publish to {}: {e}", self.topic),
+        }
+    }
+}
+
+/// header returns the value of a string header of the message.
+fn header(message: &BorrowedMessage<'_>, key: &str) -> Option<String> {
+    message.headers().and_then(|headers| {
+        headers
+            .iter()
+            .find(|header| header.key == key)
+            .and_then(|header| header.value)
+            .map(|value| String::from_utf8_lossy(value).to_string())
+    })
+}
+
+pub fn new_consumer(config: &Config, topics: &[&str]) -> anyhow::Result<StreamConsumer> {
+    let consumer: StreamConsumer = ClientConfig::new()
+        .set("bootstrap.servers", config.brokers.as_str())
+        .set("group.id", config.group_id.as_str())
+        .set("enable.auto.commit", "false")
+        .set("auto.offset.reset", "earliest")
+        .create()?;
+    consumer.subscribe(topics)?;
+
+    Ok(consumer)
+}
+
+/// consume drives the consumer until the shutdown future resolves. Offsets are committed only
+/// after the handler returns, so a message being processed during shutdown is redelivered
+/// rather than lost.
+pub async fn consume<H>(
+    consumer: StreamConsumer,
+    handler: Arc<H>,
+    shutdown: impl Future<Output = ()>,
+) -> anyhow::Result<()>
+where
+    H: MessageHandler,
+{
+    consume_messages(consumer, handler, None, shutdown).await
+}
+
+/// consume_with_dead_letters is consume, except messages the handler fails on are published to
+/// the dead letter queue before their offset is committed. Messages replayed to the group's
+/// retry topic are handled as if they came from their original topic. The consumer stops when
+/// a dead letter cannot be published, so the message is redelivered instead of lost.
+pub async fn consume_with_dead_letters<H>(
+    consumer: StreamConsumer,
+    handler: Arc<H>,
+    dead_letters: DeadLetterQueue,
+    shutdown: impl Future<Output = ()>,
+) -> anyhow::Result<()>
+where
+    H: MessageHandler,
+{
+    consume_messages(consumer, handler, Some(dead_letters), shutdown).await
+}
+
+async fn consume_messages<H>(
+    consumer: StreamConsumer,
+    handler: Arc<H>,
+    dead_letters: Option<DeadLetterQueue>,
+    shutdown: impl Future<Output = ()>,
+) -> anyhow::Result<()>
+where
+    H: MessageHandler,
+{
+    tokio::pin!(shutdown);
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => break,
+            message = consumer.recv() => {
+                let message = match message {
+                    Ok(message) => message,
+                    Err(e) => {
+                        tracing::error!("failed to receive message: {e}");
+                        continue;
+                    }
+                };
+
+                let topic = match &dead_letters {
+                    Some(dead_letters) if message.topic() == dead_letters.retry_topic => {
+                        header(&message, ORIGINAL_TOPIC_HEADER)
+                            .unwrap_or(message.topic().to_string())
+                    }
+                    _ => message.topic().to_string(),
+                };
+                let result = match message.payload() {
+                    Some(payload) => handler.handle(topic.as_str(), payload).await,
+                    None => Ok(()),
+                };
+                if let Err(e) = result {
+                    tracing::error!(
+                        topic = message.topic(),
+                        offset = message.offset(),
+                        "failed to handle message: {e:#}"
+                    );
+                    if let Some(dead_letters) = &dead_letters {
+                        dead_letters.publish(&message, topic.as_str(), &e).await?;
+                    }
+                }
+
+                consumer.commit_message(&message, CommitMode::Async)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// replay_dead_letters republishes up to `limit` dead letters of the consumer group in
+/// `config` to the group's retry topic, committing each once it is republished. It stops once
+/// no dead letter arrived for `idle_timeout` and returns how many were replayed.
+pub async fn replay_dead_letters(
+    config: &Config,
+    limit: Option<u64>,
+    idle_timeout: Duration,
+) -> anyhow::Result<u64> {
+    let dead_letter_topic = dead_letter_topic(config.group_id.as_str());
+    let retry_topic = retry_topic(config.group_id.as_str());
+    let consumer = new_consumer(
+        &Config {
+            brokers: config.brokers.clone(),
+            group_id: format!("{dead_letter_topic}_replay"),
+        },
+        &[dead_letter_topic.as_str()],
+    )?;
+    let publisher = KafkaPublisher::new(config)?;
+
+    let mut replayed = 0;
+    while limit.is_none_or(|limit| replayed < limit) {
+        let message = match tokio::time::timeout(idle_timeout, consumer.recv()).await {
+            Ok(Ok(message)) => message,
+            Ok(Err(e)) => anyhow::bail!("failed to receive dead letter: {e}"),
+            Err(_) => break,
+        };
+
+        let mut headers = OwnedHeaders::new();
+        if let Some(message_headers) = message.headers() {
+            for header in message_headers.iter() {
+                headers = headers.insert(header);
+            }
+        }
+        let mut record = FutureRecord::<[u8], [u8]>::to(retry_topic.as_str()).headers(headers);
+        if let Some(key) = message.key() {
+            record = record.key(key);
+        }
+        if let Some(payload) = message.payload() {
+            record = record.payload(payload);
+        }
+        if let Err((e, _)) = publisher
+            .producer
+            .send(record, Duration::from_secs(DEFAULT_PUBLISH_TIMEOUT_SECONDS))
+            .await
+        {
+            anyhow::bail!("failed to publish to {retry_topic}: {e}");
+        }
+
+        consumer.commit_message(&message, CommitMode::Sync)?;
+        replayed += 1;
+    }
+
+    Ok(replayed)
+}
+
+/// PartitionLag is how far a consumer group is behind on one partition of a topic.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartitionLag {
+    pub topic: String,
+    pub partition: i32,
+    /// committed is the next offset the group will consume, None when it never committed.
+    pub committed: Option<i64>,
+    pub high_watermark: i64,
+    pub lag: i64,
+}
+
+/// consumer_lag returns the lag of the consumer group in `config` on every partition of the
+/// topics. Topics which do not exist yet are skipped. A group which never committed on a
+/// partition is behind by every message still retained, as it starts from the earliest offset.
+pub async fn consumer_lag(
+    config: &Config,
+    topics: &[&str],
+    timeout: Duration,
+) -> anyhow::Result<Vec<PartitionLag>> {
+    let consumer: BaseConsumer = ClientConfig::new()
+        .set("bootstrap.servers", config.brokers.as_str())
+        .set("group.id", config.group_id.as_str())
+        .set("enable.auto.commit", "false")
+        .create()?;
+    let topics: Vec<String> = topics.iter().map(|topic| topic.to_string()).collect();
+
+    // the rdkafka calls below block, keep them off the async workers
+    match tokio::task::spawn_blocking(move || {
+        let mut partitions = TopicPartitionList::new();
+        for topic in &topics {
+            let metadata = consumer.fetch_metadata(Some(topic.as_str()), timeout)?;
+            for topic in metadata.topics().iter().filter(|t| t.error().is_none()) {
+                for partition in topic.partitions() {
+                    partitions.add_partition(topic.name(), partition.id());
+                }
+            }
+        }
+        if partitions.count() == 0 {
+            return Ok(vec![]);
+        }
+
+        let committed = consumer.committed_offsets(partitions, timeout)?;
+        committed
+            .elements()
+            .iter()
+            .map(|element| {
+                let (low, high) =
+                    consumer.fetch_watermarks(element.topic(), element.partition(), timeout)?;
+                let committed = match element.offset() {
+                    Offset::Offset(offset) => Some(offset),
+                    _ => None,
+                };
+
+                Ok(PartitionLag {
+                    topic: element.topic().to_string(),
+                    partition: element.partition(),
+                    committed,
+                    high_watermark: high,
+                    lag: partition_lag(committed, low, high),
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()
+    })
+    .await
+    {
+        Ok(result) => result,
+        Err(e) => anyhow::bail!("failed to fetch consumer lag of {}: {e}", config.group_id),
+    }
+}
+
+/// partition_lag counts the messages between the committed offset and the high watermark,
+/// starting from the low watermark when nothing was committed or the committed offset was
+/// already deleted.
+fn partition_lag(committed: Option<i64>, low: i64, high: i64) -> i64 {
+    (high - committed.unwrap_or(low).max(low)).max(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dead_letter_topics() {
+        assert_eq!(
+            dead_letter_topic("refund-processor"),
+            "refund-processor_dlq"
+        );
+        assert_eq!(retry_topic("refund-processor"), "refund-processor_retry");
+    }
+
+    #[test]
+    fn test_partition_lag() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            committed: Option<i64>,
+            low: i64,
+            high: i64,
+            expected: i64,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "behind",
+                committed: Some(40),
+                low: 0,
+                high: 100,
+                expected: 60,
+            },
+            TestCase {
+                name: "caught up",
+                committed: Some(100),
+                low: 0,
+                high: 100,
+                expected: 0,
+            },
+            TestCase {
+                name: "never committed",
+                committed: None,
+                low: 20,
+                high: 100,
+                expected: 80,
+            },
+            TestCase {
+                name: "committed offset deleted",
+                committed: Some(5),
+                low: 20,
+                high: 100,
+                expected: 80,
+            },
+        ];
+
+        for test_case in test_cases {
+            // act
+            let lag = partition_lag(test_case.committed, test_case.low, test_case.high);
+
+            // assert
+            assert_eq!(lag, test_case.expected, "{}", test_case.name);
+        }
+    }
+}
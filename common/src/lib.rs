@@ -1,6 +1,11 @@
 pub mod database;
+pub mod kafka;
+pub mod money;
 pub mod shutdown;
 
+#[cfg(feature = "test-util")]
+pub mod test_util;
+
 pub fn add(left: u64, right: u64) -> u64 {
     left + right
 }
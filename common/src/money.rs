@@ -0,0 +1,644 @@
+use std::fmt;
+
+const NANOS_PER_UNIT: i64 = 1_000_000_000;
+
+/// ISO_4217 lists the active ISO 4217 currencies with their minor unit exponent, e.g. 2 for USD
+/// cents, 0 for JPY and 3 for KWD fils.
+const ISO_4217: &[(&str, u32)] = &[
+    ("AED", 2),
+    ("AFN", 2),
+    ("ALL", 2),
+    ("AMD", 2),
+    ("ANG", 2),
+    ("AOA", 2),
+    ("ARS", 2),
+    ("AUD", 2),
+    ("AWG", 2),
+    ("AZN", 2),
+    ("BAM", 2),
+    ("BBD", 2),
+    ("BDT", 2),
+    ("BGN", 2),
+    ("BHD", 3),
+    ("BIF", 0),
+    ("BMD", 2),
+    ("BND", 2),
+    ("BOB", 2),
+    ("BOV", 2),
+    ("BRL", 2),
+    ("BSD", 2),
+    ("BTN", 2),
+    ("BWP", 2),
+    ("BYN", 2),
+    ("BZD", 2),
+    ("CAD", 2),
+    ("CDF", 2),
+    ("CHE", 2),
+    ("CHF", 2),
+    ("CHW", 2),
+    ("CLF", 4),
+    ("CLP", 0),
+    ("CNY", 2),
+    ("COP", 2),
+    ("COU", 2),
+    ("CRC", 2),
+    ("CUP", 2),
+    ("CVE", 2),
+    ("CZK", 2),
+    ("DJF", 0),
+    ("DKK", 2),
+    ("DOP", 2),
+    ("DZD", 2),
+    ("EGP", 2),
+    ("ERN", 2),
+    ("ETB", 2),
+    ("EUR", 2),
+    ("FJD", 2),
+    ("FKP", 2),
+    ("GBP", 2),
+    ("GEL", 2),
+    ("GHS", 2),
+    ("GIP", 2),
+    ("GMD", 2),
+    ("GNF", 0),
+    ("GTQ", 2),
+    ("GYD", 2),
+    ("HKD", 2),
+    ("HNL", 2),
+    ("HTG", 2),
+    ("HUF", 2),
+    ("IDR", 2),
+    ("ILS", 2),
+    ("INR", 2),
+    ("IQD", 3),
+    ("IRR", 2),
+    ("ISK", 0),
+    ("JMD", 2),
+    ("JOD", 3),
+    ("JPY", 0),
+    ("KES", 2),
+    ("KGS", 2),
+    ("KHR", 2),
+    ("KMF", 0),
+    ("KPW", 2),
+    ("KRW", 0),
+    ("KWD", 3),
+    ("KYD", 2),
+    ("KZT", 2),
+    ("LAK", 2),
+    ("LBP", 2),
+    ("LKR", 2),
+    ("LRD", 2),
+    ("LSL", 2),
+    ("LYD", 3),
+    ("MAD", 2),
+    ("MDL", 2),
+    ("MGA", 2),
+    ("MKD", 2),
+    ("MMK", 2),
+    ("MNT", 2),
+    ("MOP", 2),
+    ("MRU", 2),
+    ("MUR", 2),
+    ("MVR", 2),
+    ("MWK", 2),
+    ("MXN", 2),
+    ("MXV", 2),
+    ("MYR", 2),
+    ("MZN", 2),
+    ("NAD", 2),
+    ("NGN", 2),
+    ("NIO", 2),
+    ("NOK", 2),
+    ("NPR", 2),
+    ("NZD", 2),
+    ("OMR", 3),
+    ("PAB", 2),
+    ("PEN", 2),
+    ("PGK", 2),
+    ("PHP", 2),
+    ("PKR", 2),
+    ("PLN", 2),
+    ("PYG", 0),
+    ("QAR", 2),
+    ("RON", 2),
+    ("RSD", 2),
+    ("RUB", 2),
+    ("RWF", 0),
+    ("SAR", 2),
+    ("SBD", 2),
+    ("SCR", 2),
+    ("SDG", 2),
+    ("SEK", 2),
+    ("SGD", 2),
+    ("SHP", 2),
+    ("SLE", 2),
+    ("SOS", 2),
+    ("SRD", 2),
+    ("SSP", 2),
+    ("STN", 2),
+    ("SVC", 2),
+    ("SYP", 2),
+    ("SZL", 2),
+    ("THB", 2),
+    ("TJS", 2),
+    ("TMT", 2),
+    ("TND", 3),
+    ("TOP", 2),
+    ("TRY", 2),
+    ("TTD", 2),
+    ("TWD", 2),
+    ("TZS", 2),
+    ("UAH", 2),
+    ("UGX", 0),
+    ("USD", 2),
+    ("USN", 2),
+    ("UYI", 0),
+    ("UYU", 2),
+    ("UYW", 4),
+    ("UZS", 2),
+    ("VED", 2),
+    ("VES", 2),
+    ("VND", 0),
+    ("VUV", 0),
+    ("WST", 2),
+    ("XAF", 0),
+    ("XCD", 2),
+    ("XOF", 0),
+    ("XPF", 0),
+    ("YER", 2),
+    ("ZAR", 2),
+    ("ZMW", 2),
+    ("ZWG", 2),
+];
+
+/// Currency is an ISO 4217 currency and the number of decimals of its minor unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Currency {
+    code: &'static str,
+    exponent: u32,
+}
+
+impl Currency {
+    /// from_code looks the currency up by its ISO 4217 code, ignoring case.
+    pub fn from_code(code: &str) -> Result<Currency, MoneyError> {
+        match ISO_4217
+            .iter()
+            .find(|(iso_code, _)| iso_code.eq_ignore_ascii_case(code))
+        {
+            Some((code, exponent)) => Ok(Currency {
+                code,
+                exponent: *exponent,
+            }),
+            None => Err(MoneyError::UnknownCurrency(code.to_string())),
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    pub fn exponent(&self) -> u32 {
+        self.exponent
+    }
+
+    /// minor_per_unit is the number of minor units in a major unit, e.g. 100 for USD.
+    pub fn minor_per_unit(&self) -> i64 {
+        10i64.pow(self.exponent)
+    }
+
+    fn nanos_per_minor(&self) -> i64 {
+        NANOS_PER_UNIT / self.minor_per_unit()
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code)
+    }
+}
+
+/// Money is an amount in minor units of its currency, e.g. 1050 USD for 10.50 USD and 1050 JPY
+/// for 1050 JPY. Conversions never round and arithmetic never mixes currencies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Money {
+    amount_minor: i64,
+    currency: Currency,
+}
+
+impl Money {
+    pub fn new(amount_minor: i64, currency: Currency) -> Self {
+        Money {
+            amount_minor,
+            currency,
+        }
+    }
+
+    /// from_minor creates an amount of minor units in the currency with the given code.
+    pub fn from_minor(amount_minor: i64, currency: &str) -> Result<Money, MoneyError> {
+        Ok(Money::new(amount_minor, Currency::from_code(currency)?))
+    }
+
+    /// from_units_nanos converts a `google.type.Money` amount. Units and nanos must have the
+    /// same sign, and nanos must be whole minor units of the currency.
+    pub fn from_units_nanos(units: i64, nanos: i32, currency: &str) -> Result<Money, MoneyError> {
+        let currency = Currency::from_code(currency)?;
+        if nanos.unsigned_abs() as i64 >= NANOS_PER_UNIT
+            || (units > 0 && nanos < 0)
+            || (units < 0 && nanos > 0)
+        {
+            return Err(MoneyError::InvalidNanos { units, nanos });
+        }
+        if nanos as i64 % currency.nanos_per_minor() != 0 {
+            return Err(MoneyError::ExcessPrecision {
+                currency: currency.code.to_string(),
+                exponent: currency.exponent,
+            });
+        }
+
+        match units
+            .checked_mul(currency.minor_per_unit())
+            .and_then(|minor| minor.checked_add(nanos as i64 / currency.nanos_per_minor()))
+        {
+            Some(amount_minor) => Ok(Money::new(amount_minor, currency)),
+            None => Err(MoneyError::Overflow),
+        }
+    }
+
+    /// parse reads a decimal amount in major units such as `10.50` or `-3`, rejecting more
+    /// decimals than the currency has.
+    pub fn parse(amount: &str, currency: &str) -> Result<Money, MoneyError> {
+        let currency = Currency::from_code(currency)?;
+        let invalid = || MoneyError::InvalidAmount(amount.to_string());
+        let (negative, digits) = match amount.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, amount),
+        };
+        let (units, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        if units.is_empty()
+            || !units.chars().all(|c| c.is_ascii_digit())
+            || !fraction.chars().all(|c| c.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.len() > currency.exponent as usize {
+            return Err(MoneyError::ExcessPrecision {
+                currency: currency.code.to_string(),
+                exponent: currency.exponent,
+            });
+        }
+
+        // i128 fits any i64 amount with its sign, including i64::MIN
+        let units = units.parse::<i128>().map_err(|_| MoneyError::Overflow)?;
+        let fraction = match fraction.is_empty() {
+            true => 0,
+            false => format!("{fraction:0<width$}", width = currency.exponent as usize)
+                .parse::<i128>()
+                .map_err(|_| invalid())?,
+        };
+        let amount_minor = units
+            .checked_mul(currency.minor_per_unit() as i128)
+            .and_then(|minor| minor.checked_add(fraction))
+            .map(|minor| match negative {
+                true => -minor,
+                false => minor,
+            })
+            .and_then(|minor| i64::try_from(minor).ok())
+            .ok_or(MoneyError::Overflow)?;
+
+        Ok(Money::new(amount_minor, currency))
+    }
+
+    pub fn amount_minor(&self) -> i64 {
+        self.amount_minor
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// to_units_nanos converts the amount to `google.type.Money` units and nanos.
+    pub fn to_units_nanos(&self) -> (i64, i32) {
+        let minor_per_unit = self.currency.minor_per_unit();
+        (
+            self.amount_minor / minor_per_unit,
+            ((self.amount_minor % minor_per_unit) * self.currency.nanos_per_minor()) as i32,
+        )
+    }
+
+    /// to_decimal prints the amount in major units with all decimals of the currency, e.g.
+    /// `10.50` for USD and `1050` for JPY.
+    pub fn to_decimal(&self) -> String {
+        let minor_per_unit = self.currency.minor_per_unit();
+        let sign = match self.amount_minor < 0 {
+            true => "-",
+            false => "",
+        };
+        let units = (self.amount_minor / minor_per_unit).unsigned_abs();
+        match self.currency.exponent {
+            0 => format!("{sign}{units}"),
+            exponent => format!(
+                "{sign}{units}.{:0width$}",
+                (self.amount_minor % minor_per_unit).unsigned_abs(),
+                width = exponent as usize
+            ),
+        }
+    }
+
+    pub fn checked_add(&self, other: &Money) -> Result<Money, MoneyError> {
+        self.same_currency(other)?;
+        match self.amount_minor.checked_add(other.amount_minor) {
+            Some(amount_minor) => Ok(Money::new(amount_minor, self.currency)),
+            None => Err(MoneyError::Overflow),
+        }
+    }
+
+    pub fn checked_sub(&self, other: &Money) -> Result<Money, MoneyError> {
+        self.same_currency(other)?;
+        match self.amount_minor.checked_sub(other.amount_minor) {
+            Some(amount_minor) => Ok(Money::new(amount_minor, self.currency)),
+            None => Err(MoneyError::Overflow),
+        }
+    }
+
+    pub fn checked_neg(&self) -> Result<Money, MoneyError> {
+        match self.amount_minor.checked_neg() {
+            Some(amount_minor) => Ok(Money::new(amount_minor, self.currency)),
+            None => Err(MoneyError::Overflow),
+        }
+    }
+
+    fn same_currency(&self, other: &Money) -> Result<(), MoneyError> {
+        match self.currency == other.currency {
+            true => Ok(()),
+            false => Err(MoneyError::CurrencyMismatch {
+                left: self.currency.code.to_string(),
+                right: other.currency.code.to_string(),
+            }),
+        }
+    }
+}
+
+/// Display prints the amount with its currency, e.g. `10.50 USD`, `1050 JPY` or `-0.125 KWD`.
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.to_decimal(), self.currency)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MoneyError {
+    UnknownCurrency(String),
+    InvalidAmount(String),
+    /// InvalidNanos is returned when nanos are out of range or their sign differs from units.
+    InvalidNanos {
+        units: i64,
+        nanos: i32,
+    },
+    /// ExcessPrecision is returned when an amount has fractions of the currency's minor unit.
+    ExcessPrecision {
+        currency: String,
+        exponent: u32,
+    },
+    CurrencyMismatch {
+        left: String,
+        right: String,
+    },
+    Overflow,
+}
+
+impl fmt::Display for MoneyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoneyError::UnknownCurrency(code) => {
+                write!(f, "unknown currency {code:?}, expected an ISO 4217 code")
+            }
+            MoneyError::InvalidAmount(amount) => {
+                write!(f, "invalid amount {amount}, expected e.g. 10.50")
+            }
+            MoneyError::InvalidNanos { units, nanos } => write!(
+                f,
+                "invalid nanos {nanos} for units {units}, nanos must be within a unit and share its sign"
+            ),
+            MoneyError::ExcessPrecision { currency, exponent } => {
+                write!(f, "{currency} amounts have at most {exponent} decimals")
+            }
+            MoneyError::CurrencyMismatch { left, right } => {
+                write!(f, "cannot combine {left} and {right} amounts")
+            }
+            MoneyError::Overflow => write!(f, "amount is out of range"),
+        }
+    }
+}
+
+impl std::error::Error for MoneyError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_from_units_nanos() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            units: i64,
+            nanos: i32,
+            currency: &'static str,
+            expected: Result<i64, MoneyError>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "successfully convert cents",
+                units: 10,
+                nanos: 500_000_000,
+                currency: "USD",
+                expected: Ok(1050),
+            },
+            TestCase {
+                name: "successfully convert yen",
+                units: 1050,
+                nanos: 0,
+                currency: "JPY",
+                expected: Ok(1050),
+            },
+            TestCase {
+                name: "successfully convert fils",
+                units: 1,
+                nanos: 5_000_000,
+                currency: "KWD",
+                expected: Ok(1005),
+            },
+            TestCase {
+                name: "successfully convert negative amount",
+                units: -1,
+                nanos: -250_000_000,
+                currency: "eur",
+                expected: Ok(-125),
+            },
+            TestCase {
+                name: "error when yen have decimals",
+                units: 1,
+                nanos: 500_000_000,
+                currency: "JPY",
+                expected: Err(MoneyError::ExcessPrecision {
+                    currency: "JPY".to_string(),
+                    exponent: 0,
+                }),
+            },
+            TestCase {
+                name: "error when dollars have fractions of a cent",
+                units: 0,
+                nanos: 1_000_000,
+                currency: "USD",
+                expected: Err(MoneyError::ExcessPrecision {
+                    currency: "USD".to_string(),
+                    exponent: 2,
+                }),
+            },
+            TestCase {
+                name: "error when signs differ",
+                units: 1,
+                nanos: -500_000_000,
+                currency: "USD",
+                expected: Err(MoneyError::InvalidNanos {
+                    units: 1,
+                    nanos: -500_000_000,
+                }),
+            },
+            TestCase {
+                name: "error when currency is unknown",
+                units: 1,
+                nanos: 0,
+                currency: "XYZ",
+                expected: Err(MoneyError::UnknownCurrency("XYZ".to_string())),
+            },
+            TestCase {
+                name: "error when amount overflows",
+                units: i64::MAX,
+                nanos: 0,
+                currency: "USD",
+                expected: Err(MoneyError::Overflow),
+            },
+        ];
+
+        for test_case in test_cases {
+            // act
+            let result =
+                Money::from_units_nanos(test_case.units, test_case.nanos, test_case.currency)
+                    .map(|money| money.amount_minor());
+
+            // assert
+            assert_eq!(result, test_case.expected, "{}", test_case.name);
+        }
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let test_cases = vec![
+            ("10.5", "USD", Some("10.50 USD")),
+            ("10.500", "USD", Some("10.50 USD")),
+            ("1050", "JPY", Some("1050 JPY")),
+            ("-0.125", "KWD", Some("-0.125 KWD")),
+            ("3.", "EUR", Some("3.00 EUR")),
+            ("0.001", "USD", None),
+            ("1.5", "JPY", None),
+            ("ten", "USD", None),
+            ("1.-5", "USD", None),
+            (
+                "-92233720368547758.08",
+                "USD",
+                Some("-92233720368547758.08 USD"),
+            ),
+            ("92233720368547758.08", "USD", None),
+        ];
+
+        for (amount, currency, expected) in test_cases {
+            let result = Money::parse(amount, currency).map(|money| money.to_string());
+            assert_eq!(result.ok().as_deref(), expected, "{amount} {currency}");
+        }
+    }
+
+    #[test]
+    fn error_when_mixing_currencies() {
+        // arrange
+        let usd = Money::from_minor(100, "USD").unwrap();
+        let eur = Money::from_minor(100, "EUR").unwrap();
+
+        // act
+        let result = usd.checked_add(&eur);
+
+        // assert
+        assert_eq!(
+            result,
+            Err(MoneyError::CurrencyMismatch {
+                left: "USD".to_string(),
+                right: "EUR".to_string(),
+            })
+        );
+        assert_eq!(
+            Money::from_minor(i64::MAX, "USD")
+                .unwrap()
+                .checked_add(&usd),
+            Err(MoneyError::Overflow)
+        );
+    }
+
+    fn currency() -> impl Strategy<Value = Currency> {
+        prop::sample::select(ISO_4217).prop_map(|(code, _)| Currency::from_code(code).unwrap())
+    }
+
+    proptest! {
+        #[test]
+        fn minor_units_round_trip_through_units_and_nanos(
+            amount_minor in any::<i64>(),
+            currency in currency(),
+        ) {
+            let money = Money::new(amount_minor, currency);
+            let (units, nanos) = money.to_units_nanos();
+
+            prop_assert_eq!(Money::from_units_nanos(units, nanos, currency.code()), Ok(money));
+        }
+
+        #[test]
+        fn minor_units_round_trip_through_display(
+            amount_minor in any::<i64>(),
+            currency in currency(),
+        ) {
+            let money = Money::new(amount_minor, currency);
+            let display = money.to_string();
+            let (amount, code) = display.split_once(' ').unwrap();
+
+            prop_assert_eq!(code, currency.code());
+            prop_assert_eq!(Money::parse(amount, code), Ok(money));
+        }
+
+        #[test]
+        fn nanos_below_the_minor_unit_are_rejected(
+            units in -1_000_000i64..1_000_000,
+            nanos in 1i32..100_000,
+            currency in currency(),
+        ) {
+            let nanos = match units < 0 { true => -nanos, false => nanos };
+
+            let result = Money::from_units_nanos(units, nanos, currency.code());
+
+            prop_assert!(matches!(result, Err(MoneyError::ExcessPrecision { .. })), "{:?}", result);
+        }
+
+        #[test]
+        fn addition_and_subtraction_are_inverse(
+            a in -1_000_000_000_000i64..1_000_000_000_000,
+            b in -1_000_000_000_000i64..1_000_000_000_000,
+            currency in currency(),
+        ) {
+            let a = Money::new(a, currency);
+            let b = Money::new(b, currency);
+
+            prop_assert_eq!(a.checked_add(&b).unwrap().checked_sub(&b), Ok(a));
+        }
+    }
+}
@@ -0,0 +1,8 @@
+/// spawn_stub serves the router on a random local port and returns its base url.
+pub async fn spawn_stub(router: axum::Router) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+    format!("http://{}", addr)
+}
@@ -30,7 +30,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 event_proto_settlement_file.clone(),
                 event_proto_transaction_file.clone(),
             ],
-            &[proto_root.clone()],
+            std::slice::from_ref(&proto_root),
         )?;
 
     let protos = vec![
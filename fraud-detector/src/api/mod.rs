@@ -0,0 +1,272 @@
+mod parsers;
+
+use crate::DEFAULT_EVALUATION_REPORT_DAYS;
+use crate::api::parsers::{
+    parse_model_report_to_proto, parse_review_to_proto, parse_to_domain_review_status,
+    parse_to_domain_timestamp,
+};
+use crate::domain::review::ReviewError;
+use crate::repo::FraudRepository;
+use crate::service::FraudService;
+
+use common::kafka::Publisher;
+use fraud_proto::fraud_v1;
+
+use async_trait::async_trait;
+
+fn parse_review_id(review_id: &str) -> Result<uuid::Uuid, tonic::Status> {
+    uuid::Uuid::parse_str(review_id)
+        .map_err(|e| tonic::Status::invalid_argument(format!("invalid review_id {review_id}: {e}")))
+}
+
+/// ReportWindow is the window of evaluations a report covers.
+type ReportWindow = (chrono::DateTime<chrono::Utc>, chrono::DateTime<chrono::Utc>);
+
+/// parse_report_request returns the window to report on, the last
+/// [`DEFAULT_EVALUATION_REPORT_DAYS`] up to now unless the request says otherwise.
+fn parse_report_request(
+    request: fraud_v1::GetEvaluationReportRequest,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<ReportWindow, tonic::Status> {
+    let to = match request.to {
+        Some(to) => parse_to_domain_timestamp("to", to)
+            .map_err(|e| tonic::Status::invalid_argument(e.to_string()))?,
+        None => now,
+    };
+    let from = match request.from {
+        Some(from) => parse_to_domain_timestamp("from", from)
+            .map_err(|e| tonic::Status::invalid_argument(e.to_string()))?,
+        None => to - chrono::Duration::days(DEFAULT_EVALUATION_REPORT_DAYS),
+    };
+    if from >= to {
+        return Err(tonic::Status::invalid_argument("from must be before to"));
+    }
+
+    Ok((from, to))
+}
+
+/// parse_error_to_status maps review errors to their grpc codes, anything else is internal.
+fn parse_error_to_status(e: anyhow::Error, message: &str) -> tonic::Status {
+    match e.downcast_ref::<ReviewError>() {
+        Some(ReviewError::NotFound(_)) => tonic::Status::not_found(e.to_string()),
+        Some(ReviewError::AlreadyDecided(_, _)) => {
+            tonic::Status::failed_precondition(e.to_string())
+        }
+        None => tonic::Status::internal(format!("{message}: {e}")),
+    }
+}
+
+#[async_trait]
+impl<R, P> fraud_v1::fraud_reviews_server::FraudReviews for FraudService<R, P>
+where
+    R: FraudRepository,
+    P: Publisher,
+{
+    async fn health_check(
+        &self,
+        _request: tonic::Request<()>,
+    ) -> Result<tonic::Response<()>, tonic::Status> {
+        Ok(tonic::Response::new(()))
+    }
+
+    async fn list_reviews(
+        &self,
+        request: tonic::Request<fraud_v1::ListReviewsRequest>,
+    ) -> Result<tonic::Response<fraud_v1::ListReviewsResponse>, tonic::Status> {
+        let request = request.into_inner();
+
+        let status = match parse_to_domain_review_status(request.status) {
+            Ok(status) => status,
+            Err(e) => return Err(tonic::Status::invalid_argument(e.to_string())),
+        };
+
+        let reviews = match FraudService::list_reviews(self, status).await {
+            Ok(reviews) => reviews.into_iter().map(parse_review_to_proto).collect(),
+            Err(e) => return Err(parse_error_to_status(e, "failed to list reviews")),
+        };
+
+        Ok(tonic::Response::new(fraud_v1::ListReviewsResponse {
+            reviews,
+        }))
+    }
+
+    async fn get_review(
+        &self,
+        request: tonic::Request<fraud_v1::GetReviewRequest>,
+    ) -> Result<tonic::Response<fraud_v1::GetReviewResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let id = parse_review_id(request.review_id.as_str())?;
+
+        let review = match FraudService::get_review(self, id).await {
+            Ok(review) => parse_review_to_proto(review),
+            Err(e) => return Err(parse_error_to_status(e, "failed to get review")),
+        };
+
+        Ok(tonic::Response::new(fraud_v1::GetReviewResponse {
+            review: Some(review),
+        }))
+    }
+
+    async fn approve_review(
+        &self,
+        request: tonic::Request<fraud_v1::DecideReviewRequest>,
+    ) -> Result<tonic::Response<fraud_v1::DecideReviewResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let id = parse_review_id(request.review_id.as_str())?;
+        if request.reviewer.is_empty() {
+            return Err(tonic::Status::invalid_argument("reviewer must be set"));
+        }
+
+        let review = match FraudService::approve_review(
+            self,
+            id,
+            request.reviewer.as_str(),
+            request.notes.as_deref(),
+        )
+        .await
+        {
+            Ok(review) => parse_review_to_proto(review),
+            Err(e) => return Err(parse_error_to_status(e, "failed to approve review")),
+        };
+
+        Ok(tonic::Response::new(fraud_v1::DecideReviewResponse {
+            review: Some(review),
+        }))
+    }
+
+    async fn reject_review(
+        &self,
+        request: tonic::Request<fraud_v1::DecideReviewRequest>,
+    ) -> Result<tonic::Response<fraud_v1::DecideReviewResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let id = parse_review_id(request.review_id.as_str())?;
+        if request.reviewer.is_empty() {
+            return Err(tonic::Status::invalid_argument("reviewer must be set"));
+        }
+
+        let review = match FraudService::reject_review(
+            self,
+            id,
+            request.reviewer.as_str(),
+            request.notes.as_deref(),
+        )
+        .await
+        {
+            Ok(review) => parse_review_to_proto(review),
+            Err(e) => return Err(parse_error_to_status(e, "failed to reject review")),
+        };
+
+        Ok(tonic::Response::new(fraud_v1::DecideReviewResponse {
+            review: Some(review),
+        }))
+    }
+
+    async fn get_evaluation_report(
+        &self,
+        request: tonic::Request<fraud_v1::GetEvaluationReportRequest>,
+    ) -> Result<tonic::Response<fraud_v1::GetEvaluationReportResponse>, tonic::Status> {
+        let (from, to) = parse_report_request(request.into_inner(), chrono::Utc::now())?;
+
+        let reports = match FraudService::evaluation_report(self, from, to).await {
+            Ok(reports) => reports
+                .into_iter()
+                .map(parse_model_report_to_proto)
+                .collect(),
+            Err(e) => return Err(parse_error_to_status(e, "failed to get evaluation report")),
+        };
+
+        Ok(tonic::Response::new(
+            fraud_v1::GetEvaluationReportResponse { reports },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::review::Status;
+
+    #[test]
+    fn test_parse_report_request() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            request: fraud_v1::GetEvaluationReportRequest,
+            expected: Result<ReportWindow, tonic::Code>,
+        }
+
+        let now = chrono::DateTime::from_timestamp(1_760_000_000, 0).unwrap();
+        let day_ago = now - chrono::Duration::days(1);
+        let timestamp = |at: chrono::DateTime<chrono::Utc>| prost_types::Timestamp {
+            seconds: at.timestamp(),
+            nanos: 0,
+        };
+        let test_cases = vec![
+            TestCase {
+                name: "successfully default to the last 30 days",
+                request: fraud_v1::GetEvaluationReportRequest::default(),
+                expected: Ok((now - chrono::Duration::days(30), now)),
+            },
+            TestCase {
+                name: "successfully parse the given window",
+                request: fraud_v1::GetEvaluationReportRequest {
+                    from: Some(timestamp(day_ago - chrono::Duration::days(1))),
+                    to: Some(timestamp(day_ago)),
+                },
+                expected: Ok((day_ago - chrono::Duration::days(1), day_ago)),
+            },
+            TestCase {
+                name: "error when the window is empty",
+                request: fraud_v1::GetEvaluationReportRequest {
+                    from: Some(timestamp(now)),
+                    to: None,
+                },
+                expected: Err(tonic::Code::InvalidArgument),
+            },
+        ];
+
+        for test_case in test_cases {
+            let result = parse_report_request(test_case.request, now);
+            assert_eq!(
+                result.map_err(|status| status.code()),
+                test_case.expected,
+                "{}",
+                test_case.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_error_to_status() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            error: anyhow::Error,
+            expected: tonic::Code,
+        }
+
+        let id = uuid::Uuid::new_v4();
+        let test_cases = vec![
+            TestCase {
+                name: "not found when review does not exist",
+                error: ReviewError::NotFound(id).into(),
+                expected: tonic::Code::NotFound,
+            },
+            TestCase {
+                name: "failed precondition when review is already decided",
+                error: ReviewError::AlreadyDecided(id, Status::Approved).into(),
+                expected: tonic::Code::FailedPrecondition,
+            },
+            TestCase {
+                name: "internal for any other error",
+                error: anyhow::anyhow!("connection reset"),
+                expected: tonic::Code::Internal,
+            },
+        ];
+
+        for test_case in test_cases {
+            let status = parse_error_to_status(test_case.error, "failed");
+            assert_eq!(status.code(), test_case.expected, "{}", test_case.name);
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use crate::domain::evaluation::ModelReport;
+use crate::domain::review::{Review, Status};
+use fraud_proto::fraud_v1;
+
+use prost_types::Timestamp;
+
+fn parse_timestamp_to_proto(timestamp: chrono::DateTime<chrono::Utc>) -> Timestamp {
+    Timestamp {
+        seconds: timestamp.timestamp(),
+        nanos: timestamp.timestamp_subsec_nanos() as i32,
+    }
+}
+
+pub fn parse_review_to_proto(review: Review) -> fraud_v1::Review {
+    fraud_v1::Review {
+        id: review.id.to_string(),
+        transaction_id: review.transaction_id.to_string(),
+        idempotency_key: review.idempotency_key,
+        score: review.score,
+        model_version: review.model_version,
+        status: match review.status {
+            Status::Open => fraud_v1::ReviewStatus::Open as i32,
+            Status::Escalated => fraud_v1::ReviewStatus::Escalated as i32,
+            Status::Approved => fraud_v1::ReviewStatus::Approved as i32,
+            Status::Rejected => fraud_v1::ReviewStatus::Rejected as i32,
+        },
+        decided_by: review.decided_by,
+        notes: review.notes,
+        due_at: Some(parse_timestamp_to_proto(review.due_at)),
+        decided_at: review.decided_at.map(parse_timestamp_to_proto),
+        created_at: Some(parse_timestamp_to_proto(review.created_at)),
+        updated_at: Some(parse_timestamp_to_proto(review.updated_at)),
+    }
+}
+
+pub fn parse_model_report_to_proto(report: ModelReport) -> fraud_v1::ModelReport {
+    fraud_v1::ModelReport {
+        role: report.role.as_ref().to_string(),
+        precision: report.precision(),
+        recall: report.recall(),
+        model_version: report.model_version,
+        true_positives: report.true_positives,
+        false_positives: report.false_positives,
+        true_negatives: report.true_negatives,
+        false_negatives: report.false_negatives,
+    }
+}
+
+pub fn parse_to_domain_timestamp(
+    name: &str,
+    timestamp: Timestamp,
+) -> anyhow::Result<chrono::DateTime<chrono::Utc>> {
+    match chrono::DateTime::from_timestamp(timestamp.seconds, timestamp.nanos as u32) {
+        Some(timestamp) => Ok(timestamp),
+        None => Err(anyhow::anyhow!("invalid {name}")),
+    }
+}
+
+/// parse_to_domain_review_status defaults to open reviews when the status is unspecified.
+pub fn parse_to_domain_review_status(status: i32) -> anyhow::Result<Status> {
+    match status {
+        0 | 1 => Ok(Status::Open),
+        2 => Ok(Status::Escalated),
+        3 => Ok(Status::Approved),
+        4 => Ok(Status::Rejected),
+        _ => Err(anyhow::anyhow!("Unknown review status {status}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::evaluation::Role;
+    use fraud_proto::fraud_v1::ReviewStatus;
+
+    #[test]
+    fn test_parse_to_domain_review_status() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            status: i32,
+            expected: Option<Status>,
+        }
+
+        let test_cases: Vec<TestCase> = vec![
+            TestCase {
+                name: "successfully parse to open when status is unspecified",
+                status: ReviewStatus::Unspecified as i32,
+                expected: Some(Status::Open),
+            },
+            TestCase {
+                name: "successfully parse to domain review status when status is escalated",
+                status: ReviewStatus::Escalated as i32,
+                expected: Some(Status::Escalated),
+            },
+            TestCase {
+                name: "successfully parse to domain review status when status is rejected",
+                status: ReviewStatus::Rejected as i32,
+                expected: Some(Status::Rejected),
+            },
+            TestCase {
+                name: "error when status is unknown",
+                status: 42,
+                expected: None,
+            },
+        ];
+
+        for test_case in test_cases {
+            let resp = parse_to_domain_review_status(test_case.status);
+            match test_case.expected {
+                Some(expected) => assert_eq!(resp.unwrap(), expected, "{}", test_case.name),
+                None => assert!(resp.is_err(), "{}", test_case.name),
+            }
+        }
+    }
+
+    #[test]
+    fn successfully_parse_review_to_proto() {
+        // arrange
+        let review = Review::new(
+            uuid::Uuid::new_v4(),
+            "key",
+            0.6,
+            "v1",
+            chrono::Duration::hours(1),
+        );
+
+        // act
+        let review_proto = parse_review_to_proto(review.clone());
+
+        // assert
+        assert_eq!(review_proto.id, review.id.to_string());
+        assert_eq!(
+            review_proto.transaction_id,
+            review.transaction_id.to_string()
+        );
+        assert_eq!(review_proto.status, ReviewStatus::Open as i32);
+        assert!(review_proto.decided_at.is_none());
+    }
+
+    #[test]
+    fn successfully_parse_model_report_to_proto() {
+        // arrange
+        let mut report = ModelReport::new(Role::Challenger, "v2");
+        report.add(true, true);
+        report.add(true, false);
+
+        // act
+        let report_proto = parse_model_report_to_proto(report);
+
+        // assert
+        assert_eq!(report_proto.role, "challenger");
+        assert_eq!(report_proto.model_version, "v2");
+        assert_eq!(report_proto.true_positives, 1);
+        assert_eq!(report_proto.false_positives, 1);
+        assert_eq!(report_proto.precision, Some(0.5));
+        assert_eq!(report_proto.recall, Some(1.0));
+    }
+}
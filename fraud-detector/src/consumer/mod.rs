@@ -0,0 +1,118 @@
+use crate::repo::FraudRepository;
+use crate::service::FraudService;
+use async_trait::async_trait;
+use common::kafka::{MessageHandler, Publisher, REFUND_EVENTS_TOPIC, TRANSACTION_EVENTS_TOPIC};
+use events_proto::events_v1;
+use prost::Message;
+
+#[async_trait]
+impl<R, P> MessageHandler for FraudService<R, P>
+where
+    R: FraudRepository,
+    P: Publisher,
+{
+    async fn handle(&self, topic: &str, payload: &[u8]) -> anyhow::Result<()> {
+        match topic {
+            TRANSACTION_EVENTS_TOPIC => {
+                let transaction = match events_v1::Transaction::decode(payload) {
+                    Ok(transaction) => transaction,
+                    Err(e) => anyhow::bail!("failed to decode transaction event: {e}"),
+                };
+
+                // only newly created transactions are scored, status updates are ignored
+                if transaction.status != events_v1::TransactionStatus::Init as i32 {
+                    return Ok(());
+                }
+
+                self.check_transaction(&transaction).await?;
+            }
+            REFUND_EVENTS_TOPIC => {
+                let refund = match events_v1::Refund::decode(payload) {
+                    Ok(refund) => refund,
+                    Err(e) => anyhow::bail!("failed to decode refund event: {e}"),
+                };
+
+                self.record_refund_label(&refund).await?;
+            }
+            _ => anyhow::bail!("unexpected topic {topic}"),
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repo::PgFraudRepository;
+    use crate::service::tests::{InMemoryPublisher, contender, transaction};
+
+    async fn service(pool: sqlx::PgPool) -> FraudService<PgFraudRepository, InMemoryPublisher> {
+        FraudService::new(
+            contender(0.9, 0.5),
+            PgFraudRepository::new(common::database::Database::from_pool(pool).await.unwrap()),
+            InMemoryPublisher::default(),
+        )
+    }
+
+    #[sqlx::test(migrations = "../migrations/fraud")]
+    async fn successfully_handle_new_transaction_event(pool: sqlx::PgPool) {
+        // arrange
+        let service = service(pool).await;
+        let payload = transaction().encode_to_vec();
+
+        // act
+        let result = service.handle(TRANSACTION_EVENTS_TOPIC, &payload).await;
+
+        // assert
+        assert!(result.is_ok());
+    }
+
+    #[sqlx::test(migrations = "../migrations/fraud")]
+    async fn skip_transaction_status_updates(pool: sqlx::PgPool) {
+        // arrange
+        let service = service(pool).await;
+        let mut event = transaction();
+        event.status = events_v1::TransactionStatus::Success as i32;
+
+        // act
+        let result = service
+            .handle(TRANSACTION_EVENTS_TOPIC, &event.encode_to_vec())
+            .await;
+
+        // assert
+        assert!(result.is_ok());
+    }
+
+    #[sqlx::test(migrations = "../migrations/fraud")]
+    async fn successfully_handle_refund_event(pool: sqlx::PgPool) {
+        // arrange
+        let service = service(pool).await;
+        let refund = events_v1::Refund {
+            id: uuid::Uuid::new_v4().to_string(),
+            transaction_id: uuid::Uuid::new_v4().to_string(),
+            reason: events_v1::RefundReason::Fraudulent as i32,
+            ..Default::default()
+        };
+
+        // act
+        let result = service
+            .handle(REFUND_EVENTS_TOPIC, &refund.encode_to_vec())
+            .await;
+
+        // assert
+        assert!(result.is_ok());
+    }
+
+    #[sqlx::test(migrations = "../migrations/fraud")]
+    async fn error_when_payload_is_not_a_transaction(pool: sqlx::PgPool) {
+        let service = service(pool).await;
+
+        assert!(
+            service
+                .handle(TRANSACTION_EVENTS_TOPIC, b"\xff\xff")
+                .await
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,192 @@
+#[derive(Debug, Clone, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "evaluation_role", rename_all = "lowercase")]
+pub enum Role {
+    Champion,
+    Challenger,
+}
+
+impl AsRef<str> for Role {
+    fn as_ref(&self) -> &str {
+        match self {
+            Role::Champion => "champion",
+            Role::Challenger => "challenger",
+        }
+    }
+}
+
+/// Evaluation is a single score produced by a model for a transaction.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct Evaluation {
+    pub id: uuid::Uuid,
+    pub transaction_id: uuid::Uuid,
+    pub role: Role,
+    pub model_version: String,
+    pub score: f32,
+    pub threshold: f32,
+    pub is_fraud: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Evaluation {
+    pub fn new(
+        transaction_id: uuid::Uuid,
+        role: Role,
+        model_version: impl Into<String>,
+        score: f32,
+        threshold: f32,
+    ) -> Self {
+        Evaluation {
+            id: uuid::Uuid::new_v4(),
+            transaction_id,
+            role,
+            model_version: model_version.into(),
+            score,
+            threshold,
+            is_fraud: score >= threshold,
+            created_at: chrono::Utc::now(),
+        }
+    }
+}
+
+/// Label is the ground truth for a transaction, learnt after the fact from refunds and chargebacks.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct Label {
+    pub transaction_id: uuid::Uuid,
+    pub is_fraud: bool,
+    pub source: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// LabelledEvaluation is an evaluation joined with its label. Transactions without a label are
+/// treated as legitimate.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct LabelledEvaluation {
+    pub role: Role,
+    pub model_version: String,
+    pub is_fraud: bool,
+    pub label: Option<bool>,
+}
+
+/// ModelReport summarises how a model's verdicts compare to labels.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelReport {
+    pub role: Role,
+    pub model_version: String,
+    pub true_positives: u64,
+    pub false_positives: u64,
+    pub true_negatives: u64,
+    pub false_negatives: u64,
+}
+
+impl ModelReport {
+    pub fn new(role: Role, model_version: impl Into<String>) -> Self {
+        ModelReport {
+            role,
+            model_version: model_version.into(),
+            true_positives: 0,
+            false_positives: 0,
+            true_negatives: 0,
+            false_negatives: 0,
+        }
+    }
+
+    pub fn add(&mut self, predicted_fraud: bool, actual_fraud: bool) {
+        match (predicted_fraud, actual_fraud) {
+            (true, true) => self.true_positives += 1,
+            (true, false) => self.false_positives += 1,
+            (false, false) => self.true_negatives += 1,
+            (false, true) => self.false_negatives += 1,
+        }
+    }
+
+    /// precision is the share of flagged transactions which were actually fraud.
+    /// Returns None when the model flagged nothing.
+    pub fn precision(&self) -> Option<f64> {
+        let flagged = self.true_positives + self.false_positives;
+        (flagged > 0).then(|| self.true_positives as f64 / flagged as f64)
+    }
+
+    /// recall is the share of fraudulent transactions the model flagged.
+    /// Returns None when there was no fraud to catch.
+    pub fn recall(&self) -> Option<f64> {
+        let fraud = self.true_positives + self.false_negatives;
+        (fraud > 0).then(|| self.true_positives as f64 / fraud as f64)
+    }
+
+    pub fn from_labelled(evaluations: &[LabelledEvaluation]) -> Vec<ModelReport> {
+        let mut reports: Vec<ModelReport> = vec![];
+        for evaluation in evaluations {
+            let report = match reports.iter_mut().position(|r| {
+                r.role == evaluation.role && r.model_version == evaluation.model_version
+            }) {
+                Some(index) => &mut reports[index],
+                None => {
+                    reports.push(ModelReport::new(
+                        evaluation.role.clone(),
+                        evaluation.model_version.clone(),
+                    ));
+                    reports.last_mut().unwrap()
+                }
+            };
+            report.add(evaluation.is_fraud, evaluation.label.unwrap_or(false));
+        }
+
+        reports
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labelled(
+        role: Role,
+        version: &str,
+        is_fraud: bool,
+        label: Option<bool>,
+    ) -> LabelledEvaluation {
+        LabelledEvaluation {
+            role,
+            model_version: version.to_string(),
+            is_fraud,
+            label,
+        }
+    }
+
+    #[test]
+    fn successfully_compute_precision_and_recall_per_model() {
+        // arrange
+        let evaluations = vec![
+            labelled(Role::Champion, "v1", true, Some(true)),
+            labelled(Role::Champion, "v1", true, None),
+            labelled(Role::Champion, "v1", false, Some(true)),
+            labelled(Role::Challenger, "v2", true, Some(true)),
+            labelled(Role::Challenger, "v2", false, None),
+            labelled(Role::Challenger, "v2", true, Some(true)),
+        ];
+
+        // act
+        let reports = ModelReport::from_labelled(&evaluations);
+
+        // assert
+        assert_eq!(reports.len(), 2);
+        let champion = &reports[0];
+        assert_eq!(champion.role, Role::Champion);
+        assert_eq!(champion.precision(), Some(0.5));
+        assert_eq!(champion.recall(), Some(0.5));
+
+        let challenger = &reports[1];
+        assert_eq!(challenger.role, Role::Challenger);
+        assert_eq!(challenger.precision(), Some(1.0));
+        assert_eq!(challenger.recall(), Some(1.0));
+        assert_eq!(challenger.true_negatives, 1);
+    }
+
+    #[test]
+    fn precision_and_recall_are_undefined_without_positives() {
+        let reports = ModelReport::from_labelled(&[labelled(Role::Champion, "v1", false, None)]);
+
+        assert_eq!(reports[0].precision(), None);
+        assert_eq!(reports[0].recall(), None);
+    }
+}
@@ -0,0 +1,183 @@
+use chrono::{Datelike, Timelike};
+use common::money::Money;
+use events_proto::events_v1;
+
+/// FEATURE_NAMES is the ordered list of features the detector extracts from a transaction.
+/// Model files must declare the same list so that weights line up with values.
+pub const FEATURE_NAMES: [&str; 5] = [
+    "amount",
+    "log_amount",
+    "hour_of_day",
+    "is_weekend",
+    "is_self_transfer",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Features {
+    pub values: Vec<f32>,
+}
+
+impl Features {
+    pub fn from_transaction(transaction: &events_v1::Transaction) -> anyhow::Result<Self> {
+        let amount = match &transaction.amount {
+            Some(amount) => {
+                match Money::from_units_nanos(amount.units, amount.nanos, &amount.currency_code) {
+                    Ok(money) => money.to_decimal().parse::<f64>()?,
+                    Err(e) => {
+                        anyhow::bail!("transaction {} has invalid amount: {e}", transaction.id)
+                    }
+                }
+            }
+            None => anyhow::bail!("transaction {} has no amount", transaction.id),
+        };
+
+        let request_timestamp = match &transaction.request_timestamp {
+            Some(ts) => match chrono::DateTime::from_timestamp(ts.seconds, ts.nanos as u32) {
+                Some(timestamp) => timestamp,
+                None => anyhow::bail!("transaction {} has invalid timestamp", transaction.id),
+            },
+            None => anyhow::bail!("transaction {} has no request timestamp", transaction.id),
+        };
+
+        let is_weekend = matches!(
+            request_timestamp.weekday(),
+            chrono::Weekday::Sat | chrono::Weekday::Sun
+        );
+
+        Ok(Self {
+            values: vec![
+                amount as f32,
+                amount.max(0.0).ln_1p() as f32,
+                request_timestamp.hour() as f32 / 23.0,
+                if is_weekend { 1.0 } else { 0.0 },
+                if transaction.debit_account_id == transaction.credit_account_id {
+                    1.0
+                } else {
+                    0.0
+                },
+            ],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use events_proto::events_v1::google::r#type::Money;
+    use prost_types::Timestamp;
+
+    #[test]
+    fn successfully_extract_features_from_transaction() {
+        // arrange - saturday 2025-09-20 23:00:00 UTC
+        let transaction = events_v1::Transaction {
+            id: "tx".to_string(),
+            debit_account_id: "a".to_string(),
+            credit_account_id: "a".to_string(),
+            amount: Some(Money {
+                currency_code: "USD".to_string(),
+                units: 10,
+                nanos: 500_000_000,
+            }),
+            request_timestamp: Some(Timestamp {
+                seconds: 1758409200,
+                nanos: 0,
+            }),
+            ..Default::default()
+        };
+
+        // act
+        let features = Features::from_transaction(&transaction).unwrap();
+
+        // assert
+        assert_eq!(features.values.len(), FEATURE_NAMES.len());
+        assert_eq!(features.values[0], 10.5);
+        assert_eq!(features.values[2], 1.0);
+        assert_eq!(features.values[3], 1.0);
+        assert_eq!(features.values[4], 1.0);
+    }
+
+    #[test]
+    fn test_amount_feature() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            units: i64,
+            nanos: i32,
+            currency: &'static str,
+            expected: Option<f32>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "successfully read cents",
+                units: 10,
+                nanos: 500_000_000,
+                currency: "USD",
+                expected: Some(10.5),
+            },
+            TestCase {
+                name: "successfully read currency without minor unit",
+                units: 1050,
+                nanos: 0,
+                currency: "JPY",
+                expected: Some(1050.0),
+            },
+            TestCase {
+                name: "successfully read currency with three decimals",
+                units: 1,
+                nanos: 5_000_000,
+                currency: "KWD",
+                expected: Some(1.005),
+            },
+            TestCase {
+                name: "error when currency is unknown",
+                units: 10,
+                nanos: 0,
+                currency: "ABC",
+                expected: None,
+            },
+            TestCase {
+                name: "error when amount has fractions of a minor unit",
+                units: 10,
+                nanos: 500_000_000,
+                currency: "JPY",
+                expected: None,
+            },
+        ];
+
+        for test_case in test_cases {
+            // arrange
+            let transaction = events_v1::Transaction {
+                id: "tx".to_string(),
+                amount: Some(Money {
+                    currency_code: test_case.currency.to_string(),
+                    units: test_case.units,
+                    nanos: test_case.nanos,
+                }),
+                request_timestamp: Some(Timestamp::default()),
+                ..Default::default()
+            };
+
+            // act
+            let result = Features::from_transaction(&transaction);
+
+            // assert
+            assert_eq!(
+                result.ok().map(|features| features.values[0]),
+                test_case.expected,
+                "{}",
+                test_case.name
+            );
+        }
+    }
+
+    #[test]
+    fn error_when_transaction_has_no_amount() {
+        let transaction = events_v1::Transaction {
+            id: "tx".to_string(),
+            ..Default::default()
+        };
+
+        assert!(Features::from_transaction(&transaction).is_err());
+    }
+}
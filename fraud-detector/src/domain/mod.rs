@@ -0,0 +1,3 @@
+pub mod evaluation;
+pub mod features;
+pub mod review;
@@ -0,0 +1,191 @@
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "review_status", rename_all = "lowercase")]
+pub enum Status {
+    Open,
+    Escalated,
+    Approved,
+    Rejected,
+}
+
+impl AsRef<str> for Status {
+    fn as_ref(&self) -> &str {
+        match self {
+            Status::Open => "open",
+            Status::Escalated => "escalated",
+            Status::Approved => "approved",
+            Status::Rejected => "rejected",
+        }
+    }
+}
+
+impl Status {
+    /// is_pending is true while the review is still waiting for a decision.
+    pub fn is_pending(&self) -> bool {
+        matches!(self, Status::Open | Status::Escalated)
+    }
+}
+
+/// Decision is the final outcome of a review. Approving lets the transaction through,
+/// rejecting flags it as fraud.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Decision {
+    Approve,
+    Reject,
+}
+
+impl Decision {
+    pub fn status(&self) -> Status {
+        match self {
+            Decision::Approve => Status::Approved,
+            Decision::Reject => Status::Rejected,
+        }
+    }
+
+    pub fn is_fraud(&self) -> bool {
+        matches!(self, Decision::Reject)
+    }
+}
+
+/// Review is a transaction whose score fell in the grey zone and is parked for a human decision.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct Review {
+    pub id: uuid::Uuid,
+    pub transaction_id: uuid::Uuid,
+    pub idempotency_key: String,
+    pub score: f32,
+    pub model_version: String,
+    pub status: Status,
+    pub decided_by: Option<String>,
+    pub notes: Option<String>,
+    pub due_at: chrono::DateTime<chrono::Utc>,
+    pub decided_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Review {
+    pub fn new(
+        transaction_id: uuid::Uuid,
+        idempotency_key: impl Into<String>,
+        score: f32,
+        model_version: impl Into<String>,
+        sla: chrono::Duration,
+    ) -> Self {
+        let now = chrono::Utc::now();
+        Review {
+            id: uuid::Uuid::new_v4(),
+            transaction_id,
+            idempotency_key: idempotency_key.into(),
+            score,
+            model_version: model_version.into(),
+            status: Status::Open,
+            decided_by: None,
+            notes: None,
+            due_at: now + sla,
+            decided_at: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+/// SlaAction is what happens to an open review once its SLA has passed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SlaAction {
+    /// Escalate keeps the review pending but marks it as escalated.
+    Escalate,
+    /// Decide applies the decision on behalf of the reviewer.
+    Decide(Decision),
+}
+
+impl FromStr for SlaAction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "escalate" => Ok(SlaAction::Escalate),
+            "approve" => Ok(SlaAction::Decide(Decision::Approve)),
+            "reject" => Ok(SlaAction::Decide(Decision::Reject)),
+            _ => Err(anyhow::anyhow!(
+                "unknown SLA action {s}, expected escalate, approve or reject"
+            )),
+        }
+    }
+}
+
+/// ReviewPolicy configures the grey zone. Scores in `[review_threshold, block threshold)` are
+/// parked for review instead of being decided automatically.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewPolicy {
+    pub review_threshold: f32,
+    pub sla: chrono::Duration,
+    pub sla_action: SlaAction,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReviewError {
+    NotFound(uuid::Uuid),
+    AlreadyDecided(uuid::Uuid, Status),
+}
+
+impl fmt::Display for ReviewError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReviewError::NotFound(id) => write!(f, "review {id} not found"),
+            ReviewError::AlreadyDecided(id, status) => {
+                write!(f, "review {id} is already {}", status.as_ref())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReviewError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sla_action() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            input: &'static str,
+            expected: Option<SlaAction>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "successfully parse escalate",
+                input: "escalate",
+                expected: Some(SlaAction::Escalate),
+            },
+            TestCase {
+                name: "successfully parse approve ignoring case",
+                input: "APPROVE",
+                expected: Some(SlaAction::Decide(Decision::Approve)),
+            },
+            TestCase {
+                name: "successfully parse reject",
+                input: "reject",
+                expected: Some(SlaAction::Decide(Decision::Reject)),
+            },
+            TestCase {
+                name: "error when action is unknown",
+                input: "ignore",
+                expected: None,
+            },
+        ];
+
+        for test_case in test_cases {
+            let resp = test_case.input.parse::<SlaAction>();
+            match test_case.expected {
+                Some(expected) => assert_eq!(resp.unwrap(), expected, "{}", test_case.name),
+                None => assert!(resp.is_err(), "{}", test_case.name),
+            }
+        }
+    }
+}
@@ -0,0 +1,19 @@
+mod domain;
+pub mod model;
+pub mod repo;
+pub mod service;
+
+pub mod api;
+pub mod consumer;
+
+pub use domain::{evaluation, features, review};
+
+pub const DEFAULT_EVALUATION_REPORT_DAYS: i64 = 30;
+pub const DEFAULT_FRAUD_THRESHOLD: f32 = 0.8;
+pub const DEFAULT_MODEL_RELOAD_INTERVAL_SECONDS: u64 = 30;
+pub const DEFAULT_MODEL_TIMEOUT_MILLISECONDS: u64 = 500;
+pub const DEFAULT_REVIEW_SLA_SECONDS: i64 = 4 * 60 * 60;
+pub const DEFAULT_REVIEW_SWEEP_INTERVAL_SECONDS: u64 = 60;
+pub const DEFAULT_WRITER_MAX_CONN: u32 = 4;
+pub const DEFAULT_READER_MAX_CONN: u32 = 4;
+pub const DEFAULT_TIMEOUT_SECONDS: u64 = 5;
@@ -1,3 +1,154 @@
-fn main() {
-    println!("Hello, world!");
+use common::{database, kafka, shutdown};
+use fraud_detector::model::FraudModel;
+use fraud_detector::model::http::HttpModel;
+use fraud_detector::model::reload::ReloadableModel;
+use fraud_detector::model::rules::RuleSet;
+use fraud_detector::repo::PgFraudRepository;
+use fraud_detector::review::{ReviewPolicy, SlaAction};
+use fraud_detector::service::{Contender, FraudService};
+use fraud_detector::{
+    DEFAULT_FRAUD_THRESHOLD, DEFAULT_MODEL_RELOAD_INTERVAL_SECONDS,
+    DEFAULT_MODEL_TIMEOUT_MILLISECONDS, DEFAULT_READER_MAX_CONN, DEFAULT_REVIEW_SLA_SECONDS,
+    DEFAULT_REVIEW_SWEEP_INTERVAL_SECONDS, DEFAULT_TIMEOUT_SECONDS, DEFAULT_WRITER_MAX_CONN,
+};
+use fraud_proto::fraud_v1::{FILE_DESCRIPTOR_SET, fraud_reviews_server};
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+use tonic::codegen::tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::Server;
+
+/// load_model builds a model from `{prefix}_URL` (model server), `{prefix}_PATH` (local model
+/// file, reloaded on change) or `{prefix}_RULES_PATH` (ruleset file), in that order.
+fn load_model(prefix: &str) -> anyhow::Result<Option<Arc<dyn FraudModel>>> {
+    if let Ok(url) = env::var(format!("{prefix}_URL")) {
+        return Ok(Some(Arc::new(HttpModel::new(
+            url,
+            Duration::from_millis(DEFAULT_MODEL_TIMEOUT_MILLISECONDS),
+        )?)));
+    }
+
+    if let Ok(path) = env::var(format!("{prefix}_PATH")) {
+        let reload_interval = env::var("FRAUD_MODEL_RELOAD_INTERVAL_SECONDS")
+            .map(|v| v.parse::<u64>())
+            .unwrap_or(Ok(DEFAULT_MODEL_RELOAD_INTERVAL_SECONDS))?;
+        let model = Arc::new(ReloadableModel::from_file(path)?);
+        model.clone().watch(Duration::from_secs(reload_interval));
+        return Ok(Some(model));
+    }
+
+    if let Ok(path) = env::var(format!("{prefix}_RULES_PATH")) {
+        return Ok(Some(Arc::new(RuleSet::from_file(path)?)));
+    }
+
+    Ok(None)
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    // setup database
+    let database_config = database::Config {
+        reader_url: env::var("READER_DATABASE_URL").expect("READER_DATABASE_URL must be set"),
+        reader_max_connections: DEFAULT_READER_MAX_CONN,
+        writer_url: env::var("WRITER_DATABASE_URL").expect("WRITER_DATABASE_URL must be set"),
+        writer_max_connections: DEFAULT_WRITER_MAX_CONN,
+        timeout_in_secs: DEFAULT_TIMEOUT_SECONDS,
+    };
+    let db = database::Database::new(&database_config)
+        .await
+        .expect("failed to create database");
+
+    // setup repo layer
+    let repo = PgFraudRepository::new(db);
+
+    // setup champion and optional challenger models
+    let champion = load_model("FRAUD_MODEL")?
+        .expect("one of FRAUD_MODEL_URL, FRAUD_MODEL_PATH or FRAUD_MODEL_RULES_PATH must be set");
+    let threshold = env::var("FRAUD_THRESHOLD")
+        .map(|v| v.parse::<f32>())
+        .unwrap_or(Ok(DEFAULT_FRAUD_THRESHOLD))?;
+    let challenger = load_model("FRAUD_CHALLENGER_MODEL")?;
+    let challenger_threshold = env::var("FRAUD_CHALLENGER_THRESHOLD")
+        .map(|v| v.parse::<f32>())
+        .unwrap_or(Ok(threshold))?;
+
+    // setup kafka
+    let kafka_config = kafka::Config {
+        brokers: env::var("KAFKA_BROKERS").expect("KAFKA_BROKERS must be set"),
+        group_id: env::var("KAFKA_GROUP_ID").unwrap_or("fraud-detector".to_string()),
+    };
+    let publisher = kafka::KafkaPublisher::new(&kafka_config)?;
+    let dead_letters = kafka::DeadLetterQueue::new(&kafka_config)?;
+    let retry_topic = kafka::retry_topic(kafka_config.group_id.as_str());
+    let consumer = kafka::new_consumer(
+        &kafka_config,
+        &[
+            kafka::TRANSACTION_EVENTS_TOPIC,
+            kafka::REFUND_EVENTS_TOPIC,
+            retry_topic.as_str(),
+        ],
+    )?;
+
+    // setup service
+    let mut service = FraudService::new(Contender::new(champion, threshold), repo, publisher);
+    if let Some(challenger) = challenger {
+        service = service.with_challenger(Contender::new(challenger, challenger_threshold));
+    }
+
+    // scores in [FRAUD_REVIEW_THRESHOLD, FRAUD_THRESHOLD) are parked for manual review
+    if let Ok(review_threshold) = env::var("FRAUD_REVIEW_THRESHOLD") {
+        let sla_seconds = env::var("FRAUD_REVIEW_SLA_SECONDS")
+            .map(|v| v.parse::<i64>())
+            .unwrap_or(Ok(DEFAULT_REVIEW_SLA_SECONDS))?;
+        let sla_action = env::var("FRAUD_REVIEW_SLA_ACTION")
+            .map(|v| v.parse::<SlaAction>())
+            .unwrap_or(Ok(SlaAction::Escalate))?;
+        service = service.with_review_policy(ReviewPolicy {
+            review_threshold: review_threshold.parse()?,
+            sla: chrono::Duration::seconds(sla_seconds),
+            sla_action,
+        });
+    }
+    let service = Arc::new(service);
+    service
+        .clone()
+        .spawn_sla_sweeper(Duration::from_secs(DEFAULT_REVIEW_SWEEP_INTERVAL_SECONDS));
+
+    // add reflection
+    let reflection_service = tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(FILE_DESCRIPTOR_SET)
+        .build_v1()?;
+
+    // setup grpc server for the review queue
+    let port: u32 = env::var("PORT").unwrap_or("8000".to_string()).parse()?;
+    let address = format!("0.0.0.0:{}", port);
+    let listener = tokio::net::TcpListener::bind(address)
+        .await
+        .expect("Failed to create TCP listener ❌");
+    let server = Server::builder()
+        .add_service(reflection_service)
+        // add fraud service to fraud reviews server
+        .add_service(fraud_reviews_server::FraudReviewsServer::from_arc(
+            service.clone(),
+        ))
+        .serve_with_incoming_shutdown(
+            TcpListenerStream::new(listener),
+            shutdown::shutdown_signal(),
+        );
+
+    let (consumed, served) = tokio::join!(
+        kafka::consume_with_dead_letters(
+            consumer,
+            service,
+            dead_letters,
+            shutdown::shutdown_signal()
+        ),
+        server
+    );
+    consumed?;
+    served?;
+
+    Ok(())
 }
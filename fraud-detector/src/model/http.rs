@@ -0,0 +1,136 @@
+use crate::domain::features::{FEATURE_NAMES, Features};
+use crate::model::{FraudModel, Score};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Debug, Serialize)]
+struct ScoreRequest<'a> {
+    feature_names: &'a [&'a str],
+    features: &'a [f32],
+}
+
+#[derive(Debug, Deserialize)]
+struct ScoreResponse {
+    score: f32,
+    model_version: String,
+}
+
+/// HttpModel scores transactions by calling a remote model server.
+///
+/// The server is expected to expose `POST {base_url}/v1/score` which accepts
+/// `{"feature_names": [...], "features": [...]}` and returns `{"score": 0.1, "model_version": "..."}`.
+#[derive(Debug, Clone)]
+pub struct HttpModel {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl HttpModel {
+    pub fn new(base_url: impl Into<String>, timeout: Duration) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder().timeout(timeout).build()?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl FraudModel for HttpModel {
+    async fn score(&self, features: &Features) -> anyhow::Result<Score> {
+        let response = match self
+            .client
+            .post(format!("{}/v1/score", self.base_url))
+            .json(&ScoreRequest {
+                feature_names: &FEATURE_NAMES,
+                features: &features.values,
+            })
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => anyhow::bail!("failed to call model server: {e}"),
+        };
+
+        if !response.status().is_success() {
+            anyhow::bail!("model server returned status {}", response.status());
+        }
+
+        let response: ScoreResponse = match response.json().await {
+            Ok(response) => response,
+            Err(e) => anyhow::bail!("failed to decode model server response: {e}"),
+        };
+
+        if !(0.0..=1.0).contains(&response.score) {
+            anyhow::bail!(
+                "model server returned out of range score {}",
+                response.score
+            );
+        }
+
+        Ok(Score {
+            value: response.score,
+            model_version: response.model_version,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::Json;
+    use axum::Router;
+    use axum::http::StatusCode;
+    use axum::routing::post;
+    use common::test_util::spawn_stub;
+    use serde_json::{Value, json};
+
+    #[tokio::test]
+    async fn successfully_score_features_with_model_server() {
+        // arrange - stub returns the last feature as score
+        let router = Router::new().route(
+            "/v1/score",
+            post(|Json(body): Json<Value>| async move {
+                let score = body["features"][4].as_f64().unwrap();
+                Json(json!({"score": score, "model_version": "remote-v7"}))
+            }),
+        );
+        let base_url = spawn_stub(router).await;
+        let model = HttpModel::new(base_url, Duration::from_secs(2)).unwrap();
+
+        // act
+        let score = model
+            .score(&Features {
+                values: vec![1.0, 0.7, 0.1, 0.0, 1.0],
+            })
+            .await;
+
+        // assert
+        let score = score.unwrap();
+        assert_eq!(score.value, 1.0);
+        assert_eq!(score.model_version, "remote-v7");
+    }
+
+    #[tokio::test]
+    async fn error_when_model_server_fails() {
+        // arrange
+        let router = Router::new().route(
+            "/v1/score",
+            post(|| async { StatusCode::SERVICE_UNAVAILABLE }),
+        );
+        let base_url = spawn_stub(router).await;
+        let model = HttpModel::new(base_url, Duration::from_secs(2)).unwrap();
+
+        // act
+        let score = model
+            .score(&Features {
+                values: vec![1.0, 0.7, 0.1, 0.0, 0.0],
+            })
+            .await;
+
+        // assert
+        assert!(score.is_err());
+    }
+}
@@ -0,0 +1,147 @@
+use crate::domain::features::{FEATURE_NAMES, Features};
+use crate::model::{FraudModel, Score};
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::path::Path;
+
+/// LocalModel is a logistic regression model loaded from a JSON file and scored in-process as
+/// `sigmoid(weights · features + bias)`. Exporters write the coefficients in the order of
+/// [`FEATURE_NAMES`]; ONNX models are not supported.
+///
+/// Example model file:
+/// ```json
+/// {
+///   "version": "logreg-2025-10-01",
+///   "features": ["amount", "log_amount", "hour_of_day", "is_weekend", "is_self_transfer"],
+///   "weights": [0.0001, 0.4, 0.2, 0.1, 2.5],
+///   "bias": -4.0
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LocalModel {
+    pub version: String,
+    pub features: Vec<String>,
+    pub weights: Vec<f32>,
+    pub bias: f32,
+}
+
+impl LocalModel {
+    pub fn from_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) => anyhow::bail!("failed to read model file {}: {e}", path.display()),
+        };
+
+        Self::from_json(contents.as_str())
+    }
+
+    pub fn from_json(json: &str) -> anyhow::Result<Self> {
+        let model: LocalModel = match serde_json::from_str(json) {
+            Ok(model) => model,
+            Err(e) => anyhow::bail!("failed to parse model: {e}"),
+        };
+
+        if model.features != FEATURE_NAMES {
+            anyhow::bail!(
+                "model {} expects features {:?} but detector extracts {:?}",
+                model.version,
+                model.features,
+                FEATURE_NAMES
+            );
+        }
+
+        if model.weights.len() != model.features.len() {
+            anyhow::bail!(
+                "model {} has {} weights for {} features",
+                model.version,
+                model.weights.len(),
+                model.features.len()
+            );
+        }
+
+        Ok(model)
+    }
+
+    fn predict(&self, features: &Features) -> anyhow::Result<f32> {
+        if features.values.len() != self.weights.len() {
+            anyhow::bail!(
+                "expected {} features, got {}",
+                self.weights.len(),
+                features.values.len()
+            );
+        }
+
+        let logit: f32 = self
+            .weights
+            .iter()
+            .zip(features.values.iter())
+            .map(|(w, x)| w * x)
+            .sum::<f32>()
+            + self.bias;
+
+        Ok(1.0 / (1.0 + (-logit).exp()))
+    }
+}
+
+#[async_trait]
+impl FraudModel for LocalModel {
+    async fn score(&self, features: &Features) -> anyhow::Result<Score> {
+        Ok(Score {
+            value: self.predict(features)?,
+            model_version: self.version.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODEL: &str = r#"{
+        "version": "test-v1",
+        "features": ["amount", "log_amount", "hour_of_day", "is_weekend", "is_self_transfer"],
+        "weights": [0.0, 0.0, 0.0, 0.0, 10.0],
+        "bias": -5.0
+    }"#;
+
+    #[tokio::test]
+    async fn successfully_score_features_with_local_model() {
+        // arrange
+        let model = LocalModel::from_json(MODEL).unwrap();
+        let legit = Features {
+            values: vec![10.0, 2.4, 0.5, 0.0, 0.0],
+        };
+        let suspicious = Features {
+            values: vec![10.0, 2.4, 0.5, 0.0, 1.0],
+        };
+
+        // act
+        let legit_score = model.score(&legit).await.unwrap();
+        let suspicious_score = model.score(&suspicious).await.unwrap();
+
+        // assert
+        assert_eq!(legit_score.model_version, "test-v1");
+        assert!(legit_score.value < 0.01);
+        assert!(suspicious_score.value > 0.99);
+    }
+
+    #[test]
+    fn error_when_model_features_do_not_match_detector() {
+        let model = r#"{"version": "v", "features": ["amount"], "weights": [1.0], "bias": 0.0}"#;
+
+        assert!(LocalModel::from_json(model).is_err());
+    }
+
+    #[test]
+    fn error_when_model_weights_do_not_match_features() {
+        let model = r#"{
+            "version": "v",
+            "features": ["amount", "log_amount", "hour_of_day", "is_weekend", "is_self_transfer"],
+            "weights": [1.0],
+            "bias": 0.0
+        }"#;
+
+        assert!(LocalModel::from_json(model).is_err());
+    }
+}
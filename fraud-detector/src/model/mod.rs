@@ -0,0 +1,29 @@
+use crate::domain::features::Features;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+pub mod http;
+pub mod local;
+pub mod reload;
+pub mod rules;
+
+/// Score is the output of a fraud model for a single transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Score {
+    /// value is between 0 and 1. A higher value means higher confidence in fraud.
+    pub value: f32,
+    /// model_version identifies the model which produced the score.
+    pub model_version: String,
+}
+
+#[async_trait]
+pub trait FraudModel: 'static + Send + Sync {
+    async fn score(&self, features: &Features) -> anyhow::Result<Score>;
+}
+
+#[async_trait]
+impl FraudModel for Arc<dyn FraudModel> {
+    async fn score(&self, features: &Features) -> anyhow::Result<Score> {
+        self.as_ref().score(features).await
+    }
+}
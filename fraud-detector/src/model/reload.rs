@@ -0,0 +1,150 @@
+use crate::domain::features::Features;
+use crate::model::local::LocalModel;
+use crate::model::{FraudModel, Score};
+use async_trait::async_trait;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+
+/// ReloadableModel wraps a LocalModel and swaps it when the model file changes on disk.
+///
+/// Every call to `score` takes its own reference to the current model before scoring, so a
+/// reload never interrupts a message that is already being processed. If the new file fails
+/// to load the previous model stays active.
+pub struct ReloadableModel {
+    path: PathBuf,
+    current: RwLock<Arc<LocalModel>>,
+    modified_at: RwLock<Option<SystemTime>>,
+}
+
+impl ReloadableModel {
+    pub fn from_file(path: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let path = path.into();
+        let modified_at = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+        let model = LocalModel::from_file(&path)?;
+
+        Ok(Self {
+            path,
+            current: RwLock::new(Arc::new(model)),
+            modified_at: RwLock::new(modified_at),
+        })
+    }
+
+    pub fn current(&self) -> Arc<LocalModel> {
+        self.current.read().expect("model lock poisoned").clone()
+    }
+
+    /// reload_if_changed loads the model file again if its modification time changed.
+    /// Returns true when a new model was swapped in.
+    pub fn reload_if_changed(&self) -> anyhow::Result<bool> {
+        let modified_at = std::fs::metadata(&self.path)
+            .and_then(|m| m.modified())
+            .ok();
+        if modified_at == *self.modified_at.read().expect("model lock poisoned") {
+            return Ok(false);
+        }
+
+        let model = LocalModel::from_file(&self.path)?;
+        let previous = self.current().version.clone();
+        *self.current.write().expect("model lock poisoned") = Arc::new(model);
+        *self.modified_at.write().expect("model lock poisoned") = modified_at;
+        tracing::info!(
+            previous_version = previous,
+            version = self.current().version,
+            "reloaded fraud model"
+        );
+
+        Ok(true)
+    }
+
+    /// watch polls the model file every interval and reloads it when it changes.
+    pub fn watch(self: Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.reload_if_changed() {
+                    tracing::error!("failed to reload fraud model, keeping current model: {e}");
+                }
+            }
+        })
+    }
+}
+
+#[async_trait]
+impl FraudModel for ReloadableModel {
+    async fn score(&self, features: &Features) -> anyhow::Result<Score> {
+        let model = self.current();
+        model.score(features).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn model_json(version: &str) -> String {
+        format!(
+            r#"{{
+                "version": "{version}",
+                "features": ["amount", "log_amount", "hour_of_day", "is_weekend", "is_self_transfer"],
+                "weights": [0.0, 0.0, 0.0, 0.0, 0.0],
+                "bias": 0.0
+            }}"#
+        )
+    }
+
+    fn write_model(path: &std::path::Path, contents: &str) {
+        let mut file = std::fs::File::create(path).unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        // make sure the modification time moves forward on coarse grained filesystems
+        let modified = SystemTime::now() + Duration::from_secs(1);
+        file.set_modified(modified).unwrap();
+    }
+
+    #[tokio::test]
+    async fn successfully_reload_model_when_file_changes() {
+        // arrange
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("model.json");
+        std::fs::write(&path, model_json("v1")).unwrap();
+        let model = ReloadableModel::from_file(&path).unwrap();
+        let features = Features {
+            values: vec![0.0; 5],
+        };
+
+        // in-flight message holds the old model
+        let in_flight = model.current();
+
+        // act
+        write_model(&path, model_json("v2").as_str());
+        let reloaded = model.reload_if_changed().unwrap();
+
+        // assert
+        assert!(reloaded);
+        assert_eq!(
+            in_flight.score(&features).await.unwrap().model_version,
+            "v1"
+        );
+        assert_eq!(model.score(&features).await.unwrap().model_version, "v2");
+        assert!(!model.reload_if_changed().unwrap());
+    }
+
+    #[tokio::test]
+    async fn keep_current_model_when_new_file_is_invalid() {
+        // arrange
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("model.json");
+        std::fs::write(&path, model_json("v1")).unwrap();
+        let model = ReloadableModel::from_file(&path).unwrap();
+
+        // act
+        write_model(&path, "not a model");
+        let result = model.reload_if_changed();
+
+        // assert
+        assert!(result.is_err());
+        assert_eq!(model.current().version, "v1");
+    }
+}
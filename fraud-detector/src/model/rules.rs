@@ -0,0 +1,176 @@
+use crate::domain::features::{FEATURE_NAMES, Features};
+use crate::model::{FraudModel, Score};
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Operator {
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Eq,
+}
+
+impl Operator {
+    fn matches(&self, value: f32, threshold: f32) -> bool {
+        match self {
+            Operator::Gt => value > threshold,
+            Operator::Gte => value >= threshold,
+            Operator::Lt => value < threshold,
+            Operator::Lte => value <= threshold,
+            Operator::Eq => value == threshold,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Rule {
+    pub name: String,
+    pub feature: String,
+    pub operator: Operator,
+    pub value: f32,
+    pub score: f32,
+}
+
+/// RuleSet scores a transaction with the highest score of all rules it matches, or
+/// `default_score` when none match. It implements FraudModel so a ruleset can run as champion
+/// or challenger next to a learnt model.
+///
+/// Example ruleset file:
+/// ```json
+/// {
+///   "version": "rules-2025-10-01",
+///   "default_score": 0.0,
+///   "rules": [
+///     {"name": "large_amount", "feature": "amount", "operator": "gt", "value": 10000, "score": 0.9}
+///   ]
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RuleSet {
+    pub version: String,
+    #[serde(default)]
+    pub default_score: f32,
+    pub rules: Vec<Rule>,
+}
+
+impl RuleSet {
+    pub fn from_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) => anyhow::bail!("failed to read ruleset file {}: {e}", path.display()),
+        };
+
+        Self::from_json(contents.as_str())
+    }
+
+    pub fn from_json(json: &str) -> anyhow::Result<Self> {
+        let ruleset: RuleSet = match serde_json::from_str(json) {
+            Ok(ruleset) => ruleset,
+            Err(e) => anyhow::bail!("failed to parse ruleset: {e}"),
+        };
+
+        for rule in &ruleset.rules {
+            if !FEATURE_NAMES.contains(&rule.feature.as_str()) {
+                anyhow::bail!("rule {} uses unknown feature {}", rule.name, rule.feature);
+            }
+            if !(0.0..=1.0).contains(&rule.score) {
+                anyhow::bail!("rule {} has out of range score {}", rule.name, rule.score);
+            }
+        }
+
+        Ok(ruleset)
+    }
+}
+
+#[async_trait]
+impl FraudModel for RuleSet {
+    async fn score(&self, features: &Features) -> anyhow::Result<Score> {
+        let mut score = self.default_score;
+        for rule in &self.rules {
+            let index = match FEATURE_NAMES.iter().position(|f| *f == rule.feature) {
+                Some(index) => index,
+                None => anyhow::bail!("rule {} uses unknown feature {}", rule.name, rule.feature),
+            };
+            let value = match features.values.get(index) {
+                Some(value) => *value,
+                None => anyhow::bail!("feature {} is missing", rule.feature),
+            };
+            if rule.operator.matches(value, rule.value) {
+                score = score.max(rule.score);
+            }
+        }
+
+        Ok(Score {
+            value: score,
+            model_version: self.version.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RULES: &str = r#"{
+        "version": "rules-v1",
+        "default_score": 0.05,
+        "rules": [
+            {"name": "large_amount", "feature": "amount", "operator": "gt", "value": 1000, "score": 0.7},
+            {"name": "self_transfer", "feature": "is_self_transfer", "operator": "eq", "value": 1, "score": 0.95}
+        ]
+    }"#;
+
+    #[tokio::test]
+    async fn successfully_score_with_highest_matching_rule() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            values: Vec<f32>,
+            expected: f32,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "default score when no rule matches",
+                values: vec![10.0, 2.4, 0.5, 0.0, 0.0],
+                expected: 0.05,
+            },
+            TestCase {
+                name: "rule score when single rule matches",
+                values: vec![5000.0, 8.5, 0.5, 0.0, 0.0],
+                expected: 0.7,
+            },
+            TestCase {
+                name: "highest rule score when multiple rules match",
+                values: vec![5000.0, 8.5, 0.5, 0.0, 1.0],
+                expected: 0.95,
+            },
+        ];
+
+        let ruleset = RuleSet::from_json(RULES).unwrap();
+        for test_case in test_cases {
+            let score = ruleset
+                .score(&Features {
+                    values: test_case.values,
+                })
+                .await
+                .unwrap();
+            assert_eq!(score.value, test_case.expected, "{}", test_case.name);
+            assert_eq!(score.model_version, "rules-v1", "{}", test_case.name);
+        }
+    }
+
+    #[test]
+    fn error_when_rule_uses_unknown_feature() {
+        let rules = r#"{"version": "v", "rules": [
+            {"name": "r", "feature": "country", "operator": "eq", "value": 1, "score": 0.5}
+        ]}"#;
+
+        assert!(RuleSet::from_json(rules).is_err());
+    }
+}
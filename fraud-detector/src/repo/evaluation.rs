@@ -0,0 +1,120 @@
+use crate::domain::evaluation::{Evaluation, LabelledEvaluation};
+use crate::repo::{EvaluationReader, EvaluationWriter, PgFraudRepository};
+use async_trait::async_trait;
+
+#[async_trait]
+impl EvaluationWriter for PgFraudRepository {
+    async fn record_evaluations(&self, evaluations: &[Evaluation]) -> anyhow::Result<()> {
+        let mut tx = self.db.writer.begin().await?;
+        for evaluation in evaluations {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO fraud_evaluations (id, transaction_id, role, model_version, score, threshold, is_fraud, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                ON CONFLICT (transaction_id, role) DO NOTHING
+                "#,
+            )
+            .bind(evaluation.id)
+            .bind(evaluation.transaction_id)
+            .bind(evaluation.role.clone())
+            .bind(evaluation.model_version.as_str())
+            .bind(evaluation.score)
+            .bind(evaluation.threshold)
+            .bind(evaluation.is_fraud)
+            .bind(evaluation.created_at)
+            .execute(&mut *tx)
+            .await;
+
+            if let Err(e) = result {
+                anyhow::bail!("Failed to insert evaluation into database: {e}")
+            }
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EvaluationReader for PgFraudRepository {
+    async fn get_evaluations_by_transaction_id(
+        &self,
+        transaction_id: uuid::Uuid,
+    ) -> anyhow::Result<Vec<Evaluation>> {
+        let result = sqlx::query_as::<_, Evaluation>(
+            r#"
+                SELECT id, transaction_id, role, model_version, score, threshold, is_fraud, created_at
+                FROM fraud_evaluations
+                WHERE transaction_id = $1
+                ORDER BY role
+                "#,
+        )
+        .bind(transaction_id)
+        .fetch_all(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(evaluations) => Ok(evaluations),
+            Err(e) => anyhow::bail!("Failed to get_evaluations_by_transaction_id: {e}"),
+        }
+    }
+
+    async fn get_labelled_evaluations(
+        &self,
+        from: chrono::DateTime<chrono::Utc>,
+        to: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<Vec<LabelledEvaluation>> {
+        let result = sqlx::query_as::<_, LabelledEvaluation>(
+            r#"
+                SELECT e.role, e.model_version, e.is_fraud, l.is_fraud AS label
+                FROM fraud_evaluations e
+                LEFT JOIN fraud_labels l ON l.transaction_id = e.transaction_id
+                WHERE e.created_at >= $1 AND e.created_at < $2
+                ORDER BY e.role, e.model_version
+                "#,
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(evaluations) => Ok(evaluations),
+            Err(e) => anyhow::bail!("Failed to get_labelled_evaluations: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::evaluation::Role;
+
+    #[sqlx::test(migrations = "../migrations/fraud")]
+    async fn successfully_record_evaluations_and_retrieve_by_transaction_id(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgFraudRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let transaction_id = uuid::Uuid::new_v4();
+        let evaluations = vec![
+            Evaluation::new(transaction_id, Role::Champion, "v1", 0.9, 0.8),
+            Evaluation::new(transaction_id, Role::Challenger, "v2", 0.3, 0.8),
+        ];
+
+        // act
+        let resp = repo.record_evaluations(&evaluations).await;
+        assert!(resp.is_ok());
+
+        // assert
+        let fetched = repo
+            .get_evaluations_by_transaction_id(transaction_id)
+            .await
+            .unwrap();
+        assert_eq!(fetched.len(), 2);
+        assert_eq!(fetched[0].role, Role::Champion);
+        assert!(fetched[0].is_fraud);
+        assert_eq!(fetched[1].role, Role::Challenger);
+        assert_eq!(fetched[1].model_version, "v2");
+        assert!(!fetched[1].is_fraud);
+    }
+}
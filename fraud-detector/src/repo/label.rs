@@ -0,0 +1,87 @@
+use crate::domain::evaluation::Label;
+use crate::repo::{LabelWriter, PgFraudRepository};
+use async_trait::async_trait;
+
+#[async_trait]
+impl LabelWriter for PgFraudRepository {
+    async fn record_label(&self, label: &Label) -> anyhow::Result<()> {
+        // a positive label always wins, a refund for a legitimate reason must not hide a chargeback
+        let result = sqlx::query(
+            r#"
+            INSERT INTO fraud_labels (transaction_id, is_fraud, source, created_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (transaction_id) DO UPDATE
+            SET is_fraud = fraud_labels.is_fraud OR EXCLUDED.is_fraud,
+                source = CASE WHEN fraud_labels.is_fraud THEN fraud_labels.source ELSE EXCLUDED.source END,
+                created_at = EXCLUDED.created_at
+            "#,
+        )
+        .bind(label.transaction_id)
+        .bind(label.is_fraud)
+        .bind(label.source.as_str())
+        .bind(label.created_at)
+        .execute(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => anyhow::bail!("Failed to insert label into database: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::evaluation::{Evaluation, Role};
+    use crate::repo::{EvaluationReader, EvaluationWriter};
+
+    #[sqlx::test(migrations = "../migrations/fraud")]
+    async fn successfully_join_labels_with_evaluations(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgFraudRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let fraudulent = uuid::Uuid::new_v4();
+        let legitimate = uuid::Uuid::new_v4();
+        repo.record_evaluations(&[
+            Evaluation::new(fraudulent, Role::Champion, "v1", 0.9, 0.8),
+            Evaluation::new(legitimate, Role::Champion, "v1", 0.1, 0.8),
+        ])
+        .await
+        .unwrap();
+
+        // act
+        let resp = repo
+            .record_label(&Label {
+                transaction_id: fraudulent,
+                is_fraud: true,
+                source: "refund:chargeback".to_string(),
+                created_at: chrono::Utc::now(),
+            })
+            .await;
+        assert!(resp.is_ok());
+
+        // a later refund for a legitimate reason keeps the positive label
+        repo.record_label(&Label {
+            transaction_id: fraudulent,
+            is_fraud: false,
+            source: "refund:requested_by_customer".to_string(),
+            created_at: chrono::Utc::now(),
+        })
+        .await
+        .unwrap();
+
+        // assert
+        let evaluations = repo
+            .get_labelled_evaluations(
+                chrono::Utc::now() - chrono::Duration::hours(1),
+                chrono::Utc::now() + chrono::Duration::hours(1),
+            )
+            .await
+            .unwrap();
+        assert_eq!(evaluations.len(), 2);
+        let labels: Vec<Option<bool>> = evaluations.iter().map(|e| e.label).collect();
+        assert!(labels.contains(&Some(true)));
+        assert!(labels.contains(&None));
+    }
+}
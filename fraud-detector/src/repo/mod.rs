@@ -0,0 +1,93 @@
+use crate::domain::evaluation::{Evaluation, Label, LabelledEvaluation};
+use crate::domain::review::{Decision, Review, Status};
+use async_trait::async_trait;
+use common::database::Database;
+
+mod evaluation;
+mod label;
+mod review;
+
+#[derive(Clone, Debug)]
+pub struct PgFraudRepository {
+    pub db: Database,
+}
+
+impl PgFraudRepository {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+pub trait FraudRepository:
+    EvaluationWriter
+    + EvaluationReader
+    + LabelWriter
+    + ReviewWriter
+    + ReviewReader
+    + 'static
+    + Sync
+    + Send
+{
+}
+
+/// EvaluationWriter is the evaluation sink, every champion and challenger score is written to it.
+/// A transaction keeps the first evaluation per role, so redelivered transactions are not counted
+/// twice.
+#[async_trait]
+pub trait EvaluationWriter: 'static + Sync + Send {
+    async fn record_evaluations(&self, evaluations: &[Evaluation]) -> anyhow::Result<()>;
+}
+
+#[async_trait]
+pub trait EvaluationReader: 'static + Sync + Send {
+    async fn get_evaluations_by_transaction_id(
+        &self,
+        transaction_id: uuid::Uuid,
+    ) -> anyhow::Result<Vec<Evaluation>>;
+    async fn get_labelled_evaluations(
+        &self,
+        from: chrono::DateTime<chrono::Utc>,
+        to: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<Vec<LabelledEvaluation>>;
+}
+
+#[async_trait]
+pub trait LabelWriter: 'static + Sync + Send {
+    async fn record_label(&self, label: &Label) -> anyhow::Result<()>;
+}
+
+/// ReviewWriter manages the manual review queue. Only pending reviews can be decided, deciding
+/// an already decided review returns `ReviewError::AlreadyDecided`.
+#[async_trait]
+pub trait ReviewWriter: 'static + Sync + Send {
+    async fn create_review(&self, review: &Review) -> anyhow::Result<Review>;
+    /// decide_review records the decision and returns the decided review with the status it
+    /// had before, e.g. escalated.
+    async fn decide_review(
+        &self,
+        id: uuid::Uuid,
+        decision: Decision,
+        decided_by: &str,
+        notes: Option<&str>,
+    ) -> anyhow::Result<(Review, Status)>;
+    /// reopen_review undoes a decision, moving the review back to `status`.
+    async fn reopen_review(&self, id: uuid::Uuid, status: Status) -> anyhow::Result<()>;
+    async fn escalate_review(&self, id: uuid::Uuid) -> anyhow::Result<()>;
+}
+
+#[async_trait]
+pub trait ReviewReader: 'static + Sync + Send {
+    async fn get_review_by_id(&self, id: uuid::Uuid) -> anyhow::Result<Review>;
+    async fn get_review_by_transaction_id(
+        &self,
+        transaction_id: uuid::Uuid,
+    ) -> anyhow::Result<Review>;
+    async fn get_reviews_by_status(&self, status: Status) -> anyhow::Result<Vec<Review>>;
+    async fn get_overdue_reviews(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<Vec<Review>>;
+}
+
+impl FraudRepository for PgFraudRepository {}
@@ -0,0 +1,352 @@
+use crate::domain::review::{Decision, Review, ReviewError, Status};
+use crate::repo::{PgFraudRepository, ReviewReader, ReviewWriter};
+use async_trait::async_trait;
+
+const REVIEW_COLUMNS: &str = "id, transaction_id, idempotency_key, score, model_version, status, decided_by, notes, due_at, decided_at, created_at, updated_at";
+
+#[async_trait]
+impl ReviewWriter for PgFraudRepository {
+    async fn create_review(&self, review: &Review) -> anyhow::Result<Review> {
+        // a redelivered transaction event must not open a second review, the no-op update
+        // returns the review already open for the transaction
+        let result = sqlx::query_as::<_, Review>(&format!(
+            r#"
+            INSERT INTO fraud_reviews (id, transaction_id, idempotency_key, score, model_version, status, due_at, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT (transaction_id) DO UPDATE SET transaction_id = EXCLUDED.transaction_id
+            RETURNING {REVIEW_COLUMNS}
+            "#
+        ))
+        .bind(review.id)
+        .bind(review.transaction_id)
+        .bind(review.idempotency_key.as_str())
+        .bind(review.score)
+        .bind(review.model_version.as_str())
+        .bind(review.status.clone())
+        .bind(review.due_at)
+        .bind(review.created_at)
+        .bind(review.updated_at)
+        .fetch_one(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(review) => Ok(review),
+            Err(e) => anyhow::bail!("Failed to insert review into database: {e}"),
+        }
+    }
+
+    async fn decide_review(
+        &self,
+        id: uuid::Uuid,
+        decision: Decision,
+        decided_by: &str,
+        notes: Option<&str>,
+    ) -> anyhow::Result<(Review, Status)> {
+        let mut tx = self.db.writer.begin().await?;
+
+        let result = sqlx::query_scalar::<_, Status>(
+            "SELECT status FROM fraud_reviews WHERE id = $1 FOR UPDATE",
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await;
+
+        let previous = match result {
+            Ok(Some(status)) => status,
+            Ok(None) => return Err(ReviewError::NotFound(id).into()),
+            Err(e) => anyhow::bail!("Failed to lock review: {e}"),
+        };
+
+        let result = sqlx::query_as::<_, Review>(&format!(
+            r#"
+            UPDATE fraud_reviews
+            SET status = $2, decided_by = $3, notes = $4, decided_at = now(), updated_at = now()
+            WHERE id = $1 AND status IN ('open', 'escalated')
+            RETURNING {REVIEW_COLUMNS}
+            "#
+        ))
+        .bind(id)
+        .bind(decision.status())
+        .bind(decided_by)
+        .bind(notes)
+        .fetch_optional(&mut *tx)
+        .await;
+
+        let review = match result {
+            Ok(Some(review)) => review,
+            Ok(None) => return Err(ReviewError::AlreadyDecided(id, previous).into()),
+            Err(e) => anyhow::bail!("Failed to decide_review: {e}"),
+        };
+
+        tx.commit().await?;
+
+        Ok((review, previous))
+    }
+
+    async fn reopen_review(&self, id: uuid::Uuid, status: Status) -> anyhow::Result<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE fraud_reviews
+            SET status = $2, decided_by = NULL, notes = NULL, decided_at = NULL, updated_at = now()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(status)
+        .execute(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => anyhow::bail!("Failed to reopen_review: {e}"),
+        }
+    }
+
+    async fn escalate_review(&self, id: uuid::Uuid) -> anyhow::Result<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE fraud_reviews
+            SET status = 'escalated', updated_at = now()
+            WHERE id = $1 AND status = 'open'
+            "#,
+        )
+        .bind(id)
+        .execute(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => anyhow::bail!("Failed to escalate_review: {e}"),
+        }
+    }
+}
+
+#[async_trait]
+impl ReviewReader for PgFraudRepository {
+    async fn get_review_by_id(&self, id: uuid::Uuid) -> anyhow::Result<Review> {
+        let result = sqlx::query_as::<_, Review>(&format!(
+            "SELECT {REVIEW_COLUMNS} FROM fraud_reviews WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_one(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(review) => Ok(review),
+            Err(sqlx::Error::RowNotFound) => Err(ReviewError::NotFound(id).into()),
+            Err(e) => anyhow::bail!("Failed to get_review_by_id: {e}"),
+        }
+    }
+
+    async fn get_review_by_transaction_id(
+        &self,
+        transaction_id: uuid::Uuid,
+    ) -> anyhow::Result<Review> {
+        let result = sqlx::query_as::<_, Review>(&format!(
+            "SELECT {REVIEW_COLUMNS} FROM fraud_reviews WHERE transaction_id = $1"
+        ))
+        .bind(transaction_id)
+        .fetch_one(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(review) => Ok(review),
+            Err(sqlx::Error::RowNotFound) => {
+                anyhow::bail!("Review for transaction {transaction_id} not found")
+            }
+            Err(e) => anyhow::bail!("Failed to get_review_by_transaction_id: {e}"),
+        }
+    }
+
+    async fn get_reviews_by_status(&self, status: Status) -> anyhow::Result<Vec<Review>> {
+        let result = sqlx::query_as::<_, Review>(&format!(
+            "SELECT {REVIEW_COLUMNS} FROM fraud_reviews WHERE status = $1 ORDER BY due_at"
+        ))
+        .bind(status.clone())
+        .fetch_all(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(reviews) => Ok(reviews),
+            Err(e) => anyhow::bail!("Failed to get_reviews_by_status: {e}"),
+        }
+    }
+
+    async fn get_overdue_reviews(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<Vec<Review>> {
+        let result = sqlx::query_as::<_, Review>(&format!(
+            "SELECT {REVIEW_COLUMNS} FROM fraud_reviews WHERE status = 'open' AND due_at <= $1 ORDER BY due_at"
+        ))
+        .bind(now)
+        .fetch_all(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(reviews) => Ok(reviews),
+            Err(e) => anyhow::bail!("Failed to get_overdue_reviews: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn repo(pool: sqlx::PgPool) -> PgFraudRepository {
+        PgFraudRepository::new(common::database::Database::from_pool(pool).await.unwrap())
+    }
+
+    #[sqlx::test(migrations = "../migrations/fraud")]
+    async fn successfully_create_review_once_per_transaction(pool: sqlx::PgPool) {
+        // arrange
+        let repo = repo(pool).await;
+        let transaction_id = uuid::Uuid::new_v4();
+        let review = Review::new(transaction_id, "key", 0.6, "v1", chrono::Duration::hours(1));
+
+        // act
+        let created = repo.create_review(&review).await.unwrap();
+        let duplicate = repo
+            .create_review(&Review::new(
+                transaction_id,
+                "key",
+                0.6,
+                "v1",
+                chrono::Duration::hours(1),
+            ))
+            .await
+            .unwrap();
+
+        // assert
+        assert_eq!(created.id, review.id);
+        assert_eq!(duplicate.id, review.id);
+        assert_eq!(created.status, Status::Open);
+        let open = repo.get_reviews_by_status(Status::Open).await.unwrap();
+        assert_eq!(open.len(), 1);
+    }
+
+    #[sqlx::test(migrations = "../migrations/fraud")]
+    async fn successfully_decide_review_only_once(pool: sqlx::PgPool) {
+        // arrange
+        let repo = repo(pool).await;
+        let review = repo
+            .create_review(&Review::new(
+                uuid::Uuid::new_v4(),
+                "key",
+                0.6,
+                "v1",
+                chrono::Duration::hours(1),
+            ))
+            .await
+            .unwrap();
+
+        // act
+        let (decided, previous) = repo
+            .decide_review(review.id, Decision::Reject, "alice", Some("card testing"))
+            .await
+            .unwrap();
+        let second = repo
+            .decide_review(review.id, Decision::Approve, "bob", None)
+            .await;
+
+        // assert
+        assert_eq!(previous, Status::Open);
+        assert_eq!(decided.status, Status::Rejected);
+        assert_eq!(decided.decided_by.as_deref(), Some("alice"));
+        assert_eq!(decided.notes.as_deref(), Some("card testing"));
+        assert!(decided.decided_at.is_some());
+        let err = second.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ReviewError>(),
+            Some(&ReviewError::AlreadyDecided(review.id, Status::Rejected))
+        );
+    }
+
+    #[sqlx::test(migrations = "../migrations/fraud")]
+    async fn successfully_reopen_review_with_previous_status(pool: sqlx::PgPool) {
+        // arrange
+        let repo = repo(pool).await;
+        let review = repo
+            .create_review(&Review::new(
+                uuid::Uuid::new_v4(),
+                "key",
+                0.6,
+                "v1",
+                chrono::Duration::hours(1),
+            ))
+            .await
+            .unwrap();
+        repo.escalate_review(review.id).await.unwrap();
+        let (_, previous) = repo
+            .decide_review(review.id, Decision::Approve, "alice", None)
+            .await
+            .unwrap();
+
+        // act
+        repo.reopen_review(review.id, previous.clone())
+            .await
+            .unwrap();
+
+        // assert
+        let reopened = repo.get_review_by_id(review.id).await.unwrap();
+        assert_eq!(previous, Status::Escalated);
+        assert_eq!(reopened.status, Status::Escalated);
+        assert_eq!(reopened.decided_by, None);
+        assert_eq!(reopened.decided_at, None);
+    }
+
+    #[sqlx::test(migrations = "../migrations/fraud")]
+    async fn successfully_retrieve_overdue_reviews(pool: sqlx::PgPool) {
+        // arrange
+        let repo = repo(pool).await;
+        let overdue = repo
+            .create_review(&Review::new(
+                uuid::Uuid::new_v4(),
+                "key-1",
+                0.6,
+                "v1",
+                chrono::Duration::minutes(-5),
+            ))
+            .await
+            .unwrap();
+        repo.create_review(&Review::new(
+            uuid::Uuid::new_v4(),
+            "key-2",
+            0.6,
+            "v1",
+            chrono::Duration::hours(1),
+        ))
+        .await
+        .unwrap();
+
+        // act
+        let reviews = repo.get_overdue_reviews(chrono::Utc::now()).await.unwrap();
+
+        // assert
+        assert_eq!(reviews.len(), 1);
+        assert_eq!(reviews[0].id, overdue.id);
+
+        // escalated reviews are not overdue anymore
+        repo.escalate_review(overdue.id).await.unwrap();
+        assert!(
+            repo.get_overdue_reviews(chrono::Utc::now())
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[sqlx::test(migrations = "../migrations/fraud")]
+    async fn error_when_review_does_not_exist(pool: sqlx::PgPool) {
+        let repo = repo(pool).await;
+        let id = uuid::Uuid::new_v4();
+
+        let err = repo.get_review_by_id(id).await.unwrap_err();
+
+        assert_eq!(
+            err.downcast_ref::<ReviewError>(),
+            Some(&ReviewError::NotFound(id))
+        );
+    }
+}
@@ -0,0 +1,861 @@
+use crate::domain::evaluation::{Evaluation, Label, ModelReport, Role};
+use crate::domain::features::Features;
+use crate::domain::review::{Decision, Review, ReviewPolicy, SlaAction, Status};
+use crate::model::FraudModel;
+use crate::repo::FraudRepository;
+use common::kafka::{FRAUD_DETECTED_EVENTS_TOPIC, Publisher};
+use events_proto::events_v1;
+use prost::Message;
+use prost_types::Timestamp;
+use std::sync::Arc;
+
+/// Contender is a model together with the threshold above which its score flags fraud.
+#[derive(Clone)]
+pub struct Contender {
+    pub model: Arc<dyn FraudModel>,
+    pub threshold: f32,
+}
+
+impl Contender {
+    pub fn new(model: Arc<dyn FraudModel>, threshold: f32) -> Self {
+        Self { model, threshold }
+    }
+}
+
+/// Verdict is the outcome of checking a transaction. Scores in the grey zone are parked for a
+/// manual review and their fraud event is only published once the review is decided.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Published(events_v1::Fraud),
+    Parked(Review),
+}
+
+impl Verdict {
+    pub fn fraud(self) -> Option<events_v1::Fraud> {
+        match self {
+            Verdict::Published(fraud) => Some(fraud),
+            Verdict::Parked(_) => None,
+        }
+    }
+
+    pub fn review(self) -> Option<Review> {
+        match self {
+            Verdict::Published(_) => None,
+            Verdict::Parked(review) => Some(review),
+        }
+    }
+}
+
+pub struct FraudService<R, P>
+where
+    R: FraudRepository,
+    P: Publisher,
+{
+    champion: Contender,
+    challenger: Option<Contender>,
+    review_policy: Option<ReviewPolicy>,
+    repo: R,
+    publisher: P,
+}
+
+impl<R, P> FraudService<R, P>
+where
+    R: FraudRepository,
+    P: Publisher,
+{
+    pub fn new(champion: Contender, repo: R, publisher: P) -> Self {
+        Self {
+            champion,
+            challenger: None,
+            review_policy: None,
+            repo,
+            publisher,
+        }
+    }
+
+    /// with_challenger runs the challenger in shadow next to the champion. Its scores are only
+    /// written to the evaluation sink and never published.
+    pub fn with_challenger(mut self, challenger: Contender) -> Self {
+        self.challenger = Some(challenger);
+        self
+    }
+
+    /// with_review_policy parks transactions scoring between the review threshold and the
+    /// champion's threshold for a manual review.
+    pub fn with_review_policy(mut self, review_policy: ReviewPolicy) -> Self {
+        self.review_policy = Some(review_policy);
+        self
+    }
+
+    /// check_transaction scores the transaction with the champion, and the challenger if one is
+    /// configured, then publishes the champion's verdict to `fraud_detected_events`. When the
+    /// champion's score falls in the grey zone the transaction is parked for review instead.
+    pub async fn check_transaction(
+        &self,
+        transaction: &events_v1::Transaction,
+    ) -> anyhow::Result<Verdict> {
+        let transaction_id = match uuid::Uuid::parse_str(transaction.id.as_str()) {
+            Ok(transaction_id) => transaction_id,
+            Err(e) => anyhow::bail!("failed to parse transaction_id {}: {e}", transaction.id),
+        };
+        let features = Features::from_transaction(transaction)?;
+
+        let (champion_score, challenger_score) =
+            tokio::join!(self.champion.model.score(&features), async {
+                match &self.challenger {
+                    Some(challenger) => Some(challenger.model.score(&features).await),
+                    None => None,
+                }
+            });
+
+        let champion_score = match champion_score {
+            Ok(score) => score,
+            Err(e) => anyhow::bail!("failed to score transaction {}: {e}", transaction.id),
+        };
+
+        let mut evaluations = vec![Evaluation::new(
+            transaction_id,
+            Role::Champion,
+            champion_score.model_version.as_str(),
+            champion_score.value,
+            self.champion.threshold,
+        )];
+        match (&self.challenger, challenger_score) {
+            (Some(challenger), Some(Ok(score))) => evaluations.push(Evaluation::new(
+                transaction_id,
+                Role::Challenger,
+                score.model_version,
+                score.value,
+                challenger.threshold,
+            )),
+            (_, Some(Err(e))) => {
+                tracing::warn!(
+                    transaction_id = transaction.id,
+                    "challenger failed to score transaction: {e}"
+                )
+            }
+            _ => {}
+        }
+
+        // the evaluation sink must never hold back the champion's verdict
+        if let Err(e) = self.repo.record_evaluations(&evaluations).await {
+            tracing::warn!(
+                transaction_id = transaction.id,
+                "failed to record evaluations: {e}"
+            );
+        }
+
+        let champion = &evaluations[0];
+        match &self.review_policy {
+            Some(policy) if !champion.is_fraud && champion.score >= policy.review_threshold => {
+                let review = Review::new(
+                    transaction_id,
+                    transaction.idempotency_key.as_str(),
+                    champion.score,
+                    champion.model_version.as_str(),
+                    policy.sla,
+                );
+                return match self.repo.create_review(&review).await {
+                    Ok(review) => Ok(Verdict::Parked(review)),
+                    Err(e) => anyhow::bail!("failed to park transaction for review: {e}"),
+                };
+            }
+            _ => {}
+        }
+
+        let fraud = fraud_event(
+            transaction.id.as_str(),
+            transaction.idempotency_key.as_str(),
+            champion.is_fraud,
+            champion.score,
+            champion.is_fraud.then(|| {
+                format!(
+                    "score {:.4} is above threshold {:.4}",
+                    champion.score, champion.threshold
+                )
+            }),
+            champion.model_version.as_str(),
+        );
+        self.publish_fraud(&fraud).await?;
+
+        Ok(Verdict::Published(fraud))
+    }
+
+    async fn publish_fraud(&self, fraud: &events_v1::Fraud) -> anyhow::Result<()> {
+        match self
+            .publisher
+            .publish(
+                FRAUD_DETECTED_EVENTS_TOPIC,
+                fraud.transaction_id.as_str(),
+                fraud.encode_to_vec(),
+            )
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(e) => anyhow::bail!("failed to publish fraud event: {e}"),
+        }
+    }
+
+    pub async fn list_reviews(&self, status: Status) -> anyhow::Result<Vec<Review>> {
+        self.repo.get_reviews_by_status(status).await
+    }
+
+    pub async fn get_review(&self, id: uuid::Uuid) -> anyhow::Result<Review> {
+        self.repo.get_review_by_id(id).await
+    }
+
+    pub async fn approve_review(
+        &self,
+        id: uuid::Uuid,
+        reviewer: &str,
+        notes: Option<&str>,
+    ) -> anyhow::Result<Review> {
+        self.decide_review(id, Decision::Approve, reviewer, notes)
+            .await
+    }
+
+    pub async fn reject_review(
+        &self,
+        id: uuid::Uuid,
+        reviewer: &str,
+        notes: Option<&str>,
+    ) -> anyhow::Result<Review> {
+        self.decide_review(id, Decision::Reject, reviewer, notes)
+            .await
+    }
+
+    /// decide_review records the decision and publishes the final fraud event for the
+    /// transaction. If the event cannot be published the review is reopened in the status it
+    /// had, so it can be decided again.
+    async fn decide_review(
+        &self,
+        id: uuid::Uuid,
+        decision: Decision,
+        reviewer: &str,
+        notes: Option<&str>,
+    ) -> anyhow::Result<Review> {
+        let (review, previous) = self
+            .repo
+            .decide_review(id, decision, reviewer, notes)
+            .await?;
+
+        let details = match notes {
+            Some(notes) => format!("{} by {reviewer}: {notes}", review.status.as_ref()),
+            None => format!("{} by {reviewer}", review.status.as_ref()),
+        };
+        let fraud = fraud_event(
+            review.transaction_id.to_string().as_str(),
+            review.idempotency_key.as_str(),
+            decision.is_fraud(),
+            review.score,
+            Some(details),
+            review.model_version.as_str(),
+        );
+
+        if let Err(e) = self.publish_fraud(&fraud).await {
+            if let Err(reopen_err) = self.repo.reopen_review(id, previous).await {
+                tracing::error!(review_id = %id, "failed to reopen review: {reopen_err}");
+            }
+            return Err(e);
+        }
+
+        Ok(review)
+    }
+
+    /// sweep_overdue_reviews applies the SLA action to every open review past its due date and
+    /// returns how many reviews were swept. Decisions made on SLA breach are recorded as
+    /// decided by `sla`.
+    pub async fn sweep_overdue_reviews(&self) -> anyhow::Result<usize> {
+        let sla_action = match &self.review_policy {
+            Some(policy) => policy.sla_action,
+            None => SlaAction::Escalate,
+        };
+
+        let reviews = self.repo.get_overdue_reviews(chrono::Utc::now()).await?;
+
+        let mut swept = 0;
+        for review in reviews {
+            let result = match sla_action {
+                SlaAction::Escalate => self.repo.escalate_review(review.id).await,
+                SlaAction::Decide(decision) => self
+                    .decide_review(review.id, decision, "sla", Some("review SLA breached"))
+                    .await
+                    .map(|_| ()),
+            };
+            match result {
+                Ok(_) => swept += 1,
+                Err(e) => {
+                    tracing::warn!(review_id = %review.id, "failed to apply SLA action: {e}")
+                }
+            }
+        }
+
+        Ok(swept)
+    }
+
+    /// spawn_sla_sweeper sweeps overdue reviews on every tick of the interval.
+    pub fn spawn_sla_sweeper(
+        self: Arc<Self>,
+        interval: std::time::Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.sweep_overdue_reviews().await {
+                    tracing::error!("failed to sweep overdue reviews: {e}");
+                }
+            }
+        })
+    }
+
+    /// record_refund_label turns a refund into a label. Fraudulent refunds and chargebacks are
+    /// positive labels, refunds for any other reason are negative labels.
+    pub async fn record_refund_label(&self, refund: &events_v1::Refund) -> anyhow::Result<Label> {
+        let transaction_id = match uuid::Uuid::parse_str(refund.transaction_id.as_str()) {
+            Ok(transaction_id) => transaction_id,
+            Err(e) => anyhow::bail!(
+                "failed to parse transaction_id {}: {e}",
+                refund.transaction_id
+            ),
+        };
+        let reason = events_v1::RefundReason::try_from(refund.reason)
+            .unwrap_or(events_v1::RefundReason::Unspecified);
+
+        let label = Label {
+            transaction_id,
+            is_fraud: matches!(
+                reason,
+                events_v1::RefundReason::Fraudulent | events_v1::RefundReason::Chargeback
+            ),
+            source: format!("refund:{}", reason.as_str_name().to_lowercase()),
+            created_at: chrono::Utc::now(),
+        };
+
+        match self.repo.record_label(&label).await {
+            Ok(_) => Ok(label),
+            Err(e) => anyhow::bail!("Failed to record_refund_label: {:?}", e),
+        }
+    }
+
+    /// evaluation_report compares the verdicts of every model which scored transactions in the
+    /// window against the labels collected so far.
+    pub async fn evaluation_report(
+        &self,
+        from: chrono::DateTime<chrono::Utc>,
+        to: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<Vec<ModelReport>> {
+        match self.repo.get_labelled_evaluations(from, to).await {
+            Ok(evaluations) => Ok(ModelReport::from_labelled(&evaluations)),
+            Err(e) => anyhow::bail!("Failed to get_labelled_evaluations: {:?}", e),
+        }
+    }
+}
+
+fn fraud_event(
+    transaction_id: &str,
+    idempotency_key: &str,
+    is_fraud: bool,
+    score: f32,
+    details: Option<String>,
+    model_version: &str,
+) -> events_v1::Fraud {
+    let now = chrono::Utc::now();
+    events_v1::Fraud {
+        transaction_id: transaction_id.to_string(),
+        idempotency_key: idempotency_key.to_string(),
+        is_fraud,
+        score,
+        details,
+        created_at: Some(Timestamp {
+            seconds: now.timestamp(),
+            nanos: now.timestamp_subsec_nanos() as i32,
+        }),
+        model_version: model_version.to_string(),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::domain::review::ReviewError;
+    use crate::model::Score;
+    use crate::repo::{EvaluationReader, PgFraudRepository};
+    use async_trait::async_trait;
+    use events_proto::events_v1::google::r#type::Money;
+    use std::sync::Mutex;
+
+    pub(crate) struct FixedModel(pub f32);
+
+    #[async_trait]
+    impl FraudModel for FixedModel {
+        async fn score(&self, _features: &Features) -> anyhow::Result<Score> {
+            Ok(Score {
+                value: self.0,
+                model_version: format!("fixed-{}", self.0),
+            })
+        }
+    }
+
+    struct FailingModel;
+
+    #[async_trait]
+    impl FraudModel for FailingModel {
+        async fn score(&self, _features: &Features) -> anyhow::Result<Score> {
+            anyhow::bail!("model unavailable")
+        }
+    }
+
+    #[derive(Default)]
+    pub(crate) struct InMemoryPublisher {
+        pub messages: Mutex<Vec<(String, String, Vec<u8>)>>,
+    }
+
+    #[async_trait]
+    impl Publisher for InMemoryPublisher {
+        async fn publish(&self, topic: &str, key: &str, payload: Vec<u8>) -> anyhow::Result<()> {
+            self.messages
+                .lock()
+                .unwrap()
+                .push((topic.to_string(), key.to_string(), payload));
+            Ok(())
+        }
+    }
+
+    pub(crate) fn contender(score: f32, threshold: f32) -> Contender {
+        Contender::new(Arc::new(FixedModel(score)), threshold)
+    }
+
+    pub(crate) fn transaction() -> events_v1::Transaction {
+        events_v1::Transaction {
+            id: uuid::Uuid::new_v4().to_string(),
+            idempotency_key: "key".to_string(),
+            debit_account_id: "debit".to_string(),
+            credit_account_id: "credit".to_string(),
+            amount: Some(Money {
+                currency_code: "USD".to_string(),
+                units: 100,
+                nanos: 0,
+            }),
+            status: events_v1::TransactionStatus::Init as i32,
+            request_timestamp: Some(Timestamp {
+                seconds: 1758409200,
+                nanos: 0,
+            }),
+            created_at: None,
+        }
+    }
+
+    async fn repo(pool: sqlx::PgPool) -> PgFraudRepository {
+        PgFraudRepository::new(common::database::Database::from_pool(pool).await.unwrap())
+    }
+
+    #[sqlx::test(migrations = "../migrations/fraud")]
+    async fn successfully_flag_transaction_above_threshold(pool: sqlx::PgPool) {
+        // arrange
+        let service = FraudService::new(
+            contender(0.9, 0.8),
+            repo(pool).await,
+            InMemoryPublisher::default(),
+        );
+
+        // act
+        let fraud = service
+            .check_transaction(&transaction())
+            .await
+            .unwrap()
+            .fraud()
+            .unwrap();
+
+        // assert
+        assert!(fraud.is_fraud);
+        assert_eq!(fraud.model_version, "fixed-0.9");
+        assert!(fraud.details.is_some());
+
+        let messages = service.publisher.messages.lock().unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].0, FRAUD_DETECTED_EVENTS_TOPIC);
+        let published = events_v1::Fraud::decode(messages[0].2.as_slice()).unwrap();
+        assert_eq!(published, fraud);
+    }
+
+    #[sqlx::test(migrations = "../migrations/fraud")]
+    async fn successfully_pass_transaction_below_threshold(pool: sqlx::PgPool) {
+        // arrange
+        let service = FraudService::new(
+            contender(0.1, 0.8),
+            repo(pool).await,
+            InMemoryPublisher::default(),
+        );
+
+        // act
+        let fraud = service
+            .check_transaction(&transaction())
+            .await
+            .unwrap()
+            .fraud()
+            .unwrap();
+
+        // assert
+        assert!(!fraud.is_fraud);
+        assert_eq!(fraud.score, 0.1);
+        assert!(fraud.details.is_none());
+    }
+
+    #[sqlx::test(migrations = "../migrations/fraud")]
+    async fn successfully_publish_champion_verdict_and_record_both_scores(pool: sqlx::PgPool) {
+        // arrange
+        let service = FraudService::new(
+            contender(0.1, 0.8),
+            repo(pool).await,
+            InMemoryPublisher::default(),
+        )
+        .with_challenger(contender(0.95, 0.8));
+        let transaction = transaction();
+
+        // act
+        let fraud = service
+            .check_transaction(&transaction)
+            .await
+            .unwrap()
+            .fraud()
+            .unwrap();
+
+        // assert - only the champion verdict is published
+        assert!(!fraud.is_fraud);
+        assert_eq!(service.publisher.messages.lock().unwrap().len(), 1);
+
+        let evaluations = service
+            .repo
+            .get_evaluations_by_transaction_id(uuid::Uuid::parse_str(&transaction.id).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(evaluations.len(), 2);
+        assert_eq!(evaluations[0].role, Role::Champion);
+        assert!(!evaluations[0].is_fraud);
+        assert_eq!(evaluations[1].role, Role::Challenger);
+        assert!(evaluations[1].is_fraud);
+    }
+
+    #[sqlx::test(migrations = "../migrations/fraud")]
+    async fn successfully_record_evaluations_once_when_transaction_is_redelivered(
+        pool: sqlx::PgPool,
+    ) {
+        // arrange
+        let service = FraudService::new(
+            contender(0.1, 0.8),
+            repo(pool).await,
+            InMemoryPublisher::default(),
+        )
+        .with_challenger(contender(0.95, 0.8));
+        let transaction = transaction();
+        service.check_transaction(&transaction).await.unwrap();
+
+        // act
+        let resp = service.check_transaction(&transaction).await;
+
+        // assert
+        assert!(resp.is_ok());
+
+        let evaluations = service
+            .repo
+            .get_evaluations_by_transaction_id(uuid::Uuid::parse_str(&transaction.id).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(evaluations.len(), 2);
+        assert_eq!(evaluations[0].role, Role::Champion);
+        assert_eq!(evaluations[1].role, Role::Challenger);
+    }
+
+    #[sqlx::test(migrations = "../migrations/fraud")]
+    async fn successfully_publish_champion_verdict_when_challenger_fails(pool: sqlx::PgPool) {
+        // arrange
+        let service = FraudService::new(
+            contender(0.9, 0.8),
+            repo(pool).await,
+            InMemoryPublisher::default(),
+        )
+        .with_challenger(Contender::new(Arc::new(FailingModel), 0.8));
+
+        // act
+        let fraud = service.check_transaction(&transaction()).await;
+
+        // assert
+        assert!(fraud.unwrap().fraud().unwrap().is_fraud);
+        assert_eq!(service.publisher.messages.lock().unwrap().len(), 1);
+    }
+
+    #[sqlx::test(migrations = "../migrations/fraud")]
+    async fn successfully_report_precision_and_recall_from_refund_labels(pool: sqlx::PgPool) {
+        // arrange - champion flags nothing, challenger flags everything
+        let service = FraudService::new(
+            contender(0.1, 0.8),
+            repo(pool).await,
+            InMemoryPublisher::default(),
+        )
+        .with_challenger(contender(0.9, 0.8));
+        let fraudulent = transaction();
+        let legitimate = transaction();
+        service.check_transaction(&fraudulent).await.unwrap();
+        service.check_transaction(&legitimate).await.unwrap();
+
+        // act
+        service
+            .record_refund_label(&events_v1::Refund {
+                id: uuid::Uuid::new_v4().to_string(),
+                transaction_id: fraudulent.id.clone(),
+                reason: events_v1::RefundReason::Chargeback as i32,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let reports = service
+            .evaluation_report(
+                chrono::Utc::now() - chrono::Duration::hours(1),
+                chrono::Utc::now() + chrono::Duration::hours(1),
+            )
+            .await
+            .unwrap();
+
+        // assert
+        assert_eq!(reports.len(), 2);
+        let champion = reports.iter().find(|r| r.role == Role::Champion).unwrap();
+        assert_eq!(champion.precision(), None);
+        assert_eq!(champion.recall(), Some(0.0));
+        let challenger = reports.iter().find(|r| r.role == Role::Challenger).unwrap();
+        assert_eq!(challenger.precision(), Some(0.5));
+        assert_eq!(challenger.recall(), Some(1.0));
+    }
+
+    fn review_policy(sla: chrono::Duration, sla_action: SlaAction) -> ReviewPolicy {
+        ReviewPolicy {
+            review_threshold: 0.5,
+            sla,
+            sla_action,
+        }
+    }
+
+    #[sqlx::test(migrations = "../migrations/fraud")]
+    async fn successfully_park_transaction_in_grey_zone(pool: sqlx::PgPool) {
+        // arrange
+        let service = FraudService::new(
+            contender(0.6, 0.8),
+            repo(pool).await,
+            InMemoryPublisher::default(),
+        )
+        .with_review_policy(review_policy(
+            chrono::Duration::hours(1),
+            SlaAction::Escalate,
+        ));
+        let transaction = transaction();
+
+        // act
+        let review = service
+            .check_transaction(&transaction)
+            .await
+            .unwrap()
+            .review()
+            .unwrap();
+
+        // assert - nothing is published until the review is decided
+        assert_eq!(review.status, Status::Open);
+        assert_eq!(review.transaction_id.to_string(), transaction.id);
+        assert_eq!(review.score, 0.6);
+        assert!(service.publisher.messages.lock().unwrap().is_empty());
+    }
+
+    #[sqlx::test(migrations = "../migrations/fraud")]
+    async fn successfully_publish_verdict_outside_grey_zone(pool: sqlx::PgPool) {
+        // arrange
+        let service = FraudService::new(
+            contender(0.9, 0.8),
+            repo(pool).await,
+            InMemoryPublisher::default(),
+        )
+        .with_review_policy(review_policy(
+            chrono::Duration::hours(1),
+            SlaAction::Escalate,
+        ));
+
+        // act
+        let verdict = service.check_transaction(&transaction()).await.unwrap();
+
+        // assert
+        assert!(verdict.fraud().unwrap().is_fraud);
+        assert_eq!(service.publisher.messages.lock().unwrap().len(), 1);
+    }
+
+    #[sqlx::test(migrations = "../migrations/fraud")]
+    async fn successfully_publish_final_verdict_when_review_is_decided(pool: sqlx::PgPool) {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            decision: Decision,
+            expected_status: Status,
+            expected_is_fraud: bool,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "approved review publishes a legitimate verdict",
+                decision: Decision::Approve,
+                expected_status: Status::Approved,
+                expected_is_fraud: false,
+            },
+            TestCase {
+                name: "rejected review publishes a fraud verdict",
+                decision: Decision::Reject,
+                expected_status: Status::Rejected,
+                expected_is_fraud: true,
+            },
+        ];
+
+        let service = FraudService::new(
+            contender(0.6, 0.8),
+            repo(pool).await,
+            InMemoryPublisher::default(),
+        )
+        .with_review_policy(review_policy(
+            chrono::Duration::hours(1),
+            SlaAction::Escalate,
+        ));
+
+        for test_case in test_cases {
+            // arrange
+            service.publisher.messages.lock().unwrap().clear();
+            let transaction = transaction();
+            let review = service
+                .check_transaction(&transaction)
+                .await
+                .unwrap()
+                .review()
+                .unwrap();
+
+            // act
+            let decided = match test_case.decision {
+                Decision::Approve => service.approve_review(review.id, "alice", None).await,
+                Decision::Reject => {
+                    service
+                        .reject_review(review.id, "alice", Some("card testing"))
+                        .await
+                }
+            }
+            .unwrap();
+
+            // assert
+            assert_eq!(
+                decided.status, test_case.expected_status,
+                "{}",
+                test_case.name
+            );
+            let messages = service.publisher.messages.lock().unwrap();
+            assert_eq!(messages.len(), 1, "{}", test_case.name);
+            let fraud = events_v1::Fraud::decode(messages[0].2.as_slice()).unwrap();
+            assert_eq!(fraud.transaction_id, transaction.id, "{}", test_case.name);
+            assert_eq!(
+                fraud.is_fraud, test_case.expected_is_fraud,
+                "{}",
+                test_case.name
+            );
+            assert_eq!(fraud.score, 0.6, "{}", test_case.name);
+        }
+    }
+
+    #[sqlx::test(migrations = "../migrations/fraud")]
+    async fn error_when_review_is_decided_twice(pool: sqlx::PgPool) {
+        // arrange
+        let service = FraudService::new(
+            contender(0.6, 0.8),
+            repo(pool).await,
+            InMemoryPublisher::default(),
+        )
+        .with_review_policy(review_policy(
+            chrono::Duration::hours(1),
+            SlaAction::Escalate,
+        ));
+        let review = service
+            .check_transaction(&transaction())
+            .await
+            .unwrap()
+            .review()
+            .unwrap();
+        service
+            .approve_review(review.id, "alice", None)
+            .await
+            .unwrap();
+
+        // act
+        let result = service.reject_review(review.id, "bob", None).await;
+
+        // assert
+        assert_eq!(
+            result.unwrap_err().downcast_ref::<ReviewError>(),
+            Some(&ReviewError::AlreadyDecided(review.id, Status::Approved))
+        );
+        assert_eq!(service.publisher.messages.lock().unwrap().len(), 1);
+    }
+
+    #[sqlx::test(migrations = "../migrations/fraud")]
+    async fn successfully_apply_sla_action_to_overdue_reviews(pool: sqlx::PgPool) {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            sla_action: SlaAction,
+            expected_status: Status,
+            expected_messages: usize,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "escalate overdue review without publishing",
+                sla_action: SlaAction::Escalate,
+                expected_status: Status::Escalated,
+                expected_messages: 0,
+            },
+            TestCase {
+                name: "reject overdue review and publish fraud verdict",
+                sla_action: SlaAction::Decide(Decision::Reject),
+                expected_status: Status::Rejected,
+                expected_messages: 1,
+            },
+        ];
+
+        let db = common::database::Database::from_pool(pool).await.unwrap();
+        for test_case in test_cases {
+            // arrange
+            let service = FraudService::new(
+                contender(0.6, 0.8),
+                PgFraudRepository::new(db.clone()),
+                InMemoryPublisher::default(),
+            )
+            .with_review_policy(review_policy(
+                chrono::Duration::minutes(-1),
+                test_case.sla_action,
+            ));
+            let review = service
+                .check_transaction(&transaction())
+                .await
+                .unwrap()
+                .review()
+                .unwrap();
+
+            // act
+            let swept = service.sweep_overdue_reviews().await.unwrap();
+
+            // assert
+            assert_eq!(swept, 1, "{}", test_case.name);
+            let review = service.get_review(review.id).await.unwrap();
+            assert_eq!(
+                review.status, test_case.expected_status,
+                "{}",
+                test_case.name
+            );
+            assert_eq!(
+                service.publisher.messages.lock().unwrap().len(),
+                test_case.expected_messages,
+                "{}",
+                test_case.name
+            );
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use crate::service::CorrectionService;
+use async_trait::async_trait;
+use common::kafka::{ACCOUNTS_EVENTS_TOPIC, MessageHandler, RECONCILIATION_EVENTS_TOPIC};
+use events_proto::events_v1;
+use ledger::repo::LedgerRepository;
+use prost::Message;
+
+#[async_trait]
+impl<R> MessageHandler for CorrectionService<R>
+where
+    R: LedgerRepository,
+{
+    async fn handle(&self, topic: &str, payload: &[u8]) -> anyhow::Result<()> {
+        match topic {
+            RECONCILIATION_EVENTS_TOPIC => {
+                let reconciliation = match events_v1::Reconciliation::decode(payload) {
+                    Ok(reconciliation) => reconciliation,
+                    Err(e) => anyhow::bail!("failed to decode reconciliation event: {e}"),
+                };
+
+                if let Some(status) = self.apply_reconciliation(&reconciliation).await? {
+                    tracing::info!(
+                        transaction_id = reconciliation.transaction_id,
+                        exception_id = reconciliation.exception_id,
+                        status = status.as_ref(),
+                        "applied reconciliation correction"
+                    );
+                }
+            }
+            ACCOUNTS_EVENTS_TOPIC => {
+                let account = match events_v1::Account::decode(payload) {
+                    Ok(account) => account,
+                    Err(e) => anyhow::bail!("failed to decode account event: {e}"),
+                };
+
+                self.register_account(&account).await?;
+                tracing::info!(account_id = account.id, "registered account");
+            }
+            _ => anyhow::bail!("unexpected topic {topic}"),
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::tests::{account_event, correction_event};
+    use ledger::domain::transaction::Status;
+    use ledger::repo::{LedgerReader, PgLedgerRepository};
+    use ledger::test_util::seed_transaction;
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_handle_reconciliation_event(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let transaction = seed_transaction(&repo, 1000, Status::Pending).await;
+        let service = CorrectionService::new(repo.clone());
+
+        // act
+        let result = service
+            .handle(
+                RECONCILIATION_EVENTS_TOPIC,
+                &correction_event(transaction.id, events_v1::Status::Success).encode_to_vec(),
+            )
+            .await;
+
+        // assert
+        assert!(result.is_ok());
+        let transaction = repo.get_transaction_by_id(transaction.id).await.unwrap();
+        assert_eq!(transaction.status, Status::Success);
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_handle_account_event(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let service = CorrectionService::new(repo);
+
+        // act
+        let result = service
+            .handle(
+                ACCOUNTS_EVENTS_TOPIC,
+                &account_event(uuid::Uuid::new_v4(), events_v1::AccountType::System)
+                    .encode_to_vec(),
+            )
+            .await;
+
+        // assert
+        assert!(result.is_ok());
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn error_when_payload_is_not_a_reconciliation(pool: sqlx::PgPool) {
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let service = CorrectionService::new(repo);
+
+        assert!(
+            service
+                .handle(RECONCILIATION_EVENTS_TOPIC, b"\xff\xff")
+                .await
+                .is_err()
+        );
+    }
+}
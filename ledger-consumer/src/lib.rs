@@ -0,0 +1,6 @@
+pub mod consumer;
+pub mod service;
+
+pub const DEFAULT_WRITER_MAX_CONN: u32 = 4;
+pub const DEFAULT_READER_MAX_CONN: u32 = 2;
+pub const DEFAULT_TIMEOUT_SECONDS: u64 = 5;
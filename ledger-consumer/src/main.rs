@@ -1,3 +1,53 @@
-fn main() {
-    println!("Hello, world!");
+use common::{database, kafka, shutdown};
+use ledger::repo::PgLedgerRepository;
+use ledger_consumer::service::CorrectionService;
+use ledger_consumer::{DEFAULT_READER_MAX_CONN, DEFAULT_TIMEOUT_SECONDS, DEFAULT_WRITER_MAX_CONN};
+use std::env;
+use std::sync::Arc;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    // setup ledger database
+    let database_config = database::Config {
+        reader_url: env::var("READER_DATABASE_URL").expect("READER_DATABASE_URL must be set"),
+        reader_max_connections: DEFAULT_READER_MAX_CONN,
+        writer_url: env::var("WRITER_DATABASE_URL").expect("WRITER_DATABASE_URL must be set"),
+        writer_max_connections: DEFAULT_WRITER_MAX_CONN,
+        timeout_in_secs: DEFAULT_TIMEOUT_SECONDS,
+    };
+    let db = database::Database::new(&database_config)
+        .await
+        .expect("failed to create database");
+
+    // setup repo layer
+    let repo = PgLedgerRepository::new(db);
+
+    // setup kafka
+    let kafka_config = kafka::Config {
+        brokers: env::var("KAFKA_BROKERS").expect("KAFKA_BROKERS must be set"),
+        group_id: env::var("KAFKA_GROUP_ID").unwrap_or("ledger-consumer".to_string()),
+    };
+    let dead_letters = kafka::DeadLetterQueue::new(&kafka_config)?;
+    let retry_topic = kafka::retry_topic(kafka_config.group_id.as_str());
+    let consumer = kafka::new_consumer(
+        &kafka_config,
+        &[
+            kafka::ACCOUNTS_EVENTS_TOPIC,
+            kafka::RECONCILIATION_EVENTS_TOPIC,
+            retry_topic.as_str(),
+        ],
+    )?;
+
+    // setup service
+    let service = CorrectionService::new(repo);
+
+    kafka::consume_with_dead_letters(
+        consumer,
+        Arc::new(service),
+        dead_letters,
+        shutdown::shutdown_signal(),
+    )
+    .await
 }
@@ -0,0 +1,280 @@
+use events_proto::events_v1;
+use ledger::domain::transaction::Status;
+use ledger::repo::LedgerRepository;
+
+/// PENDING_STATUSES are the only statuses a correction moves a transaction out of, so a
+/// transaction which settled in the meantime is left alone.
+const PENDING_STATUSES: [Status; 2] = [Status::Init, Status::Pending];
+
+pub struct CorrectionService<R>
+where
+    R: LedgerRepository,
+{
+    repo: R,
+}
+
+impl<R> CorrectionService<R>
+where
+    R: LedgerRepository,
+{
+    pub fn new(repo: R) -> Self {
+        Self { repo }
+    }
+
+    /// apply_reconciliation moves the ledger transaction of a corrective reconciliation event to
+    /// the external status and returns it. Matches and mismatches are ignored, as is a
+    /// correction for a transaction which is no longer pending, so redelivered events are safe.
+    pub async fn apply_reconciliation(
+        &self,
+        event: &events_v1::Reconciliation,
+    ) -> anyhow::Result<Option<Status>> {
+        if event.result() != events_v1::ReconciliationResult::ResultCorrection {
+            return Ok(None);
+        }
+
+        let transaction_id = match uuid::Uuid::parse_str(event.transaction_id.as_str()) {
+            Ok(transaction_id) => transaction_id,
+            Err(e) => anyhow::bail!(
+                "failed to parse transaction_id {}: {e}",
+                event.transaction_id
+            ),
+        };
+        let status = match event.external_status() {
+            events_v1::Status::Success => Status::Success,
+            events_v1::Status::Failed => Status::Failed,
+            other => anyhow::bail!(
+                "cannot correct transaction {transaction_id} to {}",
+                other.as_str_name()
+            ),
+        };
+
+        match self
+            .repo
+            .transition_transaction_status(transaction_id, &PENDING_STATUSES, status.clone())
+            .await?
+        {
+            true => Ok(Some(status)),
+            false => {
+                tracing::info!(
+                    %transaction_id,
+                    exception_id = event.exception_id,
+                    "transaction is no longer pending, skipping correction"
+                );
+                Ok(None)
+            }
+        }
+    }
+
+    /// register_account registers the account of an account event with the ledger, so
+    /// transactions can move money on it. Registering is idempotent, so status updates and
+    /// redelivered events are safe.
+    pub async fn register_account(&self, event: &events_v1::Account) -> anyhow::Result<()> {
+        let account_id = match uuid::Uuid::parse_str(event.id.as_str()) {
+            Ok(account_id) => account_id,
+            Err(e) => anyhow::bail!("failed to parse account id {}: {e}", event.id),
+        };
+        let account_type = match event.r#type() {
+            events_v1::AccountType::Customer => "CUSTOMER",
+            events_v1::AccountType::Merchant => "MERCHANT",
+            events_v1::AccountType::System => "SYSTEM",
+            events_v1::AccountType::Unspecified => {
+                anyhow::bail!("account {account_id} has no type")
+            }
+        };
+
+        self.repo.create_account(account_id, account_type).await
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use ledger::domain::transaction::Transaction;
+    use ledger::repo::{LedgerReader, LedgerWriter, PgLedgerRepository};
+    use ledger::test_util::seed_transaction;
+
+    pub(crate) fn account_event(
+        id: uuid::Uuid,
+        account_type: events_v1::AccountType,
+    ) -> events_v1::Account {
+        events_v1::Account {
+            id: id.to_string(),
+            name: "test account".to_string(),
+            r#type: account_type as i32,
+            status: events_v1::AccountStatus::Active as i32,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn correction_event(
+        transaction_id: uuid::Uuid,
+        external_status: events_v1::Status,
+    ) -> events_v1::Reconciliation {
+        events_v1::Reconciliation {
+            id: uuid::Uuid::new_v4().to_string(),
+            transaction_id: transaction_id.to_string(),
+            ledger_status: events_v1::Status::Pending as i32,
+            external_status: external_status as i32,
+            result: events_v1::ReconciliationResult::ResultCorrection as i32,
+            exception_id: uuid::Uuid::new_v4().to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn test_apply_reconciliation(pool: sqlx::PgPool) {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            status: Status,
+            result: events_v1::ReconciliationResult,
+            external_status: events_v1::Status,
+            expected: Option<Status>,
+            expected_status: Status,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "successfully settle pending transaction",
+                status: Status::Pending,
+                result: events_v1::ReconciliationResult::ResultCorrection,
+                external_status: events_v1::Status::Success,
+                expected: Some(Status::Success),
+                expected_status: Status::Success,
+            },
+            TestCase {
+                name: "successfully fail initialised transaction",
+                status: Status::Init,
+                result: events_v1::ReconciliationResult::ResultCorrection,
+                external_status: events_v1::Status::Failed,
+                expected: Some(Status::Failed),
+                expected_status: Status::Failed,
+            },
+            TestCase {
+                name: "skip transaction which is no longer pending",
+                status: Status::Refunded,
+                result: events_v1::ReconciliationResult::ResultCorrection,
+                external_status: events_v1::Status::Success,
+                expected: None,
+                expected_status: Status::Refunded,
+            },
+            TestCase {
+                name: "ignore mismatches",
+                status: Status::Pending,
+                result: events_v1::ReconciliationResult::ResultMismatch,
+                external_status: events_v1::Status::Success,
+                expected: None,
+                expected_status: Status::Pending,
+            },
+        ];
+
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let service = CorrectionService::new(repo.clone());
+
+        for test_case in test_cases {
+            // arrange
+            let transaction = seed_transaction(&repo, 1000, test_case.status).await;
+            let event = events_v1::Reconciliation {
+                result: test_case.result as i32,
+                ..correction_event(transaction.id, test_case.external_status)
+            };
+
+            // act
+            let applied = service.apply_reconciliation(&event).await.unwrap();
+
+            // assert
+            assert_eq!(applied, test_case.expected, "{}", test_case.name);
+            let transaction = repo.get_transaction_by_id(transaction.id).await.unwrap();
+            assert_eq!(
+                transaction.status, test_case.expected_status,
+                "{}",
+                test_case.name
+            );
+        }
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn error_when_correction_is_not_final(pool: sqlx::PgPool) {
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let transaction = seed_transaction(&repo, 1000, Status::Init).await;
+        let service = CorrectionService::new(repo);
+
+        assert!(
+            service
+                .apply_reconciliation(&correction_event(
+                    transaction.id,
+                    events_v1::Status::Pending
+                ))
+                .await
+                .is_err()
+        );
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn test_register_account(pool: sqlx::PgPool) {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            event: events_v1::Account,
+            expected_ok: bool,
+        }
+
+        // arrange
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let service = CorrectionService::new(repo.clone());
+        let id = uuid::Uuid::new_v4();
+
+        let test_cases = vec![
+            TestCase {
+                name: "successfully register an account",
+                event: account_event(id, events_v1::AccountType::Customer),
+                expected_ok: true,
+            },
+            TestCase {
+                name: "successfully register an account twice",
+                event: account_event(id, events_v1::AccountType::Customer),
+                expected_ok: true,
+            },
+            TestCase {
+                name: "error when the account has no type",
+                event: account_event(uuid::Uuid::new_v4(), events_v1::AccountType::Unspecified),
+                expected_ok: false,
+            },
+            TestCase {
+                name: "error when the account id is invalid",
+                event: events_v1::Account {
+                    id: "invalid".to_string(),
+                    ..account_event(id, events_v1::AccountType::Merchant)
+                },
+                expected_ok: false,
+            },
+        ];
+
+        for test_case in test_cases {
+            // act
+            let result = service.register_account(&test_case.event).await;
+
+            // assert
+            assert_eq!(result.is_ok(), test_case.expected_ok, "{}", test_case.name);
+        }
+        let counterparty = uuid::Uuid::new_v4();
+        repo.create_account(counterparty, "MERCHANT").await.unwrap();
+        let transaction = repo
+            .create_transaction(
+                &Transaction::new(
+                    id,
+                    counterparty,
+                    1000,
+                    "USD",
+                    uuid::Uuid::new_v4().to_string(),
+                    chrono::Utc::now(),
+                ),
+                None,
+            )
+            .await;
+        assert!(transaction.is_ok());
+    }
+}
@@ -1,25 +1,703 @@
+mod parsers;
+
+use crate::DEFAULT_HOLD_TTL_SECONDS;
+use crate::api::parsers::{
+    parse_balance_to_proto, parse_details_to_proto, parse_error_to_status,
+    parse_fee_schedule_to_proto, parse_hold_to_proto, parse_journal_entry_to_proto,
+    parse_refund_to_proto, parse_schedule_run_to_proto, parse_schedule_to_proto,
+    parse_status_count_to_proto, parse_to_domain_amount, parse_to_domain_legs,
+    parse_to_domain_refund_reason, parse_to_domain_tiers, parse_to_domain_timestamp,
+    parse_to_domain_transaction_status, parse_to_uuid, parse_transaction_status_to_proto,
+    parse_transaction_to_proto,
+};
+use crate::domain::fee::FeeSchedule;
+use crate::domain::hold::Hold;
+use crate::domain::journal::JournalEntry;
+use crate::domain::money::Currency;
+use crate::domain::schedule::Schedule;
+use crate::domain::transaction::Transaction;
 use crate::repo::LedgerRepository;
 use crate::service::LedgerService;
 use async_trait::async_trait;
+use common::kafka::Publisher;
+use ledger_proto::ledger_v1;
 use ledger_proto::ledger_v1::ledger_server::Ledger;
 use ledger_proto::ledger_v1::{CreateTransactionRequest, CreateTransactionResponse};
 
 #[async_trait]
-impl<R> Ledger for LedgerService<R>
+impl<R, P> Ledger for LedgerService<R, P>
 where
     R: LedgerRepository,
+    P: Publisher,
 {
     async fn health_check(
         &self,
-        request: tonic::Request<()>,
+        _request: tonic::Request<()>,
     ) -> Result<tonic::Response<()>, tonic::Status> {
-        todo!()
+        Ok(tonic::Response::new(()))
     }
 
     async fn create_transaction(
         &self,
         request: tonic::Request<CreateTransactionRequest>,
     ) -> Result<tonic::Response<CreateTransactionResponse>, tonic::Status> {
-        todo!()
+        let request = request.into_inner();
+
+        let debit_account_id =
+            parse_to_uuid("debit_account_id", request.debit_account_id.as_str())?;
+        let credit_account_id =
+            parse_to_uuid("credit_account_id", request.credit_account_id.as_str())?;
+        let (amount_minor, currency) = match parse_to_domain_amount(request.amount) {
+            Ok(amount) => amount,
+            Err(e) => return Err(tonic::Status::invalid_argument(e.to_string())),
+        };
+        let request_timestamp = match parse_to_domain_timestamp(request.request_timestamp) {
+            Ok(request_timestamp) => request_timestamp,
+            Err(e) => return Err(tonic::Status::invalid_argument(e.to_string())),
+        };
+        if request.idempotency_key.is_empty() {
+            return Err(tonic::Status::invalid_argument(
+                "idempotency_key must be set",
+            ));
+        }
+
+        let transaction = Transaction::new(
+            debit_account_id,
+            credit_account_id,
+            amount_minor,
+            currency,
+            request.idempotency_key,
+            request_timestamp,
+        );
+        let result = match request.credit_currency.is_empty() {
+            true => LedgerService::create_transaction(self, &transaction).await,
+            false => match Currency::from_code(request.credit_currency.as_str()) {
+                Ok(credit_currency) => {
+                    LedgerService::create_fx_transaction(self, &transaction, credit_currency.code())
+                        .await
+                }
+                Err(e) => {
+                    return Err(tonic::Status::invalid_argument(format!(
+                        "invalid credit_currency: {e}"
+                    )));
+                }
+            },
+        };
+        let transaction = match result {
+            Ok(transaction) => transaction,
+            Err(e) => return Err(parse_error_to_status(e, "failed to create transaction")),
+        };
+
+        Ok(tonic::Response::new(CreateTransactionResponse {
+            transaction_id: transaction.id.to_string(),
+            transaction_status: parse_transaction_status_to_proto(&transaction.status),
+        }))
+    }
+
+    async fn get_transaction(
+        &self,
+        request: tonic::Request<ledger_v1::GetTransactionRequest>,
+    ) -> Result<tonic::Response<ledger_v1::GetTransactionResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let id = parse_to_uuid("transaction_id", request.transaction_id.as_str())?;
+
+        let transaction = match LedgerService::get_transaction(self, id).await {
+            Ok(transaction) => parse_transaction_to_proto(transaction)?,
+            Err(e) => return Err(parse_error_to_status(e, "failed to get transaction")),
+        };
+
+        Ok(tonic::Response::new(ledger_v1::GetTransactionResponse {
+            transaction: Some(transaction),
+        }))
+    }
+
+    async fn list_transactions(
+        &self,
+        request: tonic::Request<ledger_v1::ListTransactionsRequest>,
+    ) -> Result<tonic::Response<ledger_v1::ListTransactionsResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let account_id = match request.account_id.is_empty() {
+            true => None,
+            false => Some(parse_to_uuid("account_id", request.account_id.as_str())?),
+        };
+        let status = match parse_to_domain_transaction_status(request.status) {
+            Ok(status) => status,
+            Err(e) => return Err(tonic::Status::invalid_argument(e.to_string())),
+        };
+
+        let transactions =
+            match LedgerService::list_transactions(self, account_id, status, request.limit).await {
+                Ok(transactions) => transactions
+                    .into_iter()
+                    .map(parse_transaction_to_proto)
+                    .collect::<Result<_, _>>()?,
+                Err(e) => return Err(parse_error_to_status(e, "failed to list transactions")),
+            };
+
+        Ok(tonic::Response::new(ledger_v1::ListTransactionsResponse {
+            transactions,
+        }))
+    }
+
+    async fn post_journal_entry(
+        &self,
+        request: tonic::Request<ledger_v1::PostJournalEntryRequest>,
+    ) -> Result<tonic::Response<ledger_v1::PostJournalEntryResponse>, tonic::Status> {
+        let request = request.into_inner();
+        if request.idempotency_key.is_empty() {
+            return Err(tonic::Status::invalid_argument(
+                "idempotency_key must be set",
+            ));
+        }
+        let legs = parse_to_domain_legs(request.legs)?;
+        let request_timestamp = match parse_to_domain_timestamp(request.request_timestamp) {
+            Ok(request_timestamp) => request_timestamp,
+            Err(e) => return Err(tonic::Status::invalid_argument(e.to_string())),
+        };
+        let description = match request.description.is_empty() {
+            true => None,
+            false => Some(request.description),
+        };
+
+        let journal_entry = JournalEntry::new(
+            request.idempotency_key,
+            description,
+            legs,
+            request_timestamp,
+        );
+        match LedgerService::post_journal_entry(self, &journal_entry).await {
+            Ok(journal_entry) => Ok(tonic::Response::new(ledger_v1::PostJournalEntryResponse {
+                journal_entry: Some(parse_journal_entry_to_proto(journal_entry)?),
+            })),
+            Err(e) => Err(parse_error_to_status(e, "failed to post journal entry")),
+        }
+    }
+
+    async fn get_journal_entry(
+        &self,
+        request: tonic::Request<ledger_v1::GetJournalEntryRequest>,
+    ) -> Result<tonic::Response<ledger_v1::GetJournalEntryResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let id = parse_to_uuid("journal_entry_id", request.journal_entry_id.as_str())?;
+
+        match LedgerService::get_journal_entry(self, id).await {
+            Ok(journal_entry) => Ok(tonic::Response::new(ledger_v1::GetJournalEntryResponse {
+                journal_entry: Some(parse_journal_entry_to_proto(journal_entry)?),
+            })),
+            Err(e) => Err(parse_error_to_status(e, "failed to get journal entry")),
+        }
+    }
+
+    async fn get_transaction_details(
+        &self,
+        request: tonic::Request<ledger_v1::GetTransactionDetailsRequest>,
+    ) -> Result<tonic::Response<ledger_v1::GetTransactionDetailsResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let id = parse_to_uuid("transaction_id", request.transaction_id.as_str())?;
+
+        match LedgerService::get_transaction_details(self, id).await {
+            Ok(details) => Ok(tonic::Response::new(parse_details_to_proto(details)?)),
+            Err(e) => Err(parse_error_to_status(
+                e,
+                "failed to get transaction details",
+            )),
+        }
+    }
+
+    async fn count_transactions(
+        &self,
+        request: tonic::Request<ledger_v1::CountTransactionsRequest>,
+    ) -> Result<tonic::Response<ledger_v1::CountTransactionsResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let since =
+            match request.since {
+                Some(since) => Some(parse_to_domain_timestamp(Some(since)).map_err(|_| {
+                    tonic::Status::invalid_argument(format!("invalid since {since}"))
+                })?),
+                None => None,
+            };
+
+        let counts = match LedgerService::count_transactions(self, since).await {
+            Ok(counts) => counts
+                .into_iter()
+                .map(parse_status_count_to_proto)
+                .collect(),
+            Err(e) => return Err(parse_error_to_status(e, "failed to count transactions")),
+        };
+
+        Ok(tonic::Response::new(ledger_v1::CountTransactionsResponse {
+            counts,
+        }))
+    }
+
+    async fn get_balances(
+        &self,
+        request: tonic::Request<ledger_v1::GetBalancesRequest>,
+    ) -> Result<tonic::Response<ledger_v1::GetBalancesResponse>, tonic::Status> {
+        let request = request.into_inner();
+        if request.account_ids.is_empty() {
+            return Err(tonic::Status::invalid_argument("account_ids must be set"));
+        }
+        let account_ids = request
+            .account_ids
+            .iter()
+            .map(|account_id| parse_to_uuid("account_id", account_id.as_str()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let balances = match LedgerService::get_balances(self, &account_ids).await {
+            Ok(balances) => balances
+                .into_iter()
+                .map(parse_balance_to_proto)
+                .collect::<Result<_, _>>()?,
+            Err(e) => return Err(parse_error_to_status(e, "failed to get balances")),
+        };
+
+        Ok(tonic::Response::new(ledger_v1::GetBalancesResponse {
+            balances,
+        }))
+    }
+
+    async fn create_refund(
+        &self,
+        request: tonic::Request<ledger_v1::CreateRefundRequest>,
+    ) -> Result<tonic::Response<ledger_v1::CreateRefundResponse>, tonic::Status> {
+        let request = request.into_inner();
+
+        let transaction_id = parse_to_uuid("transaction_id", request.transaction_id.as_str())?;
+        let (amount_minor, currency) = match parse_to_domain_amount(request.amount) {
+            Ok(amount) => amount,
+            Err(e) => return Err(tonic::Status::invalid_argument(e.to_string())),
+        };
+        let reason = match parse_to_domain_refund_reason(request.reason) {
+            Ok(reason) => reason,
+            Err(e) => return Err(tonic::Status::invalid_argument(e.to_string())),
+        };
+        if request.requested_by.is_empty() {
+            return Err(tonic::Status::invalid_argument("requested_by must be set"));
+        }
+
+        let refund = match LedgerService::create_refund(
+            self,
+            transaction_id,
+            amount_minor,
+            currency.as_str(),
+            reason.as_str(),
+            request.requested_by.as_str(),
+        )
+        .await
+        {
+            Ok(refund) => parse_refund_to_proto(refund)?,
+            Err(e) => return Err(parse_error_to_status(e, "failed to create refund")),
+        };
+
+        Ok(tonic::Response::new(ledger_v1::CreateRefundResponse {
+            refund: Some(refund),
+        }))
+    }
+
+    async fn approve_refund(
+        &self,
+        request: tonic::Request<ledger_v1::DecideRefundRequest>,
+    ) -> Result<tonic::Response<ledger_v1::DecideRefundResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let id = parse_to_uuid("refund_id", request.refund_id.as_str())?;
+        if request.approver.is_empty() {
+            return Err(tonic::Status::invalid_argument("approver must be set"));
+        }
+
+        let refund = match LedgerService::approve_refund(self, id, request.approver.as_str()).await
+        {
+            Ok(refund) => parse_refund_to_proto(refund)?,
+            Err(e) => return Err(parse_error_to_status(e, "failed to approve refund")),
+        };
+
+        Ok(tonic::Response::new(ledger_v1::DecideRefundResponse {
+            refund: Some(refund),
+        }))
+    }
+
+    async fn reject_refund(
+        &self,
+        request: tonic::Request<ledger_v1::DecideRefundRequest>,
+    ) -> Result<tonic::Response<ledger_v1::DecideRefundResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let id = parse_to_uuid("refund_id", request.refund_id.as_str())?;
+        if request.approver.is_empty() {
+            return Err(tonic::Status::invalid_argument("approver must be set"));
+        }
+
+        let refund = match LedgerService::reject_refund(self, id, request.approver.as_str()).await {
+            Ok(refund) => parse_refund_to_proto(refund)?,
+            Err(e) => return Err(parse_error_to_status(e, "failed to reject refund")),
+        };
+
+        Ok(tonic::Response::new(ledger_v1::DecideRefundResponse {
+            refund: Some(refund),
+        }))
+    }
+
+    async fn get_refunds(
+        &self,
+        request: tonic::Request<ledger_v1::GetRefundsRequest>,
+    ) -> Result<tonic::Response<ledger_v1::GetRefundsResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let transaction_id = parse_to_uuid("transaction_id", request.transaction_id.as_str())?;
+
+        let refunds = match LedgerService::get_refunds_by_transaction_id(self, transaction_id).await
+        {
+            Ok(refunds) => refunds
+                .into_iter()
+                .map(parse_refund_to_proto)
+                .collect::<Result<_, _>>()?,
+            Err(e) => return Err(parse_error_to_status(e, "failed to get refunds")),
+        };
+
+        Ok(tonic::Response::new(ledger_v1::GetRefundsResponse {
+            refunds,
+        }))
+    }
+
+    async fn set_fee_schedule(
+        &self,
+        request: tonic::Request<ledger_v1::SetFeeScheduleRequest>,
+    ) -> Result<tonic::Response<ledger_v1::SetFeeScheduleResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let merchant_account_id =
+            parse_to_uuid("merchant_account_id", request.merchant_account_id.as_str())?;
+        if request.created_by.is_empty() {
+            return Err(tonic::Status::invalid_argument("created_by must be set"));
+        }
+        let tiers = parse_to_domain_tiers(request.tiers)?;
+        let effective_from = match request.effective_from {
+            Some(effective_from) => match parse_to_domain_timestamp(Some(effective_from)) {
+                Ok(effective_from) => Some(effective_from),
+                Err(_) => {
+                    return Err(tonic::Status::invalid_argument(format!(
+                        "invalid effective_from {effective_from}"
+                    )));
+                }
+            },
+            None => None,
+        };
+
+        let fee_schedule = FeeSchedule::new(
+            merchant_account_id,
+            tiers,
+            effective_from,
+            request.created_by,
+        );
+        match LedgerService::set_fee_schedule(self, &fee_schedule).await {
+            Ok(fee_schedule) => Ok(tonic::Response::new(ledger_v1::SetFeeScheduleResponse {
+                fee_schedule: Some(parse_fee_schedule_to_proto(fee_schedule)?),
+            })),
+            Err(e) => Err(parse_error_to_status(e, "failed to set fee schedule")),
+        }
+    }
+
+    async fn get_fee_schedules(
+        &self,
+        request: tonic::Request<ledger_v1::GetFeeSchedulesRequest>,
+    ) -> Result<tonic::Response<ledger_v1::GetFeeSchedulesResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let merchant_account_id =
+            parse_to_uuid("merchant_account_id", request.merchant_account_id.as_str())?;
+
+        let fee_schedules = match LedgerService::get_fee_schedules(self, merchant_account_id).await
+        {
+            Ok(fee_schedules) => fee_schedules
+                .into_iter()
+                .map(parse_fee_schedule_to_proto)
+                .collect::<Result<_, _>>()?,
+            Err(e) => return Err(parse_error_to_status(e, "failed to get fee schedules")),
+        };
+
+        Ok(tonic::Response::new(ledger_v1::GetFeeSchedulesResponse {
+            fee_schedules,
+        }))
+    }
+
+    async fn authorize_hold(
+        &self,
+        request: tonic::Request<ledger_v1::AuthorizeHoldRequest>,
+    ) -> Result<tonic::Response<ledger_v1::AuthorizeHoldResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let debit_account_id =
+            parse_to_uuid("debit_account_id", request.debit_account_id.as_str())?;
+        let credit_account_id =
+            parse_to_uuid("credit_account_id", request.credit_account_id.as_str())?;
+        let (amount_minor, currency) = match parse_to_domain_amount(request.amount) {
+            Ok(amount) => amount,
+            Err(e) => return Err(tonic::Status::invalid_argument(e.to_string())),
+        };
+        if request.idempotency_key.is_empty() {
+            return Err(tonic::Status::invalid_argument(
+                "idempotency_key must be set",
+            ));
+        }
+        let ttl_seconds = match request.ttl_seconds {
+            0 => DEFAULT_HOLD_TTL_SECONDS,
+            ttl_seconds => ttl_seconds,
+        };
+        let ttl = match i64::try_from(ttl_seconds)
+            .ok()
+            .and_then(chrono::Duration::try_seconds)
+        {
+            Some(ttl) => ttl,
+            None => {
+                return Err(tonic::Status::invalid_argument(format!(
+                    "invalid ttl_seconds {ttl_seconds}"
+                )));
+            }
+        };
+        let request_timestamp = match parse_to_domain_timestamp(request.request_timestamp) {
+            Ok(request_timestamp) => request_timestamp,
+            Err(e) => return Err(tonic::Status::invalid_argument(e.to_string())),
+        };
+
+        let hold = Hold::new(
+            debit_account_id,
+            credit_account_id,
+            amount_minor,
+            currency,
+            request.idempotency_key,
+            ttl,
+            request_timestamp,
+        );
+        match LedgerService::authorize_hold(self, &hold).await {
+            Ok(hold) => Ok(tonic::Response::new(ledger_v1::AuthorizeHoldResponse {
+                hold: Some(parse_hold_to_proto(hold)?),
+            })),
+            Err(e) => Err(parse_error_to_status(e, "failed to authorize hold")),
+        }
+    }
+
+    async fn capture_hold(
+        &self,
+        request: tonic::Request<ledger_v1::CaptureHoldRequest>,
+    ) -> Result<tonic::Response<ledger_v1::CaptureHoldResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let id = parse_to_uuid("hold_id", request.hold_id.as_str())?;
+        let amount = match request.amount {
+            Some(amount) => match parse_to_domain_amount(Some(amount)) {
+                Ok(amount) => Some(amount),
+                Err(e) => return Err(tonic::Status::invalid_argument(e.to_string())),
+            },
+            None => None,
+        };
+
+        match LedgerService::capture_hold(self, id, amount).await {
+            Ok((hold, transaction)) => Ok(tonic::Response::new(ledger_v1::CaptureHoldResponse {
+                hold: Some(parse_hold_to_proto(hold)?),
+                transaction: Some(parse_transaction_to_proto(transaction)?),
+            })),
+            Err(e) => Err(parse_error_to_status(e, "failed to capture hold")),
+        }
+    }
+
+    async fn void_hold(
+        &self,
+        request: tonic::Request<ledger_v1::VoidHoldRequest>,
+    ) -> Result<tonic::Response<ledger_v1::VoidHoldResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let id = parse_to_uuid("hold_id", request.hold_id.as_str())?;
+
+        match LedgerService::void_hold(self, id).await {
+            Ok(hold) => Ok(tonic::Response::new(ledger_v1::VoidHoldResponse {
+                hold: Some(parse_hold_to_proto(hold)?),
+            })),
+            Err(e) => Err(parse_error_to_status(e, "failed to void hold")),
+        }
+    }
+
+    async fn get_hold(
+        &self,
+        request: tonic::Request<ledger_v1::GetHoldRequest>,
+    ) -> Result<tonic::Response<ledger_v1::GetHoldResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let id = parse_to_uuid("hold_id", request.hold_id.as_str())?;
+
+        match LedgerService::get_hold(self, id).await {
+            Ok(hold) => Ok(tonic::Response::new(ledger_v1::GetHoldResponse {
+                hold: Some(parse_hold_to_proto(hold)?),
+            })),
+            Err(e) => Err(parse_error_to_status(e, "failed to get hold")),
+        }
+    }
+
+    async fn create_schedule(
+        &self,
+        request: tonic::Request<ledger_v1::CreateScheduleRequest>,
+    ) -> Result<tonic::Response<ledger_v1::CreateScheduleResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let debit_account_id =
+            parse_to_uuid("debit_account_id", request.debit_account_id.as_str())?;
+        let credit_account_id =
+            parse_to_uuid("credit_account_id", request.credit_account_id.as_str())?;
+        let (amount_minor, currency) = match parse_to_domain_amount(request.amount) {
+            Ok(amount) => amount,
+            Err(e) => return Err(tonic::Status::invalid_argument(e.to_string())),
+        };
+        if request.idempotency_key.is_empty() {
+            return Err(tonic::Status::invalid_argument(
+                "idempotency_key must be set",
+            ));
+        }
+        let cron = match request.cron.trim() {
+            "" => None,
+            cron => Some(cron.to_string()),
+        };
+        let interval_seconds = match request.interval_seconds {
+            0 => None,
+            interval_seconds => match i64::try_from(interval_seconds) {
+                Ok(interval_seconds) => Some(interval_seconds),
+                Err(_) => {
+                    return Err(tonic::Status::invalid_argument(format!(
+                        "invalid interval_seconds {interval_seconds}"
+                    )));
+                }
+            },
+        };
+        let parse_timestamp =
+            |name: &str, timestamp: Option<prost_types::Timestamp>| match timestamp {
+                Some(timestamp) => match parse_to_domain_timestamp(Some(timestamp)) {
+                    Ok(timestamp) => Ok(Some(timestamp)),
+                    Err(_) => Err(tonic::Status::invalid_argument(format!(
+                        "invalid {name} {timestamp}"
+                    ))),
+                },
+                None => Ok(None),
+            };
+        let starts_at = parse_timestamp("starts_at", request.starts_at)?;
+        let ends_at = parse_timestamp("ends_at", request.ends_at)?;
+
+        let schedule = Schedule::new(
+            debit_account_id,
+            credit_account_id,
+            amount_minor,
+            currency,
+            cron,
+            interval_seconds,
+            starts_at,
+            ends_at,
+            request.idempotency_key,
+        );
+        match LedgerService::create_schedule(self, &schedule).await {
+            Ok(schedule) => Ok(tonic::Response::new(ledger_v1::CreateScheduleResponse {
+                schedule: Some(parse_schedule_to_proto(schedule)?),
+            })),
+            Err(e) => Err(parse_error_to_status(e, "failed to create schedule")),
+        }
+    }
+
+    async fn get_schedule(
+        &self,
+        request: tonic::Request<ledger_v1::GetScheduleRequest>,
+    ) -> Result<tonic::Response<ledger_v1::GetScheduleResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let id = parse_to_uuid("schedule_id", request.schedule_id.as_str())?;
+
+        match LedgerService::get_schedule(self, id).await {
+            Ok(schedule) => Ok(tonic::Response::new(ledger_v1::GetScheduleResponse {
+                schedule: Some(parse_schedule_to_proto(schedule)?),
+            })),
+            Err(e) => Err(parse_error_to_status(e, "failed to get schedule")),
+        }
+    }
+
+    async fn list_schedules(
+        &self,
+        request: tonic::Request<ledger_v1::ListSchedulesRequest>,
+    ) -> Result<tonic::Response<ledger_v1::ListSchedulesResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let account_id = match request.account_id.is_empty() {
+            true => None,
+            false => Some(parse_to_uuid("account_id", request.account_id.as_str())?),
+        };
+
+        match LedgerService::list_schedules(self, account_id).await {
+            Ok(schedules) => Ok(tonic::Response::new(ledger_v1::ListSchedulesResponse {
+                schedules: schedules
+                    .into_iter()
+                    .map(parse_schedule_to_proto)
+                    .collect::<Result<_, _>>()?,
+            })),
+            Err(e) => Err(parse_error_to_status(e, "failed to list schedules")),
+        }
+    }
+
+    async fn list_schedule_runs(
+        &self,
+        request: tonic::Request<ledger_v1::ListScheduleRunsRequest>,
+    ) -> Result<tonic::Response<ledger_v1::ListScheduleRunsResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let id = parse_to_uuid("schedule_id", request.schedule_id.as_str())?;
+
+        match LedgerService::get_schedule_runs(self, id).await {
+            Ok(runs) => Ok(tonic::Response::new(ledger_v1::ListScheduleRunsResponse {
+                runs: runs.into_iter().map(parse_schedule_run_to_proto).collect(),
+            })),
+            Err(e) => Err(parse_error_to_status(e, "failed to list schedule runs")),
+        }
+    }
+
+    async fn pause_schedule(
+        &self,
+        request: tonic::Request<ledger_v1::ScheduleActionRequest>,
+    ) -> Result<tonic::Response<ledger_v1::ScheduleActionResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let id = parse_to_uuid("schedule_id", request.schedule_id.as_str())?;
+
+        match LedgerService::pause_schedule(self, id).await {
+            Ok(schedule) => Ok(tonic::Response::new(ledger_v1::ScheduleActionResponse {
+                schedule: Some(parse_schedule_to_proto(schedule)?),
+            })),
+            Err(e) => Err(parse_error_to_status(e, "failed to pause schedule")),
+        }
+    }
+
+    async fn resume_schedule(
+        &self,
+        request: tonic::Request<ledger_v1::ScheduleActionRequest>,
+    ) -> Result<tonic::Response<ledger_v1::ScheduleActionResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let id = parse_to_uuid("schedule_id", request.schedule_id.as_str())?;
+
+        match LedgerService::resume_schedule(self, id).await {
+            Ok(schedule) => Ok(tonic::Response::new(ledger_v1::ScheduleActionResponse {
+                schedule: Some(parse_schedule_to_proto(schedule)?),
+            })),
+            Err(e) => Err(parse_error_to_status(e, "failed to resume schedule")),
+        }
+    }
+
+    async fn cancel_schedule(
+        &self,
+        request: tonic::Request<ledger_v1::ScheduleActionRequest>,
+    ) -> Result<tonic::Response<ledger_v1::ScheduleActionResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let id = parse_to_uuid("schedule_id", request.schedule_id.as_str())?;
+
+        match LedgerService::cancel_schedule(self, id).await {
+            Ok(schedule) => Ok(tonic::Response::new(ledger_v1::ScheduleActionResponse {
+                schedule: Some(parse_schedule_to_proto(schedule)?),
+            })),
+            Err(e) => Err(parse_error_to_status(e, "failed to cancel schedule")),
+        }
+    }
+
+    async fn skip_schedule_run(
+        &self,
+        request: tonic::Request<ledger_v1::ScheduleActionRequest>,
+    ) -> Result<tonic::Response<ledger_v1::SkipScheduleRunResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let id = parse_to_uuid("schedule_id", request.schedule_id.as_str())?;
+
+        match LedgerService::skip_schedule_run(self, id).await {
+            Ok((schedule, run)) => Ok(tonic::Response::new(ledger_v1::SkipScheduleRunResponse {
+                schedule: Some(parse_schedule_to_proto(schedule)?),
+                run: Some(parse_schedule_run_to_proto(run)),
+            })),
+            Err(e) => Err(parse_error_to_status(e, "failed to skip schedule run")),
+        }
     }
 }
@@ -0,0 +1,771 @@
+use crate::domain::balance::Balance;
+use crate::domain::entry::{self, Entry};
+use crate::domain::fee::{FeeError, FeeSchedule, Tier};
+use crate::domain::fx::{Conversion, FxError};
+use crate::domain::hold::{self, Hold, HoldError};
+use crate::domain::journal::{JournalEntry, JournalError, Leg};
+use crate::domain::money::{Money, from_minor_units, to_minor_units};
+use crate::domain::refund::{Refund, RefundError, Status};
+use crate::domain::schedule::{self, Run, RunStatus, Schedule, ScheduleError};
+use crate::domain::transaction::{self, Transaction, TransactionError};
+use ledger_proto::ledger_v1;
+
+use prost_types::Timestamp;
+
+fn parse_timestamp_to_proto(timestamp: chrono::DateTime<chrono::Utc>) -> Timestamp {
+    Timestamp {
+        seconds: timestamp.timestamp(),
+        nanos: timestamp.timestamp_subsec_nanos() as i32,
+    }
+}
+
+/// parse_money_to_proto fails for amounts stored in a code outside ISO 4217, whose minor unit
+/// is unknown.
+fn parse_money_to_proto(
+    amount_minor: i64,
+    currency: String,
+) -> Result<ledger_v1::google::r#type::Money, tonic::Status> {
+    match from_minor_units(amount_minor, &currency) {
+        Ok((units, nanos)) => Ok(ledger_v1::google::r#type::Money {
+            currency_code: currency,
+            units,
+            nanos,
+        }),
+        Err(e) => Err(tonic::Status::internal(format!(
+            "failed to read stored amount: {e}"
+        ))),
+    }
+}
+
+pub fn parse_transaction_status_to_proto(status: &transaction::Status) -> i32 {
+    match status {
+        transaction::Status::Init => ledger_v1::TransactionStatus::Init as i32,
+        transaction::Status::Pending => ledger_v1::TransactionStatus::Pending as i32,
+        transaction::Status::Success => ledger_v1::TransactionStatus::Success as i32,
+        transaction::Status::Failed => ledger_v1::TransactionStatus::Failed as i32,
+        transaction::Status::Fraud => ledger_v1::TransactionStatus::Fraud as i32,
+        transaction::Status::Refund => ledger_v1::TransactionStatus::Refund as i32,
+        transaction::Status::Refunded => ledger_v1::TransactionStatus::Refunded as i32,
+    }
+}
+
+/// parse_to_domain_transaction_status returns None for an unspecified status.
+pub fn parse_to_domain_transaction_status(
+    status: i32,
+) -> anyhow::Result<Option<transaction::Status>> {
+    match ledger_v1::TransactionStatus::try_from(status) {
+        Ok(ledger_v1::TransactionStatus::Unspecified) => Ok(None),
+        Ok(ledger_v1::TransactionStatus::Init) => Ok(Some(transaction::Status::Init)),
+        Ok(ledger_v1::TransactionStatus::Pending) => Ok(Some(transaction::Status::Pending)),
+        Ok(ledger_v1::TransactionStatus::Success) => Ok(Some(transaction::Status::Success)),
+        Ok(ledger_v1::TransactionStatus::Failed) => Ok(Some(transaction::Status::Failed)),
+        Ok(ledger_v1::TransactionStatus::Fraud) => Ok(Some(transaction::Status::Fraud)),
+        Ok(ledger_v1::TransactionStatus::Refund) => Ok(Some(transaction::Status::Refund)),
+        Ok(ledger_v1::TransactionStatus::Refunded) => Ok(Some(transaction::Status::Refunded)),
+        Err(_) => Err(anyhow::anyhow!("invalid transaction status {status}")),
+    }
+}
+
+fn parse_fx_conversion_to_proto(fx: Conversion) -> Result<ledger_v1::FxConversion, tonic::Status> {
+    Ok(ledger_v1::FxConversion {
+        credit_amount: Some(parse_money_to_proto(
+            fx.credit_amount_minor,
+            fx.credit_currency,
+        )?),
+        rate: fx.rate.normalize().to_string(),
+        applied_rate: fx.applied_rate.normalize().to_string(),
+        spread_bps: fx.spread_bps as u32,
+        rounding_mode: fx.rounding_mode.as_ref().to_string(),
+        rate_source: fx.rate_source,
+        rate_as_of: Some(parse_timestamp_to_proto(fx.rate_as_of)),
+        debit_fx_account_id: fx.debit_fx_account_id.to_string(),
+        credit_fx_account_id: fx.credit_fx_account_id.to_string(),
+    })
+}
+
+pub fn parse_transaction_to_proto(
+    transaction: Transaction,
+) -> Result<ledger_v1::Transaction, tonic::Status> {
+    Ok(ledger_v1::Transaction {
+        id: transaction.id.to_string(),
+        debit_account_id: transaction.debit_account_id.to_string(),
+        credit_account_id: transaction.credit_account_id.to_string(),
+        amount: Some(parse_money_to_proto(
+            transaction.amount_minor,
+            transaction.currency,
+        )?),
+        status: parse_transaction_status_to_proto(&transaction.status),
+        idempotency_key: transaction.idempotency_key,
+        request_timestamp: Some(parse_timestamp_to_proto(transaction.request_timestamp)),
+        created_at: Some(parse_timestamp_to_proto(transaction.created_at)),
+        updated_at: Some(parse_timestamp_to_proto(transaction.updated_at)),
+        fx: transaction
+            .fx
+            .map(parse_fx_conversion_to_proto)
+            .transpose()?,
+    })
+}
+
+/// parse_to_domain_timestamp defaults to now when the client did not say when it made the
+/// request.
+pub fn parse_to_domain_timestamp(
+    timestamp: Option<Timestamp>,
+) -> anyhow::Result<chrono::DateTime<chrono::Utc>> {
+    match timestamp {
+        Some(timestamp) => {
+            match chrono::DateTime::from_timestamp(timestamp.seconds, timestamp.nanos as u32) {
+                Some(timestamp) => Ok(timestamp),
+                None => Err(anyhow::anyhow!("invalid request_timestamp")),
+            }
+        }
+        None => Ok(chrono::Utc::now()),
+    }
+}
+
+pub fn parse_refund_to_proto(refund: Refund) -> Result<ledger_v1::Refund, tonic::Status> {
+    Ok(ledger_v1::Refund {
+        id: refund.id.to_string(),
+        transaction_id: refund.transaction_id.to_string(),
+        amount: Some(parse_money_to_proto(refund.amount_minor, refund.currency)?),
+        reason: ledger_v1::RefundReason::from_str_name(
+            format!("REFUND_REASON_{}", refund.reason.to_uppercase()).as_str(),
+        )
+        .unwrap_or(ledger_v1::RefundReason::Unspecified) as i32,
+        status: match refund.status {
+            Status::AwaitingApproval => ledger_v1::RefundStatus::AwaitingApproval as i32,
+            Status::Approved => ledger_v1::RefundStatus::Approved as i32,
+            Status::Rejected => ledger_v1::RefundStatus::Rejected as i32,
+            Status::Pending => ledger_v1::RefundStatus::Pending as i32,
+            Status::Succeeded => ledger_v1::RefundStatus::Succeeded as i32,
+            Status::Failed => ledger_v1::RefundStatus::Failed as i32,
+        },
+        requested_by: refund.requested_by.unwrap_or_default(),
+        approved_by: refund.approved_by.unwrap_or_default(),
+        psp_reference: refund.psp_reference.unwrap_or_default(),
+        failure_reason: refund.failure_reason.unwrap_or_default(),
+        created_at: Some(parse_timestamp_to_proto(refund.created_at)),
+        updated_at: Some(parse_timestamp_to_proto(refund.updated_at)),
+    })
+}
+
+/// parse_to_domain_refund_reason returns the reason as stored by the ledger, e.g. `duplicate`.
+pub fn parse_to_domain_refund_reason(reason: i32) -> anyhow::Result<String> {
+    match ledger_v1::RefundReason::try_from(reason) {
+        Ok(ledger_v1::RefundReason::Unspecified) | Err(_) => {
+            Err(anyhow::anyhow!("Unspecified refund reason"))
+        }
+        Ok(reason) => Ok(reason
+            .as_str_name()
+            .trim_start_matches("REFUND_REASON_")
+            .to_lowercase()),
+    }
+}
+
+pub fn parse_balance_to_proto(balance: Balance) -> Result<ledger_v1::Balance, tonic::Status> {
+    Ok(ledger_v1::Balance {
+        account_id: balance.account_id.to_string(),
+        amount: Some(parse_money_to_proto(
+            balance.amount_minor,
+            balance.currency.clone(),
+        )?),
+        available: Some(parse_money_to_proto(
+            balance.available_minor,
+            balance.currency,
+        )?),
+    })
+}
+
+pub fn parse_entry_to_proto(entry: Entry) -> Result<ledger_v1::Entry, tonic::Status> {
+    Ok(ledger_v1::Entry {
+        id: entry.id.to_string(),
+        account_id: entry.account_id.to_string(),
+        entry_type: parse_entry_type_to_proto(&entry.entry_type),
+        amount: Some(parse_money_to_proto(entry.amount_minor, entry.currency)?),
+        refund_id: entry.refund_id.map(|id| id.to_string()),
+        created_at: Some(parse_timestamp_to_proto(entry.created_at)),
+        description: entry.description,
+    })
+}
+
+fn parse_entry_type_to_proto(entry_type: &entry::Type) -> i32 {
+    match entry_type {
+        entry::Type::Debit => ledger_v1::EntryType::Debit as i32,
+        entry::Type::Credit => ledger_v1::EntryType::Credit as i32,
+    }
+}
+
+pub fn parse_journal_entry_to_proto(
+    journal_entry: JournalEntry,
+) -> Result<ledger_v1::JournalEntry, tonic::Status> {
+    Ok(ledger_v1::JournalEntry {
+        id: journal_entry.id.to_string(),
+        idempotency_key: journal_entry.idempotency_key,
+        description: journal_entry.description.unwrap_or_default(),
+        legs: journal_entry
+            .legs
+            .into_iter()
+            .map(|leg| {
+                Ok(ledger_v1::JournalLeg {
+                    account_id: leg.account_id.to_string(),
+                    entry_type: parse_entry_type_to_proto(&leg.entry_type),
+                    amount: Some(parse_money_to_proto(leg.amount_minor, leg.currency)?),
+                    description: leg.description.unwrap_or_default(),
+                })
+            })
+            .collect::<Result<_, tonic::Status>>()?,
+        request_timestamp: Some(parse_timestamp_to_proto(journal_entry.request_timestamp)),
+        created_at: Some(parse_timestamp_to_proto(journal_entry.created_at)),
+    })
+}
+
+/// parse_to_domain_legs validates the shape of every leg, naming the first malformed one.
+/// Whether the legs balance is checked by the journal entry itself.
+pub fn parse_to_domain_legs(legs: Vec<ledger_v1::JournalLeg>) -> Result<Vec<Leg>, tonic::Status> {
+    legs.into_iter()
+        .enumerate()
+        .map(|(index, leg)| {
+            let account_id = parse_to_uuid(&format!("legs[{index}].account_id"), &leg.account_id)?;
+            let entry_type = match ledger_v1::EntryType::try_from(leg.entry_type) {
+                Ok(ledger_v1::EntryType::Debit) => entry::Type::Debit,
+                Ok(ledger_v1::EntryType::Credit) => entry::Type::Credit,
+                _ => {
+                    return Err(tonic::Status::invalid_argument(format!(
+                        "leg {index}: entry_type must be debit or credit"
+                    )));
+                }
+            };
+            let (amount_minor, currency) = match parse_to_domain_amount(leg.amount) {
+                Ok(amount) => amount,
+                Err(e) => {
+                    return Err(tonic::Status::invalid_argument(format!("leg {index}: {e}")));
+                }
+            };
+
+            Ok(Leg {
+                account_id,
+                entry_type,
+                amount_minor,
+                currency,
+                description: match leg.description.is_empty() {
+                    true => None,
+                    false => Some(leg.description),
+                },
+            })
+        })
+        .collect()
+}
+
+pub fn parse_hold_to_proto(hold: Hold) -> Result<ledger_v1::Hold, tonic::Status> {
+    Ok(ledger_v1::Hold {
+        id: hold.id.to_string(),
+        debit_account_id: hold.debit_account_id.to_string(),
+        credit_account_id: hold.credit_account_id.to_string(),
+        amount: Some(parse_money_to_proto(
+            hold.amount_minor,
+            hold.currency.clone(),
+        )?),
+        status: match hold.status {
+            hold::Status::Authorized => ledger_v1::HoldStatus::Authorized as i32,
+            hold::Status::Captured => ledger_v1::HoldStatus::Captured as i32,
+            hold::Status::Voided => ledger_v1::HoldStatus::Voided as i32,
+            hold::Status::Expired => ledger_v1::HoldStatus::Expired as i32,
+        },
+        idempotency_key: hold.idempotency_key,
+        expires_at: Some(parse_timestamp_to_proto(hold.expires_at)),
+        captured_amount: hold
+            .captured_amount_minor
+            .map(|captured_minor| parse_money_to_proto(captured_minor, hold.currency))
+            .transpose()?,
+        transaction_id: hold
+            .transaction_id
+            .map(|id| id.to_string())
+            .unwrap_or_default(),
+        request_timestamp: Some(parse_timestamp_to_proto(hold.request_timestamp)),
+        created_at: Some(parse_timestamp_to_proto(hold.created_at)),
+        updated_at: Some(parse_timestamp_to_proto(hold.updated_at)),
+    })
+}
+
+pub fn parse_schedule_to_proto(schedule: Schedule) -> Result<ledger_v1::Schedule, tonic::Status> {
+    Ok(ledger_v1::Schedule {
+        id: schedule.id.to_string(),
+        debit_account_id: schedule.debit_account_id.to_string(),
+        credit_account_id: schedule.credit_account_id.to_string(),
+        amount: Some(parse_money_to_proto(
+            schedule.amount_minor,
+            schedule.currency,
+        )?),
+        cron: schedule.cron.unwrap_or_default(),
+        interval_seconds: schedule.interval_seconds.unwrap_or_default() as u64,
+        starts_at: Some(parse_timestamp_to_proto(schedule.starts_at)),
+        ends_at: schedule.ends_at.map(parse_timestamp_to_proto),
+        next_run_at: schedule.next_run_at.map(parse_timestamp_to_proto),
+        status: match schedule.status {
+            schedule::Status::Active => ledger_v1::ScheduleStatus::Active as i32,
+            schedule::Status::Paused => ledger_v1::ScheduleStatus::Paused as i32,
+            schedule::Status::Cancelled => ledger_v1::ScheduleStatus::Cancelled as i32,
+            schedule::Status::Completed => ledger_v1::ScheduleStatus::Completed as i32,
+        },
+        idempotency_key: schedule.idempotency_key,
+        created_at: Some(parse_timestamp_to_proto(schedule.created_at)),
+        updated_at: Some(parse_timestamp_to_proto(schedule.updated_at)),
+    })
+}
+
+pub fn parse_schedule_run_to_proto(run: Run) -> ledger_v1::ScheduleRun {
+    ledger_v1::ScheduleRun {
+        id: run.id.to_string(),
+        schedule_id: run.schedule_id.to_string(),
+        occurrence_at: Some(parse_timestamp_to_proto(run.occurrence_at)),
+        status: match run.status {
+            RunStatus::Pending => ledger_v1::ScheduleRunStatus::Pending as i32,
+            RunStatus::Succeeded => ledger_v1::ScheduleRunStatus::Succeeded as i32,
+            RunStatus::Failed => ledger_v1::ScheduleRunStatus::Failed as i32,
+            RunStatus::Skipped => ledger_v1::ScheduleRunStatus::Skipped as i32,
+        },
+        transaction_id: run
+            .transaction_id
+            .map(|id| id.to_string())
+            .unwrap_or_default(),
+        error: run.error.unwrap_or_default(),
+        created_at: Some(parse_timestamp_to_proto(run.created_at)),
+        updated_at: Some(parse_timestamp_to_proto(run.updated_at)),
+    }
+}
+
+pub fn parse_fee_schedule_to_proto(
+    fee_schedule: FeeSchedule,
+) -> Result<ledger_v1::FeeSchedule, tonic::Status> {
+    Ok(ledger_v1::FeeSchedule {
+        id: fee_schedule.id.to_string(),
+        merchant_account_id: fee_schedule.merchant_account_id.to_string(),
+        version: fee_schedule.version as u32,
+        tiers: fee_schedule
+            .tiers
+            .into_iter()
+            .map(|tier| {
+                Ok(ledger_v1::FeeTier {
+                    fixed: Some(parse_money_to_proto(
+                        tier.fixed_minor,
+                        tier.currency.clone(),
+                    )?),
+                    percentage_bps: tier.percentage_bps as u32,
+                    min_monthly_volume: Some(parse_money_to_proto(
+                        tier.min_monthly_volume_minor,
+                        tier.currency,
+                    )?),
+                })
+            })
+            .collect::<Result<_, tonic::Status>>()?,
+        effective_from: Some(parse_timestamp_to_proto(fee_schedule.effective_from)),
+        created_by: fee_schedule.created_by,
+        created_at: Some(parse_timestamp_to_proto(fee_schedule.created_at)),
+    })
+}
+
+/// parse_to_domain_tiers reads the currency of every tier from its fixed fee, naming the first
+/// malformed tier. Whether the tiers make a valid schedule is checked by the schedule itself.
+pub fn parse_to_domain_tiers(tiers: Vec<ledger_v1::FeeTier>) -> Result<Vec<Tier>, tonic::Status> {
+    tiers
+        .into_iter()
+        .enumerate()
+        .map(|(index, tier)| {
+            let (fixed_minor, currency) = match parse_to_domain_amount(tier.fixed) {
+                Ok(fixed) => fixed,
+                Err(e) => {
+                    return Err(tonic::Status::invalid_argument(format!(
+                        "tier {index}: fixed {e}"
+                    )));
+                }
+            };
+            let min_monthly_volume_minor = match tier.min_monthly_volume {
+                Some(volume) if !volume.currency_code.eq_ignore_ascii_case(&currency) => {
+                    return Err(tonic::Status::invalid_argument(format!(
+                        "tier {index}: min_monthly_volume must be in {currency}"
+                    )));
+                }
+                Some(volume) => match to_minor_units(volume.units, volume.nanos, &currency) {
+                    Ok(volume_minor) => volume_minor,
+                    Err(e) => {
+                        return Err(tonic::Status::invalid_argument(format!(
+                            "tier {index}: min_monthly_volume {e}"
+                        )));
+                    }
+                },
+                None => 0,
+            };
+
+            Ok(Tier {
+                currency,
+                min_monthly_volume_minor,
+                percentage_bps: i32::try_from(tier.percentage_bps).unwrap_or(i32::MAX),
+                fixed_minor,
+            })
+        })
+        .collect()
+}
+
+pub fn parse_details_to_proto(
+    details: transaction::Details,
+) -> Result<ledger_v1::GetTransactionDetailsResponse, tonic::Status> {
+    Ok(ledger_v1::GetTransactionDetailsResponse {
+        transaction: Some(parse_transaction_to_proto(details.transaction)?),
+        entries: details
+            .entries
+            .into_iter()
+            .map(parse_entry_to_proto)
+            .collect::<Result<_, _>>()?,
+        status_history: details
+            .status_history
+            .into_iter()
+            .map(|change| ledger_v1::StatusChange {
+                status: parse_transaction_status_to_proto(&change.status),
+                changed_at: Some(parse_timestamp_to_proto(change.created_at)),
+            })
+            .collect(),
+    })
+}
+
+pub fn parse_status_count_to_proto(count: transaction::StatusCount) -> ledger_v1::StatusCount {
+    ledger_v1::StatusCount {
+        status: parse_transaction_status_to_proto(&count.status),
+        count: count.count as u64,
+    }
+}
+
+/// parse_to_domain_amount returns the amount in minor units together with its ISO 4217
+/// currency code in upper case.
+pub fn parse_to_domain_amount(
+    amount: Option<ledger_v1::google::r#type::Money>,
+) -> anyhow::Result<(i64, String)> {
+    match amount {
+        Some(amount) if !amount.currency_code.is_empty() => {
+            match Money::from_units_nanos(amount.units, amount.nanos, &amount.currency_code) {
+                Ok(money) => Ok((money.amount_minor(), money.currency().code().to_string())),
+                Err(e) => Err(anyhow::anyhow!("invalid amount: {e}")),
+            }
+        }
+        Some(_) => Err(anyhow::anyhow!("amount currency_code must be set")),
+        None => Err(anyhow::anyhow!("amount must be set")),
+    }
+}
+
+pub fn parse_to_uuid(name: &str, value: &str) -> Result<uuid::Uuid, tonic::Status> {
+    uuid::Uuid::parse_str(value)
+        .map_err(|e| tonic::Status::invalid_argument(format!("invalid {name} {value}: {e}")))
+}
+
+/// parse_error_to_status maps transaction, journal, fee, hold, schedule and refund errors to their grpc
+/// codes, anything else is internal.
+pub fn parse_error_to_status(e: anyhow::Error, message: &str) -> tonic::Status {
+    if let Some(error) = e.downcast_ref::<TransactionError>() {
+        return match error {
+            TransactionError::NotFound(_) => tonic::Status::not_found(e.to_string()),
+            TransactionError::InvalidAmount(_) | TransactionError::SameAccount(_) => {
+                tonic::Status::invalid_argument(e.to_string())
+            }
+            TransactionError::UnknownAccount => tonic::Status::failed_precondition(e.to_string()),
+            TransactionError::IdempotencyKeyReused(_) => {
+                tonic::Status::already_exists(e.to_string())
+            }
+        };
+    }
+
+    if let Some(error) = e.downcast_ref::<JournalError>() {
+        return match error {
+            JournalError::NotFound(_) => tonic::Status::not_found(e.to_string()),
+            JournalError::TooFewLegs(_)
+            | JournalError::InvalidAmount { .. }
+            | JournalError::InvalidCurrency { .. }
+            | JournalError::Unbalanced { .. } => tonic::Status::invalid_argument(e.to_string()),
+            JournalError::UnknownAccount { .. } => {
+                tonic::Status::failed_precondition(e.to_string())
+            }
+            JournalError::IdempotencyKeyReused(_) => tonic::Status::already_exists(e.to_string()),
+        };
+    }
+
+    if let Some(error) = e.downcast_ref::<FeeError>() {
+        return match error {
+            FeeError::NoTiers
+            | FeeError::InvalidTier { .. }
+            | FeeError::MissingBaseTier(_)
+            | FeeError::Backdated(_) => tonic::Status::invalid_argument(e.to_string()),
+            FeeError::EffectiveBeforeLatest { .. }
+            | FeeError::UnknownAccount(_)
+            | FeeError::NotMerchant(_) => tonic::Status::failed_precondition(e.to_string()),
+        };
+    }
+
+    if let Some(error) = e.downcast_ref::<FxError>() {
+        return match error {
+            FxError::UnknownCurrency(_) | FxError::AmountTooSmall { .. } => {
+                tonic::Status::invalid_argument(e.to_string())
+            }
+            FxError::NotConfigured | FxError::RateUnavailable { .. } | FxError::NoFxAccount(_) => {
+                tonic::Status::failed_precondition(e.to_string())
+            }
+            FxError::InvalidRate(_) | FxError::InvalidSpread(_) | FxError::Overflow => {
+                tonic::Status::internal(e.to_string())
+            }
+        };
+    }
+
+    if let Some(error) = e.downcast_ref::<HoldError>() {
+        return match error {
+            HoldError::NotFound(_) => tonic::Status::not_found(e.to_string()),
+            HoldError::InvalidAmount(_)
+            | HoldError::InvalidCurrency(_)
+            | HoldError::InvalidTtl
+            | HoldError::SameAccount(_)
+            | HoldError::CurrencyMismatch { .. } => tonic::Status::invalid_argument(e.to_string()),
+            HoldError::UnknownAccount
+            | HoldError::NotAuthorized(_, _)
+            | HoldError::Expired(_)
+            | HoldError::ExceedsHold { .. } => tonic::Status::failed_precondition(e.to_string()),
+            HoldError::IdempotencyKeyReused(_) => tonic::Status::already_exists(e.to_string()),
+        };
+    }
+
+    if let Some(error) = e.downcast_ref::<ScheduleError>() {
+        return match error {
+            ScheduleError::NotFound(_) => tonic::Status::not_found(e.to_string()),
+            ScheduleError::InvalidAmount(_)
+            | ScheduleError::InvalidCurrency(_)
+            | ScheduleError::SameAccount(_)
+            | ScheduleError::InvalidRecurrence(_)
+            | ScheduleError::EndsBeforeStart
+            | ScheduleError::NoOccurrence => tonic::Status::invalid_argument(e.to_string()),
+            ScheduleError::UnknownAccount | ScheduleError::InvalidTransition { .. } => {
+                tonic::Status::failed_precondition(e.to_string())
+            }
+            ScheduleError::IdempotencyKeyReused(_) => tonic::Status::already_exists(e.to_string()),
+        };
+    }
+
+    match e.downcast_ref::<RefundError>() {
+        Some(RefundError::TransactionNotFound(_)) | Some(RefundError::RefundNotFound(_)) => {
+            tonic::Status::not_found(e.to_string())
+        }
+        Some(RefundError::InvalidAmount(_)) | Some(RefundError::CurrencyMismatch { .. }) => {
+            tonic::Status::invalid_argument(e.to_string())
+        }
+        Some(RefundError::SelfApproval(_)) => tonic::Status::permission_denied(e.to_string()),
+        Some(RefundError::NotRefundable(_, _))
+        | Some(RefundError::ExceedsOriginal { .. })
+        | Some(RefundError::NotApproved(_, _))
+        | Some(RefundError::AlreadyDecided(_, _)) => {
+            tonic::Status::failed_precondition(e.to_string())
+        }
+        None => tonic::Status::internal(format!("{message}: {e}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ledger_proto::ledger_v1::RefundReason;
+
+    #[test]
+    fn test_parse_to_domain_refund_reason() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            reason: i32,
+            expected: Option<&'static str>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "successfully parse requested by customer",
+                reason: RefundReason::RequestedByCustomer as i32,
+                expected: Some("requested_by_customer"),
+            },
+            TestCase {
+                name: "successfully parse chargeback",
+                reason: RefundReason::Chargeback as i32,
+                expected: Some("chargeback"),
+            },
+            TestCase {
+                name: "error when reason is unspecified",
+                reason: RefundReason::Unspecified as i32,
+                expected: None,
+            },
+            TestCase {
+                name: "error when reason is unknown",
+                reason: 42,
+                expected: None,
+            },
+        ];
+
+        for test_case in test_cases {
+            let resp = parse_to_domain_refund_reason(test_case.reason);
+            match test_case.expected {
+                Some(expected) => assert_eq!(resp.unwrap(), expected, "{}", test_case.name),
+                None => assert!(resp.is_err(), "{}", test_case.name),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_to_domain_transaction_status() {
+        let test_cases = vec![
+            (ledger_v1::TransactionStatus::Unspecified as i32, Some(None)),
+            (
+                ledger_v1::TransactionStatus::Success as i32,
+                Some(Some(transaction::Status::Success)),
+            ),
+            (
+                ledger_v1::TransactionStatus::Refunded as i32,
+                Some(Some(transaction::Status::Refunded)),
+            ),
+            (42, None),
+        ];
+
+        for (status, expected) in test_cases {
+            let resp = parse_to_domain_transaction_status(status);
+            match expected {
+                Some(expected) => assert_eq!(resp.unwrap(), expected, "{status}"),
+                None => assert!(resp.is_err(), "{status}"),
+            }
+        }
+    }
+
+    #[test]
+    fn successfully_parse_refund_to_proto() {
+        // arrange
+        let mut refund = Refund::new(
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+            1050,
+            "USD",
+            "fraudulent",
+        );
+        refund.requested_by = Some("alice".to_string());
+
+        // act
+        let refund_proto = parse_refund_to_proto(refund.clone()).unwrap();
+
+        // assert
+        assert_eq!(refund_proto.id, refund.id.to_string());
+        assert_eq!(refund_proto.reason, RefundReason::Fraudulent as i32);
+        assert_eq!(refund_proto.status, ledger_v1::RefundStatus::Pending as i32);
+        let amount = refund_proto.amount.unwrap();
+        assert_eq!((amount.units, amount.nanos), (10, 500_000_000));
+        assert_eq!(refund_proto.requested_by, "alice");
+        assert_eq!(refund_proto.approved_by, "");
+    }
+
+    #[test]
+    fn error_when_stored_refund_currency_is_not_iso_4217() {
+        // arrange
+        let refund = Refund::new(
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+            1050,
+            "ABC",
+            "fraudulent",
+        );
+
+        // act
+        let result = parse_refund_to_proto(refund);
+
+        // assert
+        assert_eq!(result.unwrap_err().code(), tonic::Code::Internal);
+    }
+
+    #[test]
+    fn test_parse_error_to_status() {
+        let id = uuid::Uuid::new_v4();
+        let test_cases: Vec<(anyhow::Error, tonic::Code)> = vec![
+            (
+                RefundError::RefundNotFound(id).into(),
+                tonic::Code::NotFound,
+            ),
+            (
+                RefundError::SelfApproval(id).into(),
+                tonic::Code::PermissionDenied,
+            ),
+            (
+                RefundError::ExceedsOriginal {
+                    transaction_id: id,
+                    requested_minor: 10,
+                    remaining_minor: 5,
+                }
+                .into(),
+                tonic::Code::FailedPrecondition,
+            ),
+            (TransactionError::NotFound(id).into(), tonic::Code::NotFound),
+            (
+                TransactionError::IdempotencyKeyReused("key".to_string()).into(),
+                tonic::Code::AlreadyExists,
+            ),
+            (
+                JournalError::Unbalanced {
+                    currency: "USD".to_string(),
+                    difference_minor: 5,
+                    legs: vec![0, 1],
+                }
+                .into(),
+                tonic::Code::InvalidArgument,
+            ),
+            (
+                JournalError::UnknownAccount {
+                    leg: 1,
+                    account_id: id,
+                }
+                .into(),
+                tonic::Code::FailedPrecondition,
+            ),
+            (anyhow::anyhow!("connection reset"), tonic::Code::Internal),
+        ];
+
+        for (error, expected) in test_cases {
+            assert_eq!(parse_error_to_status(error, "failed").code(), expected);
+        }
+    }
+
+    #[test]
+    fn error_when_parsing_malformed_leg() {
+        // arrange
+        let leg = |account_id: &str, entry_type: ledger_v1::EntryType| ledger_v1::JournalLeg {
+            account_id: account_id.to_string(),
+            entry_type: entry_type as i32,
+            amount: Some(ledger_v1::google::r#type::Money {
+                currency_code: "usd".to_string(),
+                units: 1,
+                nanos: 0,
+            }),
+            description: String::new(),
+        };
+        let account_id = uuid::Uuid::new_v4().to_string();
+
+        // act
+        let parsed = parse_to_domain_legs(vec![
+            leg(&account_id, ledger_v1::EntryType::Debit),
+            leg(&account_id, ledger_v1::EntryType::Credit),
+        ])
+        .unwrap();
+        let unspecified = parse_to_domain_legs(vec![
+            leg(&account_id, ledger_v1::EntryType::Debit),
+            leg(&account_id, ledger_v1::EntryType::Unspecified),
+        ])
+        .unwrap_err();
+        let invalid_account =
+            parse_to_domain_legs(vec![leg("nope", ledger_v1::EntryType::Debit)]).unwrap_err();
+
+        // assert
+        assert_eq!(parsed[1].currency, "USD");
+        assert_eq!(parsed[1].amount_minor, 100);
+        assert_eq!(parsed[1].description, None);
+        assert_eq!(
+            unspecified.message(),
+            "leg 1: entry_type must be debit or credit"
+        );
+        assert!(
+            invalid_account
+                .message()
+                .starts_with("invalid legs[0].account_id")
+        );
+    }
+}
@@ -0,0 +1,10 @@
+/// Balance is the balance of an account in one currency. The posted balance is credits minus
+/// debits, the available balance also takes off the live holds debiting the account.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct Balance {
+    pub account_id: uuid::Uuid,
+    pub currency: String,
+    /// amount_minor is the posted balance.
+    pub amount_minor: i64,
+    pub available_minor: i64,
+}
@@ -0,0 +1,259 @@
+use chrono::{Datelike, TimeZone, Timelike};
+use std::str::FromStr;
+
+/// SEARCH_YEARS bounds the search for the next match, expressions such as `0 0 30 2 *` never
+/// match.
+const SEARCH_YEARS: i64 = 8;
+
+/// Cron is a five field cron expression, `minute hour day-of-month month day-of-week`, in UTC.
+/// Fields take `*`, numbers, ranges `a-b`, lists `a,b` and steps `*/n` or `a-b/n`. Day of week
+/// runs from 0 (Sunday) to 7 (Sunday again). When both day fields are restricted a day matching
+/// either is a match, as in cron.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cron {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    days_restricted: bool,
+    weekdays_restricted: bool,
+}
+
+impl FromStr for Cron {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = s.split_whitespace().collect();
+        let [minutes, hours, days, months, weekdays] = fields.as_slice() else {
+            anyhow::bail!("expected 5 fields, got {}", fields.len());
+        };
+
+        let mut weekdays_mask = parse_field(weekdays, 0, 7, "day of week")?;
+        // 7 is Sunday like 0
+        if weekdays_mask & (1 << 7) != 0 {
+            weekdays_mask |= 1;
+        }
+
+        Ok(Cron {
+            minutes: parse_field(minutes, 0, 59, "minute")?,
+            hours: parse_field(hours, 0, 23, "hour")?,
+            days: parse_field(days, 1, 31, "day of month")?,
+            months: parse_field(months, 1, 12, "month")?,
+            weekdays: weekdays_mask,
+            days_restricted: *days != "*",
+            weekdays_restricted: *weekdays != "*",
+        })
+    }
+}
+
+/// parse_field turns one field into a bit mask of the values it matches.
+fn parse_field(field: &str, min: u32, max: u32, name: &str) -> anyhow::Result<u64> {
+    let mut mask = 0u64;
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => match step.parse::<u32>() {
+                Ok(step) if step > 0 => (range, step),
+                _ => anyhow::bail!("invalid {name} step in {item}"),
+            },
+            None => (item, 1),
+        };
+        let value = |value: &str| match value.parse::<u32>() {
+            Ok(value) if (min..=max).contains(&value) => Ok(value),
+            _ => Err(anyhow::anyhow!(
+                "invalid {name} {value}, expected {min} to {max}"
+            )),
+        };
+        let (from, to) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((from, to)) => (value(from)?, value(to)?),
+            // a single value with a step runs to the end of the field
+            None if item.contains('/') => (value(range)?, max),
+            None => (value(range)?, value(range)?),
+        };
+        if from > to {
+            anyhow::bail!("invalid {name} range {range}");
+        }
+        for value in (from..=to).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+
+    Ok(mask)
+}
+
+impl Cron {
+    /// next_after returns the first minute strictly after `after` the expression matches, None
+    /// when it does not match in the next years.
+    pub fn next_after(
+        &self,
+        after: chrono::DateTime<chrono::Utc>,
+    ) -> Option<chrono::DateTime<chrono::Utc>> {
+        let limit = after + chrono::Duration::days(366 * SEARCH_YEARS);
+        let mut next = after.with_second(0)?.with_nanosecond(0)? + chrono::Duration::minutes(1);
+
+        while next <= limit {
+            if !matches(self.months, next.month()) {
+                let (year, month) = match next.month() {
+                    12 => (next.year() + 1, 1),
+                    month => (next.year(), month + 1),
+                };
+                next = chrono::Utc
+                    .with_ymd_and_hms(year, month, 1, 0, 0, 0)
+                    .single()?;
+                continue;
+            }
+            if !self.matches_day(next) {
+                next = (next + chrono::Duration::days(1))
+                    .with_hour(0)?
+                    .with_minute(0)?;
+                continue;
+            }
+            if !matches(self.hours, next.hour()) {
+                next = next.with_minute(0)? + chrono::Duration::hours(1);
+                continue;
+            }
+            if !matches(self.minutes, next.minute()) {
+                next += chrono::Duration::minutes(1);
+                continue;
+            }
+
+            return Some(next);
+        }
+
+        None
+    }
+
+    fn matches_day(&self, at: chrono::DateTime<chrono::Utc>) -> bool {
+        let day = matches(self.days, at.day());
+        let weekday = matches(self.weekdays, at.weekday().num_days_from_sunday());
+
+        match (self.days_restricted, self.weekdays_restricted) {
+            (true, true) => day || weekday,
+            (true, false) => day,
+            (false, true) => weekday,
+            (false, false) => true,
+        }
+    }
+}
+
+fn matches(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> chrono::DateTime<chrono::Utc> {
+        chrono::DateTime::parse_from_rfc3339(s)
+            .unwrap()
+            .with_timezone(&chrono::Utc)
+    }
+
+    #[test]
+    fn test_next_after() {
+        let test_cases = vec![
+            (
+                "every minute",
+                "* * * * *",
+                "2025-11-05T10:00:30Z",
+                Some("2025-11-05T10:01:00Z"),
+            ),
+            (
+                "strictly after a match",
+                "0 9 * * *",
+                "2025-11-05T09:00:00Z",
+                Some("2025-11-06T09:00:00Z"),
+            ),
+            (
+                "later the same day",
+                "30 14 * * *",
+                "2025-11-05T09:00:00Z",
+                Some("2025-11-05T14:30:00Z"),
+            ),
+            (
+                "every 15 minutes",
+                "*/15 * * * *",
+                "2025-11-05T10:16:00Z",
+                Some("2025-11-05T10:30:00Z"),
+            ),
+            (
+                "list of hours",
+                "0 8,20 * * *",
+                "2025-11-05T08:00:00Z",
+                Some("2025-11-05T20:00:00Z"),
+            ),
+            (
+                "first of the month",
+                "0 0 1 * *",
+                "2025-11-05T00:00:00Z",
+                Some("2025-12-01T00:00:00Z"),
+            ),
+            (
+                "over the year end",
+                "0 0 1 1 *",
+                "2025-11-05T00:00:00Z",
+                Some("2026-01-01T00:00:00Z"),
+            ),
+            (
+                "weekdays",
+                "0 9 * * 1-5",
+                "2025-11-07T10:00:00Z",
+                Some("2025-11-10T09:00:00Z"),
+            ),
+            (
+                "sunday as 7",
+                "0 9 * * 7",
+                "2025-11-05T10:00:00Z",
+                Some("2025-11-09T09:00:00Z"),
+            ),
+            (
+                "day of month or week",
+                "0 0 15 * 1",
+                "2025-11-05T00:00:00Z",
+                Some("2025-11-10T00:00:00Z"),
+            ),
+            (
+                "last day of february in a leap year",
+                "0 0 29 2 *",
+                "2025-11-05T00:00:00Z",
+                Some("2028-02-29T00:00:00Z"),
+            ),
+            ("never", "0 0 30 2 *", "2025-11-05T00:00:00Z", None),
+        ];
+
+        for (name, expression, after, expected) in test_cases {
+            // arrange
+            let cron = Cron::from_str(expression).unwrap();
+
+            // act
+            let next = cron.next_after(at(after));
+
+            // assert
+            assert_eq!(next, expected.map(at), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_from_str_errors() {
+        let test_cases = vec![
+            ("too few fields", "* * * *"),
+            ("minute out of range", "60 * * * *"),
+            ("day of month 0", "0 0 0 * *"),
+            ("month out of range", "0 0 1 13 *"),
+            ("day of week out of range", "0 0 * * 8"),
+            ("zero step", "*/0 * * * *"),
+            ("descending range", "0 10-8 * * *"),
+            ("not a number", "0 noon * * *"),
+        ];
+
+        for (name, expression) in test_cases {
+            // act
+            let result = Cron::from_str(expression);
+
+            // assert
+            assert!(result.is_err(), "{name}");
+        }
+    }
+}
@@ -1,10 +1,20 @@
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "entry_type", rename_all = "lowercase")]
 pub enum Type {
     Credit,
     Debit,
 }
 
-#[derive(Debug, Clone)]
+impl AsRef<str> for Type {
+    fn as_ref(&self) -> &str {
+        match self {
+            Type::Credit => "credit",
+            Type::Debit => "debit",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
 pub struct Entry {
     pub id: uuid::Uuid,
     pub transaction_id: uuid::Uuid,
@@ -12,5 +22,11 @@ pub struct Entry {
     pub entry_type: Type,
     pub amount_minor: i64,
     pub currency: String,
+    /// refund_id is set on reversing entries posted for a refund.
+    pub refund_id: Option<uuid::Uuid>,
+    /// description is set on journal legs which were given one.
+    pub description: Option<String>,
+    /// leg is the position of a journal leg in its journal entry.
+    pub leg: Option<i32>,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
@@ -0,0 +1,339 @@
+use crate::domain::money::is_currency;
+use std::collections::HashSet;
+use std::fmt;
+
+/// Tier prices transactions in one currency once the merchant's volume in the calendar month
+/// reached `min_monthly_volume_minor`. The fee is `percentage_bps` of the amount plus
+/// `fixed_minor`.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct Tier {
+    pub currency: String,
+    pub min_monthly_volume_minor: i64,
+    pub percentage_bps: i32,
+    pub fixed_minor: i64,
+}
+
+impl Tier {
+    /// fee rounds the percentage half up and never takes more than the amount.
+    pub fn fee(&self, amount_minor: i64) -> i64 {
+        let percentage = (amount_minor as i128 * self.percentage_bps as i128 + 5_000) / 10_000;
+        (percentage + self.fixed_minor as i128).min(amount_minor as i128) as i64
+    }
+}
+
+/// FeeSchedule is one version of the fees charged to a merchant. A new version applies to
+/// transactions created from its `effective_from`, earlier versions are kept as history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeSchedule {
+    pub id: uuid::Uuid,
+    pub merchant_account_id: uuid::Uuid,
+    /// version is assigned when the schedule is stored, counting from 1 per merchant.
+    pub version: i32,
+    pub tiers: Vec<Tier>,
+    pub effective_from: chrono::DateTime<chrono::Utc>,
+    pub created_by: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl FeeSchedule {
+    /// new creates a schedule effective from `effective_from`, or straight away when unset.
+    pub fn new(
+        merchant_account_id: uuid::Uuid,
+        tiers: Vec<Tier>,
+        effective_from: Option<chrono::DateTime<chrono::Utc>>,
+        created_by: impl Into<String>,
+    ) -> Self {
+        let now = chrono::Utc::now();
+        FeeSchedule {
+            id: uuid::Uuid::new_v4(),
+            merchant_account_id,
+            version: 0,
+            tiers,
+            effective_from: effective_from.unwrap_or(now),
+            created_by: created_by.into(),
+            created_at: now,
+        }
+    }
+
+    /// validate checks every tier and that every currency has a tier from a volume of 0, so
+    /// each transaction in a priced currency gets a fee.
+    pub fn validate(&self) -> Result<(), FeeError> {
+        if self.tiers.is_empty() {
+            return Err(FeeError::NoTiers);
+        }
+        if self.effective_from < self.created_at {
+            return Err(FeeError::Backdated(self.effective_from));
+        }
+
+        let mut seen = HashSet::new();
+        for (index, tier) in self.tiers.iter().enumerate() {
+            let invalid = |reason: &str| FeeError::InvalidTier {
+                tier: index,
+                reason: reason.to_string(),
+            };
+            if !is_currency(&tier.currency) {
+                return Err(invalid("invalid currency"));
+            }
+            if !(0..=10_000).contains(&tier.percentage_bps) {
+                return Err(invalid("percentage_bps must be between 0 and 10000"));
+            }
+            if tier.fixed_minor < 0 {
+                return Err(invalid("fixed fee must not be negative"));
+            }
+            if tier.min_monthly_volume_minor < 0 {
+                return Err(invalid("min_monthly_volume must not be negative"));
+            }
+            if !seen.insert((tier.currency.as_str(), tier.min_monthly_volume_minor)) {
+                return Err(invalid("another tier starts at the same volume"));
+            }
+        }
+
+        match self
+            .tiers
+            .iter()
+            .find(|tier| !seen.contains(&(tier.currency.as_str(), 0)))
+        {
+            Some(tier) => Err(FeeError::MissingBaseTier(tier.currency.clone())),
+            None => Ok(()),
+        }
+    }
+
+    /// tier returns the tier of the currency with the highest volume threshold the monthly
+    /// volume reached, None when the schedule does not price the currency.
+    pub fn tier(&self, currency: &str, monthly_volume_minor: i64) -> Option<&Tier> {
+        self.tiers
+            .iter()
+            .filter(|tier| {
+                tier.currency == currency && tier.min_monthly_volume_minor <= monthly_volume_minor
+            })
+            .max_by_key(|tier| tier.min_monthly_volume_minor)
+    }
+}
+
+/// Fee is charged to the credited merchant of a transaction and posted to the revenue account
+/// under the same transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fee {
+    pub fee_schedule_id: uuid::Uuid,
+    pub revenue_account_id: uuid::Uuid,
+    pub amount_minor: i64,
+    /// monthly_volume_minor is the merchant's volume in the month before the transaction,
+    /// which picked the tier.
+    pub monthly_volume_minor: i64,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FeeError {
+    NoTiers,
+    /// InvalidTier names the offending tier by its position, starting at 0.
+    InvalidTier {
+        tier: usize,
+        reason: String,
+    },
+    /// MissingBaseTier is returned when a currency has no tier starting at a volume of 0.
+    MissingBaseTier(String),
+    /// Backdated is returned when a schedule would apply to transactions already created.
+    Backdated(chrono::DateTime<chrono::Utc>),
+    /// EffectiveBeforeLatest is returned when a schedule would take effect before the latest
+    /// version of the merchant.
+    EffectiveBeforeLatest {
+        effective_from: chrono::DateTime<chrono::Utc>,
+        latest: chrono::DateTime<chrono::Utc>,
+    },
+    UnknownAccount(uuid::Uuid),
+    NotMerchant(uuid::Uuid),
+}
+
+impl fmt::Display for FeeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FeeError::NoTiers => write!(f, "fee schedule needs at least one tier"),
+            FeeError::InvalidTier { tier, reason } => write!(f, "tier {tier}: {reason}"),
+            FeeError::MissingBaseTier(currency) => {
+                write!(
+                    f,
+                    "{currency} has no tier starting at a monthly volume of 0"
+                )
+            }
+            FeeError::Backdated(effective_from) => write!(
+                f,
+                "fee schedule cannot take effect in the past, got {}",
+                effective_from.to_rfc3339()
+            ),
+            FeeError::EffectiveBeforeLatest {
+                effective_from,
+                latest,
+            } => write!(
+                f,
+                "fee schedule effective from {} would take effect before the latest version, effective from {}",
+                effective_from.to_rfc3339(),
+                latest.to_rfc3339()
+            ),
+            FeeError::UnknownAccount(id) => {
+                write!(f, "account {id} is not registered with the ledger")
+            }
+            FeeError::NotMerchant(id) => write!(f, "account {id} is not a merchant account"),
+        }
+    }
+}
+
+impl std::error::Error for FeeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tier(currency: &str, min_monthly_volume_minor: i64, percentage_bps: i32) -> Tier {
+        Tier {
+            currency: currency.to_string(),
+            min_monthly_volume_minor,
+            percentage_bps,
+            fixed_minor: 30,
+        }
+    }
+
+    #[test]
+    fn test_tier_fee() {
+        let test_cases = vec![
+            // 2.9% of 10.00 plus 0.30
+            (tier("USD", 0, 290), 1000, 59),
+            // 1.25% of 0.02 rounds to 0, the fixed fee is capped at the amount
+            (tier("USD", 0, 125), 2, 2),
+            // 2.5% of 0.20 is half a minor unit, rounded up to 1 before the cap
+            (tier("USD", 0, 250), 20, 20),
+            (
+                Tier {
+                    fixed_minor: 0,
+                    ..tier("USD", 0, 150)
+                },
+                1000,
+                15,
+            ),
+        ];
+
+        for (tier, amount_minor, expected) in test_cases {
+            assert_eq!(
+                tier.fee(amount_minor),
+                expected,
+                "{tier:?} of {amount_minor}"
+            );
+        }
+    }
+
+    #[test]
+    fn successfully_pick_tier_by_monthly_volume() {
+        // arrange
+        let schedule = FeeSchedule::new(
+            uuid::Uuid::new_v4(),
+            vec![
+                tier("USD", 0, 290),
+                tier("USD", 1_000_000, 250),
+                tier("USD", 5_000_000, 200),
+                tier("EUR", 0, 300),
+            ],
+            None,
+            "ops",
+        );
+
+        // act
+        let tiers = [
+            schedule.tier("USD", 0),
+            schedule.tier("USD", 999_999),
+            schedule.tier("USD", 1_000_000),
+            schedule.tier("USD", 9_000_000),
+            schedule.tier("EUR", 9_000_000),
+            schedule.tier("GBP", 0),
+        ];
+
+        // assert
+        assert_eq!(
+            tiers.map(|tier| tier.map(|tier| tier.percentage_bps)),
+            [Some(290), Some(290), Some(250), Some(200), Some(300), None]
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            tiers: Vec<Tier>,
+            effective_from: Option<chrono::DateTime<chrono::Utc>>,
+            expected: Result<(), FeeError>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "successfully validate tiered schedule",
+                tiers: vec![tier("USD", 0, 290), tier("USD", 1_000_000, 250)],
+                effective_from: Some(chrono::Utc::now() + chrono::Duration::days(1)),
+                expected: Ok(()),
+            },
+            TestCase {
+                name: "error when there are no tiers",
+                tiers: vec![],
+                effective_from: None,
+                expected: Err(FeeError::NoTiers),
+            },
+            TestCase {
+                name: "error when percentage is above 100%",
+                tiers: vec![tier("USD", 0, 290), tier("USD", 10, 10_001)],
+                effective_from: None,
+                expected: Err(FeeError::InvalidTier {
+                    tier: 1,
+                    reason: "percentage_bps must be between 0 and 10000".to_string(),
+                }),
+            },
+            TestCase {
+                name: "error when two tiers start at the same volume",
+                tiers: vec![tier("USD", 0, 290), tier("USD", 0, 250)],
+                effective_from: None,
+                expected: Err(FeeError::InvalidTier {
+                    tier: 1,
+                    reason: "another tier starts at the same volume".to_string(),
+                }),
+            },
+            TestCase {
+                name: "error when a currency has no base tier",
+                tiers: vec![tier("USD", 0, 290), tier("EUR", 100, 250)],
+                effective_from: None,
+                expected: Err(FeeError::MissingBaseTier("EUR".to_string())),
+            },
+        ];
+
+        for test_case in test_cases {
+            // arrange
+            let schedule = FeeSchedule::new(
+                uuid::Uuid::new_v4(),
+                test_case.tiers,
+                test_case.effective_from,
+                "ops",
+            );
+
+            // act
+            let result = schedule.validate();
+
+            // assert
+            assert_eq!(result, test_case.expected, "{}", test_case.name);
+        }
+    }
+
+    #[test]
+    fn error_when_schedule_is_backdated() {
+        // arrange
+        let effective_from = chrono::Utc::now() - chrono::Duration::hours(1);
+        let schedule = FeeSchedule::new(
+            uuid::Uuid::new_v4(),
+            vec![tier("USD", 0, 290)],
+            Some(effective_from),
+            "ops",
+        );
+
+        // act
+        let result = schedule.validate();
+
+        // assert
+        assert_eq!(result, Err(FeeError::Backdated(effective_from)));
+    }
+}
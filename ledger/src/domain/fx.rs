@@ -0,0 +1,468 @@
+use crate::domain::money::Currency;
+use crate::domain::transaction::Transaction;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::fmt;
+use std::str::FromStr;
+
+/// RoundingMode rounds a converted amount to whole minor units of the credit currency.
+#[derive(Debug, Clone, Copy, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "fx_rounding_mode", rename_all = "snake_case")]
+pub enum RoundingMode {
+    /// HalfUp rounds halves away from zero.
+    HalfUp,
+    /// HalfEven rounds halves to the even neighbour.
+    HalfEven,
+    /// Down truncates towards zero, the credited account never gets more than the rate gives.
+    Down,
+    Up,
+}
+
+impl AsRef<str> for RoundingMode {
+    fn as_ref(&self) -> &str {
+        match self {
+            RoundingMode::HalfUp => "half_up",
+            RoundingMode::HalfEven => "half_even",
+            RoundingMode::Down => "down",
+            RoundingMode::Up => "up",
+        }
+    }
+}
+
+impl FromStr for RoundingMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "half_up" => Ok(RoundingMode::HalfUp),
+            "half_even" => Ok(RoundingMode::HalfEven),
+            "down" => Ok(RoundingMode::Down),
+            "up" => Ok(RoundingMode::Up),
+            _ => Err(anyhow::anyhow!(
+                "unknown rounding mode {s}, expected half_up, half_even, down or up"
+            )),
+        }
+    }
+}
+
+impl RoundingMode {
+    fn strategy(&self) -> RoundingStrategy {
+        match self {
+            RoundingMode::HalfUp => RoundingStrategy::MidpointAwayFromZero,
+            RoundingMode::HalfEven => RoundingStrategy::MidpointNearestEven,
+            RoundingMode::Down => RoundingStrategy::ToZero,
+            RoundingMode::Up => RoundingStrategy::AwayFromZero,
+        }
+    }
+}
+
+/// Rate is the price of one unit of `base` in units of `quote` as quoted by a rate provider.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rate {
+    pub base: String,
+    pub quote: String,
+    pub rate: Decimal,
+    /// source names the provider the rate was taken from.
+    pub source: String,
+    pub as_of: chrono::DateTime<chrono::Utc>,
+}
+
+impl Rate {
+    /// inverse quotes the rate the other way round, e.g. EUR/USD from USD/EUR.
+    pub fn inverse(&self) -> Result<Rate, FxError> {
+        let rate = Decimal::ONE
+            .checked_div(self.rate)
+            .ok_or(FxError::InvalidRate(self.rate))?;
+
+        Ok(Rate {
+            base: self.quote.clone(),
+            quote: self.base.clone(),
+            rate,
+            source: self.source.clone(),
+            as_of: self.as_of,
+        })
+    }
+}
+
+/// convert turns an amount in minor units of `from` into minor units of `to` at `rate`,
+/// rounding to whole minor units.
+pub fn convert(
+    amount_minor: i64,
+    from: &str,
+    to: &str,
+    rate: Decimal,
+    rounding_mode: RoundingMode,
+) -> Result<i64, FxError> {
+    let from = Currency::from_code(from).map_err(|_| FxError::UnknownCurrency(from.to_string()))?;
+    let to = Currency::from_code(to).map_err(|_| FxError::UnknownCurrency(to.to_string()))?;
+
+    Decimal::from(amount_minor)
+        .checked_mul(rate)
+        .and_then(|amount| amount.checked_mul(Decimal::from(to.minor_per_unit())))
+        .and_then(|amount| amount.checked_div(Decimal::from(from.minor_per_unit())))
+        .map(|amount| amount.round_dp_with_strategy(0, rounding_mode.strategy()))
+        .and_then(|amount| amount.to_i64())
+        .ok_or(FxError::Overflow)
+}
+
+/// Conversion is the FX leg of a cross-currency transaction. The debited account pays the
+/// transaction amount to the FX account of the transaction currency, the FX account of the
+/// credit currency pays `credit_amount_minor` to the credited account.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct Conversion {
+    pub transaction_id: uuid::Uuid,
+    pub debit_fx_account_id: uuid::Uuid,
+    pub credit_fx_account_id: uuid::Uuid,
+    pub credit_amount_minor: i64,
+    pub credit_currency: String,
+    /// rate is the snapshot quoted by the provider, kept for audit.
+    pub rate: Decimal,
+    /// applied_rate is the rate after the spread, the one the amount was converted at.
+    pub applied_rate: Decimal,
+    pub spread_bps: i32,
+    pub rounding_mode: RoundingMode,
+    pub rate_source: String,
+    pub rate_as_of: chrono::DateTime<chrono::Utc>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Conversion {
+    /// new converts the transaction amount at the rate less the spread.
+    pub fn new(
+        transaction: &Transaction,
+        rate: &Rate,
+        spread_bps: i32,
+        rounding_mode: RoundingMode,
+        debit_fx_account_id: uuid::Uuid,
+        credit_fx_account_id: uuid::Uuid,
+    ) -> Result<Conversion, FxError> {
+        if rate.rate <= Decimal::ZERO {
+            return Err(FxError::InvalidRate(rate.rate));
+        }
+        if !(0..10_000).contains(&spread_bps) {
+            return Err(FxError::InvalidSpread(spread_bps));
+        }
+
+        let applied_rate = rate.rate * Decimal::from(10_000 - spread_bps) / Decimal::from(10_000);
+        let credit_amount_minor = convert(
+            transaction.amount_minor,
+            transaction.currency.as_str(),
+            rate.quote.as_str(),
+            applied_rate,
+            rounding_mode,
+        )?;
+        if credit_amount_minor <= 0 {
+            return Err(FxError::AmountTooSmall {
+                amount_minor: transaction.amount_minor,
+                currency: transaction.currency.clone(),
+            });
+        }
+
+        Ok(Conversion {
+            transaction_id: transaction.id,
+            debit_fx_account_id,
+            credit_fx_account_id,
+            credit_amount_minor,
+            credit_currency: rate.quote.clone(),
+            rate: rate.rate,
+            applied_rate,
+            spread_bps,
+            rounding_mode,
+            rate_source: rate.source.clone(),
+            rate_as_of: rate.as_of,
+            created_at: transaction.created_at,
+        })
+    }
+
+    /// convert converts a part of the transaction amount, e.g. a refund, at the applied rate.
+    pub fn convert(&self, amount_minor: i64, currency: &str) -> Result<i64, FxError> {
+        convert(
+            amount_minor,
+            currency,
+            self.credit_currency.as_str(),
+            self.applied_rate,
+            self.rounding_mode,
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FxError {
+    /// NotConfigured is returned when a cross-currency transfer is requested without a rate
+    /// provider.
+    NotConfigured,
+    UnknownCurrency(String),
+    RateUnavailable {
+        base: String,
+        quote: String,
+    },
+    InvalidRate(Decimal),
+    InvalidSpread(i32),
+    /// NoFxAccount is returned when no FX account is configured for the currency.
+    NoFxAccount(String),
+    /// AmountTooSmall is returned when the amount converts to less than one minor unit.
+    AmountTooSmall {
+        amount_minor: i64,
+        currency: String,
+    },
+    Overflow,
+}
+
+impl fmt::Display for FxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FxError::NotConfigured => write!(f, "cross-currency transfers are not enabled"),
+            FxError::UnknownCurrency(currency) => write!(f, "unknown currency {currency}"),
+            FxError::RateUnavailable { base, quote } => {
+                write!(f, "no {base}/{quote} rate available")
+            }
+            FxError::InvalidRate(rate) => write!(f, "rate must be positive, got {rate}"),
+            FxError::InvalidSpread(spread_bps) => {
+                write!(f, "spread must be between 0 and 9999 bps, got {spread_bps}")
+            }
+            FxError::NoFxAccount(currency) => {
+                write!(f, "no FX account is configured for {currency}")
+            }
+            FxError::AmountTooSmall {
+                amount_minor,
+                currency,
+            } => write!(
+                f,
+                "{amount_minor} minor units of {currency} convert to less than one minor unit"
+            ),
+            FxError::Overflow => write!(f, "converted amount is out of range"),
+        }
+    }
+}
+
+impl std::error::Error for FxError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate(base: &str, quote: &str, rate: &str) -> Rate {
+        Rate {
+            base: base.to_string(),
+            quote: quote.to_string(),
+            rate: Decimal::from_str(rate).unwrap(),
+            source: "test".to_string(),
+            as_of: chrono::Utc::now(),
+        }
+    }
+
+    fn transaction(amount_minor: i64, currency: &str) -> Transaction {
+        Transaction::new(
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+            amount_minor,
+            currency,
+            "key-1",
+            chrono::Utc::now(),
+        )
+    }
+
+    #[test]
+    fn test_conversion() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            transaction: Transaction,
+            rate: Rate,
+            spread_bps: i32,
+            rounding_mode: RoundingMode,
+            expected: Result<(i64, &'static str), FxError>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "converts at the quoted rate",
+                transaction: transaction(10_000, "EUR"),
+                rate: rate("EUR", "USD", "1.0850"),
+                spread_bps: 0,
+                rounding_mode: RoundingMode::HalfUp,
+                expected: Ok((10_850, "1.0850")),
+            },
+            TestCase {
+                name: "takes the spread off the rate",
+                transaction: transaction(10_000, "EUR"),
+                rate: rate("EUR", "USD", "1.0850"),
+                spread_bps: 100,
+                rounding_mode: RoundingMode::HalfUp,
+                expected: Ok((10_742, "1.074150")),
+            },
+            TestCase {
+                name: "rounds halves away from zero",
+                transaction: transaction(15, "EUR"),
+                rate: rate("EUR", "USD", "1.1"),
+                spread_bps: 0,
+                rounding_mode: RoundingMode::HalfUp,
+                expected: Ok((17, "1.1")),
+            },
+            TestCase {
+                name: "rounds halves to even",
+                transaction: transaction(15, "EUR"),
+                rate: rate("EUR", "USD", "1.1"),
+                spread_bps: 0,
+                rounding_mode: RoundingMode::HalfEven,
+                expected: Ok((16, "1.1")),
+            },
+            TestCase {
+                name: "truncates when rounding down",
+                transaction: transaction(5, "EUR"),
+                rate: rate("EUR", "USD", "1.19"),
+                spread_bps: 0,
+                rounding_mode: RoundingMode::Down,
+                expected: Ok((5, "1.19")),
+            },
+            TestCase {
+                name: "rounds any fraction up",
+                transaction: transaction(5, "EUR"),
+                rate: rate("EUR", "USD", "1.01"),
+                spread_bps: 0,
+                rounding_mode: RoundingMode::Up,
+                expected: Ok((6, "1.01")),
+            },
+            TestCase {
+                name: "scales to a currency without minor units",
+                transaction: transaction(1_000, "USD"),
+                rate: rate("USD", "JPY", "151.37"),
+                spread_bps: 0,
+                rounding_mode: RoundingMode::HalfUp,
+                expected: Ok((1_514, "151.37")),
+            },
+            TestCase {
+                name: "scales to a currency with three decimals",
+                transaction: transaction(1_000, "USD"),
+                rate: rate("USD", "KWD", "0.3071"),
+                spread_bps: 0,
+                rounding_mode: RoundingMode::HalfUp,
+                expected: Ok((3_071, "0.3071")),
+            },
+            TestCase {
+                name: "rejects amounts converting to nothing",
+                transaction: transaction(1, "JPY"),
+                rate: rate("JPY", "USD", "0.0066"),
+                spread_bps: 0,
+                rounding_mode: RoundingMode::Down,
+                expected: Err(FxError::AmountTooSmall {
+                    amount_minor: 1,
+                    currency: "JPY".to_string(),
+                }),
+            },
+            TestCase {
+                name: "rejects a spread taking the whole rate",
+                transaction: transaction(10_000, "EUR"),
+                rate: rate("EUR", "USD", "1.0850"),
+                spread_bps: 10_000,
+                rounding_mode: RoundingMode::HalfUp,
+                expected: Err(FxError::InvalidSpread(10_000)),
+            },
+            TestCase {
+                name: "rejects a zero rate",
+                transaction: transaction(10_000, "EUR"),
+                rate: rate("EUR", "USD", "0"),
+                spread_bps: 0,
+                rounding_mode: RoundingMode::HalfUp,
+                expected: Err(FxError::InvalidRate(Decimal::ZERO)),
+            },
+            TestCase {
+                name: "rejects amounts out of range",
+                transaction: transaction(i64::MAX, "EUR"),
+                rate: rate("EUR", "JPY", "160"),
+                spread_bps: 0,
+                rounding_mode: RoundingMode::HalfUp,
+                expected: Err(FxError::Overflow),
+            },
+        ];
+
+        for test_case in test_cases {
+            let (debit_fx, credit_fx) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+
+            // act
+            let result = Conversion::new(
+                &test_case.transaction,
+                &test_case.rate,
+                test_case.spread_bps,
+                test_case.rounding_mode,
+                debit_fx,
+                credit_fx,
+            );
+
+            // assert
+            match test_case.expected {
+                Ok((credit_amount_minor, applied_rate)) => {
+                    let conversion = result.unwrap();
+                    assert_eq!(
+                        conversion.credit_amount_minor, credit_amount_minor,
+                        "{}",
+                        test_case.name
+                    );
+                    assert_eq!(
+                        conversion.applied_rate,
+                        Decimal::from_str(applied_rate).unwrap(),
+                        "{}",
+                        test_case.name
+                    );
+                    assert_eq!(conversion.rate, test_case.rate.rate, "{}", test_case.name);
+                    assert_eq!(
+                        conversion.credit_currency, test_case.rate.quote,
+                        "{}",
+                        test_case.name
+                    );
+                    assert_eq!(
+                        (
+                            conversion.debit_fx_account_id,
+                            conversion.credit_fx_account_id
+                        ),
+                        (debit_fx, credit_fx),
+                        "{}",
+                        test_case.name
+                    );
+                }
+                Err(expected) => {
+                    assert_eq!(result.unwrap_err(), expected, "{}", test_case.name)
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn successfully_convert_a_partial_amount_at_the_applied_rate() {
+        // arrange
+        let conversion = Conversion::new(
+            &transaction(10_000, "EUR"),
+            &rate("EUR", "USD", "1.0850"),
+            100,
+            RoundingMode::HalfUp,
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+        )
+        .unwrap();
+
+        // act
+        let full = conversion.convert(10_000, "EUR").unwrap();
+        let partial = conversion.convert(2_500, "EUR").unwrap();
+
+        // assert
+        assert_eq!(full, conversion.credit_amount_minor);
+        assert_eq!(partial, 2_685);
+    }
+
+    #[test]
+    fn successfully_invert_a_rate() {
+        // arrange
+        let quoted = rate("USD", "EUR", "0.8");
+
+        // act
+        let inverse = quoted.inverse().unwrap();
+
+        // assert
+        assert_eq!(
+            (inverse.base.as_str(), inverse.quote.as_str()),
+            ("EUR", "USD")
+        );
+        assert_eq!(inverse.rate, Decimal::from_str("1.25").unwrap());
+        assert_eq!(inverse.as_of, quoted.as_of);
+    }
+}
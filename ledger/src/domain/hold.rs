@@ -0,0 +1,294 @@
+use crate::domain::money::is_currency;
+use crate::domain::transaction::Transaction;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "hold_status", rename_all = "lowercase")]
+pub enum Status {
+    /// Authorized holds reserve their amount on the debited account until they are captured,
+    /// voided or expire.
+    Authorized,
+    Captured,
+    Voided,
+    Expired,
+}
+
+impl AsRef<str> for Status {
+    fn as_ref(&self) -> &str {
+        match self {
+            Status::Authorized => "authorized",
+            Status::Captured => "captured",
+            Status::Voided => "voided",
+            Status::Expired => "expired",
+        }
+    }
+}
+
+/// Hold reserves an amount on the debited account for a later transfer to the credited account,
+/// e.g. a card authorization. It lowers the available balance of the debited account but posts
+/// nothing until it is captured.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct Hold {
+    pub id: uuid::Uuid,
+    pub debit_account_id: uuid::Uuid,
+    pub credit_account_id: uuid::Uuid,
+    pub amount_minor: i64,
+    pub currency: String,
+    pub status: Status,
+    pub idempotency_key: String,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    /// captured_amount_minor is the amount transferred on capture, the rest is released.
+    pub captured_amount_minor: Option<i64>,
+    /// transaction_id is the transaction the hold was captured into.
+    pub transaction_id: Option<uuid::Uuid>,
+    pub request_timestamp: chrono::DateTime<chrono::Utc>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Hold {
+    pub fn new(
+        debit_account_id: uuid::Uuid,
+        credit_account_id: uuid::Uuid,
+        amount_minor: i64,
+        currency: impl Into<String>,
+        idempotency_key: impl Into<String>,
+        ttl: chrono::Duration,
+        request_timestamp: chrono::DateTime<chrono::Utc>,
+    ) -> Self {
+        let now = chrono::Utc::now();
+        // A ttl past the supported date range leaves the hold expired on creation, which
+        // validate rejects as an invalid ttl.
+        let expires_at = now.checked_add_signed(ttl).unwrap_or(now);
+        Hold {
+            id: uuid::Uuid::new_v4(),
+            debit_account_id,
+            credit_account_id,
+            amount_minor,
+            currency: currency.into(),
+            status: Status::Authorized,
+            idempotency_key: idempotency_key.into(),
+            expires_at,
+            captured_amount_minor: None,
+            transaction_id: None,
+            request_timestamp,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), HoldError> {
+        if self.amount_minor <= 0 {
+            return Err(HoldError::InvalidAmount(self.amount_minor));
+        }
+        if self.debit_account_id == self.credit_account_id {
+            return Err(HoldError::SameAccount(self.debit_account_id));
+        }
+        if !is_currency(&self.currency) {
+            return Err(HoldError::InvalidCurrency(self.currency.clone()));
+        }
+        if self.expires_at <= self.created_at {
+            return Err(HoldError::InvalidTtl);
+        }
+
+        Ok(())
+    }
+
+    /// same_request is true when `other` reserves the same amount between the same accounts,
+    /// so a retry carrying the same idempotency key can be answered with the first hold.
+    pub fn same_request(&self, other: &Hold) -> bool {
+        self.debit_account_id == other.debit_account_id
+            && self.credit_account_id == other.credit_account_id
+            && self.amount_minor == other.amount_minor
+            && self.currency == other.currency
+    }
+
+    /// is_live is true while the hold reserves its amount.
+    pub fn is_live(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.status == Status::Authorized && self.expires_at > now
+    }
+
+    /// capture_transaction is the transfer of `amount_minor` the hold is captured into. Its
+    /// idempotency key is derived from the hold, so a hold is captured at most once.
+    pub fn capture_transaction(&self, amount_minor: i64) -> Transaction {
+        Transaction::new(
+            self.debit_account_id,
+            self.credit_account_id,
+            amount_minor,
+            self.currency.as_str(),
+            format!("hold-{}-capture", self.id),
+            chrono::Utc::now(),
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HoldError {
+    NotFound(uuid::Uuid),
+    InvalidAmount(i64),
+    InvalidCurrency(String),
+    InvalidTtl,
+    SameAccount(uuid::Uuid),
+    /// UnknownAccount is returned when one of the accounts is not registered with the ledger.
+    UnknownAccount,
+    /// NotAuthorized is returned when a hold which was already captured, voided or expired is
+    /// captured or voided.
+    NotAuthorized(uuid::Uuid, Status),
+    /// Expired is returned when a hold past its expiry, not yet swept, is captured.
+    Expired(uuid::Uuid),
+    CurrencyMismatch {
+        expected: String,
+        got: String,
+    },
+    /// ExceedsHold is returned when a capture is for more than the held amount.
+    ExceedsHold {
+        amount_minor: i64,
+        held_minor: i64,
+    },
+    IdempotencyKeyReused(String),
+}
+
+impl fmt::Display for HoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HoldError::NotFound(id) => write!(f, "hold {id} not found"),
+            HoldError::InvalidAmount(amount) => {
+                write!(f, "amount must be positive, got {amount}")
+            }
+            HoldError::InvalidCurrency(currency) => write!(f, "invalid currency {currency:?}"),
+            HoldError::InvalidTtl => write!(f, "ttl must be positive and within range"),
+            HoldError::SameAccount(id) => {
+                write!(f, "debit and credit account must differ, got {id} for both")
+            }
+            HoldError::UnknownAccount => write!(f, "account is not registered with the ledger"),
+            HoldError::NotAuthorized(id, status) => {
+                write!(f, "hold {id} is {}, not authorized", status.as_ref())
+            }
+            HoldError::Expired(id) => write!(f, "hold {id} has expired"),
+            HoldError::CurrencyMismatch { expected, got } => {
+                write!(
+                    f,
+                    "currency {got} does not match the hold currency {expected}"
+                )
+            }
+            HoldError::ExceedsHold {
+                amount_minor,
+                held_minor,
+            } => write!(
+                f,
+                "capture of {amount_minor} exceeds the held amount of {held_minor}"
+            ),
+            HoldError::IdempotencyKeyReused(key) => write!(
+                f,
+                "idempotency key {key} was already used for a different hold"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for HoldError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            hold: Hold,
+            expected: Result<(), HoldError>,
+        }
+
+        let (debit_account_id, credit_account_id) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        let hold = |amount_minor: i64, currency: &str, ttl_seconds: i64| {
+            Hold::new(
+                debit_account_id,
+                credit_account_id,
+                amount_minor,
+                currency,
+                "key",
+                chrono::Duration::seconds(ttl_seconds),
+                chrono::Utc::now(),
+            )
+        };
+
+        let test_cases = vec![
+            TestCase {
+                name: "successfully validate hold",
+                hold: hold(1000, "USD", 60),
+                expected: Ok(()),
+            },
+            TestCase {
+                name: "error when amount is not positive",
+                hold: hold(0, "USD", 60),
+                expected: Err(HoldError::InvalidAmount(0)),
+            },
+            TestCase {
+                name: "error when currency is invalid",
+                hold: hold(1000, "usd", 60),
+                expected: Err(HoldError::InvalidCurrency("usd".to_string())),
+            },
+            TestCase {
+                name: "error when ttl is not positive",
+                hold: hold(1000, "USD", 0),
+                expected: Err(HoldError::InvalidTtl),
+            },
+            TestCase {
+                name: "error when ttl overflows the date range",
+                hold: hold(1000, "USD", 1_000_000_000_000_000),
+                expected: Err(HoldError::InvalidTtl),
+            },
+            TestCase {
+                name: "error when both accounts are the same",
+                hold: Hold {
+                    credit_account_id: debit_account_id,
+                    ..hold(1000, "USD", 60)
+                },
+                expected: Err(HoldError::SameAccount(debit_account_id)),
+            },
+        ];
+
+        for test_case in test_cases {
+            // act
+            let result = test_case.hold.validate();
+
+            // assert
+            assert_eq!(result, test_case.expected, "{}", test_case.name);
+        }
+    }
+
+    #[test]
+    fn successfully_derive_capture_transaction() {
+        // arrange
+        let hold = Hold::new(
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+            1000,
+            "USD",
+            "key",
+            chrono::Duration::hours(1),
+            chrono::Utc::now(),
+        );
+
+        // act
+        let transaction = hold.capture_transaction(600);
+
+        // assert
+        assert_eq!(
+            (
+                transaction.debit_account_id,
+                transaction.credit_account_id,
+                transaction.amount_minor,
+            ),
+            (hold.debit_account_id, hold.credit_account_id, 600)
+        );
+        assert_eq!(
+            transaction.idempotency_key,
+            format!("hold-{}-capture", hold.id)
+        );
+        assert!(hold.is_live(chrono::Utc::now()));
+        assert!(!hold.is_live(hold.expires_at));
+    }
+}
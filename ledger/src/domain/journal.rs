@@ -0,0 +1,302 @@
+use crate::domain::entry::Type;
+use crate::domain::money::is_currency;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Leg is one posting of a journal entry. Amounts are positive, the entry type says which side
+/// of the account they are posted to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Leg {
+    pub account_id: uuid::Uuid,
+    pub entry_type: Type,
+    pub amount_minor: i64,
+    pub currency: String,
+    pub description: Option<String>,
+}
+
+/// JournalEntry posts any number of legs across accounts under one transaction, e.g. a payment
+/// split between the merchant, a platform fee and tax.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JournalEntry {
+    pub id: uuid::Uuid,
+    pub idempotency_key: String,
+    pub description: Option<String>,
+    pub legs: Vec<Leg>,
+    pub request_timestamp: chrono::DateTime<chrono::Utc>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl JournalEntry {
+    pub fn new(
+        idempotency_key: impl Into<String>,
+        description: Option<String>,
+        legs: Vec<Leg>,
+        request_timestamp: chrono::DateTime<chrono::Utc>,
+    ) -> Self {
+        JournalEntry {
+            id: uuid::Uuid::new_v4(),
+            idempotency_key: idempotency_key.into(),
+            description,
+            legs,
+            request_timestamp,
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    /// validate checks every leg on its own, then that debits equal credits in every currency.
+    pub fn validate(&self) -> Result<(), JournalError> {
+        if self.legs.len() < 2 {
+            return Err(JournalError::TooFewLegs(self.legs.len()));
+        }
+
+        let mut balances: BTreeMap<&str, (i64, Vec<usize>)> = BTreeMap::new();
+        for (index, leg) in self.legs.iter().enumerate() {
+            if leg.amount_minor <= 0 {
+                return Err(JournalError::InvalidAmount {
+                    leg: index,
+                    amount_minor: leg.amount_minor,
+                });
+            }
+            if !is_currency(&leg.currency) {
+                return Err(JournalError::InvalidCurrency {
+                    leg: index,
+                    currency: leg.currency.clone(),
+                });
+            }
+
+            let (balance, legs) = balances.entry(leg.currency.as_str()).or_default();
+            let amount_minor = match leg.entry_type {
+                Type::Debit => leg.amount_minor,
+                Type::Credit => -leg.amount_minor,
+            };
+            *balance = match balance.checked_add(amount_minor) {
+                Some(balance) => balance,
+                None => {
+                    return Err(JournalError::InvalidAmount {
+                        leg: index,
+                        amount_minor: leg.amount_minor,
+                    });
+                }
+            };
+            legs.push(index);
+        }
+
+        match balances.into_iter().find(|(_, (balance, _))| *balance != 0) {
+            Some((currency, (difference_minor, legs))) => Err(JournalError::Unbalanced {
+                currency: currency.to_string(),
+                difference_minor,
+                legs,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// same_request is true when `other` posts the same legs, so a retry carrying the same
+    /// idempotency key can be answered with the journal entry posted first.
+    pub fn same_request(&self, other: &JournalEntry) -> bool {
+        self.legs.len() == other.legs.len()
+            && self.legs.iter().zip(other.legs.iter()).all(|(a, b)| {
+                a.account_id == b.account_id
+                    && a.entry_type == b.entry_type
+                    && a.amount_minor == b.amount_minor
+                    && a.currency == b.currency
+            })
+    }
+}
+
+/// JournalError names the offending leg by its position in the request, starting at 0.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JournalError {
+    NotFound(uuid::Uuid),
+    TooFewLegs(usize),
+    InvalidAmount {
+        leg: usize,
+        amount_minor: i64,
+    },
+    InvalidCurrency {
+        leg: usize,
+        currency: String,
+    },
+    /// UnknownAccount is returned when the account of a leg is not registered with the ledger.
+    UnknownAccount {
+        leg: usize,
+        account_id: uuid::Uuid,
+    },
+    /// Unbalanced is returned when debits minus credits in a currency is not zero, `legs` are
+    /// the legs in that currency.
+    Unbalanced {
+        currency: String,
+        difference_minor: i64,
+        legs: Vec<usize>,
+    },
+    /// IdempotencyKeyReused is returned when a key is sent again for different legs.
+    IdempotencyKeyReused(String),
+}
+
+impl fmt::Display for JournalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JournalError::NotFound(id) => write!(f, "journal entry {id} not found"),
+            JournalError::TooFewLegs(count) => {
+                write!(f, "journal entry needs at least 2 legs, got {count}")
+            }
+            JournalError::InvalidAmount { leg, amount_minor } => {
+                write!(f, "leg {leg}: amount must be positive, got {amount_minor}")
+            }
+            JournalError::InvalidCurrency { leg, currency } => {
+                write!(f, "leg {leg}: invalid currency {currency:?}")
+            }
+            JournalError::UnknownAccount { leg, account_id } => write!(
+                f,
+                "leg {leg}: account {account_id} is not registered with the ledger"
+            ),
+            JournalError::Unbalanced {
+                currency,
+                difference_minor,
+                legs,
+            } => write!(
+                f,
+                "legs {} in {currency} do not balance, {} by {}",
+                legs.iter()
+                    .map(|leg| leg.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                match *difference_minor > 0 {
+                    true => "debits exceed credits",
+                    false => "credits exceed debits",
+                },
+                difference_minor.unsigned_abs()
+            ),
+            JournalError::IdempotencyKeyReused(key) => write!(
+                f,
+                "idempotency key {key} was already used for a different journal entry"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for JournalError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leg(entry_type: Type, amount_minor: i64, currency: &str) -> Leg {
+        Leg {
+            account_id: uuid::Uuid::new_v4(),
+            entry_type,
+            amount_minor,
+            currency: currency.to_string(),
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            legs: Vec<Leg>,
+            expected: Result<(), JournalError>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "successfully validate payment with fee and tax",
+                legs: vec![
+                    leg(Type::Debit, 1000, "USD"),
+                    leg(Type::Credit, 900, "USD"),
+                    leg(Type::Credit, 80, "USD"),
+                    leg(Type::Credit, 20, "USD"),
+                ],
+                expected: Ok(()),
+            },
+            TestCase {
+                name: "successfully validate legs balancing per currency",
+                legs: vec![
+                    leg(Type::Debit, 1000, "USD"),
+                    leg(Type::Credit, 1000, "USD"),
+                    leg(Type::Debit, 920, "EUR"),
+                    leg(Type::Credit, 920, "EUR"),
+                ],
+                expected: Ok(()),
+            },
+            TestCase {
+                name: "error when there is a single leg",
+                legs: vec![leg(Type::Debit, 1000, "USD")],
+                expected: Err(JournalError::TooFewLegs(1)),
+            },
+            TestCase {
+                name: "error when a leg amount is not positive",
+                legs: vec![leg(Type::Debit, 1000, "USD"), leg(Type::Credit, 0, "USD")],
+                expected: Err(JournalError::InvalidAmount {
+                    leg: 1,
+                    amount_minor: 0,
+                }),
+            },
+            TestCase {
+                name: "error when a leg currency is invalid",
+                legs: vec![leg(Type::Debit, 1000, "USD"), leg(Type::Credit, 1000, "us")],
+                expected: Err(JournalError::InvalidCurrency {
+                    leg: 1,
+                    currency: "us".to_string(),
+                }),
+            },
+            TestCase {
+                name: "error when currencies balance only in total",
+                legs: vec![
+                    leg(Type::Debit, 1000, "USD"),
+                    leg(Type::Credit, 1000, "EUR"),
+                    leg(Type::Debit, 5, "GBP"),
+                    leg(Type::Credit, 5, "GBP"),
+                ],
+                expected: Err(JournalError::Unbalanced {
+                    currency: "EUR".to_string(),
+                    difference_minor: -1000,
+                    legs: vec![1],
+                }),
+            },
+        ];
+
+        for test_case in test_cases {
+            // arrange
+            let journal_entry = JournalEntry::new("key", None, test_case.legs, chrono::Utc::now());
+
+            // act
+            let result = journal_entry.validate();
+
+            // assert
+            assert_eq!(result, test_case.expected, "{}", test_case.name);
+        }
+    }
+
+    #[test]
+    fn successfully_name_offending_legs() {
+        assert_eq!(
+            JournalError::InvalidAmount {
+                leg: 2,
+                amount_minor: -5
+            }
+            .to_string(),
+            "leg 2: amount must be positive, got -5"
+        );
+        assert_eq!(
+            JournalError::Unbalanced {
+                currency: "USD".to_string(),
+                difference_minor: 20,
+                legs: vec![0, 1, 3],
+            }
+            .to_string(),
+            "legs 0, 1, 3 in USD do not balance, debits exceed credits by 20"
+        );
+        assert_eq!(
+            JournalError::Unbalanced {
+                currency: "EUR".to_string(),
+                difference_minor: -1000,
+                legs: vec![1],
+            }
+            .to_string(),
+            "legs 1 in EUR do not balance, credits exceed debits by 1000"
+        );
+    }
+}
@@ -1,2 +1,11 @@
+pub mod balance;
+pub mod cron;
 pub mod entry;
+pub mod fee;
+pub mod fx;
+pub mod hold;
+pub mod journal;
+pub mod money;
+pub mod refund;
+pub mod schedule;
 pub mod transaction;
@@ -0,0 +1,119 @@
+pub use common::money::{Currency, Money, MoneyError};
+
+/// to_minor_units converts a `google.type.Money` amount to minor units of its currency, e.g.
+/// 1050 for 10.50 USD and 1050 for 1050 JPY. Fractions of a minor unit are rejected.
+pub fn to_minor_units(units: i64, nanos: i32, currency: &str) -> Result<i64, MoneyError> {
+    Money::from_units_nanos(units, nanos, currency).map(|money| money.amount_minor())
+}
+
+/// from_minor_units converts minor units of the currency back to `google.type.Money` units and
+/// nanos. Codes outside ISO 4217 are rejected, their minor unit is unknown.
+pub fn from_minor_units(amount_minor: i64, currency: &str) -> Result<(i64, i32), MoneyError> {
+    Money::from_minor(amount_minor, currency).map(|money| money.to_units_nanos())
+}
+
+/// is_currency is true for ISO 4217 codes in upper case, the form the ledger stores.
+pub fn is_currency(code: &str) -> bool {
+    Currency::from_code(code).is_ok_and(|currency| currency.code() == code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minor_units_round_trip() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            units: i64,
+            nanos: i32,
+            currency: &'static str,
+            expected: i64,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "whole units",
+                units: 10,
+                nanos: 0,
+                currency: "USD",
+                expected: 1000,
+            },
+            TestCase {
+                name: "units and cents",
+                units: 10,
+                nanos: 500_000_000,
+                currency: "USD",
+                expected: 1050,
+            },
+            TestCase {
+                name: "negative amount",
+                units: -1,
+                nanos: -250_000_000,
+                currency: "USD",
+                expected: -125,
+            },
+            TestCase {
+                name: "currency without minor unit",
+                units: 1050,
+                nanos: 0,
+                currency: "JPY",
+                expected: 1050,
+            },
+            TestCase {
+                name: "currency with three decimals",
+                units: 1,
+                nanos: 5_000_000,
+                currency: "KWD",
+                expected: 1005,
+            },
+        ];
+
+        for test_case in test_cases {
+            let minor = to_minor_units(test_case.units, test_case.nanos, test_case.currency);
+            assert_eq!(minor, Ok(test_case.expected), "{}", test_case.name);
+            assert_eq!(
+                from_minor_units(test_case.expected, test_case.currency),
+                Ok((test_case.units, test_case.nanos)),
+                "{}",
+                test_case.name
+            );
+        }
+    }
+
+    #[test]
+    fn error_when_amount_has_fractions_of_a_minor_unit() {
+        assert_eq!(
+            to_minor_units(10, 500_000_000, "JPY"),
+            Err(MoneyError::ExcessPrecision {
+                currency: "JPY".to_string(),
+                exponent: 0
+            })
+        );
+        assert_eq!(
+            to_minor_units(10, 1_000_000, "USD"),
+            Err(MoneyError::ExcessPrecision {
+                currency: "USD".to_string(),
+                exponent: 2
+            })
+        );
+    }
+
+    #[test]
+    fn error_when_stored_currency_is_not_iso_4217() {
+        assert_eq!(
+            from_minor_units(1050, "ABC"),
+            Err(MoneyError::UnknownCurrency("ABC".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_is_currency() {
+        assert!(is_currency("USD"));
+        assert!(is_currency("JPY"));
+        assert!(!is_currency("usd"));
+        assert!(!is_currency("ABC"));
+        assert!(!is_currency("US"));
+    }
+}
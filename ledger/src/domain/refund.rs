@@ -0,0 +1,145 @@
+use crate::domain::transaction;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "refund_status", rename_all = "snake_case")]
+pub enum Status {
+    /// AwaitingApproval is a manual refund above the approval threshold waiting for a second
+    /// approver.
+    AwaitingApproval,
+    /// Approved refunds have been published and wait for the refund processor.
+    Approved,
+    Rejected,
+    Pending,
+    Succeeded,
+    Failed,
+}
+
+impl AsRef<str> for Status {
+    fn as_ref(&self) -> &str {
+        match self {
+            Status::AwaitingApproval => "awaiting_approval",
+            Status::Approved => "approved",
+            Status::Rejected => "rejected",
+            Status::Pending => "pending",
+            Status::Succeeded => "succeeded",
+            Status::Failed => "failed",
+        }
+    }
+}
+
+/// Refund is a full or partial refund of a transaction. Its id is the id of the refund event and
+/// is used as the idempotency key with the PSP.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct Refund {
+    pub id: uuid::Uuid,
+    pub transaction_id: uuid::Uuid,
+    pub amount_minor: i64,
+    pub currency: String,
+    pub reason: String,
+    pub status: Status,
+    pub psp_reference: Option<String>,
+    pub failure_reason: Option<String>,
+    /// requested_by is the requester of a manual refund, automatic refunds have none.
+    pub requested_by: Option<String>,
+    /// approved_by is the second approver, or the rejecter, of a manual refund.
+    pub approved_by: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Refund {
+    pub fn new(
+        id: uuid::Uuid,
+        transaction_id: uuid::Uuid,
+        amount_minor: i64,
+        currency: impl Into<String>,
+        reason: impl Into<String>,
+    ) -> Self {
+        let now = chrono::Utc::now();
+        Refund {
+            id,
+            transaction_id,
+            amount_minor,
+            currency: currency.into(),
+            reason: reason.into(),
+            status: Status::Pending,
+            psp_reference: None,
+            failure_reason: None,
+            requested_by: None,
+            approved_by: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RefundError {
+    TransactionNotFound(uuid::Uuid),
+    RefundNotFound(uuid::Uuid),
+    NotRefundable(uuid::Uuid, transaction::Status),
+    InvalidAmount(i64),
+    /// NotApproved is returned when a manual refund is processed before being approved.
+    NotApproved(uuid::Uuid, Status),
+    /// AlreadyDecided is returned when approving or rejecting a refund which is not awaiting
+    /// approval.
+    AlreadyDecided(uuid::Uuid, Status),
+    /// SelfApproval is returned when the requester tries to approve their own refund.
+    SelfApproval(uuid::Uuid),
+    CurrencyMismatch {
+        expected: String,
+        actual: String,
+    },
+    /// ExceedsOriginal is returned when the refund would take cumulative refunds above the
+    /// original transaction amount.
+    ExceedsOriginal {
+        transaction_id: uuid::Uuid,
+        requested_minor: i64,
+        remaining_minor: i64,
+    },
+}
+
+impl fmt::Display for RefundError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RefundError::TransactionNotFound(id) => write!(f, "transaction {id} not found"),
+            RefundError::RefundNotFound(id) => write!(f, "refund {id} not found"),
+            RefundError::NotRefundable(id, status) => {
+                write!(
+                    f,
+                    "transaction {id} in status {} cannot be refunded",
+                    status.as_ref()
+                )
+            }
+            RefundError::InvalidAmount(amount) => {
+                write!(f, "refund amount must be positive, got {amount}")
+            }
+            RefundError::NotApproved(id, status) => {
+                write!(f, "refund {id} is {} and not approved", status.as_ref())
+            }
+            RefundError::AlreadyDecided(id, status) => {
+                write!(f, "refund {id} is already {}", status.as_ref())
+            }
+            RefundError::SelfApproval(id) => {
+                write!(
+                    f,
+                    "refund {id} must be approved by someone other than the requester"
+                )
+            }
+            RefundError::CurrencyMismatch { expected, actual } => {
+                write!(f, "refund currency {actual} does not match {expected}")
+            }
+            RefundError::ExceedsOriginal {
+                transaction_id,
+                requested_minor,
+                remaining_minor,
+            } => write!(
+                f,
+                "refund of {requested_minor} exceeds the {remaining_minor} left to refund on transaction {transaction_id}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RefundError {}
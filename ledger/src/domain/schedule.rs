@@ -0,0 +1,754 @@
+use crate::domain::cron::Cron;
+use crate::domain::money::is_currency;
+use crate::domain::transaction::Transaction;
+use std::fmt;
+use std::str::FromStr;
+
+/// MAX_INTERVAL_SECONDS caps intervals at about ten years, so occurrences stay well within the
+/// supported date range.
+pub const MAX_INTERVAL_SECONDS: i64 = 10 * 366 * 24 * 60 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "schedule_status", rename_all = "lowercase")]
+pub enum Status {
+    Active,
+    /// Paused schedules keep their occurrences but run none until resumed.
+    Paused,
+    Cancelled,
+    /// Completed schedules have no occurrence left before their end.
+    Completed,
+}
+
+impl AsRef<str> for Status {
+    fn as_ref(&self) -> &str {
+        match self {
+            Status::Active => "active",
+            Status::Paused => "paused",
+            Status::Cancelled => "cancelled",
+            Status::Completed => "completed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "schedule_run_status", rename_all = "lowercase")]
+pub enum RunStatus {
+    /// Pending runs are claimed and their transaction is being created.
+    Pending,
+    Succeeded,
+    Failed,
+    Skipped,
+}
+
+impl AsRef<str> for RunStatus {
+    fn as_ref(&self) -> &str {
+        match self {
+            RunStatus::Pending => "pending",
+            RunStatus::Succeeded => "succeeded",
+            RunStatus::Failed => "failed",
+            RunStatus::Skipped => "skipped",
+        }
+    }
+}
+
+/// Schedule transfers the amount between the accounts on every occurrence from `starts_at`
+/// until `ends_at`, e.g. a standing order or a subscription. It recurs on either a cron
+/// expression or a fixed interval.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct Schedule {
+    pub id: uuid::Uuid,
+    pub debit_account_id: uuid::Uuid,
+    pub credit_account_id: uuid::Uuid,
+    pub amount_minor: i64,
+    pub currency: String,
+    pub cron: Option<String>,
+    pub interval_seconds: Option<i64>,
+    pub starts_at: chrono::DateTime<chrono::Utc>,
+    pub ends_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// next_run_at is the next occurrence to run, None once cancelled or completed.
+    pub next_run_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub status: Status,
+    pub idempotency_key: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Schedule {
+    /// new creates a schedule starting at `starts_at`, or straight away when unset.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        debit_account_id: uuid::Uuid,
+        credit_account_id: uuid::Uuid,
+        amount_minor: i64,
+        currency: impl Into<String>,
+        cron: Option<String>,
+        interval_seconds: Option<i64>,
+        starts_at: Option<chrono::DateTime<chrono::Utc>>,
+        ends_at: Option<chrono::DateTime<chrono::Utc>>,
+        idempotency_key: impl Into<String>,
+    ) -> Self {
+        let now = chrono::Utc::now();
+        Schedule {
+            id: uuid::Uuid::new_v4(),
+            debit_account_id,
+            credit_account_id,
+            amount_minor,
+            currency: currency.into(),
+            cron,
+            interval_seconds,
+            starts_at: starts_at.unwrap_or(now),
+            ends_at,
+            next_run_at: None,
+            status: Status::Active,
+            idempotency_key: idempotency_key.into(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), ScheduleError> {
+        if self.amount_minor <= 0 {
+            return Err(ScheduleError::InvalidAmount(self.amount_minor));
+        }
+        if self.debit_account_id == self.credit_account_id {
+            return Err(ScheduleError::SameAccount(self.debit_account_id));
+        }
+        if !is_currency(&self.currency) {
+            return Err(ScheduleError::InvalidCurrency(self.currency.clone()));
+        }
+        match (&self.cron, self.interval_seconds) {
+            (Some(cron), None) => {
+                if let Err(e) = Cron::from_str(cron) {
+                    return Err(ScheduleError::InvalidRecurrence(format!(
+                        "invalid cron {cron:?}: {e}"
+                    )));
+                }
+            }
+            (None, Some(interval_seconds)) if interval_seconds <= 0 => {
+                return Err(ScheduleError::InvalidRecurrence(format!(
+                    "interval must be positive, got {interval_seconds} seconds"
+                )));
+            }
+            (None, Some(interval_seconds)) if interval_seconds > MAX_INTERVAL_SECONDS => {
+                return Err(ScheduleError::InvalidRecurrence(format!(
+                    "interval must be at most {MAX_INTERVAL_SECONDS} seconds, got {interval_seconds} seconds"
+                )));
+            }
+            (None, Some(_)) => {}
+            _ => {
+                return Err(ScheduleError::InvalidRecurrence(
+                    "exactly one of cron and interval must be set".to_string(),
+                ));
+            }
+        }
+        if let Some(ends_at) = self.ends_at
+            && ends_at <= self.starts_at
+        {
+            return Err(ScheduleError::EndsBeforeStart);
+        }
+
+        Ok(())
+    }
+
+    /// same_request is true when `other` asks for the same recurring transfer, so a retry
+    /// carrying the same idempotency key can be answered with the first schedule.
+    pub fn same_request(&self, other: &Schedule) -> bool {
+        self.debit_account_id == other.debit_account_id
+            && self.credit_account_id == other.credit_account_id
+            && self.amount_minor == other.amount_minor
+            && self.currency.eq_ignore_ascii_case(other.currency.as_str())
+            && self.cron == other.cron
+            && self.interval_seconds == other.interval_seconds
+            && self.ends_at == other.ends_at
+    }
+
+    /// next_occurrence returns the first occurrence at or after `starts_at` and strictly after
+    /// `after`, None when there is none before `ends_at`.
+    pub fn next_occurrence(
+        &self,
+        after: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Option<chrono::DateTime<chrono::Utc>>, ScheduleError> {
+        let next = match (&self.cron, self.interval_seconds) {
+            (Some(cron), _) => {
+                let cron = Cron::from_str(cron)
+                    .map_err(|e| ScheduleError::InvalidRecurrence(e.to_string()))?;
+                let before_start = self.starts_at - chrono::Duration::nanoseconds(1);
+                cron.next_after(after.map_or(before_start, |after| after.max(before_start)))
+            }
+            (None, Some(interval_seconds)) => match after {
+                Some(after) if after >= self.starts_at => {
+                    let next = (after - self.starts_at)
+                        .num_seconds()
+                        .checked_div(interval_seconds)
+                        .and_then(|elapsed| elapsed.checked_add(1))
+                        .and_then(|occurrences| occurrences.checked_mul(interval_seconds))
+                        .and_then(chrono::Duration::try_seconds)
+                        .and_then(|offset| self.starts_at.checked_add_signed(offset));
+                    match next {
+                        Some(next) => Some(next),
+                        None => {
+                            return Err(ScheduleError::InvalidRecurrence(format!(
+                                "interval of {interval_seconds} seconds is out of range"
+                            )));
+                        }
+                    }
+                }
+                _ => Some(self.starts_at),
+            },
+            (None, None) => {
+                return Err(ScheduleError::InvalidRecurrence(
+                    "exactly one of cron and interval must be set".to_string(),
+                ));
+            }
+        };
+
+        Ok(next.filter(|next| self.ends_at.is_none_or(|ends_at| *next <= ends_at)))
+    }
+
+    /// run_transaction is the transfer of one occurrence. Its idempotency key is derived from
+    /// the schedule and the occurrence, so an occurrence is charged at most once however often
+    /// it is run.
+    pub fn run_transaction(&self, occurrence_at: chrono::DateTime<chrono::Utc>) -> Transaction {
+        Transaction::new(
+            self.debit_account_id,
+            self.credit_account_id,
+            self.amount_minor,
+            self.currency.as_str(),
+            format!("schedule-{}-{}", self.id, occurrence_at.timestamp()),
+            occurrence_at,
+        )
+    }
+}
+
+impl Schedule {
+    /// pause stops an active schedule from running until it is resumed.
+    pub fn pause(&mut self) -> Result<(), ScheduleError> {
+        self.check_transition(&[Status::Active], "paused")?;
+        self.status = Status::Paused;
+
+        Ok(())
+    }
+
+    /// resume reactivates a paused schedule from its next occurrence after `now`, occurrences
+    /// missed while it was paused are not run. A schedule without one left completes.
+    pub fn resume(&mut self, now: chrono::DateTime<chrono::Utc>) -> Result<(), ScheduleError> {
+        self.check_transition(&[Status::Paused], "resumed")?;
+        self.next_run_at = match self.next_run_at {
+            Some(next_run_at) if next_run_at > now => Some(next_run_at),
+            _ => self.next_occurrence(Some(now))?,
+        };
+        self.status = match self.next_run_at {
+            Some(_) => Status::Active,
+            None => Status::Completed,
+        };
+
+        Ok(())
+    }
+
+    /// cancel stops the schedule for good.
+    pub fn cancel(&mut self) -> Result<(), ScheduleError> {
+        self.check_transition(&[Status::Active, Status::Paused], "cancelled")?;
+        self.status = Status::Cancelled;
+        self.next_run_at = None;
+
+        Ok(())
+    }
+
+    /// skip moves the schedule past its next occurrence without running it, and returns the
+    /// skipped occurrence. Skipping the last occurrence completes the schedule.
+    pub fn skip(&mut self) -> Result<chrono::DateTime<chrono::Utc>, ScheduleError> {
+        self.check_transition(&[Status::Active, Status::Paused], "skipped")?;
+        let Some(skipped) = self.next_run_at else {
+            return Err(ScheduleError::NoOccurrence);
+        };
+        self.next_run_at = self.next_occurrence(Some(skipped))?;
+        if self.next_run_at.is_none() {
+            self.status = Status::Completed;
+        }
+
+        Ok(skipped)
+    }
+
+    fn check_transition(&self, from: &[Status], action: &'static str) -> Result<(), ScheduleError> {
+        match from.contains(&self.status) {
+            true => Ok(()),
+            false => Err(ScheduleError::InvalidTransition {
+                id: self.id,
+                status: self.status,
+                action,
+            }),
+        }
+    }
+}
+
+/// Run is one occurrence of a schedule and its outcome.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct Run {
+    pub id: uuid::Uuid,
+    pub schedule_id: uuid::Uuid,
+    pub occurrence_at: chrono::DateTime<chrono::Utc>,
+    pub status: RunStatus,
+    /// transaction_id is the transaction a succeeded run created.
+    pub transaction_id: Option<uuid::Uuid>,
+    /// error is why a failed run was not charged.
+    pub error: Option<String>,
+    /// claimed_until is when the lease of the worker executing a pending run expires.
+    pub claimed_until: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Run {
+    pub fn new(
+        schedule_id: uuid::Uuid,
+        occurrence_at: chrono::DateTime<chrono::Utc>,
+        status: RunStatus,
+    ) -> Self {
+        let now = chrono::Utc::now();
+        Run {
+            id: uuid::Uuid::new_v4(),
+            schedule_id,
+            occurrence_at,
+            status,
+            transaction_id: None,
+            error: None,
+            claimed_until: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScheduleError {
+    NotFound(uuid::Uuid),
+    InvalidAmount(i64),
+    InvalidCurrency(String),
+    SameAccount(uuid::Uuid),
+    /// InvalidRecurrence is returned for a bad cron expression or interval, or when both or
+    /// neither are set.
+    InvalidRecurrence(String),
+    EndsBeforeStart,
+    /// NoOccurrence is returned when a schedule would never run before its end.
+    NoOccurrence,
+    /// UnknownAccount is returned when one of the accounts is not registered with the ledger.
+    UnknownAccount,
+    /// InvalidTransition is returned when a schedule cannot be paused, resumed, cancelled or
+    /// skipped in its status.
+    InvalidTransition {
+        id: uuid::Uuid,
+        status: Status,
+        action: &'static str,
+    },
+    IdempotencyKeyReused(String),
+}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScheduleError::NotFound(id) => write!(f, "schedule {id} not found"),
+            ScheduleError::InvalidAmount(amount) => {
+                write!(f, "amount must be positive, got {amount}")
+            }
+            ScheduleError::InvalidCurrency(currency) => {
+                write!(f, "invalid currency {currency:?}")
+            }
+            ScheduleError::SameAccount(id) => {
+                write!(f, "debit and credit account must differ, got {id} for both")
+            }
+            ScheduleError::InvalidRecurrence(reason) => write!(f, "{reason}"),
+            ScheduleError::EndsBeforeStart => write!(f, "schedule must end after it starts"),
+            ScheduleError::NoOccurrence => {
+                write!(f, "schedule has no occurrence before it ends")
+            }
+            ScheduleError::UnknownAccount => {
+                write!(f, "account is not registered with the ledger")
+            }
+            ScheduleError::InvalidTransition { id, status, action } => write!(
+                f,
+                "schedule {id} is {} and cannot be {action}",
+                status.as_ref()
+            ),
+            ScheduleError::IdempotencyKeyReused(key) => write!(
+                f,
+                "idempotency key {key} was already used for a different schedule"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ScheduleError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> chrono::DateTime<chrono::Utc> {
+        chrono::DateTime::parse_from_rfc3339(s)
+            .unwrap()
+            .with_timezone(&chrono::Utc)
+    }
+
+    fn schedule(cron: Option<&str>, interval_seconds: Option<i64>) -> Schedule {
+        Schedule::new(
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+            1000,
+            "USD",
+            cron.map(str::to_string),
+            interval_seconds,
+            Some(at("2025-11-05T10:00:00Z")),
+            Some(at("2025-11-05T12:00:00Z")),
+            "key-1",
+        )
+    }
+
+    #[test]
+    fn test_validate() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            schedule: Schedule,
+            expected: Result<(), ScheduleError>,
+        }
+
+        let valid = schedule(Some("0 * * * *"), None);
+        let test_cases = vec![
+            TestCase {
+                name: "valid cron schedule",
+                schedule: valid.clone(),
+                expected: Ok(()),
+            },
+            TestCase {
+                name: "valid interval schedule",
+                schedule: schedule(None, Some(3600)),
+                expected: Ok(()),
+            },
+            TestCase {
+                name: "zero amount",
+                schedule: Schedule {
+                    amount_minor: 0,
+                    ..valid.clone()
+                },
+                expected: Err(ScheduleError::InvalidAmount(0)),
+            },
+            TestCase {
+                name: "same account",
+                schedule: Schedule {
+                    credit_account_id: valid.debit_account_id,
+                    ..valid.clone()
+                },
+                expected: Err(ScheduleError::SameAccount(valid.debit_account_id)),
+            },
+            TestCase {
+                name: "unknown currency",
+                schedule: Schedule {
+                    currency: "XYZ".to_string(),
+                    ..valid.clone()
+                },
+                expected: Err(ScheduleError::InvalidCurrency("XYZ".to_string())),
+            },
+            TestCase {
+                name: "both cron and interval",
+                schedule: schedule(Some("0 * * * *"), Some(3600)),
+                expected: Err(ScheduleError::InvalidRecurrence(
+                    "exactly one of cron and interval must be set".to_string(),
+                )),
+            },
+            TestCase {
+                name: "neither cron nor interval",
+                schedule: schedule(None, None),
+                expected: Err(ScheduleError::InvalidRecurrence(
+                    "exactly one of cron and interval must be set".to_string(),
+                )),
+            },
+            TestCase {
+                name: "negative interval",
+                schedule: schedule(None, Some(-60)),
+                expected: Err(ScheduleError::InvalidRecurrence(
+                    "interval must be positive, got -60 seconds".to_string(),
+                )),
+            },
+            TestCase {
+                name: "interval longer than ten years",
+                schedule: schedule(None, Some(MAX_INTERVAL_SECONDS + 1)),
+                expected: Err(ScheduleError::InvalidRecurrence(format!(
+                    "interval must be at most {MAX_INTERVAL_SECONDS} seconds, got {} seconds",
+                    MAX_INTERVAL_SECONDS + 1
+                ))),
+            },
+            TestCase {
+                name: "ends before it starts",
+                schedule: Schedule {
+                    ends_at: Some(valid.starts_at),
+                    ..valid.clone()
+                },
+                expected: Err(ScheduleError::EndsBeforeStart),
+            },
+        ];
+
+        for test_case in test_cases {
+            // act
+            let result = test_case.schedule.validate();
+
+            // assert
+            assert_eq!(result, test_case.expected, "{}", test_case.name);
+        }
+        assert!(matches!(
+            schedule(Some("0 25 * * *"), None).validate(),
+            Err(ScheduleError::InvalidRecurrence(_))
+        ));
+    }
+
+    #[test]
+    fn test_next_occurrence() {
+        let test_cases = vec![
+            (
+                "first cron occurrence is at the start",
+                schedule(Some("0 * * * *"), None),
+                None,
+                Some("2025-11-05T10:00:00Z"),
+            ),
+            (
+                "cron occurrence after the last",
+                schedule(Some("0 * * * *"), None),
+                Some("2025-11-05T10:00:00Z"),
+                Some("2025-11-05T11:00:00Z"),
+            ),
+            (
+                "cron occurrence at the end",
+                schedule(Some("0 * * * *"), None),
+                Some("2025-11-05T11:00:00Z"),
+                Some("2025-11-05T12:00:00Z"),
+            ),
+            (
+                "no cron occurrence after the end",
+                schedule(Some("0 * * * *"), None),
+                Some("2025-11-05T12:00:00Z"),
+                None,
+            ),
+            (
+                "first interval occurrence is at the start",
+                schedule(None, Some(1800)),
+                None,
+                Some("2025-11-05T10:00:00Z"),
+            ),
+            (
+                "interval occurrence after the last",
+                schedule(None, Some(1800)),
+                Some("2025-11-05T10:00:00Z"),
+                Some("2025-11-05T10:30:00Z"),
+            ),
+            (
+                "interval occurrence between two",
+                schedule(None, Some(1800)),
+                Some("2025-11-05T10:45:00Z"),
+                Some("2025-11-05T11:00:00Z"),
+            ),
+            (
+                "interval occurrence before the start",
+                schedule(None, Some(1800)),
+                Some("2025-11-01T00:00:00Z"),
+                Some("2025-11-05T10:00:00Z"),
+            ),
+            (
+                "no interval occurrence after the end",
+                schedule(None, Some(1800)),
+                Some("2025-11-05T12:00:00Z"),
+                None,
+            ),
+        ];
+
+        for (name, schedule, after, expected) in test_cases {
+            // act
+            let next = schedule.next_occurrence(after.map(at)).unwrap();
+
+            // assert
+            assert_eq!(next, expected.map(at), "{name}");
+        }
+    }
+
+    #[test]
+    fn error_when_interval_occurrence_is_out_of_range() {
+        for interval_seconds in [1_000_000_000_000_000, i64::MAX] {
+            // arrange
+            let schedule = Schedule {
+                ends_at: None,
+                ..schedule(None, Some(interval_seconds))
+            };
+
+            // act
+            let result = schedule.next_occurrence(Some(schedule.starts_at));
+
+            // assert
+            assert_eq!(
+                result,
+                Err(ScheduleError::InvalidRecurrence(format!(
+                    "interval of {interval_seconds} seconds is out of range"
+                ))),
+                "{interval_seconds}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_transitions() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            status: Status,
+            next_run_at: Option<&'static str>,
+            action: &'static str,
+            expected_status: Status,
+            expected_next_run_at: Option<&'static str>,
+            expected_error: bool,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "pause active",
+                status: Status::Active,
+                next_run_at: Some("2025-11-05T11:00:00Z"),
+                action: "pause",
+                expected_status: Status::Paused,
+                expected_next_run_at: Some("2025-11-05T11:00:00Z"),
+                expected_error: false,
+            },
+            TestCase {
+                name: "pause paused",
+                status: Status::Paused,
+                next_run_at: Some("2025-11-05T11:00:00Z"),
+                action: "pause",
+                expected_status: Status::Paused,
+                expected_next_run_at: Some("2025-11-05T11:00:00Z"),
+                expected_error: true,
+            },
+            TestCase {
+                name: "resume before the next occurrence",
+                status: Status::Paused,
+                next_run_at: Some("2025-11-05T11:30:00Z"),
+                action: "resume",
+                expected_status: Status::Active,
+                expected_next_run_at: Some("2025-11-05T11:30:00Z"),
+                expected_error: false,
+            },
+            TestCase {
+                name: "resume skips missed occurrences",
+                status: Status::Paused,
+                next_run_at: Some("2025-11-05T10:00:00Z"),
+                action: "resume",
+                expected_status: Status::Active,
+                expected_next_run_at: Some("2025-11-05T11:30:00Z"),
+                expected_error: false,
+            },
+            TestCase {
+                name: "resume active",
+                status: Status::Active,
+                next_run_at: Some("2025-11-05T11:30:00Z"),
+                action: "resume",
+                expected_status: Status::Active,
+                expected_next_run_at: Some("2025-11-05T11:30:00Z"),
+                expected_error: true,
+            },
+            TestCase {
+                name: "cancel paused",
+                status: Status::Paused,
+                next_run_at: Some("2025-11-05T11:00:00Z"),
+                action: "cancel",
+                expected_status: Status::Cancelled,
+                expected_next_run_at: None,
+                expected_error: false,
+            },
+            TestCase {
+                name: "cancel completed",
+                status: Status::Completed,
+                next_run_at: None,
+                action: "cancel",
+                expected_status: Status::Completed,
+                expected_next_run_at: None,
+                expected_error: true,
+            },
+            TestCase {
+                name: "skip the next occurrence",
+                status: Status::Active,
+                next_run_at: Some("2025-11-05T11:00:00Z"),
+                action: "skip",
+                expected_status: Status::Active,
+                expected_next_run_at: Some("2025-11-05T11:30:00Z"),
+                expected_error: false,
+            },
+            TestCase {
+                name: "skip the last occurrence",
+                status: Status::Active,
+                next_run_at: Some("2025-11-05T12:00:00Z"),
+                action: "skip",
+                expected_status: Status::Completed,
+                expected_next_run_at: None,
+                expected_error: false,
+            },
+            TestCase {
+                name: "skip cancelled",
+                status: Status::Cancelled,
+                next_run_at: None,
+                action: "skip",
+                expected_status: Status::Cancelled,
+                expected_next_run_at: None,
+                expected_error: true,
+            },
+        ];
+
+        for test_case in test_cases {
+            // arrange
+            let mut schedule = Schedule {
+                status: test_case.status,
+                next_run_at: test_case.next_run_at.map(at),
+                ..schedule(None, Some(1800))
+            };
+
+            // act
+            let result = match test_case.action {
+                "pause" => schedule.pause(),
+                "resume" => schedule.resume(at("2025-11-05T11:10:00Z")),
+                "cancel" => schedule.cancel(),
+                _ => schedule.skip().map(|_| ()),
+            };
+
+            // assert
+            assert_eq!(
+                result.is_err(),
+                test_case.expected_error,
+                "{}",
+                test_case.name
+            );
+            assert_eq!(
+                schedule.status, test_case.expected_status,
+                "{}",
+                test_case.name
+            );
+            assert_eq!(
+                schedule.next_run_at,
+                test_case.expected_next_run_at.map(at),
+                "{}",
+                test_case.name
+            );
+        }
+    }
+
+    #[test]
+    fn successfully_derive_run_transaction_from_occurrence() {
+        // arrange
+        let schedule = schedule(None, Some(3600));
+        let occurrence_at = at("2025-11-05T11:00:00Z");
+
+        // act
+        let first = schedule.run_transaction(occurrence_at);
+        let again = schedule.run_transaction(occurrence_at);
+        let next = schedule.run_transaction(occurrence_at + chrono::Duration::hours(1));
+
+        // assert
+        assert_eq!(first.idempotency_key, again.idempotency_key);
+        assert_ne!(first.idempotency_key, next.idempotency_key);
+        assert_eq!(first.request_timestamp, occurrence_at);
+        assert_eq!((first.amount_minor, first.currency.as_str()), (1000, "USD"));
+    }
+}
@@ -1,18 +1,77 @@
-#[derive(Debug, Clone)]
+use crate::domain::fx::Conversion;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "transaction_status", rename_all = "lowercase")]
 pub enum Status {
     Init,
     Pending,
     Success,
     Failed,
     Fraud,
+    /// Refund is set once a refund is initiated and stays while the transaction is only
+    /// partially refunded.
     Refund,
+    /// Refunded is set once refunds add up to the full transaction amount.
     Refunded,
 }
 
-#[derive(Debug, Clone)]
+impl AsRef<str> for Status {
+    fn as_ref(&self) -> &str {
+        match self {
+            Status::Init => "init",
+            Status::Pending => "pending",
+            Status::Success => "success",
+            Status::Failed => "failed",
+            Status::Fraud => "fraud",
+            Status::Refund => "refund",
+            Status::Refunded => "refunded",
+        }
+    }
+}
+
+impl Status {
+    /// is_refundable is true once money has moved and not all of it has been refunded.
+    pub fn is_refundable(&self) -> bool {
+        matches!(self, Status::Success | Status::Refund)
+    }
+}
+
+/// Filter narrows the transactions returned by get_transactions, unset fields match every
+/// transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filter {
+    pub account_id: Option<uuid::Uuid>,
+    pub status: Option<Status>,
+    pub limit: i64,
+}
+
+/// StatusChange records a status the transaction moved to and when.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct StatusChange {
+    pub transaction_id: uuid::Uuid,
+    pub status: Status,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Details is a transaction together with its ledger entries and status history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Details {
+    pub transaction: Transaction,
+    pub entries: Vec<crate::domain::entry::Entry>,
+    pub status_history: Vec<StatusChange>,
+}
+
+/// StatusCount is the number of transactions currently in a status.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct StatusCount {
+    pub status: Status,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
 pub struct Transaction {
     pub id: uuid::Uuid,
-    pub client_id: uuid::Uuid,
     pub debit_account_id: uuid::Uuid,
     pub credit_account_id: uuid::Uuid,
     pub amount_minor: i64,
@@ -22,4 +81,94 @@ pub struct Transaction {
     pub request_timestamp: chrono::DateTime<chrono::Utc>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    /// fx is set on cross-currency transactions, which credit the converted amount.
+    #[sqlx(skip)]
+    pub fx: Option<Conversion>,
 }
+
+impl Transaction {
+    pub fn new(
+        debit_account_id: uuid::Uuid,
+        credit_account_id: uuid::Uuid,
+        amount_minor: i64,
+        currency: impl Into<String>,
+        idempotency_key: impl Into<String>,
+        request_timestamp: chrono::DateTime<chrono::Utc>,
+    ) -> Self {
+        let now = chrono::Utc::now();
+        Transaction {
+            id: uuid::Uuid::new_v4(),
+            debit_account_id,
+            credit_account_id,
+            amount_minor,
+            currency: currency.into(),
+            status: Status::Init,
+            idempotency_key: idempotency_key.into(),
+            request_timestamp,
+            created_at: now,
+            updated_at: now,
+            fx: None,
+        }
+    }
+
+    /// credit_currency is the currency the credited account is paid in.
+    pub fn credit_currency(&self) -> &str {
+        match &self.fx {
+            Some(fx) => fx.credit_currency.as_str(),
+            None => self.currency.as_str(),
+        }
+    }
+
+    /// credit_amount_minor is the amount the credited account is paid, in minor units of the
+    /// credit currency.
+    pub fn credit_amount_minor(&self) -> i64 {
+        match &self.fx {
+            Some(fx) => fx.credit_amount_minor,
+            None => self.amount_minor,
+        }
+    }
+
+    /// same_request is true when `other` asks for the same transfer, so a retry carrying the
+    /// same idempotency key can be answered with the transaction recorded first.
+    pub fn same_request(&self, other: &Transaction) -> bool {
+        self.debit_account_id == other.debit_account_id
+            && self.credit_account_id == other.credit_account_id
+            && self.amount_minor == other.amount_minor
+            && self.currency.eq_ignore_ascii_case(other.currency.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransactionError {
+    NotFound(uuid::Uuid),
+    InvalidAmount(i64),
+    /// SameAccount is returned when a transaction debits and credits the same account.
+    SameAccount(uuid::Uuid),
+    /// UnknownAccount is returned when an account has not been registered with the ledger.
+    UnknownAccount,
+    /// IdempotencyKeyReused is returned when a key is sent again for a different transfer.
+    IdempotencyKeyReused(String),
+}
+
+impl fmt::Display for TransactionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransactionError::NotFound(id) => write!(f, "transaction {id} not found"),
+            TransactionError::InvalidAmount(amount) => {
+                write!(f, "transaction amount must be positive, got {amount}")
+            }
+            TransactionError::SameAccount(id) => {
+                write!(f, "account {id} cannot be both debited and credited")
+            }
+            TransactionError::UnknownAccount => {
+                write!(f, "account is not registered with the ledger")
+            }
+            TransactionError::IdempotencyKeyReused(key) => write!(
+                f,
+                "idempotency key {key} was already used for a different transaction"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TransactionError {}
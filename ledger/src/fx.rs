@@ -0,0 +1,266 @@
+use crate::domain::fx::{FxError, Rate};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+/// FxRateProvider quotes the rates cross-currency transfers are converted at.
+#[async_trait]
+pub trait FxRateProvider: 'static + Send + Sync {
+    /// get_rate returns the price of one unit of `base` in `quote`, FxError::RateUnavailable
+    /// when the pair is not quoted.
+    async fn get_rate(&self, base: &str, quote: &str) -> anyhow::Result<Rate>;
+}
+
+/// QuotedRate is a rate with the time it was quoted at.
+type QuotedRate = (Decimal, chrono::DateTime<chrono::Utc>);
+
+/// InMemoryFxRates quotes a fixed set of rates. A pair is also quoted the other way round
+/// through the inverse of its rate.
+#[derive(Debug)]
+pub struct InMemoryFxRates {
+    source: String,
+    rates: RwLock<HashMap<(String, String), QuotedRate>>,
+}
+
+impl InMemoryFxRates {
+    pub fn new() -> Self {
+        Self {
+            source: "memory".to_string(),
+            rates: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// from_file loads the rates from lines of `base,quote,rate[,as_of]`, as_of being RFC 3339
+    /// and the time of loading when left out. Blank lines, lines starting with `#` and a
+    /// `base,quote,...` header are skipped.
+    pub fn from_file(path: &str) -> anyhow::Result<Self> {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) => anyhow::bail!("Failed to read FX rates file {path}: {e}"),
+        };
+
+        let rates = Self {
+            source: format!("file:{path}"),
+            rates: RwLock::new(HashMap::new()),
+        };
+        let loaded_at = chrono::Utc::now();
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with("base,") {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let (base, quote, rate, as_of) = match fields.as_slice() {
+                [base, quote, rate] => (base, quote, rate, None),
+                [base, quote, rate, as_of] => (base, quote, rate, Some(as_of)),
+                _ => anyhow::bail!(
+                    "{path}:{}: expected base,quote,rate[,as_of], got {line}",
+                    index + 1
+                ),
+            };
+            let rate = match Decimal::from_str(rate) {
+                Ok(rate) if rate > Decimal::ZERO => rate,
+                _ => anyhow::bail!("{path}:{}: invalid rate {rate}", index + 1),
+            };
+            let as_of = match as_of {
+                Some(as_of) => match chrono::DateTime::parse_from_rfc3339(as_of) {
+                    Ok(as_of) => as_of.with_timezone(&chrono::Utc),
+                    Err(e) => anyhow::bail!("{path}:{}: invalid as_of {as_of}: {e}", index + 1),
+                },
+                None => loaded_at,
+            };
+            rates.set_rate(base, quote, rate, as_of);
+        }
+
+        Ok(rates)
+    }
+
+    /// with_rate quotes one unit of `base` at `rate` units of `quote` as of now.
+    pub fn with_rate(self, base: &str, quote: &str, rate: Decimal) -> Self {
+        self.set_rate(base, quote, rate, chrono::Utc::now());
+        self
+    }
+
+    /// set_rate replaces the rate of the pair.
+    pub fn set_rate(
+        &self,
+        base: &str,
+        quote: &str,
+        rate: Decimal,
+        as_of: chrono::DateTime<chrono::Utc>,
+    ) {
+        self.rates
+            .write()
+            .expect("FX rates lock poisoned")
+            .insert((base.to_uppercase(), quote.to_uppercase()), (rate, as_of));
+    }
+}
+
+impl Default for InMemoryFxRates {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl FxRateProvider for InMemoryFxRates {
+    async fn get_rate(&self, base: &str, quote: &str) -> anyhow::Result<Rate> {
+        let (base, quote) = (base.to_uppercase(), quote.to_uppercase());
+        let rates = self.rates.read().expect("FX rates lock poisoned");
+
+        let rate = |base: &str, quote: &str| {
+            rates
+                .get(&(base.to_string(), quote.to_string()))
+                .map(|(rate, as_of)| Rate {
+                    base: base.to_string(),
+                    quote: quote.to_string(),
+                    rate: *rate,
+                    source: self.source.clone(),
+                    as_of: *as_of,
+                })
+        };
+
+        match (rate(&base, &quote), rate(&quote, &base)) {
+            (Some(rate), _) => Ok(rate),
+            (None, Some(inverse)) => Ok(inverse.inverse()?),
+            (None, None) => Err(FxError::RateUnavailable { base, quote }.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[tokio::test]
+    async fn test_get_rate() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            base: &'static str,
+            quote: &'static str,
+            expected: Result<&'static str, FxError>,
+        }
+
+        // arrange
+        let rates = InMemoryFxRates::new()
+            .with_rate("EUR", "USD", Decimal::from_str("1.25").unwrap())
+            .with_rate("USD", "JPY", Decimal::from_str("150").unwrap());
+
+        let test_cases = vec![
+            TestCase {
+                name: "quoted pair",
+                base: "EUR",
+                quote: "USD",
+                expected: Ok("1.25"),
+            },
+            TestCase {
+                name: "inverse of a quoted pair",
+                base: "USD",
+                quote: "EUR",
+                expected: Ok("0.8"),
+            },
+            TestCase {
+                name: "lowercase codes",
+                base: "usd",
+                quote: "jpy",
+                expected: Ok("150"),
+            },
+            TestCase {
+                name: "pair not quoted either way",
+                base: "EUR",
+                quote: "JPY",
+                expected: Err(FxError::RateUnavailable {
+                    base: "EUR".to_string(),
+                    quote: "JPY".to_string(),
+                }),
+            },
+        ];
+
+        for test_case in test_cases {
+            // act
+            let result = rates.get_rate(test_case.base, test_case.quote).await;
+
+            // assert
+            match test_case.expected {
+                Ok(expected) => {
+                    let rate = result.unwrap();
+                    assert_eq!(
+                        rate.rate,
+                        Decimal::from_str(expected).unwrap(),
+                        "{}",
+                        test_case.name
+                    );
+                    assert_eq!(
+                        (rate.base, rate.quote),
+                        (
+                            test_case.base.to_uppercase(),
+                            test_case.quote.to_uppercase()
+                        ),
+                        "{}",
+                        test_case.name
+                    );
+                    assert_eq!(rate.source, "memory", "{}", test_case.name);
+                }
+                Err(expected) => assert_eq!(
+                    result.unwrap_err().downcast_ref::<FxError>(),
+                    Some(&expected),
+                    "{}",
+                    test_case.name
+                ),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn successfully_load_rates_from_file() {
+        // arrange
+        let path = std::env::temp_dir().join(format!("fx-rates-{}.csv", uuid::Uuid::new_v4()));
+        let mut file = std::fs::File::create(&path).unwrap();
+        writeln!(
+            file,
+            "base,quote,rate,as_of\n# daily fixing\n\nEUR,USD,1.0850,2025-11-04T09:00:00Z\nGBP,USD,1.27"
+        )
+        .unwrap();
+        let path = path.to_str().unwrap();
+
+        // act
+        let rates = InMemoryFxRates::from_file(path).unwrap();
+
+        // assert
+        let eur = rates.get_rate("EUR", "USD").await.unwrap();
+        assert_eq!(eur.rate, Decimal::from_str("1.0850").unwrap());
+        assert_eq!(eur.as_of.to_rfc3339(), "2025-11-04T09:00:00+00:00");
+        assert_eq!(eur.source, format!("file:{path}"));
+        let gbp = rates.get_rate("GBP", "USD").await.unwrap();
+        assert_eq!(gbp.rate, Decimal::from_str("1.27").unwrap());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_from_file_errors() {
+        let test_cases = vec![
+            ("missing rate", "EUR,USD\n"),
+            ("zero rate", "EUR,USD,0\n"),
+            ("not a number", "EUR,USD,abc\n"),
+            ("invalid as_of", "EUR,USD,1.1,yesterday\n"),
+        ];
+
+        for (name, contents) in test_cases {
+            // arrange
+            let path = std::env::temp_dir().join(format!("fx-rates-{}.csv", uuid::Uuid::new_v4()));
+            std::fs::write(&path, contents).unwrap();
+
+            // act
+            let result = InMemoryFxRates::from_file(path.to_str().unwrap());
+
+            // assert
+            assert!(result.is_err(), "{name}");
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+}
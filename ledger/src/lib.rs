@@ -1,6 +1,28 @@
-mod domain;
+pub mod domain;
+pub mod fx;
 
 pub mod repo;
 pub mod service;
 
 pub mod api;
+
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
+
+/// Manual refunds above 100.00 need a second approver.
+pub const DEFAULT_REFUND_APPROVAL_THRESHOLD_MINOR: i64 = 10_000;
+/// Holds reserve their amount for 7 days unless authorized with a ttl.
+pub const DEFAULT_HOLD_TTL_SECONDS: u64 = 7 * 24 * 60 * 60;
+/// Cross-currency transfers are converted at the quoted rate unless a spread is configured.
+pub const DEFAULT_FX_SPREAD_BPS: i32 = 0;
+pub const DEFAULT_HOLD_SWEEP_INTERVAL_SECONDS: u64 = 60;
+pub const DEFAULT_SCHEDULE_POLL_INTERVAL_SECONDS: u64 = 30;
+/// Due schedules are run in batches of 100 per tick.
+pub const SCHEDULE_RUN_BATCH_SIZE: i64 = 100;
+/// A worker has 5 minutes to complete a run before others may retry it.
+pub const SCHEDULE_RUN_LEASE_SECONDS: i64 = 5 * 60;
+pub const DEFAULT_LIST_TRANSACTIONS_LIMIT: i64 = 50;
+pub const MAX_LIST_TRANSACTIONS_LIMIT: i64 = 500;
+pub const DEFAULT_WRITER_MAX_CONN: u32 = 4;
+pub const DEFAULT_READER_MAX_CONN: u32 = 4;
+pub const DEFAULT_TIMEOUT_SECONDS: u64 = 5;
@@ -1,21 +1,33 @@
-use common::shutdown;
+use common::{kafka, shutdown};
+use ledger::domain::fx::RoundingMode;
+use ledger::domain::money::Currency;
+use ledger::fx::InMemoryFxRates;
 use ledger::repo::PgLedgerRepository;
 use ledger::service::LedgerService;
+use ledger::{
+    DEFAULT_FX_SPREAD_BPS, DEFAULT_HOLD_SWEEP_INTERVAL_SECONDS, DEFAULT_READER_MAX_CONN,
+    DEFAULT_REFUND_APPROVAL_THRESHOLD_MINOR, DEFAULT_SCHEDULE_POLL_INTERVAL_SECONDS,
+    DEFAULT_TIMEOUT_SECONDS, DEFAULT_WRITER_MAX_CONN,
+};
 use ledger_proto::ledger_v1::FILE_DESCRIPTOR_SET;
 use ledger_proto::ledger_v1::ledger_server::LedgerServer;
 use std::env;
+use std::sync::Arc;
+use std::time::Duration;
 use tonic::codegen::tokio_stream::wrappers::TcpListenerStream;
 use tonic::transport::Server;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt::init();
+
     // setup database
     let database_config = common::database::Config {
-        reader_url: "".to_string(),
-        reader_max_connections: 0,
-        writer_url: "".to_string(),
-        writer_max_connections: 0,
-        timeout_in_secs: 0,
+        reader_url: env::var("READER_DATABASE_URL").expect("READER_DATABASE_URL must be set"),
+        reader_max_connections: DEFAULT_READER_MAX_CONN,
+        writer_url: env::var("WRITER_DATABASE_URL").expect("WRITER_DATABASE_URL must be set"),
+        writer_max_connections: DEFAULT_WRITER_MAX_CONN,
+        timeout_in_secs: DEFAULT_TIMEOUT_SECONDS,
     };
     let db = common::database::Database::new(&database_config)
         .await
@@ -24,8 +36,70 @@ async fn main() -> anyhow::Result<()> {
     // setup repo
     let repo = PgLedgerRepository::new(db);
 
-    // setup service
-    let ledger_service = LedgerService::new(repo);
+    // setup kafka
+    let kafka_config = kafka::Config {
+        brokers: env::var("KAFKA_BROKERS").expect("KAFKA_BROKERS must be set"),
+        group_id: env::var("KAFKA_GROUP_ID").unwrap_or("ledger".to_string()),
+    };
+    let publisher = kafka::KafkaPublisher::new(&kafka_config)?;
+
+    // setup service, manual refunds above the threshold need a second approver
+    let refund_approval_threshold = env::var("REFUND_APPROVAL_THRESHOLD_MINOR")
+        .map(|v| v.parse::<i64>())
+        .unwrap_or(Ok(DEFAULT_REFUND_APPROVAL_THRESHOLD_MINOR))?;
+    let mut ledger_service = LedgerService::new(repo, publisher)
+        .with_refund_approval_threshold(refund_approval_threshold);
+    // merchant fees are only charged once the system account they are credited to is set
+    if let Ok(fee_revenue_account_id) = env::var("FEE_REVENUE_ACCOUNT_ID") {
+        ledger_service = ledger_service
+            .with_fee_revenue_account(uuid::Uuid::parse_str(&fee_revenue_account_id)?);
+    }
+
+    // cross-currency transfers are only accepted once rates are loaded, each currency goes
+    // through its FX account, e.g. FX_ACCOUNTS=EUR=<uuid>,USD=<uuid>
+    if let Ok(fx_rates_file) = env::var("FX_RATES_FILE") {
+        ledger_service = ledger_service
+            .with_fx_rates(Arc::new(InMemoryFxRates::from_file(&fx_rates_file)?))
+            .with_fx_spread(
+                env::var("FX_SPREAD_BPS")
+                    .map(|v| v.parse::<i32>())
+                    .unwrap_or(Ok(DEFAULT_FX_SPREAD_BPS))?,
+            )
+            .with_fx_rounding_mode(
+                env::var("FX_ROUNDING_MODE")
+                    .map(|v| v.parse::<RoundingMode>())
+                    .unwrap_or(Ok(RoundingMode::HalfUp))?,
+            );
+        for fx_account in env::var("FX_ACCOUNTS").unwrap_or_default().split(',') {
+            if fx_account.trim().is_empty() {
+                continue;
+            }
+            let Some((currency, account_id)) = fx_account.split_once('=') else {
+                anyhow::bail!("invalid FX_ACCOUNTS entry {fx_account}, expected CURRENCY=uuid");
+            };
+            ledger_service = ledger_service.with_fx_account(
+                Currency::from_code(currency.trim())?.code(),
+                uuid::Uuid::parse_str(account_id.trim())?,
+            );
+        }
+    }
+
+    // expire holds past their ttl in the background
+    let ledger_service = Arc::new(ledger_service);
+    let hold_sweep_interval = env::var("HOLD_SWEEP_INTERVAL_SECONDS")
+        .map(|v| v.parse::<u64>())
+        .unwrap_or(Ok(DEFAULT_HOLD_SWEEP_INTERVAL_SECONDS))?;
+    ledger_service
+        .clone()
+        .spawn_hold_sweeper(Duration::from_secs(hold_sweep_interval));
+
+    // run scheduled transfers as their occurrences come due
+    let schedule_poll_interval = env::var("SCHEDULE_POLL_INTERVAL_SECONDS")
+        .map(|v| v.parse::<u64>())
+        .unwrap_or(Ok(DEFAULT_SCHEDULE_POLL_INTERVAL_SECONDS))?;
+    ledger_service
+        .clone()
+        .spawn_scheduler(Duration::from_secs(schedule_poll_interval));
 
     // setup reflection service
     let reflection_service = tonic_reflection::server::Builder::configure()
@@ -42,7 +116,7 @@ async fn main() -> anyhow::Result<()> {
         .expect("Failed to create TCP listener ❌");
     Server::builder()
         .add_service(reflection_service)
-        .add_service(LedgerServer::new(ledger_service))
+        .add_service(LedgerServer::from_arc(ledger_service))
         .serve_with_incoming_shutdown(
             TcpListenerStream::new(listener),
             shutdown::shutdown_signal(),
@@ -0,0 +1,389 @@
+use crate::domain::fee::{FeeError, FeeSchedule, Tier};
+use crate::repo::{FeeReader, FeeWriter, PgLedgerRepository};
+use async_trait::async_trait;
+
+const FEE_SCHEDULE_COLUMNS: &str =
+    "id, merchant_account_id, version, effective_from, created_by, created_at";
+
+#[derive(sqlx::FromRow)]
+struct FeeScheduleRow {
+    id: uuid::Uuid,
+    merchant_account_id: uuid::Uuid,
+    version: i32,
+    effective_from: chrono::DateTime<chrono::Utc>,
+    created_by: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(sqlx::FromRow)]
+struct TierRow {
+    fee_schedule_id: uuid::Uuid,
+    #[sqlx(flatten)]
+    tier: Tier,
+}
+
+impl PgLedgerRepository {
+    /// fee_schedules reads the tiers of the schedules, keeping the order of the rows.
+    async fn fee_schedules(&self, rows: Vec<FeeScheduleRow>) -> anyhow::Result<Vec<FeeSchedule>> {
+        let ids: Vec<uuid::Uuid> = rows.iter().map(|row| row.id).collect();
+        let result = sqlx::query_as::<_, TierRow>(
+            r#"
+            SELECT fee_schedule_id, currency, min_monthly_volume_minor, percentage_bps, fixed_minor
+            FROM fee_schedule_tiers
+            WHERE fee_schedule_id = ANY($1)
+            ORDER BY currency, min_monthly_volume_minor
+            "#,
+        )
+        .bind(&ids)
+        .fetch_all(&self.db.reader)
+        .await;
+
+        let tiers = match result {
+            Ok(tiers) => tiers,
+            Err(e) => anyhow::bail!("Failed to get fee schedule tiers: {e}"),
+        };
+
+        Ok(rows
+            .into_iter()
+            .map(|row| FeeSchedule {
+                id: row.id,
+                merchant_account_id: row.merchant_account_id,
+                version: row.version,
+                tiers: tiers
+                    .iter()
+                    .filter(|tier| tier.fee_schedule_id == row.id)
+                    .map(|tier| tier.tier.clone())
+                    .collect(),
+                effective_from: row.effective_from,
+                created_by: row.created_by,
+                created_at: row.created_at,
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl FeeWriter for PgLedgerRepository {
+    async fn create_fee_schedule(&self, fee_schedule: &FeeSchedule) -> anyhow::Result<FeeSchedule> {
+        let mut tx = self.db.writer.begin().await?;
+
+        // locking the account serialises versions of the same merchant
+        let account_type = match sqlx::query_scalar::<_, String>(
+            "SELECT account_type FROM accounts WHERE id = $1 FOR UPDATE",
+        )
+        .bind(fee_schedule.merchant_account_id)
+        .fetch_optional(&mut *tx)
+        .await
+        {
+            Ok(Some(account_type)) => account_type,
+            Ok(None) => {
+                return Err(FeeError::UnknownAccount(fee_schedule.merchant_account_id).into());
+            }
+            Err(e) => anyhow::bail!("Failed to create_fee_schedule: {e}"),
+        };
+        if account_type != "MERCHANT" {
+            return Err(FeeError::NotMerchant(fee_schedule.merchant_account_id).into());
+        }
+
+        let latest = match sqlx::query_as::<_, (i32, chrono::DateTime<chrono::Utc>)>(
+            r#"
+            SELECT version, effective_from FROM fee_schedules
+            WHERE merchant_account_id = $1
+            ORDER BY version DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(fee_schedule.merchant_account_id)
+        .fetch_optional(&mut *tx)
+        .await
+        {
+            Ok(latest) => latest,
+            Err(e) => anyhow::bail!("Failed to create_fee_schedule: {e}"),
+        };
+        if let Some((_, latest)) = latest
+            && fee_schedule.effective_from < latest
+        {
+            return Err(FeeError::EffectiveBeforeLatest {
+                effective_from: fee_schedule.effective_from,
+                latest,
+            }
+            .into());
+        }
+
+        let created = FeeSchedule {
+            version: latest.map(|(version, _)| version).unwrap_or(0) + 1,
+            ..fee_schedule.clone()
+        };
+        let result = sqlx::query(
+            r#"
+            INSERT INTO fee_schedules (id, merchant_account_id, version, effective_from, created_by, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(created.id)
+        .bind(created.merchant_account_id)
+        .bind(created.version)
+        .bind(created.effective_from)
+        .bind(created.created_by.as_str())
+        .bind(created.created_at)
+        .execute(&mut *tx)
+        .await;
+
+        if let Err(e) = result {
+            anyhow::bail!("Failed to insert fee schedule into database: {e}");
+        }
+
+        for tier in &created.tiers {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO fee_schedule_tiers (fee_schedule_id, currency, min_monthly_volume_minor, percentage_bps, fixed_minor)
+                VALUES ($1, $2, $3, $4, $5)
+                "#,
+            )
+            .bind(created.id)
+            .bind(tier.currency.as_str())
+            .bind(tier.min_monthly_volume_minor)
+            .bind(tier.percentage_bps)
+            .bind(tier.fixed_minor)
+            .execute(&mut *tx)
+            .await;
+
+            if let Err(e) = result {
+                anyhow::bail!("Failed to insert fee schedule tier into database: {e}");
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(created)
+    }
+}
+
+#[async_trait]
+impl FeeReader for PgLedgerRepository {
+    async fn get_fee_schedules(
+        &self,
+        merchant_account_id: uuid::Uuid,
+    ) -> anyhow::Result<Vec<FeeSchedule>> {
+        let result = sqlx::query_as::<_, FeeScheduleRow>(&format!(
+            "SELECT {FEE_SCHEDULE_COLUMNS} FROM fee_schedules WHERE merchant_account_id = $1 ORDER BY version DESC"
+        ))
+        .bind(merchant_account_id)
+        .fetch_all(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(rows) => self.fee_schedules(rows).await,
+            Err(e) => anyhow::bail!("Failed to get_fee_schedules: {e}"),
+        }
+    }
+
+    async fn get_effective_fee_schedule(
+        &self,
+        merchant_account_id: uuid::Uuid,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<Option<FeeSchedule>> {
+        let result = sqlx::query_as::<_, FeeScheduleRow>(&format!(
+            r#"
+            SELECT {FEE_SCHEDULE_COLUMNS} FROM fee_schedules
+            WHERE merchant_account_id = $1 AND effective_from <= $2
+            ORDER BY effective_from DESC, version DESC
+            LIMIT 1
+            "#
+        ))
+        .bind(merchant_account_id)
+        .bind(at)
+        .fetch_optional(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(Some(row)) => Ok(self.fee_schedules(vec![row]).await?.pop()),
+            Ok(None) => Ok(None),
+            Err(e) => anyhow::bail!("Failed to get_effective_fee_schedule: {e}"),
+        }
+    }
+
+    async fn get_monthly_volume(
+        &self,
+        account_id: uuid::Uuid,
+        currency: &str,
+        before: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<i64> {
+        let result = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COALESCE(SUM(COALESCE(fx.credit_amount_minor, t.amount_minor)), 0)::BIGINT
+            FROM transactions t
+            LEFT JOIN transaction_fx fx ON fx.transaction_id = t.id
+            WHERE t.kind = 'transfer'
+              AND t.credit_account_id = $1
+              AND COALESCE(fx.credit_currency, t.currency) = $2
+              AND t.created_at >= date_trunc('month', $3 AT TIME ZONE 'UTC') AT TIME ZONE 'UTC'
+              AND t.created_at < $3
+              AND t.status NOT IN ('failed', 'fraud')
+            "#,
+        )
+        .bind(account_id)
+        .bind(currency)
+        .bind(before)
+        .fetch_one(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(volume) => Ok(volume),
+            Err(e) => anyhow::bail!("Failed to get_monthly_volume: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::transaction::{Status, Transaction};
+    use crate::repo::LedgerWriter;
+    use chrono::SubsecRound;
+
+    fn tiers(percentage_bps: i32) -> Vec<Tier> {
+        vec![Tier {
+            currency: "USD".to_string(),
+            min_monthly_volume_minor: 0,
+            percentage_bps,
+            fixed_minor: 30,
+        }]
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_version_fee_schedules(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let merchant = uuid::Uuid::new_v4();
+        repo.create_account(merchant, "MERCHANT").await.unwrap();
+        // the database keeps microseconds
+        let now = chrono::Utc::now().trunc_subsecs(6);
+        let next_month = now + chrono::Duration::days(30);
+
+        // act
+        let first = repo
+            .create_fee_schedule(&FeeSchedule::new(merchant, tiers(290), None, "ops"))
+            .await
+            .unwrap();
+        let second = repo
+            .create_fee_schedule(&FeeSchedule::new(
+                merchant,
+                tiers(250),
+                Some(next_month),
+                "ops",
+            ))
+            .await
+            .unwrap();
+        let backdated = repo
+            .create_fee_schedule(&FeeSchedule::new(merchant, tiers(200), Some(now), "ops"))
+            .await;
+
+        // assert
+        assert_eq!((first.version, second.version), (1, 2));
+        assert_eq!(
+            backdated.unwrap_err().downcast_ref::<FeeError>(),
+            Some(&FeeError::EffectiveBeforeLatest {
+                effective_from: now,
+                latest: next_month,
+            })
+        );
+        let history = repo.get_fee_schedules(merchant).await.unwrap();
+        assert_eq!(
+            history.iter().map(|s| s.version).collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+        assert_eq!(history[0].tiers, tiers(250));
+        let effective_now = repo
+            .get_effective_fee_schedule(merchant, chrono::Utc::now())
+            .await
+            .unwrap();
+        let effective_next_month = repo
+            .get_effective_fee_schedule(merchant, next_month)
+            .await
+            .unwrap();
+        assert_eq!(effective_now.map(|s| s.version), Some(1));
+        assert_eq!(effective_next_month.map(|s| s.version), Some(2));
+        assert_eq!(
+            repo.get_effective_fee_schedule(merchant, now - chrono::Duration::days(1))
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn test_create_fee_schedule_errors(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let customer = uuid::Uuid::new_v4();
+        repo.create_account(customer, "CUSTOMER").await.unwrap();
+        let unknown = uuid::Uuid::new_v4();
+
+        // act
+        let not_merchant = repo
+            .create_fee_schedule(&FeeSchedule::new(customer, tiers(290), None, "ops"))
+            .await;
+        let unknown_account = repo
+            .create_fee_schedule(&FeeSchedule::new(unknown, tiers(290), None, "ops"))
+            .await;
+
+        // assert
+        assert_eq!(
+            not_merchant.unwrap_err().downcast_ref::<FeeError>(),
+            Some(&FeeError::NotMerchant(customer))
+        );
+        assert_eq!(
+            unknown_account.unwrap_err().downcast_ref::<FeeError>(),
+            Some(&FeeError::UnknownAccount(unknown))
+        );
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_sum_monthly_volume(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let customer = uuid::Uuid::new_v4();
+        let merchant = uuid::Uuid::new_v4();
+        repo.create_account(customer, "CUSTOMER").await.unwrap();
+        repo.create_account(merchant, "MERCHANT").await.unwrap();
+        let transfer = |amount_minor: i64, currency: &str| {
+            Transaction::new(
+                customer,
+                merchant,
+                amount_minor,
+                currency,
+                uuid::Uuid::new_v4().to_string(),
+                chrono::Utc::now(),
+            )
+        };
+        repo.create_transaction(&transfer(1000, "USD"), None)
+            .await
+            .unwrap();
+        repo.create_transaction(&transfer(500, "EUR"), None)
+            .await
+            .unwrap();
+        let failed = repo
+            .create_transaction(&transfer(700, "USD"), None)
+            .await
+            .unwrap();
+        repo.update_transaction_status(failed.id, Status::Failed)
+            .await
+            .unwrap();
+        let mut last_month = transfer(900, "USD");
+        last_month.created_at = chrono::Utc::now() - chrono::Duration::days(40);
+        repo.create_transaction(&last_month, None).await.unwrap();
+
+        // act
+        let volume = repo
+            .get_monthly_volume(merchant, "USD", chrono::Utc::now())
+            .await
+            .unwrap();
+
+        // assert
+        assert_eq!(volume, 1000);
+    }
+}
@@ -0,0 +1,351 @@
+use crate::domain::fee::Fee;
+use crate::domain::hold::{Hold, HoldError, Status};
+use crate::domain::transaction::{Transaction, TransactionError};
+use crate::repo::transaction::insert_transaction;
+use crate::repo::{HoldReader, HoldWriter, PgLedgerRepository};
+use async_trait::async_trait;
+
+const HOLD_COLUMNS: &str = "id, debit_account_id, credit_account_id, amount_minor, currency, status, idempotency_key, expires_at, captured_amount_minor, transaction_id, request_timestamp, created_at, updated_at";
+
+/// lock_authorized_hold reads the hold for update and checks it still reserves its amount.
+async fn lock_authorized_hold(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    id: uuid::Uuid,
+) -> anyhow::Result<Hold> {
+    let result = sqlx::query_as::<_, Hold>(&format!(
+        "SELECT {HOLD_COLUMNS} FROM holds WHERE id = $1 FOR UPDATE"
+    ))
+    .bind(id)
+    .fetch_optional(&mut **tx)
+    .await;
+
+    match result {
+        Ok(Some(hold)) if hold.status != Status::Authorized => {
+            Err(HoldError::NotAuthorized(id, hold.status).into())
+        }
+        Ok(Some(hold)) => Ok(hold),
+        Ok(None) => Err(HoldError::NotFound(id).into()),
+        Err(e) => anyhow::bail!("Failed to lock hold: {e}"),
+    }
+}
+
+#[async_trait]
+impl HoldWriter for PgLedgerRepository {
+    async fn create_hold(&self, hold: &Hold) -> anyhow::Result<Hold> {
+        let result = sqlx::query_as::<_, Hold>(&format!(
+            r#"
+            INSERT INTO holds (id, debit_account_id, credit_account_id, amount_minor, currency, status, idempotency_key, expires_at, request_timestamp, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING {HOLD_COLUMNS}
+            "#
+        ))
+        .bind(hold.id)
+        .bind(hold.debit_account_id)
+        .bind(hold.credit_account_id)
+        .bind(hold.amount_minor)
+        .bind(hold.currency.as_str())
+        .bind(hold.status.clone())
+        .bind(hold.idempotency_key.as_str())
+        .bind(hold.expires_at)
+        .bind(hold.request_timestamp)
+        .bind(hold.created_at)
+        .bind(hold.updated_at)
+        .fetch_one(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(created) => Ok(created),
+            Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => {
+                Err(HoldError::UnknownAccount.into())
+            }
+            Err(sqlx::Error::Database(e))
+                if e.constraint() == Some("holds_idempotency_key_key") =>
+            {
+                Err(HoldError::IdempotencyKeyReused(hold.idempotency_key.clone()).into())
+            }
+            Err(e) => anyhow::bail!("Failed to insert hold into database: {e}"),
+        }
+    }
+
+    async fn capture_hold(
+        &self,
+        id: uuid::Uuid,
+        transaction: &Transaction,
+        fee: Option<&Fee>,
+    ) -> anyhow::Result<(Hold, Transaction)> {
+        let mut tx = self.db.writer.begin().await?;
+
+        let hold = lock_authorized_hold(&mut tx, id).await?;
+        if hold.expires_at <= chrono::Utc::now() {
+            return Err(HoldError::Expired(id).into());
+        }
+        if transaction.amount_minor > hold.amount_minor {
+            return Err(HoldError::ExceedsHold {
+                amount_minor: transaction.amount_minor,
+                held_minor: hold.amount_minor,
+            }
+            .into());
+        }
+
+        let created = match insert_transaction(&mut tx, transaction, fee).await {
+            Ok(created) => created,
+            Err(e)
+                if e.downcast_ref::<TransactionError>()
+                    == Some(&TransactionError::UnknownAccount) =>
+            {
+                return Err(HoldError::UnknownAccount.into());
+            }
+            Err(e) => return Err(e),
+        };
+
+        let result = sqlx::query_as::<_, Hold>(&format!(
+            r#"
+            UPDATE holds
+            SET status = $2, captured_amount_minor = $3, transaction_id = $4, updated_at = now()
+            WHERE id = $1
+            RETURNING {HOLD_COLUMNS}
+            "#
+        ))
+        .bind(id)
+        .bind(Status::Captured)
+        .bind(created.amount_minor)
+        .bind(created.id)
+        .fetch_one(&mut *tx)
+        .await;
+
+        let captured = match result {
+            Ok(captured) => captured,
+            Err(e) => anyhow::bail!("Failed to capture_hold: {e}"),
+        };
+
+        tx.commit().await?;
+
+        Ok((captured, created))
+    }
+
+    async fn void_hold(&self, id: uuid::Uuid) -> anyhow::Result<Hold> {
+        let mut tx = self.db.writer.begin().await?;
+
+        lock_authorized_hold(&mut tx, id).await?;
+        let result = sqlx::query_as::<_, Hold>(&format!(
+            "UPDATE holds SET status = $2, updated_at = now() WHERE id = $1 RETURNING {HOLD_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(Status::Voided)
+        .fetch_one(&mut *tx)
+        .await;
+
+        let voided = match result {
+            Ok(voided) => voided,
+            Err(e) => anyhow::bail!("Failed to void_hold: {e}"),
+        };
+
+        tx.commit().await?;
+
+        Ok(voided)
+    }
+
+    async fn expire_holds(&self, now: chrono::DateTime<chrono::Utc>) -> anyhow::Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE holds
+            SET status = $2, updated_at = now()
+            WHERE status = $3 AND expires_at <= $1
+            "#,
+        )
+        .bind(now)
+        .bind(Status::Expired)
+        .bind(Status::Authorized)
+        .execute(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(result) => Ok(result.rows_affected()),
+            Err(e) => anyhow::bail!("Failed to expire_holds: {e}"),
+        }
+    }
+}
+
+#[async_trait]
+impl HoldReader for PgLedgerRepository {
+    async fn get_hold_by_id(&self, id: uuid::Uuid) -> anyhow::Result<Hold> {
+        let result =
+            sqlx::query_as::<_, Hold>(&format!("SELECT {HOLD_COLUMNS} FROM holds WHERE id = $1"))
+                .bind(id)
+                .fetch_one(&self.db.reader)
+                .await;
+
+        match result {
+            Ok(hold) => Ok(hold),
+            Err(sqlx::Error::RowNotFound) => Err(HoldError::NotFound(id).into()),
+            Err(e) => anyhow::bail!("Failed to get_hold_by_id: {e}"),
+        }
+    }
+
+    async fn get_hold_by_idempotency_key(
+        &self,
+        idempotency_key: &str,
+    ) -> anyhow::Result<Option<Hold>> {
+        let result = sqlx::query_as::<_, Hold>(&format!(
+            "SELECT {HOLD_COLUMNS} FROM holds WHERE idempotency_key = $1"
+        ))
+        .bind(idempotency_key)
+        .fetch_optional(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(hold) => Ok(hold),
+            Err(e) => anyhow::bail!("Failed to get_hold_by_idempotency_key: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repo::{LedgerReader, LedgerWriter};
+
+    async fn accounts(repo: &PgLedgerRepository) -> (uuid::Uuid, uuid::Uuid) {
+        let (customer, merchant) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        repo.create_account(customer, "CUSTOMER").await.unwrap();
+        repo.create_account(merchant, "MERCHANT").await.unwrap();
+
+        (customer, merchant)
+    }
+
+    fn hold(customer: uuid::Uuid, merchant: uuid::Uuid, amount_minor: i64) -> Hold {
+        Hold::new(
+            customer,
+            merchant,
+            amount_minor,
+            "USD",
+            uuid::Uuid::new_v4().to_string(),
+            chrono::Duration::hours(1),
+            chrono::Utc::now(),
+        )
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_reduce_available_balance_until_captured(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let (customer, merchant) = accounts(&repo).await;
+        let authorized = repo
+            .create_hold(&hold(customer, merchant, 1000))
+            .await
+            .unwrap();
+        let held = repo.get_balances(&[customer]).await.unwrap();
+
+        // act
+        let (captured, transaction) = repo
+            .capture_hold(authorized.id, &authorized.capture_transaction(600), None)
+            .await
+            .unwrap();
+
+        // assert
+        assert_eq!(
+            held.iter()
+                .map(|balance| (balance.amount_minor, balance.available_minor))
+                .collect::<Vec<_>>(),
+            vec![(0, -1000)]
+        );
+        assert_eq!(captured.status, Status::Captured);
+        assert_eq!(captured.captured_amount_minor, Some(600));
+        assert_eq!(captured.transaction_id, Some(transaction.id));
+        let balances = repo.get_balances(&[customer, merchant]).await.unwrap();
+        let balances: Vec<_> = balances
+            .iter()
+            .map(|balance| {
+                (
+                    balance.account_id,
+                    balance.amount_minor,
+                    balance.available_minor,
+                )
+            })
+            .collect();
+        assert!(balances.contains(&(customer, -600, -600)));
+        assert!(balances.contains(&(merchant, 600, 600)));
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn test_capture_hold_errors(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let (customer, merchant) = accounts(&repo).await;
+        let voided = repo
+            .create_hold(&hold(customer, merchant, 1000))
+            .await
+            .unwrap();
+        repo.void_hold(voided.id).await.unwrap();
+        let authorized = repo
+            .create_hold(&hold(customer, merchant, 1000))
+            .await
+            .unwrap();
+        let unknown = uuid::Uuid::new_v4();
+
+        // act
+        let capture_voided = repo
+            .capture_hold(voided.id, &voided.capture_transaction(1000), None)
+            .await;
+        let exceeding = repo
+            .capture_hold(authorized.id, &authorized.capture_transaction(1001), None)
+            .await;
+        let not_found = repo
+            .capture_hold(unknown, &authorized.capture_transaction(10), None)
+            .await;
+
+        // assert
+        assert_eq!(
+            capture_voided.unwrap_err().downcast_ref::<HoldError>(),
+            Some(&HoldError::NotAuthorized(voided.id, Status::Voided))
+        );
+        assert_eq!(
+            exceeding.unwrap_err().downcast_ref::<HoldError>(),
+            Some(&HoldError::ExceedsHold {
+                amount_minor: 1001,
+                held_minor: 1000
+            })
+        );
+        assert_eq!(
+            not_found.unwrap_err().downcast_ref::<HoldError>(),
+            Some(&HoldError::NotFound(unknown))
+        );
+        assert_eq!(
+            repo.get_hold_by_id(authorized.id).await.unwrap().status,
+            Status::Authorized
+        );
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_expire_holds_past_their_ttl(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let (customer, merchant) = accounts(&repo).await;
+        let live = repo
+            .create_hold(&hold(customer, merchant, 1000))
+            .await
+            .unwrap();
+        let mut stale = hold(customer, merchant, 500);
+        stale.expires_at = chrono::Utc::now() - chrono::Duration::seconds(1);
+        let stale = repo.create_hold(&stale).await.unwrap();
+
+        // act
+        let expired = repo.expire_holds(chrono::Utc::now()).await.unwrap();
+
+        // assert
+        assert_eq!(expired, 1);
+        assert_eq!(
+            repo.get_hold_by_id(stale.id).await.unwrap().status,
+            Status::Expired
+        );
+        assert_eq!(
+            repo.get_hold_by_id(live.id).await.unwrap().status,
+            Status::Authorized
+        );
+        let balances = repo.get_balances(&[customer]).await.unwrap();
+        assert_eq!(balances[0].available_minor, -1000);
+    }
+}
@@ -0,0 +1,318 @@
+use crate::domain::entry::Entry;
+use crate::domain::journal::{JournalEntry, JournalError, Leg};
+use crate::domain::transaction::Status;
+use crate::repo::transaction::{ENTRY_COLUMNS, insert_entry, insert_status_change};
+use crate::repo::{JournalReader, JournalWriter, PgLedgerRepository};
+use async_trait::async_trait;
+
+const JOURNAL_COLUMNS: &str = "id, idempotency_key, description, request_timestamp, created_at";
+
+#[derive(sqlx::FromRow)]
+struct JournalRow {
+    id: uuid::Uuid,
+    idempotency_key: String,
+    description: Option<String>,
+    request_timestamp: chrono::DateTime<chrono::Utc>,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl PgLedgerRepository {
+    /// journal_entry reads the legs of the journal entry, in the order they were posted.
+    async fn journal_entry(&self, row: JournalRow) -> anyhow::Result<JournalEntry> {
+        let result = sqlx::query_as::<_, Entry>(&format!(
+            "SELECT {ENTRY_COLUMNS} FROM ledger_entries WHERE transaction_id = $1 ORDER BY leg"
+        ))
+        .bind(row.id)
+        .fetch_all(&self.db.reader)
+        .await;
+
+        let entries = match result {
+            Ok(entries) => entries,
+            Err(e) => anyhow::bail!("Failed to get journal legs: {e}"),
+        };
+
+        Ok(JournalEntry {
+            id: row.id,
+            idempotency_key: row.idempotency_key,
+            description: row.description,
+            legs: entries
+                .into_iter()
+                .map(|entry| Leg {
+                    account_id: entry.account_id,
+                    entry_type: entry.entry_type,
+                    amount_minor: entry.amount_minor,
+                    currency: entry.currency,
+                    description: entry.description,
+                })
+                .collect(),
+            request_timestamp: row.request_timestamp,
+            created_at: row.created_at,
+        })
+    }
+}
+
+#[async_trait]
+impl JournalWriter for PgLedgerRepository {
+    async fn post_journal_entry(
+        &self,
+        journal_entry: &JournalEntry,
+    ) -> anyhow::Result<JournalEntry> {
+        let mut tx = self.db.writer.begin().await?;
+
+        let account_ids: Vec<uuid::Uuid> = journal_entry
+            .legs
+            .iter()
+            .map(|leg| leg.account_id)
+            .collect();
+        let registered =
+            match sqlx::query_scalar::<_, uuid::Uuid>("SELECT id FROM accounts WHERE id = ANY($1)")
+                .bind(&account_ids)
+                .fetch_all(&mut *tx)
+                .await
+            {
+                Ok(registered) => registered,
+                Err(e) => anyhow::bail!("Failed to post_journal_entry: {e}"),
+            };
+        if let Some((leg, account_id)) = account_ids
+            .iter()
+            .enumerate()
+            .find(|(_, account_id)| !registered.contains(account_id))
+        {
+            return Err(JournalError::UnknownAccount {
+                leg,
+                account_id: *account_id,
+            }
+            .into());
+        }
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO transactions (id, kind, description, status, idempotency_key, request_timestamp, created_at, updated_at)
+            VALUES ($1, 'journal', $2, $3, $4, $5, $6, $6)
+            "#,
+        )
+        .bind(journal_entry.id)
+        .bind(journal_entry.description.as_deref())
+        .bind(Status::Success)
+        .bind(journal_entry.idempotency_key.as_str())
+        .bind(journal_entry.request_timestamp)
+        .bind(journal_entry.created_at)
+        .execute(&mut *tx)
+        .await;
+
+        match result {
+            Ok(_) => {}
+            Err(sqlx::Error::Database(e))
+                if e.constraint() == Some("transactions_idempotency_key_key") =>
+            {
+                return Err(JournalError::IdempotencyKeyReused(
+                    journal_entry.idempotency_key.clone(),
+                )
+                .into());
+            }
+            Err(e) => anyhow::bail!("Failed to insert journal entry into database: {e}"),
+        }
+        insert_status_change(
+            &mut tx,
+            journal_entry.id,
+            Status::Success,
+            journal_entry.created_at,
+        )
+        .await?;
+
+        for (index, leg) in journal_entry.legs.iter().enumerate() {
+            let entry = Entry {
+                id: uuid::Uuid::new_v4(),
+                transaction_id: journal_entry.id,
+                account_id: leg.account_id,
+                entry_type: leg.entry_type.clone(),
+                amount_minor: leg.amount_minor,
+                currency: leg.currency.clone(),
+                refund_id: None,
+                description: leg.description.clone(),
+                leg: Some(index as i32),
+                created_at: journal_entry.created_at,
+            };
+            insert_entry(&mut tx, &entry).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(journal_entry.clone())
+    }
+}
+
+#[async_trait]
+impl JournalReader for PgLedgerRepository {
+    async fn get_journal_entry_by_id(&self, id: uuid::Uuid) -> anyhow::Result<JournalEntry> {
+        let result = sqlx::query_as::<_, JournalRow>(&format!(
+            "SELECT {JOURNAL_COLUMNS} FROM transactions WHERE id = $1 AND kind = 'journal'"
+        ))
+        .bind(id)
+        .fetch_one(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(row) => self.journal_entry(row).await,
+            Err(sqlx::Error::RowNotFound) => Err(JournalError::NotFound(id).into()),
+            Err(e) => anyhow::bail!("Failed to get_journal_entry_by_id: {e}"),
+        }
+    }
+
+    async fn get_journal_entry_by_idempotency_key(
+        &self,
+        idempotency_key: &str,
+    ) -> anyhow::Result<Option<JournalEntry>> {
+        let result = sqlx::query_as::<_, JournalRow>(&format!(
+            "SELECT {JOURNAL_COLUMNS} FROM transactions WHERE idempotency_key = $1 AND kind = 'journal'"
+        ))
+        .bind(idempotency_key)
+        .fetch_optional(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(Some(row)) => Ok(Some(self.journal_entry(row).await?)),
+            Ok(None) => Ok(None),
+            Err(e) => anyhow::bail!("Failed to get_journal_entry_by_idempotency_key: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entry::Type;
+    use crate::repo::{LedgerReader, LedgerWriter};
+
+    async fn registered_accounts(repo: &PgLedgerRepository, count: usize) -> Vec<uuid::Uuid> {
+        let mut account_ids = vec![];
+        for _ in 0..count {
+            let account_id = uuid::Uuid::new_v4();
+            repo.create_account(account_id, "MERCHANT").await.unwrap();
+            account_ids.push(account_id);
+        }
+
+        account_ids
+    }
+
+    fn leg(account_id: uuid::Uuid, entry_type: Type, amount_minor: i64) -> Leg {
+        Leg {
+            account_id,
+            entry_type,
+            amount_minor,
+            currency: "USD".to_string(),
+            description: Some(format!("leg of {amount_minor}")),
+        }
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_post_journal_entry(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let accounts = registered_accounts(&repo, 3).await;
+        let journal_entry = JournalEntry::new(
+            "payment-with-fee",
+            Some("payment with platform fee".to_string()),
+            vec![
+                leg(accounts[0], Type::Debit, 1000),
+                leg(accounts[1], Type::Credit, 970),
+                leg(accounts[2], Type::Credit, 30),
+            ],
+            chrono::Utc::now(),
+        );
+
+        // act
+        repo.post_journal_entry(&journal_entry).await.unwrap();
+        let by_id = repo
+            .get_journal_entry_by_id(journal_entry.id)
+            .await
+            .unwrap();
+        let by_key = repo
+            .get_journal_entry_by_idempotency_key("payment-with-fee")
+            .await
+            .unwrap();
+
+        // assert
+        assert_eq!(by_id.legs, journal_entry.legs);
+        assert_eq!(by_id.description, journal_entry.description);
+        assert_eq!(by_key.map(|j| j.id), Some(journal_entry.id));
+        let balances = repo.get_balances(&accounts).await.unwrap();
+        assert_eq!(
+            balances
+                .iter()
+                .map(|balance| balance.amount_minor)
+                .sum::<i64>(),
+            0
+        );
+        assert_eq!(
+            repo.get_status_history(journal_entry.id)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn test_post_journal_entry_errors(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let accounts = registered_accounts(&repo, 2).await;
+        let unknown = uuid::Uuid::new_v4();
+        let posted = JournalEntry::new(
+            "posted",
+            None,
+            vec![
+                leg(accounts[0], Type::Debit, 500),
+                leg(accounts[1], Type::Credit, 500),
+            ],
+            chrono::Utc::now(),
+        );
+        repo.post_journal_entry(&posted).await.unwrap();
+
+        // act
+        let unknown_account = repo
+            .post_journal_entry(&JournalEntry::new(
+                "unknown-account",
+                None,
+                vec![
+                    leg(accounts[0], Type::Debit, 500),
+                    leg(unknown, Type::Credit, 500),
+                ],
+                chrono::Utc::now(),
+            ))
+            .await;
+        let reused_key = repo
+            .post_journal_entry(&JournalEntry::new(
+                "posted",
+                None,
+                posted.legs.clone(),
+                chrono::Utc::now(),
+            ))
+            .await;
+
+        // assert
+        assert_eq!(
+            unknown_account.unwrap_err().downcast_ref::<JournalError>(),
+            Some(&JournalError::UnknownAccount {
+                leg: 1,
+                account_id: unknown
+            })
+        );
+        assert_eq!(
+            reused_key.unwrap_err().downcast_ref::<JournalError>(),
+            Some(&JournalError::IdempotencyKeyReused("posted".to_string()))
+        );
+        assert_eq!(
+            repo.get_transaction_by_id(posted.id)
+                .await
+                .unwrap_err()
+                .downcast_ref(),
+            Some(&crate::domain::transaction::TransactionError::NotFound(
+                posted.id
+            ))
+        );
+    }
+}
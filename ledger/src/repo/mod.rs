@@ -1,30 +1,304 @@
+use crate::domain::balance::Balance;
+use crate::domain::entry::Entry;
+use crate::domain::fee::{Fee, FeeSchedule};
+use crate::domain::hold::Hold;
+use crate::domain::journal::JournalEntry;
+use crate::domain::refund::Refund;
+use crate::domain::schedule::{Run, RunStatus, Schedule};
+use crate::domain::transaction::{Filter, Status, StatusChange, StatusCount, Transaction};
 use async_trait::async_trait;
 use common::database::Database;
 
+mod fee;
+mod hold;
+mod journal;
+mod refund;
+mod schedule;
+pub(crate) mod transaction;
+
 #[derive(Debug, Clone)]
 pub struct PgLedgerRepository {
-    db: Database,
+    pub db: Database,
 }
 
 impl PgLedgerRepository {
-    pub fn new(db: Database) -> impl LedgerRepository {
+    pub fn new(db: Database) -> Self {
         Self { db }
     }
 }
 
 #[async_trait]
-pub trait LedgerRepository: LedgerWriter + LedgerReader + 'static + Send + Sync {}
+pub trait LedgerRepository:
+    LedgerWriter
+    + LedgerReader
+    + RefundWriter
+    + RefundReader
+    + JournalWriter
+    + JournalReader
+    + FeeWriter
+    + FeeReader
+    + HoldWriter
+    + HoldReader
+    + ScheduleWriter
+    + ScheduleReader
+    + 'static
+    + Send
+    + Sync
+{
+}
+
+#[async_trait]
+pub trait LedgerWriter: 'static + Send + Sync {
+    /// create_account registers an account with the ledger, it is a no-op if the account exists.
+    async fn create_account(&self, id: uuid::Uuid, account_type: &str) -> anyhow::Result<()>;
+    /// create_transaction records the transaction together with its debit and credit entries.
+    /// A fee is posted under the same transaction, debiting the credited account and crediting
+    /// the revenue account. A transaction with an FX conversion is posted through the FX
+    /// accounts of both currencies and keeps its rate snapshot.
+    async fn create_transaction(
+        &self,
+        transaction: &Transaction,
+        fee: Option<&Fee>,
+    ) -> anyhow::Result<Transaction>;
+    async fn update_transaction_status(&self, id: uuid::Uuid, status: Status)
+    -> anyhow::Result<()>;
+    /// transition_transaction_status moves the transaction to `status` only while it is in one
+    /// of the `from` statuses, and returns whether it moved.
+    async fn transition_transaction_status(
+        &self,
+        id: uuid::Uuid,
+        from: &[Status],
+        status: Status,
+    ) -> anyhow::Result<bool>;
+}
 
 #[async_trait]
-pub trait LedgerWriter: 'static + Send + Sync {}
+pub trait LedgerReader: 'static + Send + Sync {
+    async fn get_transaction_by_id(&self, id: uuid::Uuid) -> anyhow::Result<Transaction>;
+    async fn get_transaction_by_idempotency_key(
+        &self,
+        idempotency_key: &str,
+    ) -> anyhow::Result<Option<Transaction>>;
+    async fn get_entries_by_transaction_id(
+        &self,
+        transaction_id: uuid::Uuid,
+    ) -> anyhow::Result<Vec<Entry>>;
+    /// get_status_history returns every status the transaction moved to, oldest first.
+    async fn get_status_history(
+        &self,
+        transaction_id: uuid::Uuid,
+    ) -> anyhow::Result<Vec<StatusChange>>;
+    /// count_transactions_by_status counts transactions per current status, only those created
+    /// at or after `since` when it is set. Statuses without transactions are left out.
+    async fn count_transactions_by_status(
+        &self,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> anyhow::Result<Vec<StatusCount>>;
+    /// get_transactions_created_between returns transactions created in `[from, to)`.
+    async fn get_transactions_created_between(
+        &self,
+        from: chrono::DateTime<chrono::Utc>,
+        to: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<Vec<Transaction>>;
+    /// get_transactions returns up to `filter.limit` transactions matching the filter, newest
+    /// first.
+    async fn get_transactions(&self, filter: &Filter) -> anyhow::Result<Vec<Transaction>>;
+    /// get_transactions_by_references returns transactions whose id or idempotency key is in
+    /// the given lists. Unknown references are skipped.
+    async fn get_transactions_by_references(
+        &self,
+        ids: &[uuid::Uuid],
+        idempotency_keys: &[String],
+    ) -> anyhow::Result<Vec<Transaction>>;
+    /// get_balances returns the posted and available balances of the accounts per currency,
+    /// leaving out failed and fraudulent transactions and expired holds. It fails with
+    /// TransactionError::UnknownAccount when one of the accounts is not registered.
+    async fn get_balances(&self, account_ids: &[uuid::Uuid]) -> anyhow::Result<Vec<Balance>>;
+}
 
+/// RefundWriter moves refunds through their lifecycle. Every step runs in a database
+/// transaction holding a lock on the refunded transaction, so concurrent refunds cannot take
+/// cumulative refunds above the original amount.
 #[async_trait]
-pub trait LedgerReader: 'static + Send + Sync {}
+pub trait RefundWriter: 'static + Send + Sync {
+    /// begin_refund reserves the refund amount and moves the transaction to REFUND. Beginning
+    /// a refund which is already pending or done returns it unchanged, approved and failed
+    /// refunds are (re)started and refunds awaiting approval are rejected.
+    async fn begin_refund(&self, refund: &Refund) -> anyhow::Result<Refund>;
+    /// complete_refund posts the reversing entries and moves the transaction to REFUNDED once
+    /// it is fully refunded.
+    async fn complete_refund(&self, id: uuid::Uuid, psp_reference: &str) -> anyhow::Result<Refund>;
+    /// fail_refund releases the reserved amount.
+    async fn fail_refund(&self, id: uuid::Uuid, failure_reason: &str) -> anyhow::Result<Refund>;
+    /// request_refund records a manual refund in its initial status, either awaiting approval
+    /// or approved. The amount is reserved against the transaction straight away.
+    async fn request_refund(&self, refund: &Refund) -> anyhow::Result<Refund>;
+    /// decide_refund approves or rejects a refund awaiting approval.
+    async fn decide_refund(
+        &self,
+        id: uuid::Uuid,
+        approver: &str,
+        approve: bool,
+    ) -> anyhow::Result<Refund>;
+}
 
-impl LedgerRepository for PgLedgerRepository {}
+#[async_trait]
+pub trait RefundReader: 'static + Send + Sync {
+    async fn get_refund_by_id(&self, id: uuid::Uuid) -> anyhow::Result<Refund>;
+    async fn get_refunds_by_transaction_id(
+        &self,
+        transaction_id: uuid::Uuid,
+    ) -> anyhow::Result<Vec<Refund>>;
+}
+
+#[async_trait]
+pub trait JournalWriter: 'static + Send + Sync {
+    /// post_journal_entry records the journal entry as a settled transaction with one ledger
+    /// entry per leg. It fails with JournalError::UnknownAccount naming the first leg whose
+    /// account is not registered.
+    async fn post_journal_entry(
+        &self,
+        journal_entry: &JournalEntry,
+    ) -> anyhow::Result<JournalEntry>;
+}
+
+#[async_trait]
+pub trait JournalReader: 'static + Send + Sync {
+    async fn get_journal_entry_by_id(&self, id: uuid::Uuid) -> anyhow::Result<JournalEntry>;
+    async fn get_journal_entry_by_idempotency_key(
+        &self,
+        idempotency_key: &str,
+    ) -> anyhow::Result<Option<JournalEntry>>;
+}
 
 #[async_trait]
-impl LedgerReader for PgLedgerRepository {}
+pub trait FeeWriter: 'static + Send + Sync {
+    /// create_fee_schedule stores the schedule as the next version of the merchant. It fails
+    /// with FeeError::EffectiveBeforeLatest when the latest version takes effect after it.
+    async fn create_fee_schedule(&self, fee_schedule: &FeeSchedule) -> anyhow::Result<FeeSchedule>;
+}
+
+#[async_trait]
+pub trait FeeReader: 'static + Send + Sync {
+    /// get_fee_schedules returns every version of the merchant's schedule, newest first.
+    async fn get_fee_schedules(
+        &self,
+        merchant_account_id: uuid::Uuid,
+    ) -> anyhow::Result<Vec<FeeSchedule>>;
+    /// get_effective_fee_schedule returns the latest version in effect at `at`.
+    async fn get_effective_fee_schedule(
+        &self,
+        merchant_account_id: uuid::Uuid,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<Option<FeeSchedule>>;
+    /// get_monthly_volume sums the transfers credited to the account in the currency from the
+    /// start of the calendar month (UTC) of `before` until `before`, leaving out failed and
+    /// fraudulent transactions. Cross-currency transfers count with their converted amount.
+    async fn get_monthly_volume(
+        &self,
+        account_id: uuid::Uuid,
+        currency: &str,
+        before: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<i64>;
+}
+
+/// HoldWriter moves holds through their lifecycle. Capturing and voiding lock the hold, so a
+/// hold is captured or voided at most once.
+#[async_trait]
+pub trait HoldWriter: 'static + Send + Sync {
+    /// create_hold records an authorized hold. It fails with HoldError::UnknownAccount when
+    /// one of the accounts is not registered.
+    async fn create_hold(&self, hold: &Hold) -> anyhow::Result<Hold>;
+    /// capture_hold records the transaction, with its fee, and links the hold to it. It fails
+    /// unless the hold is authorized, not past its expiry and holds at least the amount of the
+    /// transaction.
+    async fn capture_hold(
+        &self,
+        id: uuid::Uuid,
+        transaction: &Transaction,
+        fee: Option<&Fee>,
+    ) -> anyhow::Result<(Hold, Transaction)>;
+    /// void_hold releases an authorized hold.
+    async fn void_hold(&self, id: uuid::Uuid) -> anyhow::Result<Hold>;
+    /// expire_holds moves authorized holds past their expiry at `now` to expired, and returns
+    /// how many it moved.
+    async fn expire_holds(&self, now: chrono::DateTime<chrono::Utc>) -> anyhow::Result<u64>;
+}
+
+#[async_trait]
+pub trait HoldReader: 'static + Send + Sync {
+    async fn get_hold_by_id(&self, id: uuid::Uuid) -> anyhow::Result<Hold>;
+    async fn get_hold_by_idempotency_key(
+        &self,
+        idempotency_key: &str,
+    ) -> anyhow::Result<Option<Hold>>;
+}
+
+/// ScheduleWriter stores schedules and their runs. Pausing, resuming, cancelling and skipping
+/// lock the schedule, and an occurrence is claimed at most once.
+#[async_trait]
+pub trait ScheduleWriter: 'static + Send + Sync {
+    /// create_schedule stores the schedule. It fails with ScheduleError::UnknownAccount when
+    /// one of the accounts is not registered.
+    async fn create_schedule(&self, schedule: &Schedule) -> anyhow::Result<Schedule>;
+    /// claim_run records a pending run of the occurrence, leased until `claimed_until`, and
+    /// moves the schedule on to `next_run_at`, completing it when unset. It returns None when
+    /// the schedule is no longer active or the occurrence was claimed already.
+    async fn claim_run(
+        &self,
+        id: uuid::Uuid,
+        occurrence_at: chrono::DateTime<chrono::Utc>,
+        next_run_at: Option<chrono::DateTime<chrono::Utc>>,
+        claimed_until: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<Option<Run>>;
+    /// claim_pending_runs leases up to `limit` pending runs whose lease expired by `now` until
+    /// `claimed_until`, oldest first. Runs leased by another worker are skipped.
+    async fn claim_pending_runs(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        claimed_until: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<Run>>;
+    /// complete_run records the outcome of a pending run. It returns None when the run is no
+    /// longer pending, e.g. completed by another worker after the lease expired.
+    async fn complete_run(
+        &self,
+        id: uuid::Uuid,
+        status: RunStatus,
+        transaction_id: Option<uuid::Uuid>,
+        error: Option<&str>,
+    ) -> anyhow::Result<Option<Run>>;
+    async fn pause_schedule(&self, id: uuid::Uuid) -> anyhow::Result<Schedule>;
+    /// resume_schedule reactivates a paused schedule from its next occurrence after `now`.
+    async fn resume_schedule(
+        &self,
+        id: uuid::Uuid,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<Schedule>;
+    async fn cancel_schedule(&self, id: uuid::Uuid) -> anyhow::Result<Schedule>;
+    /// skip_schedule_run records the next occurrence as skipped and moves the schedule past it.
+    async fn skip_schedule_run(&self, id: uuid::Uuid) -> anyhow::Result<(Schedule, Run)>;
+}
 
 #[async_trait]
-impl LedgerWriter for PgLedgerRepository {}
+pub trait ScheduleReader: 'static + Send + Sync {
+    async fn get_schedule_by_id(&self, id: uuid::Uuid) -> anyhow::Result<Schedule>;
+    async fn get_schedule_by_idempotency_key(
+        &self,
+        idempotency_key: &str,
+    ) -> anyhow::Result<Option<Schedule>>;
+    /// get_schedules returns the schedules debiting or crediting the account, every schedule
+    /// when unset, newest first.
+    async fn get_schedules(&self, account_id: Option<uuid::Uuid>) -> anyhow::Result<Vec<Schedule>>;
+    /// get_schedule_runs returns the runs of the schedule, latest occurrence first.
+    async fn get_schedule_runs(&self, schedule_id: uuid::Uuid) -> anyhow::Result<Vec<Run>>;
+    /// get_due_schedules returns up to `limit` active schedules whose next run is at or before
+    /// `now`, longest overdue first.
+    async fn get_due_schedules(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<Schedule>>;
+}
+
+impl LedgerRepository for PgLedgerRepository {}
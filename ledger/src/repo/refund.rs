@@ -0,0 +1,858 @@
+use crate::domain::entry::{Entry, Type};
+use crate::domain::refund::{Refund, RefundError, Status};
+use crate::domain::transaction;
+use crate::repo::transaction::{get_conversions, insert_entry};
+use crate::repo::{PgLedgerRepository, RefundReader, RefundWriter};
+use async_trait::async_trait;
+
+const REFUND_COLUMNS: &str = "id, transaction_id, amount_minor, currency, reason, status, psp_reference, failure_reason, requested_by, approved_by, created_at, updated_at";
+
+/// RESERVING_STATUSES count towards the amount already refunded on a transaction.
+const RESERVING_STATUSES: [Status; 4] = [
+    Status::AwaitingApproval,
+    Status::Approved,
+    Status::Pending,
+    Status::Succeeded,
+];
+
+#[derive(sqlx::FromRow)]
+struct LockedTransaction {
+    debit_account_id: uuid::Uuid,
+    credit_account_id: uuid::Uuid,
+    amount_minor: i64,
+    currency: String,
+    status: transaction::Status,
+}
+
+/// lock_transaction locks the transaction row until the database transaction ends, which
+/// serialises every refund of the same transaction.
+async fn lock_transaction(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    id: uuid::Uuid,
+) -> anyhow::Result<LockedTransaction> {
+    let result = sqlx::query_as::<_, LockedTransaction>(
+        r#"
+        SELECT debit_account_id, credit_account_id, amount_minor, currency, status
+        FROM transactions
+        WHERE id = $1 AND kind = 'transfer'
+        FOR UPDATE
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(&mut **tx)
+    .await;
+
+    match result {
+        Ok(Some(transaction)) => Ok(transaction),
+        Ok(None) => Err(RefundError::TransactionNotFound(id).into()),
+        Err(e) => anyhow::bail!("Failed to lock transaction: {e}"),
+    }
+}
+
+/// sum_refunds adds up the refunds of a transaction in the given statuses, leaving out the
+/// refund being checked.
+async fn sum_refunds(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    transaction_id: uuid::Uuid,
+    statuses: &[Status],
+    excluding: Option<uuid::Uuid>,
+) -> anyhow::Result<i64> {
+    let result = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COALESCE(SUM(amount_minor), 0)::BIGINT
+        FROM refunds
+        WHERE transaction_id = $1 AND status = ANY($2) AND ($3::UUID IS NULL OR id <> $3)
+        "#,
+    )
+    .bind(transaction_id)
+    .bind(statuses.to_vec())
+    .bind(excluding)
+    .fetch_one(&mut **tx)
+    .await;
+
+    match result {
+        Ok(sum) => Ok(sum),
+        Err(e) => anyhow::bail!("Failed to sum refunds: {e}"),
+    }
+}
+
+/// check_refundable rejects refunds of transactions which did not settle, in another currency
+/// or which would take cumulative refunds above the original amount.
+async fn check_refundable(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    transaction: LockedTransaction,
+    refund: &Refund,
+) -> anyhow::Result<()> {
+    if !transaction.status.is_refundable() {
+        return Err(RefundError::NotRefundable(refund.transaction_id, transaction.status).into());
+    }
+    if !transaction.currency.eq_ignore_ascii_case(&refund.currency) {
+        return Err(RefundError::CurrencyMismatch {
+            expected: transaction.currency,
+            actual: refund.currency.clone(),
+        }
+        .into());
+    }
+
+    let reserved = sum_refunds(
+        tx,
+        refund.transaction_id,
+        &RESERVING_STATUSES,
+        Some(refund.id),
+    )
+    .await?;
+    let remaining = transaction.amount_minor - reserved;
+    if refund.amount_minor > remaining {
+        return Err(RefundError::ExceedsOriginal {
+            transaction_id: refund.transaction_id,
+            requested_minor: refund.amount_minor,
+            remaining_minor: remaining,
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+async fn set_transaction_status(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    id: uuid::Uuid,
+    status: transaction::Status,
+) -> anyhow::Result<()> {
+    let result = sqlx::query(
+        r#"
+        WITH updated AS (
+            UPDATE transactions
+            SET status = $2, updated_at = now()
+            WHERE id = $1
+            RETURNING id, status, updated_at
+        )
+        INSERT INTO transaction_status_history (transaction_id, status, created_at)
+        SELECT id, status, updated_at FROM updated
+        "#,
+    )
+    .bind(id)
+    .bind(status)
+    .execute(&mut **tx)
+    .await;
+
+    match result {
+        Ok(_) => Ok(()),
+        Err(e) => anyhow::bail!("Failed to update transaction status: {e}"),
+    }
+}
+
+#[async_trait]
+impl RefundWriter for PgLedgerRepository {
+    async fn begin_refund(&self, refund: &Refund) -> anyhow::Result<Refund> {
+        if refund.amount_minor <= 0 {
+            return Err(RefundError::InvalidAmount(refund.amount_minor).into());
+        }
+
+        let mut tx = self.db.writer.begin().await?;
+        let transaction = lock_transaction(&mut tx, refund.transaction_id).await?;
+
+        // a redelivered refund is returned as is, approved and failed refunds are processed
+        let existing = sqlx::query_as::<_, Refund>(&format!(
+            "SELECT {REFUND_COLUMNS} FROM refunds WHERE id = $1"
+        ))
+        .bind(refund.id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        match existing {
+            Some(existing) if matches!(existing.status, Status::Pending | Status::Succeeded) => {
+                return Ok(existing);
+            }
+            Some(existing)
+                if matches!(existing.status, Status::AwaitingApproval | Status::Rejected) =>
+            {
+                return Err(RefundError::NotApproved(existing.id, existing.status).into());
+            }
+            _ => {}
+        }
+
+        check_refundable(&mut tx, transaction, refund).await?;
+
+        let result = sqlx::query_as::<_, Refund>(&format!(
+            r#"
+            INSERT INTO refunds (id, transaction_id, amount_minor, currency, reason, status, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, 'pending', $6, $7)
+            ON CONFLICT (id) DO UPDATE
+            SET amount_minor = EXCLUDED.amount_minor, status = 'pending', failure_reason = NULL, updated_at = EXCLUDED.updated_at
+            RETURNING {REFUND_COLUMNS}
+            "#
+        ))
+        .bind(refund.id)
+        .bind(refund.transaction_id)
+        .bind(refund.amount_minor)
+        .bind(refund.currency.as_str())
+        .bind(refund.reason.as_str())
+        .bind(refund.created_at)
+        .bind(refund.updated_at)
+        .fetch_one(&mut *tx)
+        .await;
+
+        let created = match result {
+            Ok(created) => created,
+            Err(e) => anyhow::bail!("Failed to insert refund into database: {e}"),
+        };
+
+        set_transaction_status(&mut tx, refund.transaction_id, transaction::Status::Refund).await?;
+        tx.commit().await?;
+
+        Ok(created)
+    }
+
+    async fn complete_refund(&self, id: uuid::Uuid, psp_reference: &str) -> anyhow::Result<Refund> {
+        let refund = self.get_refund_by_id(id).await?;
+
+        let mut tx = self.db.writer.begin().await?;
+        let transaction = lock_transaction(&mut tx, refund.transaction_id).await?;
+
+        let result = sqlx::query_as::<_, Refund>(&format!(
+            r#"
+            UPDATE refunds
+            SET status = 'succeeded', psp_reference = $2, updated_at = now()
+            WHERE id = $1 AND status = 'pending'
+            RETURNING {REFUND_COLUMNS}
+            "#
+        ))
+        .bind(id)
+        .bind(psp_reference)
+        .fetch_optional(&mut *tx)
+        .await;
+
+        let refund = match result {
+            Ok(Some(refund)) => refund,
+            // already completed by an earlier delivery, the entries are in place
+            Ok(None) if refund.status == Status::Succeeded => return Ok(refund),
+            Ok(None) => anyhow::bail!(
+                "refund {id} is {} and cannot be completed",
+                refund.status.as_ref()
+            ),
+            Err(e) => anyhow::bail!("Failed to complete_refund: {e}"),
+        };
+
+        // reverse the original entries for the refunded amount, a cross-currency transaction
+        // back through the FX accounts at the rate it was converted at. Converting the running
+        // total keeps partial refunds from adding up to more than was credited.
+        let now = chrono::Utc::now();
+        let legs = match get_conversions(&mut *tx, &[refund.transaction_id])
+            .await?
+            .pop()
+        {
+            Some(fx) => {
+                let before = sum_refunds(
+                    &mut tx,
+                    refund.transaction_id,
+                    &[Status::Succeeded],
+                    Some(refund.id),
+                )
+                .await?;
+                let credit_amount_minor = fx
+                    .convert(before + refund.amount_minor, &refund.currency)?
+                    - fx.convert(before, &refund.currency)?;
+                vec![
+                    (
+                        transaction.credit_account_id,
+                        Type::Debit,
+                        credit_amount_minor,
+                        fx.credit_currency.clone(),
+                    ),
+                    (
+                        fx.credit_fx_account_id,
+                        Type::Credit,
+                        credit_amount_minor,
+                        fx.credit_currency.clone(),
+                    ),
+                    (
+                        fx.debit_fx_account_id,
+                        Type::Debit,
+                        refund.amount_minor,
+                        refund.currency.clone(),
+                    ),
+                    (
+                        transaction.debit_account_id,
+                        Type::Credit,
+                        refund.amount_minor,
+                        refund.currency.clone(),
+                    ),
+                ]
+            }
+            None => vec![
+                (
+                    transaction.credit_account_id,
+                    Type::Debit,
+                    refund.amount_minor,
+                    refund.currency.clone(),
+                ),
+                (
+                    transaction.debit_account_id,
+                    Type::Credit,
+                    refund.amount_minor,
+                    refund.currency.clone(),
+                ),
+            ],
+        };
+        for (account_id, entry_type, amount_minor, currency) in legs {
+            let entry = Entry {
+                id: uuid::Uuid::new_v4(),
+                transaction_id: refund.transaction_id,
+                account_id,
+                entry_type,
+                amount_minor,
+                currency,
+                refund_id: Some(refund.id),
+                description: None,
+                leg: None,
+                created_at: now,
+            };
+            insert_entry(&mut tx, &entry).await?;
+        }
+
+        let refunded =
+            sum_refunds(&mut tx, refund.transaction_id, &[Status::Succeeded], None).await?;
+        let status = if refunded >= transaction.amount_minor {
+            transaction::Status::Refunded
+        } else {
+            transaction::Status::Refund
+        };
+        set_transaction_status(&mut tx, refund.transaction_id, status).await?;
+        tx.commit().await?;
+
+        Ok(refund)
+    }
+
+    async fn fail_refund(&self, id: uuid::Uuid, failure_reason: &str) -> anyhow::Result<Refund> {
+        let refund = self.get_refund_by_id(id).await?;
+
+        let mut tx = self.db.writer.begin().await?;
+        let transaction = lock_transaction(&mut tx, refund.transaction_id).await?;
+
+        let result = sqlx::query_as::<_, Refund>(&format!(
+            r#"
+            UPDATE refunds
+            SET status = 'failed', failure_reason = $2, updated_at = now()
+            WHERE id = $1 AND status = 'pending'
+            RETURNING {REFUND_COLUMNS}
+            "#
+        ))
+        .bind(id)
+        .bind(failure_reason)
+        .fetch_optional(&mut *tx)
+        .await;
+
+        let refund = match result {
+            Ok(Some(refund)) => refund,
+            Ok(None) => anyhow::bail!(
+                "refund {id} is {} and cannot be failed",
+                refund.status.as_ref()
+            ),
+            Err(e) => anyhow::bail!("Failed to fail_refund: {e}"),
+        };
+
+        // the transaction goes back to SUCCESS when no other refund is pending or done
+        let reserved = sum_refunds(
+            &mut tx,
+            refund.transaction_id,
+            &[Status::Pending, Status::Succeeded],
+            None,
+        )
+        .await?;
+        if reserved == 0 && transaction.status == transaction::Status::Refund {
+            set_transaction_status(&mut tx, refund.transaction_id, transaction::Status::Success)
+                .await?;
+        }
+        tx.commit().await?;
+
+        Ok(refund)
+    }
+
+    async fn request_refund(&self, refund: &Refund) -> anyhow::Result<Refund> {
+        if refund.amount_minor <= 0 {
+            return Err(RefundError::InvalidAmount(refund.amount_minor).into());
+        }
+
+        let mut tx = self.db.writer.begin().await?;
+        let transaction = lock_transaction(&mut tx, refund.transaction_id).await?;
+        check_refundable(&mut tx, transaction, refund).await?;
+
+        let result = sqlx::query_as::<_, Refund>(&format!(
+            r#"
+            INSERT INTO refunds (id, transaction_id, amount_minor, currency, reason, status, requested_by, approved_by, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING {REFUND_COLUMNS}
+            "#
+        ))
+        .bind(refund.id)
+        .bind(refund.transaction_id)
+        .bind(refund.amount_minor)
+        .bind(refund.currency.as_str())
+        .bind(refund.reason.as_str())
+        .bind(refund.status.clone())
+        .bind(refund.requested_by.as_deref())
+        .bind(refund.approved_by.as_deref())
+        .bind(refund.created_at)
+        .bind(refund.updated_at)
+        .fetch_one(&mut *tx)
+        .await;
+
+        let created = match result {
+            Ok(created) => created,
+            Err(e) => anyhow::bail!("Failed to insert refund into database: {e}"),
+        };
+        tx.commit().await?;
+
+        Ok(created)
+    }
+
+    async fn decide_refund(
+        &self,
+        id: uuid::Uuid,
+        approver: &str,
+        approve: bool,
+    ) -> anyhow::Result<Refund> {
+        let status = match approve {
+            true => Status::Approved,
+            false => Status::Rejected,
+        };
+
+        let result = sqlx::query_as::<_, Refund>(&format!(
+            r#"
+            UPDATE refunds
+            SET status = $2, approved_by = $3, updated_at = now()
+            WHERE id = $1 AND status = 'awaiting_approval'
+            RETURNING {REFUND_COLUMNS}
+            "#
+        ))
+        .bind(id)
+        .bind(status)
+        .bind(approver)
+        .fetch_optional(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(Some(refund)) => Ok(refund),
+            Ok(None) => {
+                let refund = self.get_refund_by_id(id).await?;
+                Err(RefundError::AlreadyDecided(id, refund.status).into())
+            }
+            Err(e) => anyhow::bail!("Failed to decide_refund: {e}"),
+        }
+    }
+}
+
+#[async_trait]
+impl RefundReader for PgLedgerRepository {
+    async fn get_refund_by_id(&self, id: uuid::Uuid) -> anyhow::Result<Refund> {
+        let result = sqlx::query_as::<_, Refund>(&format!(
+            "SELECT {REFUND_COLUMNS} FROM refunds WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(Some(refund)) => Ok(refund),
+            Ok(None) => Err(RefundError::RefundNotFound(id).into()),
+            Err(e) => anyhow::bail!("Failed to get_refund_by_id: {e}"),
+        }
+    }
+
+    async fn get_refunds_by_transaction_id(
+        &self,
+        transaction_id: uuid::Uuid,
+    ) -> anyhow::Result<Vec<Refund>> {
+        let result = sqlx::query_as::<_, Refund>(&format!(
+            "SELECT {REFUND_COLUMNS} FROM refunds WHERE transaction_id = $1 ORDER BY created_at"
+        ))
+        .bind(transaction_id)
+        .fetch_all(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(refunds) => Ok(refunds),
+            Err(e) => anyhow::bail!("Failed to get_refunds_by_transaction_id: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repo::LedgerReader;
+    use crate::repo::transaction::tests::seed_fx_transaction;
+    use crate::test_util::seed_transaction;
+
+    async fn repo(pool: sqlx::PgPool) -> PgLedgerRepository {
+        PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap())
+    }
+
+    fn refund(transaction_id: uuid::Uuid, amount_minor: i64) -> Refund {
+        Refund::new(
+            uuid::Uuid::new_v4(),
+            transaction_id,
+            amount_minor,
+            "USD",
+            "requested_by_customer",
+        )
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_refund_transaction_in_full(pool: sqlx::PgPool) {
+        // arrange
+        let repo = repo(pool).await;
+        let transaction = seed_transaction(&repo, 1000, transaction::Status::Success).await;
+        let refund = repo
+            .begin_refund(&refund(transaction.id, 1000))
+            .await
+            .unwrap();
+        assert_eq!(
+            repo.get_transaction_by_id(transaction.id)
+                .await
+                .unwrap()
+                .status,
+            transaction::Status::Refund
+        );
+
+        // act
+        let completed = repo.complete_refund(refund.id, "psp-ref-1").await.unwrap();
+
+        // assert
+        assert_eq!(completed.status, Status::Succeeded);
+        assert_eq!(completed.psp_reference.as_deref(), Some("psp-ref-1"));
+        assert_eq!(
+            repo.get_transaction_by_id(transaction.id)
+                .await
+                .unwrap()
+                .status,
+            transaction::Status::Refunded
+        );
+
+        // assert - reversing entries move the money back
+        let entries = repo
+            .get_entries_by_transaction_id(transaction.id)
+            .await
+            .unwrap();
+        let reversing: Vec<&Entry> = entries
+            .iter()
+            .filter(|e| e.refund_id == Some(refund.id))
+            .collect();
+        assert_eq!(reversing.len(), 2);
+        let debit = reversing
+            .iter()
+            .find(|e| e.entry_type == Type::Debit)
+            .unwrap();
+        assert_eq!(debit.account_id, transaction.credit_account_id);
+        assert_eq!(debit.amount_minor, 1000);
+        let credit = reversing
+            .iter()
+            .find(|e| e.entry_type == Type::Credit)
+            .unwrap();
+        assert_eq!(credit.account_id, transaction.debit_account_id);
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_refund_transaction_partially(pool: sqlx::PgPool) {
+        // arrange
+        let repo = repo(pool).await;
+        let transaction = seed_transaction(&repo, 1000, transaction::Status::Success).await;
+
+        // act
+        let first = repo
+            .begin_refund(&refund(transaction.id, 400))
+            .await
+            .unwrap();
+        repo.complete_refund(first.id, "psp-ref-1").await.unwrap();
+        let after_first = repo.get_transaction_by_id(transaction.id).await.unwrap();
+        let second = repo
+            .begin_refund(&refund(transaction.id, 600))
+            .await
+            .unwrap();
+        repo.complete_refund(second.id, "psp-ref-2").await.unwrap();
+        let after_second = repo.get_transaction_by_id(transaction.id).await.unwrap();
+
+        // assert
+        assert_eq!(after_first.status, transaction::Status::Refund);
+        assert_eq!(after_second.status, transaction::Status::Refunded);
+        let refunds = repo
+            .get_refunds_by_transaction_id(transaction.id)
+            .await
+            .unwrap();
+        assert_eq!(refunds.len(), 2);
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_refund_fx_transaction_through_fx_accounts(pool: sqlx::PgPool) {
+        // arrange - 100.00 EUR credited 107.42 USD
+        let repo = repo(pool).await;
+        let transaction = seed_fx_transaction(&repo, 10_000).await;
+        let fx = transaction.fx.clone().unwrap();
+
+        // act
+        let mut refunds = vec![];
+        for amount_minor in [3_333, 3_333, 3_334] {
+            let mut eur_refund = refund(transaction.id, amount_minor);
+            eur_refund.currency = "EUR".to_string();
+            let started = repo.begin_refund(&eur_refund).await.unwrap();
+            refunds.push(repo.complete_refund(started.id, "psp-ref").await.unwrap());
+        }
+
+        // assert - each refund converts the running total, so the merchant pays back exactly
+        // what it was credited
+        let entries = repo
+            .get_entries_by_transaction_id(transaction.id)
+            .await
+            .unwrap();
+        let merchant_debits: Vec<i64> = refunds
+            .iter()
+            .map(|refund| {
+                entries
+                    .iter()
+                    .find(|e| {
+                        e.refund_id == Some(refund.id)
+                            && e.account_id == transaction.credit_account_id
+                    })
+                    .unwrap()
+                    .amount_minor
+            })
+            .collect();
+        assert_eq!(merchant_debits, vec![3_580, 3_580, 3_582]);
+        let balances = repo
+            .get_balances(&[
+                transaction.debit_account_id,
+                transaction.credit_account_id,
+                fx.debit_fx_account_id,
+                fx.credit_fx_account_id,
+            ])
+            .await
+            .unwrap();
+        assert_eq!(balances.len(), 4);
+        assert!(balances.iter().all(|balance| balance.amount_minor == 0));
+        assert_eq!(
+            repo.get_transaction_by_id(transaction.id)
+                .await
+                .unwrap()
+                .status,
+            transaction::Status::Refunded
+        );
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn error_when_cumulative_refunds_exceed_original_amount(pool: sqlx::PgPool) {
+        // arrange - a pending refund already reserves part of the amount
+        let repo = repo(pool).await;
+        let transaction = seed_transaction(&repo, 1000, transaction::Status::Success).await;
+        repo.begin_refund(&refund(transaction.id, 700))
+            .await
+            .unwrap();
+
+        // act
+        let result = repo.begin_refund(&refund(transaction.id, 301)).await;
+
+        // assert
+        assert_eq!(
+            result.unwrap_err().downcast_ref::<RefundError>(),
+            Some(&RefundError::ExceedsOriginal {
+                transaction_id: transaction.id,
+                requested_minor: 301,
+                remaining_minor: 300,
+            })
+        );
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_begin_refund_idempotently(pool: sqlx::PgPool) {
+        // arrange
+        let repo = repo(pool).await;
+        let transaction = seed_transaction(&repo, 1000, transaction::Status::Success).await;
+        let refund = refund(transaction.id, 1000);
+        let first = repo.begin_refund(&refund).await.unwrap();
+        repo.complete_refund(first.id, "psp-ref-1").await.unwrap();
+
+        // act - redelivery of the same refund
+        let second = repo.begin_refund(&refund).await.unwrap();
+        let completed_again = repo.complete_refund(first.id, "psp-ref-1").await.unwrap();
+
+        // assert - nothing is posted twice
+        assert_eq!(second.status, Status::Succeeded);
+        assert_eq!(completed_again.status, Status::Succeeded);
+        let entries = repo
+            .get_entries_by_transaction_id(transaction.id)
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 4);
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_release_amount_when_refund_fails(pool: sqlx::PgPool) {
+        // arrange
+        let repo = repo(pool).await;
+        let transaction = seed_transaction(&repo, 1000, transaction::Status::Success).await;
+        let refund = refund(transaction.id, 1000);
+        repo.begin_refund(&refund).await.unwrap();
+
+        // act
+        let failed = repo.fail_refund(refund.id, "card expired").await.unwrap();
+
+        // assert
+        assert_eq!(failed.status, Status::Failed);
+        assert_eq!(failed.failure_reason.as_deref(), Some("card expired"));
+        assert_eq!(
+            repo.get_transaction_by_id(transaction.id)
+                .await
+                .unwrap()
+                .status,
+            transaction::Status::Success
+        );
+
+        // assert - a failed refund can be retried
+        let retried = repo.begin_refund(&refund).await.unwrap();
+        assert_eq!(retried.status, Status::Pending);
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn error_when_transaction_is_not_refundable(pool: sqlx::PgPool) {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            status: transaction::Status,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "error when transaction is not settled yet",
+                status: transaction::Status::Pending,
+            },
+            TestCase {
+                name: "error when transaction failed",
+                status: transaction::Status::Failed,
+            },
+            TestCase {
+                name: "error when transaction is already refunded",
+                status: transaction::Status::Refunded,
+            },
+        ];
+
+        let repo = repo(pool).await;
+        for test_case in test_cases {
+            // arrange
+            let transaction = seed_transaction(&repo, 1000, transaction::Status::Success).await;
+            crate::repo::LedgerWriter::update_transaction_status(
+                &repo,
+                transaction.id,
+                test_case.status.clone(),
+            )
+            .await
+            .unwrap();
+
+            // act
+            let result = repo.begin_refund(&refund(transaction.id, 100)).await;
+
+            // assert
+            assert_eq!(
+                result.unwrap_err().downcast_ref::<RefundError>(),
+                Some(&RefundError::NotRefundable(
+                    transaction.id,
+                    test_case.status
+                )),
+                "{}",
+                test_case.name
+            );
+        }
+    }
+
+    fn manual_refund(transaction_id: uuid::Uuid, amount_minor: i64, status: Status) -> Refund {
+        let mut refund = refund(transaction_id, amount_minor);
+        refund.status = status;
+        refund.requested_by = Some("alice".to_string());
+        refund
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_process_refund_only_once_approved(pool: sqlx::PgPool) {
+        // arrange
+        let repo = repo(pool).await;
+        let transaction = seed_transaction(&repo, 1000, transaction::Status::Success).await;
+        let requested = repo
+            .request_refund(&manual_refund(
+                transaction.id,
+                800,
+                Status::AwaitingApproval,
+            ))
+            .await
+            .unwrap();
+
+        // act
+        let before_approval = repo.begin_refund(&requested).await;
+        let approved = repo.decide_refund(requested.id, "bob", true).await.unwrap();
+        let begun = repo.begin_refund(&approved).await.unwrap();
+
+        // assert
+        assert_eq!(
+            before_approval.unwrap_err().downcast_ref::<RefundError>(),
+            Some(&RefundError::NotApproved(
+                requested.id,
+                Status::AwaitingApproval
+            ))
+        );
+        assert_eq!(approved.approved_by.as_deref(), Some("bob"));
+        assert_eq!(begun.status, Status::Pending);
+        assert_eq!(begun.requested_by.as_deref(), Some("alice"));
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn error_when_requested_refunds_exceed_original_amount(pool: sqlx::PgPool) {
+        // arrange - a refund awaiting approval reserves its amount
+        let repo = repo(pool).await;
+        let transaction = seed_transaction(&repo, 1000, transaction::Status::Success).await;
+        repo.request_refund(&manual_refund(
+            transaction.id,
+            800,
+            Status::AwaitingApproval,
+        ))
+        .await
+        .unwrap();
+
+        // act
+        let result = repo
+            .request_refund(&manual_refund(transaction.id, 300, Status::Approved))
+            .await;
+
+        // assert
+        assert!(matches!(
+            result.unwrap_err().downcast_ref::<RefundError>(),
+            Some(RefundError::ExceedsOriginal {
+                remaining_minor: 200,
+                ..
+            })
+        ));
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn error_when_refund_is_decided_twice(pool: sqlx::PgPool) {
+        // arrange
+        let repo = repo(pool).await;
+        let transaction = seed_transaction(&repo, 1000, transaction::Status::Success).await;
+        let requested = repo
+            .request_refund(&manual_refund(
+                transaction.id,
+                800,
+                Status::AwaitingApproval,
+            ))
+            .await
+            .unwrap();
+        repo.decide_refund(requested.id, "bob", false)
+            .await
+            .unwrap();
+
+        // act
+        let result = repo.decide_refund(requested.id, "carol", true).await;
+
+        // assert
+        assert_eq!(
+            result.unwrap_err().downcast_ref::<RefundError>(),
+            Some(&RefundError::AlreadyDecided(requested.id, Status::Rejected))
+        );
+    }
+}
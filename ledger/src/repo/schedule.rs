@@ -0,0 +1,557 @@
+use crate::domain::schedule::{Run, RunStatus, Schedule, ScheduleError, Status};
+use crate::repo::{PgLedgerRepository, ScheduleReader, ScheduleWriter};
+use async_trait::async_trait;
+
+const SCHEDULE_COLUMNS: &str = "id, debit_account_id, credit_account_id, amount_minor, currency, cron, interval_seconds, starts_at, ends_at, next_run_at, status, idempotency_key, created_at, updated_at";
+const RUN_COLUMNS: &str = "id, schedule_id, occurrence_at, status, transaction_id, error, claimed_until, created_at, updated_at";
+
+/// lock_schedule reads the schedule for update.
+async fn lock_schedule(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    id: uuid::Uuid,
+) -> anyhow::Result<Schedule> {
+    let result = sqlx::query_as::<_, Schedule>(&format!(
+        "SELECT {SCHEDULE_COLUMNS} FROM schedules WHERE id = $1 FOR UPDATE"
+    ))
+    .bind(id)
+    .fetch_optional(&mut **tx)
+    .await;
+
+    match result {
+        Ok(Some(schedule)) => Ok(schedule),
+        Ok(None) => Err(ScheduleError::NotFound(id).into()),
+        Err(e) => anyhow::bail!("Failed to lock schedule: {e}"),
+    }
+}
+
+/// update_schedule stores the status and next run of the schedule.
+async fn update_schedule(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    schedule: &Schedule,
+) -> anyhow::Result<Schedule> {
+    let result = sqlx::query_as::<_, Schedule>(&format!(
+        r#"
+        UPDATE schedules
+        SET status = $2, next_run_at = $3, updated_at = now()
+        WHERE id = $1
+        RETURNING {SCHEDULE_COLUMNS}
+        "#
+    ))
+    .bind(schedule.id)
+    .bind(schedule.status)
+    .bind(schedule.next_run_at)
+    .fetch_one(&mut **tx)
+    .await;
+
+    match result {
+        Ok(updated) => Ok(updated),
+        Err(e) => anyhow::bail!("Failed to update schedule: {e}"),
+    }
+}
+
+/// insert_run records an occurrence of the schedule, None when it was recorded before.
+async fn insert_run(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    run: &Run,
+) -> anyhow::Result<Option<Run>> {
+    let result = sqlx::query_as::<_, Run>(&format!(
+        r#"
+        INSERT INTO schedule_runs (id, schedule_id, occurrence_at, status, transaction_id, error, claimed_until, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        ON CONFLICT (schedule_id, occurrence_at) DO NOTHING
+        RETURNING {RUN_COLUMNS}
+        "#
+    ))
+    .bind(run.id)
+    .bind(run.schedule_id)
+    .bind(run.occurrence_at)
+    .bind(run.status)
+    .bind(run.transaction_id)
+    .bind(run.error.as_deref())
+    .bind(run.claimed_until)
+    .bind(run.created_at)
+    .bind(run.updated_at)
+    .fetch_optional(&mut **tx)
+    .await;
+
+    match result {
+        Ok(run) => Ok(run),
+        Err(e) => anyhow::bail!("Failed to insert schedule run into database: {e}"),
+    }
+}
+
+#[async_trait]
+impl ScheduleWriter for PgLedgerRepository {
+    async fn create_schedule(&self, schedule: &Schedule) -> anyhow::Result<Schedule> {
+        let result = sqlx::query_as::<_, Schedule>(&format!(
+            r#"
+            INSERT INTO schedules (id, debit_account_id, credit_account_id, amount_minor, currency, cron, interval_seconds, starts_at, ends_at, next_run_at, status, idempotency_key, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            RETURNING {SCHEDULE_COLUMNS}
+            "#
+        ))
+        .bind(schedule.id)
+        .bind(schedule.debit_account_id)
+        .bind(schedule.credit_account_id)
+        .bind(schedule.amount_minor)
+        .bind(schedule.currency.as_str())
+        .bind(schedule.cron.as_deref())
+        .bind(schedule.interval_seconds)
+        .bind(schedule.starts_at)
+        .bind(schedule.ends_at)
+        .bind(schedule.next_run_at)
+        .bind(schedule.status)
+        .bind(schedule.idempotency_key.as_str())
+        .bind(schedule.created_at)
+        .bind(schedule.updated_at)
+        .fetch_one(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(created) => Ok(created),
+            Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => {
+                Err(ScheduleError::UnknownAccount.into())
+            }
+            Err(sqlx::Error::Database(e))
+                if e.constraint() == Some("schedules_idempotency_key_key") =>
+            {
+                Err(ScheduleError::IdempotencyKeyReused(schedule.idempotency_key.clone()).into())
+            }
+            Err(e) => anyhow::bail!("Failed to insert schedule into database: {e}"),
+        }
+    }
+
+    async fn claim_run(
+        &self,
+        id: uuid::Uuid,
+        occurrence_at: chrono::DateTime<chrono::Utc>,
+        next_run_at: Option<chrono::DateTime<chrono::Utc>>,
+        claimed_until: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<Option<Run>> {
+        let mut tx = self.db.writer.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE schedules
+            SET next_run_at = $3,
+                status = CASE WHEN $3::TIMESTAMPTZ IS NULL THEN $4 ELSE status END,
+                updated_at = now()
+            WHERE id = $1 AND next_run_at = $2 AND status = $5
+            "#,
+        )
+        .bind(id)
+        .bind(occurrence_at)
+        .bind(next_run_at)
+        .bind(Status::Completed)
+        .bind(Status::Active)
+        .execute(&mut *tx)
+        .await;
+
+        match result {
+            Ok(result) if result.rows_affected() == 0 => return Ok(None),
+            Ok(_) => {}
+            Err(e) => anyhow::bail!("Failed to claim_run: {e}"),
+        }
+
+        let run = Run {
+            claimed_until: Some(claimed_until),
+            ..Run::new(id, occurrence_at, RunStatus::Pending)
+        };
+        let claimed = insert_run(&mut tx, &run).await?;
+
+        tx.commit().await?;
+
+        Ok(claimed)
+    }
+
+    async fn claim_pending_runs(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        claimed_until: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<Run>> {
+        let result = sqlx::query_as::<_, Run>(&format!(
+            r#"
+            UPDATE schedule_runs
+            SET claimed_until = $2, updated_at = now()
+            WHERE id IN (
+                SELECT id FROM schedule_runs
+                WHERE status = $3 AND (claimed_until IS NULL OR claimed_until <= $1)
+                ORDER BY created_at
+                LIMIT $4
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING {RUN_COLUMNS}
+            "#
+        ))
+        .bind(now)
+        .bind(claimed_until)
+        .bind(RunStatus::Pending)
+        .bind(limit)
+        .fetch_all(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(mut runs) => {
+                runs.sort_by_key(|run| run.created_at);
+                Ok(runs)
+            }
+            Err(e) => anyhow::bail!("Failed to claim_pending_runs: {e}"),
+        }
+    }
+
+    async fn complete_run(
+        &self,
+        id: uuid::Uuid,
+        status: RunStatus,
+        transaction_id: Option<uuid::Uuid>,
+        error: Option<&str>,
+    ) -> anyhow::Result<Option<Run>> {
+        let result = sqlx::query_as::<_, Run>(&format!(
+            r#"
+            UPDATE schedule_runs
+            SET status = $2, transaction_id = $3, error = $4, claimed_until = NULL, updated_at = now()
+            WHERE id = $1 AND status = $5
+            RETURNING {RUN_COLUMNS}
+            "#
+        ))
+        .bind(id)
+        .bind(status)
+        .bind(transaction_id)
+        .bind(error)
+        .bind(RunStatus::Pending)
+        .fetch_optional(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(run) => Ok(run),
+            Err(e) => anyhow::bail!("Failed to complete_run: {e}"),
+        }
+    }
+
+    async fn pause_schedule(&self, id: uuid::Uuid) -> anyhow::Result<Schedule> {
+        let mut tx = self.db.writer.begin().await?;
+
+        let mut schedule = lock_schedule(&mut tx, id).await?;
+        schedule.pause()?;
+        let paused = update_schedule(&mut tx, &schedule).await?;
+
+        tx.commit().await?;
+
+        Ok(paused)
+    }
+
+    async fn resume_schedule(
+        &self,
+        id: uuid::Uuid,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<Schedule> {
+        let mut tx = self.db.writer.begin().await?;
+
+        let mut schedule = lock_schedule(&mut tx, id).await?;
+        schedule.resume(now)?;
+        let resumed = update_schedule(&mut tx, &schedule).await?;
+
+        tx.commit().await?;
+
+        Ok(resumed)
+    }
+
+    async fn cancel_schedule(&self, id: uuid::Uuid) -> anyhow::Result<Schedule> {
+        let mut tx = self.db.writer.begin().await?;
+
+        let mut schedule = lock_schedule(&mut tx, id).await?;
+        schedule.cancel()?;
+        let cancelled = update_schedule(&mut tx, &schedule).await?;
+
+        tx.commit().await?;
+
+        Ok(cancelled)
+    }
+
+    async fn skip_schedule_run(&self, id: uuid::Uuid) -> anyhow::Result<(Schedule, Run)> {
+        let mut tx = self.db.writer.begin().await?;
+
+        let mut schedule = lock_schedule(&mut tx, id).await?;
+        let skipped_at = schedule.skip()?;
+        let skipped =
+            match insert_run(&mut tx, &Run::new(id, skipped_at, RunStatus::Skipped)).await? {
+                Some(skipped) => skipped,
+                None => anyhow::bail!(
+                    "Failed to skip_schedule_run: occurrence {skipped_at} was already run"
+                ),
+            };
+        let updated = update_schedule(&mut tx, &schedule).await?;
+
+        tx.commit().await?;
+
+        Ok((updated, skipped))
+    }
+}
+
+#[async_trait]
+impl ScheduleReader for PgLedgerRepository {
+    async fn get_schedule_by_id(&self, id: uuid::Uuid) -> anyhow::Result<Schedule> {
+        let result = sqlx::query_as::<_, Schedule>(&format!(
+            "SELECT {SCHEDULE_COLUMNS} FROM schedules WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_one(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(schedule) => Ok(schedule),
+            Err(sqlx::Error::RowNotFound) => Err(ScheduleError::NotFound(id).into()),
+            Err(e) => anyhow::bail!("Failed to get_schedule_by_id: {e}"),
+        }
+    }
+
+    async fn get_schedule_by_idempotency_key(
+        &self,
+        idempotency_key: &str,
+    ) -> anyhow::Result<Option<Schedule>> {
+        let result = sqlx::query_as::<_, Schedule>(&format!(
+            "SELECT {SCHEDULE_COLUMNS} FROM schedules WHERE idempotency_key = $1"
+        ))
+        .bind(idempotency_key)
+        .fetch_optional(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(schedule) => Ok(schedule),
+            Err(e) => anyhow::bail!("Failed to get_schedule_by_idempotency_key: {e}"),
+        }
+    }
+
+    async fn get_schedules(&self, account_id: Option<uuid::Uuid>) -> anyhow::Result<Vec<Schedule>> {
+        let result = sqlx::query_as::<_, Schedule>(&format!(
+            r#"
+            SELECT {SCHEDULE_COLUMNS} FROM schedules
+            WHERE $1::UUID IS NULL OR debit_account_id = $1 OR credit_account_id = $1
+            ORDER BY created_at DESC, id
+            "#
+        ))
+        .bind(account_id)
+        .fetch_all(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(schedules) => Ok(schedules),
+            Err(e) => anyhow::bail!("Failed to get_schedules: {e}"),
+        }
+    }
+
+    async fn get_schedule_runs(&self, schedule_id: uuid::Uuid) -> anyhow::Result<Vec<Run>> {
+        let result = sqlx::query_as::<_, Run>(&format!(
+            "SELECT {RUN_COLUMNS} FROM schedule_runs WHERE schedule_id = $1 ORDER BY occurrence_at DESC"
+        ))
+        .bind(schedule_id)
+        .fetch_all(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(runs) => Ok(runs),
+            Err(e) => anyhow::bail!("Failed to get_schedule_runs: {e}"),
+        }
+    }
+
+    async fn get_due_schedules(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<Schedule>> {
+        let result = sqlx::query_as::<_, Schedule>(&format!(
+            r#"
+            SELECT {SCHEDULE_COLUMNS} FROM schedules
+            WHERE status = $1 AND next_run_at <= $2
+            ORDER BY next_run_at
+            LIMIT $3
+            "#
+        ))
+        .bind(Status::Active)
+        .bind(now)
+        .bind(limit)
+        .fetch_all(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(schedules) => Ok(schedules),
+            Err(e) => anyhow::bail!("Failed to get_due_schedules: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repo::LedgerWriter;
+    use chrono::SubsecRound;
+
+    async fn schedule(repo: &PgLedgerRepository) -> Schedule {
+        let (customer, merchant) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        repo.create_account(customer, "CUSTOMER").await.unwrap();
+        repo.create_account(merchant, "MERCHANT").await.unwrap();
+        let mut schedule = Schedule::new(
+            customer,
+            merchant,
+            1000,
+            "USD",
+            None,
+            Some(3600),
+            Some(chrono::Utc::now() - chrono::Duration::minutes(90)),
+            None,
+            uuid::Uuid::new_v4().to_string(),
+        );
+        schedule.next_run_at = schedule.next_occurrence(None).unwrap();
+
+        repo.create_schedule(&schedule).await.unwrap()
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_claim_each_occurrence_once(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let schedule = schedule(&repo).await;
+        let occurrence_at = schedule.next_run_at.unwrap();
+        let next_run_at = schedule.next_occurrence(Some(occurrence_at)).unwrap();
+        let claimed_until = chrono::Utc::now().trunc_subsecs(6) + chrono::Duration::minutes(5);
+
+        // act
+        let claimed = repo
+            .claim_run(schedule.id, occurrence_at, next_run_at, claimed_until)
+            .await
+            .unwrap();
+        let claimed_again = repo
+            .claim_run(schedule.id, occurrence_at, next_run_at, claimed_until)
+            .await
+            .unwrap();
+
+        // assert
+        let claimed = claimed.unwrap();
+        assert_eq!(claimed.status, RunStatus::Pending);
+        assert_eq!(claimed.occurrence_at, occurrence_at);
+        assert_eq!(claimed.claimed_until, Some(claimed_until));
+        assert_eq!(claimed_again, None);
+        assert_eq!(
+            repo.get_schedule_by_id(schedule.id)
+                .await
+                .unwrap()
+                .next_run_at,
+            next_run_at
+        );
+        let due = repo
+            .get_due_schedules(chrono::Utc::now(), 10)
+            .await
+            .unwrap();
+        assert_eq!(
+            due.iter().map(|due| due.id).collect::<Vec<_>>(),
+            vec![schedule.id]
+        );
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_lease_and_complete_pending_run_once(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let schedule = schedule(&repo).await;
+        let occurrence_at = schedule.next_run_at.unwrap();
+        let now = chrono::Utc::now().trunc_subsecs(6);
+        let claimed = repo
+            .claim_run(
+                schedule.id,
+                occurrence_at,
+                schedule.next_occurrence(Some(occurrence_at)).unwrap(),
+                now + chrono::Duration::minutes(5),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+
+        // act
+        let leased = repo
+            .claim_pending_runs(now, now + chrono::Duration::minutes(5), 10)
+            .await
+            .unwrap();
+        let expired = repo
+            .claim_pending_runs(
+                now + chrono::Duration::minutes(5),
+                now + chrono::Duration::minutes(10),
+                10,
+            )
+            .await
+            .unwrap();
+        let completed = repo
+            .complete_run(claimed.id, RunStatus::Failed, None, Some("declined"))
+            .await
+            .unwrap();
+        let completed_again = repo
+            .complete_run(claimed.id, RunStatus::Succeeded, None, None)
+            .await
+            .unwrap();
+
+        // assert
+        assert!(leased.is_empty());
+        assert_eq!(
+            expired.iter().map(|run| run.id).collect::<Vec<_>>(),
+            vec![claimed.id]
+        );
+        assert_eq!(
+            expired[0].claimed_until,
+            Some(now + chrono::Duration::minutes(10))
+        );
+        let completed = completed.unwrap();
+        assert_eq!(completed.status, RunStatus::Failed);
+        assert_eq!(completed.claimed_until, None);
+        assert_eq!(completed_again, None);
+        assert_eq!(
+            repo.get_schedule_runs(schedule.id).await.unwrap(),
+            vec![completed]
+        );
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_skip_pause_and_cancel_schedule(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let schedule = schedule(&repo).await;
+
+        // act
+        let (skipped, run) = repo.skip_schedule_run(schedule.id).await.unwrap();
+        let paused = repo.pause_schedule(schedule.id).await.unwrap();
+        let due = repo
+            .get_due_schedules(chrono::Utc::now(), 10)
+            .await
+            .unwrap();
+        let cancelled = repo.cancel_schedule(schedule.id).await.unwrap();
+        let resumed = repo.resume_schedule(schedule.id, chrono::Utc::now()).await;
+
+        // assert
+        assert_eq!(run.status, RunStatus::Skipped);
+        assert_eq!(Some(run.occurrence_at), schedule.next_run_at);
+        assert_eq!(
+            skipped.next_run_at,
+            schedule.next_occurrence(schedule.next_run_at).unwrap()
+        );
+        assert_eq!(paused.status, Status::Paused);
+        assert!(due.is_empty());
+        assert_eq!(
+            (cancelled.status, cancelled.next_run_at),
+            (Status::Cancelled, None)
+        );
+        assert_eq!(
+            resumed.unwrap_err().downcast_ref::<ScheduleError>(),
+            Some(&ScheduleError::InvalidTransition {
+                id: schedule.id,
+                status: Status::Cancelled,
+                action: "resumed",
+            })
+        );
+        assert_eq!(
+            repo.get_schedule_runs(schedule.id).await.unwrap(),
+            vec![run]
+        );
+    }
+}
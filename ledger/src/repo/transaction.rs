@@ -0,0 +1,1053 @@
+use crate::domain::balance::Balance;
+use crate::domain::entry::{Entry, Type};
+use crate::domain::fee::Fee;
+use crate::domain::fx::Conversion;
+use crate::domain::transaction::{
+    Filter, Status, StatusChange, StatusCount, Transaction, TransactionError,
+};
+use crate::repo::{LedgerReader, LedgerWriter, PgLedgerRepository};
+use async_trait::async_trait;
+
+pub(crate) const TRANSACTION_COLUMNS: &str = "id, debit_account_id, credit_account_id, amount_minor, currency, status, idempotency_key, request_timestamp, created_at, updated_at";
+const CONVERSION_COLUMNS: &str = "transaction_id, debit_fx_account_id, credit_fx_account_id, credit_amount_minor, credit_currency, rate, applied_rate, spread_bps, rounding_mode, rate_source, rate_as_of, created_at";
+pub(crate) const ENTRY_COLUMNS: &str = "id, transaction_id, account_id, entry_type, amount_minor, currency, refund_id, description, leg, created_at";
+
+/// insert_status_change records the status a transaction moved to as part of an open database
+/// transaction.
+pub(crate) async fn insert_status_change(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    transaction_id: uuid::Uuid,
+    status: Status,
+    created_at: chrono::DateTime<chrono::Utc>,
+) -> anyhow::Result<()> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO transaction_status_history (transaction_id, status, created_at)
+        VALUES ($1, $2, $3)
+        "#,
+    )
+    .bind(transaction_id)
+    .bind(status)
+    .bind(created_at)
+    .execute(&mut **tx)
+    .await;
+
+    match result {
+        Ok(_) => Ok(()),
+        Err(e) => anyhow::bail!("Failed to insert status change into database: {e}"),
+    }
+}
+
+/// insert_entry posts a single ledger entry as part of an open database transaction.
+pub(crate) async fn insert_entry(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    entry: &Entry,
+) -> anyhow::Result<()> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO ledger_entries (id, transaction_id, account_id, entry_type, amount_minor, currency, refund_id, description, leg, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        "#,
+    )
+    .bind(entry.id)
+    .bind(entry.transaction_id)
+    .bind(entry.account_id)
+    .bind(entry.entry_type.clone())
+    .bind(entry.amount_minor)
+    .bind(entry.currency.as_str())
+    .bind(entry.refund_id)
+    .bind(entry.description.as_deref())
+    .bind(entry.leg)
+    .bind(entry.created_at)
+    .execute(&mut **tx)
+    .await;
+
+    match result {
+        Ok(_) => Ok(()),
+        Err(e) => anyhow::bail!("Failed to insert ledger entry into database: {e}"),
+    }
+}
+
+/// insert_transaction records a transfer with its debit and credit entries, and the fee legs when
+/// a fee is charged, as part of an open database transaction.
+pub(crate) async fn insert_transaction(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    transaction: &Transaction,
+    fee: Option<&Fee>,
+) -> anyhow::Result<Transaction> {
+    let result = sqlx::query_as::<_, Transaction>(&format!(
+        r#"
+        INSERT INTO transactions (id, debit_account_id, credit_account_id, amount_minor, currency, status, idempotency_key, request_timestamp, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        RETURNING {TRANSACTION_COLUMNS}
+        "#
+    ))
+    .bind(transaction.id)
+    .bind(transaction.debit_account_id)
+    .bind(transaction.credit_account_id)
+    .bind(transaction.amount_minor)
+    .bind(transaction.currency.as_str())
+    .bind(transaction.status.clone())
+    .bind(transaction.idempotency_key.as_str())
+    .bind(transaction.request_timestamp)
+    .bind(transaction.created_at)
+    .bind(transaction.updated_at)
+    .fetch_one(&mut **tx)
+    .await;
+
+    let mut created = match result {
+        Ok(created) => created,
+        Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => {
+            return Err(TransactionError::UnknownAccount.into());
+        }
+        Err(sqlx::Error::Database(e))
+            if e.constraint() == Some("transactions_idempotency_key_key") =>
+        {
+            return Err(TransactionError::IdempotencyKeyReused(
+                transaction.idempotency_key.clone(),
+            )
+            .into());
+        }
+        Err(e) => anyhow::bail!("Failed to insert transaction into database: {e}"),
+    };
+    insert_status_change(tx, created.id, created.status.clone(), created.updated_at).await?;
+
+    // a cross-currency transfer goes through the FX account of each currency
+    let legs = match &transaction.fx {
+        Some(fx) => vec![
+            (
+                transaction.debit_account_id,
+                Type::Debit,
+                transaction.amount_minor,
+                transaction.currency.as_str(),
+            ),
+            (
+                fx.debit_fx_account_id,
+                Type::Credit,
+                transaction.amount_minor,
+                transaction.currency.as_str(),
+            ),
+            (
+                fx.credit_fx_account_id,
+                Type::Debit,
+                fx.credit_amount_minor,
+                fx.credit_currency.as_str(),
+            ),
+            (
+                transaction.credit_account_id,
+                Type::Credit,
+                fx.credit_amount_minor,
+                fx.credit_currency.as_str(),
+            ),
+        ],
+        None => vec![
+            (
+                transaction.debit_account_id,
+                Type::Debit,
+                transaction.amount_minor,
+                transaction.currency.as_str(),
+            ),
+            (
+                transaction.credit_account_id,
+                Type::Credit,
+                transaction.amount_minor,
+                transaction.currency.as_str(),
+            ),
+        ],
+    };
+    for (account_id, entry_type, amount_minor, currency) in legs {
+        let entry = Entry {
+            id: uuid::Uuid::new_v4(),
+            transaction_id: transaction.id,
+            account_id,
+            entry_type,
+            amount_minor,
+            currency: currency.to_string(),
+            refund_id: None,
+            description: None,
+            leg: None,
+            created_at: transaction.created_at,
+        };
+        insert_entry(tx, &entry).await?;
+    }
+
+    if let Some(fx) = &transaction.fx {
+        created.fx = Some(insert_conversion(tx, fx).await?);
+    }
+
+    if let Some(fee) = fee {
+        for (account_id, entry_type) in [
+            (transaction.credit_account_id, Type::Debit),
+            (fee.revenue_account_id, Type::Credit),
+        ] {
+            let entry = Entry {
+                id: uuid::Uuid::new_v4(),
+                transaction_id: transaction.id,
+                account_id,
+                entry_type,
+                amount_minor: fee.amount_minor,
+                currency: transaction.credit_currency().to_string(),
+                refund_id: None,
+                description: Some(fee.description.clone()),
+                leg: None,
+                created_at: transaction.created_at,
+            };
+            insert_entry(tx, &entry).await?;
+        }
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO transaction_fees (transaction_id, fee_schedule_id, revenue_account_id, amount_minor, monthly_volume_minor, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(transaction.id)
+        .bind(fee.fee_schedule_id)
+        .bind(fee.revenue_account_id)
+        .bind(fee.amount_minor)
+        .bind(fee.monthly_volume_minor)
+        .bind(transaction.created_at)
+        .execute(&mut **tx)
+        .await;
+
+        match result {
+            Ok(_) => {}
+            Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => {
+                return Err(TransactionError::UnknownAccount.into());
+            }
+            Err(e) => anyhow::bail!("Failed to insert transaction fee into database: {e}"),
+        }
+    }
+
+    Ok(created)
+}
+
+/// insert_conversion records the rate snapshot of a cross-currency transaction as part of an
+/// open database transaction.
+async fn insert_conversion(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    fx: &Conversion,
+) -> anyhow::Result<Conversion> {
+    let result = sqlx::query_as::<_, Conversion>(&format!(
+        r#"
+        INSERT INTO transaction_fx (transaction_id, debit_fx_account_id, credit_fx_account_id, credit_amount_minor, credit_currency, rate, applied_rate, spread_bps, rounding_mode, rate_source, rate_as_of, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        RETURNING {CONVERSION_COLUMNS}
+        "#
+    ))
+    .bind(fx.transaction_id)
+    .bind(fx.debit_fx_account_id)
+    .bind(fx.credit_fx_account_id)
+    .bind(fx.credit_amount_minor)
+    .bind(fx.credit_currency.as_str())
+    .bind(fx.rate)
+    .bind(fx.applied_rate)
+    .bind(fx.spread_bps)
+    .bind(fx.rounding_mode)
+    .bind(fx.rate_source.as_str())
+    .bind(fx.rate_as_of)
+    .bind(fx.created_at)
+    .fetch_one(&mut **tx)
+    .await;
+
+    match result {
+        Ok(created) => Ok(created),
+        Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => {
+            Err(TransactionError::UnknownAccount.into())
+        }
+        Err(e) => anyhow::bail!("Failed to insert transaction FX into database: {e}"),
+    }
+}
+
+/// get_conversions reads the rate snapshots of the cross-currency transactions among `ids`.
+pub(crate) async fn get_conversions<'e, E>(
+    executor: E,
+    ids: &[uuid::Uuid],
+) -> anyhow::Result<Vec<Conversion>>
+where
+    E: sqlx::PgExecutor<'e>,
+{
+    let result = sqlx::query_as::<_, Conversion>(&format!(
+        "SELECT {CONVERSION_COLUMNS} FROM transaction_fx WHERE transaction_id = ANY($1)"
+    ))
+    .bind(ids)
+    .fetch_all(executor)
+    .await;
+
+    match result {
+        Ok(conversions) => Ok(conversions),
+        Err(e) => anyhow::bail!("Failed to get transaction FX: {e}"),
+    }
+}
+
+impl PgLedgerRepository {
+    /// with_conversions attaches the rate snapshots to the cross-currency transactions.
+    async fn with_conversions(
+        &self,
+        mut transactions: Vec<Transaction>,
+    ) -> anyhow::Result<Vec<Transaction>> {
+        let ids: Vec<uuid::Uuid> = transactions.iter().map(|t| t.id).collect();
+        let mut conversions: std::collections::HashMap<uuid::Uuid, Conversion> =
+            get_conversions(&self.db.reader, &ids)
+                .await?
+                .into_iter()
+                .map(|fx| (fx.transaction_id, fx))
+                .collect();
+        for transaction in transactions.iter_mut() {
+            transaction.fx = conversions.remove(&transaction.id);
+        }
+
+        Ok(transactions)
+    }
+}
+
+#[async_trait]
+impl LedgerWriter for PgLedgerRepository {
+    async fn create_account(&self, id: uuid::Uuid, account_type: &str) -> anyhow::Result<()> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO accounts (id, account_type)
+            VALUES ($1, $2)
+            ON CONFLICT (id) DO NOTHING
+            "#,
+        )
+        .bind(id)
+        .bind(account_type)
+        .execute(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => anyhow::bail!("Failed to insert account into database: {e}"),
+        }
+    }
+
+    async fn create_transaction(
+        &self,
+        transaction: &Transaction,
+        fee: Option<&Fee>,
+    ) -> anyhow::Result<Transaction> {
+        let mut tx = self.db.writer.begin().await?;
+        let created = insert_transaction(&mut tx, transaction, fee).await?;
+        tx.commit().await?;
+
+        Ok(created)
+    }
+
+    async fn update_transaction_status(
+        &self,
+        id: uuid::Uuid,
+        status: Status,
+    ) -> anyhow::Result<()> {
+        let result = sqlx::query(
+            r#"
+            WITH updated AS (
+                UPDATE transactions
+                SET status = $2, updated_at = now()
+                WHERE id = $1
+                RETURNING id, status, updated_at
+            )
+            INSERT INTO transaction_status_history (transaction_id, status, created_at)
+            SELECT id, status, updated_at FROM updated
+            "#,
+        )
+        .bind(id)
+        .bind(status)
+        .execute(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => anyhow::bail!("Failed to update_transaction_status: {e}"),
+        }
+    }
+
+    async fn transition_transaction_status(
+        &self,
+        id: uuid::Uuid,
+        from: &[Status],
+        status: Status,
+    ) -> anyhow::Result<bool> {
+        let from: Vec<&str> = from.iter().map(|status| status.as_ref()).collect();
+        let result = sqlx::query(
+            r#"
+            WITH updated AS (
+                UPDATE transactions
+                SET status = $2, updated_at = now()
+                WHERE id = $1 AND status::TEXT = ANY($3)
+                RETURNING id, status, updated_at
+            )
+            INSERT INTO transaction_status_history (transaction_id, status, created_at)
+            SELECT id, status, updated_at FROM updated
+            "#,
+        )
+        .bind(id)
+        .bind(status)
+        .bind(from)
+        .execute(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(result) => Ok(result.rows_affected() == 1),
+            Err(e) => anyhow::bail!("Failed to transition_transaction_status: {e}"),
+        }
+    }
+}
+
+#[async_trait]
+impl LedgerReader for PgLedgerRepository {
+    async fn get_transaction_by_id(&self, id: uuid::Uuid) -> anyhow::Result<Transaction> {
+        let result = sqlx::query_as::<_, Transaction>(&format!(
+            "SELECT {TRANSACTION_COLUMNS} FROM transactions WHERE id = $1 AND kind = 'transfer'"
+        ))
+        .bind(id)
+        .fetch_one(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(transaction) => Ok(self.with_conversions(vec![transaction]).await?.remove(0)),
+            Err(sqlx::Error::RowNotFound) => Err(TransactionError::NotFound(id).into()),
+            Err(e) => anyhow::bail!("Failed to get_transaction_by_id: {e}"),
+        }
+    }
+
+    async fn get_transaction_by_idempotency_key(
+        &self,
+        idempotency_key: &str,
+    ) -> anyhow::Result<Option<Transaction>> {
+        let result = sqlx::query_as::<_, Transaction>(&format!(
+            "SELECT {TRANSACTION_COLUMNS} FROM transactions WHERE idempotency_key = $1 AND kind = 'transfer'"
+        ))
+        .bind(idempotency_key)
+        .fetch_optional(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(Some(transaction)) => Ok(self.with_conversions(vec![transaction]).await?.pop()),
+            Ok(None) => Ok(None),
+            Err(e) => anyhow::bail!("Failed to get_transaction_by_idempotency_key: {e}"),
+        }
+    }
+
+    async fn get_entries_by_transaction_id(
+        &self,
+        transaction_id: uuid::Uuid,
+    ) -> anyhow::Result<Vec<Entry>> {
+        let result = sqlx::query_as::<_, Entry>(&format!(
+            "SELECT {ENTRY_COLUMNS} FROM ledger_entries WHERE transaction_id = $1 ORDER BY created_at, leg, entry_type"
+        ))
+        .bind(transaction_id)
+        .fetch_all(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(entries) => Ok(entries),
+            Err(e) => anyhow::bail!("Failed to get_entries_by_transaction_id: {e}"),
+        }
+    }
+
+    async fn get_status_history(
+        &self,
+        transaction_id: uuid::Uuid,
+    ) -> anyhow::Result<Vec<StatusChange>> {
+        let result = sqlx::query_as::<_, StatusChange>(
+            r#"
+            SELECT transaction_id, status, created_at
+            FROM transaction_status_history
+            WHERE transaction_id = $1
+            ORDER BY created_at, id
+            "#,
+        )
+        .bind(transaction_id)
+        .fetch_all(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(changes) => Ok(changes),
+            Err(e) => anyhow::bail!("Failed to get_status_history: {e}"),
+        }
+    }
+
+    async fn count_transactions_by_status(
+        &self,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> anyhow::Result<Vec<StatusCount>> {
+        let result = sqlx::query_as::<_, StatusCount>(
+            r#"
+            SELECT status, COUNT(*) AS count
+            FROM transactions
+            WHERE kind = 'transfer' AND ($1::TIMESTAMPTZ IS NULL OR created_at >= $1)
+            GROUP BY status
+            ORDER BY status
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(counts) => Ok(counts),
+            Err(e) => anyhow::bail!("Failed to count_transactions_by_status: {e}"),
+        }
+    }
+
+    async fn get_transactions_created_between(
+        &self,
+        from: chrono::DateTime<chrono::Utc>,
+        to: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<Vec<Transaction>> {
+        let result = sqlx::query_as::<_, Transaction>(&format!(
+            "SELECT {TRANSACTION_COLUMNS} FROM transactions WHERE kind = 'transfer' AND created_at >= $1 AND created_at < $2 ORDER BY created_at"
+        ))
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(transactions) => self.with_conversions(transactions).await,
+            Err(e) => anyhow::bail!("Failed to get_transactions_created_between: {e}"),
+        }
+    }
+
+    async fn get_transactions(&self, filter: &Filter) -> anyhow::Result<Vec<Transaction>> {
+        let result = sqlx::query_as::<_, Transaction>(&format!(
+            r#"
+            SELECT {TRANSACTION_COLUMNS}
+            FROM transactions
+            WHERE kind = 'transfer'
+              AND ($1::UUID IS NULL OR debit_account_id = $1 OR credit_account_id = $1)
+              AND ($2::transaction_status IS NULL OR status = $2)
+            ORDER BY created_at DESC, id
+            LIMIT $3
+            "#
+        ))
+        .bind(filter.account_id)
+        .bind(filter.status.clone())
+        .bind(filter.limit)
+        .fetch_all(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(transactions) => self.with_conversions(transactions).await,
+            Err(e) => anyhow::bail!("Failed to get_transactions: {e}"),
+        }
+    }
+
+    async fn get_transactions_by_references(
+        &self,
+        ids: &[uuid::Uuid],
+        idempotency_keys: &[String],
+    ) -> anyhow::Result<Vec<Transaction>> {
+        let result = sqlx::query_as::<_, Transaction>(&format!(
+            "SELECT {TRANSACTION_COLUMNS} FROM transactions WHERE kind = 'transfer' AND (id = ANY($1) OR idempotency_key = ANY($2)) ORDER BY created_at"
+        ))
+        .bind(ids)
+        .bind(idempotency_keys)
+        .fetch_all(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(transactions) => self.with_conversions(transactions).await,
+            Err(e) => anyhow::bail!("Failed to get_transactions_by_references: {e}"),
+        }
+    }
+
+    async fn get_balances(&self, account_ids: &[uuid::Uuid]) -> anyhow::Result<Vec<Balance>> {
+        let registered =
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM accounts WHERE id = ANY($1)")
+                .bind(account_ids)
+                .fetch_one(&self.db.reader)
+                .await;
+        match registered {
+            Ok(registered)
+                if registered
+                    < account_ids
+                        .iter()
+                        .collect::<std::collections::HashSet<_>>()
+                        .len() as i64 =>
+            {
+                return Err(TransactionError::UnknownAccount.into());
+            }
+            Ok(_) => {}
+            Err(e) => anyhow::bail!("Failed to get_balances: {e}"),
+        }
+
+        let result = sqlx::query_as::<_, Balance>(
+            r#"
+            WITH posted AS (
+                SELECT e.account_id,
+                       e.currency,
+                       SUM(CASE WHEN e.entry_type = 'credit' THEN e.amount_minor ELSE -e.amount_minor END) AS amount_minor
+                FROM ledger_entries e
+                JOIN transactions t ON t.id = e.transaction_id
+                WHERE e.account_id = ANY($1)
+                  AND t.status NOT IN ('failed', 'fraud')
+                GROUP BY e.account_id, e.currency
+            ), held AS (
+                SELECT debit_account_id AS account_id, currency, SUM(amount_minor) AS amount_minor
+                FROM holds
+                WHERE debit_account_id = ANY($1)
+                  AND status = 'authorized'
+                  AND expires_at > now()
+                GROUP BY debit_account_id, currency
+            )
+            SELECT COALESCE(p.account_id, h.account_id) AS account_id,
+                   COALESCE(p.currency, h.currency) AS currency,
+                   COALESCE(p.amount_minor, 0)::BIGINT AS amount_minor,
+                   (COALESCE(p.amount_minor, 0) - COALESCE(h.amount_minor, 0))::BIGINT AS available_minor
+            FROM posted p
+            FULL OUTER JOIN held h ON h.account_id = p.account_id AND h.currency = p.currency
+            ORDER BY 1, 2
+            "#,
+        )
+        .bind(account_ids)
+        .fetch_all(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(balances) => Ok(balances),
+            Err(e) => anyhow::bail!("Failed to get_balances: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::test_util::seed_transaction;
+
+    /// seed_fx_transaction registers the accounts and records a settled EUR transaction
+    /// crediting USD at 1.0850 less a 1% spread.
+    pub(crate) async fn seed_fx_transaction(
+        repo: &PgLedgerRepository,
+        amount_minor: i64,
+    ) -> Transaction {
+        let (debit_account_id, credit_account_id) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        let (eur_fx_account_id, usd_fx_account_id) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        for (account_id, account_type) in [
+            (debit_account_id, "CUSTOMER"),
+            (credit_account_id, "MERCHANT"),
+            (eur_fx_account_id, "SYSTEM"),
+            (usd_fx_account_id, "SYSTEM"),
+        ] {
+            repo.create_account(account_id, account_type).await.unwrap();
+        }
+
+        let mut transaction = Transaction::new(
+            debit_account_id,
+            credit_account_id,
+            amount_minor,
+            "EUR",
+            uuid::Uuid::new_v4().to_string(),
+            chrono::Utc::now(),
+        );
+        let rate = crate::domain::fx::Rate {
+            base: "EUR".to_string(),
+            quote: "USD".to_string(),
+            rate: rust_decimal::Decimal::new(10850, 4),
+            source: "test".to_string(),
+            as_of: chrono::Utc::now(),
+        };
+        transaction.fx = Some(
+            Conversion::new(
+                &transaction,
+                &rate,
+                100,
+                crate::domain::fx::RoundingMode::HalfUp,
+                eur_fx_account_id,
+                usd_fx_account_id,
+            )
+            .unwrap(),
+        );
+        let transaction = repo.create_transaction(&transaction, None).await.unwrap();
+        repo.update_transaction_status(transaction.id, Status::Success)
+            .await
+            .unwrap();
+
+        repo.get_transaction_by_id(transaction.id).await.unwrap()
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_create_fx_transaction_through_fx_accounts(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+
+        // act
+        let transaction = seed_fx_transaction(&repo, 10_000).await;
+
+        // assert
+        let fx = transaction.fx.clone().unwrap();
+        assert_eq!(fx.credit_amount_minor, 10_742);
+        assert_eq!(fx.credit_currency, "USD");
+        assert_eq!(fx.rate, rust_decimal::Decimal::new(10850, 4));
+        assert_eq!(fx.applied_rate, rust_decimal::Decimal::new(107415, 5));
+        assert_eq!(fx.rate_source, "test");
+        assert_eq!(transaction.credit_amount_minor(), 10_742);
+        let entries = repo
+            .get_entries_by_transaction_id(transaction.id)
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 4);
+        let balances: Vec<_> = repo
+            .get_balances(&[
+                transaction.debit_account_id,
+                transaction.credit_account_id,
+                fx.debit_fx_account_id,
+                fx.credit_fx_account_id,
+            ])
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|balance| (balance.account_id, balance.currency, balance.amount_minor))
+            .collect();
+        assert!(balances.contains(&(transaction.debit_account_id, "EUR".to_string(), -10_000)));
+        assert!(balances.contains(&(fx.debit_fx_account_id, "EUR".to_string(), 10_000)));
+        assert!(balances.contains(&(fx.credit_fx_account_id, "USD".to_string(), -10_742)));
+        assert!(balances.contains(&(transaction.credit_account_id, "USD".to_string(), 10_742)));
+        let listed = repo
+            .get_transactions(&Filter {
+                account_id: Some(transaction.credit_account_id),
+                status: None,
+                limit: 10,
+            })
+            .await
+            .unwrap();
+        assert_eq!(listed, vec![transaction]);
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_create_transaction_with_balanced_entries(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+
+        // act
+        let transaction = seed_transaction(&repo, 1000, Status::Success).await;
+
+        // assert
+        assert_eq!(transaction.status, Status::Success);
+        assert_eq!(transaction.amount_minor, 1000);
+        let entries = repo
+            .get_entries_by_transaction_id(transaction.id)
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 2);
+        let debit = entries
+            .iter()
+            .find(|e| e.entry_type == Type::Debit)
+            .unwrap();
+        assert_eq!(debit.account_id, transaction.debit_account_id);
+        let credit = entries
+            .iter()
+            .find(|e| e.entry_type == Type::Credit)
+            .unwrap();
+        assert_eq!(credit.account_id, transaction.credit_account_id);
+        assert_eq!(debit.amount_minor, credit.amount_minor);
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_retrieve_transactions_by_window_and_reference(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let from = chrono::Utc::now();
+        let first = seed_transaction(&repo, 1000, Status::Success).await;
+        let second = seed_transaction(&repo, 2000, Status::Success).await;
+        let to = chrono::Utc::now();
+
+        // act
+        let in_window = repo
+            .get_transactions_created_between(from, to)
+            .await
+            .unwrap();
+        let before_window = repo
+            .get_transactions_created_between(from - chrono::Duration::hours(1), from)
+            .await
+            .unwrap();
+        let by_reference = repo
+            .get_transactions_by_references(
+                &[first.id, uuid::Uuid::new_v4()],
+                &[second.idempotency_key.clone(), "unknown".to_string()],
+            )
+            .await
+            .unwrap();
+
+        // assert
+        assert_eq!(in_window, vec![first.clone(), second.clone()]);
+        assert!(before_window.is_empty());
+        assert_eq!(by_reference, vec![first, second]);
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn test_get_transactions_by_filter(pool: sqlx::PgPool) {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            filter: Filter,
+            expected: Vec<uuid::Uuid>,
+        }
+
+        // arrange
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let first = seed_transaction(&repo, 1000, Status::Success).await;
+        let second = seed_transaction(&repo, 2000, Status::Success).await;
+        let third = seed_transaction(&repo, 3000, Status::Success).await;
+        repo.update_transaction_status(second.id, Status::Failed)
+            .await
+            .unwrap();
+
+        let test_cases = vec![
+            TestCase {
+                name: "newest transactions first",
+                filter: Filter {
+                    account_id: None,
+                    status: None,
+                    limit: 10,
+                },
+                expected: vec![third.id, second.id, first.id],
+            },
+            TestCase {
+                name: "no more than the limit",
+                filter: Filter {
+                    account_id: None,
+                    status: None,
+                    limit: 1,
+                },
+                expected: vec![third.id],
+            },
+            TestCase {
+                name: "transactions crediting the account",
+                filter: Filter {
+                    account_id: Some(first.credit_account_id),
+                    status: None,
+                    limit: 10,
+                },
+                expected: vec![first.id],
+            },
+            TestCase {
+                name: "transactions in the status",
+                filter: Filter {
+                    account_id: None,
+                    status: Some(Status::Success),
+                    limit: 10,
+                },
+                expected: vec![third.id, first.id],
+            },
+            TestCase {
+                name: "nothing when the account has no transactions in the status",
+                filter: Filter {
+                    account_id: Some(second.debit_account_id),
+                    status: Some(Status::Success),
+                    limit: 10,
+                },
+                expected: vec![],
+            },
+        ];
+
+        for test_case in test_cases {
+            // act
+            let transactions = repo.get_transactions(&test_case.filter).await.unwrap();
+
+            // assert
+            let ids: Vec<uuid::Uuid> = transactions.iter().map(|t| t.id).collect();
+            assert_eq!(ids, test_case.expected, "{}", test_case.name);
+        }
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_transition_only_from_expected_status(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let transaction = seed_transaction(&repo, 1000, Status::Success).await;
+        let pending = [Status::Init, Status::Pending];
+
+        // act
+        let from_success = repo
+            .transition_transaction_status(transaction.id, &pending, Status::Failed)
+            .await
+            .unwrap();
+        repo.update_transaction_status(transaction.id, Status::Pending)
+            .await
+            .unwrap();
+        let from_pending = repo
+            .transition_transaction_status(transaction.id, &pending, Status::Failed)
+            .await
+            .unwrap();
+
+        // assert
+        assert!(!from_success);
+        assert!(from_pending);
+        assert_eq!(
+            repo.get_transaction_by_id(transaction.id)
+                .await
+                .unwrap()
+                .status,
+            Status::Failed
+        );
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_get_balances(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let settled = seed_transaction(&repo, 1000, Status::Success).await;
+        let failed = repo
+            .create_transaction(
+                &Transaction::new(
+                    settled.debit_account_id,
+                    settled.credit_account_id,
+                    500,
+                    "USD",
+                    uuid::Uuid::new_v4().to_string(),
+                    chrono::Utc::now(),
+                ),
+                None,
+            )
+            .await
+            .unwrap();
+        repo.update_transaction_status(failed.id, Status::Failed)
+            .await
+            .unwrap();
+        repo.create_transaction(
+            &Transaction::new(
+                settled.debit_account_id,
+                settled.credit_account_id,
+                250,
+                "EUR",
+                uuid::Uuid::new_v4().to_string(),
+                chrono::Utc::now(),
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+        let idle = uuid::Uuid::new_v4();
+        repo.create_account(idle, "SYSTEM").await.unwrap();
+
+        // act
+        let balances = repo
+            .get_balances(&[settled.debit_account_id, settled.credit_account_id, idle])
+            .await
+            .unwrap();
+
+        // assert
+        let balance = |account_id: uuid::Uuid, currency: &str| {
+            balances
+                .iter()
+                .find(|b| b.account_id == account_id && b.currency == currency)
+                .map(|b| b.amount_minor)
+        };
+        assert_eq!(balances.len(), 4);
+        assert_eq!(balance(settled.debit_account_id, "USD"), Some(-1000));
+        assert_eq!(balance(settled.credit_account_id, "USD"), Some(1000));
+        assert_eq!(balance(settled.debit_account_id, "EUR"), Some(-250));
+        assert_eq!(balance(settled.credit_account_id, "EUR"), Some(250));
+        assert_eq!(balance(idle, "USD"), None);
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn error_when_getting_balances_of_unregistered_account(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let settled = seed_transaction(&repo, 1000, Status::Success).await;
+
+        // act
+        let result = repo
+            .get_balances(&[
+                settled.debit_account_id,
+                settled.debit_account_id,
+                uuid::Uuid::new_v4(),
+            ])
+            .await;
+
+        // assert
+        assert_eq!(
+            result.unwrap_err().downcast_ref::<TransactionError>(),
+            Some(&TransactionError::UnknownAccount)
+        );
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_record_status_history(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let settled = seed_transaction(&repo, 1000, Status::Success).await;
+
+        // act
+        let moved = repo
+            .transition_transaction_status(settled.id, &[Status::Success], Status::Refund)
+            .await
+            .unwrap();
+        let skipped = repo
+            .transition_transaction_status(settled.id, &[Status::Success], Status::Fraud)
+            .await
+            .unwrap();
+        let history = repo.get_status_history(settled.id).await.unwrap();
+
+        // assert
+        assert!(moved);
+        assert!(!skipped);
+        assert_eq!(
+            history
+                .iter()
+                .map(|change| change.status.clone())
+                .collect::<Vec<_>>(),
+            vec![Status::Init, Status::Success, Status::Refund]
+        );
+        assert!(
+            history
+                .iter()
+                .all(|change| change.transaction_id == settled.id)
+        );
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_count_transactions_by_status(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let failed = seed_transaction(&repo, 1000, Status::Success).await;
+        repo.update_transaction_status(failed.id, Status::Failed)
+            .await
+            .unwrap();
+        let since = chrono::Utc::now();
+        seed_transaction(&repo, 2000, Status::Success).await;
+        seed_transaction(&repo, 3000, Status::Success).await;
+
+        // act
+        let all = repo.count_transactions_by_status(None).await.unwrap();
+        let recent = repo
+            .count_transactions_by_status(Some(since))
+            .await
+            .unwrap();
+
+        // assert
+        assert_eq!(
+            all,
+            vec![
+                StatusCount {
+                    status: Status::Success,
+                    count: 2
+                },
+                StatusCount {
+                    status: Status::Failed,
+                    count: 1
+                },
+            ]
+        );
+        assert_eq!(
+            recent,
+            vec![StatusCount {
+                status: Status::Success,
+                count: 2
+            }]
+        );
+    }
+}
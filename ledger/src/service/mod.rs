@@ -1,17 +1,1626 @@
-use crate::repo::{LedgerRepository, PgLedgerRepository};
+use crate::domain::balance::Balance;
+use crate::domain::fee::{Fee, FeeSchedule};
+use crate::domain::fx::{Conversion, FxError, RoundingMode};
+use crate::domain::hold::{self, Hold, HoldError};
+use crate::domain::journal::{JournalEntry, JournalError};
+use crate::domain::money::from_minor_units;
+use crate::domain::refund::{Refund, RefundError, Status};
+use crate::domain::schedule::{self, Run, RunStatus, Schedule, ScheduleError};
+use crate::domain::transaction::{self, Transaction, TransactionError};
+use crate::fx::FxRateProvider;
+use crate::repo::LedgerRepository;
+use crate::{
+    DEFAULT_LIST_TRANSACTIONS_LIMIT, MAX_LIST_TRANSACTIONS_LIMIT, SCHEDULE_RUN_BATCH_SIZE,
+    SCHEDULE_RUN_LEASE_SECONDS,
+};
+use common::kafka::{Publisher, REFUND_EVENTS_TOPIC, TRANSACTION_EVENTS_TOPIC};
+use events_proto::events_v1;
+use prost::Message;
+use prost_types::Timestamp;
+use std::collections::HashMap;
+use std::sync::Arc;
 
-pub struct LedgerService<R>
+pub struct LedgerService<R, P>
 where
     R: LedgerRepository,
+    P: Publisher,
 {
     repo: R,
+    publisher: P,
+    refund_approval_threshold_minor: i64,
+    fee_revenue_account_id: Option<uuid::Uuid>,
+    fx_rates: Option<Arc<dyn FxRateProvider>>,
+    fx_accounts: HashMap<String, uuid::Uuid>,
+    fx_spread_bps: i32,
+    fx_rounding_mode: RoundingMode,
 }
 
-impl<R> LedgerService<R>
+impl<R, P> LedgerService<R, P>
 where
     R: LedgerRepository,
+    P: Publisher,
 {
-    pub fn new(repo: R) -> Self {
-        Self { repo }
+    pub fn new(repo: R, publisher: P) -> Self {
+        Self {
+            repo,
+            publisher,
+            refund_approval_threshold_minor: crate::DEFAULT_REFUND_APPROVAL_THRESHOLD_MINOR,
+            fee_revenue_account_id: None,
+            fx_rates: None,
+            fx_accounts: HashMap::new(),
+            fx_spread_bps: 0,
+            fx_rounding_mode: RoundingMode::HalfUp,
+        }
+    }
+
+    /// with_refund_approval_threshold sets the amount, in minor units, above which a manual
+    /// refund needs a second approver.
+    pub fn with_refund_approval_threshold(mut self, threshold_minor: i64) -> Self {
+        self.refund_approval_threshold_minor = threshold_minor;
+        self
+    }
+
+    /// with_fee_revenue_account sets the system account merchant fees are credited to. Without
+    /// it no fees are charged.
+    pub fn with_fee_revenue_account(mut self, account_id: uuid::Uuid) -> Self {
+        self.fee_revenue_account_id = Some(account_id);
+        self
+    }
+
+    /// with_fx_rates sets the provider cross-currency transfers are quoted by. Without it only
+    /// transfers in a single currency are accepted.
+    pub fn with_fx_rates(mut self, fx_rates: Arc<dyn FxRateProvider>) -> Self {
+        self.fx_rates = Some(fx_rates);
+        self
+    }
+
+    /// with_fx_account sets the system account cross-currency transfers go through in the
+    /// currency.
+    pub fn with_fx_account(mut self, currency: &str, account_id: uuid::Uuid) -> Self {
+        self.fx_accounts.insert(currency.to_uppercase(), account_id);
+        self
+    }
+
+    /// with_fx_spread sets the basis points taken off the quoted rate.
+    pub fn with_fx_spread(mut self, spread_bps: i32) -> Self {
+        self.fx_spread_bps = spread_bps;
+        self
+    }
+
+    /// with_fx_rounding_mode sets how converted amounts are rounded to minor units.
+    pub fn with_fx_rounding_mode(mut self, rounding_mode: RoundingMode) -> Self {
+        self.fx_rounding_mode = rounding_mode;
+        self
+    }
+
+    /// create_transaction records the transaction with its entries and publishes it to
+    /// `transaction_events`. A retry with the same idempotency key returns the transaction
+    /// recorded first and publishes it again while it is still init, a different transfer under
+    /// the same key is rejected. The fee of the credited merchant is posted under the same transaction.
+    pub async fn create_transaction(
+        &self,
+        transaction: &Transaction,
+    ) -> anyhow::Result<Transaction> {
+        self.create_transfer(transaction, transaction.currency.as_str())
+            .await
+    }
+
+    /// create_fx_transaction is create_transaction crediting the account in `credit_currency`.
+    /// The amount is converted at the current rate less the spread, and the rate snapshot is
+    /// kept with the transaction.
+    pub async fn create_fx_transaction(
+        &self,
+        transaction: &Transaction,
+        credit_currency: &str,
+    ) -> anyhow::Result<Transaction> {
+        self.create_transfer(transaction, credit_currency).await
+    }
+
+    async fn create_transfer(
+        &self,
+        transaction: &Transaction,
+        credit_currency: &str,
+    ) -> anyhow::Result<Transaction> {
+        if transaction.amount_minor <= 0 {
+            return Err(TransactionError::InvalidAmount(transaction.amount_minor).into());
+        }
+        if transaction.debit_account_id == transaction.credit_account_id {
+            return Err(TransactionError::SameAccount(transaction.debit_account_id).into());
+        }
+
+        if let Some(existing) = self.existing_transfer(transaction, credit_currency).await? {
+            return Ok(existing);
+        }
+
+        let mut request = transaction.clone();
+        if !request.currency.eq_ignore_ascii_case(credit_currency) {
+            request.fx = Some(self.conversion(&request, credit_currency).await?);
+        }
+        let fee = self.fee(&request).await?;
+        let created = match self.repo.create_transaction(&request, fee.as_ref()).await {
+            Ok(created) => created,
+            // a concurrent request with the same key was recorded first
+            Err(e)
+                if matches!(
+                    e.downcast_ref::<TransactionError>(),
+                    Some(TransactionError::IdempotencyKeyReused(_))
+                ) =>
+            {
+                return match self.existing_transfer(transaction, credit_currency).await? {
+                    Some(existing) => Ok(existing),
+                    None => Err(e),
+                };
+            }
+            Err(e) => return Err(e),
+        };
+        self.publish_transaction(&created).await?;
+
+        Ok(created)
+    }
+
+    /// existing_transfer returns the transaction recorded under the idempotency key of
+    /// `transaction`, republished while it is init, and fails when it was another transfer.
+    async fn existing_transfer(
+        &self,
+        transaction: &Transaction,
+        credit_currency: &str,
+    ) -> anyhow::Result<Option<Transaction>> {
+        let Some(existing) = self
+            .repo
+            .get_transaction_by_idempotency_key(transaction.idempotency_key.as_str())
+            .await?
+        else {
+            return Ok(None);
+        };
+        if !existing.same_request(transaction)
+            || !existing
+                .credit_currency()
+                .eq_ignore_ascii_case(credit_currency)
+        {
+            return Err(TransactionError::IdempotencyKeyReused(
+                transaction.idempotency_key.clone(),
+            )
+            .into());
+        }
+        self.republish_transaction(&existing).await?;
+
+        Ok(Some(existing))
+    }
+
+    /// conversion quotes the rate from the transaction currency to `credit_currency` and
+    /// converts the amount through the FX accounts of both currencies.
+    async fn conversion(
+        &self,
+        transaction: &Transaction,
+        credit_currency: &str,
+    ) -> anyhow::Result<Conversion> {
+        let Some(fx_rates) = &self.fx_rates else {
+            return Err(FxError::NotConfigured.into());
+        };
+        let fx_account = |currency: &str| match self.fx_accounts.get(&currency.to_uppercase()) {
+            Some(account_id) => Ok(*account_id),
+            None => Err(FxError::NoFxAccount(currency.to_uppercase())),
+        };
+        let debit_fx_account_id = fx_account(transaction.currency.as_str())?;
+        let credit_fx_account_id = fx_account(credit_currency)?;
+
+        let rate = fx_rates
+            .get_rate(transaction.currency.as_str(), credit_currency)
+            .await?;
+        Ok(Conversion::new(
+            transaction,
+            &rate,
+            self.fx_spread_bps,
+            self.fx_rounding_mode,
+            debit_fx_account_id,
+            credit_fx_account_id,
+        )?)
+    }
+
+    /// fee prices the transaction with the fee schedule of the credited account in effect when
+    /// it was created, picking the tier by the account's volume earlier in the month. The fee is
+    /// charged in the currency the account is credited in.
+    async fn fee(&self, transaction: &Transaction) -> anyhow::Result<Option<Fee>> {
+        let Some(revenue_account_id) = self.fee_revenue_account_id else {
+            return Ok(None);
+        };
+        let Some(fee_schedule) = self
+            .repo
+            .get_effective_fee_schedule(transaction.credit_account_id, transaction.created_at)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let monthly_volume_minor = self
+            .repo
+            .get_monthly_volume(
+                transaction.credit_account_id,
+                transaction.credit_currency(),
+                transaction.created_at,
+            )
+            .await?;
+        let Some(tier) = fee_schedule.tier(transaction.credit_currency(), monthly_volume_minor)
+        else {
+            return Ok(None);
+        };
+
+        match tier.fee(transaction.credit_amount_minor()) {
+            0 => Ok(None),
+            amount_minor => Ok(Some(Fee {
+                fee_schedule_id: fee_schedule.id,
+                revenue_account_id,
+                amount_minor,
+                monthly_volume_minor,
+                description: format!(
+                    "fee, schedule v{} tier from {} minor units",
+                    fee_schedule.version, tier.min_monthly_volume_minor
+                ),
+            })),
+        }
+    }
+
+    /// set_fee_schedule stores the schedule as the next version of the merchant's fees.
+    pub async fn set_fee_schedule(
+        &self,
+        fee_schedule: &FeeSchedule,
+    ) -> anyhow::Result<FeeSchedule> {
+        fee_schedule.validate()?;
+
+        self.repo.create_fee_schedule(fee_schedule).await
+    }
+
+    /// get_fee_schedules returns every version of the merchant's fees, newest first.
+    pub async fn get_fee_schedules(
+        &self,
+        merchant_account_id: uuid::Uuid,
+    ) -> anyhow::Result<Vec<FeeSchedule>> {
+        self.repo.get_fee_schedules(merchant_account_id).await
+    }
+
+    /// post_journal_entry validates the legs and posts them under one transaction. A retry with
+    /// the same idempotency key returns the journal entry posted first, different legs under the
+    /// same key are rejected.
+    pub async fn post_journal_entry(
+        &self,
+        journal_entry: &JournalEntry,
+    ) -> anyhow::Result<JournalEntry> {
+        journal_entry.validate()?;
+
+        if let Some(existing) = self
+            .repo
+            .get_journal_entry_by_idempotency_key(journal_entry.idempotency_key.as_str())
+            .await?
+        {
+            return match existing.same_request(journal_entry) {
+                true => Ok(existing),
+                false => Err(JournalError::IdempotencyKeyReused(
+                    journal_entry.idempotency_key.clone(),
+                )
+                .into()),
+            };
+        }
+
+        self.repo.post_journal_entry(journal_entry).await
+    }
+
+    pub async fn get_journal_entry(&self, id: uuid::Uuid) -> anyhow::Result<JournalEntry> {
+        self.repo.get_journal_entry_by_id(id).await
+    }
+
+    pub async fn get_transaction(&self, id: uuid::Uuid) -> anyhow::Result<Transaction> {
+        self.repo.get_transaction_by_id(id).await
+    }
+
+    /// get_transaction_details returns the transaction with its entries and status history.
+    pub async fn get_transaction_details(
+        &self,
+        id: uuid::Uuid,
+    ) -> anyhow::Result<transaction::Details> {
+        let transaction = self.repo.get_transaction_by_id(id).await?;
+        let entries = self.repo.get_entries_by_transaction_id(id).await?;
+        let status_history = self.repo.get_status_history(id).await?;
+
+        Ok(transaction::Details {
+            transaction,
+            entries,
+            status_history,
+        })
+    }
+
+    /// count_transactions counts transactions per current status, only those created at or
+    /// after `since` when it is set.
+    pub async fn count_transactions(
+        &self,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> anyhow::Result<Vec<transaction::StatusCount>> {
+        self.repo.count_transactions_by_status(since).await
+    }
+
+    /// list_transactions returns the newest transactions matching the filter. A limit of 0
+    /// uses the default and larger limits are capped.
+    pub async fn list_transactions(
+        &self,
+        account_id: Option<uuid::Uuid>,
+        status: Option<transaction::Status>,
+        limit: u32,
+    ) -> anyhow::Result<Vec<Transaction>> {
+        let limit = match limit {
+            0 => DEFAULT_LIST_TRANSACTIONS_LIMIT,
+            limit => (limit as i64).min(MAX_LIST_TRANSACTIONS_LIMIT),
+        };
+
+        self.repo
+            .get_transactions(&transaction::Filter {
+                account_id,
+                status,
+                limit,
+            })
+            .await
+    }
+
+    /// get_balances returns the posted balances of the accounts per currency.
+    pub async fn get_balances(&self, account_ids: &[uuid::Uuid]) -> anyhow::Result<Vec<Balance>> {
+        self.repo.get_balances(account_ids).await
+    }
+
+    /// authorize_hold reserves the amount on the debited account. A retry with the same
+    /// idempotency key returns the hold authorized first, a different hold under the same key
+    /// is rejected.
+    pub async fn authorize_hold(&self, hold: &Hold) -> anyhow::Result<Hold> {
+        hold.validate()?;
+
+        if let Some(existing) = self
+            .repo
+            .get_hold_by_idempotency_key(hold.idempotency_key.as_str())
+            .await?
+        {
+            return match existing.same_request(hold) {
+                true => Ok(existing),
+                false => Err(HoldError::IdempotencyKeyReused(hold.idempotency_key.clone()).into()),
+            };
+        }
+
+        self.repo.create_hold(hold).await
+    }
+
+    /// capture_hold transfers `amount`, or the held amount when unset, and publishes the
+    /// transaction like create_transaction, fee included. Capturing a captured hold again for
+    /// the same amount returns it with its transaction.
+    pub async fn capture_hold(
+        &self,
+        id: uuid::Uuid,
+        amount: Option<(i64, String)>,
+    ) -> anyhow::Result<(Hold, Transaction)> {
+        let hold = self.repo.get_hold_by_id(id).await?;
+        let amount_minor = match amount {
+            Some((_, currency)) if currency != hold.currency => {
+                return Err(HoldError::CurrencyMismatch {
+                    expected: hold.currency,
+                    got: currency,
+                }
+                .into());
+            }
+            Some((amount_minor, _)) if amount_minor <= 0 => {
+                return Err(HoldError::InvalidAmount(amount_minor).into());
+            }
+            Some((amount_minor, _)) => amount_minor,
+            None => hold.amount_minor,
+        };
+
+        if let (hold::Status::Captured, Some(transaction_id)) = (&hold.status, hold.transaction_id)
+            && hold.captured_amount_minor == Some(amount_minor)
+        {
+            let transaction = self.repo.get_transaction_by_id(transaction_id).await?;
+            self.republish_transaction(&transaction).await?;
+            return Ok((hold, transaction));
+        }
+
+        let transaction = hold.capture_transaction(amount_minor);
+        let fee = self.fee(&transaction).await?;
+        let (captured, created) = self
+            .repo
+            .capture_hold(id, &transaction, fee.as_ref())
+            .await?;
+        self.publish_transaction(&created).await?;
+
+        Ok((captured, created))
+    }
+
+    pub async fn void_hold(&self, id: uuid::Uuid) -> anyhow::Result<Hold> {
+        self.repo.void_hold(id).await
+    }
+
+    pub async fn get_hold(&self, id: uuid::Uuid) -> anyhow::Result<Hold> {
+        self.repo.get_hold_by_id(id).await
+    }
+
+    /// expire_holds releases the authorized holds past their expiry.
+    pub async fn expire_holds(&self) -> anyhow::Result<u64> {
+        self.repo.expire_holds(chrono::Utc::now()).await
+    }
+
+    /// spawn_hold_sweeper expires holds on every tick of the interval.
+    pub fn spawn_hold_sweeper(
+        self: Arc<Self>,
+        interval: std::time::Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                match self.expire_holds().await {
+                    Ok(0) => {}
+                    Ok(expired) => tracing::info!(expired, "expired holds"),
+                    Err(e) => tracing::error!("failed to expire holds: {e}"),
+                }
+            }
+        })
+    }
+
+    /// create_schedule stores the schedule with its first occurrence. A retry with the same
+    /// idempotency key returns the schedule created first, a different schedule under the same
+    /// key is rejected.
+    pub async fn create_schedule(&self, schedule: &Schedule) -> anyhow::Result<Schedule> {
+        schedule.validate()?;
+
+        if let Some(existing) = self
+            .repo
+            .get_schedule_by_idempotency_key(schedule.idempotency_key.as_str())
+            .await?
+        {
+            return match existing.same_request(schedule) {
+                true => Ok(existing),
+                false => Err(
+                    ScheduleError::IdempotencyKeyReused(schedule.idempotency_key.clone()).into(),
+                ),
+            };
+        }
+
+        let mut schedule = schedule.clone();
+        schedule.next_run_at = match schedule.next_occurrence(None)? {
+            Some(next_run_at) => Some(next_run_at),
+            None => return Err(ScheduleError::NoOccurrence.into()),
+        };
+
+        self.repo.create_schedule(&schedule).await
+    }
+
+    pub async fn get_schedule(&self, id: uuid::Uuid) -> anyhow::Result<Schedule> {
+        self.repo.get_schedule_by_id(id).await
+    }
+
+    /// list_schedules returns the schedules of the account, every schedule when unset.
+    pub async fn list_schedules(
+        &self,
+        account_id: Option<uuid::Uuid>,
+    ) -> anyhow::Result<Vec<Schedule>> {
+        self.repo.get_schedules(account_id).await
+    }
+
+    /// get_schedule_runs returns the run history of the schedule, latest occurrence first.
+    pub async fn get_schedule_runs(&self, id: uuid::Uuid) -> anyhow::Result<Vec<Run>> {
+        self.repo.get_schedule_by_id(id).await?;
+
+        self.repo.get_schedule_runs(id).await
+    }
+
+    pub async fn pause_schedule(&self, id: uuid::Uuid) -> anyhow::Result<Schedule> {
+        self.repo.pause_schedule(id).await
+    }
+
+    /// resume_schedule reactivates a paused schedule, occurrences missed while it was paused
+    /// are not run.
+    pub async fn resume_schedule(&self, id: uuid::Uuid) -> anyhow::Result<Schedule> {
+        self.repo.resume_schedule(id, chrono::Utc::now()).await
+    }
+
+    pub async fn cancel_schedule(&self, id: uuid::Uuid) -> anyhow::Result<Schedule> {
+        self.repo.cancel_schedule(id).await
+    }
+
+    /// skip_schedule_run skips the next occurrence of the schedule.
+    pub async fn skip_schedule_run(&self, id: uuid::Uuid) -> anyhow::Result<(Schedule, Run)> {
+        self.repo.skip_schedule_run(id).await
+    }
+
+    /// run_due_schedules runs the occurrences due at `now`, one per schedule, and returns how
+    /// many runs it completed. Runs left pending by an earlier attempt are retried first once
+    /// their lease expired, their transaction is created with the same idempotency key so an
+    /// occurrence is never charged twice. A schedule which fails is logged and left for the
+    /// next tick.
+    pub async fn run_due_schedules(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<u64> {
+        let claimed_until = now + chrono::Duration::seconds(SCHEDULE_RUN_LEASE_SECONDS);
+        let mut completed = 0;
+
+        for run in self
+            .repo
+            .claim_pending_runs(now, claimed_until, SCHEDULE_RUN_BATCH_SIZE)
+            .await?
+        {
+            match self.retry_run(&run).await {
+                Ok(Some(_)) => completed += 1,
+                Ok(None) => {}
+                Err(e) => tracing::error!(run_id = %run.id, "failed to retry schedule run: {e}"),
+            }
+        }
+
+        for schedule in self
+            .repo
+            .get_due_schedules(now, SCHEDULE_RUN_BATCH_SIZE)
+            .await?
+        {
+            match self.run_schedule(&schedule, claimed_until).await {
+                Ok(Some(_)) => completed += 1,
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::error!(schedule_id = %schedule.id, "failed to run schedule: {e}")
+                }
+            }
+        }
+
+        Ok(completed)
+    }
+
+    /// retry_run executes a run left pending by an earlier attempt. The run of a schedule
+    /// paused or cancelled since is skipped, unless its transaction was created before.
+    async fn retry_run(&self, run: &Run) -> anyhow::Result<Option<Run>> {
+        let schedule = self.repo.get_schedule_by_id(run.schedule_id).await?;
+        if let schedule::Status::Active | schedule::Status::Completed = schedule.status {
+            return self.execute_run(&schedule, run).await;
+        }
+
+        let transaction = schedule.run_transaction(run.occurrence_at);
+        match self
+            .repo
+            .get_transaction_by_idempotency_key(transaction.idempotency_key.as_str())
+            .await?
+        {
+            Some(existing) => {
+                self.repo
+                    .complete_run(run.id, RunStatus::Succeeded, Some(existing.id), None)
+                    .await
+            }
+            None => {
+                let error = format!("schedule is {}", schedule.status.as_ref());
+                self.repo
+                    .complete_run(run.id, RunStatus::Skipped, None, Some(error.as_str()))
+                    .await
+            }
+        }
+    }
+
+    /// run_schedule claims the due occurrence of the schedule until `claimed_until` and
+    /// executes it, None when another worker claimed it first.
+    async fn run_schedule(
+        &self,
+        schedule: &Schedule,
+        claimed_until: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<Option<Run>> {
+        let Some(occurrence_at) = schedule.next_run_at else {
+            return Ok(None);
+        };
+        let next_run_at = schedule.next_occurrence(Some(occurrence_at))?;
+        let Some(run) = self
+            .repo
+            .claim_run(schedule.id, occurrence_at, next_run_at, claimed_until)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        self.execute_run(schedule, &run).await
+    }
+
+    /// execute_run creates the transaction of a pending run. A transfer the ledger rejects
+    /// fails the run, other errors leave it pending for the next tick. It returns None when the
+    /// run was completed by another worker meanwhile.
+    async fn execute_run(&self, schedule: &Schedule, run: &Run) -> anyhow::Result<Option<Run>> {
+        match self
+            .create_transaction(&schedule.run_transaction(run.occurrence_at))
+            .await
+        {
+            Ok(transaction) => {
+                self.repo
+                    .complete_run(run.id, RunStatus::Succeeded, Some(transaction.id), None)
+                    .await
+            }
+            Err(e)
+                if e.downcast_ref::<TransactionError>().is_some()
+                    || e.downcast_ref::<FxError>().is_some() =>
+            {
+                self.repo
+                    .complete_run(run.id, RunStatus::Failed, None, Some(&e.to_string()))
+                    .await
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// spawn_scheduler runs the due schedules on every tick of the interval.
+    pub fn spawn_scheduler(
+        self: Arc<Self>,
+        interval: std::time::Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                match self.run_due_schedules(chrono::Utc::now()).await {
+                    Ok(0) => {}
+                    Ok(completed) => tracing::info!(completed, "ran scheduled transfers"),
+                    Err(e) => tracing::error!("failed to run scheduled transfers: {e}"),
+                }
+            }
+        })
+    }
+
+    /// create_refund records a manual refund. Refunds up to the approval threshold are approved
+    /// straight away and published to `refund_events`, larger ones wait for `approve_refund`.
+    pub async fn create_refund(
+        &self,
+        transaction_id: uuid::Uuid,
+        amount_minor: i64,
+        currency: &str,
+        reason: &str,
+        requested_by: &str,
+    ) -> anyhow::Result<Refund> {
+        let mut refund = Refund::new(
+            uuid::Uuid::new_v4(),
+            transaction_id,
+            amount_minor,
+            currency,
+            reason,
+        );
+        refund.requested_by = Some(requested_by.to_string());
+        refund.status = match amount_minor > self.refund_approval_threshold_minor {
+            true => Status::AwaitingApproval,
+            false => Status::Approved,
+        };
+
+        let refund = self.repo.request_refund(&refund).await?;
+        if refund.status == Status::Approved {
+            self.publish_refund(&refund).await?;
+        }
+
+        Ok(refund)
+    }
+
+    /// approve_refund is the checker's side of maker-checker. Approving a refund which is
+    /// already approved by the same approver publishes it again, so a failed publish can be
+    /// retried; the refund processor deduplicates on the refund id.
+    pub async fn approve_refund(&self, id: uuid::Uuid, approver: &str) -> anyhow::Result<Refund> {
+        let refund = self.repo.get_refund_by_id(id).await?;
+        if refund.requested_by.as_deref() == Some(approver) {
+            return Err(RefundError::SelfApproval(id).into());
+        }
+
+        let refund = match refund.status {
+            Status::Approved if refund.approved_by.as_deref() == Some(approver) => refund,
+            _ => self.repo.decide_refund(id, approver, true).await?,
+        };
+        self.publish_refund(&refund).await?;
+
+        Ok(refund)
+    }
+
+    pub async fn reject_refund(&self, id: uuid::Uuid, approver: &str) -> anyhow::Result<Refund> {
+        self.repo.decide_refund(id, approver, false).await
+    }
+
+    pub async fn get_refunds_by_transaction_id(
+        &self,
+        transaction_id: uuid::Uuid,
+    ) -> anyhow::Result<Vec<Refund>> {
+        self.repo
+            .get_refunds_by_transaction_id(transaction_id)
+            .await
+    }
+
+    /// republish_transaction publishes a transaction found on retry again while no consumer has
+    /// moved it past init, as the publish after it was recorded may have failed.
+    async fn republish_transaction(&self, transaction: &Transaction) -> anyhow::Result<()> {
+        if transaction.status != transaction::Status::Init {
+            return Ok(());
+        }
+        self.publish_transaction(transaction).await
+    }
+
+    async fn publish_transaction(&self, transaction: &Transaction) -> anyhow::Result<()> {
+        let (units, nanos) = from_minor_units(transaction.amount_minor, &transaction.currency)?;
+        let now = chrono::Utc::now();
+        let event = events_v1::Transaction {
+            id: transaction.id.to_string(),
+            idempotency_key: transaction.idempotency_key.clone(),
+            debit_account_id: transaction.debit_account_id.to_string(),
+            credit_account_id: transaction.credit_account_id.to_string(),
+            amount: Some(events_v1::google::r#type::Money {
+                currency_code: transaction.currency.clone(),
+                units,
+                nanos,
+            }),
+            status: match transaction.status {
+                transaction::Status::Init => events_v1::TransactionStatus::Init,
+                transaction::Status::Pending => events_v1::TransactionStatus::Pending,
+                transaction::Status::Success => events_v1::TransactionStatus::Success,
+                transaction::Status::Failed => events_v1::TransactionStatus::Failed,
+                transaction::Status::Fraud => events_v1::TransactionStatus::Fraud,
+                transaction::Status::Refund => events_v1::TransactionStatus::Refund,
+                transaction::Status::Refunded => events_v1::TransactionStatus::Refunded,
+            } as i32,
+            request_timestamp: Some(Timestamp {
+                seconds: transaction.request_timestamp.timestamp(),
+                nanos: transaction.request_timestamp.timestamp_subsec_nanos() as i32,
+            }),
+            created_at: Some(Timestamp {
+                seconds: now.timestamp(),
+                nanos: now.timestamp_subsec_nanos() as i32,
+            }),
+        };
+
+        match self
+            .publisher
+            .publish(
+                TRANSACTION_EVENTS_TOPIC,
+                event.id.as_str(),
+                event.encode_to_vec(),
+            )
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(e) => anyhow::bail!("failed to publish transaction event: {e}"),
+        }
+    }
+
+    /// publish_refund emits the refund event. Money flows back, so the original credit
+    /// account is debited and the original debit account credited.
+    async fn publish_refund(&self, refund: &Refund) -> anyhow::Result<()> {
+        let transaction = self
+            .repo
+            .get_transaction_by_id(refund.transaction_id)
+            .await?;
+
+        let (units, nanos) = from_minor_units(refund.amount_minor, &refund.currency)?;
+        let reason = events_v1::RefundReason::from_str_name(
+            format!("REFUND_REASON_{}", refund.reason.to_uppercase()).as_str(),
+        )
+        .unwrap_or(events_v1::RefundReason::Unspecified);
+        let now = chrono::Utc::now();
+        let event = events_v1::Refund {
+            id: refund.id.to_string(),
+            transaction_id: refund.transaction_id.to_string(),
+            idempotency_key: transaction.idempotency_key,
+            debit_account_id: transaction.credit_account_id.to_string(),
+            credit_account_id: transaction.debit_account_id.to_string(),
+            amount: Some(events_v1::google::r#type::Money {
+                currency_code: refund.currency.clone(),
+                units,
+                nanos,
+            }),
+            created_at: Some(Timestamp {
+                seconds: now.timestamp(),
+                nanos: now.timestamp_subsec_nanos() as i32,
+            }),
+            reason: reason as i32,
+        };
+
+        match self
+            .publisher
+            .publish(
+                REFUND_EVENTS_TOPIC,
+                event.transaction_id.as_str(),
+                event.encode_to_vec(),
+            )
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(e) => anyhow::bail!("failed to publish refund event: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::repo::{LedgerReader, LedgerWriter, PgLedgerRepository, ScheduleWriter};
+    use crate::test_util::seed_transaction;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    pub(crate) struct InMemoryPublisher {
+        pub messages: Mutex<Vec<(String, String, Vec<u8>)>>,
+    }
+
+    #[async_trait]
+    impl Publisher for InMemoryPublisher {
+        async fn publish(&self, topic: &str, key: &str, payload: Vec<u8>) -> anyhow::Result<()> {
+            self.messages
+                .lock()
+                .unwrap()
+                .push((topic.to_string(), key.to_string(), payload));
+            Ok(())
+        }
+    }
+
+    async fn service(
+        pool: sqlx::PgPool,
+    ) -> (
+        LedgerService<PgLedgerRepository, InMemoryPublisher>,
+        PgLedgerRepository,
+    ) {
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let service = LedgerService::new(repo.clone(), InMemoryPublisher::default())
+            .with_refund_approval_threshold(500);
+
+        (service, repo)
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_publish_refund_below_approval_threshold(pool: sqlx::PgPool) {
+        // arrange
+        let (service, repo) = service(pool).await;
+        let transaction = seed_transaction(&repo, 1000, transaction::Status::Success).await;
+
+        // act
+        let refund = service
+            .create_refund(transaction.id, 250, "USD", "duplicate", "alice")
+            .await
+            .unwrap();
+
+        // assert
+        assert_eq!(refund.status, Status::Approved);
+        let messages = service.publisher.messages.lock().unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].0, REFUND_EVENTS_TOPIC);
+        let event = events_v1::Refund::decode(messages[0].2.as_slice()).unwrap();
+        assert_eq!(event.id, refund.id.to_string());
+        assert_eq!(event.reason, events_v1::RefundReason::Duplicate as i32);
+        assert_eq!(
+            event.debit_account_id,
+            transaction.credit_account_id.to_string()
+        );
+        assert_eq!(event.amount.unwrap().units, 2);
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_require_second_approver_above_threshold(pool: sqlx::PgPool) {
+        // arrange
+        let (service, repo) = service(pool).await;
+        let transaction = seed_transaction(&repo, 1000, transaction::Status::Success).await;
+        let refund = service
+            .create_refund(transaction.id, 800, "USD", "requested_by_customer", "alice")
+            .await
+            .unwrap();
+        assert_eq!(refund.status, Status::AwaitingApproval);
+        assert!(service.publisher.messages.lock().unwrap().is_empty());
+
+        // act
+        let self_approval = service.approve_refund(refund.id, "alice").await;
+        let approved = service.approve_refund(refund.id, "bob").await.unwrap();
+
+        // assert
+        assert_eq!(
+            self_approval.unwrap_err().downcast_ref::<RefundError>(),
+            Some(&RefundError::SelfApproval(refund.id))
+        );
+        assert_eq!(approved.status, Status::Approved);
+        assert_eq!(approved.approved_by.as_deref(), Some("bob"));
+        assert_eq!(service.publisher.messages.lock().unwrap().len(), 1);
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_list_refunds_by_transaction(pool: sqlx::PgPool) {
+        // arrange
+        let (service, repo) = service(pool).await;
+        let transaction = seed_transaction(&repo, 1000, transaction::Status::Success).await;
+        let first = service
+            .create_refund(transaction.id, 800, "USD", "chargeback", "alice")
+            .await
+            .unwrap();
+        service.reject_refund(first.id, "bob").await.unwrap();
+        service
+            .create_refund(transaction.id, 100, "USD", "duplicate", "alice")
+            .await
+            .unwrap();
+
+        // act
+        let refunds = service
+            .get_refunds_by_transaction_id(transaction.id)
+            .await
+            .unwrap();
+
+        // assert
+        assert_eq!(refunds.len(), 2);
+        assert_eq!(refunds[0].status, Status::Rejected);
+        assert_eq!(refunds[1].status, Status::Approved);
+        assert!(service.publisher.messages.lock().unwrap().len() == 1);
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_list_transactions_with_default_limit(pool: sqlx::PgPool) {
+        // arrange
+        let (service, repo) = service(pool).await;
+        let first = seed_transaction(&repo, 1000, transaction::Status::Success).await;
+        let second = seed_transaction(&repo, 2000, transaction::Status::Success).await;
+
+        // act
+        let every = service.list_transactions(None, None, 0).await.unwrap();
+        let newest = service.list_transactions(None, None, 1).await.unwrap();
+        let by_account = service
+            .list_transactions(
+                Some(first.debit_account_id),
+                Some(transaction::Status::Success),
+                0,
+            )
+            .await
+            .unwrap();
+
+        // assert
+        assert_eq!(every, vec![second.clone(), first.clone()]);
+        assert_eq!(newest, vec![second]);
+        assert_eq!(by_account, vec![first]);
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_create_transaction_once_per_idempotency_key(pool: sqlx::PgPool) {
+        // arrange
+        let (service, repo) = service(pool).await;
+        let (debit_account_id, credit_account_id) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        repo.create_account(debit_account_id, "CUSTOMER")
+            .await
+            .unwrap();
+        repo.create_account(credit_account_id, "MERCHANT")
+            .await
+            .unwrap();
+        let request = Transaction::new(
+            debit_account_id,
+            credit_account_id,
+            1050,
+            "USD",
+            "key-1",
+            chrono::Utc::now(),
+        );
+
+        // act
+        let created = service.create_transaction(&request).await.unwrap();
+        let retried = service
+            .create_transaction(&Transaction {
+                id: uuid::Uuid::new_v4(),
+                ..request.clone()
+            })
+            .await
+            .unwrap();
+        let reused = service
+            .create_transaction(&Transaction {
+                amount_minor: 2000,
+                ..request.clone()
+            })
+            .await;
+        let unknown_account = service
+            .create_transaction(&Transaction {
+                id: uuid::Uuid::new_v4(),
+                credit_account_id: uuid::Uuid::new_v4(),
+                idempotency_key: "key-2".to_string(),
+                ..request.clone()
+            })
+            .await;
+
+        // assert
+        assert_eq!(created.id, request.id);
+        assert_eq!(created.status, transaction::Status::Init);
+        assert_eq!(retried, created);
+        assert_eq!(
+            reused.unwrap_err().downcast_ref::<TransactionError>(),
+            Some(&TransactionError::IdempotencyKeyReused("key-1".to_string()))
+        );
+        assert_eq!(
+            unknown_account
+                .unwrap_err()
+                .downcast_ref::<TransactionError>(),
+            Some(&TransactionError::UnknownAccount)
+        );
+        let messages = service.publisher.messages.lock().unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].1, messages[1].1);
+        assert_eq!(messages[0].0, TRANSACTION_EVENTS_TOPIC);
+        let event = events_v1::Transaction::decode(messages[0].2.as_slice()).unwrap();
+        assert_eq!(event.id, created.id.to_string());
+        assert_eq!(event.status, events_v1::TransactionStatus::Init as i32);
+        let amount = event.amount.unwrap();
+        assert_eq!((amount.units, amount.nanos), (10, 500_000_000));
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_skip_republish_once_transaction_is_consumed(pool: sqlx::PgPool) {
+        // arrange
+        let (service, repo) = service(pool).await;
+        let (debit_account_id, credit_account_id) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        repo.create_account(debit_account_id, "CUSTOMER")
+            .await
+            .unwrap();
+        repo.create_account(credit_account_id, "MERCHANT")
+            .await
+            .unwrap();
+        let request = Transaction::new(
+            debit_account_id,
+            credit_account_id,
+            1050,
+            "USD",
+            "key-1",
+            chrono::Utc::now(),
+        );
+        let created = service.create_transaction(&request).await.unwrap();
+        repo.update_transaction_status(created.id, transaction::Status::Pending)
+            .await
+            .unwrap();
+
+        // act
+        let retried = service.create_transaction(&request).await.unwrap();
+
+        // assert
+        assert_eq!(retried.id, created.id);
+        assert_eq!(retried.status, transaction::Status::Pending);
+        assert_eq!(service.publisher.messages.lock().unwrap().len(), 1);
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn error_when_transaction_is_not_found(pool: sqlx::PgPool) {
+        let (service, _) = service(pool).await;
+        let id = uuid::Uuid::new_v4();
+
+        let result = service.get_transaction(id).await;
+
+        assert_eq!(
+            result.unwrap_err().downcast_ref::<TransactionError>(),
+            Some(&TransactionError::NotFound(id))
+        );
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_post_journal_entry_once_per_idempotency_key(pool: sqlx::PgPool) {
+        // arrange
+        let (service, repo) = service(pool).await;
+        let mut legs = vec![];
+        for (entry_type, amount_minor) in [
+            (crate::domain::entry::Type::Debit, 1000),
+            (crate::domain::entry::Type::Credit, 900),
+            (crate::domain::entry::Type::Credit, 100),
+        ] {
+            let account_id = uuid::Uuid::new_v4();
+            repo.create_account(account_id, "MERCHANT").await.unwrap();
+            legs.push(crate::domain::journal::Leg {
+                account_id,
+                entry_type,
+                amount_minor,
+                currency: "USD".to_string(),
+                description: None,
+            });
+        }
+        let request = JournalEntry::new("journal-1", None, legs.clone(), chrono::Utc::now());
+
+        // act
+        let posted = service.post_journal_entry(&request).await.unwrap();
+        let retried = service
+            .post_journal_entry(&JournalEntry::new(
+                "journal-1",
+                None,
+                legs.clone(),
+                chrono::Utc::now(),
+            ))
+            .await
+            .unwrap();
+        legs[1].amount_minor = 800;
+        let unbalanced = service
+            .post_journal_entry(&JournalEntry::new(
+                "journal-2",
+                None,
+                legs.clone(),
+                chrono::Utc::now(),
+            ))
+            .await;
+        legs[0].amount_minor = 900;
+        let reused = service
+            .post_journal_entry(&JournalEntry::new(
+                "journal-1",
+                None,
+                legs,
+                chrono::Utc::now(),
+            ))
+            .await;
+
+        // assert
+        assert_eq!(retried.id, posted.id);
+        assert!(matches!(
+            unbalanced.unwrap_err().downcast_ref::<JournalError>(),
+            Some(JournalError::Unbalanced { .. })
+        ));
+        assert_eq!(
+            reused.unwrap_err().downcast_ref::<JournalError>(),
+            Some(&JournalError::IdempotencyKeyReused("journal-1".to_string()))
+        );
+        assert!(service.publisher.messages.lock().unwrap().is_empty());
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_charge_tiered_fee_to_revenue_account(pool: sqlx::PgPool) {
+        // arrange
+        let (service, repo) = service(pool).await;
+        let (customer, merchant, revenue) = (
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+        );
+        repo.create_account(customer, "CUSTOMER").await.unwrap();
+        repo.create_account(merchant, "MERCHANT").await.unwrap();
+        repo.create_account(revenue, "SYSTEM").await.unwrap();
+        let service = service.with_fee_revenue_account(revenue);
+        let tier =
+            |min_monthly_volume_minor, percentage_bps, fixed_minor| crate::domain::fee::Tier {
+                currency: "USD".to_string(),
+                min_monthly_volume_minor,
+                percentage_bps,
+                fixed_minor,
+            };
+        service
+            .set_fee_schedule(&FeeSchedule::new(
+                merchant,
+                vec![tier(0, 290, 30), tier(2000, 100, 0)],
+                None,
+                "ops",
+            ))
+            .await
+            .unwrap();
+        let invalid = service
+            .set_fee_schedule(&FeeSchedule::new(
+                merchant,
+                vec![tier(2000, 100, 0)],
+                None,
+                "ops",
+            ))
+            .await;
+
+        // act
+        let mut transactions = vec![];
+        for (amount_minor, currency) in [(1000, "USD"), (1500, "USD"), (1000, "USD"), (500, "EUR")]
+        {
+            let transaction = service
+                .create_transaction(&Transaction::new(
+                    customer,
+                    merchant,
+                    amount_minor,
+                    currency,
+                    uuid::Uuid::new_v4().to_string(),
+                    chrono::Utc::now(),
+                ))
+                .await
+                .unwrap();
+            transactions.push(transaction);
+        }
+
+        // assert
+        assert_eq!(
+            invalid
+                .unwrap_err()
+                .downcast_ref::<crate::domain::fee::FeeError>(),
+            Some(&crate::domain::fee::FeeError::MissingBaseTier(
+                "USD".to_string()
+            ))
+        );
+        // 2.9% + 0.30 of 10.00 and 15.00, then 1% once the month reached 20.00
+        let fees = vec![59, 74, 10];
+        for (transaction, fee) in transactions.iter().zip(fees) {
+            let entries = repo
+                .get_entries_by_transaction_id(transaction.id)
+                .await
+                .unwrap();
+            let fee_entries: Vec<_> = entries
+                .iter()
+                .filter(|entry| entry.description.is_some())
+                .map(|entry| {
+                    (
+                        entry.account_id,
+                        entry.entry_type.clone(),
+                        entry.amount_minor,
+                    )
+                })
+                .collect();
+            assert_eq!(fee_entries.len(), 2);
+            assert!(fee_entries.contains(&(merchant, crate::domain::entry::Type::Debit, fee)));
+            assert!(fee_entries.contains(&(revenue, crate::domain::entry::Type::Credit, fee)));
+        }
+        let eur_entries = repo
+            .get_entries_by_transaction_id(transactions[3].id)
+            .await
+            .unwrap();
+        assert_eq!(eur_entries.len(), 2);
+        let balances: Vec<_> = service
+            .get_balances(&[merchant, revenue])
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|balance| (balance.account_id, balance.currency, balance.amount_minor))
+            .collect();
+        assert!(balances.contains(&(merchant, "USD".to_string(), 3500 - 143)));
+        assert!(balances.contains(&(merchant, "EUR".to_string(), 500)));
+        assert!(balances.contains(&(revenue, "USD".to_string(), 143)));
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_create_fx_transaction_once_per_idempotency_key(pool: sqlx::PgPool) {
+        // arrange
+        let (service, repo) = service(pool).await;
+        let (customer, merchant, eur_fx, usd_fx) = (
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+        );
+        repo.create_account(customer, "CUSTOMER").await.unwrap();
+        repo.create_account(merchant, "MERCHANT").await.unwrap();
+        repo.create_account(eur_fx, "SYSTEM").await.unwrap();
+        repo.create_account(usd_fx, "SYSTEM").await.unwrap();
+        let unconfigured = service
+            .create_fx_transaction(
+                &Transaction::new(customer, merchant, 1000, "EUR", "key-0", chrono::Utc::now()),
+                "USD",
+            )
+            .await;
+        let rates = crate::fx::InMemoryFxRates::new().with_rate(
+            "USD",
+            "EUR",
+            rust_decimal::Decimal::new(8, 1),
+        );
+        let service = service
+            .with_fx_rates(Arc::new(rates))
+            .with_fx_account("eur", eur_fx)
+            .with_fx_account("USD", usd_fx)
+            .with_fx_spread(50)
+            .with_fx_rounding_mode(RoundingMode::Down);
+        let request =
+            Transaction::new(customer, merchant, 1000, "EUR", "key-1", chrono::Utc::now());
+
+        // act
+        let created = service
+            .create_fx_transaction(&request, "USD")
+            .await
+            .unwrap();
+        let retried = service
+            .create_fx_transaction(
+                &Transaction {
+                    id: uuid::Uuid::new_v4(),
+                    ..request.clone()
+                },
+                "USD",
+            )
+            .await
+            .unwrap();
+        let other_currency = service.create_transaction(&request).await;
+        let no_fx_account = service
+            .create_fx_transaction(
+                &Transaction::new(customer, merchant, 1000, "EUR", "key-2", chrono::Utc::now()),
+                "GBP",
+            )
+            .await;
+
+        // assert - 10.00 EUR at 1.25 less 0.5% is 12.4375 USD, rounded down
+        assert_eq!(
+            unconfigured.unwrap_err().downcast_ref::<FxError>(),
+            Some(&FxError::NotConfigured)
+        );
+        let fx = created.fx.clone().unwrap();
+        assert_eq!(
+            (fx.credit_amount_minor, fx.credit_currency.as_str()),
+            (1243, "USD")
+        );
+        assert_eq!(fx.rate, rust_decimal::Decimal::new(125, 2));
+        assert_eq!(fx.spread_bps, 50);
+        assert_eq!(fx.rounding_mode, RoundingMode::Down);
+        assert_eq!(fx.rate_source, "memory");
+        assert_eq!(
+            (fx.debit_fx_account_id, fx.credit_fx_account_id),
+            (eur_fx, usd_fx)
+        );
+        assert_eq!(retried, created);
+        assert_eq!(
+            other_currency
+                .unwrap_err()
+                .downcast_ref::<TransactionError>(),
+            Some(&TransactionError::IdempotencyKeyReused("key-1".to_string()))
+        );
+        assert_eq!(
+            no_fx_account.unwrap_err().downcast_ref::<FxError>(),
+            Some(&FxError::NoFxAccount("GBP".to_string()))
+        );
+        assert_eq!(
+            service.get_transaction(created.id).await.unwrap().fx,
+            Some(fx)
+        );
+        assert_eq!(service.publisher.messages.lock().unwrap().len(), 2);
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_capture_hold_once(pool: sqlx::PgPool) {
+        // arrange
+        let (service, repo) = service(pool).await;
+        let (customer, merchant) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        repo.create_account(customer, "CUSTOMER").await.unwrap();
+        repo.create_account(merchant, "MERCHANT").await.unwrap();
+        let request = Hold::new(
+            customer,
+            merchant,
+            1000,
+            "USD",
+            "hold-1",
+            chrono::Duration::hours(1),
+            chrono::Utc::now(),
+        );
+        let authorized = service.authorize_hold(&request).await.unwrap();
+
+        // act
+        let retried = service
+            .authorize_hold(&Hold {
+                id: uuid::Uuid::new_v4(),
+                ..request.clone()
+            })
+            .await
+            .unwrap();
+        let mismatch = service
+            .capture_hold(authorized.id, Some((600, "EUR".to_string())))
+            .await;
+        let (captured, transaction) = service
+            .capture_hold(authorized.id, Some((600, "USD".to_string())))
+            .await
+            .unwrap();
+        let (_, recaptured) = service
+            .capture_hold(authorized.id, Some((600, "USD".to_string())))
+            .await
+            .unwrap();
+        let different_amount = service.capture_hold(authorized.id, None).await;
+        let voided = service.void_hold(authorized.id).await;
+
+        // assert
+        assert_eq!(retried.id, authorized.id);
+        assert_eq!(
+            mismatch.unwrap_err().downcast_ref::<HoldError>(),
+            Some(&HoldError::CurrencyMismatch {
+                expected: "USD".to_string(),
+                got: "EUR".to_string()
+            })
+        );
+        assert_eq!(captured.transaction_id, Some(transaction.id));
+        assert_eq!(transaction.amount_minor, 600);
+        assert_eq!(recaptured.id, transaction.id);
+        assert_eq!(
+            different_amount.unwrap_err().downcast_ref::<HoldError>(),
+            Some(&HoldError::NotAuthorized(
+                authorized.id,
+                hold::Status::Captured
+            ))
+        );
+        assert_eq!(
+            voided.unwrap_err().downcast_ref::<HoldError>(),
+            Some(&HoldError::NotAuthorized(
+                authorized.id,
+                hold::Status::Captured
+            ))
+        );
+        assert_eq!(service.publisher.messages.lock().unwrap().len(), 2);
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_run_each_occurrence_once(pool: sqlx::PgPool) {
+        // arrange
+        let (service, repo) = service(pool).await;
+        let (customer, merchant) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        repo.create_account(customer, "CUSTOMER").await.unwrap();
+        repo.create_account(merchant, "MERCHANT").await.unwrap();
+        let now = chrono::Utc::now();
+        let request = Schedule::new(
+            customer,
+            merchant,
+            1000,
+            "USD",
+            None,
+            Some(3600),
+            Some(now - chrono::Duration::minutes(150)),
+            None,
+            "schedule-1",
+        );
+        let schedule = service.create_schedule(&request).await.unwrap();
+        let retried = service
+            .create_schedule(&Schedule {
+                id: uuid::Uuid::new_v4(),
+                ..request.clone()
+            })
+            .await
+            .unwrap();
+        // the first occurrence was charged before the scheduler crashed
+        let occurrence_at = schedule.next_run_at.unwrap();
+        repo.claim_run(
+            schedule.id,
+            occurrence_at,
+            schedule.next_occurrence(Some(occurrence_at)).unwrap(),
+            now,
+        )
+        .await
+        .unwrap();
+        let charged = service
+            .create_transaction(&schedule.run_transaction(occurrence_at))
+            .await
+            .unwrap();
+
+        // act
+        let first = service.run_due_schedules(now).await.unwrap();
+        let second = service.run_due_schedules(now).await.unwrap();
+        let third = service.run_due_schedules(now).await.unwrap();
+
+        // assert
+        assert_eq!(retried.id, schedule.id);
+        assert_eq!((first, second, third), (2, 1, 0));
+        let runs = service.get_schedule_runs(schedule.id).await.unwrap();
+        assert_eq!(
+            runs.iter().map(|run| run.status).collect::<Vec<_>>(),
+            vec![RunStatus::Succeeded; 3]
+        );
+        assert_eq!(runs[2].transaction_id, Some(charged.id));
+        assert_eq!(service.publisher.messages.lock().unwrap().len(), 4);
+        let balances = repo.get_balances(&[customer]).await.unwrap();
+        assert_eq!(balances[0].amount_minor, -3000);
+        assert_eq!(
+            service.get_schedule(schedule.id).await.unwrap().next_run_at,
+            Some(schedule.starts_at + chrono::Duration::hours(3))
+        );
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_run_schedules_after_one_fails(pool: sqlx::PgPool) {
+        // arrange
+        let (service, repo) = service(pool.clone()).await;
+        let (customer, merchant) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        repo.create_account(customer, "CUSTOMER").await.unwrap();
+        repo.create_account(merchant, "MERCHANT").await.unwrap();
+        let now = chrono::Utc::now();
+        let mut schedules = Vec::new();
+        for (minutes_ago, idempotency_key) in [(30, "broken"), (10, "healthy")] {
+            let schedule = Schedule::new(
+                customer,
+                merchant,
+                1000,
+                "USD",
+                None,
+                Some(3600),
+                Some(now - chrono::Duration::minutes(minutes_ago)),
+                None,
+                idempotency_key,
+            );
+            schedules.push(service.create_schedule(&schedule).await.unwrap());
+        }
+        sqlx::query(
+            "UPDATE schedules SET cron = 'not a cron', interval_seconds = NULL WHERE id = $1",
+        )
+        .bind(schedules[0].id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // act
+        let completed = service.run_due_schedules(now).await.unwrap();
+
+        // assert
+        assert_eq!(completed, 1);
+        assert!(
+            service
+                .get_schedule_runs(schedules[0].id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        let runs = service.get_schedule_runs(schedules[1].id).await.unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].status, RunStatus::Succeeded);
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_create_transaction_once_under_concurrent_requests(pool: sqlx::PgPool) {
+        // arrange
+        let (service, repo) = service(pool).await;
+        let (debit_account_id, credit_account_id) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        repo.create_account(debit_account_id, "CUSTOMER")
+            .await
+            .unwrap();
+        repo.create_account(credit_account_id, "MERCHANT")
+            .await
+            .unwrap();
+        let request = Transaction::new(
+            debit_account_id,
+            credit_account_id,
+            1050,
+            "USD",
+            "key-1",
+            chrono::Utc::now(),
+        );
+        let retry = Transaction {
+            id: uuid::Uuid::new_v4(),
+            ..request.clone()
+        };
+
+        // act
+        let (created, retried) = tokio::join!(
+            service.create_transaction(&request),
+            service.create_transaction(&retry)
+        );
+
+        // assert
+        assert_eq!(created.unwrap().id, retried.unwrap().id);
+        let balances = repo.get_balances(&[debit_account_id]).await.unwrap();
+        assert_eq!(balances[0].amount_minor, -1050);
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_skip_pending_runs_of_inactive_schedules(pool: sqlx::PgPool) {
+        // arrange
+        let (service, repo) = service(pool).await;
+        let (customer, merchant) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        repo.create_account(customer, "CUSTOMER").await.unwrap();
+        repo.create_account(merchant, "MERCHANT").await.unwrap();
+        let now = chrono::Utc::now();
+        let mut runs = Vec::new();
+        for idempotency_key in ["paused", "cancelled"] {
+            let schedule = service
+                .create_schedule(&Schedule::new(
+                    customer,
+                    merchant,
+                    1000,
+                    "USD",
+                    None,
+                    Some(3600),
+                    Some(now - chrono::Duration::minutes(30)),
+                    None,
+                    idempotency_key,
+                ))
+                .await
+                .unwrap();
+            let occurrence_at = schedule.next_run_at.unwrap();
+            let run = repo
+                .claim_run(
+                    schedule.id,
+                    occurrence_at,
+                    schedule.next_occurrence(Some(occurrence_at)).unwrap(),
+                    now,
+                )
+                .await
+                .unwrap()
+                .unwrap();
+            runs.push((schedule, run));
+        }
+        // the run of the paused schedule was charged before the scheduler crashed
+        let charged = service
+            .create_transaction(&runs[0].0.run_transaction(runs[0].1.occurrence_at))
+            .await
+            .unwrap();
+        service.pause_schedule(runs[0].0.id).await.unwrap();
+        service.cancel_schedule(runs[1].0.id).await.unwrap();
+
+        // act
+        let completed = service.run_due_schedules(now).await.unwrap();
+
+        // assert
+        assert_eq!(completed, 2);
+        let paused_runs = service.get_schedule_runs(runs[0].0.id).await.unwrap();
+        assert_eq!(paused_runs[0].status, RunStatus::Succeeded);
+        assert_eq!(paused_runs[0].transaction_id, Some(charged.id));
+        let cancelled_runs = service.get_schedule_runs(runs[1].0.id).await.unwrap();
+        assert_eq!(cancelled_runs[0].status, RunStatus::Skipped);
+        assert_eq!(
+            cancelled_runs[0].error.as_deref(),
+            Some("schedule is cancelled")
+        );
+        let balances = repo.get_balances(&[customer]).await.unwrap();
+        assert_eq!(balances[0].amount_minor, -1000);
     }
 }
@@ -0,0 +1,38 @@
+use crate::domain::transaction::{Status, Transaction};
+use crate::repo::{LedgerReader, LedgerWriter, PgLedgerRepository};
+
+/// seed_transaction registers both accounts and records a USD transaction with the given status.
+pub async fn seed_transaction(
+    repo: &PgLedgerRepository,
+    amount_minor: i64,
+    status: Status,
+) -> Transaction {
+    let debit_account_id = uuid::Uuid::new_v4();
+    let credit_account_id = uuid::Uuid::new_v4();
+    repo.create_account(debit_account_id, "CUSTOMER")
+        .await
+        .unwrap();
+    repo.create_account(credit_account_id, "MERCHANT")
+        .await
+        .unwrap();
+
+    let transaction = repo
+        .create_transaction(
+            &Transaction::new(
+                debit_account_id,
+                credit_account_id,
+                amount_minor,
+                "USD",
+                uuid::Uuid::new_v4().to_string(),
+                chrono::Utc::now(),
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+    repo.update_transaction_status(transaction.id, status)
+        .await
+        .unwrap();
+
+    repo.get_transaction_by_id(transaction.id).await.unwrap()
+}
@@ -0,0 +1,11 @@
+CREATE TABLE api_keys (
+                          id UUID PRIMARY KEY,
+                          principal TEXT NOT NULL,                     -- merchant or operator the key authenticates as
+                          key_hash TEXT NOT NULL UNIQUE,               -- sha256 of the key, the key itself is only shown once
+                          account_ids UUID[] NOT NULL DEFAULT '{}',    -- accounts the principal may use
+                          all_accounts BOOLEAN NOT NULL DEFAULT FALSE, -- operators may use every account
+                          created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                          revoked_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_api_keys_principal ON api_keys(principal);
@@ -0,0 +1,5 @@
+CREATE TABLE rate_limit_buckets (
+                                    key TEXT PRIMARY KEY,                 -- principal, method and route the bucket limits
+                                    tokens DOUBLE PRECISION NOT NULL,     -- tokens left as of updated_at
+                                    updated_at TIMESTAMPTZ NOT NULL
+);
@@ -0,0 +1,44 @@
+CREATE TYPE webhook_delivery_status AS ENUM ('pending', 'succeeded', 'failed');
+
+CREATE TABLE webhook_endpoints (
+                                   id UUID PRIMARY KEY,
+                                   principal TEXT NOT NULL,                     -- merchant or operator the endpoint was registered by
+                                   url TEXT NOT NULL,
+                                   secret TEXT NOT NULL,                        -- shared secret payloads are signed with
+                                   event_types TEXT[] NOT NULL DEFAULT '{}',    -- empty subscribes to every event type
+                                   account_ids UUID[] NOT NULL DEFAULT '{}',    -- accounts of the principal when the endpoint was registered
+                                   all_accounts BOOLEAN NOT NULL DEFAULT FALSE,
+                                   created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                                   disabled_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_webhook_endpoints_principal ON webhook_endpoints(principal);
+
+CREATE TABLE webhook_deliveries (
+                                    id UUID PRIMARY KEY,
+                                    endpoint_id UUID NOT NULL REFERENCES webhook_endpoints(id),
+                                    event_id UUID NOT NULL,
+                                    event_type TEXT NOT NULL,
+                                    payload TEXT NOT NULL,                   -- json body exactly as it is signed and sent
+                                    status webhook_delivery_status NOT NULL DEFAULT 'pending',
+                                    attempts INT NOT NULL DEFAULT 0,
+                                    next_attempt_at TIMESTAMPTZ NOT NULL,
+                                    last_response_status INT,
+                                    last_error TEXT,
+                                    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                                    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                                    UNIQUE (endpoint_id, event_id)
+);
+
+CREATE INDEX idx_webhook_deliveries_due ON webhook_deliveries(next_attempt_at) WHERE status = 'pending';
+
+CREATE TABLE webhook_delivery_attempts (
+                                           id BIGSERIAL PRIMARY KEY,
+                                           delivery_id UUID NOT NULL REFERENCES webhook_deliveries(id),
+                                           attempt INT NOT NULL,
+                                           response_status INT,
+                                           error TEXT,
+                                           attempted_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_webhook_delivery_attempts_delivery_id ON webhook_delivery_attempts(delivery_id);
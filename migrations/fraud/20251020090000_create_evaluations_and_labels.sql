@@ -0,0 +1,22 @@
+CREATE TYPE evaluation_role AS ENUM ('champion', 'challenger');
+
+CREATE TABLE fraud_evaluations (
+                                   id UUID PRIMARY KEY,
+                                   transaction_id UUID NOT NULL,
+                                   role evaluation_role NOT NULL,            -- champion verdicts are published, challenger runs in shadow
+                                   model_version TEXT NOT NULL,
+                                   score REAL NOT NULL,
+                                   threshold REAL NOT NULL,
+                                   is_fraud BOOLEAN NOT NULL,
+                                   created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE TABLE fraud_labels (
+                              transaction_id UUID PRIMARY KEY,
+                              is_fraud BOOLEAN NOT NULL,
+                              source TEXT NOT NULL,                        -- where the label came from e.g. refund:chargeback
+                              created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_fraud_evaluations_transaction ON fraud_evaluations(transaction_id);
+CREATE INDEX idx_fraud_evaluations_created_at ON fraud_evaluations(created_at);
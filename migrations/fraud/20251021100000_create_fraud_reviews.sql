@@ -0,0 +1,18 @@
+CREATE TYPE review_status AS ENUM ('open', 'escalated', 'approved', 'rejected');
+
+CREATE TABLE fraud_reviews (
+                               id UUID PRIMARY KEY,
+                               transaction_id UUID NOT NULL UNIQUE,
+                               idempotency_key TEXT NOT NULL,
+                               score REAL NOT NULL,
+                               model_version TEXT NOT NULL,
+                               status review_status NOT NULL DEFAULT 'open',
+                               decided_by TEXT,                             -- reviewer, or 'sla' when the SLA decided
+                               notes TEXT,
+                               due_at TIMESTAMPTZ NOT NULL,                 -- SLA deadline for a decision
+                               decided_at TIMESTAMPTZ,
+                               created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                               updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_fraud_reviews_status_due_at ON fraud_reviews(status, due_at);
@@ -0,0 +1,10 @@
+-- a redelivered transaction must not be evaluated twice, keep the first evaluation per role
+DELETE FROM fraud_evaluations e
+    USING fraud_evaluations first
+WHERE e.transaction_id = first.transaction_id
+  AND e.role = first.role
+  AND (e.created_at, e.id) > (first.created_at, first.id);
+
+ALTER TABLE fraud_evaluations ADD CONSTRAINT fraud_evaluations_transaction_id_role_key UNIQUE (transaction_id, role);
+
+DROP INDEX idx_fraud_evaluations_transaction;
@@ -0,0 +1,14 @@
+-- Add migration script here
+CREATE TYPE transaction_status AS ENUM ('init', 'pending', 'success', 'failed', 'fraud', 'refund', 'refunded');
+CREATE TYPE entry_type AS ENUM ('debit', 'credit');
+
+ALTER TABLE transactions ALTER COLUMN status DROP DEFAULT;
+
+ALTER TABLE transactions
+        ALTER COLUMN status TYPE transaction_status USING LOWER(status)::transaction_status;
+
+ALTER TABLE transactions
+        ALTER COLUMN status SET DEFAULT 'init';
+
+ALTER TABLE ledger_entries
+        ALTER COLUMN entry_type TYPE entry_type USING LOWER(entry_type)::entry_type;
@@ -0,0 +1,23 @@
+-- Add migration script here
+CREATE TYPE refund_status AS ENUM ('pending', 'succeeded', 'failed');
+
+CREATE TABLE refunds (
+                         id UUID PRIMARY KEY,            -- Refund ID (from refund event), also the PSP idempotency key
+                         transaction_id UUID NOT NULL REFERENCES transactions(id),
+                         amount_minor BIGINT NOT NULL CHECK (amount_minor > 0),
+                         currency CHAR(3) NOT NULL,
+                         reason TEXT NOT NULL,
+                         status refund_status NOT NULL DEFAULT 'pending',
+                         psp_reference TEXT,
+                         failure_reason TEXT,
+                         created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+                         updated_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+
+-- reversing entries point at the refund they were posted for
+ALTER TABLE ledger_entries
+    ADD COLUMN refund_id UUID REFERENCES refunds(id);
+
+-- Indexes
+CREATE INDEX idx_refunds_transaction ON refunds(transaction_id);
+CREATE INDEX idx_ledger_entries_transaction ON ledger_entries(transaction_id);
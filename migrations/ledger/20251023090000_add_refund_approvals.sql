@@ -0,0 +1,9 @@
+-- Add migration script here
+-- manual refunds above the approval threshold wait for a second approver (maker-checker)
+ALTER TYPE refund_status ADD VALUE IF NOT EXISTS 'awaiting_approval';
+ALTER TYPE refund_status ADD VALUE IF NOT EXISTS 'approved';
+ALTER TYPE refund_status ADD VALUE IF NOT EXISTS 'rejected';
+
+ALTER TABLE refunds
+    ADD COLUMN requested_by TEXT,
+    ADD COLUMN approved_by TEXT;
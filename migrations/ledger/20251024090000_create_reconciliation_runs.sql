@@ -0,0 +1,19 @@
+-- Add migration script here
+CREATE TYPE reconciliation_run_status AS ENUM ('running', 'completed', 'failed');
+
+CREATE TABLE reconciliation_runs (
+                                     id UUID PRIMARY KEY,
+                                     window_start TIMESTAMP WITH TIME ZONE NOT NULL,  -- inclusive
+                                     window_end TIMESTAMP WITH TIME ZONE NOT NULL,    -- exclusive
+                                     status reconciliation_run_status NOT NULL DEFAULT 'running',
+                                     matched_count BIGINT NOT NULL DEFAULT 0,
+                                     mismatched_count BIGINT NOT NULL DEFAULT 0,
+                                     failure_reason TEXT,
+                                     started_at TIMESTAMP WITH TIME ZONE NOT NULL,
+                                     finished_at TIMESTAMP WITH TIME ZONE,
+                                     CHECK (window_start < window_end)
+);
+
+-- Indexes
+CREATE INDEX idx_reconciliation_runs_window_end ON reconciliation_runs(window_end);
+CREATE INDEX idx_transactions_created_at ON transactions(created_at);
@@ -0,0 +1,7 @@
+-- Add migration script here
+-- runs are kept per source so imported statement files do not move the PSP schedule
+ALTER TABLE reconciliation_runs
+    ADD COLUMN source TEXT NOT NULL DEFAULT 'psp';
+
+DROP INDEX idx_reconciliation_runs_window_end;
+CREATE INDEX idx_reconciliation_runs_source_window_end ON reconciliation_runs(source, window_end);
@@ -0,0 +1,37 @@
+-- Add migration script here
+CREATE TYPE reconciliation_match_strategy AS ENUM ('reference', 'amount_tolerance', 'fee_adjusted', 'date_window', 'sum');
+
+-- one row per matched ledger transaction, a sum match pairs one statement line with many
+CREATE TABLE reconciliation_matches (
+                                        run_id UUID NOT NULL REFERENCES reconciliation_runs(id),
+                                        transaction_id UUID NOT NULL,
+                                        statement_reference TEXT NOT NULL,
+                                        strategy reconciliation_match_strategy NOT NULL,
+                                        created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+                                        PRIMARY KEY (run_id, transaction_id)
+);
+
+CREATE TABLE reconciliation_exceptions (
+                                           id UUID PRIMARY KEY,
+                                           source TEXT NOT NULL,
+                                           reference TEXT NOT NULL,  -- statement line reference, or the ledger transaction id when the PSP reported nothing
+                                           ledger_transaction_id UUID,
+                                           statement_reference TEXT,
+                                           discrepancies TEXT[] NOT NULL,
+                                           ledger_amount_minor BIGINT,
+                                           external_amount_minor BIGINT,
+                                           currency TEXT NOT NULL,
+                                           first_seen_run_id UUID NOT NULL REFERENCES reconciliation_runs(id),
+                                           last_seen_run_id UUID NOT NULL REFERENCES reconciliation_runs(id),
+                                           first_seen_at TIMESTAMP WITH TIME ZONE NOT NULL,
+                                           last_seen_at TIMESTAMP WITH TIME ZONE NOT NULL,
+                                           resolved_at TIMESTAMP WITH TIME ZONE,
+                                           resolved_run_id UUID REFERENCES reconciliation_runs(id)
+);
+
+-- Indexes
+CREATE INDEX idx_reconciliation_matches_transaction_id ON reconciliation_matches(transaction_id);
+CREATE UNIQUE INDEX idx_reconciliation_exceptions_open_reference
+    ON reconciliation_exceptions(source, reference) WHERE resolved_at IS NULL;
+CREATE INDEX idx_reconciliation_exceptions_open_first_seen_at
+    ON reconciliation_exceptions(first_seen_at) WHERE resolved_at IS NULL;
@@ -0,0 +1,24 @@
+-- Add migration script here
+CREATE TYPE reconciliation_exception_status AS ENUM ('open', 'investigating', 'resolved', 'written_off');
+
+-- resolved_at is set once the exception is closed, whether resolved or written off
+ALTER TABLE reconciliation_exceptions
+    ADD COLUMN status reconciliation_exception_status NOT NULL DEFAULT 'open',
+    ADD COLUMN ledger_status transaction_status,
+    ADD COLUMN external_status TEXT,
+    ADD COLUMN updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now();
+
+UPDATE reconciliation_exceptions SET status = 'resolved' WHERE resolved_at IS NOT NULL;
+
+CREATE TABLE reconciliation_exception_notes (
+                                                id UUID PRIMARY KEY,
+                                                exception_id UUID NOT NULL REFERENCES reconciliation_exceptions(id),
+                                                author TEXT NOT NULL,
+                                                status reconciliation_exception_status NOT NULL,
+                                                text TEXT NOT NULL,
+                                                created_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+
+-- Indexes
+CREATE INDEX idx_reconciliation_exceptions_status ON reconciliation_exceptions(status, first_seen_at);
+CREATE INDEX idx_reconciliation_exception_notes_exception_id ON reconciliation_exception_notes(exception_id, created_at);
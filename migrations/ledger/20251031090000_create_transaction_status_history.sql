@@ -0,0 +1,15 @@
+-- Add migration script here
+-- every status a transaction has been in, written together with the status change
+CREATE TABLE transaction_status_history (
+                                            id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                                            transaction_id UUID NOT NULL REFERENCES transactions(id),
+                                            status transaction_status NOT NULL,
+                                            created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+-- earlier changes were not recorded, existing transactions start from their current status
+INSERT INTO transaction_status_history (transaction_id, status, created_at)
+SELECT id, status, updated_at FROM transactions;
+
+-- Indexes
+CREATE INDEX idx_transaction_status_history_transaction_id ON transaction_status_history(transaction_id, created_at);
@@ -0,0 +1,24 @@
+-- Add migration script here
+CREATE TYPE transaction_kind AS ENUM ('transfer', 'journal');
+
+-- journal entries are transactions without a single debit and credit pair, their legs are the
+-- ledger entries posted under them
+ALTER TABLE transactions
+    ADD COLUMN kind transaction_kind NOT NULL DEFAULT 'transfer',
+    ADD COLUMN description TEXT,
+    ALTER COLUMN debit_account_id DROP NOT NULL,
+    ALTER COLUMN credit_account_id DROP NOT NULL,
+    ALTER COLUMN amount_minor DROP NOT NULL,
+    ALTER COLUMN currency DROP NOT NULL,
+    ADD CONSTRAINT transactions_transfer_pair_check CHECK (
+        kind = 'journal'
+        OR (debit_account_id IS NOT NULL AND credit_account_id IS NOT NULL AND amount_minor IS NOT NULL AND currency IS NOT NULL)
+    );
+
+-- leg is the position of a journal leg in the posted entry
+ALTER TABLE ledger_entries
+    ADD COLUMN description TEXT,
+    ADD COLUMN leg INTEGER;
+
+-- Indexes
+CREATE INDEX idx_transactions_kind ON transactions(kind, created_at);
@@ -0,0 +1,37 @@
+-- Add migration script here
+-- every version of the fees charged to a merchant, a version applies from its effective_from
+-- until the next version takes effect
+CREATE TABLE fee_schedules (
+                               id UUID PRIMARY KEY,
+                               merchant_account_id UUID NOT NULL REFERENCES accounts(id),
+                               version INTEGER NOT NULL,
+                               effective_from TIMESTAMP WITH TIME ZONE NOT NULL,
+                               created_by TEXT NOT NULL,
+                               created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+                               UNIQUE (merchant_account_id, version)
+);
+
+CREATE TABLE fee_schedule_tiers (
+                                    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                                    fee_schedule_id UUID NOT NULL REFERENCES fee_schedules(id),
+                                    currency CHAR(3) NOT NULL,
+                                    min_monthly_volume_minor BIGINT NOT NULL CHECK (min_monthly_volume_minor >= 0),
+                                    percentage_bps INTEGER NOT NULL CHECK (percentage_bps BETWEEN 0 AND 10000),
+                                    fixed_minor BIGINT NOT NULL CHECK (fixed_minor >= 0),
+                                    UNIQUE (fee_schedule_id, currency, min_monthly_volume_minor)
+);
+
+-- the fee charged on a transaction and the schedule version it was priced with, its entries
+-- are posted under the transaction
+CREATE TABLE transaction_fees (
+                                  transaction_id UUID PRIMARY KEY REFERENCES transactions(id),
+                                  fee_schedule_id UUID NOT NULL REFERENCES fee_schedules(id),
+                                  revenue_account_id UUID NOT NULL REFERENCES accounts(id),
+                                  amount_minor BIGINT NOT NULL,
+                                  monthly_volume_minor BIGINT NOT NULL,
+                                  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+-- Indexes
+CREATE INDEX idx_fee_schedules_merchant_account_id ON fee_schedules(merchant_account_id, effective_from);
+CREATE INDEX idx_transactions_credit_account_id ON transactions(credit_account_id, currency, created_at);
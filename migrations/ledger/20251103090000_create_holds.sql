@@ -0,0 +1,24 @@
+-- Add migration script here
+CREATE TYPE hold_status AS ENUM ('authorized', 'captured', 'voided', 'expired');
+
+-- funds reserved on the debited account until the hold is captured into a transaction, voided
+-- or expires. Authorized holds count against the available balance, not the posted one.
+CREATE TABLE holds (
+                       id UUID PRIMARY KEY,
+                       debit_account_id UUID NOT NULL REFERENCES accounts(id),
+                       credit_account_id UUID NOT NULL REFERENCES accounts(id),
+                       amount_minor BIGINT NOT NULL CHECK (amount_minor > 0),
+                       currency CHAR(3) NOT NULL,
+                       status hold_status NOT NULL DEFAULT 'authorized',
+                       idempotency_key TEXT NOT NULL UNIQUE,
+                       expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+                       captured_amount_minor BIGINT,
+                       transaction_id UUID REFERENCES transactions(id),
+                       request_timestamp TIMESTAMP WITH TIME ZONE NOT NULL,
+                       created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+                       updated_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+
+-- Indexes
+CREATE INDEX idx_holds_debit_account_id ON holds(debit_account_id) WHERE status = 'authorized';
+CREATE INDEX idx_holds_expires_at ON holds(expires_at) WHERE status = 'authorized';
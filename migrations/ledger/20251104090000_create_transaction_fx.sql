@@ -0,0 +1,20 @@
+-- Add migration script here
+CREATE TYPE fx_rounding_mode AS ENUM ('half_up', 'half_even', 'down', 'up');
+
+-- the FX leg of a cross-currency transfer. The debited account pays the transaction amount into
+-- the FX account of its currency, the FX account of the credit currency pays the converted
+-- amount to the credited account. The rate is kept as it was quoted for audit.
+CREATE TABLE transaction_fx (
+                                transaction_id UUID PRIMARY KEY REFERENCES transactions(id),
+                                debit_fx_account_id UUID NOT NULL REFERENCES accounts(id),
+                                credit_fx_account_id UUID NOT NULL REFERENCES accounts(id),
+                                credit_amount_minor BIGINT NOT NULL CHECK (credit_amount_minor > 0),
+                                credit_currency CHAR(3) NOT NULL,
+                                rate NUMERIC NOT NULL CHECK (rate > 0),
+                                applied_rate NUMERIC NOT NULL CHECK (applied_rate > 0),
+                                spread_bps INTEGER NOT NULL CHECK (spread_bps BETWEEN 0 AND 10000),
+                                rounding_mode fx_rounding_mode NOT NULL,
+                                rate_source TEXT NOT NULL,
+                                rate_as_of TIMESTAMP WITH TIME ZONE NOT NULL,
+                                created_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
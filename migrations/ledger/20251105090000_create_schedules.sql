@@ -0,0 +1,43 @@
+-- Add migration script here
+CREATE TYPE schedule_status AS ENUM ('active', 'paused', 'cancelled', 'completed');
+CREATE TYPE schedule_run_status AS ENUM ('pending', 'succeeded', 'failed', 'skipped');
+
+-- standing orders and recurring pulls. A schedule recurs on a cron expression or a fixed
+-- interval from starts_at, next_run_at is the next occurrence to run and NULL once it is
+-- cancelled or completed.
+CREATE TABLE schedules (
+                           id UUID PRIMARY KEY,
+                           debit_account_id UUID NOT NULL REFERENCES accounts(id),
+                           credit_account_id UUID NOT NULL REFERENCES accounts(id),
+                           amount_minor BIGINT NOT NULL CHECK (amount_minor > 0),
+                           currency CHAR(3) NOT NULL,
+                           cron TEXT,
+                           interval_seconds BIGINT CHECK (interval_seconds > 0),
+                           starts_at TIMESTAMP WITH TIME ZONE NOT NULL,
+                           ends_at TIMESTAMP WITH TIME ZONE,
+                           next_run_at TIMESTAMP WITH TIME ZONE,
+                           status schedule_status NOT NULL DEFAULT 'active',
+                           idempotency_key TEXT NOT NULL UNIQUE,
+                           created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+                           updated_at TIMESTAMP WITH TIME ZONE NOT NULL,
+                           CHECK ((cron IS NULL) <> (interval_seconds IS NULL))
+);
+
+-- one row per occurrence, claimed as pending before its transaction is created
+CREATE TABLE schedule_runs (
+                               id UUID PRIMARY KEY,
+                               schedule_id UUID NOT NULL REFERENCES schedules(id),
+                               occurrence_at TIMESTAMP WITH TIME ZONE NOT NULL,
+                               status schedule_run_status NOT NULL,
+                               transaction_id UUID REFERENCES transactions(id),
+                               error TEXT,
+                               created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+                               updated_at TIMESTAMP WITH TIME ZONE NOT NULL,
+                               UNIQUE (schedule_id, occurrence_at)
+);
+
+-- Indexes
+CREATE INDEX idx_schedules_next_run_at ON schedules(next_run_at) WHERE status = 'active';
+CREATE INDEX idx_schedules_debit_account_id ON schedules(debit_account_id);
+CREATE INDEX idx_schedules_credit_account_id ON schedules(credit_account_id);
+CREATE INDEX idx_schedule_runs_pending ON schedule_runs(created_at) WHERE status = 'pending';
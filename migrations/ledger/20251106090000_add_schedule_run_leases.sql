@@ -0,0 +1,4 @@
+-- Add migration script here
+-- a pending run is leased to the worker executing it until claimed_until, other workers retry
+-- it only once the lease expired
+ALTER TABLE schedule_runs ADD COLUMN claimed_until TIMESTAMP WITH TIME ZONE;
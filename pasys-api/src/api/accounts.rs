@@ -0,0 +1,160 @@
+use crate::api::error::{ApiError, ErrorBody};
+use crate::api::idempotency::IdempotencyKeyHeader;
+use crate::api::parsers::{
+    parse_account_status_from_proto, parse_account_type_from_proto, parse_timestamp_from_proto,
+};
+use crate::api::{ApiJson, AppState};
+use crate::domain::principal::Principal;
+use accounts_proto::accounts_v1;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AccountType {
+    Customer,
+    Merchant,
+    System,
+}
+
+impl From<AccountType> for accounts_v1::AccountType {
+    fn from(account_type: AccountType) -> Self {
+        match account_type {
+            AccountType::Customer => accounts_v1::AccountType::Customer,
+            AccountType::Merchant => accounts_v1::AccountType::Merchant,
+            AccountType::System => accounts_v1::AccountType::System,
+        }
+    }
+}
+
+/// CreateAccountRequest has no `created_by`, accounts are created by the authenticated
+/// principal.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateAccountRequest {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub account_type: AccountType,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Account {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "type")]
+    #[schema(example = "merchant")]
+    pub account_type: String,
+    #[schema(example = "active")]
+    pub status: String,
+    pub created_by: String,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<accounts_v1::Account> for Account {
+    fn from(account: accounts_v1::Account) -> Self {
+        Self {
+            id: account.id,
+            name: account.name,
+            account_type: parse_account_type_from_proto(account.r#type),
+            status: parse_account_status_from_proto(account.status),
+            created_by: account.created_by,
+            created_at: parse_timestamp_from_proto(account.created_at),
+            updated_at: parse_timestamp_from_proto(account.updated_at),
+        }
+    }
+}
+
+/// account_from_response unwraps the account of a response, which the accounts service always
+/// sets on success.
+fn account_from_response(account: Option<accounts_v1::Account>) -> Result<Account, ApiError> {
+    match account {
+        Some(account) => Ok(account.into()),
+        None => Err(tonic::Status::internal("accounts service returned no account").into()),
+    }
+}
+
+/// create_account is limited to operators, a merchant could not read the account back as it
+/// is not in its scope.
+#[utoipa::path(
+    post,
+    path = "/v1/accounts",
+    tag = "accounts",
+    summary = "Create an account",
+    description = "Only operators can create accounts, `created_by` is set to the authenticated principal.",
+    params(IdempotencyKeyHeader),
+    request_body = CreateAccountRequest,
+    responses(
+        (status = 201, description = "Account created", body = Account),
+        (status = 400, description = "Invalid request", body = ErrorBody),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorBody),
+        (status = 403, description = "Caller is not an operator", body = ErrorBody),
+        (status = 409, description = "Idempotency key is in use", body = ErrorBody),
+        (status = 422, description = "Idempotency key was used with another body", body = ErrorBody),
+        (status = 429, description = "Rate limit exceeded, retry after `Retry-After` seconds", body = ErrorBody),
+    )
+)]
+pub async fn create_account(
+    State(state): State<AppState>,
+    Extension(principal): Extension<Principal>,
+    ApiJson(request): ApiJson<CreateAccountRequest>,
+) -> Result<(StatusCode, Json<Account>), ApiError> {
+    if !principal.is_unrestricted() {
+        return Err(ApiError::permission_denied(
+            "only operators can create accounts",
+        ));
+    }
+
+    let response = state
+        .accounts
+        .clone()
+        .create_account(accounts_v1::CreateAccountRequest {
+            name: request.name,
+            r#type: accounts_v1::AccountType::from(request.account_type) as i32,
+            created_by: principal.subject,
+        })
+        .await?
+        .into_inner();
+
+    Ok((
+        StatusCode::CREATED,
+        Json(account_from_response(response.account)?),
+    ))
+}
+
+/// get_account answers accounts out of the principal's scope as not found, so merchants cannot
+/// probe for the accounts of others.
+#[utoipa::path(
+    get,
+    path = "/v1/accounts/{id}",
+    tag = "accounts",
+    summary = "Get an account",
+    description = "Accounts out of the caller's scope are answered as not found.",
+    params(("id" = String, Path, description = "Account id")),
+    responses(
+        (status = 200, description = "Account found", body = Account),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorBody),
+        (status = 404, description = "Account not found", body = ErrorBody),
+        (status = 429, description = "Rate limit exceeded, retry after `Retry-After` seconds", body = ErrorBody),
+    )
+)]
+pub async fn get_account(
+    State(state): State<AppState>,
+    Extension(principal): Extension<Principal>,
+    Path(id): Path<String>,
+) -> Result<Json<Account>, ApiError> {
+    if !principal.can_access(id.as_str()) {
+        return Err(ApiError::not_found(format!("account {id} not found")));
+    }
+
+    let response = state
+        .accounts
+        .clone()
+        .get_account(accounts_v1::GetAccountRequest { account_id: id })
+        .await?
+        .into_inner();
+
+    Ok(Json(account_from_response(response.account)?))
+}
@@ -0,0 +1,196 @@
+use axum::Json;
+use axum::extract::rejection::JsonRejection;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// ApiError is returned by every endpoint that fails, rendered as
+/// `{"error": {"code": "...", "message": "..."}}`.
+#[derive(Debug)]
+pub struct ApiError {
+    pub status: StatusCode,
+    pub code: &'static str,
+    pub message: String,
+}
+
+/// ErrorBody is the body of every error response.
+#[derive(Serialize, ToSchema)]
+pub struct ErrorBody<'a> {
+    error: ErrorDetail<'a>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ErrorDetail<'a> {
+    #[schema(example = "not_found")]
+    code: &'a str,
+    message: &'a str,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn invalid_argument(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, "invalid_argument", message)
+    }
+
+    pub fn permission_denied(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::FORBIDDEN, "permission_denied", message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, "not_found", message)
+    }
+}
+
+/// parse_code_to_http maps a grpc code to its HTTP status and error code, following the
+/// mapping of `google.rpc.Code`.
+pub fn parse_code_to_http(code: tonic::Code) -> (StatusCode, &'static str) {
+    match code {
+        tonic::Code::Ok => (StatusCode::OK, "ok"),
+        tonic::Code::Cancelled => (
+            StatusCode::from_u16(499).expect("499 is a valid status code"),
+            "cancelled",
+        ),
+        tonic::Code::InvalidArgument => (StatusCode::BAD_REQUEST, "invalid_argument"),
+        tonic::Code::FailedPrecondition => (StatusCode::BAD_REQUEST, "failed_precondition"),
+        tonic::Code::OutOfRange => (StatusCode::BAD_REQUEST, "out_of_range"),
+        tonic::Code::Unauthenticated => (StatusCode::UNAUTHORIZED, "unauthenticated"),
+        tonic::Code::PermissionDenied => (StatusCode::FORBIDDEN, "permission_denied"),
+        tonic::Code::NotFound => (StatusCode::NOT_FOUND, "not_found"),
+        tonic::Code::AlreadyExists => (StatusCode::CONFLICT, "already_exists"),
+        tonic::Code::Aborted => (StatusCode::CONFLICT, "aborted"),
+        tonic::Code::ResourceExhausted => (StatusCode::TOO_MANY_REQUESTS, "resource_exhausted"),
+        tonic::Code::Unimplemented => (StatusCode::NOT_IMPLEMENTED, "unimplemented"),
+        tonic::Code::Unavailable => (StatusCode::SERVICE_UNAVAILABLE, "unavailable"),
+        tonic::Code::DeadlineExceeded => (StatusCode::GATEWAY_TIMEOUT, "deadline_exceeded"),
+        tonic::Code::Unknown => (StatusCode::INTERNAL_SERVER_ERROR, "unknown"),
+        tonic::Code::Internal => (StatusCode::INTERNAL_SERVER_ERROR, "internal"),
+        tonic::Code::DataLoss => (StatusCode::INTERNAL_SERVER_ERROR, "data_loss"),
+    }
+}
+
+/// Server errors only keep their code, the grpc message may hold database details which are
+/// logged instead of being returned to the client.
+impl From<tonic::Status> for ApiError {
+    fn from(status: tonic::Status) -> Self {
+        let (http_status, code) = parse_code_to_http(status.code());
+        if http_status.is_server_error() {
+            tracing::error!(
+                code,
+                message = status.message(),
+                "upstream grpc call failed"
+            );
+            let message = http_status.canonical_reason().unwrap_or("server error");
+            return Self::new(http_status, code, message.to_lowercase());
+        }
+
+        Self::new(http_status, code, status.message())
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        Self::new(
+            rejection.status(),
+            "invalid_argument",
+            rejection.body_text(),
+        )
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = ErrorBody {
+            error: ErrorDetail {
+                code: self.code,
+                message: self.message.as_str(),
+            },
+        };
+
+        (self.status, Json(body)).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_code_to_http() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            code: tonic::Code,
+            expected: StatusCode,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "bad request when argument is invalid",
+                code: tonic::Code::InvalidArgument,
+                expected: StatusCode::BAD_REQUEST,
+            },
+            TestCase {
+                name: "bad request when precondition failed",
+                code: tonic::Code::FailedPrecondition,
+                expected: StatusCode::BAD_REQUEST,
+            },
+            TestCase {
+                name: "not found when resource does not exist",
+                code: tonic::Code::NotFound,
+                expected: StatusCode::NOT_FOUND,
+            },
+            TestCase {
+                name: "conflict when resource already exists",
+                code: tonic::Code::AlreadyExists,
+                expected: StatusCode::CONFLICT,
+            },
+            TestCase {
+                name: "too many requests when resource is exhausted",
+                code: tonic::Code::ResourceExhausted,
+                expected: StatusCode::TOO_MANY_REQUESTS,
+            },
+            TestCase {
+                name: "service unavailable when upstream is down",
+                code: tonic::Code::Unavailable,
+                expected: StatusCode::SERVICE_UNAVAILABLE,
+            },
+            TestCase {
+                name: "gateway timeout when deadline is exceeded",
+                code: tonic::Code::DeadlineExceeded,
+                expected: StatusCode::GATEWAY_TIMEOUT,
+            },
+            TestCase {
+                name: "internal server error for internal errors",
+                code: tonic::Code::Internal,
+                expected: StatusCode::INTERNAL_SERVER_ERROR,
+            },
+        ];
+
+        for test_case in test_cases {
+            let (status, _) = parse_code_to_http(test_case.code);
+            assert_eq!(status, test_case.expected, "{}", test_case.name);
+        }
+    }
+
+    #[test]
+    fn successfully_hide_message_of_server_errors() {
+        // arrange
+        let status = tonic::Status::internal("failed to create transaction: pool timed out");
+
+        // act
+        let error = ApiError::from(status);
+
+        // assert
+        assert_eq!(error.status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(error.code, "internal");
+        assert_eq!(error.message, "internal server error");
+    }
+}
@@ -0,0 +1,530 @@
+use crate::api::AppState;
+use crate::api::error::ApiError;
+use crate::domain::principal::Principal;
+use axum::body::{Body, Bytes};
+use axum::extract::{Request, State};
+use axum::http::header::CONTENT_TYPE;
+use axum::http::{HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use sha2::{Digest, Sha256};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// MAX_KEY_LENGTH bounds the keys clients may send, UUIDs being the expected format.
+const MAX_KEY_LENGTH: usize = 255;
+/// MAX_BODY_BYTES matches the default body limit of axum.
+const MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+/// IN_FLIGHT_TIMEOUT releases the key of a request which never completed, e.g. because the
+/// handler panicked, so the client can retry it.
+const IN_FLIGHT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// IdempotencyKey is the `Idempotency-Key` header of the request, available to handlers as an
+/// extension.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdempotencyKey(pub String);
+
+/// IdempotencyKeyHeader documents the `Idempotency-Key` header accepted by every POST.
+#[derive(Debug, utoipa::IntoParams)]
+#[into_params(parameter_in = Header)]
+pub struct IdempotencyKeyHeader {
+    /// Idempotency-Key replays the first response of the key for the same body, as a
+    /// structured field string or a bare token of at most 255 characters.
+    #[param(rename = "Idempotency-Key")]
+    pub idempotency_key: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+    status: StatusCode,
+    content_type: Option<HeaderValue>,
+    body: Bytes,
+}
+
+impl IntoResponse for CachedResponse {
+    fn into_response(self) -> Response {
+        let mut response = (self.status, self.body).into_response();
+        if let Some(content_type) = self.content_type {
+            response.headers_mut().insert(CONTENT_TYPE, content_type);
+        }
+        response
+            .headers_mut()
+            .insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+        response
+    }
+}
+
+#[derive(Debug)]
+struct Entry {
+    fingerprint: [u8; 32],
+    response: Option<CachedResponse>,
+    expires_at: Instant,
+}
+
+/// Lookup is the outcome of starting a request with an idempotency key.
+#[derive(Debug)]
+pub enum Lookup {
+    /// Started means the key is new and the request should be processed.
+    Started,
+    /// InFlight means a request with the key is still being processed.
+    InFlight,
+    /// Mismatch means the key was first used with a different body.
+    Mismatch,
+    /// Replay holds the response of the first request with the key.
+    Replay(CachedResponse),
+}
+
+/// IdempotencyCache remembers the first response per idempotency key until the ttl passes. It
+/// holds at most `max_entries` keys, forgetting the ones expiring soonest first. The cache is per
+/// replica, retries reaching another replica are only deduplicated by the ledger idempotency key.
+#[derive(Debug)]
+pub struct IdempotencyCache {
+    ttl: Duration,
+    max_entries: usize,
+    entries: Mutex<Entries>,
+}
+
+/// Entries are the cached keys and their expiry times, soonest first. Expiries are pushed again
+/// when a key completes, so stale ones are skipped when the key expires later.
+#[derive(Debug, Default)]
+struct Entries {
+    keys: HashMap<String, Entry>,
+    expiries: BinaryHeap<Reverse<(Instant, String)>>,
+}
+
+impl Entries {
+    fn insert(&mut self, key: &str, entry: Entry) {
+        self.expiries
+            .push(Reverse((entry.expires_at, key.to_string())));
+        self.keys.insert(key.to_string(), entry);
+    }
+
+    /// evict_expired drops the keys expired at `now`, looking only at the expiries due.
+    fn evict_expired(&mut self, now: Instant) {
+        while let Some(Reverse((expires_at, _))) = self.expiries.peek() {
+            if *expires_at > now {
+                return;
+            }
+            let Some(Reverse((_, key))) = self.expiries.pop() else {
+                return;
+            };
+            if self
+                .keys
+                .get(&key)
+                .is_some_and(|entry| entry.expires_at <= now)
+            {
+                self.keys.remove(&key);
+            }
+        }
+    }
+
+    /// evict_soonest drops the key expiring soonest, returning false when there is none.
+    fn evict_soonest(&mut self) -> bool {
+        while let Some(Reverse((expires_at, key))) = self.expiries.pop() {
+            if self
+                .keys
+                .get(&key)
+                .is_some_and(|entry| entry.expires_at == expires_at)
+            {
+                self.keys.remove(&key);
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+impl IdempotencyCache {
+    pub fn new(ttl: Duration, max_entries: usize) -> Self {
+        Self {
+            ttl,
+            max_entries,
+            entries: Mutex::new(Entries::default()),
+        }
+    }
+
+    /// begin marks the key as in flight unless it was seen before, dropping expired keys first.
+    pub fn begin(&self, key: &str, fingerprint: [u8; 32], now: Instant) -> Lookup {
+        let mut entries = self
+            .entries
+            .lock()
+            .expect("idempotency cache lock poisoned");
+        entries.evict_expired(now);
+
+        match entries.keys.get(key) {
+            Some(entry) if entry.fingerprint != fingerprint => Lookup::Mismatch,
+            Some(entry) => match &entry.response {
+                Some(response) => Lookup::Replay(response.clone()),
+                None => Lookup::InFlight,
+            },
+            None => {
+                while entries.keys.len() >= self.max_entries && entries.evict_soonest() {}
+                entries.insert(
+                    key,
+                    Entry {
+                        fingerprint,
+                        response: None,
+                        expires_at: now + IN_FLIGHT_TIMEOUT.min(self.ttl),
+                    },
+                );
+                Lookup::Started
+            }
+        }
+    }
+
+    /// complete stores the response of the key, replayed until the ttl passes.
+    pub fn complete(&self, key: &str, response: CachedResponse, now: Instant) {
+        let mut entries = self
+            .entries
+            .lock()
+            .expect("idempotency cache lock poisoned");
+        let expires_at = now + self.ttl;
+        if let Some(entry) = entries.keys.get_mut(key) {
+            entry.response = Some(response);
+            entry.expires_at = expires_at;
+            entries
+                .expiries
+                .push(Reverse((expires_at, key.to_string())));
+        }
+    }
+
+    /// release forgets the key so the request can be retried.
+    pub fn release(&self, key: &str) {
+        let mut entries = self
+            .entries
+            .lock()
+            .expect("idempotency cache lock poisoned");
+        entries.keys.remove(key);
+    }
+}
+
+/// parse_idempotency_key accepts the key as a structured field string, `"key"`, or as a bare
+/// token.
+fn parse_idempotency_key(value: &HeaderValue) -> Result<String, ApiError> {
+    let value = match value.to_str() {
+        Ok(value) => value.trim(),
+        Err(_) => {
+            return Err(ApiError::invalid_argument(
+                "Idempotency-Key must be visible ascii",
+            ));
+        }
+    };
+    let key = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .unwrap_or(value);
+    if key.is_empty() || key.len() > MAX_KEY_LENGTH {
+        return Err(ApiError::invalid_argument(format!(
+            "Idempotency-Key must be between 1 and {MAX_KEY_LENGTH} characters"
+        )));
+    }
+
+    Ok(key.to_string())
+}
+
+/// ledger_idempotency_key derives the key forwarded to the ledger from the key of the
+/// principal, so principals sending the same key do not share a ledger transaction.
+pub fn ledger_idempotency_key(principal: &Principal, key: &str) -> String {
+    hex::encode(Sha256::digest(format!("{}:{key}", principal.subject)))
+}
+
+/// idempotency replays the first response of POST requests carrying an `Idempotency-Key` to
+/// the principal which sent it, following the semantics of the IETF idempotency-key draft: a
+/// key still in flight is a conflict and a key reused with a different body is unprocessable.
+/// Server errors are not cached so they can be retried.
+pub async fn idempotency(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    if request.method() != Method::POST {
+        return next.run(request).await;
+    }
+    let key = match request.headers().get(IDEMPOTENCY_KEY_HEADER) {
+        Some(value) => match parse_idempotency_key(value) {
+            Ok(key) => key,
+            Err(e) => return e.into_response(),
+        },
+        None => return next.run(request).await,
+    };
+
+    let (parts, body) = request.into_parts();
+    let body = match axum::body::to_bytes(body, MAX_BODY_BYTES).await {
+        Ok(body) => body,
+        Err(e) => {
+            return ApiError::new(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "invalid_argument",
+                e.to_string(),
+            )
+            .into_response();
+        }
+    };
+    let subject = parts
+        .extensions
+        .get::<Principal>()
+        .map(|principal| principal.subject.as_str())
+        .unwrap_or_default();
+    let scope = format!("{subject} {} {} {key}", parts.method, parts.uri.path());
+    let fingerprint: [u8; 32] = Sha256::digest(&body).into();
+
+    match state.idempotency.begin(&scope, fingerprint, Instant::now()) {
+        Lookup::Started => {}
+        Lookup::Replay(response) => return response.into_response(),
+        Lookup::InFlight => {
+            return ApiError::new(
+                StatusCode::CONFLICT,
+                "idempotency_key_in_use",
+                "a request with this Idempotency-Key is still being processed",
+            )
+            .into_response();
+        }
+        Lookup::Mismatch => {
+            return ApiError::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "idempotency_key_reused",
+                "Idempotency-Key was already used with a different request body",
+            )
+            .into_response();
+        }
+    }
+
+    let mut request = Request::from_parts(parts, Body::from(body));
+    request.extensions_mut().insert(IdempotencyKey(key));
+    let response = next.run(request).await;
+    if response.status().is_server_error() {
+        state.idempotency.release(&scope);
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => {
+            state.idempotency.release(&scope);
+            tracing::error!("failed to buffer response: {e}");
+            return ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "internal",
+                "internal server error",
+            )
+            .into_response();
+        }
+    };
+    state.idempotency.complete(
+        &scope,
+        CachedResponse {
+            status: parts.status,
+            content_type: parts.headers.get(CONTENT_TYPE).cloned(),
+            body: body.clone(),
+        },
+        Instant::now(),
+    );
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::principal::AccountScope;
+
+    fn cached_response() -> CachedResponse {
+        CachedResponse {
+            status: StatusCode::CREATED,
+            content_type: None,
+            body: Bytes::from_static(b"{}"),
+        }
+    }
+
+    #[test]
+    fn test_begin() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            fingerprint: [u8; 32],
+            completed: bool,
+            elapsed: Duration,
+            expected: &'static str,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "in flight while the first request is processed",
+                fingerprint: [1; 32],
+                completed: false,
+                elapsed: Duration::from_secs(1),
+                expected: "in_flight",
+            },
+            TestCase {
+                name: "replay once the first request completed",
+                fingerprint: [1; 32],
+                completed: true,
+                elapsed: Duration::from_secs(1),
+                expected: "replay",
+            },
+            TestCase {
+                name: "mismatch when the body differs",
+                fingerprint: [2; 32],
+                completed: true,
+                elapsed: Duration::from_secs(1),
+                expected: "mismatch",
+            },
+            TestCase {
+                name: "start again once the ttl passed",
+                fingerprint: [2; 32],
+                completed: true,
+                elapsed: Duration::from_secs(3601),
+                expected: "started",
+            },
+            TestCase {
+                name: "start again once an abandoned request timed out",
+                fingerprint: [1; 32],
+                completed: false,
+                elapsed: IN_FLIGHT_TIMEOUT,
+                expected: "started",
+            },
+        ];
+
+        for test_case in test_cases {
+            // arrange
+            let cache = IdempotencyCache::new(Duration::from_secs(3600), 10);
+            let now = Instant::now();
+            assert!(matches!(
+                cache.begin("POST /v1/transactions key", [1; 32], now),
+                Lookup::Started
+            ));
+            if test_case.completed {
+                cache.complete("POST /v1/transactions key", cached_response(), now);
+            }
+
+            // act
+            let lookup = cache.begin(
+                "POST /v1/transactions key",
+                test_case.fingerprint,
+                now + test_case.elapsed,
+            );
+
+            // assert
+            let lookup = match lookup {
+                Lookup::Started => "started",
+                Lookup::InFlight => "in_flight",
+                Lookup::Mismatch => "mismatch",
+                Lookup::Replay(_) => "replay",
+            };
+            assert_eq!(lookup, test_case.expected, "{}", test_case.name);
+        }
+    }
+
+    #[test]
+    fn successfully_derive_ledger_idempotency_key_per_principal() {
+        // arrange
+        let merchant = Principal::new("merchant-a", AccountScope::All);
+        let other_merchant = Principal::new("merchant-b", AccountScope::All);
+
+        // act
+        let key = ledger_idempotency_key(&merchant, "order-1");
+
+        // assert
+        assert_eq!(key.len(), 64);
+        assert_eq!(key, ledger_idempotency_key(&merchant, "order-1"));
+        assert_ne!(key, ledger_idempotency_key(&other_merchant, "order-1"));
+        assert_ne!(key, ledger_idempotency_key(&merchant, "order-2"));
+    }
+
+    #[test]
+    fn successfully_retry_released_key() {
+        // arrange
+        let cache = IdempotencyCache::new(Duration::from_secs(3600), 10);
+        let now = Instant::now();
+        cache.begin("key", [1; 32], now);
+
+        // act
+        cache.release("key");
+
+        // assert
+        assert!(matches!(cache.begin("key", [2; 32], now), Lookup::Started));
+    }
+
+    #[test]
+    fn successfully_forget_keys_expiring_soonest_when_full() {
+        // arrange
+        let cache = IdempotencyCache::new(Duration::from_secs(3600), 2);
+        let now = Instant::now();
+        cache.begin("first", [1; 32], now);
+        cache.complete("first", cached_response(), now);
+        cache.begin("second", [1; 32], now + Duration::from_secs(1));
+
+        // act
+        let lookup = cache.begin("third", [1; 32], now + Duration::from_secs(2));
+
+        // assert - the in flight key expires before the completed one
+        assert!(matches!(lookup, Lookup::Started));
+        let entries = cache.entries.lock().unwrap();
+        assert_eq!(entries.keys.len(), 2);
+        assert!(entries.keys.contains_key("first"));
+        assert!(!entries.keys.contains_key("second"));
+    }
+
+    #[test]
+    fn successfully_drop_expired_keys_without_dropping_completed_ones() {
+        // arrange
+        let cache = IdempotencyCache::new(Duration::from_secs(3600), 10);
+        let now = Instant::now();
+        cache.begin("completed", [1; 32], now);
+        cache.complete("completed", cached_response(), now);
+        cache.begin("abandoned", [1; 32], now);
+
+        // act
+        cache.begin("other", [1; 32], now + IN_FLIGHT_TIMEOUT);
+
+        // assert
+        let entries = cache.entries.lock().unwrap();
+        assert!(entries.keys.contains_key("completed"));
+        assert!(!entries.keys.contains_key("abandoned"));
+        assert!(entries.keys.contains_key("other"));
+    }
+
+    #[test]
+    fn test_parse_idempotency_key() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            value: HeaderValue,
+            expected: Option<&'static str>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "successfully parse structured field string",
+                value: HeaderValue::from_static("\"8e03978e-40d5-43e8-bc93-6894a57f9324\""),
+                expected: Some("8e03978e-40d5-43e8-bc93-6894a57f9324"),
+            },
+            TestCase {
+                name: "successfully parse bare token",
+                value: HeaderValue::from_static("order-1"),
+                expected: Some("order-1"),
+            },
+            TestCase {
+                name: "error when key is empty",
+                value: HeaderValue::from_static("\"\""),
+                expected: None,
+            },
+            TestCase {
+                name: "error when key is too long",
+                value: HeaderValue::from_str(&"k".repeat(MAX_KEY_LENGTH + 1)).unwrap(),
+                expected: None,
+            },
+        ];
+
+        for test_case in test_cases {
+            let key = parse_idempotency_key(&test_case.value);
+            match test_case.expected {
+                Some(expected) => assert_eq!(key.unwrap(), expected, "{}", test_case.name),
+                None => assert!(key.is_err(), "{}", test_case.name),
+            }
+        }
+    }
+}
@@ -0,0 +1,191 @@
+mod accounts;
+mod error;
+pub mod idempotency;
+pub mod openapi;
+mod parsers;
+pub mod rate_limit;
+mod schedules;
+mod transactions;
+mod webhooks;
+
+pub use error::{ApiError, parse_code_to_http};
+
+use crate::api::idempotency::IdempotencyCache;
+use crate::api::openapi::ApiDoc;
+use crate::api::rate_limit::RateLimiter;
+use crate::auth::{self, Authenticator};
+use crate::domain::rate_limit::{RateLimit, RateLimits};
+use crate::repo::InMemoryRateLimitStore;
+use crate::{
+    DEFAULT_IDEMPOTENCY_MAX_ENTRIES, DEFAULT_IDEMPOTENCY_TTL_SECONDS, DEFAULT_RATE_LIMIT_REQUESTS,
+    DEFAULT_RATE_LIMIT_WINDOW_SECONDS,
+};
+use accounts_proto::accounts_v1::accounts_client::AccountsClient;
+use axum::Router;
+use axum::extract::rejection::JsonRejection;
+use axum::extract::{FromRequest, Request};
+use axum::middleware;
+use axum::routing::{delete, get, post};
+use ledger_proto::ledger_v1::ledger_client::LedgerClient;
+use std::sync::Arc;
+use std::time::Duration;
+use tonic::transport::Channel;
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
+use webhook_dispatcher::domain::endpoint::UrlPolicy;
+use webhook_dispatcher::repo::WebhookRepository;
+
+/// AppState holds the grpc clients the handlers forward to. The clients share their channel,
+/// so cloning them per request is cheap.
+#[derive(Debug, Clone)]
+pub struct AppState {
+    pub accounts: AccountsClient<Channel>,
+    pub ledger: LedgerClient<Channel>,
+    pub authenticator: Arc<Authenticator>,
+    pub idempotency: Arc<IdempotencyCache>,
+    pub rate_limiter: Arc<RateLimiter>,
+    /// webhooks holds the merchant webhook endpoints, the webhook routes answer 501 without.
+    pub webhooks: Option<Arc<dyn WebhookRepository>>,
+    /// webhook_url_policy decides which urls endpoints may be registered with.
+    pub webhook_url_policy: UrlPolicy,
+}
+
+impl AppState {
+    pub fn new(
+        accounts: AccountsClient<Channel>,
+        ledger: LedgerClient<Channel>,
+        authenticator: Authenticator,
+    ) -> Self {
+        Self {
+            accounts,
+            ledger,
+            authenticator: Arc::new(authenticator),
+            idempotency: Arc::new(IdempotencyCache::new(
+                Duration::from_secs(DEFAULT_IDEMPOTENCY_TTL_SECONDS),
+                DEFAULT_IDEMPOTENCY_MAX_ENTRIES,
+            )),
+            rate_limiter: Arc::new(RateLimiter::new(
+                RateLimits::new(RateLimit::new(
+                    DEFAULT_RATE_LIMIT_REQUESTS,
+                    Duration::from_secs(DEFAULT_RATE_LIMIT_WINDOW_SECONDS),
+                )),
+                Arc::new(InMemoryRateLimitStore::default()),
+            )),
+            webhooks: None,
+            webhook_url_policy: UrlPolicy::Public,
+        }
+    }
+
+    pub fn with_idempotency(mut self, ttl: Duration, max_entries: usize) -> Self {
+        self.idempotency = Arc::new(IdempotencyCache::new(ttl, max_entries));
+        self
+    }
+
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Arc::new(rate_limiter);
+        self
+    }
+
+    pub fn with_webhooks(
+        mut self,
+        webhooks: impl WebhookRepository,
+        url_policy: UrlPolicy,
+    ) -> Self {
+        self.webhooks = Some(Arc::new(webhooks));
+        self.webhook_url_policy = url_policy;
+        self
+    }
+}
+
+/// ApiJson is `axum::Json` rejecting malformed bodies with an `ApiError`.
+pub struct ApiJson<T>(pub T);
+
+impl<S, T> FromRequest<S> for ApiJson<T>
+where
+    axum::Json<T>: FromRequest<S, Rejection = JsonRejection>,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        match axum::Json::<T>::from_request(request, state).await {
+            Ok(axum::Json(value)) => Ok(Self(value)),
+            Err(rejection) => Err(rejection.into()),
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "health",
+    summary = "Check the api is up",
+    security(()),
+    responses((status = 200, description = "The api is up"))
+)]
+async fn health_check() {}
+
+async fn fallback() -> ApiError {
+    ApiError::not_found("route not found")
+}
+
+/// router returns the v1 HTTP API. Every v1 route needs credentials, requests are
+/// authenticated before they are rate limited and idempotency keys are looked up, so both
+/// are scoped to the principal.
+/// The OpenAPI document and its docs UI at `/docs` are public.
+pub fn router(state: AppState) -> Router {
+    let v1 = Router::new()
+        .route("/v1/accounts", post(accounts::create_account))
+        .route("/v1/accounts/{id}", get(accounts::get_account))
+        .route("/v1/transactions", post(transactions::create_transaction))
+        .route("/v1/transactions/{id}", get(transactions::get_transaction))
+        .route("/v1/schedules", post(schedules::create_schedule))
+        .route("/v1/schedules/{id}", get(schedules::get_schedule))
+        .route(
+            "/v1/schedules/{id}/runs",
+            get(schedules::list_schedule_runs),
+        )
+        .route("/v1/schedules/{id}/pause", post(schedules::pause_schedule))
+        .route(
+            "/v1/schedules/{id}/resume",
+            post(schedules::resume_schedule),
+        )
+        .route(
+            "/v1/schedules/{id}/cancel",
+            post(schedules::cancel_schedule),
+        )
+        .route(
+            "/v1/schedules/{id}/skip",
+            post(schedules::skip_schedule_run),
+        )
+        .route(
+            "/v1/webhooks",
+            post(webhooks::create_webhook).get(webhooks::list_webhooks),
+        )
+        .route("/v1/webhooks/{id}", delete(webhooks::delete_webhook))
+        .route("/v1/webhooks/deliveries", get(webhooks::list_deliveries))
+        .route("/v1/webhooks/deliveries/{id}", get(webhooks::get_delivery))
+        .route(
+            "/v1/webhooks/deliveries/{id}/redeliver",
+            post(webhooks::redeliver_delivery),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            idempotency::idempotency,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit::rate_limit,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth::authenticate,
+        ));
+
+    Router::new()
+        .route("/health", get(health_check))
+        .merge(SwaggerUi::new("/docs").url("/openapi.json", ApiDoc::openapi()))
+        .merge(v1)
+        .fallback(fallback)
+        .with_state(state)
+}
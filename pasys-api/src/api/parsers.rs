@@ -0,0 +1,166 @@
+use crate::api::error::ApiError;
+use accounts_proto::accounts_v1;
+use ledger_proto::google::r#type::Money;
+use ledger_proto::ledger_v1;
+use prost_types::Timestamp;
+
+/// parse_enum_name turns a proto enum name such as `ACCOUNT_STATUS_ACTIVE` into the name used
+/// by the HTTP API, `active`.
+fn parse_enum_name(name: &str, prefix: &str) -> String {
+    name.trim_start_matches(prefix).to_lowercase()
+}
+
+pub fn parse_timestamp_from_proto(
+    timestamp: Option<Timestamp>,
+) -> Option<chrono::DateTime<chrono::Utc>> {
+    timestamp.and_then(|timestamp| {
+        chrono::DateTime::from_timestamp(timestamp.seconds, timestamp.nanos as u32)
+    })
+}
+
+pub fn parse_timestamp_to_proto(timestamp: chrono::DateTime<chrono::Utc>) -> Timestamp {
+    Timestamp {
+        seconds: timestamp.timestamp(),
+        nanos: timestamp.timestamp_subsec_nanos() as i32,
+    }
+}
+
+pub fn parse_account_type_from_proto(account_type: i32) -> String {
+    let account_type = accounts_v1::AccountType::try_from(account_type)
+        .unwrap_or(accounts_v1::AccountType::Unspecified);
+    parse_enum_name(account_type.as_str_name(), "ACCOUNT_TYPE_")
+}
+
+pub fn parse_account_status_from_proto(status: i32) -> String {
+    let status = accounts_v1::AccountStatus::try_from(status)
+        .unwrap_or(accounts_v1::AccountStatus::Unspecified);
+    parse_enum_name(status.as_str_name(), "ACCOUNT_STATUS_")
+}
+
+pub fn parse_transaction_status_from_proto(status: i32) -> String {
+    let status = ledger_v1::TransactionStatus::try_from(status)
+        .unwrap_or(ledger_v1::TransactionStatus::Unspecified);
+    parse_enum_name(status.as_str_name(), "TRANSACTION_STATUS_")
+}
+
+pub fn parse_schedule_status_from_proto(status: i32) -> String {
+    let status = ledger_v1::ScheduleStatus::try_from(status)
+        .unwrap_or(ledger_v1::ScheduleStatus::Unspecified);
+    parse_enum_name(status.as_str_name(), "SCHEDULE_STATUS_")
+}
+
+pub fn parse_schedule_run_status_from_proto(status: i32) -> String {
+    let status = ledger_v1::ScheduleRunStatus::try_from(status)
+        .unwrap_or(ledger_v1::ScheduleRunStatus::Unspecified);
+    parse_enum_name(status.as_str_name(), "SCHEDULE_RUN_STATUS_")
+}
+
+/// parse_amount_to_proto rejects currencies outside ISO 4217.
+pub fn parse_amount_to_proto(amount_minor: i64, currency: &str) -> Result<Money, ApiError> {
+    match common::money::Money::from_minor(amount_minor, currency) {
+        Ok(money) => {
+            let (units, nanos) = money.to_units_nanos();
+            Ok(Money {
+                currency_code: money.currency().code().to_string(),
+                units,
+                nanos,
+            })
+        }
+        Err(e) => Err(ApiError::invalid_argument(format!("invalid amount: {e}"))),
+    }
+}
+
+/// parse_amount_from_proto returns the amount in minor units and its currency, zero when the
+/// amount is missing. The ledger only answers amounts in ISO 4217 currencies, anything else is
+/// an internal error.
+pub fn parse_amount_from_proto(amount: Option<Money>) -> Result<(i64, String), ApiError> {
+    match amount {
+        Some(amount) => {
+            match common::money::Money::from_units_nanos(
+                amount.units,
+                amount.nanos,
+                &amount.currency_code,
+            ) {
+                Ok(money) => Ok((money.amount_minor(), money.currency().code().to_string())),
+                Err(e) => Err(tonic::Status::internal(format!(
+                    "ledger service returned an invalid amount: {e}"
+                ))
+                .into()),
+            }
+        }
+        None => Ok((0, String::new())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_enum_from_proto() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            parsed: String,
+            expected: &'static str,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "successfully parse account type",
+                parsed: parse_account_type_from_proto(accounts_v1::AccountType::Merchant as i32),
+                expected: "merchant",
+            },
+            TestCase {
+                name: "successfully parse account status",
+                parsed: parse_account_status_from_proto(accounts_v1::AccountStatus::Frozen as i32),
+                expected: "frozen",
+            },
+            TestCase {
+                name: "successfully parse transaction status",
+                parsed: parse_transaction_status_from_proto(
+                    ledger_v1::TransactionStatus::Refunded as i32,
+                ),
+                expected: "refunded",
+            },
+            TestCase {
+                name: "unspecified when value is unknown",
+                parsed: parse_transaction_status_from_proto(42),
+                expected: "unspecified",
+            },
+        ];
+
+        for test_case in test_cases {
+            assert_eq!(test_case.parsed, test_case.expected, "{}", test_case.name);
+        }
+    }
+
+    #[test]
+    fn successfully_round_trip_amount() {
+        // arrange
+        let amount = parse_amount_to_proto(1050, "usd").unwrap();
+
+        // act
+        let (amount_minor, currency) = parse_amount_from_proto(Some(amount)).unwrap();
+
+        // assert
+        assert_eq!(amount_minor, 1050);
+        assert_eq!(currency, "USD");
+    }
+
+    #[test]
+    fn successfully_convert_amount_by_currency_exponent() {
+        // act
+        let yen = parse_amount_to_proto(1050, "JPY").unwrap();
+        let dinar = parse_amount_to_proto(1005, "KWD").unwrap();
+        let unknown = parse_amount_to_proto(1050, "XYZ");
+
+        // assert
+        assert_eq!((yen.units, yen.nanos), (1050, 0));
+        assert_eq!((dinar.units, dinar.nanos), (1, 5_000_000));
+        assert_eq!(
+            unknown.unwrap_err().status,
+            axum::http::StatusCode::BAD_REQUEST
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use crate::api::AppState;
+use crate::api::error::ApiError;
+use crate::domain::principal::Principal;
+use crate::domain::rate_limit::{Decision, RateLimits};
+use crate::repo::RateLimitStore;
+use axum::extract::{MatchedPath, Request, State};
+use axum::http::header::RETRY_AFTER;
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use std::sync::Arc;
+
+pub const RATE_LIMIT_LIMIT_HEADER: &str = "ratelimit-limit";
+pub const RATE_LIMIT_REMAINING_HEADER: &str = "ratelimit-remaining";
+pub const RATE_LIMIT_RESET_HEADER: &str = "ratelimit-reset";
+pub const RATE_LIMIT_POLICY_HEADER: &str = "ratelimit-policy";
+
+/// RateLimiter limits every principal per route, so a merchant flooding one route neither
+/// starves other merchants nor its own other routes.
+#[derive(Clone)]
+pub struct RateLimiter {
+    limits: RateLimits,
+    store: Arc<dyn RateLimitStore>,
+}
+
+impl std::fmt::Debug for RateLimiter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RateLimiter")
+            .field("limits", &self.limits)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RateLimiter {
+    pub fn new(limits: RateLimits, store: Arc<dyn RateLimitStore>) -> Self {
+        Self { limits, store }
+    }
+}
+
+fn insert_header(headers: &mut HeaderMap, name: &'static str, value: String) {
+    if let Ok(value) = HeaderValue::from_str(value.as_str()) {
+        headers.insert(name, value);
+    }
+}
+
+/// insert_rate_limit_headers reports the limit following the IETF RateLimit header fields
+/// draft, e.g. `RateLimit-Policy: 600;w=60`.
+fn insert_rate_limit_headers(headers: &mut HeaderMap, decision: &Decision) {
+    let limit = decision.limit;
+    insert_header(headers, RATE_LIMIT_LIMIT_HEADER, limit.requests.to_string());
+    insert_header(
+        headers,
+        RATE_LIMIT_REMAINING_HEADER,
+        decision.remaining.to_string(),
+    );
+    insert_header(
+        headers,
+        RATE_LIMIT_RESET_HEADER,
+        decision.reset_seconds.to_string(),
+    );
+    insert_header(
+        headers,
+        RATE_LIMIT_POLICY_HEADER,
+        format!("{};w={}", limit.requests, limit.window.as_secs()),
+    );
+    if let Some(retry_after) = decision.retry_after_seconds {
+        insert_header(headers, RETRY_AFTER.as_str(), retry_after.to_string());
+    }
+}
+
+/// rate_limit takes a token from the bucket of the principal and route before the request
+/// is handled. Requests are let through when the store fails, the limiter protecting the
+/// ledger should not take the api down with it.
+pub async fn rate_limit(
+    State(state): State<AppState>,
+    matched_path: Option<MatchedPath>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let route = format!(
+        "{} {}",
+        request.method(),
+        matched_path
+            .as_ref()
+            .map(MatchedPath::as_str)
+            .unwrap_or(request.uri().path())
+    );
+    let subject = request
+        .extensions()
+        .get::<Principal>()
+        .map(|principal| principal.subject.as_str())
+        .unwrap_or_default();
+    let limit = state.rate_limiter.limits.limit_for(route.as_str());
+    let key = format!("{subject} {route}");
+
+    let decision = match state
+        .rate_limiter
+        .store
+        .take_token(key.as_str(), &limit, chrono::Utc::now())
+        .await
+    {
+        Ok(decision) => decision,
+        Err(e) => {
+            tracing::error!("failed to take rate limit token: {e}");
+            return next.run(request).await;
+        }
+    };
+
+    let mut response = match decision.allowed {
+        true => next.run(request).await,
+        false => ApiError::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "resource_exhausted",
+            format!(
+                "rate limit of {} requests per {} seconds exceeded",
+                limit.requests,
+                limit.window.as_secs()
+            ),
+        )
+        .into_response(),
+    };
+    insert_rate_limit_headers(response.headers_mut(), &decision);
+
+    response
+}
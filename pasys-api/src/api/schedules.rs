@@ -0,0 +1,415 @@
+use crate::api::error::{ApiError, ErrorBody};
+use crate::api::idempotency::{IdempotencyKey, IdempotencyKeyHeader, ledger_idempotency_key};
+use crate::api::parsers::{
+    parse_amount_from_proto, parse_amount_to_proto, parse_schedule_run_status_from_proto,
+    parse_schedule_status_from_proto, parse_timestamp_from_proto, parse_timestamp_to_proto,
+};
+use crate::api::transactions::authorize_transfer;
+use crate::api::{ApiJson, AppState};
+use crate::domain::principal::Principal;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use ledger_proto::ledger_v1;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateScheduleRequest {
+    pub debit_account_id: String,
+    pub credit_account_id: String,
+    /// amount_minor is transferred on every occurrence, in minor units of the currency.
+    pub amount_minor: i64,
+    #[schema(example = "USD")]
+    pub currency: String,
+    /// cron is a five field cron expression in UTC, exactly one of cron and interval_seconds
+    /// must be set.
+    #[schema(example = "0 9 1 * *")]
+    pub cron: Option<String>,
+    pub interval_seconds: Option<u64>,
+    /// starts_at defaults to now.
+    pub starts_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// ends_at is the last time an occurrence may run, the schedule runs until cancelled when
+    /// unset.
+    pub ends_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// idempotency_key defaults to the `Idempotency-Key` header. The ledger is sent a key derived
+    /// from it and the caller, like for transactions.
+    pub idempotency_key: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Schedule {
+    pub id: String,
+    pub debit_account_id: String,
+    pub credit_account_id: String,
+    pub amount_minor: i64,
+    #[schema(example = "USD")]
+    pub currency: String,
+    pub cron: Option<String>,
+    pub interval_seconds: Option<u64>,
+    pub starts_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub ends_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// next_run_at is unset once the schedule is cancelled or completed.
+    pub next_run_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[schema(example = "active")]
+    pub status: String,
+    /// idempotency_key is the key recorded by the ledger, derived from the caller's key.
+    pub idempotency_key: String,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ScheduleRun {
+    pub id: String,
+    pub schedule_id: String,
+    pub occurrence_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[schema(example = "succeeded")]
+    pub status: String,
+    /// transaction_id is the transaction a succeeded run created.
+    pub transaction_id: Option<String>,
+    /// error is why a failed run was not charged.
+    pub error: Option<String>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ScheduleRuns {
+    pub runs: Vec<ScheduleRun>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SkipScheduleRunResponse {
+    pub schedule: Schedule,
+    /// run is the skipped occurrence.
+    pub run: ScheduleRun,
+}
+
+impl TryFrom<ledger_v1::Schedule> for Schedule {
+    type Error = ApiError;
+
+    fn try_from(schedule: ledger_v1::Schedule) -> Result<Self, Self::Error> {
+        let (amount_minor, currency) = parse_amount_from_proto(schedule.amount)?;
+        Ok(Self {
+            id: schedule.id,
+            debit_account_id: schedule.debit_account_id,
+            credit_account_id: schedule.credit_account_id,
+            amount_minor,
+            currency,
+            cron: Some(schedule.cron).filter(|cron| !cron.is_empty()),
+            interval_seconds: Some(schedule.interval_seconds).filter(|interval| *interval > 0),
+            starts_at: parse_timestamp_from_proto(schedule.starts_at),
+            ends_at: parse_timestamp_from_proto(schedule.ends_at),
+            next_run_at: parse_timestamp_from_proto(schedule.next_run_at),
+            status: parse_schedule_status_from_proto(schedule.status),
+            idempotency_key: schedule.idempotency_key,
+            created_at: parse_timestamp_from_proto(schedule.created_at),
+            updated_at: parse_timestamp_from_proto(schedule.updated_at),
+        })
+    }
+}
+
+impl From<ledger_v1::ScheduleRun> for ScheduleRun {
+    fn from(run: ledger_v1::ScheduleRun) -> Self {
+        Self {
+            id: run.id,
+            schedule_id: run.schedule_id,
+            occurrence_at: parse_timestamp_from_proto(run.occurrence_at),
+            status: parse_schedule_run_status_from_proto(run.status),
+            transaction_id: Some(run.transaction_id).filter(|id| !id.is_empty()),
+            error: Some(run.error).filter(|error| !error.is_empty()),
+            created_at: parse_timestamp_from_proto(run.created_at),
+            updated_at: parse_timestamp_from_proto(run.updated_at),
+        }
+    }
+}
+
+/// get_owned_schedule answers schedules of other principals as not found.
+async fn get_owned_schedule(
+    state: &AppState,
+    principal: &Principal,
+    id: &str,
+) -> Result<ledger_v1::Schedule, ApiError> {
+    let response = state
+        .ledger
+        .clone()
+        .get_schedule(ledger_v1::GetScheduleRequest {
+            schedule_id: id.to_string(),
+        })
+        .await?
+        .into_inner();
+
+    match response.schedule {
+        Some(schedule)
+            if principal.can_access(schedule.debit_account_id.as_str())
+                || principal.can_access(schedule.credit_account_id.as_str()) =>
+        {
+            Ok(schedule)
+        }
+        Some(_) => Err(ApiError::not_found(format!("schedule {id} not found"))),
+        None => Err(tonic::Status::internal("ledger service returned no schedule").into()),
+    }
+}
+
+/// parse_schedule_from_proto fails when the ledger answered without a schedule.
+fn parse_schedule_from_proto(schedule: Option<ledger_v1::Schedule>) -> Result<Schedule, ApiError> {
+    match schedule {
+        Some(schedule) => schedule.try_into(),
+        None => Err(tonic::Status::internal("ledger service returned no schedule").into()),
+    }
+}
+
+/// create_schedule needs the debit account to be in the principal's scope, and a credited
+/// merchant account as well, like create_transaction.
+#[utoipa::path(
+    post,
+    path = "/v1/schedules",
+    tag = "schedules",
+    summary = "Schedule a recurring transfer",
+    description = "Transfers the amount on every occurrence of the cron expression or interval from `starts_at` until `ends_at`. Each occurrence creates one transaction, whose idempotency key is derived from the schedule and the occurrence.",
+    params(IdempotencyKeyHeader),
+    request_body = CreateScheduleRequest,
+    responses(
+        (status = 201, description = "Schedule created", body = Schedule),
+        (status = 400, description = "Invalid request", body = ErrorBody),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorBody),
+        (status = 403, description = "The debit account, or the credited merchant account, does not belong to the caller", body = ErrorBody),
+        (status = 409, description = "Idempotency key is in use", body = ErrorBody),
+        (status = 422, description = "Idempotency key was used with another body", body = ErrorBody),
+        (status = 429, description = "Rate limit exceeded, retry after `Retry-After` seconds", body = ErrorBody),
+    )
+)]
+pub async fn create_schedule(
+    State(state): State<AppState>,
+    Extension(principal): Extension<Principal>,
+    header_key: Option<Extension<IdempotencyKey>>,
+    ApiJson(request): ApiJson<CreateScheduleRequest>,
+) -> Result<(StatusCode, Json<Schedule>), ApiError> {
+    authorize_transfer(
+        &state,
+        &principal,
+        request.debit_account_id.as_str(),
+        request.credit_account_id.as_str(),
+    )
+    .await?;
+    let idempotency_key = match (request.idempotency_key, header_key) {
+        (Some(key), _) if !key.is_empty() => key,
+        (_, Some(Extension(IdempotencyKey(key)))) => key,
+        _ => {
+            return Err(ApiError::invalid_argument(
+                "Idempotency-Key header or idempotency_key must be set",
+            ));
+        }
+    };
+
+    let response = state
+        .ledger
+        .clone()
+        .create_schedule(ledger_v1::CreateScheduleRequest {
+            debit_account_id: request.debit_account_id,
+            credit_account_id: request.credit_account_id,
+            amount: Some(parse_amount_to_proto(
+                request.amount_minor,
+                request.currency.as_str(),
+            )?),
+            cron: request.cron.unwrap_or_default(),
+            interval_seconds: request.interval_seconds.unwrap_or_default(),
+            starts_at: request.starts_at.map(parse_timestamp_to_proto),
+            ends_at: request.ends_at.map(parse_timestamp_to_proto),
+            idempotency_key: ledger_idempotency_key(&principal, idempotency_key.as_str()),
+        })
+        .await?
+        .into_inner();
+
+    Ok((
+        StatusCode::CREATED,
+        Json(parse_schedule_from_proto(response.schedule)?),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/schedules/{id}",
+    tag = "schedules",
+    summary = "Get a schedule",
+    description = "Schedules of other callers are answered as not found.",
+    params(("id" = String, Path, description = "Schedule id")),
+    responses(
+        (status = 200, description = "Schedule found", body = Schedule),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorBody),
+        (status = 404, description = "Schedule not found", body = ErrorBody),
+        (status = 429, description = "Rate limit exceeded, retry after `Retry-After` seconds", body = ErrorBody),
+    )
+)]
+pub async fn get_schedule(
+    State(state): State<AppState>,
+    Extension(principal): Extension<Principal>,
+    Path(id): Path<String>,
+) -> Result<Json<Schedule>, ApiError> {
+    let schedule = get_owned_schedule(&state, &principal, &id).await?;
+
+    Ok(Json(schedule.try_into()?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/schedules/{id}/runs",
+    tag = "schedules",
+    summary = "List the runs of a schedule",
+    description = "Every occurrence run or skipped so far, latest first.",
+    params(("id" = String, Path, description = "Schedule id")),
+    responses(
+        (status = 200, description = "Runs of the schedule", body = ScheduleRuns),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorBody),
+        (status = 404, description = "Schedule not found", body = ErrorBody),
+        (status = 429, description = "Rate limit exceeded, retry after `Retry-After` seconds", body = ErrorBody),
+    )
+)]
+pub async fn list_schedule_runs(
+    State(state): State<AppState>,
+    Extension(principal): Extension<Principal>,
+    Path(id): Path<String>,
+) -> Result<Json<ScheduleRuns>, ApiError> {
+    get_owned_schedule(&state, &principal, &id).await?;
+    let response = state
+        .ledger
+        .clone()
+        .list_schedule_runs(ledger_v1::ListScheduleRunsRequest { schedule_id: id })
+        .await?
+        .into_inner();
+
+    Ok(Json(ScheduleRuns {
+        runs: response.runs.into_iter().map(ScheduleRun::from).collect(),
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/schedules/{id}/pause",
+    tag = "schedules",
+    summary = "Pause a schedule",
+    description = "An active schedule runs nothing until it is resumed.",
+    params(("id" = String, Path, description = "Schedule id")),
+    responses(
+        (status = 200, description = "Schedule paused", body = Schedule),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorBody),
+        (status = 404, description = "Schedule not found", body = ErrorBody),
+        (status = 400, description = "Schedule is not active", body = ErrorBody),
+        (status = 429, description = "Rate limit exceeded, retry after `Retry-After` seconds", body = ErrorBody),
+    )
+)]
+pub async fn pause_schedule(
+    State(state): State<AppState>,
+    Extension(principal): Extension<Principal>,
+    Path(id): Path<String>,
+) -> Result<Json<Schedule>, ApiError> {
+    get_owned_schedule(&state, &principal, &id).await?;
+    let response = state
+        .ledger
+        .clone()
+        .pause_schedule(ledger_v1::ScheduleActionRequest { schedule_id: id })
+        .await?
+        .into_inner();
+
+    Ok(Json(parse_schedule_from_proto(response.schedule)?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/schedules/{id}/resume",
+    tag = "schedules",
+    summary = "Resume a schedule",
+    description = "A paused schedule runs again from its next occurrence, occurrences missed while it was paused are not run.",
+    params(("id" = String, Path, description = "Schedule id")),
+    responses(
+        (status = 200, description = "Schedule resumed", body = Schedule),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorBody),
+        (status = 404, description = "Schedule not found", body = ErrorBody),
+        (status = 400, description = "Schedule is not paused", body = ErrorBody),
+        (status = 429, description = "Rate limit exceeded, retry after `Retry-After` seconds", body = ErrorBody),
+    )
+)]
+pub async fn resume_schedule(
+    State(state): State<AppState>,
+    Extension(principal): Extension<Principal>,
+    Path(id): Path<String>,
+) -> Result<Json<Schedule>, ApiError> {
+    get_owned_schedule(&state, &principal, &id).await?;
+    let response = state
+        .ledger
+        .clone()
+        .resume_schedule(ledger_v1::ScheduleActionRequest { schedule_id: id })
+        .await?
+        .into_inner();
+
+    Ok(Json(parse_schedule_from_proto(response.schedule)?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/schedules/{id}/cancel",
+    tag = "schedules",
+    summary = "Cancel a schedule",
+    description = "An active or paused schedule stops for good.",
+    params(("id" = String, Path, description = "Schedule id")),
+    responses(
+        (status = 200, description = "Schedule cancelled", body = Schedule),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorBody),
+        (status = 404, description = "Schedule not found", body = ErrorBody),
+        (status = 400, description = "Schedule is cancelled or completed", body = ErrorBody),
+        (status = 429, description = "Rate limit exceeded, retry after `Retry-After` seconds", body = ErrorBody),
+    )
+)]
+pub async fn cancel_schedule(
+    State(state): State<AppState>,
+    Extension(principal): Extension<Principal>,
+    Path(id): Path<String>,
+) -> Result<Json<Schedule>, ApiError> {
+    get_owned_schedule(&state, &principal, &id).await?;
+    let response = state
+        .ledger
+        .clone()
+        .cancel_schedule(ledger_v1::ScheduleActionRequest { schedule_id: id })
+        .await?
+        .into_inner();
+
+    Ok(Json(parse_schedule_from_proto(response.schedule)?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/schedules/{id}/skip",
+    tag = "schedules",
+    summary = "Skip the next run of a schedule",
+    description = "The next occurrence is recorded as a skipped run and not charged.",
+    params(("id" = String, Path, description = "Schedule id")),
+    responses(
+        (status = 200, description = "Next run skipped", body = SkipScheduleRunResponse),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorBody),
+        (status = 404, description = "Schedule not found", body = ErrorBody),
+        (status = 400, description = "Schedule is cancelled or completed", body = ErrorBody),
+        (status = 429, description = "Rate limit exceeded, retry after `Retry-After` seconds", body = ErrorBody),
+    )
+)]
+pub async fn skip_schedule_run(
+    State(state): State<AppState>,
+    Extension(principal): Extension<Principal>,
+    Path(id): Path<String>,
+) -> Result<Json<SkipScheduleRunResponse>, ApiError> {
+    get_owned_schedule(&state, &principal, &id).await?;
+    let response = state
+        .ledger
+        .clone()
+        .skip_schedule_run(ledger_v1::ScheduleActionRequest { schedule_id: id })
+        .await?
+        .into_inner();
+
+    match response.run {
+        Some(run) => Ok(Json(SkipScheduleRunResponse {
+            schedule: parse_schedule_from_proto(response.schedule)?,
+            run: run.into(),
+        })),
+        None => Err(tonic::Status::internal("ledger service returned no run").into()),
+    }
+}
@@ -0,0 +1,269 @@
+use crate::api::error::{ApiError, ErrorBody};
+use crate::api::idempotency::{IdempotencyKey, IdempotencyKeyHeader, ledger_idempotency_key};
+use crate::api::parsers::{
+    parse_amount_from_proto, parse_amount_to_proto, parse_timestamp_from_proto,
+    parse_timestamp_to_proto, parse_transaction_status_from_proto,
+};
+use crate::api::{ApiJson, AppState};
+use crate::domain::principal::Principal;
+use accounts_proto::accounts_v1;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use ledger_proto::ledger_v1;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateTransactionRequest {
+    pub debit_account_id: String,
+    pub credit_account_id: String,
+    /// amount_minor is the amount in minor units of the currency, e.g. 1050 for 10.50 USD or
+    /// 1050 JPY.
+    pub amount_minor: i64,
+    #[schema(example = "USD")]
+    pub currency: String,
+    /// credit_currency is the currency the credited account is paid in, `currency` when unset.
+    /// The amount is converted at the ledger's current rate.
+    #[schema(example = "EUR")]
+    pub credit_currency: Option<String>,
+    /// idempotency_key defaults to the `Idempotency-Key` header. The ledger is sent a key derived
+    /// from it and the caller, so keys of different callers never collide.
+    pub idempotency_key: Option<String>,
+    /// request_timestamp defaults to the time the ledger receives the request.
+    pub request_timestamp: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreateTransactionResponse {
+    pub id: String,
+    #[schema(example = "pending")]
+    pub status: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Transaction {
+    pub id: String,
+    pub debit_account_id: String,
+    pub credit_account_id: String,
+    pub amount_minor: i64,
+    #[schema(example = "USD")]
+    pub currency: String,
+    #[schema(example = "pending")]
+    pub status: String,
+    /// idempotency_key is the key recorded by the ledger, derived from the caller's key.
+    pub idempotency_key: String,
+    pub request_timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// fx is the rate snapshot of a cross-currency transaction.
+    pub fx: Option<FxConversion>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FxConversion {
+    /// credit_amount_minor is the converted amount paid to the credited account.
+    pub credit_amount_minor: i64,
+    #[schema(example = "EUR")]
+    pub credit_currency: String,
+    /// rate is the quoted price of one unit of `currency` in `credit_currency`.
+    #[schema(example = "0.9216")]
+    pub rate: String,
+    /// applied_rate is the rate after the spread, the amount was converted at.
+    pub applied_rate: String,
+    pub spread_bps: u32,
+    #[schema(example = "half_up")]
+    pub rounding_mode: String,
+    pub rate_source: String,
+    pub rate_as_of: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl TryFrom<ledger_v1::FxConversion> for FxConversion {
+    type Error = ApiError;
+
+    fn try_from(fx: ledger_v1::FxConversion) -> Result<Self, Self::Error> {
+        let (credit_amount_minor, credit_currency) = parse_amount_from_proto(fx.credit_amount)?;
+        Ok(Self {
+            credit_amount_minor,
+            credit_currency,
+            rate: fx.rate,
+            applied_rate: fx.applied_rate,
+            spread_bps: fx.spread_bps,
+            rounding_mode: fx.rounding_mode,
+            rate_source: fx.rate_source,
+            rate_as_of: parse_timestamp_from_proto(fx.rate_as_of),
+        })
+    }
+}
+
+impl TryFrom<ledger_v1::Transaction> for Transaction {
+    type Error = ApiError;
+
+    fn try_from(transaction: ledger_v1::Transaction) -> Result<Self, Self::Error> {
+        let (amount_minor, currency) = parse_amount_from_proto(transaction.amount)?;
+        Ok(Self {
+            id: transaction.id,
+            debit_account_id: transaction.debit_account_id,
+            credit_account_id: transaction.credit_account_id,
+            amount_minor,
+            currency,
+            status: parse_transaction_status_from_proto(transaction.status),
+            idempotency_key: transaction.idempotency_key,
+            request_timestamp: parse_timestamp_from_proto(transaction.request_timestamp),
+            created_at: parse_timestamp_from_proto(transaction.created_at),
+            updated_at: parse_timestamp_from_proto(transaction.updated_at),
+            fx: transaction.fx.map(FxConversion::try_from).transpose()?,
+        })
+    }
+}
+
+/// create_transaction needs the debit account to be in the principal's scope, and the credit
+/// account as well when it belongs to a merchant.
+#[utoipa::path(
+    post,
+    path = "/v1/transactions",
+    tag = "transactions",
+    summary = "Create a transaction",
+    description = "Moves money from the debit account, which must be in the caller's scope, to the credit account. Paying a merchant account needs it in the caller's scope as well.",
+    params(IdempotencyKeyHeader),
+    request_body = CreateTransactionRequest,
+    responses(
+        (status = 201, description = "Transaction created", body = CreateTransactionResponse),
+        (status = 400, description = "Invalid request", body = ErrorBody),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorBody),
+        (status = 403, description = "The debit account, or the credited merchant account, does not belong to the caller", body = ErrorBody),
+        (status = 409, description = "Idempotency key is in use", body = ErrorBody),
+        (status = 422, description = "Idempotency key was used with another body", body = ErrorBody),
+        (status = 429, description = "Rate limit exceeded, retry after `Retry-After` seconds", body = ErrorBody),
+    )
+)]
+pub async fn create_transaction(
+    State(state): State<AppState>,
+    Extension(principal): Extension<Principal>,
+    header_key: Option<Extension<IdempotencyKey>>,
+    ApiJson(request): ApiJson<CreateTransactionRequest>,
+) -> Result<(StatusCode, Json<CreateTransactionResponse>), ApiError> {
+    authorize_transfer(
+        &state,
+        &principal,
+        request.debit_account_id.as_str(),
+        request.credit_account_id.as_str(),
+    )
+    .await?;
+    let idempotency_key = match (request.idempotency_key, header_key) {
+        (Some(key), _) if !key.is_empty() => key,
+        (_, Some(Extension(IdempotencyKey(key)))) => key,
+        _ => {
+            return Err(ApiError::invalid_argument(
+                "Idempotency-Key header or idempotency_key must be set",
+            ));
+        }
+    };
+
+    let response = state
+        .ledger
+        .clone()
+        .create_transaction(ledger_v1::CreateTransactionRequest {
+            id: String::new(),
+            debit_account_id: request.debit_account_id,
+            credit_account_id: request.credit_account_id,
+            amount: Some(parse_amount_to_proto(
+                request.amount_minor,
+                request.currency.as_str(),
+            )?),
+            request_timestamp: request.request_timestamp.map(parse_timestamp_to_proto),
+            idempotency_key: ledger_idempotency_key(&principal, idempotency_key.as_str()),
+            credit_currency: request.credit_currency.unwrap_or_default().to_uppercase(),
+        })
+        .await?
+        .into_inner();
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateTransactionResponse {
+            id: response.transaction_id,
+            status: parse_transaction_status_from_proto(response.transaction_status),
+        }),
+    ))
+}
+
+/// authorize_transfer checks the principal may move money out of the debit account. Paying
+/// a merchant account out of the principal's scope is refused, credit accounts unknown to the
+/// accounts service are left for the ledger to reject.
+pub(crate) async fn authorize_transfer(
+    state: &AppState,
+    principal: &Principal,
+    debit_account_id: &str,
+    credit_account_id: &str,
+) -> Result<(), ApiError> {
+    if !principal.can_access(debit_account_id) {
+        return Err(ApiError::permission_denied(
+            "the debit account does not belong to the caller",
+        ));
+    }
+    if principal.can_access(credit_account_id) {
+        return Ok(());
+    }
+
+    let account = match state
+        .accounts
+        .clone()
+        .get_account(accounts_v1::GetAccountRequest {
+            account_id: credit_account_id.to_string(),
+        })
+        .await
+    {
+        Ok(response) => response.into_inner().account,
+        Err(status) if status.code() == tonic::Code::NotFound => None,
+        Err(status) => return Err(status.into()),
+    };
+    match account {
+        Some(account) if account.r#type == accounts_v1::AccountType::Merchant as i32 => {
+            Err(ApiError::permission_denied(
+                "the credited merchant account does not belong to the caller",
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// get_transaction answers transactions of other principals as not found.
+#[utoipa::path(
+    get,
+    path = "/v1/transactions/{id}",
+    tag = "transactions",
+    summary = "Get a transaction",
+    description = "Transactions of other callers are answered as not found.",
+    params(("id" = String, Path, description = "Transaction id")),
+    responses(
+        (status = 200, description = "Transaction found", body = Transaction),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorBody),
+        (status = 404, description = "Transaction not found", body = ErrorBody),
+        (status = 429, description = "Rate limit exceeded, retry after `Retry-After` seconds", body = ErrorBody),
+    )
+)]
+pub async fn get_transaction(
+    State(state): State<AppState>,
+    Extension(principal): Extension<Principal>,
+    Path(id): Path<String>,
+) -> Result<Json<Transaction>, ApiError> {
+    let response = state
+        .ledger
+        .clone()
+        .get_transaction(ledger_v1::GetTransactionRequest {
+            transaction_id: id.clone(),
+        })
+        .await?
+        .into_inner();
+
+    match response.transaction {
+        Some(transaction)
+            if principal.can_access(transaction.debit_account_id.as_str())
+                || principal.can_access(transaction.credit_account_id.as_str()) =>
+        {
+            Ok(Json(transaction.try_into()?))
+        }
+        Some(_) => Err(ApiError::not_found(format!("transaction {id} not found"))),
+        None => Err(tonic::Status::internal("ledger service returned no transaction").into()),
+    }
+}
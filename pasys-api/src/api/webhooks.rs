@@ -0,0 +1,439 @@
+use crate::api::error::{ApiError, ErrorBody};
+use crate::api::idempotency::IdempotencyKeyHeader;
+use crate::api::{ApiJson, AppState};
+use crate::domain::principal::{AccountScope, Principal};
+use axum::extract::rejection::QueryRejection;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+use webhook_dispatcher::domain::delivery::{self, DeliveryFilter};
+use webhook_dispatcher::domain::endpoint::{self, EndpointError};
+use webhook_dispatcher::repo::WebhookRepository;
+
+/// DEFAULT_DELIVERY_LIMIT and MAX_DELIVERY_LIMIT bound the delivery log returned per request.
+const DEFAULT_DELIVERY_LIMIT: i64 = 50;
+const MAX_DELIVERY_LIMIT: i64 = 500;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateWebhookRequest {
+    /// url events are POSTed to, an https url of a public host.
+    #[schema(example = "https://merchant.example/webhooks")]
+    pub url: String,
+    /// event_types to subscribe to, every event type when empty or missing.
+    #[serde(default)]
+    #[schema(example = json!(["transaction.settled", "transaction.refunded"]))]
+    pub event_types: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookEndpoint {
+    pub id: String,
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<endpoint::Endpoint> for WebhookEndpoint {
+    fn from(endpoint: endpoint::Endpoint) -> Self {
+        Self {
+            id: endpoint.id.to_string(),
+            url: endpoint.url,
+            event_types: endpoint.event_types,
+            created_at: endpoint.created_at,
+        }
+    }
+}
+
+/// CreatedWebhookEndpoint is the only response carrying the signing secret.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreatedWebhookEndpoint {
+    #[serde(flatten)]
+    pub endpoint: WebhookEndpoint,
+    #[schema(example = "whsec_...")]
+    pub secret: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookAttempt {
+    pub attempt: i32,
+    pub response_status: Option<i32>,
+    pub error: Option<String>,
+    pub attempted_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<delivery::Attempt> for WebhookAttempt {
+    fn from(attempt: delivery::Attempt) -> Self {
+        Self {
+            attempt: attempt.attempt,
+            response_status: attempt.response_status,
+            error: attempt.error,
+            attempted_at: attempt.attempted_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookDelivery {
+    pub id: String,
+    pub endpoint_id: String,
+    pub event_id: String,
+    #[schema(example = "transaction.settled")]
+    pub event_type: String,
+    /// payload is the body as it was signed and sent.
+    pub payload: String,
+    #[schema(example = "pending")]
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: chrono::DateTime<chrono::Utc>,
+    pub last_response_status: Option<i32>,
+    pub last_error: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    /// attempt_log is only set when a single delivery is requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attempt_log: Option<Vec<WebhookAttempt>>,
+}
+
+impl From<delivery::Delivery> for WebhookDelivery {
+    fn from(delivery: delivery::Delivery) -> Self {
+        Self {
+            id: delivery.id.to_string(),
+            endpoint_id: delivery.endpoint_id.to_string(),
+            event_id: delivery.event_id.to_string(),
+            event_type: delivery.event_type,
+            payload: delivery.payload,
+            status: delivery.status.as_ref().to_string(),
+            attempts: delivery.attempts,
+            next_attempt_at: delivery.next_attempt_at,
+            last_response_status: delivery.last_response_status,
+            last_error: delivery.last_error,
+            created_at: delivery.created_at,
+            updated_at: delivery.updated_at,
+            attempt_log: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DeliveriesQuery {
+    pub endpoint_id: Option<uuid::Uuid>,
+    /// status is one of `pending`, `succeeded` or `failed`.
+    pub status: Option<String>,
+    /// limit defaults to 50 and is capped at 500.
+    pub limit: Option<i64>,
+}
+
+/// parse_error_to_api_error maps invalid endpoints to 400, anything else, e.g. the api
+/// database being down, is a server error.
+fn parse_error_to_api_error(e: anyhow::Error) -> ApiError {
+    match e.downcast_ref::<EndpointError>() {
+        Some(_) => ApiError::invalid_argument(e.to_string()),
+        None => {
+            tracing::error!("failed to manage webhooks: {e}");
+            ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "internal",
+                "internal server error",
+            )
+        }
+    }
+}
+
+fn webhooks(state: &AppState) -> Result<Arc<dyn WebhookRepository>, ApiError> {
+    match &state.webhooks {
+        Some(webhooks) => Ok(webhooks.clone()),
+        None => Err(ApiError::new(
+            StatusCode::NOT_IMPLEMENTED,
+            "unimplemented",
+            "webhooks are not enabled",
+        )),
+    }
+}
+
+/// owns checks the principal registered the endpoint, operators own every endpoint.
+fn owns(principal: &Principal, endpoint: &endpoint::Endpoint) -> bool {
+    principal.is_unrestricted() || endpoint.principal == principal.subject
+}
+
+fn parse_id(id: &str, resource: &str) -> Result<uuid::Uuid, ApiError> {
+    match uuid::Uuid::parse_str(id) {
+        Ok(id) => Ok(id),
+        Err(_) => Err(ApiError::not_found(format!("{resource} {id} not found"))),
+    }
+}
+
+/// find_delivery answers deliveries to endpoints of other principals as not found.
+async fn find_delivery(
+    webhooks: &Arc<dyn WebhookRepository>,
+    principal: &Principal,
+    id: &str,
+) -> Result<delivery::Delivery, ApiError> {
+    let not_found = || ApiError::not_found(format!("webhook delivery {id} not found"));
+    let delivery = match webhooks
+        .get_delivery_by_id(parse_id(id, "webhook delivery")?)
+        .await
+        .map_err(parse_error_to_api_error)?
+    {
+        Some(delivery) => delivery,
+        None => return Err(not_found()),
+    };
+    match webhooks
+        .get_endpoint_by_id(delivery.endpoint_id)
+        .await
+        .map_err(parse_error_to_api_error)?
+    {
+        Some(endpoint) if owns(principal, &endpoint) => Ok(delivery),
+        _ => Err(not_found()),
+    }
+}
+
+/// create_webhook registers an endpoint for the accounts of the principal, events on accounts
+/// added to its scope later are not sent to it.
+#[utoipa::path(
+    post,
+    path = "/v1/webhooks",
+    tag = "webhooks",
+    summary = "Register a webhook endpoint",
+    description = "Events on the caller's accounts are POSTed to the url, signed with the returned secret in the `Pasys-Signature` header. The secret is only returned here.",
+    params(IdempotencyKeyHeader),
+    request_body = CreateWebhookRequest,
+    responses(
+        (status = 201, description = "Endpoint registered", body = CreatedWebhookEndpoint),
+        (status = 400, description = "Invalid url, a url which is not https on a public host, or an unknown event type", body = ErrorBody),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorBody),
+        (status = 409, description = "Idempotency key is in use", body = ErrorBody),
+        (status = 422, description = "Idempotency key was used with another body", body = ErrorBody),
+        (status = 429, description = "Rate limit exceeded, retry after `Retry-After` seconds", body = ErrorBody),
+        (status = 501, description = "Webhooks are not enabled", body = ErrorBody),
+    )
+)]
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    Extension(principal): Extension<Principal>,
+    ApiJson(request): ApiJson<CreateWebhookRequest>,
+) -> Result<(StatusCode, Json<CreatedWebhookEndpoint>), ApiError> {
+    let webhooks = webhooks(&state)?;
+    let (account_ids, all_accounts) = match principal.scope {
+        AccountScope::All => (vec![], true),
+        AccountScope::Only(account_ids) => (account_ids, false),
+    };
+    let endpoint = endpoint::Endpoint::new(
+        principal.subject.as_str(),
+        request.url.as_str(),
+        request.event_types,
+        account_ids,
+        all_accounts,
+        state.webhook_url_policy,
+    )
+    .map_err(parse_error_to_api_error)?;
+
+    let endpoint = webhooks
+        .create_endpoint(&endpoint)
+        .await
+        .map_err(parse_error_to_api_error)?;
+    let secret = endpoint.secret.clone();
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreatedWebhookEndpoint {
+            endpoint: endpoint.into(),
+            secret,
+        }),
+    ))
+}
+
+/// list_webhooks returns the enabled endpoints of the principal, of every principal for
+/// operators.
+#[utoipa::path(
+    get,
+    path = "/v1/webhooks",
+    tag = "webhooks",
+    summary = "List webhook endpoints",
+    description = "Operators see the endpoints of every principal.",
+    responses(
+        (status = 200, description = "Enabled endpoints, oldest first", body = Vec<WebhookEndpoint>),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorBody),
+        (status = 429, description = "Rate limit exceeded, retry after `Retry-After` seconds", body = ErrorBody),
+        (status = 501, description = "Webhooks are not enabled", body = ErrorBody),
+    )
+)]
+pub async fn list_webhooks(
+    State(state): State<AppState>,
+    Extension(principal): Extension<Principal>,
+) -> Result<Json<Vec<WebhookEndpoint>>, ApiError> {
+    let webhooks = webhooks(&state)?;
+    let owner = match principal.is_unrestricted() {
+        true => None,
+        false => Some(principal.subject.as_str()),
+    };
+    let endpoints = webhooks
+        .get_endpoints(owner)
+        .await
+        .map_err(parse_error_to_api_error)?;
+
+    Ok(Json(endpoints.into_iter().map(Into::into).collect()))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/v1/webhooks/{id}",
+    tag = "webhooks",
+    summary = "Disable a webhook endpoint",
+    description = "Pending deliveries to the endpoint are failed instead of sent.",
+    params(("id" = String, Path, description = "Endpoint id")),
+    responses(
+        (status = 204, description = "Endpoint disabled"),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorBody),
+        (status = 404, description = "Endpoint not found", body = ErrorBody),
+        (status = 429, description = "Rate limit exceeded, retry after `Retry-After` seconds", body = ErrorBody),
+        (status = 501, description = "Webhooks are not enabled", body = ErrorBody),
+    )
+)]
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    Extension(principal): Extension<Principal>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let webhooks = webhooks(&state)?;
+    let endpoint_id = parse_id(id.as_str(), "webhook endpoint")?;
+    let disabled = match webhooks
+        .get_endpoint_by_id(endpoint_id)
+        .await
+        .map_err(parse_error_to_api_error)?
+    {
+        Some(endpoint) if owns(&principal, &endpoint) => webhooks
+            .disable_endpoint(endpoint_id)
+            .await
+            .map_err(parse_error_to_api_error)?,
+        _ => false,
+    };
+
+    match disabled {
+        true => Ok(StatusCode::NO_CONTENT),
+        false => Err(ApiError::not_found(format!(
+            "webhook endpoint {id} not found"
+        ))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/webhooks/deliveries",
+    tag = "webhooks",
+    summary = "List webhook deliveries",
+    description = "The delivery log of the caller's endpoints, newest first.",
+    params(DeliveriesQuery),
+    responses(
+        (status = 200, description = "Deliveries, newest first", body = Vec<WebhookDelivery>),
+        (status = 400, description = "Invalid filter", body = ErrorBody),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorBody),
+        (status = 429, description = "Rate limit exceeded, retry after `Retry-After` seconds", body = ErrorBody),
+        (status = 501, description = "Webhooks are not enabled", body = ErrorBody),
+    )
+)]
+pub async fn list_deliveries(
+    State(state): State<AppState>,
+    Extension(principal): Extension<Principal>,
+    query: Result<Query<DeliveriesQuery>, QueryRejection>,
+) -> Result<Json<Vec<WebhookDelivery>>, ApiError> {
+    let webhooks = webhooks(&state)?;
+    let Query(query) = match query {
+        Ok(query) => query,
+        Err(rejection) => return Err(ApiError::invalid_argument(rejection.body_text())),
+    };
+    let status = match query.status.as_deref().map(str::parse::<delivery::Status>) {
+        Some(Ok(status)) => Some(status),
+        Some(Err(e)) => return Err(ApiError::invalid_argument(e.to_string())),
+        None => None,
+    };
+    let filter = DeliveryFilter {
+        principal: match principal.is_unrestricted() {
+            true => None,
+            false => Some(principal.subject.clone()),
+        },
+        endpoint_id: query.endpoint_id,
+        status,
+        limit: query
+            .limit
+            .unwrap_or(DEFAULT_DELIVERY_LIMIT)
+            .clamp(1, MAX_DELIVERY_LIMIT),
+    };
+    let deliveries = webhooks
+        .get_deliveries(&filter)
+        .await
+        .map_err(parse_error_to_api_error)?;
+
+    Ok(Json(deliveries.into_iter().map(Into::into).collect()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/webhooks/deliveries/{id}",
+    tag = "webhooks",
+    summary = "Get a webhook delivery",
+    description = "The delivery with the log of its attempts.",
+    params(("id" = String, Path, description = "Delivery id")),
+    responses(
+        (status = 200, description = "Delivery found", body = WebhookDelivery),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorBody),
+        (status = 404, description = "Delivery not found", body = ErrorBody),
+        (status = 429, description = "Rate limit exceeded, retry after `Retry-After` seconds", body = ErrorBody),
+        (status = 501, description = "Webhooks are not enabled", body = ErrorBody),
+    )
+)]
+pub async fn get_delivery(
+    State(state): State<AppState>,
+    Extension(principal): Extension<Principal>,
+    Path(id): Path<String>,
+) -> Result<Json<WebhookDelivery>, ApiError> {
+    let webhooks = webhooks(&state)?;
+    let delivery = find_delivery(&webhooks, &principal, id.as_str()).await?;
+    let attempts = webhooks
+        .get_attempts_by_delivery_id(delivery.id)
+        .await
+        .map_err(parse_error_to_api_error)?;
+
+    let mut delivery = WebhookDelivery::from(delivery);
+    delivery.attempt_log = Some(attempts.into_iter().map(Into::into).collect());
+    Ok(Json(delivery))
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/webhooks/deliveries/{id}/redeliver",
+    tag = "webhooks",
+    summary = "Redeliver a webhook",
+    description = "Queues the delivery to be sent again with a fresh set of attempts, e.g. after it failed while the endpoint was down.",
+    params(("id" = String, Path, description = "Delivery id"), IdempotencyKeyHeader),
+    responses(
+        (status = 202, description = "Delivery queued", body = WebhookDelivery),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorBody),
+        (status = 404, description = "Delivery not found", body = ErrorBody),
+        (status = 409, description = "Idempotency key is in use", body = ErrorBody),
+        (status = 429, description = "Rate limit exceeded, retry after `Retry-After` seconds", body = ErrorBody),
+        (status = 501, description = "Webhooks are not enabled", body = ErrorBody),
+    )
+)]
+pub async fn redeliver_delivery(
+    State(state): State<AppState>,
+    Extension(principal): Extension<Principal>,
+    Path(id): Path<String>,
+) -> Result<(StatusCode, Json<WebhookDelivery>), ApiError> {
+    let webhooks = webhooks(&state)?;
+    let delivery = find_delivery(&webhooks, &principal, id.as_str()).await?;
+
+    match webhooks
+        .redeliver_delivery(delivery.id, chrono::Utc::now())
+        .await
+        .map_err(parse_error_to_api_error)?
+    {
+        Some(delivery) => Ok((StatusCode::ACCEPTED, Json(delivery.into()))),
+        None => Err(ApiError::not_found(format!(
+            "webhook delivery {id} not found"
+        ))),
+    }
+}
@@ -0,0 +1,497 @@
+use crate::domain::principal::{AccountScope, AuthError, Principal};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use ring::signature;
+use serde::Deserialize;
+
+/// LEEWAY_SECONDS allows for clock skew between the issuer and the api.
+const LEEWAY_SECONDS: i64 = 60;
+
+#[derive(Debug, Deserialize)]
+struct Header {
+    alg: String,
+    kid: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawJwk {
+    kty: String,
+    kid: Option<String>,
+    alg: Option<String>,
+    crv: Option<String>,
+    n: Option<String>,
+    e: Option<String>,
+    x: Option<String>,
+    y: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawJwks {
+    keys: Vec<RawJwk>,
+}
+
+#[derive(Debug, Clone)]
+enum VerifyingKey {
+    Rs256 { n: Vec<u8>, e: Vec<u8> },
+    Es256 { point: Vec<u8> },
+    EdDsa { x: Vec<u8> },
+}
+
+impl VerifyingKey {
+    fn alg(&self) -> &'static str {
+        match self {
+            VerifyingKey::Rs256 { .. } => "RS256",
+            VerifyingKey::Es256 { .. } => "ES256",
+            VerifyingKey::EdDsa { .. } => "EdDSA",
+        }
+    }
+
+    fn verify(&self, message: &[u8], signature: &[u8]) -> bool {
+        let result = match self {
+            VerifyingKey::Rs256 { n, e } => signature::RsaPublicKeyComponents { n, e }.verify(
+                &signature::RSA_PKCS1_2048_8192_SHA256,
+                message,
+                signature,
+            ),
+            VerifyingKey::Es256 { point } => {
+                signature::UnparsedPublicKey::new(&signature::ECDSA_P256_SHA256_FIXED, point)
+                    .verify(message, signature)
+            }
+            VerifyingKey::EdDsa { x } => {
+                signature::UnparsedPublicKey::new(&signature::ED25519, x).verify(message, signature)
+            }
+        };
+        result.is_ok()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Jwk {
+    kid: Option<String>,
+    key: VerifyingKey,
+}
+
+fn decode_member(member: Option<String>, name: &str) -> anyhow::Result<Vec<u8>> {
+    let member = match member {
+        Some(member) => member,
+        None => anyhow::bail!("jwk is missing {name}"),
+    };
+    match URL_SAFE_NO_PAD.decode(member.as_bytes()) {
+        Ok(bytes) => Ok(bytes),
+        Err(e) => anyhow::bail!("jwk {name} is not base64url: {e}"),
+    }
+}
+
+impl TryFrom<RawJwk> for Jwk {
+    type Error = anyhow::Error;
+
+    fn try_from(jwk: RawJwk) -> anyhow::Result<Self> {
+        let key = match (jwk.kty.as_str(), jwk.crv.as_deref()) {
+            ("RSA", _) => VerifyingKey::Rs256 {
+                n: decode_member(jwk.n, "n")?,
+                e: decode_member(jwk.e, "e")?,
+            },
+            ("EC", Some("P-256")) => {
+                let mut point = vec![0x04];
+                point.extend(decode_member(jwk.x, "x")?);
+                point.extend(decode_member(jwk.y, "y")?);
+                VerifyingKey::Es256 { point }
+            }
+            ("OKP", Some("Ed25519")) => VerifyingKey::EdDsa {
+                x: decode_member(jwk.x, "x")?,
+            },
+            (kty, crv) => anyhow::bail!("unsupported jwk kty {kty} crv {crv:?}"),
+        };
+        match jwk.alg.as_deref() {
+            Some(alg) if alg != key.alg() => {
+                anyhow::bail!("jwk alg {alg} does not match its {} key", key.alg())
+            }
+            _ => {}
+        }
+
+        Ok(Self { kid: jwk.kid, key })
+    }
+}
+
+/// Jwks is the set of public keys bearer tokens are verified against, loaded from a local
+/// file rather than fetched from the issuer.
+#[derive(Debug, Clone)]
+pub struct Jwks {
+    keys: Vec<Jwk>,
+}
+
+impl Jwks {
+    pub fn parse(json: &str) -> anyhow::Result<Self> {
+        let jwks: RawJwks = match serde_json::from_str(json) {
+            Ok(jwks) => jwks,
+            Err(e) => anyhow::bail!("failed to parse jwks: {e}"),
+        };
+        let keys = jwks
+            .keys
+            .into_iter()
+            .map(Jwk::try_from)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(Self { keys })
+    }
+
+    /// find returns the key with the kid, or the only key of the algorithm when the token has
+    /// no kid.
+    fn find(&self, kid: Option<&str>, alg: &str) -> Option<&Jwk> {
+        let mut keys = self.keys.iter().filter(|jwk| jwk.key.alg() == alg);
+        match kid {
+            Some(kid) => keys.find(|jwk| jwk.kid.as_deref() == Some(kid)),
+            None => match (keys.next(), keys.next()) {
+                (Some(jwk), None) => Some(jwk),
+                _ => None,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Audience {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Audience {
+    fn contains(&self, audience: &str) -> bool {
+        match self {
+            Audience::One(one) => one == audience,
+            Audience::Many(many) => many.iter().any(|one| one == audience),
+        }
+    }
+}
+
+/// Claims are the token claims the api reads. `account_ids` scopes a merchant to its accounts,
+/// `all_accounts` is given to operators.
+#[derive(Debug, Deserialize)]
+struct Claims {
+    sub: String,
+    exp: i64,
+    nbf: Option<i64>,
+    iss: Option<String>,
+    aud: Option<Audience>,
+    #[serde(default)]
+    account_ids: Vec<uuid::Uuid>,
+    #[serde(default)]
+    all_accounts: bool,
+}
+
+fn decode_segment<T: serde::de::DeserializeOwned>(segment: &str) -> Result<T, AuthError> {
+    let bytes = match URL_SAFE_NO_PAD.decode(segment.as_bytes()) {
+        Ok(bytes) => bytes,
+        Err(_) => return Err(AuthError::InvalidToken("malformed token".to_string())),
+    };
+    match serde_json::from_slice(&bytes) {
+        Ok(value) => Ok(value),
+        Err(e) => Err(AuthError::InvalidToken(e.to_string())),
+    }
+}
+
+/// JwtVerifier checks bearer tokens were signed by a key of the jwks, are within their
+/// validity window and, when configured, were issued by and for the expected parties.
+#[derive(Debug, Clone)]
+pub struct JwtVerifier {
+    jwks: Jwks,
+    issuer: Option<String>,
+    audience: Option<String>,
+}
+
+impl JwtVerifier {
+    pub fn new(jwks: Jwks) -> Self {
+        Self {
+            jwks,
+            issuer: None,
+            audience: None,
+        }
+    }
+
+    pub fn with_issuer(mut self, issuer: impl Into<String>) -> Self {
+        self.issuer = Some(issuer.into());
+        self
+    }
+
+    pub fn with_audience(mut self, audience: impl Into<String>) -> Self {
+        self.audience = Some(audience.into());
+        self
+    }
+
+    pub fn verify(
+        &self,
+        token: &str,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Principal, AuthError> {
+        let segments: Vec<&str> = token.split('.').collect();
+        let (header, claims, signature) = match segments.as_slice() {
+            [header, claims, signature] => (*header, *claims, *signature),
+            _ => return Err(AuthError::InvalidToken("malformed token".to_string())),
+        };
+
+        let parsed_header: Header = decode_segment(header)?;
+        let jwk = match self
+            .jwks
+            .find(parsed_header.kid.as_deref(), parsed_header.alg.as_str())
+        {
+            Some(jwk) => jwk,
+            None => {
+                return Err(AuthError::InvalidToken(format!(
+                    "no {} key for kid {:?}",
+                    parsed_header.alg, parsed_header.kid
+                )));
+            }
+        };
+        let signature = match URL_SAFE_NO_PAD.decode(signature.as_bytes()) {
+            Ok(signature) => signature,
+            Err(_) => return Err(AuthError::InvalidToken("malformed signature".to_string())),
+        };
+        let message = &token[..header.len() + 1 + claims.len()];
+        if !jwk.key.verify(message.as_bytes(), &signature) {
+            return Err(AuthError::InvalidToken("signature mismatch".to_string()));
+        }
+
+        let claims: Claims = decode_segment(claims)?;
+        let now = now.timestamp();
+        if claims.exp + LEEWAY_SECONDS <= now {
+            return Err(AuthError::InvalidToken("token expired".to_string()));
+        }
+        if claims.nbf.is_some_and(|nbf| nbf - LEEWAY_SECONDS > now) {
+            return Err(AuthError::InvalidToken("token not yet valid".to_string()));
+        }
+        if self.issuer.is_some() && claims.iss != self.issuer {
+            return Err(AuthError::InvalidToken("unexpected issuer".to_string()));
+        }
+        let audience_matches = match (&self.audience, &claims.aud) {
+            (None, _) => true,
+            (Some(audience), Some(aud)) => aud.contains(audience),
+            (Some(_), None) => false,
+        };
+        if !audience_matches {
+            return Err(AuthError::InvalidToken("unexpected audience".to_string()));
+        }
+
+        let scope = match claims.all_accounts {
+            true => AccountScope::All,
+            false => AccountScope::Only(claims.account_ids),
+        };
+        Ok(Principal::new(claims.sub, scope))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use ring::rand::SystemRandom;
+    use ring::signature::{EcdsaKeyPair, Ed25519KeyPair, KeyPair, RsaKeyPair};
+    use serde_json::{Value, json};
+
+    const RSA_PKCS8: &[u8] = include_bytes!("testdata/rsa_2048.pk8");
+
+    fn encode(value: &Value) -> String {
+        URL_SAFE_NO_PAD.encode(value.to_string())
+    }
+
+    /// Signer signs test tokens and exposes its public key as a jwk.
+    pub(crate) enum Signer {
+        Rsa(RsaKeyPair),
+        Ec(EcdsaKeyPair),
+        Ed(Ed25519KeyPair),
+    }
+
+    impl Signer {
+        pub(crate) fn rsa() -> Self {
+            Signer::Rsa(RsaKeyPair::from_pkcs8(RSA_PKCS8).unwrap())
+        }
+
+        pub(crate) fn ec() -> Self {
+            let rng = SystemRandom::new();
+            let alg = &signature::ECDSA_P256_SHA256_FIXED_SIGNING;
+            let pkcs8 = EcdsaKeyPair::generate_pkcs8(alg, &rng).unwrap();
+            Signer::Ec(EcdsaKeyPair::from_pkcs8(alg, pkcs8.as_ref(), &rng).unwrap())
+        }
+
+        pub(crate) fn ed() -> Self {
+            let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+            Signer::Ed(Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap())
+        }
+
+        fn alg(&self) -> &'static str {
+            match self {
+                Signer::Rsa(_) => "RS256",
+                Signer::Ec(_) => "ES256",
+                Signer::Ed(_) => "EdDSA",
+            }
+        }
+
+        pub(crate) fn jwk(&self, kid: &str) -> Value {
+            match self {
+                Signer::Rsa(key_pair) => {
+                    let public =
+                        signature::RsaPublicKeyComponents::<Vec<u8>>::from(key_pair.public());
+                    json!({
+                        "kty": "RSA", "kid": kid, "alg": "RS256",
+                        "n": URL_SAFE_NO_PAD.encode(public.n),
+                        "e": URL_SAFE_NO_PAD.encode(public.e),
+                    })
+                }
+                Signer::Ec(key_pair) => {
+                    let point = key_pair.public_key().as_ref();
+                    json!({
+                        "kty": "EC", "kid": kid, "crv": "P-256",
+                        "x": URL_SAFE_NO_PAD.encode(&point[1..33]),
+                        "y": URL_SAFE_NO_PAD.encode(&point[33..]),
+                    })
+                }
+                Signer::Ed(key_pair) => json!({
+                    "kty": "OKP", "kid": kid, "crv": "Ed25519",
+                    "x": URL_SAFE_NO_PAD.encode(key_pair.public_key().as_ref()),
+                }),
+            }
+        }
+
+        pub(crate) fn sign(&self, kid: &str, claims: &Value) -> String {
+            let message = format!(
+                "{}.{}",
+                encode(&json!({"alg": self.alg(), "typ": "JWT", "kid": kid})),
+                encode(claims)
+            );
+            let rng = SystemRandom::new();
+            let signature = match self {
+                Signer::Rsa(key_pair) => {
+                    let mut signature = vec![0; key_pair.public().modulus_len()];
+                    key_pair
+                        .sign(
+                            &signature::RSA_PKCS1_SHA256,
+                            &rng,
+                            message.as_bytes(),
+                            &mut signature,
+                        )
+                        .unwrap();
+                    signature
+                }
+                Signer::Ec(key_pair) => key_pair
+                    .sign(&rng, message.as_bytes())
+                    .unwrap()
+                    .as_ref()
+                    .to_vec(),
+                Signer::Ed(key_pair) => key_pair.sign(message.as_bytes()).as_ref().to_vec(),
+            };
+            format!("{message}.{}", URL_SAFE_NO_PAD.encode(signature))
+        }
+    }
+
+    #[test]
+    fn test_verify() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            token: String,
+            expected: Result<Principal, &'static str>,
+        }
+
+        let now = chrono::Utc::now();
+        let account_id = uuid::Uuid::new_v4();
+        let (rsa, ec, ed, other) = (Signer::rsa(), Signer::ec(), Signer::ed(), Signer::ed());
+        let claims = json!({
+            "sub": "merchant-a",
+            "iss": "https://auth.pasys.dev",
+            "aud": ["pasys-api"],
+            "exp": now.timestamp() + 300,
+            "account_ids": [account_id],
+        });
+        let merchant = Principal::new("merchant-a", AccountScope::Only(vec![account_id]));
+        let mut expired = claims.clone();
+        expired["exp"] = json!(now.timestamp() - 300);
+        let mut other_audience = claims.clone();
+        other_audience["aud"] = json!("another-api");
+        let mut operator = claims.clone();
+        operator["all_accounts"] = json!(true);
+        let signed = ed.sign("ed", &claims);
+
+        let test_cases = vec![
+            TestCase {
+                name: "successfully verify RS256 token",
+                token: rsa.sign("rsa", &claims),
+                expected: Ok(merchant.clone()),
+            },
+            TestCase {
+                name: "successfully verify ES256 token",
+                token: ec.sign("ec", &claims),
+                expected: Ok(merchant.clone()),
+            },
+            TestCase {
+                name: "successfully verify EdDSA token",
+                token: signed.clone(),
+                expected: Ok(merchant.clone()),
+            },
+            TestCase {
+                name: "successfully verify operator token",
+                token: ed.sign("ed", &operator),
+                expected: Ok(Principal::new("merchant-a", AccountScope::All)),
+            },
+            TestCase {
+                name: "error when token is expired",
+                token: ed.sign("ed", &expired),
+                expected: Err("token expired"),
+            },
+            TestCase {
+                name: "error when audience is unexpected",
+                token: ed.sign("ed", &other_audience),
+                expected: Err("unexpected audience"),
+            },
+            TestCase {
+                name: "error when signed by an unknown key",
+                token: other.sign("ed", &claims),
+                expected: Err("signature mismatch"),
+            },
+            TestCase {
+                name: "error when claims were tampered with",
+                token: format!(
+                    "{}.{}.{}",
+                    signed.split('.').next().unwrap(),
+                    encode(&operator),
+                    signed.split('.').nth(2).unwrap()
+                ),
+                expected: Err("signature mismatch"),
+            },
+            TestCase {
+                name: "error when algorithm is none",
+                token: format!(
+                    "{}.{}.",
+                    encode(&json!({"alg": "none", "kid": "ed"})),
+                    encode(&claims)
+                ),
+                expected: Err("no none key"),
+            },
+        ];
+
+        let jwks =
+            Jwks::parse(&json!({"keys": [rsa.jwk("rsa"), ec.jwk("ec"), ed.jwk("ed")]}).to_string())
+                .unwrap();
+        let verifier = JwtVerifier::new(jwks)
+            .with_issuer("https://auth.pasys.dev")
+            .with_audience("pasys-api");
+
+        for test_case in test_cases {
+            let result = verifier.verify(test_case.token.as_str(), now);
+            match test_case.expected {
+                Ok(expected) => assert_eq!(result.unwrap(), expected, "{}", test_case.name),
+                Err(reason) => assert!(
+                    result.unwrap_err().to_string().contains(reason),
+                    "{}",
+                    test_case.name
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn error_when_jwk_alg_does_not_match_key() {
+        let mut jwk = Signer::ed().jwk("ed");
+        jwk["alg"] = json!("RS256");
+
+        assert!(Jwks::parse(&json!({ "keys": [jwk] }).to_string()).is_err());
+    }
+}
@@ -0,0 +1,225 @@
+pub mod jwt;
+
+use crate::api::{ApiError, AppState};
+use crate::auth::jwt::JwtVerifier;
+use crate::domain::api_key::hash_api_key;
+use crate::domain::principal::{AuthError, Principal};
+use crate::repo::ApiKeyReader;
+use axum::extract::{Request, State};
+use axum::http::header::AUTHORIZATION;
+use axum::http::{HeaderMap, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use std::sync::Arc;
+
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Authenticator resolves the principal of a request from its api key or bearer token.
+#[derive(Clone)]
+pub struct Authenticator {
+    api_keys: Arc<dyn ApiKeyReader>,
+    jwt: Option<JwtVerifier>,
+}
+
+impl std::fmt::Debug for Authenticator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Authenticator")
+            .field("jwt", &self.jwt)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Authenticator {
+    pub fn new(api_keys: Arc<dyn ApiKeyReader>) -> Self {
+        Self {
+            api_keys,
+            jwt: None,
+        }
+    }
+
+    /// with_jwt_verifier accepts bearer tokens besides api keys.
+    pub fn with_jwt_verifier(mut self, jwt: JwtVerifier) -> Self {
+        self.jwt = Some(jwt);
+        self
+    }
+
+    pub async fn authenticate(&self, headers: &HeaderMap) -> anyhow::Result<Principal> {
+        if let Some(key) = headers.get(API_KEY_HEADER) {
+            let key = match key.to_str() {
+                Ok(key) => key.trim(),
+                Err(_) => return Err(AuthError::InvalidApiKey.into()),
+            };
+            return match self
+                .api_keys
+                .get_api_key_by_hash(&hash_api_key(key))
+                .await?
+            {
+                Some(api_key) => Ok(api_key.principal()),
+                None => Err(AuthError::InvalidApiKey.into()),
+            };
+        }
+
+        let token = headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        match (token, &self.jwt) {
+            (Some(token), Some(jwt)) => Ok(jwt.verify(token.trim(), chrono::Utc::now())?),
+            (Some(_), None) => {
+                Err(AuthError::InvalidToken("bearer tokens are not enabled".to_string()).into())
+            }
+            (None, _) => Err(AuthError::MissingCredentials.into()),
+        }
+    }
+}
+
+/// parse_error_to_api_error maps auth errors to 401, anything else, e.g. the api key store
+/// being down, is a server error.
+fn parse_error_to_api_error(e: anyhow::Error) -> ApiError {
+    match e.downcast_ref::<AuthError>() {
+        Some(_) => ApiError::new(StatusCode::UNAUTHORIZED, "unauthenticated", e.to_string()),
+        None => {
+            tracing::error!("failed to authenticate request: {e}");
+            ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "internal",
+                "internal server error",
+            )
+        }
+    }
+}
+
+/// authenticate rejects requests without valid credentials and makes the principal available
+/// to handlers as an extension.
+pub async fn authenticate(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    match state.authenticator.authenticate(request.headers()).await {
+        Ok(principal) => {
+            request.extensions_mut().insert(principal);
+            next.run(request).await
+        }
+        Err(e) => {
+            let mut response = parse_error_to_api_error(e).into_response();
+            if response.status() == StatusCode::UNAUTHORIZED {
+                response.headers_mut().insert(
+                    axum::http::header::WWW_AUTHENTICATE,
+                    axum::http::HeaderValue::from_static("Bearer"),
+                );
+            }
+            response
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::jwt::Jwks;
+    use crate::auth::jwt::tests::Signer;
+    use crate::domain::api_key::ApiKey;
+    use crate::domain::principal::AccountScope;
+    use async_trait::async_trait;
+    use axum::http::HeaderValue;
+    use serde_json::json;
+
+    struct StaticApiKeys(Vec<ApiKey>);
+
+    #[async_trait]
+    impl ApiKeyReader for StaticApiKeys {
+        async fn get_api_key_by_hash(&self, key_hash: &str) -> anyhow::Result<Option<ApiKey>> {
+            Ok(self.0.iter().find(|key| key.key_hash == key_hash).cloned())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_authenticate() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            headers: Vec<(&'static str, String)>,
+            expected: Result<Principal, AuthError>,
+        }
+
+        let account_id = uuid::Uuid::new_v4();
+        let (api_key, key) =
+            ApiKey::generate("merchant-a", AccountScope::Only(vec![account_id])).unwrap();
+        let signer = Signer::ed();
+        let token = signer.sign(
+            "ed",
+            &json!({"sub": "ops", "exp": chrono::Utc::now().timestamp() + 60, "all_accounts": true}),
+        );
+        let jwks = Jwks::parse(&json!({"keys": [signer.jwk("ed")]}).to_string()).unwrap();
+        let authenticator = Authenticator::new(Arc::new(StaticApiKeys(vec![api_key])))
+            .with_jwt_verifier(JwtVerifier::new(jwks));
+
+        let test_cases = vec![
+            TestCase {
+                name: "successfully authenticate api key",
+                headers: vec![(API_KEY_HEADER, key.clone())],
+                expected: Ok(Principal::new(
+                    "merchant-a",
+                    AccountScope::Only(vec![account_id]),
+                )),
+            },
+            TestCase {
+                name: "successfully authenticate bearer token",
+                headers: vec![("authorization", format!("Bearer {token}"))],
+                expected: Ok(Principal::new("ops", AccountScope::All)),
+            },
+            TestCase {
+                name: "error when api key is unknown",
+                headers: vec![(API_KEY_HEADER, format!("{key}x"))],
+                expected: Err(AuthError::InvalidApiKey),
+            },
+            TestCase {
+                name: "error when no credentials are given",
+                headers: vec![],
+                expected: Err(AuthError::MissingCredentials),
+            },
+            TestCase {
+                name: "error when authorization is not a bearer token",
+                headers: vec![("authorization", format!("Basic {key}"))],
+                expected: Err(AuthError::MissingCredentials),
+            },
+        ];
+
+        for test_case in test_cases {
+            // arrange
+            let mut headers = HeaderMap::new();
+            for (name, value) in test_case.headers {
+                headers.insert(name, HeaderValue::from_str(value.as_str()).unwrap());
+            }
+
+            // act
+            let result = authenticator.authenticate(&headers).await;
+
+            // assert
+            match test_case.expected {
+                Ok(expected) => assert_eq!(result.unwrap(), expected, "{}", test_case.name),
+                Err(expected) => assert_eq!(
+                    result.unwrap_err().downcast_ref::<AuthError>(),
+                    Some(&expected),
+                    "{}",
+                    test_case.name
+                ),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn error_when_bearer_tokens_are_not_enabled() {
+        let authenticator = Authenticator::new(Arc::new(StaticApiKeys(vec![])));
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", HeaderValue::from_static("Bearer a.b.c"));
+
+        let result = authenticator.authenticate(&headers).await;
+
+        assert!(matches!(
+            result.unwrap_err().downcast_ref::<AuthError>(),
+            Some(AuthError::InvalidToken(_))
+        ));
+    }
+}
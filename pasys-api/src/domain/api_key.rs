@@ -0,0 +1,86 @@
+use crate::domain::principal::{AccountScope, Principal};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use ring::rand::{SecureRandom, SystemRandom};
+use sha2::{Digest, Sha256};
+
+/// API_KEY_PREFIX makes keys recognisable, e.g. by secret scanners.
+pub const API_KEY_PREFIX: &str = "pasys_";
+
+/// ApiKey is a stored credential. Only the hash of the key is kept, keys are random 256 bit
+/// values so a plain sha256 is enough to make a leaked table useless.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct ApiKey {
+    pub id: uuid::Uuid,
+    pub principal: String,
+    pub key_hash: String,
+    pub account_ids: Vec<uuid::Uuid>,
+    pub all_accounts: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl ApiKey {
+    /// generate returns a new api key for the principal together with the key itself, which is
+    /// shown to the caller once and never stored.
+    pub fn generate(principal: &str, scope: AccountScope) -> anyhow::Result<(Self, String)> {
+        let mut secret = [0u8; 32];
+        if SystemRandom::new().fill(&mut secret).is_err() {
+            anyhow::bail!("failed to generate api key");
+        }
+        let key = format!("{API_KEY_PREFIX}{}", URL_SAFE_NO_PAD.encode(secret));
+        let (account_ids, all_accounts) = match scope {
+            AccountScope::All => (vec![], true),
+            AccountScope::Only(account_ids) => (account_ids, false),
+        };
+
+        Ok((
+            Self {
+                id: uuid::Uuid::new_v4(),
+                principal: principal.to_string(),
+                key_hash: hash_api_key(key.as_str()),
+                account_ids,
+                all_accounts,
+                created_at: chrono::Utc::now(),
+                revoked_at: None,
+            },
+            key,
+        ))
+    }
+
+    pub fn principal(&self) -> Principal {
+        let scope = match self.all_accounts {
+            true => AccountScope::All,
+            false => AccountScope::Only(self.account_ids.clone()),
+        };
+        Principal::new(self.principal.as_str(), scope)
+    }
+}
+
+pub fn hash_api_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn successfully_generate_api_key() {
+        // arrange
+        let account_id = uuid::Uuid::new_v4();
+
+        // act
+        let (api_key, key) =
+            ApiKey::generate("merchant-a", AccountScope::Only(vec![account_id])).unwrap();
+
+        // assert
+        assert!(key.starts_with(API_KEY_PREFIX));
+        assert_eq!(api_key.key_hash, hash_api_key(key.as_str()));
+        assert_ne!(api_key.key_hash, key);
+        assert_eq!(
+            api_key.principal(),
+            Principal::new("merchant-a", AccountScope::Only(vec![account_id]))
+        );
+    }
+}
@@ -0,0 +1,3 @@
+pub mod api_key;
+pub mod principal;
+pub mod rate_limit;
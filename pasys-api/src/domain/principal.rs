@@ -0,0 +1,116 @@
+use std::fmt;
+
+/// AccountScope is the set of accounts a principal may use.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccountScope {
+    /// All is given to operators, e.g. the admin CLI.
+    All,
+    Only(Vec<uuid::Uuid>),
+}
+
+/// Principal is the authenticated caller of the API, a merchant or an operator.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Principal {
+    pub subject: String,
+    pub scope: AccountScope,
+}
+
+impl Principal {
+    pub fn new(subject: impl Into<String>, scope: AccountScope) -> Self {
+        Self {
+            subject: subject.into(),
+            scope,
+        }
+    }
+
+    pub fn is_unrestricted(&self) -> bool {
+        self.scope == AccountScope::All
+    }
+
+    /// can_access checks the account is in scope. Ids which are not UUIDs are never in scope
+    /// of a restricted principal.
+    pub fn can_access(&self, account_id: &str) -> bool {
+        match &self.scope {
+            AccountScope::All => true,
+            AccountScope::Only(account_ids) => match uuid::Uuid::parse_str(account_id) {
+                Ok(account_id) => account_ids.contains(&account_id),
+                Err(_) => false,
+            },
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AuthError {
+    MissingCredentials,
+    InvalidApiKey,
+    InvalidToken(String),
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthError::MissingCredentials => {
+                write!(f, "an X-API-Key header or bearer token must be set")
+            }
+            AuthError::InvalidApiKey => write!(f, "api key is invalid or revoked"),
+            AuthError::InvalidToken(reason) => write!(f, "bearer token is invalid: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for AuthError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_can_access() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            scope: AccountScope,
+            account_id: String,
+            expected: bool,
+        }
+
+        let account_id = uuid::Uuid::new_v4();
+        let test_cases = vec![
+            TestCase {
+                name: "successfully access own account",
+                scope: AccountScope::Only(vec![account_id]),
+                account_id: account_id.to_string(),
+                expected: true,
+            },
+            TestCase {
+                name: "denied for account of another merchant",
+                scope: AccountScope::Only(vec![account_id]),
+                account_id: uuid::Uuid::new_v4().to_string(),
+                expected: false,
+            },
+            TestCase {
+                name: "denied for invalid account id",
+                scope: AccountScope::Only(vec![account_id]),
+                account_id: "account".to_string(),
+                expected: false,
+            },
+            TestCase {
+                name: "successfully access any account as operator",
+                scope: AccountScope::All,
+                account_id: uuid::Uuid::new_v4().to_string(),
+                expected: true,
+            },
+        ];
+
+        for test_case in test_cases {
+            let principal = Principal::new("merchant-a", test_case.scope);
+            assert_eq!(
+                principal.can_access(test_case.account_id.as_str()),
+                test_case.expected,
+                "{}",
+                test_case.name
+            );
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// RateLimit allows `requests` per `window` as a token bucket: the bucket holds up to
+/// `requests` tokens and refills continuously, so a client may burst the whole limit at once.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    pub requests: u32,
+    pub window: Duration,
+}
+
+impl RateLimit {
+    pub fn new(requests: u32, window: Duration) -> Self {
+        Self { requests, window }
+    }
+
+    /// parse reads `<requests>/<seconds>`, e.g. `600/60`.
+    pub fn parse(limit: &str) -> anyhow::Result<Self> {
+        let (requests, seconds) = match limit.trim().split_once('/') {
+            Some(parts) => parts,
+            None => anyhow::bail!("rate limit {limit} is not <requests>/<seconds>"),
+        };
+        let requests: u32 = match requests.trim().parse() {
+            Ok(requests) if requests > 0 => requests,
+            _ => anyhow::bail!("rate limit {limit} must allow at least one request"),
+        };
+        let seconds: u64 = match seconds.trim().parse() {
+            Ok(seconds) if seconds > 0 => seconds,
+            _ => anyhow::bail!("rate limit {limit} must have a window of at least one second"),
+        };
+
+        Ok(Self::new(requests, Duration::from_secs(seconds)))
+    }
+
+    fn tokens_per_second(&self) -> f64 {
+        self.requests as f64 / self.window.as_secs_f64()
+    }
+
+    /// seconds_until returns how long the bucket takes to refill to `tokens`.
+    fn seconds_until(&self, from: f64, tokens: f64) -> u64 {
+        ((tokens - from).max(0.0) / self.tokens_per_second()).ceil() as u64
+    }
+}
+
+/// RateLimits holds the default limit and the overrides of single routes, keyed by method and
+/// route template, e.g. `POST /v1/transactions`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimits {
+    default: RateLimit,
+    routes: HashMap<String, RateLimit>,
+}
+
+impl RateLimits {
+    pub fn new(default: RateLimit) -> Self {
+        Self {
+            default,
+            routes: HashMap::new(),
+        }
+    }
+
+    pub fn with_route(mut self, route: impl Into<String>, limit: RateLimit) -> Self {
+        self.routes.insert(route.into(), limit);
+        self
+    }
+
+    pub fn limit_for(&self, route: &str) -> RateLimit {
+        self.routes.get(route).copied().unwrap_or(self.default)
+    }
+}
+
+/// Bucket is the state of a token bucket as of `updated_at`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bucket {
+    pub tokens: f64,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Decision is the outcome of taking a token, with what the `RateLimit-*` headers report.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Decision {
+    pub allowed: bool,
+    pub limit: RateLimit,
+    pub remaining: u32,
+    /// reset_seconds is the time until the bucket is full again.
+    pub reset_seconds: u64,
+    /// retry_after_seconds is the time until the next token when the request was denied.
+    pub retry_after_seconds: Option<u64>,
+}
+
+impl Bucket {
+    pub fn full(limit: &RateLimit, now: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            tokens: limit.requests as f64,
+            updated_at: now,
+        }
+    }
+
+    /// take refills the bucket for the time since it was last updated and takes a token if one
+    /// is left. Clocks of replicas may disagree, a bucket updated "in the future" is not refilled.
+    pub fn take(self, limit: &RateLimit, now: chrono::DateTime<chrono::Utc>) -> (Bucket, Decision) {
+        let elapsed = (now - self.updated_at).as_seconds_f64().max(0.0);
+        let capacity = limit.requests as f64;
+        let refilled = (self.tokens + elapsed * limit.tokens_per_second()).min(capacity);
+        let updated_at = now.max(self.updated_at);
+
+        let (tokens, allowed) = match refilled >= 1.0 {
+            true => (refilled - 1.0, true),
+            false => (refilled, false),
+        };
+        let decision = Decision {
+            allowed,
+            limit: *limit,
+            remaining: tokens.floor() as u32,
+            reset_seconds: limit.seconds_until(tokens, capacity),
+            retry_after_seconds: match allowed {
+                true => None,
+                false => Some(limit.seconds_until(tokens, 1.0).max(1)),
+            },
+        };
+
+        (Bucket { tokens, updated_at }, decision)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            limit: &'static str,
+            expected: Option<RateLimit>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "successfully parse requests per seconds",
+                limit: "600/60",
+                expected: Some(RateLimit::new(600, Duration::from_secs(60))),
+            },
+            TestCase {
+                name: "error when window is missing",
+                limit: "600",
+                expected: None,
+            },
+            TestCase {
+                name: "error when no request is allowed",
+                limit: "0/60",
+                expected: None,
+            },
+            TestCase {
+                name: "error when window is empty",
+                limit: "10/0",
+                expected: None,
+            },
+        ];
+
+        for test_case in test_cases {
+            let result = RateLimit::parse(test_case.limit);
+            assert_eq!(result.ok(), test_case.expected, "{}", test_case.name);
+        }
+    }
+
+    #[test]
+    fn test_take() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            tokens: f64,
+            elapsed_ms: i64,
+            expected_allowed: bool,
+            expected_remaining: u32,
+            expected_reset_seconds: u64,
+            expected_retry_after_seconds: Option<u64>,
+        }
+
+        // 10 requests per 20 seconds refill a token every 2 seconds
+        let limit = RateLimit::new(10, Duration::from_secs(20));
+        let test_cases = vec![
+            TestCase {
+                name: "successfully take from full bucket",
+                tokens: 10.0,
+                elapsed_ms: 0,
+                expected_allowed: true,
+                expected_remaining: 9,
+                expected_reset_seconds: 2,
+                expected_retry_after_seconds: None,
+            },
+            TestCase {
+                name: "successfully take refilled token",
+                tokens: 0.0,
+                elapsed_ms: 2_000,
+                expected_allowed: true,
+                expected_remaining: 0,
+                expected_reset_seconds: 20,
+                expected_retry_after_seconds: None,
+            },
+            TestCase {
+                name: "successfully cap refill at capacity",
+                tokens: 5.0,
+                elapsed_ms: 3_600_000,
+                expected_allowed: true,
+                expected_remaining: 9,
+                expected_reset_seconds: 2,
+                expected_retry_after_seconds: None,
+            },
+            TestCase {
+                name: "deny until the next token is refilled",
+                tokens: 0.0,
+                elapsed_ms: 500,
+                expected_allowed: false,
+                expected_remaining: 0,
+                expected_reset_seconds: 20,
+                expected_retry_after_seconds: Some(2),
+            },
+            TestCase {
+                name: "deny without refill when bucket was updated in the future",
+                tokens: 0.5,
+                elapsed_ms: -10_000,
+                expected_allowed: false,
+                expected_remaining: 0,
+                expected_reset_seconds: 19,
+                expected_retry_after_seconds: Some(1),
+            },
+        ];
+
+        for test_case in test_cases {
+            // arrange
+            let updated_at = chrono::Utc::now();
+            let now = updated_at + chrono::Duration::milliseconds(test_case.elapsed_ms);
+            let bucket = Bucket {
+                tokens: test_case.tokens,
+                updated_at,
+            };
+
+            // act
+            let (_, decision) = bucket.take(&limit, now);
+
+            // assert
+            assert_eq!(
+                decision.allowed, test_case.expected_allowed,
+                "{}",
+                test_case.name
+            );
+            assert_eq!(
+                decision.remaining, test_case.expected_remaining,
+                "{}",
+                test_case.name
+            );
+            assert_eq!(
+                decision.reset_seconds, test_case.expected_reset_seconds,
+                "{}",
+                test_case.name
+            );
+            assert_eq!(
+                decision.retry_after_seconds, test_case.expected_retry_after_seconds,
+                "{}",
+                test_case.name
+            );
+        }
+    }
+}
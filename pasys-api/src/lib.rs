@@ -0,0 +1,18 @@
+pub mod api;
+pub mod auth;
+pub mod domain;
+pub mod repo;
+
+pub const DEFAULT_PORT: u16 = 8080;
+/// DEFAULT_IDEMPOTENCY_TTL_SECONDS keeps responses replayable for a day, as most clients retry
+/// well within that.
+pub const DEFAULT_IDEMPOTENCY_TTL_SECONDS: u64 = 86_400;
+/// DEFAULT_IDEMPOTENCY_MAX_ENTRIES bounds the responses each replica keeps for replays.
+pub const DEFAULT_IDEMPOTENCY_MAX_ENTRIES: usize = 100_000;
+/// DEFAULT_RATE_LIMIT_REQUESTS per DEFAULT_RATE_LIMIT_WINDOW_SECONDS apply to every principal
+/// and route without a configured limit.
+pub const DEFAULT_RATE_LIMIT_REQUESTS: u32 = 600;
+pub const DEFAULT_RATE_LIMIT_WINDOW_SECONDS: u64 = 60;
+pub const DEFAULT_WRITER_MAX_CONN: u32 = 4;
+pub const DEFAULT_READER_MAX_CONN: u32 = 4;
+pub const DEFAULT_TIMEOUT_SECONDS: u64 = 5;
@@ -1,3 +1,165 @@
-fn main() {
-    println!("Hello, world!");
+use accounts_proto::accounts_v1::accounts_client::AccountsClient;
+use common::{database, shutdown};
+use ledger_proto::ledger_v1::ledger_client::LedgerClient;
+use pasys_api::api::openapi::ApiDoc;
+use pasys_api::api::rate_limit::RateLimiter;
+use pasys_api::api::{self, AppState};
+use pasys_api::auth::Authenticator;
+use pasys_api::auth::jwt::{Jwks, JwtVerifier};
+use pasys_api::domain::api_key::ApiKey;
+use pasys_api::domain::principal::AccountScope;
+use pasys_api::domain::rate_limit::{RateLimit, RateLimits};
+use pasys_api::repo::{ApiKeyWriter, InMemoryRateLimitStore, PgApiRepository, RateLimitStore};
+use pasys_api::{
+    DEFAULT_IDEMPOTENCY_MAX_ENTRIES, DEFAULT_IDEMPOTENCY_TTL_SECONDS, DEFAULT_PORT,
+    DEFAULT_RATE_LIMIT_REQUESTS, DEFAULT_RATE_LIMIT_WINDOW_SECONDS, DEFAULT_READER_MAX_CONN,
+    DEFAULT_TIMEOUT_SECONDS, DEFAULT_WRITER_MAX_CONN,
+};
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+use tonic::transport::Channel;
+use utoipa::OpenApi;
+use webhook_dispatcher::domain::endpoint::UrlPolicy;
+use webhook_dispatcher::repo::PgWebhookRepository;
+
+/// parse_account_scope reads `*` as every account, otherwise a comma separated list of ids.
+fn parse_account_scope(scope: &str) -> anyhow::Result<AccountScope> {
+    if scope == "*" {
+        return Ok(AccountScope::All);
+    }
+    let account_ids = scope
+        .split(',')
+        .filter(|id| !id.is_empty())
+        .map(|id| uuid::Uuid::parse_str(id.trim()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(AccountScope::Only(account_ids))
+}
+
+/// parse_route_limits reads comma separated `<method> <route>=<requests>/<seconds>`, e.g.
+/// `POST /v1/transactions=60/60`.
+fn parse_route_limits(mut limits: RateLimits, routes: &str) -> anyhow::Result<RateLimits> {
+    for route in routes.split(',').filter(|route| !route.trim().is_empty()) {
+        let (route, limit) = match route.rsplit_once('=') {
+            Some(parts) => parts,
+            None => anyhow::bail!("route limit {route} is not <method> <route>=<limit>"),
+        };
+        limits = limits.with_route(route.trim(), RateLimit::parse(limit)?);
+    }
+
+    Ok(limits)
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    // `pasys-api openapi` prints the OpenAPI document, used to refresh `openapi.json`
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Some("openapi") = args.first().map(String::as_str) {
+        println!("{}", ApiDoc::openapi().to_pretty_json()?);
+        return Ok(());
+    }
+
+    // setup api database holding the api keys
+    let database_config = database::Config {
+        reader_url: env::var("READER_DATABASE_URL").expect("READER_DATABASE_URL must be set"),
+        reader_max_connections: DEFAULT_READER_MAX_CONN,
+        writer_url: env::var("WRITER_DATABASE_URL").expect("WRITER_DATABASE_URL must be set"),
+        writer_max_connections: DEFAULT_WRITER_MAX_CONN,
+        timeout_in_secs: DEFAULT_TIMEOUT_SECONDS,
+    };
+    let db = database::Database::new(&database_config)
+        .await
+        .expect("failed to create database");
+    let repo = PgApiRepository::new(db);
+
+    // `pasys-api create-api-key <principal> <account ids|*>` prints a new key once
+    if let Some("create-api-key") = args.first().map(String::as_str) {
+        let (principal, scope) = match (args.get(1), args.get(2)) {
+            (Some(principal), Some(scope)) => (principal, parse_account_scope(scope)?),
+            _ => anyhow::bail!("usage: pasys-api create-api-key <principal> <account ids|*>"),
+        };
+        let (api_key, key) = ApiKey::generate(principal, scope)?;
+        let api_key = repo.create_api_key(&api_key).await?;
+        tracing::info!(id = %api_key.id, principal = api_key.principal, "created api key");
+        println!("{key}");
+        return Ok(());
+    }
+
+    // setup authentication, bearer tokens are only accepted when a jwks is configured
+    let mut authenticator = Authenticator::new(Arc::new(repo.clone()));
+    if let Ok(path) = env::var("JWKS_PATH") {
+        let mut verifier = JwtVerifier::new(Jwks::parse(&std::fs::read_to_string(path)?)?);
+        if let Ok(issuer) = env::var("JWT_ISSUER") {
+            verifier = verifier.with_issuer(issuer);
+        }
+        if let Ok(audience) = env::var("JWT_AUDIENCE") {
+            verifier = verifier.with_audience(audience);
+        }
+        authenticator = authenticator.with_jwt_verifier(verifier);
+    }
+
+    // setup grpc clients, connecting on first use so the gateway starts before its upstreams
+    let accounts_url = env::var("ACCOUNTS_URL").expect("ACCOUNTS_URL must be set");
+    let ledger_url = env::var("LEDGER_URL").expect("LEDGER_URL must be set");
+    let accounts = AccountsClient::new(Channel::from_shared(accounts_url)?.connect_lazy());
+    let ledger = LedgerClient::new(Channel::from_shared(ledger_url)?.connect_lazy());
+
+    let idempotency_ttl: u64 = env::var("IDEMPOTENCY_TTL_SECONDS")
+        .unwrap_or(DEFAULT_IDEMPOTENCY_TTL_SECONDS.to_string())
+        .parse()?;
+    let idempotency_max_entries: usize = env::var("IDEMPOTENCY_MAX_ENTRIES")
+        .unwrap_or(DEFAULT_IDEMPOTENCY_MAX_ENTRIES.to_string())
+        .parse()?;
+
+    // setup rate limiting, buckets are shared between replicas when kept in postgres
+    let mut limits = RateLimits::new(RateLimit::new(
+        DEFAULT_RATE_LIMIT_REQUESTS,
+        Duration::from_secs(DEFAULT_RATE_LIMIT_WINDOW_SECONDS),
+    ));
+    if let Ok(limit) = env::var("RATE_LIMIT") {
+        limits = RateLimits::new(RateLimit::parse(&limit)?);
+    }
+    if let Ok(routes) = env::var("RATE_LIMIT_ROUTES") {
+        limits = parse_route_limits(limits, &routes)?;
+    }
+    let store: Arc<dyn RateLimitStore> = match env::var("RATE_LIMIT_STORE").as_deref() {
+        Ok("postgres") => Arc::new(repo.clone()),
+        Ok("memory") | Err(_) => Arc::new(InMemoryRateLimitStore::default()),
+        Ok(store) => anyhow::bail!("RATE_LIMIT_STORE {store} is not memory or postgres"),
+    };
+
+    // webhook endpoints live in the api database, next to the api keys
+    let webhooks = PgWebhookRepository::new(repo.db);
+    let webhook_url_policy = match env::var("WEBHOOK_ALLOW_PRIVATE_URLS")
+        .map(|v| v.parse::<bool>())
+        .unwrap_or(Ok(false))?
+    {
+        true => UrlPolicy::AllowPrivate,
+        false => UrlPolicy::Public,
+    };
+
+    let state = AppState::new(accounts, ledger, authenticator)
+        .with_idempotency(
+            Duration::from_secs(idempotency_ttl),
+            idempotency_max_entries,
+        )
+        .with_rate_limiter(RateLimiter::new(limits, store))
+        .with_webhooks(webhooks, webhook_url_policy);
+
+    // setup http server
+    let port: u16 = env::var("PORT")
+        .unwrap_or(DEFAULT_PORT.to_string())
+        .parse()?;
+    let address = format!("0.0.0.0:{}", port);
+    let listener = tokio::net::TcpListener::bind(address)
+        .await
+        .expect("Failed to create TCP listener ❌");
+    axum::serve(listener, api::router(state))
+        .with_graceful_shutdown(shutdown::shutdown_signal())
+        .await?;
+
+    Ok(())
 }
@@ -0,0 +1,103 @@
+use crate::domain::api_key::ApiKey;
+use crate::repo::{ApiKeyReader, ApiKeyWriter, PgApiRepository};
+use async_trait::async_trait;
+
+const API_KEY_COLUMNS: &str =
+    "id, principal, key_hash, account_ids, all_accounts, created_at, revoked_at";
+
+#[async_trait]
+impl ApiKeyWriter for PgApiRepository {
+    async fn create_api_key(&self, api_key: &ApiKey) -> anyhow::Result<ApiKey> {
+        let result = sqlx::query_as::<_, ApiKey>(&format!(
+            r#"
+            INSERT INTO api_keys (id, principal, key_hash, account_ids, all_accounts, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING {API_KEY_COLUMNS}
+            "#
+        ))
+        .bind(api_key.id)
+        .bind(api_key.principal.as_str())
+        .bind(api_key.key_hash.as_str())
+        .bind(&api_key.account_ids)
+        .bind(api_key.all_accounts)
+        .bind(api_key.created_at)
+        .fetch_one(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(api_key) => Ok(api_key),
+            Err(e) => anyhow::bail!("Failed to insert api key into database: {e}"),
+        }
+    }
+
+    async fn revoke_api_key(&self, id: uuid::Uuid) -> anyhow::Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE api_keys
+            SET revoked_at = now()
+            WHERE id = $1 AND revoked_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .execute(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(result) => Ok(result.rows_affected() == 1),
+            Err(e) => anyhow::bail!("Failed to revoke api key: {e}"),
+        }
+    }
+}
+
+#[async_trait]
+impl ApiKeyReader for PgApiRepository {
+    async fn get_api_key_by_hash(&self, key_hash: &str) -> anyhow::Result<Option<ApiKey>> {
+        let result = sqlx::query_as::<_, ApiKey>(&format!(
+            r#"
+            SELECT {API_KEY_COLUMNS}
+            FROM api_keys
+            WHERE key_hash = $1 AND revoked_at IS NULL
+            "#
+        ))
+        .bind(key_hash)
+        .fetch_optional(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(api_key) => Ok(api_key),
+            Err(e) => anyhow::bail!("Failed to get api key from database: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::principal::AccountScope;
+
+    #[sqlx::test(migrations = "../migrations/api")]
+    async fn successfully_get_api_key_until_revoked(pool: sqlx::PgPool) {
+        // arrange
+        let repo = PgApiRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let (api_key, _) =
+            ApiKey::generate("merchant-a", AccountScope::Only(vec![uuid::Uuid::new_v4()])).unwrap();
+        let created = repo.create_api_key(&api_key).await.unwrap();
+
+        // act
+        let found = repo
+            .get_api_key_by_hash(api_key.key_hash.as_str())
+            .await
+            .unwrap();
+
+        // assert
+        assert_eq!(found, Some(created));
+        assert!(repo.revoke_api_key(api_key.id).await.unwrap());
+        assert!(!repo.revoke_api_key(api_key.id).await.unwrap());
+        assert_eq!(
+            repo.get_api_key_by_hash(api_key.key_hash.as_str())
+                .await
+                .unwrap(),
+            None
+        );
+    }
+}
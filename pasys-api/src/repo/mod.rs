@@ -0,0 +1,52 @@
+use crate::domain::api_key::ApiKey;
+use crate::domain::rate_limit::{Decision, RateLimit};
+use async_trait::async_trait;
+use common::database::Database;
+
+mod api_key;
+mod rate_limit;
+
+pub use rate_limit::InMemoryRateLimitStore;
+
+#[derive(Debug, Clone)]
+pub struct PgApiRepository {
+    pub db: Database,
+}
+
+impl PgApiRepository {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+pub trait ApiKeyRepository: ApiKeyWriter + ApiKeyReader + 'static + Send + Sync {}
+
+#[async_trait]
+pub trait ApiKeyWriter: 'static + Send + Sync {
+    async fn create_api_key(&self, api_key: &ApiKey) -> anyhow::Result<ApiKey>;
+    /// revoke_api_key stops the key from authenticating, returning false when it was not
+    /// found or already revoked.
+    async fn revoke_api_key(&self, id: uuid::Uuid) -> anyhow::Result<bool>;
+}
+
+#[async_trait]
+pub trait ApiKeyReader: 'static + Send + Sync {
+    /// get_api_key_by_hash returns the key with the hash unless it was revoked.
+    async fn get_api_key_by_hash(&self, key_hash: &str) -> anyhow::Result<Option<ApiKey>>;
+}
+
+impl ApiKeyRepository for PgApiRepository {}
+
+/// RateLimitStore holds the token buckets. `InMemoryRateLimitStore` limits each replica on
+/// its own, `PgApiRepository` shares the buckets between replicas.
+#[async_trait]
+pub trait RateLimitStore: 'static + Send + Sync {
+    /// take_token takes a token from the bucket of the key, which starts out full.
+    async fn take_token(
+        &self,
+        key: &str,
+        limit: &RateLimit,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<Decision>;
+}
@@ -0,0 +1,144 @@
+use crate::domain::rate_limit::{Bucket, Decision, RateLimit};
+use crate::repo::{PgApiRepository, RateLimitStore};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// InMemoryRateLimitStore keeps the buckets of this replica.
+#[derive(Debug, Default)]
+pub struct InMemoryRateLimitStore {
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+#[async_trait]
+impl RateLimitStore for InMemoryRateLimitStore {
+    async fn take_token(
+        &self,
+        key: &str,
+        limit: &RateLimit,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<Decision> {
+        let mut buckets = self
+            .buckets
+            .lock()
+            .expect("rate limit buckets lock poisoned");
+        let bucket = buckets
+            .get(key)
+            .copied()
+            .unwrap_or_else(|| Bucket::full(limit, now));
+        let (bucket, decision) = bucket.take(limit, now);
+        buckets.insert(key.to_string(), bucket);
+
+        Ok(decision)
+    }
+}
+
+/// The bucket row is locked while a token is taken, so concurrent requests on different
+/// replicas cannot take the same token.
+#[async_trait]
+impl RateLimitStore for PgApiRepository {
+    async fn take_token(
+        &self,
+        key: &str,
+        limit: &RateLimit,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<Decision> {
+        let mut tx = self.db.writer.begin().await?;
+        let full = Bucket::full(limit, now);
+        let result = sqlx::query(
+            r#"
+            INSERT INTO rate_limit_buckets (key, tokens, updated_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (key) DO NOTHING
+            "#,
+        )
+        .bind(key)
+        .bind(full.tokens)
+        .bind(full.updated_at)
+        .execute(&mut *tx)
+        .await;
+        if let Err(e) = result {
+            anyhow::bail!("Failed to insert rate limit bucket into database: {e}")
+        }
+
+        let result = sqlx::query_as::<_, (f64, chrono::DateTime<chrono::Utc>)>(
+            r#"
+            SELECT tokens, updated_at
+            FROM rate_limit_buckets
+            WHERE key = $1
+            FOR UPDATE
+            "#,
+        )
+        .bind(key)
+        .fetch_one(&mut *tx)
+        .await;
+        let bucket = match result {
+            Ok((tokens, updated_at)) => Bucket { tokens, updated_at },
+            Err(e) => anyhow::bail!("Failed to get rate limit bucket from database: {e}"),
+        };
+
+        let (bucket, decision) = bucket.take(limit, now);
+        let result = sqlx::query(
+            r#"
+            UPDATE rate_limit_buckets
+            SET tokens = $2, updated_at = $3
+            WHERE key = $1
+            "#,
+        )
+        .bind(key)
+        .bind(bucket.tokens)
+        .bind(bucket.updated_at)
+        .execute(&mut *tx)
+        .await;
+        if let Err(e) = result {
+            anyhow::bail!("Failed to update rate limit bucket: {e}")
+        }
+        tx.commit().await?;
+
+        Ok(decision)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    async fn take_tokens(store: &dyn RateLimitStore) {
+        // arrange
+        let limit = RateLimit::new(2, Duration::from_secs(60));
+        let now = chrono::Utc::now();
+
+        // act
+        let first = store.take_token("merchant-a", &limit, now).await.unwrap();
+        let second = store.take_token("merchant-a", &limit, now).await.unwrap();
+        let denied = store.take_token("merchant-a", &limit, now).await.unwrap();
+        let other = store.take_token("merchant-b", &limit, now).await.unwrap();
+        let refilled = store
+            .take_token("merchant-a", &limit, now + chrono::Duration::seconds(30))
+            .await
+            .unwrap();
+
+        // assert
+        assert!(first.allowed);
+        assert_eq!(first.remaining, 1);
+        assert!(second.allowed);
+        assert_eq!(second.remaining, 0);
+        assert!(!denied.allowed);
+        assert_eq!(denied.retry_after_seconds, Some(30));
+        assert!(other.allowed);
+        assert!(refilled.allowed);
+        assert_eq!(refilled.remaining, 0);
+    }
+
+    #[tokio::test]
+    async fn successfully_take_tokens_in_memory() {
+        take_tokens(&InMemoryRateLimitStore::default()).await;
+    }
+
+    #[sqlx::test(migrations = "../migrations/api")]
+    async fn successfully_take_tokens_in_postgres(pool: sqlx::PgPool) {
+        let repo = PgApiRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        take_tokens(&repo).await;
+    }
+}
@@ -0,0 +1,103 @@
+use crate::helpers;
+use serde_json::{Value, json};
+
+#[tokio::test]
+async fn successfully_create_account_and_get_it_by_id() {
+    // arrange
+    let app = helpers::spawn_app().await;
+    let base_url = app.base_url.as_str();
+    let client = helpers::client(app.operator_key.as_str());
+
+    // act
+    let response = client
+        .post(format!("{base_url}/v1/accounts"))
+        .json(&json!({"name": "coffee shop", "type": "merchant"}))
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(response.status(), reqwest::StatusCode::CREATED);
+    let account: Value = response.json().await.unwrap();
+    assert_eq!(account["type"], "merchant");
+    assert_eq!(account["status"], "active");
+    assert_eq!(account["created_by"], "ops");
+
+    let response = client
+        .get(format!(
+            "{base_url}/v1/accounts/{}",
+            account["id"].as_str().unwrap()
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let found: Value = response.json().await.unwrap();
+    assert_eq!(found, account);
+}
+
+#[tokio::test]
+async fn test_account_errors() {
+    #[derive(Debug)]
+    struct TestCase {
+        name: &'static str,
+        request: reqwest::RequestBuilder,
+        expected_status: reqwest::StatusCode,
+        expected_code: &'static str,
+    }
+
+    let app = helpers::spawn_app().await;
+    let base_url = app.base_url.as_str();
+    let client = helpers::client(app.operator_key.as_str());
+    let test_cases = vec![
+        TestCase {
+            name: "not found when account does not exist",
+            request: client.get(format!("{base_url}/v1/accounts/{}", uuid::Uuid::new_v4())),
+            expected_status: reqwest::StatusCode::NOT_FOUND,
+            expected_code: "not_found",
+        },
+        TestCase {
+            name: "unprocessable when account type is unknown",
+            request: client
+                .post(format!("{base_url}/v1/accounts"))
+                .json(&json!({"name": "coffee shop", "type": "bank"})),
+            expected_status: reqwest::StatusCode::UNPROCESSABLE_ENTITY,
+            expected_code: "invalid_argument",
+        },
+        TestCase {
+            name: "bad request when body is not json",
+            request: client
+                .post(format!("{base_url}/v1/accounts"))
+                .header("content-type", "application/json")
+                .body("{"),
+            expected_status: reqwest::StatusCode::BAD_REQUEST,
+            expected_code: "invalid_argument",
+        },
+        TestCase {
+            name: "not found when route does not exist",
+            request: client.get(format!("{base_url}/v1/merchants")),
+            expected_status: reqwest::StatusCode::NOT_FOUND,
+            expected_code: "not_found",
+        },
+    ];
+
+    for test_case in test_cases {
+        // act
+        let response = test_case.request.send().await.unwrap();
+
+        // assert
+        assert_eq!(
+            response.status(),
+            test_case.expected_status,
+            "{}",
+            test_case.name
+        );
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(
+            body["error"]["code"], test_case.expected_code,
+            "{}",
+            test_case.name
+        );
+        assert!(body["error"]["message"].is_string(), "{}", test_case.name);
+    }
+}
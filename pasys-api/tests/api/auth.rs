@@ -0,0 +1,241 @@
+use crate::helpers;
+use serde_json::{Value, json};
+
+#[tokio::test]
+async fn test_unauthenticated_requests() {
+    #[derive(Debug)]
+    struct TestCase {
+        name: &'static str,
+        request: reqwest::RequestBuilder,
+    }
+
+    let app = helpers::spawn_app().await;
+    let base_url = app.base_url.as_str();
+    let client = reqwest::Client::new();
+    let test_cases = vec![
+        TestCase {
+            name: "unauthorized without credentials",
+            request: client.get(format!("{base_url}/v1/accounts/{}", uuid::Uuid::new_v4())),
+        },
+        TestCase {
+            name: "unauthorized with unknown api key",
+            request: client
+                .post(format!("{base_url}/v1/transactions"))
+                .header("x-api-key", "pasys_unknown")
+                .json(&helpers::transaction_request(app.merchant_account_id)),
+        },
+        TestCase {
+            name: "unauthorized with bearer token while jwks is not configured",
+            request: client
+                .get(format!(
+                    "{base_url}/v1/transactions/{}",
+                    uuid::Uuid::new_v4()
+                ))
+                .bearer_auth("a.b.c"),
+        },
+    ];
+
+    for test_case in test_cases {
+        // act
+        let response = test_case.request.send().await.unwrap();
+
+        // assert
+        assert_eq!(
+            response.status(),
+            reqwest::StatusCode::UNAUTHORIZED,
+            "{}",
+            test_case.name
+        );
+        assert_eq!(
+            response.headers()["www-authenticate"],
+            "Bearer",
+            "{}",
+            test_case.name
+        );
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(
+            body["error"]["code"], "unauthenticated",
+            "{}",
+            test_case.name
+        );
+    }
+}
+
+#[tokio::test]
+async fn successfully_call_health_check_without_credentials() {
+    let app = helpers::spawn_app().await;
+
+    let response = reqwest::get(format!("{}/health", app.base_url))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+}
+
+#[tokio::test]
+async fn successfully_scope_merchant_to_its_accounts() {
+    // arrange
+    let app = helpers::spawn_app().await;
+    let base_url = app.base_url.as_str();
+    let operator = helpers::client(app.operator_key.as_str());
+    let merchant = helpers::client(app.merchant_key.as_str());
+    let other_merchant = helpers::client(app.other_merchant_key.as_str());
+    let account: Value = operator
+        .post(format!("{base_url}/v1/accounts"))
+        .json(&json!({"name": "coffee shop", "type": "merchant"}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    // act
+    let created = merchant
+        .post(format!("{base_url}/v1/transactions"))
+        .json(&helpers::transaction_request(app.merchant_account_id))
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(created.status(), reqwest::StatusCode::CREATED);
+    let created: Value = created.json().await.unwrap();
+    let transaction_url = format!(
+        "{base_url}/v1/transactions/{}",
+        created["id"].as_str().unwrap()
+    );
+    let response = merchant.get(&transaction_url).send().await.unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let response = other_merchant.get(&transaction_url).send().await.unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+
+    let response = merchant
+        .get(format!(
+            "{base_url}/v1/accounts/{}",
+            account["id"].as_str().unwrap()
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+
+    let response = merchant
+        .post(format!("{base_url}/v1/accounts"))
+        .json(&json!({"name": "coffee shop", "type": "merchant"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::FORBIDDEN);
+
+    let response = merchant
+        .post(format!("{base_url}/v1/transactions"))
+        .json(&helpers::transaction_request(uuid::Uuid::new_v4()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::FORBIDDEN);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"]["code"], "permission_denied");
+}
+
+#[tokio::test]
+async fn test_transfer_scope() {
+    #[derive(Debug)]
+    struct TestCase {
+        name: &'static str,
+        debit_account_id: String,
+        credit_account_id: String,
+        expected_status: reqwest::StatusCode,
+    }
+
+    let app = helpers::spawn_app().await;
+    let base_url = app.base_url.as_str();
+    let operator = helpers::client(app.operator_key.as_str());
+    let merchant = helpers::client(app.merchant_key.as_str());
+    let mut account_ids = Vec::new();
+    for account_type in ["merchant", "customer"] {
+        let account: Value = operator
+            .post(format!("{base_url}/v1/accounts"))
+            .json(&json!({"name": account_type, "type": account_type}))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        account_ids.push(account["id"].as_str().unwrap().to_string());
+    }
+    let test_cases = vec![
+        TestCase {
+            name: "forbidden when the debit account belongs to someone else",
+            debit_account_id: account_ids[1].clone(),
+            credit_account_id: app.merchant_account_id.to_string(),
+            expected_status: reqwest::StatusCode::FORBIDDEN,
+        },
+        TestCase {
+            name: "forbidden when paying the account of another merchant",
+            debit_account_id: app.merchant_account_id.to_string(),
+            credit_account_id: account_ids[0].clone(),
+            expected_status: reqwest::StatusCode::FORBIDDEN,
+        },
+        TestCase {
+            name: "created when paying a customer out of the own account",
+            debit_account_id: app.merchant_account_id.to_string(),
+            credit_account_id: account_ids[1].clone(),
+            expected_status: reqwest::StatusCode::CREATED,
+        },
+    ];
+
+    for test_case in test_cases {
+        // act
+        let response = merchant
+            .post(format!("{base_url}/v1/transactions"))
+            .json(&json!({
+                "debit_account_id": test_case.debit_account_id,
+                "credit_account_id": test_case.credit_account_id,
+                "amount_minor": 1050,
+                "currency": "USD",
+                "idempotency_key": uuid::Uuid::new_v4().to_string(),
+            }))
+            .send()
+            .await
+            .unwrap();
+
+        // assert
+        assert_eq!(
+            response.status(),
+            test_case.expected_status,
+            "{}",
+            test_case.name
+        );
+    }
+}
+
+#[tokio::test]
+async fn successfully_scope_idempotency_keys_to_principal() {
+    // arrange
+    let app = helpers::spawn_app().await;
+    let base_url = app.base_url.as_str();
+    let send = |api_key: &str| {
+        helpers::client(api_key)
+            .post(format!("{base_url}/v1/transactions"))
+            .header("Idempotency-Key", "order-1")
+            .json(&json!({
+                "debit_account_id": app.merchant_account_id.to_string(),
+                "credit_account_id": uuid::Uuid::new_v4().to_string(),
+                "amount_minor": 1050,
+                "currency": "USD",
+            }))
+            .send()
+    };
+    let first = send(app.merchant_key.as_str()).await.unwrap();
+    assert_eq!(first.status(), reqwest::StatusCode::CREATED);
+
+    // act
+    let response = send(app.operator_key.as_str()).await.unwrap();
+
+    // assert
+    assert_eq!(response.status(), reqwest::StatusCode::CREATED);
+    assert!(response.headers().get("idempotent-replayed").is_none());
+}
@@ -0,0 +1,534 @@
+use accounts_proto::accounts_v1::accounts_client::AccountsClient;
+use accounts_proto::accounts_v1::{self, accounts_server};
+use async_trait::async_trait;
+use ledger_proto::ledger_v1::ledger_client::LedgerClient;
+use ledger_proto::ledger_v1::{self, ledger_server};
+use pasys_api::api::rate_limit::RateLimiter;
+use pasys_api::api::{self, AppState};
+use pasys_api::auth::Authenticator;
+use pasys_api::domain::api_key::ApiKey;
+use pasys_api::domain::principal::AccountScope;
+use pasys_api::domain::rate_limit::{RateLimit, RateLimits};
+use pasys_api::repo::{ApiKeyReader, InMemoryRateLimitStore};
+use pasys_api::{DEFAULT_RATE_LIMIT_REQUESTS, DEFAULT_RATE_LIMIT_WINDOW_SECONDS};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tonic::codegen::tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::{Channel, Server};
+
+/// FakeAccounts keeps accounts in memory in place of the accounts service.
+#[derive(Default)]
+pub struct FakeAccounts {
+    accounts: Mutex<HashMap<String, accounts_v1::Account>>,
+}
+
+#[async_trait]
+impl accounts_server::Accounts for FakeAccounts {
+    async fn health_check(
+        &self,
+        _request: tonic::Request<()>,
+    ) -> Result<tonic::Response<()>, tonic::Status> {
+        Ok(tonic::Response::new(()))
+    }
+
+    async fn create_account(
+        &self,
+        request: tonic::Request<accounts_v1::CreateAccountRequest>,
+    ) -> Result<tonic::Response<accounts_v1::CreateAccountResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let now = prost_types::Timestamp::from(std::time::SystemTime::now());
+        let account = accounts_v1::Account {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: request.name,
+            r#type: request.r#type,
+            status: accounts_v1::AccountStatus::Active as i32,
+            created_by: request.created_by,
+            created_at: Some(now),
+            updated_at: Some(now),
+        };
+        self.accounts
+            .lock()
+            .unwrap()
+            .insert(account.id.clone(), account.clone());
+
+        Ok(tonic::Response::new(accounts_v1::CreateAccountResponse {
+            account: Some(account),
+        }))
+    }
+
+    async fn get_accounts(
+        &self,
+        _request: tonic::Request<accounts_v1::GetAccountsRequest>,
+    ) -> Result<tonic::Response<accounts_v1::GetAccountsResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented("get_accounts"))
+    }
+
+    async fn get_account(
+        &self,
+        request: tonic::Request<accounts_v1::GetAccountRequest>,
+    ) -> Result<tonic::Response<accounts_v1::GetAccountResponse>, tonic::Status> {
+        let id = request.into_inner().account_id;
+        match self.accounts.lock().unwrap().get(&id) {
+            Some(account) => Ok(tonic::Response::new(accounts_v1::GetAccountResponse {
+                account: Some(account.clone()),
+            })),
+            None => Err(tonic::Status::not_found(format!("account {id} not found"))),
+        }
+    }
+    async fn update_account_status(
+        &self,
+        _request: tonic::Request<accounts_v1::UpdateAccountStatusRequest>,
+    ) -> Result<tonic::Response<accounts_v1::UpdateAccountStatusResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented("update_account_status"))
+    }
+}
+
+/// FakeLedger keeps transactions and schedules in memory in place of the ledger service,
+/// answering a repeated idempotency key with the transaction created first and failing with
+/// `internal` when the amount is 13 minor units.
+#[derive(Default)]
+pub struct FakeLedger {
+    transactions: Mutex<HashMap<String, ledger_v1::Transaction>>,
+    schedules: Mutex<HashMap<String, ledger_v1::Schedule>>,
+}
+
+impl FakeLedger {
+    /// move_schedule sets the status of the schedule if it is in one of the `from` statuses.
+    fn move_schedule(
+        &self,
+        id: &str,
+        from: &[ledger_v1::ScheduleStatus],
+        status: ledger_v1::ScheduleStatus,
+    ) -> Result<ledger_v1::Schedule, tonic::Status> {
+        let mut schedules = self.schedules.lock().unwrap();
+        let Some(schedule) = schedules.get_mut(id) else {
+            return Err(tonic::Status::not_found(format!("schedule {id} not found")));
+        };
+        if !from.iter().any(|from| *from as i32 == schedule.status) {
+            return Err(tonic::Status::failed_precondition(format!(
+                "schedule {id} cannot be moved to {}",
+                status.as_str_name()
+            )));
+        }
+        schedule.status = status as i32;
+
+        Ok(schedule.clone())
+    }
+}
+
+#[async_trait]
+impl ledger_server::Ledger for FakeLedger {
+    async fn health_check(
+        &self,
+        _request: tonic::Request<()>,
+    ) -> Result<tonic::Response<()>, tonic::Status> {
+        Ok(tonic::Response::new(()))
+    }
+
+    async fn create_transaction(
+        &self,
+        request: tonic::Request<ledger_v1::CreateTransactionRequest>,
+    ) -> Result<tonic::Response<ledger_v1::CreateTransactionResponse>, tonic::Status> {
+        let request = request.into_inner();
+        if request.idempotency_key.is_empty() {
+            return Err(tonic::Status::invalid_argument(
+                "idempotency_key must be set",
+            ));
+        }
+        if request
+            .amount
+            .as_ref()
+            .is_some_and(|amount| amount.nanos == 13 * 10_000_000)
+        {
+            return Err(tonic::Status::internal(
+                "failed to create transaction: pool timed out",
+            ));
+        }
+
+        if let Some(existing) = self
+            .transactions
+            .lock()
+            .unwrap()
+            .values()
+            .find(|transaction| transaction.idempotency_key == request.idempotency_key)
+        {
+            return Ok(tonic::Response::new(ledger_v1::CreateTransactionResponse {
+                transaction_id: existing.id.clone(),
+                transaction_status: existing.status,
+            }));
+        }
+
+        let transaction = ledger_v1::Transaction {
+            id: uuid::Uuid::new_v4().to_string(),
+            debit_account_id: request.debit_account_id,
+            credit_account_id: request.credit_account_id,
+            amount: request.amount,
+            status: ledger_v1::TransactionStatus::Init as i32,
+            idempotency_key: request.idempotency_key,
+            request_timestamp: request.request_timestamp,
+            created_at: Some(prost_types::Timestamp::from(std::time::SystemTime::now())),
+            updated_at: Some(prost_types::Timestamp::from(std::time::SystemTime::now())),
+            fx: None,
+        };
+        self.transactions
+            .lock()
+            .unwrap()
+            .insert(transaction.id.clone(), transaction.clone());
+
+        Ok(tonic::Response::new(ledger_v1::CreateTransactionResponse {
+            transaction_id: transaction.id,
+            transaction_status: transaction.status,
+        }))
+    }
+
+    async fn get_transaction(
+        &self,
+        request: tonic::Request<ledger_v1::GetTransactionRequest>,
+    ) -> Result<tonic::Response<ledger_v1::GetTransactionResponse>, tonic::Status> {
+        let id = request.into_inner().transaction_id;
+        match self.transactions.lock().unwrap().get(&id) {
+            Some(transaction) => Ok(tonic::Response::new(ledger_v1::GetTransactionResponse {
+                transaction: Some(transaction.clone()),
+            })),
+            None => Err(tonic::Status::not_found(format!(
+                "transaction {id} not found"
+            ))),
+        }
+    }
+
+    async fn list_transactions(
+        &self,
+        _request: tonic::Request<ledger_v1::ListTransactionsRequest>,
+    ) -> Result<tonic::Response<ledger_v1::ListTransactionsResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented("list_transactions"))
+    }
+
+    async fn post_journal_entry(
+        &self,
+        _request: tonic::Request<ledger_v1::PostJournalEntryRequest>,
+    ) -> Result<tonic::Response<ledger_v1::PostJournalEntryResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented("post_journal_entry"))
+    }
+
+    async fn get_journal_entry(
+        &self,
+        _request: tonic::Request<ledger_v1::GetJournalEntryRequest>,
+    ) -> Result<tonic::Response<ledger_v1::GetJournalEntryResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented("get_journal_entry"))
+    }
+
+    async fn get_transaction_details(
+        &self,
+        _request: tonic::Request<ledger_v1::GetTransactionDetailsRequest>,
+    ) -> Result<tonic::Response<ledger_v1::GetTransactionDetailsResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented("get_transaction_details"))
+    }
+
+    async fn count_transactions(
+        &self,
+        _request: tonic::Request<ledger_v1::CountTransactionsRequest>,
+    ) -> Result<tonic::Response<ledger_v1::CountTransactionsResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented("count_transactions"))
+    }
+
+    async fn get_balances(
+        &self,
+        _request: tonic::Request<ledger_v1::GetBalancesRequest>,
+    ) -> Result<tonic::Response<ledger_v1::GetBalancesResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented("get_balances"))
+    }
+
+    async fn set_fee_schedule(
+        &self,
+        _request: tonic::Request<ledger_v1::SetFeeScheduleRequest>,
+    ) -> Result<tonic::Response<ledger_v1::SetFeeScheduleResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented("set_fee_schedule"))
+    }
+
+    async fn get_fee_schedules(
+        &self,
+        _request: tonic::Request<ledger_v1::GetFeeSchedulesRequest>,
+    ) -> Result<tonic::Response<ledger_v1::GetFeeSchedulesResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented("get_fee_schedules"))
+    }
+
+    async fn authorize_hold(
+        &self,
+        _request: tonic::Request<ledger_v1::AuthorizeHoldRequest>,
+    ) -> Result<tonic::Response<ledger_v1::AuthorizeHoldResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented("authorize_hold"))
+    }
+
+    async fn capture_hold(
+        &self,
+        _request: tonic::Request<ledger_v1::CaptureHoldRequest>,
+    ) -> Result<tonic::Response<ledger_v1::CaptureHoldResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented("capture_hold"))
+    }
+
+    async fn void_hold(
+        &self,
+        _request: tonic::Request<ledger_v1::VoidHoldRequest>,
+    ) -> Result<tonic::Response<ledger_v1::VoidHoldResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented("void_hold"))
+    }
+
+    async fn get_hold(
+        &self,
+        _request: tonic::Request<ledger_v1::GetHoldRequest>,
+    ) -> Result<tonic::Response<ledger_v1::GetHoldResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented("get_hold"))
+    }
+
+    async fn create_refund(
+        &self,
+        _request: tonic::Request<ledger_v1::CreateRefundRequest>,
+    ) -> Result<tonic::Response<ledger_v1::CreateRefundResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented("create_refund"))
+    }
+
+    async fn approve_refund(
+        &self,
+        _request: tonic::Request<ledger_v1::DecideRefundRequest>,
+    ) -> Result<tonic::Response<ledger_v1::DecideRefundResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented("approve_refund"))
+    }
+
+    async fn reject_refund(
+        &self,
+        _request: tonic::Request<ledger_v1::DecideRefundRequest>,
+    ) -> Result<tonic::Response<ledger_v1::DecideRefundResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented("reject_refund"))
+    }
+
+    async fn get_refunds(
+        &self,
+        _request: tonic::Request<ledger_v1::GetRefundsRequest>,
+    ) -> Result<tonic::Response<ledger_v1::GetRefundsResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented("get_refunds"))
+    }
+
+    async fn create_schedule(
+        &self,
+        request: tonic::Request<ledger_v1::CreateScheduleRequest>,
+    ) -> Result<tonic::Response<ledger_v1::CreateScheduleResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let now = prost_types::Timestamp::from(std::time::SystemTime::now());
+        let starts_at = request.starts_at.unwrap_or(now);
+        let schedule = ledger_v1::Schedule {
+            id: uuid::Uuid::new_v4().to_string(),
+            debit_account_id: request.debit_account_id,
+            credit_account_id: request.credit_account_id,
+            amount: request.amount,
+            cron: request.cron,
+            interval_seconds: request.interval_seconds,
+            starts_at: Some(starts_at),
+            ends_at: request.ends_at,
+            next_run_at: Some(starts_at),
+            status: ledger_v1::ScheduleStatus::Active as i32,
+            idempotency_key: request.idempotency_key,
+            created_at: Some(now),
+            updated_at: Some(now),
+        };
+        self.schedules
+            .lock()
+            .unwrap()
+            .insert(schedule.id.clone(), schedule.clone());
+
+        Ok(tonic::Response::new(ledger_v1::CreateScheduleResponse {
+            schedule: Some(schedule),
+        }))
+    }
+
+    async fn get_schedule(
+        &self,
+        request: tonic::Request<ledger_v1::GetScheduleRequest>,
+    ) -> Result<tonic::Response<ledger_v1::GetScheduleResponse>, tonic::Status> {
+        let id = request.into_inner().schedule_id;
+        match self.schedules.lock().unwrap().get(&id) {
+            Some(schedule) => Ok(tonic::Response::new(ledger_v1::GetScheduleResponse {
+                schedule: Some(schedule.clone()),
+            })),
+            None => Err(tonic::Status::not_found(format!("schedule {id} not found"))),
+        }
+    }
+
+    async fn list_schedules(
+        &self,
+        _request: tonic::Request<ledger_v1::ListSchedulesRequest>,
+    ) -> Result<tonic::Response<ledger_v1::ListSchedulesResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented("list_schedules"))
+    }
+
+    async fn list_schedule_runs(
+        &self,
+        _request: tonic::Request<ledger_v1::ListScheduleRunsRequest>,
+    ) -> Result<tonic::Response<ledger_v1::ListScheduleRunsResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented("list_schedule_runs"))
+    }
+
+    async fn pause_schedule(
+        &self,
+        request: tonic::Request<ledger_v1::ScheduleActionRequest>,
+    ) -> Result<tonic::Response<ledger_v1::ScheduleActionResponse>, tonic::Status> {
+        let schedule = self.move_schedule(
+            request.into_inner().schedule_id.as_str(),
+            &[ledger_v1::ScheduleStatus::Active],
+            ledger_v1::ScheduleStatus::Paused,
+        )?;
+
+        Ok(tonic::Response::new(ledger_v1::ScheduleActionResponse {
+            schedule: Some(schedule),
+        }))
+    }
+
+    async fn resume_schedule(
+        &self,
+        request: tonic::Request<ledger_v1::ScheduleActionRequest>,
+    ) -> Result<tonic::Response<ledger_v1::ScheduleActionResponse>, tonic::Status> {
+        let schedule = self.move_schedule(
+            request.into_inner().schedule_id.as_str(),
+            &[ledger_v1::ScheduleStatus::Paused],
+            ledger_v1::ScheduleStatus::Active,
+        )?;
+
+        Ok(tonic::Response::new(ledger_v1::ScheduleActionResponse {
+            schedule: Some(schedule),
+        }))
+    }
+
+    async fn cancel_schedule(
+        &self,
+        request: tonic::Request<ledger_v1::ScheduleActionRequest>,
+    ) -> Result<tonic::Response<ledger_v1::ScheduleActionResponse>, tonic::Status> {
+        let schedule = self.move_schedule(
+            request.into_inner().schedule_id.as_str(),
+            &[
+                ledger_v1::ScheduleStatus::Active,
+                ledger_v1::ScheduleStatus::Paused,
+            ],
+            ledger_v1::ScheduleStatus::Cancelled,
+        )?;
+
+        Ok(tonic::Response::new(ledger_v1::ScheduleActionResponse {
+            schedule: Some(schedule),
+        }))
+    }
+
+    async fn skip_schedule_run(
+        &self,
+        _request: tonic::Request<ledger_v1::ScheduleActionRequest>,
+    ) -> Result<tonic::Response<ledger_v1::SkipScheduleRunResponse>, tonic::Status> {
+        Err(tonic::Status::unimplemented("skip_schedule_run"))
+    }
+}
+
+/// FakeApiKeys keeps api keys in memory in place of the api database.
+pub struct FakeApiKeys(Vec<ApiKey>);
+
+#[async_trait]
+impl ApiKeyReader for FakeApiKeys {
+    async fn get_api_key_by_hash(&self, key_hash: &str) -> anyhow::Result<Option<ApiKey>> {
+        Ok(self.0.iter().find(|key| key.key_hash == key_hash).cloned())
+    }
+}
+
+/// TestApp is a running gateway with an operator, allowed every account, and two merchants
+/// each owning one account.
+pub struct TestApp {
+    pub base_url: String,
+    pub operator_key: String,
+    pub merchant_key: String,
+    pub merchant_account_id: uuid::Uuid,
+    pub other_merchant_key: String,
+}
+
+/// spawn_app starts the fake grpc services and the gateway in front of them.
+pub async fn spawn_app() -> TestApp {
+    spawn_app_with_rate_limits(RateLimits::new(RateLimit::new(
+        DEFAULT_RATE_LIMIT_REQUESTS,
+        Duration::from_secs(DEFAULT_RATE_LIMIT_WINDOW_SECONDS),
+    )))
+    .await
+}
+
+/// spawn_app_with_rate_limits starts the gateway limiting requests in memory.
+pub async fn spawn_app_with_rate_limits(limits: RateLimits) -> TestApp {
+    spawn_app_with(|state| {
+        state.with_rate_limiter(RateLimiter::new(
+            limits,
+            Arc::new(InMemoryRateLimitStore::default()),
+        ))
+    })
+    .await
+}
+
+/// spawn_app_with starts the gateway with the state changed by `configure`.
+pub async fn spawn_app_with(configure: impl FnOnce(AppState) -> AppState) -> TestApp {
+    let grpc_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let grpc_addr = grpc_listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        Server::builder()
+            .add_service(accounts_server::AccountsServer::new(FakeAccounts::default()))
+            .add_service(ledger_server::LedgerServer::new(FakeLedger::default()))
+            .serve_with_incoming(TcpListenerStream::new(grpc_listener))
+            .await
+            .unwrap()
+    });
+
+    let merchant_account_id = uuid::Uuid::new_v4();
+    let (operator, operator_key) = ApiKey::generate("ops", AccountScope::All).unwrap();
+    let (merchant, merchant_key) =
+        ApiKey::generate("merchant-a", AccountScope::Only(vec![merchant_account_id])).unwrap();
+    let (other_merchant, other_merchant_key) =
+        ApiKey::generate("merchant-b", AccountScope::Only(vec![uuid::Uuid::new_v4()])).unwrap();
+    let authenticator = Authenticator::new(Arc::new(FakeApiKeys(vec![
+        operator,
+        merchant,
+        other_merchant,
+    ])));
+
+    let channel = Channel::from_shared(format!("http://{grpc_addr}"))
+        .unwrap()
+        .connect_lazy();
+    let state = configure(AppState::new(
+        AccountsClient::new(channel.clone()),
+        LedgerClient::new(channel),
+        authenticator,
+    ));
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, api::router(state)).await.unwrap() });
+
+    TestApp {
+        base_url: format!("http://{addr}"),
+        operator_key,
+        merchant_key,
+        merchant_account_id,
+        other_merchant_key,
+    }
+}
+
+/// client sends every request with the api key.
+pub fn client(api_key: &str) -> reqwest::Client {
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("x-api-key", api_key.parse().unwrap());
+    reqwest::Client::builder()
+        .default_headers(headers)
+        .build()
+        .unwrap()
+}
+
+/// transaction_request is a USD transfer out of the debit account to a new account.
+pub fn transaction_request(debit_account_id: uuid::Uuid) -> serde_json::Value {
+    serde_json::json!({
+        "debit_account_id": debit_account_id.to_string(),
+        "credit_account_id": uuid::Uuid::new_v4().to_string(),
+        "amount_minor": 1050,
+        "currency": "USD",
+        "idempotency_key": uuid::Uuid::new_v4().to_string(),
+    })
+}
@@ -0,0 +1,192 @@
+use crate::helpers;
+use pasys_api::api::idempotency::ledger_idempotency_key;
+use pasys_api::domain::principal::{AccountScope, Principal};
+use serde_json::{Value, json};
+
+fn transaction_request(amount_minor: i64) -> Value {
+    json!({
+        "debit_account_id": "0d6cb2b4-9a8e-4f43-8f6e-7a4c56b1c0de",
+        "credit_account_id": "5b1f6f7e-2f0a-4a8a-9b9e-2f4d1d3c8a11",
+        "amount_minor": amount_minor,
+        "currency": "USD",
+    })
+}
+
+#[tokio::test]
+async fn successfully_replay_first_response_of_idempotency_key() {
+    // arrange
+    let app = helpers::spawn_app().await;
+    let base_url = app.base_url.as_str();
+    let client = helpers::client(app.operator_key.as_str());
+    let send = || {
+        client
+            .post(format!("{base_url}/v1/transactions"))
+            .header("Idempotency-Key", "\"order-1\"")
+            .json(&transaction_request(1050))
+            .send()
+    };
+    let first = send().await.unwrap();
+    assert_eq!(first.status(), reqwest::StatusCode::CREATED);
+    assert!(first.headers().get("idempotent-replayed").is_none());
+    let first: Value = first.json().await.unwrap();
+
+    // act
+    let replayed = send().await.unwrap();
+
+    // assert
+    assert_eq!(replayed.status(), reqwest::StatusCode::CREATED);
+    assert_eq!(replayed.headers()["idempotent-replayed"], "true");
+    assert_eq!(replayed.headers()["content-type"], "application/json");
+    let replayed: Value = replayed.json().await.unwrap();
+    assert_eq!(replayed, first);
+
+    let transaction: Value = client
+        .get(format!(
+            "{base_url}/v1/transactions/{}",
+            first["id"].as_str().unwrap()
+        ))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(
+        transaction["idempotency_key"],
+        ledger_idempotency_key(&Principal::new("ops", AccountScope::All), "order-1")
+    );
+}
+
+#[tokio::test]
+async fn successfully_derive_ledger_key_per_principal() {
+    // arrange
+    let app = helpers::spawn_app().await;
+    let base_url = app.base_url.as_str();
+    let operator = helpers::client(app.operator_key.as_str());
+    let merchant = helpers::client(app.merchant_key.as_str());
+    let request = json!({
+        "debit_account_id": app.merchant_account_id.to_string(),
+        "credit_account_id": "5b1f6f7e-2f0a-4a8a-9b9e-2f4d1d3c8a11",
+        "amount_minor": 1050,
+        "currency": "USD",
+        "idempotency_key": "order-1",
+    });
+    let mut created = Vec::new();
+
+    // act
+    for client in [&merchant, &operator] {
+        let response = client
+            .post(format!("{base_url}/v1/transactions"))
+            .json(&request)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::CREATED);
+        let response: Value = response.json().await.unwrap();
+        created.push(response["id"].as_str().unwrap().to_string());
+    }
+
+    // assert
+    assert_ne!(created[0], created[1]);
+    let transaction: Value = operator
+        .get(format!("{base_url}/v1/transactions/{}", created[0]))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(
+        transaction["idempotency_key"],
+        ledger_idempotency_key(&Principal::new("merchant-a", AccountScope::All), "order-1")
+    );
+}
+
+#[tokio::test]
+async fn test_idempotency_key_errors() {
+    #[derive(Debug)]
+    struct TestCase {
+        name: &'static str,
+        idempotency_key: &'static str,
+        amount_minor: i64,
+        expected_status: reqwest::StatusCode,
+        expected_code: &'static str,
+    }
+
+    let app = helpers::spawn_app().await;
+    let base_url = app.base_url.as_str();
+    let client = helpers::client(app.operator_key.as_str());
+    let response = client
+        .post(format!("{base_url}/v1/transactions"))
+        .header("Idempotency-Key", "order-1")
+        .json(&transaction_request(1050))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::CREATED);
+
+    let test_cases = vec![
+        TestCase {
+            name: "unprocessable when key is reused with a different body",
+            idempotency_key: "order-1",
+            amount_minor: 2000,
+            expected_status: reqwest::StatusCode::UNPROCESSABLE_ENTITY,
+            expected_code: "idempotency_key_reused",
+        },
+        TestCase {
+            name: "bad request when key is empty",
+            idempotency_key: "\"\"",
+            amount_minor: 1050,
+            expected_status: reqwest::StatusCode::BAD_REQUEST,
+            expected_code: "invalid_argument",
+        },
+    ];
+
+    for test_case in test_cases {
+        // act
+        let response = client
+            .post(format!("{base_url}/v1/transactions"))
+            .header("Idempotency-Key", test_case.idempotency_key)
+            .json(&transaction_request(test_case.amount_minor))
+            .send()
+            .await
+            .unwrap();
+
+        // assert
+        assert_eq!(
+            response.status(),
+            test_case.expected_status,
+            "{}",
+            test_case.name
+        );
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(
+            body["error"]["code"], test_case.expected_code,
+            "{}",
+            test_case.name
+        );
+    }
+}
+
+#[tokio::test]
+async fn successfully_retry_key_after_server_error() {
+    // arrange
+    let app = helpers::spawn_app().await;
+    let base_url = app.base_url.as_str();
+    let client = helpers::client(app.operator_key.as_str());
+    let send = |amount_minor: i64| {
+        client
+            .post(format!("{base_url}/v1/transactions"))
+            .header("Idempotency-Key", "order-13")
+            .json(&transaction_request(amount_minor))
+            .send()
+    };
+    let failed = send(13).await.unwrap();
+    assert_eq!(failed.status(), reqwest::StatusCode::INTERNAL_SERVER_ERROR);
+
+    // act
+    let retried = send(1300).await.unwrap();
+
+    // assert
+    assert_eq!(retried.status(), reqwest::StatusCode::CREATED);
+}
@@ -0,0 +1,9 @@
+mod account;
+mod auth;
+mod helpers;
+mod idempotency;
+mod openapi;
+mod rate_limit;
+mod schedule;
+mod transaction;
+mod webhook;
@@ -0,0 +1,39 @@
+use crate::helpers;
+use serde_json::Value;
+
+#[tokio::test]
+async fn successfully_serve_openapi_document_without_credentials() {
+    // arrange
+    let app = helpers::spawn_app().await;
+    let committed: Value = serde_json::from_str(include_str!("../../openapi.json")).unwrap();
+
+    // act
+    let response = reqwest::get(format!("{}/openapi.json", app.base_url))
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let served: Value = response.json().await.unwrap();
+    assert_eq!(served, committed);
+}
+
+#[tokio::test]
+async fn successfully_serve_docs_ui_without_credentials() {
+    // arrange
+    let app = helpers::spawn_app().await;
+
+    // act
+    let response = reqwest::get(format!("{}/docs/", app.base_url))
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    assert!(
+        response.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/html")
+    );
+}
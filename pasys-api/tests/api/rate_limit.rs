@@ -0,0 +1,74 @@
+use crate::helpers;
+use pasys_api::domain::rate_limit::{RateLimit, RateLimits};
+use serde_json::Value;
+use std::time::Duration;
+
+#[tokio::test]
+async fn successfully_limit_principal_per_route() {
+    // arrange
+    let limits = RateLimits::new(RateLimit::new(100, Duration::from_secs(60))).with_route(
+        "POST /v1/transactions",
+        RateLimit::new(2, Duration::from_secs(60)),
+    );
+    let app = helpers::spawn_app_with_rate_limits(limits).await;
+    let base_url = app.base_url.as_str();
+    let merchant = helpers::client(app.merchant_key.as_str());
+    let send = || {
+        merchant
+            .post(format!("{base_url}/v1/transactions"))
+            .json(&helpers::transaction_request(app.merchant_account_id))
+            .send()
+    };
+    let first = send().await.unwrap();
+    assert_eq!(first.status(), reqwest::StatusCode::CREATED);
+    assert_eq!(first.headers()["ratelimit-limit"], "2");
+    assert_eq!(first.headers()["ratelimit-remaining"], "1");
+    assert_eq!(first.headers()["ratelimit-policy"], "2;w=60");
+    assert!(first.headers().get("retry-after").is_none());
+    let second = send().await.unwrap();
+    assert_eq!(second.status(), reqwest::StatusCode::CREATED);
+
+    // act
+    let response = send().await.unwrap();
+
+    // assert
+    assert_eq!(response.status(), reqwest::StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers()["ratelimit-remaining"], "0");
+    assert_eq!(response.headers()["retry-after"], "30");
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"]["code"], "resource_exhausted");
+
+    let response = merchant
+        .get(format!(
+            "{base_url}/v1/accounts/{}",
+            app.merchant_account_id
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.headers()["ratelimit-limit"], "100");
+    let response = helpers::client(app.operator_key.as_str())
+        .post(format!("{base_url}/v1/transactions"))
+        .json(&helpers::transaction_request(app.merchant_account_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn successfully_skip_rate_limit_of_unauthenticated_requests() {
+    // arrange
+    let limits = RateLimits::new(RateLimit::new(1, Duration::from_secs(60)));
+    let app = helpers::spawn_app_with_rate_limits(limits).await;
+    let url = format!("{}/v1/accounts/{}", app.base_url, uuid::Uuid::new_v4());
+
+    for _ in 0..3 {
+        // act
+        let response = reqwest::get(url.as_str()).await.unwrap();
+
+        // assert
+        assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+        assert!(response.headers().get("ratelimit-limit").is_none());
+    }
+}
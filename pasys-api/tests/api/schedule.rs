@@ -0,0 +1,186 @@
+use crate::helpers;
+use pasys_api::api::idempotency::ledger_idempotency_key;
+use pasys_api::domain::principal::{AccountScope, Principal};
+use serde_json::{Value, json};
+
+fn schedule_request(debit_account_id: &str, idempotency_key: &str) -> Value {
+    json!({
+        "debit_account_id": debit_account_id,
+        "credit_account_id": uuid::Uuid::new_v4().to_string(),
+        "amount_minor": 999,
+        "currency": "usd",
+        "cron": "0 9 1 * *",
+        "starts_at": "2025-11-05T09:00:00Z",
+        "idempotency_key": idempotency_key,
+    })
+}
+
+#[tokio::test]
+async fn successfully_create_pause_and_cancel_schedule() {
+    // arrange
+    let app = helpers::spawn_app().await;
+    let base_url = app.base_url.as_str();
+    let client = helpers::client(app.merchant_key.as_str());
+    let request = schedule_request(&app.merchant_account_id.to_string(), "subscription-1");
+
+    // act
+    let response = client
+        .post(format!("{base_url}/v1/schedules"))
+        .json(&request)
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(response.status(), reqwest::StatusCode::CREATED);
+    let created: Value = response.json().await.unwrap();
+    assert_eq!(created["status"], "active");
+    assert_eq!(created["cron"], "0 9 1 * *");
+    assert_eq!(created["interval_seconds"], Value::Null);
+    assert_eq!(created["amount_minor"], 999);
+    assert_eq!(created["currency"], "USD");
+    assert_eq!(
+        created["idempotency_key"],
+        ledger_idempotency_key(
+            &Principal::new("merchant-a", AccountScope::All),
+            "subscription-1"
+        )
+    );
+    let id = created["id"].as_str().unwrap();
+
+    let paused: Value = client
+        .post(format!("{base_url}/v1/schedules/{id}/pause"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(paused["status"], "paused");
+    let cancelled: Value = client
+        .post(format!("{base_url}/v1/schedules/{id}/cancel"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(cancelled["status"], "cancelled");
+    let response = client
+        .post(format!("{base_url}/v1/schedules/{id}/resume"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"]["code"], "failed_precondition");
+}
+
+#[tokio::test]
+async fn test_schedule_scope_errors() {
+    #[derive(Debug)]
+    struct TestCase {
+        name: &'static str,
+        request: reqwest::RequestBuilder,
+        expected_status: reqwest::StatusCode,
+        expected_code: &'static str,
+    }
+
+    let app = helpers::spawn_app().await;
+    let base_url = app.base_url.as_str();
+    let merchant = helpers::client(app.merchant_key.as_str());
+    let other_merchant = helpers::client(app.other_merchant_key.as_str());
+    let other_merchant_account: Value = helpers::client(app.operator_key.as_str())
+        .post(format!("{base_url}/v1/accounts"))
+        .json(&json!({"name": "book shop", "type": "merchant"}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let mut pay_other_merchant =
+        schedule_request(&app.merchant_account_id.to_string(), "subscription-3");
+    pay_other_merchant["credit_account_id"] = other_merchant_account["id"].clone();
+    let created: Value = merchant
+        .post(format!("{base_url}/v1/schedules"))
+        .json(&schedule_request(
+            &app.merchant_account_id.to_string(),
+            "subscription-1",
+        ))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let id = created["id"].as_str().unwrap();
+    let test_cases = vec![
+        TestCase {
+            name: "forbidden when the debit account belongs to someone else",
+            request: other_merchant
+                .post(format!("{base_url}/v1/schedules"))
+                .json(&schedule_request(
+                    &app.merchant_account_id.to_string(),
+                    "subscription-2",
+                )),
+            expected_status: reqwest::StatusCode::FORBIDDEN,
+            expected_code: "permission_denied",
+        },
+        TestCase {
+            name: "forbidden when paying the account of another merchant",
+            request: merchant
+                .post(format!("{base_url}/v1/schedules"))
+                .json(&pay_other_merchant),
+            expected_status: reqwest::StatusCode::FORBIDDEN,
+            expected_code: "permission_denied",
+        },
+        TestCase {
+            name: "not found when getting another caller's schedule",
+            request: other_merchant.get(format!("{base_url}/v1/schedules/{id}")),
+            expected_status: reqwest::StatusCode::NOT_FOUND,
+            expected_code: "not_found",
+        },
+        TestCase {
+            name: "not found when pausing another caller's schedule",
+            request: other_merchant.post(format!("{base_url}/v1/schedules/{id}/pause")),
+            expected_status: reqwest::StatusCode::NOT_FOUND,
+            expected_code: "not_found",
+        },
+        TestCase {
+            name: "not found when skipping another caller's schedule",
+            request: other_merchant.post(format!("{base_url}/v1/schedules/{id}/skip")),
+            expected_status: reqwest::StatusCode::NOT_FOUND,
+            expected_code: "not_found",
+        },
+    ];
+
+    for test_case in test_cases {
+        // act
+        let response = test_case.request.send().await.unwrap();
+
+        // assert
+        assert_eq!(
+            response.status(),
+            test_case.expected_status,
+            "{}",
+            test_case.name
+        );
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(
+            body["error"]["code"], test_case.expected_code,
+            "{}",
+            test_case.name
+        );
+    }
+
+    let schedule: Value = merchant
+        .get(format!("{base_url}/v1/schedules/{id}"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(schedule["status"], "active");
+}
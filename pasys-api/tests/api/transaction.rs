@@ -0,0 +1,129 @@
+use crate::helpers;
+use pasys_api::api::idempotency::ledger_idempotency_key;
+use pasys_api::domain::principal::{AccountScope, Principal};
+use serde_json::{Value, json};
+
+fn transaction_request(amount_minor: i64, idempotency_key: &str) -> Value {
+    json!({
+        "debit_account_id": uuid::Uuid::new_v4().to_string(),
+        "credit_account_id": uuid::Uuid::new_v4().to_string(),
+        "amount_minor": amount_minor,
+        "currency": "usd",
+        "idempotency_key": idempotency_key,
+        "request_timestamp": "2025-10-27T09:00:00Z",
+    })
+}
+
+#[tokio::test]
+async fn successfully_create_transaction_and_get_it_by_id() {
+    // arrange
+    let app = helpers::spawn_app().await;
+    let base_url = app.base_url.as_str();
+    let client = helpers::client(app.operator_key.as_str());
+    let request = transaction_request(1050, "order-1");
+
+    // act
+    let response = client
+        .post(format!("{base_url}/v1/transactions"))
+        .json(&request)
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(response.status(), reqwest::StatusCode::CREATED);
+    let created: Value = response.json().await.unwrap();
+    assert_eq!(created["status"], "init");
+
+    let response = client
+        .get(format!(
+            "{base_url}/v1/transactions/{}",
+            created["id"].as_str().unwrap()
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let transaction: Value = response.json().await.unwrap();
+    assert_eq!(transaction["id"], created["id"]);
+    assert_eq!(transaction["debit_account_id"], request["debit_account_id"]);
+    assert_eq!(transaction["amount_minor"], 1050);
+    assert_eq!(transaction["currency"], "USD");
+    assert_eq!(
+        transaction["idempotency_key"],
+        ledger_idempotency_key(&Principal::new("ops", AccountScope::All), "order-1")
+    );
+    assert_eq!(transaction["request_timestamp"], "2025-10-27T09:00:00Z");
+}
+
+#[tokio::test]
+async fn test_transaction_errors() {
+    #[derive(Debug)]
+    struct TestCase {
+        name: &'static str,
+        request: reqwest::RequestBuilder,
+        expected_status: reqwest::StatusCode,
+        expected_code: &'static str,
+        expected_message: Option<&'static str>,
+    }
+
+    let app = helpers::spawn_app().await;
+    let base_url = app.base_url.as_str();
+    let client = helpers::client(app.operator_key.as_str());
+    let test_cases = vec![
+        TestCase {
+            name: "bad request when idempotency key is missing",
+            request: client
+                .post(format!("{base_url}/v1/transactions"))
+                .json(&transaction_request(1050, "")),
+            expected_status: reqwest::StatusCode::BAD_REQUEST,
+            expected_code: "invalid_argument",
+            expected_message: Some("Idempotency-Key header or idempotency_key must be set"),
+        },
+        TestCase {
+            name: "internal server error without upstream details",
+            request: client
+                .post(format!("{base_url}/v1/transactions"))
+                .json(&transaction_request(13, "order-13")),
+            expected_status: reqwest::StatusCode::INTERNAL_SERVER_ERROR,
+            expected_code: "internal",
+            expected_message: Some("internal server error"),
+        },
+        TestCase {
+            name: "not found when transaction does not exist",
+            request: client.get(format!(
+                "{base_url}/v1/transactions/{}",
+                uuid::Uuid::new_v4()
+            )),
+            expected_status: reqwest::StatusCode::NOT_FOUND,
+            expected_code: "not_found",
+            expected_message: None,
+        },
+    ];
+
+    for test_case in test_cases {
+        // act
+        let response = test_case.request.send().await.unwrap();
+
+        // assert
+        assert_eq!(
+            response.status(),
+            test_case.expected_status,
+            "{}",
+            test_case.name
+        );
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(
+            body["error"]["code"], test_case.expected_code,
+            "{}",
+            test_case.name
+        );
+        if let Some(expected_message) = test_case.expected_message {
+            assert_eq!(
+                body["error"]["message"], expected_message,
+                "{}",
+                test_case.name
+            );
+        }
+    }
+}
@@ -0,0 +1,274 @@
+use crate::helpers;
+use serde_json::{Value, json};
+use webhook_dispatcher::domain::delivery::Delivery;
+use webhook_dispatcher::domain::endpoint::UrlPolicy;
+use webhook_dispatcher::domain::event::{EventType, TransactionData, WebhookEvent};
+use webhook_dispatcher::repo::{DeliveryWriter, PgWebhookRepository};
+
+async fn spawn_app_with_webhooks(pool: sqlx::PgPool) -> (helpers::TestApp, PgWebhookRepository) {
+    let repo = PgWebhookRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+    let webhooks = repo.clone();
+    let app =
+        helpers::spawn_app_with(|state| state.with_webhooks(webhooks, UrlPolicy::Public)).await;
+
+    (app, repo)
+}
+
+fn settled_event(credit_account_id: uuid::Uuid) -> WebhookEvent {
+    WebhookEvent {
+        id: uuid::Uuid::new_v4(),
+        event_type: EventType::TransactionSettled,
+        created_at: chrono::Utc::now(),
+        data: TransactionData {
+            transaction_id: uuid::Uuid::new_v4().to_string(),
+            debit_account_id: uuid::Uuid::new_v4().to_string(),
+            credit_account_id: credit_account_id.to_string(),
+            amount_minor: 1050,
+            currency: "USD".to_string(),
+            refund_id: None,
+            refund_reason: None,
+            fraud_score: None,
+        },
+    }
+}
+
+#[sqlx::test(migrations = "../migrations/api")]
+async fn successfully_register_list_and_disable_webhook(pool: sqlx::PgPool) {
+    // arrange
+    let (app, _) = spawn_app_with_webhooks(pool).await;
+    let base_url = app.base_url.as_str();
+    let merchant = helpers::client(app.merchant_key.as_str());
+    let other_merchant = helpers::client(app.other_merchant_key.as_str());
+
+    // act
+    let response = merchant
+        .post(format!("{base_url}/v1/webhooks"))
+        .json(&json!({
+            "url": "https://merchant.example/webhooks",
+            "event_types": ["transaction.settled"],
+        }))
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(response.status(), reqwest::StatusCode::CREATED);
+    let created: Value = response.json().await.unwrap();
+    assert!(created["secret"].as_str().unwrap().starts_with("whsec_"));
+    let id = created["id"].as_str().unwrap();
+
+    let listed: Value = merchant
+        .get(format!("{base_url}/v1/webhooks"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(listed.as_array().unwrap().len(), 1);
+    assert_eq!(listed[0]["id"], id);
+    assert!(listed[0].get("secret").is_none());
+    let other_listed: Value = other_merchant
+        .get(format!("{base_url}/v1/webhooks"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(other_listed, json!([]));
+
+    let response = other_merchant
+        .delete(format!("{base_url}/v1/webhooks/{id}"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+    let response = merchant
+        .delete(format!("{base_url}/v1/webhooks/{id}"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::NO_CONTENT);
+    let listed: Value = merchant
+        .get(format!("{base_url}/v1/webhooks"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(listed, json!([]));
+}
+
+#[sqlx::test(migrations = "../migrations/api")]
+async fn successfully_view_and_redeliver_deliveries(pool: sqlx::PgPool) {
+    // arrange
+    let (app, repo) = spawn_app_with_webhooks(pool).await;
+    let base_url = app.base_url.as_str();
+    let merchant = helpers::client(app.merchant_key.as_str());
+    let other_merchant = helpers::client(app.other_merchant_key.as_str());
+    let operator = helpers::client(app.operator_key.as_str());
+    let created: Value = merchant
+        .post(format!("{base_url}/v1/webhooks"))
+        .json(&json!({"url": "https://merchant.example/webhooks"}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let endpoint_id = uuid::Uuid::parse_str(created["id"].as_str().unwrap()).unwrap();
+    let delivery = Delivery::new(endpoint_id, &settled_event(app.merchant_account_id)).unwrap();
+    repo.record_deliveries(std::slice::from_ref(&delivery))
+        .await
+        .unwrap();
+
+    // act
+    let listed: Value = merchant
+        .get(format!(
+            "{base_url}/v1/webhooks/deliveries?endpoint_id={endpoint_id}&status=pending"
+        ))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let response = merchant
+        .post(format!(
+            "{base_url}/v1/webhooks/deliveries/{}/redeliver",
+            delivery.id
+        ))
+        .send()
+        .await
+        .unwrap();
+
+    // assert
+    assert_eq!(listed.as_array().unwrap().len(), 1);
+    assert_eq!(listed[0]["id"], delivery.id.to_string());
+    assert_eq!(listed[0]["event_type"], "transaction.settled");
+    assert!(listed[0].get("attempt_log").is_none());
+    assert_eq!(response.status(), reqwest::StatusCode::ACCEPTED);
+
+    let found: Value = operator
+        .get(format!("{base_url}/v1/webhooks/deliveries/{}", delivery.id))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(found["status"], "pending");
+    assert_eq!(found["attempt_log"], json!([]));
+    let other_listed: Value = other_merchant
+        .get(format!("{base_url}/v1/webhooks/deliveries"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(other_listed, json!([]));
+    let response = other_merchant
+        .post(format!(
+            "{base_url}/v1/webhooks/deliveries/{}/redeliver",
+            delivery.id
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test(migrations = "../migrations/api")]
+async fn test_webhook_errors(pool: sqlx::PgPool) {
+    #[derive(Debug)]
+    struct TestCase {
+        name: &'static str,
+        request: reqwest::RequestBuilder,
+        expected_status: reqwest::StatusCode,
+        expected_code: &'static str,
+    }
+
+    let (app, _) = spawn_app_with_webhooks(pool).await;
+    let base_url = app.base_url.as_str();
+    let client = helpers::client(app.merchant_key.as_str());
+    let disabled_app = helpers::spawn_app().await;
+    let test_cases = vec![
+        TestCase {
+            name: "bad request when url is not http",
+            request: client
+                .post(format!("{base_url}/v1/webhooks"))
+                .json(&json!({"url": "ftp://merchant.example"})),
+            expected_status: reqwest::StatusCode::BAD_REQUEST,
+            expected_code: "invalid_argument",
+        },
+        TestCase {
+            name: "bad request when url is not https",
+            request: client
+                .post(format!("{base_url}/v1/webhooks"))
+                .json(&json!({"url": "http://merchant.example"})),
+            expected_status: reqwest::StatusCode::BAD_REQUEST,
+            expected_code: "invalid_argument",
+        },
+        TestCase {
+            name: "bad request when host is not public",
+            request: client
+                .post(format!("{base_url}/v1/webhooks"))
+                .json(&json!({"url": "https://169.254.169.254/latest/meta-data"})),
+            expected_status: reqwest::StatusCode::BAD_REQUEST,
+            expected_code: "invalid_argument",
+        },
+        TestCase {
+            name: "bad request when event type is unknown",
+            request: client.post(format!("{base_url}/v1/webhooks")).json(&json!({
+                "url": "https://merchant.example",
+                "event_types": ["transaction.created"],
+            })),
+            expected_status: reqwest::StatusCode::BAD_REQUEST,
+            expected_code: "invalid_argument",
+        },
+        TestCase {
+            name: "bad request when status filter is unknown",
+            request: client.get(format!("{base_url}/v1/webhooks/deliveries?status=lost")),
+            expected_status: reqwest::StatusCode::BAD_REQUEST,
+            expected_code: "invalid_argument",
+        },
+        TestCase {
+            name: "not found when delivery does not exist",
+            request: client.get(format!(
+                "{base_url}/v1/webhooks/deliveries/{}",
+                uuid::Uuid::new_v4()
+            )),
+            expected_status: reqwest::StatusCode::NOT_FOUND,
+            expected_code: "not_found",
+        },
+        TestCase {
+            name: "not implemented when webhooks are not enabled",
+            request: helpers::client(disabled_app.merchant_key.as_str())
+                .get(format!("{}/v1/webhooks", disabled_app.base_url)),
+            expected_status: reqwest::StatusCode::NOT_IMPLEMENTED,
+            expected_code: "unimplemented",
+        },
+    ];
+
+    for test_case in test_cases {
+        // act
+        let response = test_case.request.send().await.unwrap();
+
+        // assert
+        assert_eq!(
+            response.status(),
+            test_case.expected_status,
+            "{}",
+            test_case.name
+        );
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(
+            body["error"]["code"], test_case.expected_code,
+            "{}",
+            test_case.name
+        );
+    }
+}
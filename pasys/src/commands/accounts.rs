@@ -0,0 +1,156 @@
+use crate::cli::{AccountStatus, AccountType, AccountsCommand, Cli};
+use crate::commands::{connect, enum_name, format_timestamp, status_error};
+use crate::output::Table;
+use accounts_proto::accounts_v1;
+use accounts_proto::accounts_v1::accounts_client::AccountsClient;
+
+pub async fn run(cli: &Cli, command: &AccountsCommand) -> anyhow::Result<Table> {
+    let mut client =
+        AccountsClient::new(connect(cli.accounts_url.as_deref(), "ACCOUNTS_URL").await?);
+
+    let accounts = match command {
+        AccountsCommand::Create(args) => {
+            let request = accounts_v1::CreateAccountRequest {
+                name: args.name.clone(),
+                r#type: parse_account_type(args.account_type) as i32,
+                created_by: args.created_by.clone(),
+            };
+            match client.create_account(request).await {
+                Ok(response) => response.into_inner().account.into_iter().collect(),
+                Err(e) => return Err(status_error("create account", e)),
+            }
+        }
+        AccountsCommand::Get { account_id } => {
+            let request = accounts_v1::GetAccountRequest {
+                account_id: account_id.clone(),
+            };
+            match client.get_account(request).await {
+                Ok(response) => response.into_inner().account.into_iter().collect(),
+                Err(e) => return Err(status_error("get account", e)),
+            }
+        }
+        AccountsCommand::List(args) => {
+            let request = accounts_v1::GetAccountsRequest {
+                filter: Some(accounts_v1::get_accounts_request::Filter {
+                    account_ids: args.ids.clone(),
+                    account_type: args
+                        .account_type
+                        .map(|account_type| parse_account_type(account_type) as i32)
+                        .unwrap_or_default(),
+                    account_status: args
+                        .status
+                        .map(|status| parse_account_status(status) as i32)
+                        .unwrap_or_default(),
+                }),
+            };
+            match client.get_accounts(request).await {
+                Ok(response) => response.into_inner().accounts,
+                Err(e) => return Err(status_error("list accounts", e)),
+            }
+        }
+        AccountsCommand::Freeze { account_id } => {
+            update_status(&mut client, account_id, AccountStatus::Frozen).await?
+        }
+        AccountsCommand::Close { account_id } => {
+            update_status(&mut client, account_id, AccountStatus::Closed).await?
+        }
+    };
+
+    Ok(accounts_table(accounts))
+}
+
+async fn update_status(
+    client: &mut AccountsClient<tonic::transport::Channel>,
+    account_id: &str,
+    status: AccountStatus,
+) -> anyhow::Result<Vec<accounts_v1::Account>> {
+    let request = accounts_v1::UpdateAccountStatusRequest {
+        account_id: account_id.to_string(),
+        status: parse_account_status(status) as i32,
+    };
+
+    match client.update_account_status(request).await {
+        Ok(response) => Ok(response.into_inner().account.into_iter().collect()),
+        Err(e) => Err(status_error("update account status", e)),
+    }
+}
+
+fn parse_account_type(account_type: AccountType) -> accounts_v1::AccountType {
+    match account_type {
+        AccountType::Customer => accounts_v1::AccountType::Customer,
+        AccountType::Merchant => accounts_v1::AccountType::Merchant,
+        AccountType::System => accounts_v1::AccountType::System,
+    }
+}
+
+fn parse_account_status(status: AccountStatus) -> accounts_v1::AccountStatus {
+    match status {
+        AccountStatus::Active => accounts_v1::AccountStatus::Active,
+        AccountStatus::Frozen => accounts_v1::AccountStatus::Frozen,
+        AccountStatus::Closed => accounts_v1::AccountStatus::Closed,
+    }
+}
+
+pub fn accounts_table(accounts: Vec<accounts_v1::Account>) -> Table {
+    let mut table = Table::new(vec![
+        "id",
+        "name",
+        "type",
+        "status",
+        "created_by",
+        "created_at",
+        "updated_at",
+    ]);
+    for account in accounts {
+        table.push(vec![
+            account.id.clone(),
+            account.name.clone(),
+            enum_name(account.r#type().as_str_name(), "ACCOUNT_TYPE_"),
+            enum_name(account.status().as_str_name(), "ACCOUNT_STATUS_"),
+            account.created_by.clone(),
+            format_timestamp(account.created_at.as_ref()),
+            format_timestamp(account.updated_at.as_ref()),
+        ]);
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn successfully_build_accounts_table() {
+        // arrange
+        let account = accounts_v1::Account {
+            id: "3f0c".to_string(),
+            name: "Acme".to_string(),
+            r#type: accounts_v1::AccountType::Merchant as i32,
+            status: accounts_v1::AccountStatus::Frozen as i32,
+            created_by: "ops".to_string(),
+            created_at: Some(prost_types::Timestamp {
+                seconds: 1_761_814_800,
+                nanos: 0,
+            }),
+            updated_at: None,
+        };
+
+        // act
+        let table = accounts_table(vec![account]);
+
+        // assert
+        assert_eq!(
+            table.rows,
+            vec![vec![
+                "3f0c".to_string(),
+                "Acme".to_string(),
+                "merchant".to_string(),
+                "frozen".to_string(),
+                "ops".to_string(),
+                "2025-10-30T09:00:00Z".to_string(),
+                String::new(),
+            ]]
+        );
+    }
+}
@@ -0,0 +1,908 @@
+use crate::cli::{Cli, DashboardArgs};
+use crate::commands::{connect, enum_name, format_amount, format_timestamp, status_error};
+use common::kafka;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use fraud_proto::fraud_v1;
+use fraud_proto::fraud_v1::fraud_reviews_client::FraudReviewsClient;
+use ledger_proto::ledger_v1;
+use ledger_proto::ledger_v1::ledger_client::LedgerClient;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Cell, Paragraph, Row, Table, TableState, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use reconciliation_proto::reconciliation_v1;
+use reconciliation_proto::reconciliation_v1::reconciliation_exceptions_client::ReconciliationExceptionsClient;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tonic::transport::Channel;
+
+/// WORKERS are the consumer groups shown in the lag panel with the topics they consume, their
+/// retry topic is added to these.
+const WORKERS: [(&str, &[&str]); 4] = [
+    (
+        "ledger-consumer",
+        &[
+            kafka::ACCOUNTS_EVENTS_TOPIC,
+            kafka::RECONCILIATION_EVENTS_TOPIC,
+        ],
+    ),
+    (
+        "fraud-detector",
+        &[kafka::TRANSACTION_EVENTS_TOPIC, kafka::REFUND_EVENTS_TOPIC],
+    ),
+    ("refund-processor", &[kafka::REFUND_EVENTS_TOPIC]),
+    (
+        "webhook-dispatcher",
+        &[
+            kafka::SETTLEMENT_RESULT_EVENTS_TOPIC,
+            kafka::FRAUD_DETECTED_EVENTS_TOPIC,
+            kafka::REFUND_EVENTS_TOPIC,
+        ],
+    ),
+];
+
+const LAG_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Panel holds the rows of a panel, or why they could not be loaded.
+type Panel<T> = Result<Vec<T>, String>;
+
+/// WorkerLag is the lag of a consumer group summed over the partitions of its topics.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkerLag {
+    pub worker: &'static str,
+    pub partitions: usize,
+    pub lag: i64,
+}
+
+/// Snapshot is everything the dashboard shows, fetched in one refresh.
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub transactions: Panel<ledger_v1::Transaction>,
+    pub counts: Panel<ledger_v1::StatusCount>,
+    pub lag: Panel<WorkerLag>,
+    pub reviews: Panel<fraud_v1::Review>,
+    pub exceptions: Panel<reconciliation_v1::Exception>,
+    pub refreshed_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Action is what the event loop does after a key press.
+#[derive(Debug, PartialEq)]
+pub enum Action {
+    None,
+    Quit,
+    Refresh,
+    OpenTransaction(String),
+}
+
+/// App is the state of the dashboard between refreshes.
+#[derive(Debug, Default)]
+pub struct App {
+    pub snapshot: Option<Snapshot>,
+    pub selected: usize,
+    pub details: Option<Result<ledger_v1::GetTransactionDetailsResponse, String>>,
+    pub refreshing: bool,
+}
+
+impl App {
+    /// update shows the snapshot, keeping the selection within the transactions.
+    pub fn update(&mut self, snapshot: Snapshot) {
+        self.selected = self
+            .selected
+            .min(transactions(&snapshot).len().saturating_sub(1));
+        self.snapshot = Some(snapshot);
+        self.refreshing = false;
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Action {
+        let transactions = self.snapshot.as_ref().map(transactions).unwrap_or(&[]);
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
+            KeyCode::Char('q') => Action::Quit,
+            KeyCode::Char('r') => Action::Refresh,
+            KeyCode::Esc | KeyCode::Backspace => {
+                self.details = None;
+                Action::None
+            }
+            KeyCode::Down | KeyCode::Char('j') if self.details.is_none() => {
+                self.selected = (self.selected + 1).min(transactions.len().saturating_sub(1));
+                Action::None
+            }
+            KeyCode::Up | KeyCode::Char('k') if self.details.is_none() => {
+                self.selected = self.selected.saturating_sub(1);
+                Action::None
+            }
+            KeyCode::Enter if self.details.is_none() => match transactions.get(self.selected) {
+                Some(transaction) => Action::OpenTransaction(transaction.id.clone()),
+                None => Action::None,
+            },
+            _ => Action::None,
+        }
+    }
+
+    /// open_transaction is the id of the transaction being drilled into.
+    fn open_transaction(&self) -> Option<String> {
+        match &self.details {
+            Some(Ok(details)) => details.transaction.as_ref().map(|t| t.id.clone()),
+            _ => None,
+        }
+    }
+}
+
+fn transactions(snapshot: &Snapshot) -> &[ledger_v1::Transaction] {
+    match &snapshot.transactions {
+        Ok(transactions) => transactions,
+        Err(_) => &[],
+    }
+}
+
+/// Sources are the services the dashboard refreshes from. The fraud and reconciliation admin
+/// APIs and kafka are optional, their panels say how to configure them when unset.
+#[derive(Clone)]
+struct Sources {
+    ledger: LedgerClient<Channel>,
+    fraud: Option<FraudReviewsClient<Channel>>,
+    reconciliation: Option<ReconciliationExceptionsClient<Channel>>,
+    kafka_brokers: Option<String>,
+    limit: u32,
+    window: Option<Duration>,
+}
+
+impl Sources {
+    async fn connect(cli: &Cli, args: &DashboardArgs) -> anyhow::Result<Self> {
+        let ledger = LedgerClient::new(connect(cli.ledger_url.as_deref(), "LEDGER_URL").await?);
+        let fraud = match cli.fraud_url.as_deref() {
+            Some(url) => Some(FraudReviewsClient::new(
+                connect(Some(url), "FRAUD_URL").await?,
+            )),
+            None => None,
+        };
+        let reconciliation = match cli.reconciliation_url.as_deref() {
+            Some(url) => Some(ReconciliationExceptionsClient::new(
+                connect(Some(url), "RECONCILIATION_URL").await?,
+            )),
+            None => None,
+        };
+
+        Ok(Self {
+            ledger,
+            fraud,
+            reconciliation,
+            kafka_brokers: cli.kafka_brokers.clone(),
+            limit: args.limit,
+            window: args.window.map(|minutes| Duration::from_secs(minutes * 60)),
+        })
+    }
+
+    async fn snapshot(self) -> Snapshot {
+        let (transactions, counts, lag, reviews, exceptions) = tokio::join!(
+            self.transactions(),
+            self.counts(),
+            self.lag(),
+            self.reviews(),
+            self.exceptions()
+        );
+
+        Snapshot {
+            transactions,
+            counts,
+            lag,
+            reviews,
+            exceptions,
+            refreshed_at: chrono::Utc::now(),
+        }
+    }
+
+    async fn transactions(&self) -> Panel<ledger_v1::Transaction> {
+        let request = ledger_v1::ListTransactionsRequest {
+            limit: self.limit,
+            ..Default::default()
+        };
+
+        match self.ledger.clone().list_transactions(request).await {
+            Ok(response) => Ok(response.into_inner().transactions),
+            Err(e) => Err(status_error("list transactions", e).to_string()),
+        }
+    }
+
+    async fn counts(&self) -> Panel<ledger_v1::StatusCount> {
+        let since = self
+            .window
+            .map(|window| prost_types::Timestamp::from(std::time::SystemTime::now() - window));
+
+        match self
+            .ledger
+            .clone()
+            .count_transactions(ledger_v1::CountTransactionsRequest { since })
+            .await
+        {
+            Ok(response) => Ok(response.into_inner().counts),
+            Err(e) => Err(status_error("count transactions", e).to_string()),
+        }
+    }
+
+    async fn lag(&self) -> Panel<WorkerLag> {
+        let brokers = match &self.kafka_brokers {
+            Some(brokers) => brokers,
+            None => return Err("set KAFKA_BROKERS or pass --kafka-brokers".to_string()),
+        };
+
+        let mut workers = vec![];
+        for (worker, topics) in WORKERS {
+            let retry_topic = kafka::retry_topic(worker);
+            let mut topics = topics.to_vec();
+            topics.push(retry_topic.as_str());
+            let config = kafka::Config {
+                brokers: brokers.clone(),
+                group_id: worker.to_string(),
+            };
+            match kafka::consumer_lag(&config, &topics, LAG_TIMEOUT).await {
+                Ok(partitions) => workers.push(worker_lag(worker, &partitions)),
+                Err(e) => return Err(format!("failed to fetch lag of {worker}: {e}")),
+            }
+        }
+
+        Ok(workers)
+    }
+
+    async fn reviews(&self) -> Panel<fraud_v1::Review> {
+        let mut client = match &self.fraud {
+            Some(client) => client.clone(),
+            None => return Err("set FRAUD_URL or pass --fraud-url".to_string()),
+        };
+
+        let mut reviews = vec![];
+        for status in [
+            fraud_v1::ReviewStatus::Escalated,
+            fraud_v1::ReviewStatus::Open,
+        ] {
+            let request = fraud_v1::ListReviewsRequest {
+                status: status as i32,
+            };
+            match client.list_reviews(request).await {
+                Ok(response) => reviews.extend(response.into_inner().reviews),
+                Err(e) => return Err(status_error("list reviews", e).to_string()),
+            }
+        }
+
+        Ok(reviews)
+    }
+
+    async fn exceptions(&self) -> Panel<reconciliation_v1::Exception> {
+        let mut client = match &self.reconciliation {
+            Some(client) => client.clone(),
+            None => return Err("set RECONCILIATION_URL or pass --reconciliation-url".to_string()),
+        };
+
+        let mut exceptions = vec![];
+        for status in [
+            reconciliation_v1::ExceptionStatus::Open,
+            reconciliation_v1::ExceptionStatus::Investigating,
+        ] {
+            let request = reconciliation_v1::ListExceptionsRequest {
+                status: status as i32,
+                ..Default::default()
+            };
+            match client.list_exceptions(request).await {
+                Ok(response) => exceptions.extend(response.into_inner().exceptions),
+                Err(e) => return Err(status_error("list exceptions", e).to_string()),
+            }
+        }
+
+        Ok(exceptions)
+    }
+
+    async fn details(
+        &self,
+        transaction_id: String,
+    ) -> Result<ledger_v1::GetTransactionDetailsResponse, String> {
+        let request = ledger_v1::GetTransactionDetailsRequest { transaction_id };
+
+        match self.ledger.clone().get_transaction_details(request).await {
+            Ok(response) => Ok(response.into_inner()),
+            Err(e) => Err(status_error("get transaction details", e).to_string()),
+        }
+    }
+}
+
+/// worker_lag sums the lag of a consumer group over its partitions.
+pub fn worker_lag(worker: &'static str, partitions: &[kafka::PartitionLag]) -> WorkerLag {
+    WorkerLag {
+        worker,
+        partitions: partitions.len(),
+        lag: partitions.iter().map(|partition| partition.lag).sum(),
+    }
+}
+
+/// run shows the dashboard until q is pressed. It draws on the alternate screen and leaves
+/// the terminal as it found it.
+pub async fn run(cli: &Cli, args: &DashboardArgs) -> anyhow::Result<()> {
+    let sources = Sources::connect(cli, args).await?;
+
+    let mut terminal = ratatui::init();
+    let result = run_app(
+        &mut terminal,
+        sources,
+        Duration::from_secs(args.refresh.max(1)),
+    )
+    .await;
+    ratatui::restore();
+
+    result
+}
+
+async fn run_app(
+    terminal: &mut DefaultTerminal,
+    sources: Sources,
+    refresh: Duration,
+) -> anyhow::Result<()> {
+    // crossterm reads block, so terminal events are read on a thread of their own
+    let (events_tx, mut events) = mpsc::unbounded_channel();
+    std::thread::spawn(move || {
+        while let Ok(event) = event::read() {
+            if events_tx.send(event).is_err() {
+                break;
+            }
+        }
+    });
+
+    let (snapshots_tx, mut snapshots) = mpsc::channel(1);
+    let mut ticker = tokio::time::interval(refresh);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut app = App::default();
+    loop {
+        terminal.draw(|frame| draw(frame, &app))?;
+
+        tokio::select! {
+            _ = ticker.tick() => {
+                if !app.refreshing {
+                    app.refreshing = true;
+                    let sources = sources.clone();
+                    let snapshots_tx = snapshots_tx.clone();
+                    tokio::spawn(async move {
+                        let _ = snapshots_tx.send(sources.snapshot().await).await;
+                    });
+                }
+            }
+            Some(snapshot) = snapshots.recv() => {
+                app.update(snapshot);
+                if let Some(transaction_id) = app.open_transaction() {
+                    app.details = Some(sources.details(transaction_id).await);
+                }
+            }
+            event = events.recv() => match event {
+                Some(Event::Key(key)) if key.kind == KeyEventKind::Press => {
+                    match app.handle_key(key) {
+                        Action::Quit => break,
+                        Action::Refresh => ticker.reset_immediately(),
+                        Action::OpenTransaction(transaction_id) => {
+                            app.details = Some(sources.details(transaction_id).await);
+                        }
+                        Action::None => {}
+                    }
+                }
+                Some(_) => {}
+                None => break,
+            },
+        }
+    }
+
+    Ok(())
+}
+
+/// draw lays out the panels: counts and lag on top, the transactions or the drilled in
+/// transaction in the middle, and the review and exception queues at the bottom.
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [header, top, middle, bottom, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(10),
+        Constraint::Min(8),
+        Constraint::Length(10),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [counts, lag] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(top);
+    let [reviews, exceptions] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(bottom);
+
+    let refreshed = match &app.snapshot {
+        Some(snapshot) => format!(
+            "refreshed {}",
+            snapshot
+                .refreshed_at
+                .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+        ),
+        None => "loading".to_string(),
+    };
+    let refreshing = match app.refreshing {
+        true => ", refreshing",
+        false => "",
+    };
+    frame.render_widget(
+        Line::from(format!(" pasys dashboard, {refreshed}{refreshing}"))
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+        header,
+    );
+    frame.render_widget(
+        Line::from(" q quit  r refresh  ↑/↓ select  enter open transaction  esc back")
+            .style(Style::default().fg(Color::DarkGray)),
+        footer,
+    );
+
+    let snapshot = match &app.snapshot {
+        Some(snapshot) => snapshot,
+        None => return,
+    };
+
+    draw_panel(
+        frame,
+        counts,
+        "Transactions per status",
+        &snapshot.counts,
+        vec!["status", "count"],
+        vec![Constraint::Length(12), Constraint::Min(8)],
+        |count| {
+            let status = count.status();
+            Row::new(vec![
+                Cell::from(status_name(status)).style(status_style(status)),
+                Cell::from(count.count.to_string()),
+            ])
+        },
+    );
+    draw_panel(
+        frame,
+        lag,
+        "Consumer lag",
+        &snapshot.lag,
+        vec!["worker", "partitions", "lag"],
+        vec![
+            Constraint::Length(20),
+            Constraint::Length(10),
+            Constraint::Min(8),
+        ],
+        |worker| {
+            let style = match worker.lag {
+                0 => Style::default(),
+                _ => Style::default().fg(Color::Yellow),
+            };
+            Row::new(vec![
+                Cell::from(worker.worker),
+                Cell::from(worker.partitions.to_string()),
+                Cell::from(worker.lag.to_string()).style(style),
+            ])
+        },
+    );
+
+    match &app.details {
+        Some(details) => draw_details(frame, middle, details),
+        None => draw_transactions(frame, middle, snapshot, app.selected),
+    }
+
+    draw_panel(
+        frame,
+        reviews,
+        "Fraud review queue",
+        &snapshot.reviews,
+        vec!["transaction", "score", "status", "due_at"],
+        vec![
+            Constraint::Length(36),
+            Constraint::Length(6),
+            Constraint::Length(10),
+            Constraint::Min(20),
+        ],
+        |review| {
+            let status = enum_name(review.status().as_str_name(), "REVIEW_STATUS_");
+            let style = match review.status() {
+                fraud_v1::ReviewStatus::Escalated => Style::default().fg(Color::Red),
+                _ => Style::default(),
+            };
+            Row::new(vec![
+                Cell::from(review.transaction_id.clone()),
+                Cell::from(format!("{:.2}", review.score)),
+                Cell::from(status).style(style),
+                Cell::from(format_timestamp(review.due_at.as_ref())),
+            ])
+        },
+    );
+    draw_panel(
+        frame,
+        exceptions,
+        "Reconciliation exceptions",
+        &snapshot.exceptions,
+        vec!["reference", "discrepancies", "status", "first_seen_at"],
+        vec![
+            Constraint::Length(36),
+            Constraint::Length(20),
+            Constraint::Length(13),
+            Constraint::Min(20),
+        ],
+        |exception| {
+            Row::new(vec![
+                Cell::from(exception.reference.clone()),
+                Cell::from(exception.discrepancies.join(",")),
+                Cell::from(enum_name(
+                    exception.status().as_str_name(),
+                    "EXCEPTION_STATUS_",
+                )),
+                Cell::from(format_timestamp(exception.first_seen_at.as_ref())),
+            ])
+        },
+    );
+}
+
+fn draw_transactions(frame: &mut Frame, area: Rect, snapshot: &Snapshot, selected: usize) {
+    let block = Block::bordered().title("Recent transactions");
+    let transactions = match &snapshot.transactions {
+        Ok(transactions) => transactions,
+        Err(e) => return draw_error(frame, area, block, e),
+    };
+
+    let rows = transactions.iter().map(|transaction| {
+        let status = transaction.status();
+        let (amount, currency) = money(transaction.amount.as_ref());
+        Row::new(vec![
+            Cell::from(transaction.id.clone()),
+            Cell::from(status_name(status)).style(status_style(status)),
+            Cell::from(amount),
+            Cell::from(currency),
+            Cell::from(transaction.debit_account_id.clone()),
+            Cell::from(transaction.credit_account_id.clone()),
+            Cell::from(format_timestamp(transaction.created_at.as_ref())),
+        ])
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(36),
+            Constraint::Length(9),
+            Constraint::Length(12),
+            Constraint::Length(8),
+            Constraint::Length(36),
+            Constraint::Length(36),
+            Constraint::Min(20),
+        ],
+    )
+    .header(header_row(vec![
+        "id",
+        "status",
+        "amount",
+        "currency",
+        "debit_account_id",
+        "credit_account_id",
+        "created_at",
+    ]))
+    .block(block)
+    .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    let mut state = TableState::default().with_selected(Some(selected));
+    frame.render_stateful_widget(table, area, &mut state);
+}
+
+fn draw_details(
+    frame: &mut Frame,
+    area: Rect,
+    details: &Result<ledger_v1::GetTransactionDetailsResponse, String>,
+) {
+    let details = match details {
+        Ok(details) => details,
+        Err(e) => return draw_error(frame, area, Block::bordered().title("Transaction"), e),
+    };
+    let (title, summary) = match &details.transaction {
+        Some(transaction) => {
+            let (amount, currency) = money(transaction.amount.as_ref());
+            (
+                format!("Transaction {}", transaction.id),
+                format!(
+                    " {amount} {currency} from {} to {}, {}, key {}",
+                    transaction.debit_account_id,
+                    transaction.credit_account_id,
+                    status_name(transaction.status()),
+                    transaction.idempotency_key
+                ),
+            )
+        }
+        None => ("Transaction".to_string(), String::new()),
+    };
+
+    let block = Block::bordered().title(title);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let [summary_area, tables] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(3)]).areas(inner);
+    let [entries, history] =
+        Layout::horizontal([Constraint::Percentage(70), Constraint::Percentage(30)]).areas(tables);
+    frame.render_widget(Line::from(summary), summary_area);
+
+    let rows = details.entries.iter().map(|entry| {
+        let (amount, currency) = money(entry.amount.as_ref());
+        Row::new(vec![
+            Cell::from(entry.account_id.clone()),
+            Cell::from(enum_name(entry.entry_type().as_str_name(), "ENTRY_TYPE_")),
+            Cell::from(amount),
+            Cell::from(currency),
+            Cell::from(entry.refund_id.clone().unwrap_or_default()),
+            Cell::from(format_timestamp(entry.created_at.as_ref())),
+        ])
+    });
+    frame.render_widget(
+        Table::new(
+            rows,
+            [
+                Constraint::Length(36),
+                Constraint::Length(6),
+                Constraint::Length(12),
+                Constraint::Length(8),
+                Constraint::Length(36),
+                Constraint::Min(20),
+            ],
+        )
+        .header(header_row(vec![
+            "account_id",
+            "type",
+            "amount",
+            "currency",
+            "refund_id",
+            "created_at",
+        ]))
+        .block(Block::bordered().title("Entries")),
+        entries,
+    );
+
+    let rows = details.status_history.iter().map(|change| {
+        let status = change.status();
+        Row::new(vec![
+            Cell::from(status_name(status)).style(status_style(status)),
+            Cell::from(format_timestamp(change.changed_at.as_ref())),
+        ])
+    });
+    frame.render_widget(
+        Table::new(rows, [Constraint::Length(9), Constraint::Min(20)])
+            .header(header_row(vec!["status", "changed_at"]))
+            .block(Block::bordered().title("Status history")),
+        history,
+    );
+}
+
+/// draw_panel draws the rows of a panel as a table, or why they could not be loaded.
+fn draw_panel<T>(
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    panel: &Panel<T>,
+    header: Vec<&str>,
+    widths: Vec<Constraint>,
+    row: impl Fn(&T) -> Row<'static>,
+) {
+    let block = Block::bordered().title(format!("{title} ({})", panel_len(panel)));
+    match panel {
+        Ok(items) => frame.render_widget(
+            Table::new(items.iter().map(row), widths)
+                .header(header_row(header))
+                .block(block),
+            area,
+        ),
+        Err(e) => draw_error(frame, area, block, e),
+    }
+}
+
+fn draw_error(frame: &mut Frame, area: Rect, block: Block, error: &str) {
+    frame.render_widget(
+        Paragraph::new(error.to_string())
+            .style(Style::default().fg(Color::Red))
+            .wrap(Wrap { trim: true })
+            .block(block),
+        area,
+    );
+}
+
+fn panel_len<T>(panel: &Panel<T>) -> String {
+    match panel {
+        Ok(items) => items.len().to_string(),
+        Err(_) => "-".to_string(),
+    }
+}
+
+fn header_row(header: Vec<&str>) -> Row<'static> {
+    Row::new(header.into_iter().map(str::to_string))
+        .style(Style::default().add_modifier(Modifier::BOLD))
+}
+
+fn money(money: Option<&ledger_v1::google::r#type::Money>) -> (String, String) {
+    match money {
+        Some(money) => (format_amount(money), money.currency_code.clone()),
+        None => (String::new(), String::new()),
+    }
+}
+
+fn status_name(status: ledger_v1::TransactionStatus) -> String {
+    enum_name(status.as_str_name(), "TRANSACTION_STATUS_")
+}
+
+fn status_style(status: ledger_v1::TransactionStatus) -> Style {
+    match status {
+        ledger_v1::TransactionStatus::Success => Style::default().fg(Color::Green),
+        ledger_v1::TransactionStatus::Failed | ledger_v1::TransactionStatus::Fraud => {
+            Style::default().fg(Color::Red)
+        }
+        ledger_v1::TransactionStatus::Refund | ledger_v1::TransactionStatus::Refunded => {
+            Style::default().fg(Color::Magenta)
+        }
+        _ => Style::default().fg(Color::Yellow),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    fn snapshot(transaction_ids: &[&str]) -> Snapshot {
+        Snapshot {
+            transactions: Ok(transaction_ids
+                .iter()
+                .map(|id| ledger_v1::Transaction {
+                    id: id.to_string(),
+                    status: ledger_v1::TransactionStatus::Success as i32,
+                    ..Default::default()
+                })
+                .collect()),
+            counts: Ok(vec![ledger_v1::StatusCount {
+                status: ledger_v1::TransactionStatus::Success as i32,
+                count: transaction_ids.len() as u64,
+            }]),
+            lag: Ok(vec![WorkerLag {
+                worker: "refund-processor",
+                partitions: 2,
+                lag: 7,
+            }]),
+            reviews: Err("set FRAUD_URL or pass --fraud-url".to_string()),
+            exceptions: Ok(vec![]),
+            refreshed_at: chrono::Utc::now(),
+        }
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn render(app: &App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(200, 40)).unwrap();
+        terminal.draw(|frame| draw(frame, app)).unwrap();
+
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect()
+    }
+
+    #[test]
+    fn successfully_sum_worker_lag() {
+        // arrange
+        let partitions = vec![
+            kafka::PartitionLag {
+                topic: kafka::REFUND_EVENTS_TOPIC.to_string(),
+                partition: 0,
+                committed: Some(10),
+                high_watermark: 15,
+                lag: 5,
+            },
+            kafka::PartitionLag {
+                topic: kafka::retry_topic("refund-processor"),
+                partition: 0,
+                committed: None,
+                high_watermark: 2,
+                lag: 2,
+            },
+        ];
+
+        // act
+        let lag = worker_lag("refund-processor", &partitions);
+
+        // assert
+        assert_eq!(
+            lag,
+            WorkerLag {
+                worker: "refund-processor",
+                partitions: 2,
+                lag: 7
+            }
+        );
+    }
+
+    #[test]
+    fn successfully_navigate_transactions() {
+        // arrange
+        let mut app = App::default();
+        app.update(snapshot(&["first", "second"]));
+
+        // act
+        let moved = [
+            app.handle_key(key(KeyCode::Down)),
+            app.handle_key(key(KeyCode::Down)),
+        ];
+        let opened = app.handle_key(key(KeyCode::Enter));
+        let selected = app.selected;
+        app.details = Some(Err("not found".to_string()));
+        let ignored = app.handle_key(key(KeyCode::Up));
+        let closed = app.handle_key(key(KeyCode::Esc));
+
+        // assert
+        assert_eq!(moved, [Action::None, Action::None]);
+        assert_eq!(selected, 1);
+        assert_eq!(opened, Action::OpenTransaction("second".to_string()));
+        assert_eq!(ignored, Action::None);
+        assert_eq!(app.selected, 1);
+        assert_eq!(closed, Action::None);
+        assert!(app.details.is_none());
+        assert_eq!(app.handle_key(key(KeyCode::Char('q'))), Action::Quit);
+    }
+
+    #[test]
+    fn successfully_keep_selection_within_refreshed_transactions() {
+        // arrange
+        let mut app = App::default();
+        app.update(snapshot(&["first", "second", "third"]));
+        app.selected = 2;
+
+        // act
+        app.update(snapshot(&["first"]));
+
+        // assert
+        assert_eq!(app.selected, 0);
+        assert!(!app.refreshing);
+    }
+
+    #[test]
+    fn successfully_draw_dashboard() {
+        // arrange
+        let mut app = App::default();
+        app.update(snapshot(&["3f0c9a52-tx"]));
+
+        // act
+        let screen = render(&app);
+
+        // assert
+        assert!(screen.contains("3f0c9a52-tx"));
+        assert!(screen.contains("Transactions per status (1)"));
+        assert!(screen.contains("refund-processor"));
+        assert!(screen.contains("set FRAUD_URL or pass --fraud-url"));
+        assert!(screen.contains("Reconciliation exceptions (0)"));
+    }
+
+    #[test]
+    fn successfully_draw_transaction_details() {
+        // arrange
+        let mut app = App::default();
+        app.update(snapshot(&["3f0c9a52-tx"]));
+        app.details = Some(Ok(ledger_v1::GetTransactionDetailsResponse {
+            transaction: Some(ledger_v1::Transaction {
+                id: "3f0c9a52-tx".to_string(),
+                ..Default::default()
+            }),
+            entries: vec![ledger_v1::Entry {
+                account_id: "customer-account".to_string(),
+                entry_type: ledger_v1::EntryType::Debit as i32,
+                ..Default::default()
+            }],
+            status_history: vec![ledger_v1::StatusChange {
+                status: ledger_v1::TransactionStatus::Pending as i32,
+                changed_at: None,
+            }],
+        }));
+
+        // act
+        let screen = render(&app);
+
+        // assert
+        assert!(screen.contains("Transaction 3f0c9a52-tx"));
+        assert!(screen.contains("customer-account"));
+        assert!(screen.contains("debit"));
+        assert!(screen.contains("pending"));
+        assert!(!screen.contains("Recent transactions"));
+    }
+}
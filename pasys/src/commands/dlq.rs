@@ -0,0 +1,36 @@
+use crate::cli::{Cli, DlqCommand};
+use crate::output::Table;
+use common::kafka;
+use std::time::Duration;
+
+pub async fn run(cli: &Cli, command: &DlqCommand) -> anyhow::Result<Table> {
+    let brokers = match &cli.kafka_brokers {
+        Some(brokers) => brokers.clone(),
+        None => anyhow::bail!("KAFKA_BROKERS must be set or passed with --kafka-brokers"),
+    };
+
+    match command {
+        DlqCommand::Replay {
+            group,
+            limit,
+            idle_timeout,
+        } => {
+            let config = kafka::Config {
+                brokers,
+                group_id: group.clone(),
+            };
+            let replayed =
+                kafka::replay_dead_letters(&config, *limit, Duration::from_secs(*idle_timeout))
+                    .await?;
+
+            let mut table = Table::new(vec!["group", "from", "to", "replayed"]);
+            table.push(vec![
+                group.clone(),
+                kafka::dead_letter_topic(group),
+                kafka::retry_topic(group),
+                replayed.to_string(),
+            ]);
+            Ok(table)
+        }
+    }
+}
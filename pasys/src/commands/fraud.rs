@@ -0,0 +1,102 @@
+use crate::cli::{Cli, FraudCommand};
+use crate::commands::{connect, parse_timestamp, status_error};
+use crate::output::Table;
+use fraud_proto::fraud_v1;
+use fraud_proto::fraud_v1::fraud_reviews_client::FraudReviewsClient;
+
+pub async fn run(cli: &Cli, command: &FraudCommand) -> anyhow::Result<Table> {
+    let mut client = FraudReviewsClient::new(connect(cli.fraud_url.as_deref(), "FRAUD_URL").await?);
+
+    let reports = match command {
+        FraudCommand::Report { from, to } => {
+            let request = fraud_v1::GetEvaluationReportRequest {
+                from: match from {
+                    Some(from) => Some(parse_timestamp("--from", from)?),
+                    None => None,
+                },
+                to: match to {
+                    Some(to) => Some(parse_timestamp("--to", to)?),
+                    None => None,
+                },
+            };
+            match client.get_evaluation_report(request).await {
+                Ok(response) => response.into_inner().reports,
+                Err(e) => return Err(status_error("get evaluation report", e)),
+            }
+        }
+    };
+
+    Ok(reports_table(reports))
+}
+
+fn format_ratio(ratio: Option<f64>) -> String {
+    match ratio {
+        Some(ratio) => format!("{:.4}", ratio),
+        None => "n/a".to_string(),
+    }
+}
+
+pub fn reports_table(reports: Vec<fraud_v1::ModelReport>) -> Table {
+    let mut table = Table::new(vec![
+        "role",
+        "model_version",
+        "tp",
+        "fp",
+        "tn",
+        "fn",
+        "precision",
+        "recall",
+    ]);
+    for report in reports {
+        table.push(vec![
+            report.role.clone(),
+            report.model_version.clone(),
+            report.true_positives.to_string(),
+            report.false_positives.to_string(),
+            report.true_negatives.to_string(),
+            report.false_negatives.to_string(),
+            format_ratio(report.precision),
+            format_ratio(report.recall),
+        ]);
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn successfully_build_reports_table() {
+        // arrange
+        let report = fraud_v1::ModelReport {
+            role: "champion".to_string(),
+            model_version: "logreg-v1".to_string(),
+            true_positives: 3,
+            false_positives: 1,
+            true_negatives: 90,
+            false_negatives: 0,
+            precision: Some(0.75),
+            recall: None,
+        };
+
+        // act
+        let table = reports_table(vec![report]);
+
+        // assert
+        assert_eq!(
+            table.rows,
+            vec![vec![
+                "champion".to_string(),
+                "logreg-v1".to_string(),
+                "3".to_string(),
+                "1".to_string(),
+                "90".to_string(),
+                "0".to_string(),
+                "0.7500".to_string(),
+                "n/a".to_string(),
+            ]]
+        );
+    }
+}
@@ -0,0 +1,266 @@
+pub mod accounts;
+pub mod dashboard;
+pub mod dlq;
+pub mod fraud;
+pub mod recon;
+pub mod refund;
+pub mod schedule;
+pub mod seed;
+pub mod simulate;
+pub mod stack;
+pub mod tx;
+
+use crate::cli::{Cli, Command};
+use crate::output::Table;
+use ledger_proto::google::r#type::Money;
+use prost_types::Timestamp;
+use tonic::transport::Channel;
+
+/// run executes the command and returns its results for printing, most commands have one and
+/// the dashboard none.
+pub async fn run(cli: &Cli) -> anyhow::Result<Vec<Table>> {
+    let table = match &cli.command {
+        Command::Accounts(command) => accounts::run(cli, command).await?,
+        Command::Tx(command) => tx::run(cli, command).await?,
+        Command::Refund(command) => refund::run(cli, command).await?,
+        Command::Schedule(command) => schedule::run(cli, command).await?,
+        Command::Recon(command) => recon::run(cli, command).await?,
+        Command::Fraud(command) => fraud::run(cli, command).await?,
+        Command::Dlq(command) => dlq::run(cli, command).await?,
+        Command::Seed(args) => seed::run(cli, args).await?,
+        Command::Simulate(args) => return simulate::run(cli, args).await,
+        Command::Up(args) => stack::up(cli, args).await?,
+        Command::Down(args) => stack::down(args).await?,
+        Command::Dashboard(args) => {
+            dashboard::run(cli, args).await?;
+            return Ok(vec![]);
+        }
+    };
+
+    Ok(vec![table])
+}
+
+/// connect opens a channel to the service at `url`, `env` names the variable it is read from.
+async fn connect(url: Option<&str>, env: &str) -> anyhow::Result<Channel> {
+    let url = match url {
+        Some(url) => url,
+        None => anyhow::bail!(
+            "{env} must be set or passed with --{}",
+            env.to_lowercase().replace('_', "-")
+        ),
+    };
+    let endpoint = match Channel::from_shared(url.to_string()) {
+        Ok(endpoint) => endpoint,
+        Err(e) => anyhow::bail!("invalid {env} {url}: {e}"),
+    };
+
+    match endpoint.connect().await {
+        Ok(channel) => Ok(channel),
+        Err(e) => anyhow::bail!("failed to connect to {url}: {e}"),
+    }
+}
+
+/// status_error turns a failed call into an error carrying the service's message.
+fn status_error(action: &str, status: tonic::Status) -> anyhow::Error {
+    anyhow::anyhow!(
+        "failed to {action}: {} ({:?})",
+        status.message(),
+        status.code()
+    )
+}
+
+/// enum_name turns a proto enum name such as `ACCOUNT_STATUS_ACTIVE` into `active`.
+fn enum_name(name: &str, prefix: &str) -> String {
+    name.strip_prefix(prefix).unwrap_or(name).to_lowercase()
+}
+
+/// parse_amount parses a non-negative decimal amount in major units of the currency, such as
+/// `10.50` USD or `1050` JPY, rejecting more decimals than the currency has.
+pub fn parse_amount(amount: &str, currency: &str) -> anyhow::Result<Money> {
+    if amount.starts_with('-') {
+        anyhow::bail!("invalid amount {amount}, must not be negative");
+    }
+    let money = match common::money::Money::parse(amount, currency) {
+        Ok(money) => money,
+        Err(e) => anyhow::bail!("invalid amount {amount}: {e}"),
+    };
+    let (units, nanos) = money.to_units_nanos();
+
+    Ok(Money {
+        currency_code: money.currency().code().to_string(),
+        units,
+        nanos,
+    })
+}
+
+/// format_amount prints the amount with all decimals of its currency. Amounts outside ISO 4217
+/// are printed with at least two decimals.
+pub fn format_amount(money: &Money) -> String {
+    if let Ok(money) =
+        common::money::Money::from_units_nanos(money.units, money.nanos, &money.currency_code)
+    {
+        return money.to_decimal();
+    }
+
+    let fraction = format!("{:09}", money.nanos.unsigned_abs());
+    let fraction = fraction.trim_end_matches('0');
+    let sign = match money.units == 0 && money.nanos < 0 {
+        true => "-",
+        false => "",
+    };
+
+    format!("{sign}{}.{fraction:0<2}", money.units)
+}
+
+/// parse_timestamp parses an RFC 3339 timestamp.
+fn parse_timestamp(name: &str, timestamp: &str) -> anyhow::Result<Timestamp> {
+    match chrono::DateTime::parse_from_rfc3339(timestamp) {
+        Ok(timestamp) => Ok(Timestamp {
+            seconds: timestamp.timestamp(),
+            nanos: timestamp.timestamp_subsec_nanos() as i32,
+        }),
+        Err(e) => anyhow::bail!("invalid {name} {timestamp}, expected RFC 3339: {e}"),
+    }
+}
+
+/// format_timestamp prints the timestamp as RFC 3339 in UTC, empty when it is unset.
+fn format_timestamp(timestamp: Option<&Timestamp>) -> String {
+    timestamp
+        .and_then(|timestamp| {
+            chrono::DateTime::from_timestamp(timestamp.seconds, timestamp.nanos as u32)
+        })
+        .map(|timestamp| timestamp.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_amount() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            amount: &'static str,
+            currency: &'static str,
+            expected: Option<(i64, i32)>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "successfully parse whole units",
+                amount: "10",
+                currency: "USD",
+                expected: Some((10, 0)),
+            },
+            TestCase {
+                name: "successfully parse cents",
+                amount: "10.05",
+                currency: "usd",
+                expected: Some((10, 50_000_000)),
+            },
+            TestCase {
+                name: "successfully parse a trailing dot",
+                amount: "3.",
+                currency: "USD",
+                expected: Some((3, 0)),
+            },
+            TestCase {
+                name: "successfully parse fils",
+                amount: "1.005",
+                currency: "KWD",
+                expected: Some((1, 5_000_000)),
+            },
+            TestCase {
+                name: "error when amount is negative",
+                amount: "-1.00",
+                currency: "USD",
+                expected: None,
+            },
+            TestCase {
+                name: "error when amount has more decimals than the currency",
+                amount: "0.001",
+                currency: "USD",
+                expected: None,
+            },
+            TestCase {
+                name: "error when yen have decimals",
+                amount: "10.5",
+                currency: "JPY",
+                expected: None,
+            },
+            TestCase {
+                name: "error when currency is unknown",
+                amount: "10",
+                currency: "XYZ",
+                expected: None,
+            },
+            TestCase {
+                name: "error when amount is not a number",
+                amount: "ten",
+                currency: "USD",
+                expected: None,
+            },
+        ];
+
+        for test_case in test_cases {
+            let resp = parse_amount(test_case.amount, test_case.currency);
+            match test_case.expected {
+                Some(expected) => {
+                    let money = resp.unwrap();
+                    assert_eq!((money.units, money.nanos), expected, "{}", test_case.name);
+                    assert_eq!(
+                        money.currency_code,
+                        test_case.currency.to_uppercase(),
+                        "{}",
+                        test_case.name
+                    );
+                }
+                None => assert!(resp.is_err(), "{}", test_case.name),
+            }
+        }
+    }
+
+    #[test]
+    fn test_format_amount() {
+        let test_cases = vec![
+            ((10, 0, "USD"), "10.00"),
+            ((10, 50_000_000, "USD"), "10.05"),
+            ((1050, 0, "JPY"), "1050"),
+            ((0, 125_000_000, "KWD"), "0.125"),
+            ((0, -500_000_000, "USD"), "-0.50"),
+            ((0, 125_000_000, "XYZ"), "0.125"),
+            ((0, -500_000_000, "XYZ"), "-0.50"),
+        ];
+
+        for ((units, nanos, currency), expected) in test_cases {
+            let money = Money {
+                currency_code: currency.to_string(),
+                units,
+                nanos,
+            };
+            assert_eq!(format_amount(&money), expected);
+        }
+    }
+
+    #[test]
+    fn successfully_round_trip_timestamp() {
+        // act
+        let timestamp = parse_timestamp("from", "2025-10-30T10:00:00+01:00").unwrap();
+
+        // assert
+        assert_eq!(format_timestamp(Some(&timestamp)), "2025-10-30T09:00:00Z");
+        assert_eq!(format_timestamp(None), "");
+        assert!(parse_timestamp("from", "yesterday").is_err());
+    }
+
+    #[test]
+    fn successfully_strip_enum_prefix() {
+        assert_eq!(
+            enum_name("ACCOUNT_STATUS_ACTIVE", "ACCOUNT_STATUS_"),
+            "active"
+        );
+        assert_eq!(enum_name("UNKNOWN", "ACCOUNT_STATUS_"), "unknown");
+    }
+}
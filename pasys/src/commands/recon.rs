@@ -0,0 +1,60 @@
+use crate::cli::{Cli, ReconCommand};
+use crate::commands::{connect, enum_name, format_timestamp, parse_timestamp, status_error};
+use crate::output::Table;
+use reconciliation_proto::reconciliation_v1;
+use reconciliation_proto::reconciliation_v1::reconciliation_exceptions_client::ReconciliationExceptionsClient;
+
+pub async fn run(cli: &Cli, command: &ReconCommand) -> anyhow::Result<Table> {
+    let mut client = ReconciliationExceptionsClient::new(
+        connect(cli.reconciliation_url.as_deref(), "RECONCILIATION_URL").await?,
+    );
+
+    let runs = match command {
+        ReconCommand::Run { from, to } => {
+            let request = reconciliation_v1::RunReconciliationRequest {
+                window_start: match from {
+                    Some(from) => Some(parse_timestamp("--from", from)?),
+                    None => None,
+                },
+                window_end: match to {
+                    Some(to) => Some(parse_timestamp("--to", to)?),
+                    None => None,
+                },
+            };
+            match client.run_reconciliation(request).await {
+                Ok(response) => response.into_inner().run.into_iter().collect(),
+                Err(e) => return Err(status_error("run reconciliation", e)),
+            }
+        }
+    };
+
+    Ok(runs_table(runs))
+}
+
+/// runs_table has no rows when there was nothing left to reconcile.
+pub fn runs_table(runs: Vec<reconciliation_v1::Run>) -> Table {
+    let mut table = Table::new(vec![
+        "id",
+        "source",
+        "window_start",
+        "window_end",
+        "status",
+        "matched",
+        "mismatched",
+        "failure_reason",
+    ]);
+    for run in runs {
+        table.push(vec![
+            run.id.clone(),
+            run.source.clone(),
+            format_timestamp(run.window_start.as_ref()),
+            format_timestamp(run.window_end.as_ref()),
+            enum_name(run.status().as_str_name(), "RUN_STATUS_"),
+            run.matched_count.to_string(),
+            run.mismatched_count.to_string(),
+            run.failure_reason.clone().unwrap_or_default(),
+        ]);
+    }
+
+    table
+}
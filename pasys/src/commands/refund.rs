@@ -0,0 +1,69 @@
+use crate::cli::{Cli, RefundCommand, RefundReason};
+use crate::commands::{
+    connect, enum_name, format_amount, format_timestamp, parse_amount, status_error,
+};
+use crate::output::Table;
+use ledger_proto::ledger_v1;
+use ledger_proto::ledger_v1::ledger_client::LedgerClient;
+
+pub async fn run(cli: &Cli, command: &RefundCommand) -> anyhow::Result<Table> {
+    let mut client = LedgerClient::new(connect(cli.ledger_url.as_deref(), "LEDGER_URL").await?);
+
+    let refunds = match command {
+        RefundCommand::Create(args) => {
+            let amount = parse_amount(args.amount.as_str(), args.currency.as_str())?;
+            let request = ledger_v1::CreateRefundRequest {
+                transaction_id: args.transaction_id.clone(),
+                amount: Some(amount),
+                reason: parse_refund_reason(args.reason) as i32,
+                requested_by: args.requested_by.clone(),
+            };
+            match client.create_refund(request).await {
+                Ok(response) => response.into_inner().refund.into_iter().collect(),
+                Err(e) => return Err(status_error("create refund", e)),
+            }
+        }
+    };
+
+    Ok(refunds_table(refunds))
+}
+
+fn parse_refund_reason(reason: RefundReason) -> ledger_v1::RefundReason {
+    match reason {
+        RefundReason::RequestedByCustomer => ledger_v1::RefundReason::RequestedByCustomer,
+        RefundReason::Duplicate => ledger_v1::RefundReason::Duplicate,
+        RefundReason::Fraudulent => ledger_v1::RefundReason::Fraudulent,
+        RefundReason::Chargeback => ledger_v1::RefundReason::Chargeback,
+    }
+}
+
+pub fn refunds_table(refunds: Vec<ledger_v1::Refund>) -> Table {
+    let mut table = Table::new(vec![
+        "id",
+        "transaction_id",
+        "amount",
+        "currency",
+        "reason",
+        "status",
+        "requested_by",
+        "created_at",
+    ]);
+    for refund in refunds {
+        let (amount, currency) = match &refund.amount {
+            Some(money) => (format_amount(money), money.currency_code.clone()),
+            None => (String::new(), String::new()),
+        };
+        table.push(vec![
+            refund.id.clone(),
+            refund.transaction_id.clone(),
+            amount,
+            currency,
+            enum_name(refund.reason().as_str_name(), "REFUND_REASON_"),
+            enum_name(refund.status().as_str_name(), "REFUND_STATUS_"),
+            refund.requested_by.clone(),
+            format_timestamp(refund.created_at.as_ref()),
+        ]);
+    }
+
+    table
+}
@@ -0,0 +1,166 @@
+use crate::cli::{Cli, ScheduleCommand};
+use crate::commands::{
+    connect, enum_name, format_amount, format_timestamp, parse_amount, parse_timestamp,
+    status_error,
+};
+use crate::output::Table;
+use ledger_proto::ledger_v1;
+use ledger_proto::ledger_v1::ledger_client::LedgerClient;
+
+pub async fn run(cli: &Cli, command: &ScheduleCommand) -> anyhow::Result<Table> {
+    let mut client = LedgerClient::new(connect(cli.ledger_url.as_deref(), "LEDGER_URL").await?);
+
+    let schedules = match command {
+        ScheduleCommand::Create(args) => {
+            let amount = parse_amount(args.amount.as_str(), args.currency.as_str())?;
+            let request = ledger_v1::CreateScheduleRequest {
+                debit_account_id: args.from.clone(),
+                credit_account_id: args.to.clone(),
+                amount: Some(amount),
+                cron: args.cron.clone().unwrap_or_default(),
+                interval_seconds: args.every.unwrap_or_default(),
+                starts_at: args
+                    .starts_at
+                    .as_deref()
+                    .map(|starts_at| parse_timestamp("starts-at", starts_at))
+                    .transpose()?,
+                ends_at: args
+                    .ends_at
+                    .as_deref()
+                    .map(|ends_at| parse_timestamp("ends-at", ends_at))
+                    .transpose()?,
+                idempotency_key: args
+                    .idempotency_key
+                    .clone()
+                    .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+            };
+            match client.create_schedule(request).await {
+                Ok(response) => response.into_inner().schedule.into_iter().collect(),
+                Err(e) => return Err(status_error("create schedule", e)),
+            }
+        }
+        ScheduleCommand::Get { schedule_id } => {
+            let request = ledger_v1::GetScheduleRequest {
+                schedule_id: schedule_id.clone(),
+            };
+            match client.get_schedule(request).await {
+                Ok(response) => response.into_inner().schedule.into_iter().collect(),
+                Err(e) => return Err(status_error("get schedule", e)),
+            }
+        }
+        ScheduleCommand::List { account } => {
+            let request = ledger_v1::ListSchedulesRequest {
+                account_id: account.clone().unwrap_or_default(),
+            };
+            match client.list_schedules(request).await {
+                Ok(response) => response.into_inner().schedules,
+                Err(e) => return Err(status_error("list schedules", e)),
+            }
+        }
+        ScheduleCommand::Runs { schedule_id } => {
+            let request = ledger_v1::ListScheduleRunsRequest {
+                schedule_id: schedule_id.clone(),
+            };
+            return match client.list_schedule_runs(request).await {
+                Ok(response) => Ok(runs_table(response.into_inner().runs)),
+                Err(e) => Err(status_error("list schedule runs", e)),
+            };
+        }
+        ScheduleCommand::Pause { schedule_id } => {
+            let request = action_request(schedule_id);
+            match client.pause_schedule(request).await {
+                Ok(response) => response.into_inner().schedule.into_iter().collect(),
+                Err(e) => return Err(status_error("pause schedule", e)),
+            }
+        }
+        ScheduleCommand::Resume { schedule_id } => {
+            let request = action_request(schedule_id);
+            match client.resume_schedule(request).await {
+                Ok(response) => response.into_inner().schedule.into_iter().collect(),
+                Err(e) => return Err(status_error("resume schedule", e)),
+            }
+        }
+        ScheduleCommand::Cancel { schedule_id } => {
+            let request = action_request(schedule_id);
+            match client.cancel_schedule(request).await {
+                Ok(response) => response.into_inner().schedule.into_iter().collect(),
+                Err(e) => return Err(status_error("cancel schedule", e)),
+            }
+        }
+        ScheduleCommand::Skip { schedule_id } => {
+            let request = action_request(schedule_id);
+            return match client.skip_schedule_run(request).await {
+                Ok(response) => Ok(runs_table(response.into_inner().run.into_iter().collect())),
+                Err(e) => Err(status_error("skip schedule run", e)),
+            };
+        }
+    };
+
+    Ok(schedules_table(schedules))
+}
+
+fn action_request(schedule_id: &str) -> ledger_v1::ScheduleActionRequest {
+    ledger_v1::ScheduleActionRequest {
+        schedule_id: schedule_id.to_string(),
+    }
+}
+
+pub fn schedules_table(schedules: Vec<ledger_v1::Schedule>) -> Table {
+    let mut table = Table::new(vec![
+        "id",
+        "from",
+        "to",
+        "amount",
+        "currency",
+        "recurrence",
+        "status",
+        "next_run_at",
+        "ends_at",
+    ]);
+    for schedule in schedules {
+        let (amount, currency) = match &schedule.amount {
+            Some(money) => (format_amount(money), money.currency_code.clone()),
+            None => (String::new(), String::new()),
+        };
+        let recurrence = match schedule.cron.is_empty() {
+            true => format!("every {}s", schedule.interval_seconds),
+            false => schedule.cron.clone(),
+        };
+        table.push(vec![
+            schedule.id.clone(),
+            schedule.debit_account_id.clone(),
+            schedule.credit_account_id.clone(),
+            amount,
+            currency,
+            recurrence,
+            enum_name(schedule.status().as_str_name(), "SCHEDULE_STATUS_"),
+            format_timestamp(schedule.next_run_at.as_ref()),
+            format_timestamp(schedule.ends_at.as_ref()),
+        ]);
+    }
+
+    table
+}
+
+pub fn runs_table(runs: Vec<ledger_v1::ScheduleRun>) -> Table {
+    let mut table = Table::new(vec![
+        "id",
+        "schedule_id",
+        "occurrence_at",
+        "status",
+        "transaction_id",
+        "error",
+    ]);
+    for run in runs {
+        table.push(vec![
+            run.id.clone(),
+            run.schedule_id.clone(),
+            format_timestamp(run.occurrence_at.as_ref()),
+            enum_name(run.status().as_str_name(), "SCHEDULE_RUN_STATUS_"),
+            run.transaction_id.clone(),
+            run.error.clone(),
+        ]);
+    }
+
+    table
+}
@@ -0,0 +1,92 @@
+use crate::cli::{Cli, SeedArgs};
+use crate::commands::accounts::accounts_table;
+use crate::commands::{connect, status_error};
+use crate::output::Table;
+use accounts_proto::accounts_v1;
+use accounts_proto::accounts_v1::accounts_client::AccountsClient;
+use futures_util::{StreamExt, TryStreamExt, stream};
+
+pub async fn run(cli: &Cli, args: &SeedArgs) -> anyhow::Result<Table> {
+    let client = AccountsClient::new(connect(cli.accounts_url.as_deref(), "ACCOUNTS_URL").await?);
+
+    let accounts: Vec<accounts_v1::Account> = stream::iter(seed_requests(args))
+        .map(|request| {
+            let mut client = client.clone();
+            async move {
+                match client.create_account(request).await {
+                    Ok(response) => Ok(response.into_inner().account),
+                    Err(e) => Err(status_error("create account", e)),
+                }
+            }
+        })
+        .buffered(args.concurrency.max(1))
+        .try_collect::<Vec<_>>()
+        .await?
+        .into_iter()
+        .flatten()
+        .collect();
+
+    Ok(accounts_table(accounts))
+}
+
+/// seed_requests lists the accounts to create, customers first, then merchants and system
+/// accounts.
+pub fn seed_requests(args: &SeedArgs) -> Vec<accounts_v1::CreateAccountRequest> {
+    [
+        (
+            accounts_v1::AccountType::Customer,
+            "customer",
+            args.customers,
+        ),
+        (
+            accounts_v1::AccountType::Merchant,
+            "merchant",
+            args.merchants,
+        ),
+        (accounts_v1::AccountType::System, "system", args.system),
+    ]
+    .into_iter()
+    .flat_map(|(account_type, name, count)| {
+        (1..=count).map(move |n| accounts_v1::CreateAccountRequest {
+            name: format!("{} {name} {n}", args.prefix),
+            r#type: account_type as i32,
+            created_by: args.created_by.clone(),
+        })
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn successfully_build_seed_requests() {
+        // arrange
+        let args = SeedArgs {
+            customers: 2,
+            merchants: 1,
+            system: 0,
+            prefix: "demo".to_string(),
+            created_by: "ops".to_string(),
+            concurrency: 1,
+        };
+
+        // act
+        let requests = seed_requests(&args);
+
+        // assert
+        assert_eq!(
+            requests
+                .iter()
+                .map(|request| (request.name.as_str(), request.r#type()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("demo customer 1", accounts_v1::AccountType::Customer),
+                ("demo customer 2", accounts_v1::AccountType::Customer),
+                ("demo merchant 1", accounts_v1::AccountType::Merchant),
+            ]
+        );
+        assert!(requests.iter().all(|request| request.created_by == "ops"));
+    }
+}
@@ -0,0 +1,549 @@
+use crate::cli::{Cli, SimulateArgs};
+use crate::commands::{connect, parse_amount, status_error};
+use crate::output::Table;
+use accounts_proto::accounts_v1;
+use accounts_proto::accounts_v1::accounts_client::AccountsClient;
+use common::money::Money;
+use futures_util::{StreamExt, stream};
+use ledger_proto::ledger_v1;
+use ledger_proto::ledger_v1::ledger_client::LedgerClient;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tokio::time::Instant;
+use tonic::transport::Channel;
+
+const REGISTRATION_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Transfer is a synthetic transaction, sent `at` after the simulation started. `burst` numbers
+/// the burst it belongs to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transfer {
+    pub debit_account_id: String,
+    pub credit_account_id: String,
+    pub amount_minor: i64,
+    pub currency: String,
+    pub burst: Option<u32>,
+    pub at: Duration,
+}
+
+impl Transfer {
+    /// amount converts the amount to `google.type.Money`, the currency was checked when the
+    /// transfer was generated.
+    fn amount(&self) -> ledger_v1::google::r#type::Money {
+        let (units, nanos) = Money::from_minor(self.amount_minor, &self.currency)
+            .expect("generate only draws ISO 4217 currencies")
+            .to_units_nanos();
+        ledger_v1::google::r#type::Money {
+            currency_code: self.currency.clone(),
+            units,
+            nanos,
+        }
+    }
+}
+
+/// Outcome is how the ledger answered a transfer, `Err` holds the grpc code of a rejection.
+type Outcome = (Duration, Result<(), tonic::Code>);
+
+/// Balances are posted balances in minor units keyed by account and currency.
+type Balances = BTreeMap<(String, String), i64>;
+
+pub async fn run(cli: &Cli, args: &SimulateArgs) -> anyhow::Result<Vec<Table>> {
+    let mut accounts =
+        AccountsClient::new(connect(cli.accounts_url.as_deref(), "ACCOUNTS_URL").await?);
+    let ledger = LedgerClient::new(connect(cli.ledger_url.as_deref(), "LEDGER_URL").await?);
+
+    let customers = active_accounts(&mut accounts, accounts_v1::AccountType::Customer).await?;
+    let merchants = active_accounts(&mut accounts, accounts_v1::AccountType::Merchant).await?;
+    if customers.is_empty() || merchants.is_empty() {
+        anyhow::bail!(
+            "simulate needs active customer and merchant accounts, create some with `pasys seed`"
+        );
+    }
+    let account_ids: Vec<String> = customers.iter().chain(&merchants).cloned().collect();
+
+    let seed = args.seed.unwrap_or_else(rand::random);
+    let transfers = generate(
+        args,
+        &customers,
+        &merchants,
+        &mut StdRng::seed_from_u64(seed),
+    )?;
+
+    let before = get_balances(
+        &mut ledger.clone(),
+        &account_ids,
+        Duration::from_secs(args.wait),
+    )
+    .await?;
+
+    let run_id = uuid::Uuid::new_v4();
+    let started = Instant::now();
+    let outcomes: Vec<Outcome> = stream::iter(transfers.iter().enumerate())
+        .map(|(index, transfer)| {
+            let mut ledger = ledger.clone();
+            let mut request = ledger_v1::CreateTransactionRequest {
+                id: uuid::Uuid::new_v4().to_string(),
+                debit_account_id: transfer.debit_account_id.clone(),
+                credit_account_id: transfer.credit_account_id.clone(),
+                amount: Some(transfer.amount()),
+                request_timestamp: None,
+                idempotency_key: format!("simulate-{run_id}-{index}"),
+                credit_currency: String::new(),
+            };
+            let at = started + transfer.at;
+            async move {
+                tokio::time::sleep_until(at).await;
+                request.request_timestamp =
+                    Some(prost_types::Timestamp::from(std::time::SystemTime::now()));
+                let sent = Instant::now();
+                let result = ledger.create_transaction(request).await;
+                (sent.elapsed(), result.map(|_| ()).map_err(|e| e.code()))
+            }
+        })
+        .buffer_unordered(args.concurrency.max(1))
+        .collect()
+        .await;
+    let elapsed = started.elapsed();
+
+    let after = get_balances(&mut ledger.clone(), &account_ids, Duration::ZERO).await?;
+
+    Ok(vec![
+        summary_table(seed, &transfers, &outcomes, elapsed),
+        balances_table(&customers, &before, &after),
+    ])
+}
+
+async fn active_accounts(
+    client: &mut AccountsClient<Channel>,
+    account_type: accounts_v1::AccountType,
+) -> anyhow::Result<Vec<String>> {
+    let request = accounts_v1::GetAccountsRequest {
+        filter: Some(accounts_v1::get_accounts_request::Filter {
+            account_ids: vec![],
+            account_type: account_type as i32,
+            account_status: accounts_v1::AccountStatus::Active as i32,
+        }),
+    };
+
+    match client.get_accounts(request).await {
+        Ok(response) => Ok(response
+            .into_inner()
+            .accounts
+            .into_iter()
+            .map(|account| account.id)
+            .collect()),
+        Err(e) => Err(status_error("list accounts", e)),
+    }
+}
+
+/// get_balances reads the posted balances, waiting up to `wait` for the accounts to be
+/// registered with the ledger, which happens asynchronously after they are created.
+async fn get_balances(
+    client: &mut LedgerClient<Channel>,
+    account_ids: &[String],
+    wait: Duration,
+) -> anyhow::Result<Balances> {
+    let deadline = Instant::now() + wait;
+    loop {
+        let request = ledger_v1::GetBalancesRequest {
+            account_ids: account_ids.to_vec(),
+        };
+        match client.get_balances(request).await {
+            Ok(response) => {
+                return Ok(response
+                    .into_inner()
+                    .balances
+                    .into_iter()
+                    .filter_map(|balance| {
+                        let amount = balance.amount?;
+                        let money = Money::from_units_nanos(
+                            amount.units,
+                            amount.nanos,
+                            &amount.currency_code,
+                        )
+                        .ok()?;
+                        Some((
+                            (balance.account_id, amount.currency_code),
+                            money.amount_minor(),
+                        ))
+                    })
+                    .collect());
+            }
+            Err(e) if e.code() == tonic::Code::FailedPrecondition && Instant::now() < deadline => {
+                tokio::time::sleep(REGISTRATION_POLL_INTERVAL).await;
+            }
+            Err(e) => return Err(status_error("get balances", e)),
+        }
+    }
+}
+
+/// generate draws the transfers of a simulation. Customers pay merchants log-normally
+/// distributed amounts in weighted currencies, paced at `args.rate`. A transfer may instead
+/// start a burst, a run of larger payments from one customer sent at once, as fraud tends to
+/// look.
+pub fn generate(
+    args: &SimulateArgs,
+    customers: &[String],
+    merchants: &[String],
+    rng: &mut impl Rng,
+) -> anyhow::Result<Vec<Transfer>> {
+    let mut medians_minor = BTreeMap::new();
+    for (currency, _) in &args.currencies {
+        let median = parse_amount(args.amount_median.as_str(), currency)?;
+        let median_minor = Money::from_units_nanos(median.units, median.nanos, currency)?;
+        if median_minor.amount_minor() <= 0 {
+            anyhow::bail!("amount median must be at least one minor unit of {currency}");
+        }
+        medians_minor.insert(currency.as_str(), median_minor.amount_minor());
+    }
+    if !(0.0..=1.0).contains(&args.burst_probability) {
+        anyhow::bail!("burst probability must be between 0 and 1");
+    }
+    if customers.is_empty() || merchants.is_empty() || args.currencies.is_empty() {
+        anyhow::bail!("simulate needs customers, merchants and currencies");
+    }
+
+    let count = args.transactions as usize;
+    let mut transfers = Vec::with_capacity(count);
+    let mut bursts = 0;
+    while transfers.len() < count {
+        let at = match args.rate > 0.0 {
+            true => Duration::from_secs_f64(transfers.len() as f64 / args.rate),
+            false => Duration::ZERO,
+        };
+        let customer = pick(customers, rng);
+        let currency = pick_weighted(&args.currencies, rng);
+        let amount_minor = log_normal(medians_minor[currency.as_str()], args.amount_sigma, rng);
+
+        let (size, multiplier, burst) = match rng.gen_bool(args.burst_probability) {
+            true => {
+                bursts += 1;
+                (
+                    args.burst_size.max(1) as usize,
+                    args.burst_multiplier,
+                    Some(bursts),
+                )
+            }
+            false => (1, 1.0, None),
+        };
+        for _ in 0..size.min(count - transfers.len()) {
+            transfers.push(Transfer {
+                debit_account_id: customer.clone(),
+                credit_account_id: pick(merchants, rng).clone(),
+                amount_minor: ((amount_minor as f64 * multiplier).round() as i64).max(1),
+                currency: currency.clone(),
+                burst,
+                at,
+            });
+        }
+    }
+
+    Ok(transfers)
+}
+
+fn pick<'a, T>(items: &'a [T], rng: &mut impl Rng) -> &'a T {
+    &items[rng.gen_range(0..items.len())]
+}
+
+fn pick_weighted(items: &[(String, f64)], rng: &mut impl Rng) -> String {
+    let total: f64 = items.iter().map(|(_, weight)| weight).sum();
+    let mut target = rng.r#gen::<f64>() * total;
+    for (item, weight) in items {
+        if target < *weight {
+            return item.clone();
+        }
+        target -= weight;
+    }
+
+    items[items.len() - 1].0.clone()
+}
+
+/// log_normal draws an amount whose logarithm is normally distributed around the median's,
+/// using the Box-Muller transform. Amounts are at least one minor unit.
+fn log_normal(median_minor: i64, sigma: f64, rng: &mut impl Rng) -> i64 {
+    let u1 = 1.0 - rng.r#gen::<f64>();
+    let u2 = rng.r#gen::<f64>();
+    let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+
+    ((median_minor as f64 * (sigma * z).exp()).round() as i64).max(1)
+}
+
+/// format_minor prints minor units of a currency the ledger answered, which is always in ISO 4217.
+fn format_minor(amount_minor: i64, currency: &str) -> String {
+    match Money::from_minor(amount_minor, currency) {
+        Ok(money) => money.to_decimal(),
+        Err(_) => amount_minor.to_string(),
+    }
+}
+
+/// percentile returns the nearest-rank percentile of sorted latencies.
+pub fn percentile(sorted: &[Duration], percentile: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = ((percentile / 100.0) * sorted.len() as f64).ceil() as usize;
+
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn summary_table(
+    seed: u64,
+    transfers: &[Transfer],
+    outcomes: &[Outcome],
+    elapsed: Duration,
+) -> Table {
+    let mut latencies: Vec<Duration> = outcomes.iter().map(|(latency, _)| *latency).collect();
+    latencies.sort();
+    let mut rejected: BTreeMap<String, u64> = BTreeMap::new();
+    for (_, result) in outcomes {
+        if let Err(code) = result {
+            *rejected
+                .entry(format!("rejected_{}", code_name(*code)))
+                .or_default() += 1;
+        }
+    }
+    let bursts: std::collections::BTreeSet<u32> = transfers
+        .iter()
+        .filter_map(|transfer| transfer.burst)
+        .collect();
+    let millis = |duration: Duration| format!("{:.1}", duration.as_secs_f64() * 1000.0);
+
+    let mut table = Table::new(vec!["metric", "value"]).with_title("summary");
+    let rows = [
+        ("seed", seed.to_string()),
+        ("transactions", transfers.len().to_string()),
+        ("bursts", bursts.len().to_string()),
+        (
+            "burst_transactions",
+            transfers
+                .iter()
+                .filter(|t| t.burst.is_some())
+                .count()
+                .to_string(),
+        ),
+        (
+            "accepted",
+            outcomes
+                .iter()
+                .filter(|(_, r)| r.is_ok())
+                .count()
+                .to_string(),
+        ),
+        ("rejected", rejected.values().sum::<u64>().to_string()),
+        ("elapsed_seconds", format!("{:.3}", elapsed.as_secs_f64())),
+        (
+            "throughput_per_second",
+            format!(
+                "{:.1}",
+                outcomes.len() as f64 / elapsed.as_secs_f64().max(f64::EPSILON)
+            ),
+        ),
+        ("latency_p50_ms", millis(percentile(&latencies, 50.0))),
+        ("latency_p90_ms", millis(percentile(&latencies, 90.0))),
+        ("latency_p99_ms", millis(percentile(&latencies, 99.0))),
+        ("latency_max_ms", millis(percentile(&latencies, 100.0))),
+    ];
+    for (metric, value) in rows {
+        table.push(vec![metric.to_string(), value]);
+    }
+    for (metric, count) in rejected {
+        table.push(vec![metric, count.to_string()]);
+    }
+
+    table
+}
+
+fn code_name(code: tonic::Code) -> String {
+    let mut name = String::new();
+    for (index, c) in format!("{code:?}").chars().enumerate() {
+        if c.is_ascii_uppercase() && index > 0 {
+            name.push('_');
+        }
+        name.push(c.to_ascii_lowercase());
+    }
+
+    name
+}
+
+fn balances_table(customers: &[String], before: &Balances, after: &Balances) -> Table {
+    let mut table = Table::new(vec![
+        "account_id",
+        "type",
+        "currency",
+        "before",
+        "after",
+        "change",
+    ])
+    .with_title("balances");
+
+    let keys: std::collections::BTreeSet<&(String, String)> =
+        before.keys().chain(after.keys()).collect();
+    for key @ (account_id, currency) in keys {
+        let before = before.get(key).copied().unwrap_or_default();
+        let after = after.get(key).copied().unwrap_or_default();
+        let account_type = match customers.contains(account_id) {
+            true => "customer",
+            false => "merchant",
+        };
+        table.push(vec![
+            account_id.clone(),
+            account_type.to_string(),
+            currency.clone(),
+            format_minor(before, currency),
+            format_minor(after, currency),
+            format_minor(after - before, currency),
+        ]);
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{Cli, Command};
+    use clap::Parser;
+
+    fn simulate_args(extra: &[&str]) -> SimulateArgs {
+        let args = [&["pasys", "simulate"], extra].concat();
+        match Cli::try_parse_from(args).unwrap().command {
+            Command::Simulate(args) => args,
+            command => panic!("unexpected command {command:?}"),
+        }
+    }
+
+    fn accounts(prefix: &str, count: usize) -> Vec<String> {
+        (0..count).map(|n| format!("{prefix}-{n}")).collect()
+    }
+
+    #[test]
+    fn successfully_generate_reproducible_transfers() {
+        // arrange
+        let args = simulate_args(&[
+            "--transactions",
+            "500",
+            "--rate",
+            "100",
+            "--currency",
+            "USD=3",
+            "--currency",
+            "EUR=1",
+            "--burst-probability",
+            "0.05",
+            "--burst-size",
+            "4",
+        ]);
+        let (customers, merchants) = (accounts("customer", 5), accounts("merchant", 2));
+
+        // act
+        let transfers =
+            generate(&args, &customers, &merchants, &mut StdRng::seed_from_u64(7)).unwrap();
+        let again = generate(&args, &customers, &merchants, &mut StdRng::seed_from_u64(7)).unwrap();
+
+        // assert
+        assert_eq!(transfers, again);
+        assert_eq!(transfers.len(), 500);
+        assert!(transfers.iter().all(|t| t.amount_minor >= 1
+            && customers.contains(&t.debit_account_id)
+            && merchants.contains(&t.credit_account_id)));
+        assert!(transfers.iter().any(|t| t.currency == "EUR"));
+        assert!(transfers.iter().filter(|t| t.currency == "USD").count() > 250);
+        assert_eq!(transfers[1].at, Duration::from_millis(10));
+        assert!(transfers.iter().any(|t| t.burst.is_some()));
+        for burst in transfers.iter().filter(|t| t.burst.is_some()) {
+            assert!(
+                transfers
+                    .iter()
+                    .filter(|t| t.burst == burst.burst)
+                    .all(|t| t.debit_account_id == burst.debit_account_id && t.at == burst.at)
+            );
+        }
+    }
+
+    #[test]
+    fn error_when_generating_with_invalid_arguments() {
+        let (customers, merchants) = (accounts("customer", 1), accounts("merchant", 1));
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let args = simulate_args(&["--amount-median", "0"]);
+        assert!(generate(&args, &customers, &merchants, &mut rng).is_err());
+        let args = simulate_args(&["--burst-probability", "1.5"]);
+        assert!(generate(&args, &customers, &merchants, &mut rng).is_err());
+        let args = simulate_args(&[]);
+        assert!(generate(&args, &[], &merchants, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_percentile() {
+        let latencies: Vec<Duration> = (1..=100).map(Duration::from_millis).collect();
+
+        let test_cases = vec![
+            (50.0, Duration::from_millis(50)),
+            (99.0, Duration::from_millis(99)),
+            (100.0, Duration::from_millis(100)),
+            (0.0, Duration::from_millis(1)),
+        ];
+
+        for (p, expected) in test_cases {
+            assert_eq!(percentile(&latencies, p), expected, "p{p}");
+        }
+        assert_eq!(percentile(&[], 50.0), Duration::ZERO);
+    }
+
+    #[test]
+    fn successfully_build_summary_and_balances_tables() {
+        // arrange
+        let transfer = Transfer {
+            debit_account_id: "customer".to_string(),
+            credit_account_id: "merchant".to_string(),
+            amount_minor: 250,
+            currency: "USD".to_string(),
+            burst: None,
+            at: Duration::ZERO,
+        };
+        let outcomes = vec![
+            (Duration::from_millis(10), Ok(())),
+            (
+                Duration::from_millis(30),
+                Err(tonic::Code::FailedPrecondition),
+            ),
+        ];
+        let before = Balances::from([(("customer".to_string(), "USD".to_string()), 1000)]);
+        let after = Balances::from([
+            (("customer".to_string(), "USD".to_string()), 750),
+            (("merchant".to_string(), "USD".to_string()), 250),
+        ]);
+
+        // act
+        let summary = summary_table(
+            7,
+            &[transfer.clone(), transfer],
+            &outcomes,
+            Duration::from_secs(2),
+        );
+        let balances = balances_table(&["customer".to_string()], &before, &after);
+
+        // assert
+        let metric = |name: &str| {
+            summary
+                .rows
+                .iter()
+                .find(|row| row[0] == name)
+                .map(|row| row[1].clone())
+        };
+        assert_eq!(metric("accepted").as_deref(), Some("1"));
+        assert_eq!(metric("rejected").as_deref(), Some("1"));
+        assert_eq!(metric("rejected_failed_precondition").as_deref(), Some("1"));
+        assert_eq!(metric("throughput_per_second").as_deref(), Some("1.0"));
+        assert_eq!(metric("latency_max_ms").as_deref(), Some("30.0"));
+        assert_eq!(
+            balances.rows,
+            vec![
+                vec!["customer", "customer", "USD", "10.00", "7.50", "-2.50"],
+                vec!["merchant", "merchant", "USD", "0.00", "2.50", "2.50"],
+            ]
+        );
+    }
+}
@@ -0,0 +1,903 @@
+use crate::cli::{Cli, DownArgs, UpArgs};
+use crate::output::Table;
+use accounts_proto::accounts_v1::accounts_client::AccountsClient;
+use common::shutdown;
+use fraud_proto::fraud_v1::fraud_reviews_client::FraudReviewsClient;
+use ledger_proto::ledger_v1::ledger_client::LedgerClient;
+use reconciliation_proto::reconciliation_v1::reconciliation_exceptions_client::ReconciliationExceptionsClient;
+use sqlx::migrate::{MigrateDatabase, Migrator};
+use sqlx::{PgPool, Postgres};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::Command;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tonic::transport::Channel;
+
+pub const DEFAULT_KAFKA_BROKERS: &str = "localhost:9094";
+const HEALTH_PROBE_INTERVAL: Duration = Duration::from_millis(500);
+const HEALTH_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+const STOP_TIMEOUT: Duration = Duration::from_secs(10);
+const FRAUD_MODEL_ENV: [&str; 3] = [
+    "FRAUD_MODEL_URL",
+    "FRAUD_MODEL_PATH",
+    "FRAUD_MODEL_RULES_PATH",
+];
+const COLOURS: [u8; 6] = [36, 33, 32, 35, 34, 91];
+const PASYS_COLOUR: u8 = 1;
+
+/// Health is how `pasys up` decides a started service is ready for the ones after it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Health {
+    Accounts(String),
+    Ledger(String),
+    FraudReviews(String),
+    Reconciliation(String),
+    Http(String),
+    /// Running is for workers without an API, they are ready once started.
+    Running,
+}
+
+/// Service is a process started by `pasys up`, `database` names its migrations directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Service {
+    pub name: &'static str,
+    pub database: &'static str,
+    pub env: Vec<(&'static str, String)>,
+    pub health: Health,
+}
+
+/// Plan is the services started in order and the ones skipped with the reason.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plan {
+    pub services: Vec<Service>,
+    pub skipped: Vec<(&'static str, String)>,
+}
+
+/// plan lists the services to start. Services are configured with the environment variables
+/// their binaries read, fraud-detector also needs one of the `FRAUD_MODEL_*` variables set.
+pub fn plan(kafka_brokers: &str, args: &UpArgs, fraud_model: bool) -> Plan {
+    let url = |port: u16| format!("http://localhost:{port}");
+    let accounts_url = url(args.base_port);
+    let ledger_url = url(args.base_port + 1);
+    let fraud_url = url(args.base_port + 2);
+    let reconciliation_url = url(args.base_port + 3);
+    let api_url = url(args.api_port);
+
+    let database = |database: &'static str| {
+        let url = database_url(args, database).to_string();
+        vec![
+            ("READER_DATABASE_URL", url.clone()),
+            ("WRITER_DATABASE_URL", url),
+        ]
+    };
+    let kafka = ("KAFKA_BROKERS", kafka_brokers.to_string());
+    let port = |port: u16| ("PORT", port.to_string());
+
+    let mut services = vec![
+        Service {
+            name: "accounts",
+            database: "accounts",
+            env: [
+                database("accounts"),
+                vec![kafka.clone(), port(args.base_port)],
+            ]
+            .concat(),
+            health: Health::Accounts(accounts_url.clone()),
+        },
+        Service {
+            name: "ledger",
+            database: "ledger",
+            env: [
+                database("ledger"),
+                vec![kafka.clone(), port(args.base_port + 1)],
+            ]
+            .concat(),
+            health: Health::Ledger(ledger_url.clone()),
+        },
+        Service {
+            name: "ledger-consumer",
+            database: "ledger",
+            env: [database("ledger"), vec![kafka.clone()]].concat(),
+            health: Health::Running,
+        },
+    ];
+    let mut skipped = vec![];
+
+    match fraud_model {
+        true => services.push(Service {
+            name: "fraud-detector",
+            database: "fraud",
+            env: [
+                database("fraud"),
+                vec![kafka.clone(), port(args.base_port + 2)],
+            ]
+            .concat(),
+            health: Health::FraudReviews(fraud_url),
+        }),
+        false => skipped.push((
+            "fraud-detector",
+            format!("none of {} is set", FRAUD_MODEL_ENV.join(", ")),
+        )),
+    }
+
+    match &args.psp_url {
+        Some(psp_url) => {
+            let psp = ("PSP_URL", psp_url.clone());
+            services.push(Service {
+                name: "refund-processor",
+                database: "ledger",
+                env: [database("ledger"), vec![kafka.clone(), psp.clone()]].concat(),
+                health: Health::Running,
+            });
+            services.push(Service {
+                name: "reconciliation",
+                database: "ledger",
+                env: [
+                    database("ledger"),
+                    vec![kafka.clone(), psp, port(args.base_port + 3)],
+                ]
+                .concat(),
+                health: Health::Reconciliation(reconciliation_url),
+            });
+        }
+        None => {
+            for name in ["refund-processor", "reconciliation"] {
+                skipped.push((name, "PSP_URL is not set".to_string()));
+            }
+        }
+    }
+
+    services.push(Service {
+        name: "webhook-dispatcher",
+        database: "api",
+        env: [
+            database("api"),
+            vec![kafka, ("LEDGER_URL", ledger_url.clone())],
+        ]
+        .concat(),
+        health: Health::Running,
+    });
+    services.push(Service {
+        name: "pasys-api",
+        database: "api",
+        env: [
+            database("api"),
+            vec![
+                ("ACCOUNTS_URL", accounts_url),
+                ("LEDGER_URL", ledger_url),
+                port(args.api_port),
+            ],
+        ]
+        .concat(),
+        health: Health::Http(api_url),
+    });
+
+    Plan { services, skipped }
+}
+
+fn database_url<'a>(args: &'a UpArgs, database: &str) -> &'a str {
+    match database {
+        "accounts" => &args.accounts_database_url,
+        "ledger" => &args.ledger_database_url,
+        "fraud" => &args.fraud_database_url,
+        _ => &args.api_database_url,
+    }
+}
+
+/// Prefix puts the service name padded to the longest one in front of each log line, coloured
+/// when stdout is a terminal.
+#[derive(Debug, Clone, Copy)]
+pub struct Prefix {
+    width: usize,
+    colour: bool,
+}
+
+impl Prefix {
+    pub fn new(width: usize, colour: bool) -> Self {
+        Prefix { width, colour }
+    }
+
+    pub fn line(&self, name: &str, colour: u8, line: &str) -> String {
+        let name = format!("{name:<width$}", width = self.width);
+        match self.colour {
+            true => format!("\x1b[{colour}m{name} |\x1b[0m {line}"),
+            false => format!("{name} | {line}"),
+        }
+    }
+}
+
+/// format_pid_file writes one `name pid start_time` line per process.
+pub fn format_pid_file(processes: &[(String, u32, u64)]) -> String {
+    processes
+        .iter()
+        .map(|(name, pid, start_time)| format!("{name} {pid} {start_time}\n"))
+        .collect()
+}
+
+/// parse_pid_file reads the processes written by format_pid_file. Pids which would signal a
+/// process group, 0 and those above i32::MAX, and init are rejected.
+pub fn parse_pid_file(content: &str) -> anyhow::Result<Vec<(String, u32, u64)>> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (name, pid, start_time) = match line.split_whitespace().collect::<Vec<_>>()[..] {
+                [name, pid, start_time] => (name, pid, start_time),
+                _ => anyhow::bail!("invalid line {line}, expected `name pid start_time`"),
+            };
+            let pid = match pid.parse::<u32>() {
+                Ok(pid) if pid > 1 && pid <= i32::MAX as u32 => pid,
+                Ok(pid) => anyhow::bail!("invalid pid {pid} in line {line}"),
+                Err(e) => anyhow::bail!("invalid pid in line {line}: {e}"),
+            };
+            match start_time.parse::<u64>() {
+                Ok(start_time) => Ok((name.to_string(), pid, start_time)),
+                Err(e) => anyhow::bail!("invalid start time in line {line}: {e}"),
+            }
+        })
+        .collect()
+}
+
+/// parse_start_time reads the start time of a process, in clock ticks since boot, from its
+/// `/proc/<pid>/stat`. The name before it is in parentheses and may contain spaces.
+fn parse_start_time(stat: &str) -> Option<u64> {
+    let (_, fields) = stat.rsplit_once(')')?;
+    fields.split_whitespace().nth(19)?.parse().ok()
+}
+
+/// start_time returns when the process started, so a pid reused by another process is not
+/// mistaken for it. None when the process does not exist or `/proc` is not available.
+fn start_time(pid: u32) -> Option<u64> {
+    let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    parse_start_time(&stat)
+}
+
+/// up migrates the databases, starts the planned services one after the other once the
+/// previous one is healthy and streams their logs until interrupted or one of them exits.
+pub async fn up(cli: &Cli, args: &UpArgs) -> anyhow::Result<Table> {
+    let kafka_brokers = cli
+        .kafka_brokers
+        .as_deref()
+        .unwrap_or(DEFAULT_KAFKA_BROKERS);
+    let fraud_model = FRAUD_MODEL_ENV
+        .iter()
+        .any(|name| std::env::var_os(name).is_some());
+    let plan = plan(kafka_brokers, args, fraud_model);
+
+    let bin_dir = match &args.bin_dir {
+        Some(bin_dir) => bin_dir.clone(),
+        None => match std::env::current_exe()?.parent() {
+            Some(bin_dir) => bin_dir.to_path_buf(),
+            None => anyhow::bail!("failed to find the directory of pasys, pass --bin-dir"),
+        },
+    };
+    for service in &plan.services {
+        let binary = bin_dir.join(service.name);
+        if !binary.is_file() {
+            anyhow::bail!(
+                "{} not found, build the services with `cargo build --workspace` or pass --bin-dir",
+                binary.display()
+            );
+        }
+    }
+
+    let width = plan
+        .services
+        .iter()
+        .map(|service| service.name.len())
+        .max()
+        .unwrap_or_default();
+    let prefix = Prefix::new(
+        width,
+        std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none(),
+    );
+    let log = |line: String| println!("{}", prefix.line("pasys", PASYS_COLOUR, &line));
+
+    for (name, reason) in &plan.skipped {
+        log(format!("skipping {name}, {reason}"));
+    }
+
+    if !args.skip_migrations {
+        let mut migrated = vec![];
+        for service in &plan.services {
+            if migrated.contains(&service.database) {
+                continue;
+            }
+            migrate(
+                database_url(args, service.database),
+                &args.migrations_dir.join(service.database),
+            )
+            .await?;
+            log(format!("migrated the {} database", service.database));
+            migrated.push(service.database);
+        }
+    }
+
+    let mut stack = Stack::new(bin_dir, prefix, args.pid_file.clone());
+    let started = stack
+        .start_all(&plan, Duration::from_secs(args.health_timeout))
+        .await;
+    match started {
+        Ok(()) => {
+            log("all services are up, press ctrl-c to stop them".to_string());
+            tokio::select! {
+                _ = shutdown::shutdown_signal() => log("stopping".to_string()),
+                exit = stack.exits.recv() => {
+                    if let Some((name, status)) = exit {
+                        log(format!("{name} exited with {status}, stopping"));
+                        stack.exited.push((name, status));
+                    }
+                }
+            }
+        }
+        Err(e) => {
+            log(format!("{e:#}, stopping"));
+            stack.stop().await;
+            return Err(e);
+        }
+    }
+
+    Ok(stack.stop().await)
+}
+
+async fn migrate(url: &str, dir: &Path) -> anyhow::Result<()> {
+    match Postgres::database_exists(url).await {
+        Ok(true) => {}
+        Ok(false) => {
+            if let Err(e) = Postgres::create_database(url).await {
+                anyhow::bail!("failed to create database {url}: {e}");
+            }
+        }
+        Err(e) => anyhow::bail!("failed to connect to {url}: {e}"),
+    }
+
+    let migrator = match Migrator::new(dir).await {
+        Ok(migrator) => migrator,
+        Err(e) => anyhow::bail!("failed to read migrations from {}: {e}", dir.display()),
+    };
+    let pool = PgPool::connect(url).await?;
+    let migrated = migrator.run(&pool).await;
+    pool.close().await;
+
+    match migrated {
+        Ok(()) => Ok(()),
+        Err(e) => anyhow::bail!("failed to migrate {url}: {e}"),
+    }
+}
+
+type Exit = (&'static str, String);
+
+/// Stack is the processes started by `pasys up`, exits are reported on `exits` by the task
+/// waiting on each process.
+struct Stack {
+    bin_dir: PathBuf,
+    prefix: Prefix,
+    pid_file: PathBuf,
+    running: Vec<(&'static str, u32)>,
+    exited: Vec<Exit>,
+    exits: mpsc::UnboundedReceiver<Exit>,
+    exits_tx: mpsc::UnboundedSender<Exit>,
+}
+
+impl Stack {
+    fn new(bin_dir: PathBuf, prefix: Prefix, pid_file: PathBuf) -> Self {
+        let (exits_tx, exits) = mpsc::unbounded_channel();
+        Stack {
+            bin_dir,
+            prefix,
+            pid_file,
+            running: vec![],
+            exited: vec![],
+            exits,
+            exits_tx,
+        }
+    }
+
+    async fn start_all(&mut self, plan: &Plan, health_timeout: Duration) -> anyhow::Result<()> {
+        for (index, service) in plan.services.iter().enumerate() {
+            self.start(service, COLOURS[index % COLOURS.len()])?;
+            self.write_pid_file()?;
+            self.wait_healthy(service, health_timeout).await?;
+            println!(
+                "{}",
+                self.prefix.line(
+                    "pasys",
+                    PASYS_COLOUR,
+                    &match &service.health {
+                        Health::Running => format!("{} is running", service.name),
+                        Health::Accounts(url)
+                        | Health::Ledger(url)
+                        | Health::FraudReviews(url)
+                        | Health::Reconciliation(url)
+                        | Health::Http(url) => format!("{} is healthy at {url}", service.name),
+                    }
+                )
+            );
+        }
+
+        Ok(())
+    }
+
+    fn start(&mut self, service: &Service, colour: u8) -> anyhow::Result<()> {
+        let mut child = match Command::new(self.bin_dir.join(service.name))
+            .env_remove("PORT")
+            .env_remove("KAFKA_GROUP_ID")
+            .envs(service.env.iter().cloned())
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+        {
+            Ok(child) => child,
+            Err(e) => anyhow::bail!("failed to start {}: {e}", service.name),
+        };
+
+        if let Some(stdout) = child.stdout.take() {
+            forward(stdout, service.name, colour, self.prefix);
+        }
+        if let Some(stderr) = child.stderr.take() {
+            forward(stderr, service.name, colour, self.prefix);
+        }
+        if let Some(pid) = child.id() {
+            self.running.push((service.name, pid));
+        }
+
+        let name = service.name;
+        let exits = self.exits_tx.clone();
+        tokio::spawn(async move {
+            let status = match child.wait().await {
+                Ok(status) => status.to_string(),
+                Err(e) => format!("unknown status: {e}"),
+            };
+            let _ = exits.send((name, status));
+        });
+
+        Ok(())
+    }
+
+    async fn wait_healthy(&mut self, service: &Service, timeout: Duration) -> anyhow::Result<()> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if probe(&service.health).await {
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                anyhow::bail!(
+                    "{} did not become healthy within {}s",
+                    service.name,
+                    timeout.as_secs()
+                );
+            }
+
+            tokio::select! {
+                exit = self.exits.recv() => {
+                    if let Some((name, status)) = exit {
+                        self.exited.push((name, status.clone()));
+                        anyhow::bail!("{name} exited with {status} while starting {}", service.name);
+                    }
+                }
+                _ = tokio::time::sleep(HEALTH_PROBE_INTERVAL) => {}
+            }
+        }
+    }
+
+    fn write_pid_file(&self) -> anyhow::Result<()> {
+        let processes: Vec<(String, u32, u64)> = std::iter::once(("pasys", std::process::id()))
+            .chain(self.running.iter().copied())
+            .map(|(name, pid)| (name.to_string(), pid, start_time(pid).unwrap_or_default()))
+            .collect();
+
+        match std::fs::write(&self.pid_file, format_pid_file(&processes)) {
+            Ok(()) => Ok(()),
+            Err(e) => anyhow::bail!("failed to write {}: {e}", self.pid_file.display()),
+        }
+    }
+
+    /// stop terminates the running processes in the reverse order they were started, killing
+    /// the ones still running after STOP_TIMEOUT.
+    async fn stop(&mut self) -> Table {
+        let is_running = |exited: &[Exit], name: &str| exited.iter().all(|(n, _)| *n != name);
+
+        for (name, pid) in self.running.iter().rev() {
+            if is_running(&self.exited, name) {
+                signal(*pid, libc::SIGTERM);
+            }
+        }
+
+        let deadline = Instant::now() + STOP_TIMEOUT;
+        while self.exited.len() < self.running.len() {
+            match tokio::time::timeout_at(deadline, self.exits.recv()).await {
+                Ok(Some(exit)) => self.exited.push(exit),
+                Ok(None) => break,
+                Err(_) => {
+                    for (name, pid) in &self.running {
+                        if is_running(&self.exited, name) {
+                            signal(*pid, libc::SIGKILL);
+                        }
+                    }
+                    while self.exited.len() < self.running.len() {
+                        match self.exits.recv().await {
+                            Some(exit) => self.exited.push(exit),
+                            None => break,
+                        }
+                    }
+                }
+            }
+        }
+        let _ = std::fs::remove_file(&self.pid_file);
+
+        let mut table = Table::new(vec!["service", "pid", "status"]);
+        for (name, pid) in &self.running {
+            let status = self
+                .exited
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, status)| status.clone())
+                .unwrap_or_default();
+            table.push(vec![name.to_string(), pid.to_string(), status]);
+        }
+
+        table
+    }
+}
+
+/// forward prints each line the process writes with the service's prefix.
+fn forward<R>(reader: R, name: &'static str, colour: u8, prefix: Prefix)
+where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(reader).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            println!("{}", prefix.line(name, colour, &line));
+        }
+    });
+}
+
+async fn probe(health: &Health) -> bool {
+    let probe = async {
+        match health {
+            Health::Accounts(url) => match channel(url).await {
+                Some(channel) => AccountsClient::new(channel).health_check(()).await.is_ok(),
+                None => false,
+            },
+            Health::Ledger(url) => match channel(url).await {
+                Some(channel) => LedgerClient::new(channel).health_check(()).await.is_ok(),
+                None => false,
+            },
+            Health::FraudReviews(url) => match channel(url).await {
+                Some(channel) => FraudReviewsClient::new(channel)
+                    .health_check(())
+                    .await
+                    .is_ok(),
+                None => false,
+            },
+            Health::Reconciliation(url) => match channel(url).await {
+                Some(channel) => ReconciliationExceptionsClient::new(channel)
+                    .health_check(())
+                    .await
+                    .is_ok(),
+                None => false,
+            },
+            Health::Http(url) => match reqwest::get(format!("{url}/health")).await {
+                Ok(response) => response.status().is_success(),
+                Err(_) => false,
+            },
+            Health::Running => true,
+        }
+    };
+
+    tokio::time::timeout(HEALTH_PROBE_TIMEOUT, probe)
+        .await
+        .unwrap_or(false)
+}
+
+async fn channel(url: &str) -> Option<Channel> {
+    Channel::from_shared(url.to_string())
+        .ok()?
+        .connect()
+        .await
+        .ok()
+}
+
+/// signal sends `signal` to the process, returning whether it exists.
+fn signal(pid: u32, signal: i32) -> bool {
+    // SAFETY: kill has no memory safety requirements, a stale pid only fails with ESRCH.
+    unsafe { libc::kill(pid as libc::pid_t, signal) == 0 }
+}
+
+/// down stops the processes `pasys up` wrote to the pid file, the services before pasys up
+/// itself, killing the ones still running after the timeout. Processes are only signalled while
+/// they have the start time recorded in the pid file, a stale pid file does not stop processes
+/// which reused the pids.
+pub async fn down(args: &DownArgs) -> anyhow::Result<Table> {
+    let content = match std::fs::read_to_string(&args.pid_file) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            anyhow::bail!(
+                "{} not found, is pasys up running?",
+                args.pid_file.display()
+            )
+        }
+        Err(e) => anyhow::bail!("failed to read {}: {e}", args.pid_file.display()),
+    };
+    let processes = parse_pid_file(&content)?;
+    let started = |pid: u32, recorded: u64| start_time(pid) == Some(recorded);
+
+    let mut statuses = vec![];
+    for (_, pid, recorded) in processes.iter().rev() {
+        statuses.push(
+            match started(*pid, *recorded) && signal(*pid, libc::SIGTERM) {
+                true => "stopped",
+                false => "not running",
+            },
+        );
+    }
+    statuses.reverse();
+
+    let deadline = Instant::now() + Duration::from_secs(args.timeout);
+    while processes
+        .iter()
+        .any(|(_, pid, recorded)| started(*pid, *recorded))
+        && Instant::now() < deadline
+    {
+        tokio::time::sleep(HEALTH_PROBE_INTERVAL).await;
+    }
+    for ((_, pid, recorded), status) in processes.iter().zip(statuses.iter_mut()) {
+        if started(*pid, *recorded) && signal(*pid, libc::SIGKILL) {
+            *status = "killed";
+        }
+    }
+    let _ = std::fs::remove_file(&args.pid_file);
+
+    let mut table = Table::new(vec!["service", "pid", "status"]);
+    for ((name, pid, _), status) in processes.iter().zip(statuses) {
+        table.push(vec![name.clone(), pid.to_string(), status.to_string()]);
+    }
+
+    Ok(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Command;
+    use clap::Parser;
+
+    fn up_args(extra: &[&str]) -> UpArgs {
+        let args = [&["pasys", "up"], extra].concat();
+        match Cli::try_parse_from(args).unwrap().command {
+            Command::Up(args) => args,
+            command => panic!("unexpected command {command:?}"),
+        }
+    }
+
+    fn names(plan: &Plan) -> Vec<&'static str> {
+        plan.services.iter().map(|service| service.name).collect()
+    }
+
+    #[test]
+    fn successfully_plan_services_without_optional_config() {
+        // arrange
+        let args = up_args(&["--base-port", "9000", "--api-port", "9900"]);
+        let args = UpArgs {
+            psp_url: None,
+            ..args
+        };
+
+        // act
+        let plan = plan("kafka:9092", &args, false);
+
+        // assert
+        assert_eq!(
+            names(&plan),
+            vec![
+                "accounts",
+                "ledger",
+                "ledger-consumer",
+                "webhook-dispatcher",
+                "pasys-api"
+            ]
+        );
+        assert_eq!(
+            plan.skipped
+                .iter()
+                .map(|(name, _)| *name)
+                .collect::<Vec<_>>(),
+            vec!["fraud-detector", "refund-processor", "reconciliation"]
+        );
+
+        let api = plan.services.last().unwrap();
+        assert_eq!(
+            api.health,
+            Health::Http("http://localhost:9900".to_string())
+        );
+        assert!(
+            api.env
+                .contains(&("ACCOUNTS_URL", "http://localhost:9000".to_string()))
+        );
+        assert!(
+            api.env
+                .contains(&("LEDGER_URL", "http://localhost:9001".to_string()))
+        );
+        assert!(api.env.contains(&("PORT", "9900".to_string())));
+        assert!(
+            api.env
+                .contains(&("WRITER_DATABASE_URL", args.api_database_url.clone()))
+        );
+    }
+
+    #[test]
+    fn successfully_plan_all_services() {
+        // arrange
+        let args = up_args(&["--psp-url", "http://psp"]);
+
+        // act
+        let plan = plan("kafka:9092", &args, true);
+
+        // assert
+        assert_eq!(
+            names(&plan),
+            vec![
+                "accounts",
+                "ledger",
+                "ledger-consumer",
+                "fraud-detector",
+                "refund-processor",
+                "reconciliation",
+                "webhook-dispatcher",
+                "pasys-api"
+            ]
+        );
+        assert!(plan.skipped.is_empty());
+
+        let reconciliation = &plan.services[5];
+        assert_eq!(reconciliation.database, "ledger");
+        assert_eq!(
+            reconciliation.health,
+            Health::Reconciliation("http://localhost:8003".to_string())
+        );
+        assert!(
+            reconciliation
+                .env
+                .contains(&("PSP_URL", "http://psp".to_string()))
+        );
+        assert!(
+            reconciliation
+                .env
+                .contains(&("KAFKA_BROKERS", "kafka:9092".to_string()))
+        );
+    }
+
+    #[test]
+    fn successfully_prefix_lines() {
+        // arrange
+        let plain = Prefix::new(8, false);
+        let coloured = Prefix::new(8, true);
+
+        // act & assert
+        assert_eq!(plain.line("ledger", 36, "started"), "ledger   | started");
+        assert_eq!(
+            coloured.line("ledger", 36, "started"),
+            "\x1b[36mledger   |\x1b[0m started"
+        );
+    }
+
+    #[test]
+    fn successfully_round_trip_pid_file() {
+        // arrange
+        let processes = vec![
+            ("pasys".to_string(), 10, 4200),
+            ("accounts".to_string(), 11, 4210),
+        ];
+
+        // act
+        let content = format_pid_file(&processes);
+
+        // assert
+        assert_eq!(content, "pasys 10 4200\naccounts 11 4210\n");
+        assert_eq!(parse_pid_file(&content).unwrap(), processes);
+    }
+
+    #[test]
+    fn test_parse_pid_file() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            content: &'static str,
+            expected: Option<Vec<(String, u32, u64)>>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "successfully parse processes",
+                content: "pasys 10 4200\n\naccounts 11 4210\n",
+                expected: Some(vec![
+                    ("pasys".to_string(), 10, 4200),
+                    ("accounts".to_string(), 11, 4210),
+                ]),
+            },
+            TestCase {
+                name: "error when pid is not a number",
+                content: "accounts eleven 4210",
+                expected: None,
+            },
+            TestCase {
+                name: "error when start time is missing",
+                content: "accounts 11",
+                expected: None,
+            },
+            TestCase {
+                name: "error when pid is 0, which signals the process group",
+                content: "accounts 0 4210",
+                expected: None,
+            },
+            TestCase {
+                name: "error when pid is init",
+                content: "accounts 1 4210",
+                expected: None,
+            },
+            TestCase {
+                name: "error when pid would be negative",
+                content: "accounts 4294967295 4210",
+                expected: None,
+            },
+        ];
+
+        for test_case in test_cases {
+            let result = parse_pid_file(test_case.content);
+            assert_eq!(result.ok(), test_case.expected, "{}", test_case.name);
+        }
+    }
+
+    #[test]
+    fn successfully_parse_start_time_of_process_with_spaces_in_its_name() {
+        // arrange
+        let stat = "4242 (ledger (v2) x) S 1 4242 4242 0 -1 4194560 100 0 0 0 1 2 0 0 20 0 4 0 987654 1000 10";
+
+        // act
+        let start_time = parse_start_time(stat);
+
+        // assert
+        assert_eq!(start_time, Some(987654));
+        assert_eq!(parse_start_time("4242 (ledger"), None);
+    }
+
+    #[tokio::test]
+    async fn successfully_skip_processes_which_reused_the_pid() {
+        // arrange - this process is alive but did not start at the recorded time
+        let pid = std::process::id();
+        let pid_file =
+            std::env::temp_dir().join(format!("pasys-down-{}.pid", uuid::Uuid::new_v4()));
+        let recorded = start_time(pid).unwrap() + 1;
+        std::fs::write(
+            &pid_file,
+            format_pid_file(&[("accounts".to_string(), pid, recorded)]),
+        )
+        .unwrap();
+
+        // act
+        let table = down(&DownArgs {
+            pid_file: pid_file.clone(),
+            timeout: 1,
+        })
+        .await
+        .unwrap();
+
+        // assert
+        assert_eq!(
+            table.rows,
+            vec![vec![
+                "accounts".to_string(),
+                pid.to_string(),
+                "not running".to_string()
+            ]]
+        );
+        assert!(!pid_file.exists());
+    }
+}
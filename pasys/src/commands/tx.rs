@@ -0,0 +1,146 @@
+use crate::cli::{Cli, TxCommand, TxStatus};
+use crate::commands::{
+    connect, enum_name, format_amount, format_timestamp, parse_amount, status_error,
+};
+use crate::output::Table;
+use ledger_proto::ledger_v1;
+use ledger_proto::ledger_v1::ledger_client::LedgerClient;
+
+pub async fn run(cli: &Cli, command: &TxCommand) -> anyhow::Result<Table> {
+    let mut client = LedgerClient::new(connect(cli.ledger_url.as_deref(), "LEDGER_URL").await?);
+
+    let transactions = match command {
+        TxCommand::Create(args) => {
+            let amount = parse_amount(args.amount.as_str(), args.currency.as_str())?;
+            let request = ledger_v1::CreateTransactionRequest {
+                id: uuid::Uuid::new_v4().to_string(),
+                debit_account_id: args.from.clone(),
+                credit_account_id: args.to.clone(),
+                amount: Some(amount),
+                request_timestamp: Some(prost_types::Timestamp::from(std::time::SystemTime::now())),
+                idempotency_key: args
+                    .idempotency_key
+                    .clone()
+                    .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+                credit_currency: args
+                    .credit_currency
+                    .as_deref()
+                    .unwrap_or_default()
+                    .to_uppercase(),
+            };
+            let transaction_id = match client.create_transaction(request).await {
+                Ok(response) => response.into_inner().transaction_id,
+                Err(e) => return Err(status_error("create transaction", e)),
+            };
+            get_transaction(&mut client, transaction_id).await?
+        }
+        TxCommand::Get { transaction_id } => {
+            get_transaction(&mut client, transaction_id.clone()).await?
+        }
+        TxCommand::List(args) => {
+            let request = ledger_v1::ListTransactionsRequest {
+                account_id: args.account.clone().unwrap_or_default(),
+                status: args
+                    .status
+                    .map(|status| parse_transaction_status(status) as i32)
+                    .unwrap_or_default(),
+                limit: args.limit,
+            };
+            match client.list_transactions(request).await {
+                Ok(response) => response.into_inner().transactions,
+                Err(e) => return Err(status_error("list transactions", e)),
+            }
+        }
+    };
+
+    Ok(transactions_table(transactions))
+}
+
+async fn get_transaction(
+    client: &mut LedgerClient<tonic::transport::Channel>,
+    transaction_id: String,
+) -> anyhow::Result<Vec<ledger_v1::Transaction>> {
+    let request = ledger_v1::GetTransactionRequest { transaction_id };
+
+    match client.get_transaction(request).await {
+        Ok(response) => Ok(response.into_inner().transaction.into_iter().collect()),
+        Err(e) => Err(status_error("get transaction", e)),
+    }
+}
+
+fn parse_transaction_status(status: TxStatus) -> ledger_v1::TransactionStatus {
+    match status {
+        TxStatus::Init => ledger_v1::TransactionStatus::Init,
+        TxStatus::Pending => ledger_v1::TransactionStatus::Pending,
+        TxStatus::Success => ledger_v1::TransactionStatus::Success,
+        TxStatus::Failed => ledger_v1::TransactionStatus::Failed,
+        TxStatus::Fraud => ledger_v1::TransactionStatus::Fraud,
+        TxStatus::Refund => ledger_v1::TransactionStatus::Refund,
+        TxStatus::Refunded => ledger_v1::TransactionStatus::Refunded,
+    }
+}
+
+pub fn transactions_table(transactions: Vec<ledger_v1::Transaction>) -> Table {
+    let mut table = Table::new(vec![
+        "id",
+        "debit_account_id",
+        "credit_account_id",
+        "amount",
+        "currency",
+        "status",
+        "idempotency_key",
+        "created_at",
+    ]);
+    for transaction in transactions {
+        let (amount, currency) = match &transaction.amount {
+            Some(money) => (format_amount(money), money.currency_code.clone()),
+            None => (String::new(), String::new()),
+        };
+        table.push(vec![
+            transaction.id.clone(),
+            transaction.debit_account_id.clone(),
+            transaction.credit_account_id.clone(),
+            amount,
+            currency,
+            enum_name(transaction.status().as_str_name(), "TRANSACTION_STATUS_"),
+            transaction.idempotency_key.clone(),
+            format_timestamp(transaction.created_at.as_ref()),
+        ]);
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn successfully_build_transactions_table() {
+        // arrange
+        let transaction = ledger_v1::Transaction {
+            id: "tx".to_string(),
+            debit_account_id: "debit".to_string(),
+            credit_account_id: "credit".to_string(),
+            amount: Some(ledger_v1::google::r#type::Money {
+                currency_code: "EUR".to_string(),
+                units: 12,
+                nanos: 340_000_000,
+            }),
+            status: ledger_v1::TransactionStatus::Refunded as i32,
+            idempotency_key: "key".to_string(),
+            ..ledger_v1::Transaction::default()
+        };
+
+        // act
+        let table = transactions_table(vec![transaction]);
+
+        // assert
+        assert_eq!(
+            table.rows[0],
+            vec![
+                "tx", "debit", "credit", "12.34", "EUR", "refunded", "key", ""
+            ]
+        );
+    }
+}
@@ -1,3 +1,16 @@
-fn main() {
-    println!("Hello, PaySys");
+use clap::Parser;
+use pasys::cli::Cli;
+use pasys::commands;
+use pasys::output;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let tables = commands::run(&cli).await?;
+    if !tables.is_empty() {
+        println!("{}", output::render_all(&tables, cli.output)?);
+    }
+
+    Ok(())
 }
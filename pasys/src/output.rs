@@ -0,0 +1,224 @@
+use clap::ValueEnum;
+
+/// Format is how a command result is printed. Json and csv are meant for scripts.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Format {
+    Table,
+    Json,
+    Csv,
+}
+
+/// Table is a command result with one row per record. Empty cells are printed as null in json.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table {
+    pub title: Option<&'static str>,
+    pub headers: Vec<&'static str>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// render_all prints the results of a command. A single table is printed on its own, several
+/// are printed one after the other under their titles, or as one json object keyed by title.
+pub fn render_all(tables: &[Table], format: Format) -> anyhow::Result<String> {
+    if let [table] = tables {
+        return table.render(format);
+    }
+
+    match format {
+        Format::Json => {
+            let object = tables
+                .iter()
+                .enumerate()
+                .map(|(index, table)| {
+                    let title = table
+                        .title
+                        .map(str::to_string)
+                        .unwrap_or_else(|| index.to_string());
+                    (title, serde_json::Value::Array(table.json_records()))
+                })
+                .collect();
+            match serde_json::to_string_pretty(&serde_json::Value::Object(object)) {
+                Ok(json) => Ok(json),
+                Err(e) => anyhow::bail!("failed to encode json: {e}"),
+            }
+        }
+        Format::Table | Format::Csv => {
+            let mut rendered = vec![];
+            for table in tables {
+                let body = table.render(format)?;
+                rendered.push(match table.title {
+                    Some(title) => format!("{title}\n{body}"),
+                    None => body,
+                });
+            }
+            Ok(rendered.join("\n\n"))
+        }
+    }
+}
+
+impl Table {
+    pub fn new(headers: Vec<&'static str>) -> Self {
+        Table {
+            title: None,
+            headers,
+            rows: vec![],
+        }
+    }
+
+    /// with_title names the table when a command prints several.
+    pub fn with_title(mut self, title: &'static str) -> Self {
+        self.title = Some(title);
+        self
+    }
+
+    pub fn push(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+
+    pub fn render(&self, format: Format) -> anyhow::Result<String> {
+        match format {
+            Format::Table => Ok(self.render_table()),
+            Format::Json => self.render_json(),
+            Format::Csv => self.render_csv(),
+        }
+    }
+
+    fn render_table(&self) -> String {
+        let mut table = comfy_table::Table::new();
+        table
+            .load_preset(comfy_table::presets::UTF8_FULL_CONDENSED)
+            .set_content_arrangement(comfy_table::ContentArrangement::Dynamic)
+            .set_header(&self.headers);
+        for row in &self.rows {
+            table.add_row(row);
+        }
+
+        table.to_string()
+    }
+
+    fn render_json(&self) -> anyhow::Result<String> {
+        match serde_json::to_string_pretty(&self.json_records()) {
+            Ok(json) => Ok(json),
+            Err(e) => anyhow::bail!("failed to encode json: {e}"),
+        }
+    }
+
+    fn json_records(&self) -> Vec<serde_json::Value> {
+        self.rows
+            .iter()
+            .map(|row| {
+                let record = self
+                    .headers
+                    .iter()
+                    .zip(row)
+                    .map(|(header, cell)| {
+                        let value = match cell.is_empty() {
+                            true => serde_json::Value::Null,
+                            false => serde_json::Value::String(cell.clone()),
+                        };
+                        (header.to_string(), value)
+                    })
+                    .collect();
+                serde_json::Value::Object(record)
+            })
+            .collect()
+    }
+
+    fn render_csv(&self) -> anyhow::Result<String> {
+        let mut writer = csv::Writer::from_writer(vec![]);
+        writer.write_record(&self.headers)?;
+        for row in &self.rows {
+            writer.write_record(row)?;
+        }
+
+        match writer.into_inner() {
+            Ok(bytes) => Ok(String::from_utf8(bytes)?.trim_end().to_string()),
+            Err(e) => anyhow::bail!("failed to encode csv: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> Table {
+        let mut table = Table::new(vec!["id", "name", "closed_at"]);
+        table.push(vec![
+            "1".to_string(),
+            "Acme, Inc".to_string(),
+            String::new(),
+        ]);
+        table.push(vec![
+            "2".to_string(),
+            "Globex".to_string(),
+            "2025-10-30T09:00:00Z".to_string(),
+        ]);
+        table
+    }
+
+    #[test]
+    fn successfully_render_csv_quoting_cells_with_commas() {
+        // act
+        let rendered = table().render(Format::Csv).unwrap();
+
+        // assert
+        assert_eq!(
+            rendered,
+            "id,name,closed_at\n1,\"Acme, Inc\",\n2,Globex,2025-10-30T09:00:00Z"
+        );
+    }
+
+    #[test]
+    fn successfully_render_json_objects_keyed_by_header() {
+        // act
+        let rendered = table().render(Format::Json).unwrap();
+
+        // assert
+        let records: serde_json::Value = serde_json::from_str(rendered.as_str()).unwrap();
+        assert_eq!(
+            records,
+            serde_json::json!([
+                {"id": "1", "name": "Acme, Inc", "closed_at": null},
+                {"id": "2", "name": "Globex", "closed_at": "2025-10-30T09:00:00Z"},
+            ])
+        );
+    }
+
+    #[test]
+    fn successfully_render_table_with_every_cell() {
+        // act
+        let rendered = table().render(Format::Table).unwrap();
+
+        // assert
+        for cell in ["id", "name", "closed_at", "Acme, Inc", "Globex"] {
+            assert!(rendered.contains(cell), "{cell} missing from\n{rendered}");
+        }
+    }
+
+    #[test]
+    fn successfully_render_empty_json_as_empty_array() {
+        let rendered = Table::new(vec!["id"]).render(Format::Json).unwrap();
+        assert_eq!(rendered, "[]");
+    }
+
+    #[test]
+    fn successfully_render_several_tables_under_their_titles() {
+        // arrange
+        let mut summary = Table::new(vec!["metric", "value"]).with_title("summary");
+        summary.push(vec!["transactions".to_string(), "10".to_string()]);
+        let tables = vec![summary, table().with_title("accounts")];
+
+        // act
+        let csv = render_all(&tables, Format::Csv).unwrap();
+        let json = render_all(&tables, Format::Json).unwrap();
+
+        // assert
+        assert!(csv.starts_with("summary\nmetric,value\ntransactions,10\n\naccounts\nid,"));
+        let json: serde_json::Value = serde_json::from_str(json.as_str()).unwrap();
+        assert_eq!(
+            json["summary"],
+            serde_json::json!([{"metric": "transactions", "value": "10"}])
+        );
+        assert_eq!(json["accounts"].as_array().unwrap().len(), 2);
+    }
+}
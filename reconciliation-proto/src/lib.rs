@@ -0,0 +1,6 @@
+pub mod reconciliation_v1 {
+    tonic::include_proto!("reconciliation_v1");
+
+    pub const FILE_DESCRIPTOR_SET: &[u8] =
+        tonic::include_file_descriptor_set!("reconciliation_v1_descriptor");
+}
@@ -0,0 +1,397 @@
+mod parsers;
+
+use crate::DEFAULT_RUN_WINDOW_SECONDS;
+use crate::api::parsers::{
+    parse_exception_to_proto, parse_run_to_proto, parse_to_domain_exception_status,
+    parse_to_domain_timestamp,
+};
+use crate::domain::exception::ExceptionError;
+use crate::psp::PaymentServiceProvider;
+use crate::repo::ReconciliationRepository;
+use crate::service::ReconciliationService;
+
+use common::kafka::Publisher;
+use ledger::repo::LedgerReader;
+use reconciliation_proto::reconciliation_v1;
+
+use async_trait::async_trait;
+
+fn parse_exception_id(exception_id: &str) -> Result<uuid::Uuid, tonic::Status> {
+    uuid::Uuid::parse_str(exception_id).map_err(|e| {
+        tonic::Status::invalid_argument(format!("invalid exception_id {exception_id}: {e}"))
+    })
+}
+
+/// parse_update_request checks the request and returns the exception id. Closing an exception
+/// needs notes saying why, investigating one does not.
+fn parse_update_request(
+    request: &reconciliation_v1::UpdateExceptionRequest,
+    notes_required: bool,
+) -> Result<uuid::Uuid, tonic::Status> {
+    let id = parse_exception_id(request.exception_id.as_str())?;
+    if request.author.is_empty() {
+        return Err(tonic::Status::invalid_argument("author must be set"));
+    }
+    if notes_required && request.notes.trim().is_empty() {
+        return Err(tonic::Status::invalid_argument("notes must be set"));
+    }
+
+    Ok(id)
+}
+
+/// RunWindow is the window a run reconciles, the start is None when the run should continue
+/// from the last completed run.
+type RunWindow = (
+    Option<chrono::DateTime<chrono::Utc>>,
+    chrono::DateTime<chrono::Utc>,
+);
+
+/// parse_run_request returns the window to reconcile, ending now unless the request says
+/// otherwise.
+fn parse_run_request(
+    request: reconciliation_v1::RunReconciliationRequest,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<RunWindow, tonic::Status> {
+    let to = match request.window_end {
+        Some(window_end) => parse_to_domain_timestamp("window_end", window_end)
+            .map_err(|e| tonic::Status::invalid_argument(e.to_string()))?,
+        None => now,
+    };
+    let from = match request.window_start {
+        Some(window_start) => Some(
+            parse_to_domain_timestamp("window_start", window_start)
+                .map_err(|e| tonic::Status::invalid_argument(e.to_string()))?,
+        ),
+        None => None,
+    };
+    if from.is_some_and(|from| from >= to) {
+        return Err(tonic::Status::invalid_argument(
+            "window_start must be before window_end",
+        ));
+    }
+
+    Ok((from, to))
+}
+
+/// parse_error_to_status maps exception errors to their grpc codes, anything else is internal.
+fn parse_error_to_status(e: anyhow::Error, message: &str) -> tonic::Status {
+    match e.downcast_ref::<ExceptionError>() {
+        Some(ExceptionError::NotFound(_)) => tonic::Status::not_found(e.to_string()),
+        Some(ExceptionError::InvalidTransition(_, _, _)) => {
+            tonic::Status::failed_precondition(e.to_string())
+        }
+        None => tonic::Status::internal(format!("{message}: {e}")),
+    }
+}
+
+#[async_trait]
+impl<L, R, P, B> reconciliation_v1::reconciliation_exceptions_server::ReconciliationExceptions
+    for ReconciliationService<L, R, P, B>
+where
+    L: LedgerReader,
+    R: ReconciliationRepository,
+    P: PaymentServiceProvider,
+    B: Publisher,
+{
+    async fn health_check(
+        &self,
+        _request: tonic::Request<()>,
+    ) -> Result<tonic::Response<()>, tonic::Status> {
+        Ok(tonic::Response::new(()))
+    }
+
+    async fn list_exceptions(
+        &self,
+        request: tonic::Request<reconciliation_v1::ListExceptionsRequest>,
+    ) -> Result<tonic::Response<reconciliation_v1::ListExceptionsResponse>, tonic::Status> {
+        let request = request.into_inner();
+
+        let status = match parse_to_domain_exception_status(request.status) {
+            Ok(status) => status,
+            Err(e) => return Err(tonic::Status::invalid_argument(e.to_string())),
+        };
+        let source = (!request.source.is_empty()).then_some(request.source.as_str());
+
+        let exceptions = match ReconciliationService::list_exceptions(self, status, source).await {
+            Ok(exceptions) => exceptions
+                .into_iter()
+                .map(|exception| parse_exception_to_proto(exception, vec![]))
+                .collect(),
+            Err(e) => return Err(parse_error_to_status(e, "failed to list exceptions")),
+        };
+
+        Ok(tonic::Response::new(
+            reconciliation_v1::ListExceptionsResponse { exceptions },
+        ))
+    }
+
+    async fn get_exception(
+        &self,
+        request: tonic::Request<reconciliation_v1::GetExceptionRequest>,
+    ) -> Result<tonic::Response<reconciliation_v1::GetExceptionResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let id = parse_exception_id(request.exception_id.as_str())?;
+
+        let exception = match ReconciliationService::get_exception(self, id).await {
+            Ok((exception, notes)) => parse_exception_to_proto(exception, notes),
+            Err(e) => return Err(parse_error_to_status(e, "failed to get exception")),
+        };
+
+        Ok(tonic::Response::new(
+            reconciliation_v1::GetExceptionResponse {
+                exception: Some(exception),
+            },
+        ))
+    }
+
+    async fn investigate_exception(
+        &self,
+        request: tonic::Request<reconciliation_v1::UpdateExceptionRequest>,
+    ) -> Result<tonic::Response<reconciliation_v1::UpdateExceptionResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let id = parse_update_request(&request, false)?;
+
+        let exception = match ReconciliationService::investigate_exception(
+            self,
+            id,
+            request.author.as_str(),
+            request.notes.as_str(),
+        )
+        .await
+        {
+            Ok((exception, notes)) => parse_exception_to_proto(exception, notes),
+            Err(e) => return Err(parse_error_to_status(e, "failed to investigate exception")),
+        };
+
+        Ok(tonic::Response::new(
+            reconciliation_v1::UpdateExceptionResponse {
+                exception: Some(exception),
+            },
+        ))
+    }
+
+    async fn resolve_exception(
+        &self,
+        request: tonic::Request<reconciliation_v1::UpdateExceptionRequest>,
+    ) -> Result<tonic::Response<reconciliation_v1::UpdateExceptionResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let id = parse_update_request(&request, true)?;
+
+        let exception = match ReconciliationService::resolve_exception(
+            self,
+            id,
+            request.author.as_str(),
+            request.notes.as_str(),
+        )
+        .await
+        {
+            Ok((exception, notes)) => parse_exception_to_proto(exception, notes),
+            Err(e) => return Err(parse_error_to_status(e, "failed to resolve exception")),
+        };
+
+        Ok(tonic::Response::new(
+            reconciliation_v1::UpdateExceptionResponse {
+                exception: Some(exception),
+            },
+        ))
+    }
+
+    async fn write_off_exception(
+        &self,
+        request: tonic::Request<reconciliation_v1::UpdateExceptionRequest>,
+    ) -> Result<tonic::Response<reconciliation_v1::UpdateExceptionResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let id = parse_update_request(&request, true)?;
+
+        let exception = match ReconciliationService::write_off_exception(
+            self,
+            id,
+            request.author.as_str(),
+            request.notes.as_str(),
+        )
+        .await
+        {
+            Ok((exception, notes)) => parse_exception_to_proto(exception, notes),
+            Err(e) => return Err(parse_error_to_status(e, "failed to write off exception")),
+        };
+
+        Ok(tonic::Response::new(
+            reconciliation_v1::UpdateExceptionResponse {
+                exception: Some(exception),
+            },
+        ))
+    }
+
+    async fn run_reconciliation(
+        &self,
+        request: tonic::Request<reconciliation_v1::RunReconciliationRequest>,
+    ) -> Result<tonic::Response<reconciliation_v1::RunReconciliationResponse>, tonic::Status> {
+        let (from, to) = parse_run_request(request.into_inner(), chrono::Utc::now())?;
+
+        let run = match from {
+            Some(from) => ReconciliationService::run(self, from, to).await.map(Some),
+            None => {
+                let window = chrono::Duration::seconds(DEFAULT_RUN_WINDOW_SECONDS as i64);
+                ReconciliationService::run_next(self, to, window).await
+            }
+        };
+        let run = match run {
+            Ok(run) => run.map(parse_run_to_proto),
+            Err(e) => return Err(parse_error_to_status(e, "failed to run reconciliation")),
+        };
+
+        Ok(tonic::Response::new(
+            reconciliation_v1::RunReconciliationResponse { run },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::exception::Status;
+
+    #[test]
+    fn test_parse_error_to_status() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            error: anyhow::Error,
+            expected: tonic::Code,
+        }
+
+        let id = uuid::Uuid::new_v4();
+        let test_cases = vec![
+            TestCase {
+                name: "not found when exception does not exist",
+                error: ExceptionError::NotFound(id).into(),
+                expected: tonic::Code::NotFound,
+            },
+            TestCase {
+                name: "failed precondition when exception is already closed",
+                error: ExceptionError::InvalidTransition(id, Status::Resolved, Status::WrittenOff)
+                    .into(),
+                expected: tonic::Code::FailedPrecondition,
+            },
+            TestCase {
+                name: "internal for any other error",
+                error: anyhow::anyhow!("connection reset"),
+                expected: tonic::Code::Internal,
+            },
+        ];
+
+        for test_case in test_cases {
+            let status = parse_error_to_status(test_case.error, "failed");
+            assert_eq!(status.code(), test_case.expected, "{}", test_case.name);
+        }
+    }
+
+    #[test]
+    fn test_parse_run_request() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            request: reconciliation_v1::RunReconciliationRequest,
+            expected: Result<RunWindow, tonic::Code>,
+        }
+
+        let now = chrono::DateTime::from_timestamp(1_760_000_000, 0).unwrap();
+        let hour_ago = now - chrono::Duration::hours(1);
+        let timestamp = |at: chrono::DateTime<chrono::Utc>| prost_types::Timestamp {
+            seconds: at.timestamp(),
+            nanos: 0,
+        };
+        let test_cases = vec![
+            TestCase {
+                name: "continue up to now when no window is given",
+                request: reconciliation_v1::RunReconciliationRequest::default(),
+                expected: Ok((None, now)),
+            },
+            TestCase {
+                name: "successfully parse the given window",
+                request: reconciliation_v1::RunReconciliationRequest {
+                    window_start: Some(timestamp(hour_ago - chrono::Duration::hours(1))),
+                    window_end: Some(timestamp(hour_ago)),
+                },
+                expected: Ok((Some(hour_ago - chrono::Duration::hours(1)), hour_ago)),
+            },
+            TestCase {
+                name: "error when the window is empty",
+                request: reconciliation_v1::RunReconciliationRequest {
+                    window_start: Some(timestamp(now)),
+                    window_end: None,
+                },
+                expected: Err(tonic::Code::InvalidArgument),
+            },
+        ];
+
+        for test_case in test_cases {
+            let result = parse_run_request(test_case.request, now);
+            assert_eq!(
+                result.map_err(|status| status.code()),
+                test_case.expected,
+                "{}",
+                test_case.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_update_request() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            request: reconciliation_v1::UpdateExceptionRequest,
+            notes_required: bool,
+            expected: Option<tonic::Code>,
+        }
+
+        let request = reconciliation_v1::UpdateExceptionRequest {
+            exception_id: uuid::Uuid::new_v4().to_string(),
+            author: "ops@pasys.dev".to_string(),
+            notes: String::new(),
+        };
+        let test_cases = vec![
+            TestCase {
+                name: "successfully investigate without notes",
+                request: request.clone(),
+                notes_required: false,
+                expected: None,
+            },
+            TestCase {
+                name: "error when closing without notes",
+                request: request.clone(),
+                notes_required: true,
+                expected: Some(tonic::Code::InvalidArgument),
+            },
+            TestCase {
+                name: "error when author is missing",
+                request: reconciliation_v1::UpdateExceptionRequest {
+                    author: String::new(),
+                    ..request.clone()
+                },
+                notes_required: false,
+                expected: Some(tonic::Code::InvalidArgument),
+            },
+            TestCase {
+                name: "error when exception id is invalid",
+                request: reconciliation_v1::UpdateExceptionRequest {
+                    exception_id: "exception".to_string(),
+                    ..request.clone()
+                },
+                notes_required: false,
+                expected: Some(tonic::Code::InvalidArgument),
+            },
+        ];
+
+        for test_case in test_cases {
+            let result = parse_update_request(&test_case.request, test_case.notes_required);
+            assert_eq!(
+                result.err().map(|status| status.code()),
+                test_case.expected,
+                "{}",
+                test_case.name
+            );
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use crate::domain::exception::{Exception, Note, Status};
+use crate::domain::run::{self, Run};
+use reconciliation_proto::reconciliation_v1;
+
+use prost_types::Timestamp;
+
+fn parse_timestamp_to_proto(timestamp: chrono::DateTime<chrono::Utc>) -> Timestamp {
+    Timestamp {
+        seconds: timestamp.timestamp(),
+        nanos: timestamp.timestamp_subsec_nanos() as i32,
+    }
+}
+
+fn parse_status_to_proto(status: Status) -> i32 {
+    match status {
+        Status::Open => reconciliation_v1::ExceptionStatus::Open as i32,
+        Status::Investigating => reconciliation_v1::ExceptionStatus::Investigating as i32,
+        Status::Resolved => reconciliation_v1::ExceptionStatus::Resolved as i32,
+        Status::WrittenOff => reconciliation_v1::ExceptionStatus::WrittenOff as i32,
+    }
+}
+
+fn parse_note_to_proto(note: Note) -> reconciliation_v1::Note {
+    reconciliation_v1::Note {
+        author: note.author,
+        status: parse_status_to_proto(note.status),
+        text: note.text,
+        created_at: Some(parse_timestamp_to_proto(note.created_at)),
+    }
+}
+
+pub fn parse_exception_to_proto(
+    exception: Exception,
+    notes: Vec<Note>,
+) -> reconciliation_v1::Exception {
+    reconciliation_v1::Exception {
+        id: exception.id.to_string(),
+        source: exception.source,
+        reference: exception.reference,
+        ledger_transaction_id: exception.ledger_transaction_id.map(|id| id.to_string()),
+        statement_reference: exception.statement_reference,
+        discrepancies: exception.discrepancies,
+        ledger_amount_minor: exception.ledger_amount_minor,
+        external_amount_minor: exception.external_amount_minor,
+        currency: exception.currency,
+        ledger_status: exception
+            .ledger_status
+            .map(|status| status.as_ref().to_string()),
+        external_status: exception.external_status,
+        status: parse_status_to_proto(exception.status),
+        notes: notes.into_iter().map(parse_note_to_proto).collect(),
+        first_seen_at: Some(parse_timestamp_to_proto(exception.first_seen_at)),
+        last_seen_at: Some(parse_timestamp_to_proto(exception.last_seen_at)),
+        resolved_at: exception.resolved_at.map(parse_timestamp_to_proto),
+    }
+}
+
+fn parse_run_status_to_proto(status: run::Status) -> i32 {
+    match status {
+        run::Status::Running => reconciliation_v1::RunStatus::Running as i32,
+        run::Status::Completed => reconciliation_v1::RunStatus::Completed as i32,
+        run::Status::Failed => reconciliation_v1::RunStatus::Failed as i32,
+    }
+}
+
+pub fn parse_run_to_proto(run: Run) -> reconciliation_v1::Run {
+    reconciliation_v1::Run {
+        id: run.id.to_string(),
+        source: run.source,
+        window_start: Some(parse_timestamp_to_proto(run.window_start)),
+        window_end: Some(parse_timestamp_to_proto(run.window_end)),
+        status: parse_run_status_to_proto(run.status),
+        matched_count: run.matched_count,
+        mismatched_count: run.mismatched_count,
+        failure_reason: run.failure_reason,
+        started_at: Some(parse_timestamp_to_proto(run.started_at)),
+        finished_at: run.finished_at.map(parse_timestamp_to_proto),
+    }
+}
+
+pub fn parse_to_domain_timestamp(
+    name: &str,
+    timestamp: Timestamp,
+) -> anyhow::Result<chrono::DateTime<chrono::Utc>> {
+    match chrono::DateTime::from_timestamp(timestamp.seconds, timestamp.nanos as u32) {
+        Some(timestamp) => Ok(timestamp),
+        None => Err(anyhow::anyhow!("invalid {name}")),
+    }
+}
+
+/// parse_to_domain_exception_status defaults to open exceptions when the status is unspecified.
+pub fn parse_to_domain_exception_status(status: i32) -> anyhow::Result<Status> {
+    match status {
+        0 | 1 => Ok(Status::Open),
+        2 => Ok(Status::Investigating),
+        3 => Ok(Status::Resolved),
+        4 => Ok(Status::WrittenOff),
+        _ => Err(anyhow::anyhow!("Unknown exception status {status}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::matching::Reconciled;
+    use crate::domain::run::Run;
+    use reconciliation_proto::reconciliation_v1::ExceptionStatus;
+
+    #[test]
+    fn test_parse_to_domain_exception_status() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            status: i32,
+            expected: Option<Status>,
+        }
+
+        let test_cases: Vec<TestCase> = vec![
+            TestCase {
+                name: "successfully parse to open when status is unspecified",
+                status: ExceptionStatus::Unspecified as i32,
+                expected: Some(Status::Open),
+            },
+            TestCase {
+                name: "successfully parse to domain exception status when status is investigating",
+                status: ExceptionStatus::Investigating as i32,
+                expected: Some(Status::Investigating),
+            },
+            TestCase {
+                name: "successfully parse to domain exception status when status is written off",
+                status: ExceptionStatus::WrittenOff as i32,
+                expected: Some(Status::WrittenOff),
+            },
+            TestCase {
+                name: "error when status is unknown",
+                status: 42,
+                expected: None,
+            },
+        ];
+
+        for test_case in test_cases {
+            let resp = parse_to_domain_exception_status(test_case.status);
+            match test_case.expected {
+                Some(expected) => assert_eq!(resp.unwrap(), expected, "{}", test_case.name),
+                None => assert!(resp.is_err(), "{}", test_case.name),
+            }
+        }
+    }
+
+    #[test]
+    fn successfully_parse_run_to_proto() {
+        // arrange
+        let now = chrono::Utc::now();
+        let mut run = Run::new("psp", now - chrono::Duration::hours(1), now);
+        run.status = run::Status::Completed;
+        run.matched_count = 3;
+        run.mismatched_count = 1;
+
+        // act
+        let run_proto = parse_run_to_proto(run.clone());
+
+        // assert
+        assert_eq!(run_proto.id, run.id.to_string());
+        assert_eq!(
+            run_proto.status,
+            reconciliation_v1::RunStatus::Completed as i32
+        );
+        assert_eq!(run_proto.matched_count, 3);
+        assert_eq!(run_proto.mismatched_count, 1);
+        assert_eq!(
+            parse_to_domain_timestamp("window_end", run_proto.window_end.unwrap()).unwrap(),
+            now
+        );
+        assert!(run_proto.finished_at.is_none());
+    }
+
+    #[test]
+    fn successfully_parse_exception_to_proto() {
+        // arrange
+        let now = chrono::Utc::now();
+        let run = Run::new("psp", now - chrono::Duration::hours(1), now);
+        let exception = Exception::new(
+            &run,
+            &Reconciled {
+                ledger: None,
+                external: None,
+                discrepancies: vec![],
+                strategy: None,
+            },
+        );
+        let note = Note::new(
+            exception.id,
+            "ops@pasys.dev",
+            Status::Investigating,
+            "on it",
+        );
+
+        // act
+        let exception_proto = parse_exception_to_proto(exception.clone(), vec![note]);
+
+        // assert
+        assert_eq!(exception_proto.id, exception.id.to_string());
+        assert_eq!(exception_proto.status, ExceptionStatus::Open as i32);
+        assert!(exception_proto.ledger_transaction_id.is_none());
+        assert!(exception_proto.resolved_at.is_none());
+        assert_eq!(exception_proto.notes.len(), 1);
+        assert_eq!(
+            exception_proto.notes[0].status,
+            ExceptionStatus::Investigating as i32
+        );
+    }
+}
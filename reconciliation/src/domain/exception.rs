@@ -0,0 +1,493 @@
+use crate::domain::matching::{Discrepancy, Reconciled, Strategy};
+use crate::domain::run::Run;
+use crate::domain::statement;
+use ledger::domain::transaction::{self, Transaction};
+use std::fmt;
+use std::str::FromStr;
+
+/// AUTOMATIC_AUTHOR signs the notes of exceptions reconciliation resolved by itself.
+pub const AUTOMATIC_AUTHOR: &str = "reconciliation";
+
+/// Match records which strategy paired a ledger transaction with a statement line.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct Match {
+    pub run_id: uuid::Uuid,
+    pub transaction_id: uuid::Uuid,
+    pub statement_reference: String,
+    pub strategy: Strategy,
+}
+
+impl Match {
+    /// new is the match of a reconciled pair, none when it is a mismatch or one side is missing.
+    pub fn new(run: &Run, reconciled: &Reconciled) -> Option<Self> {
+        match (
+            &reconciled.ledger,
+            &reconciled.external,
+            reconciled.strategy,
+        ) {
+            (Some(ledger), Some(external), Some(strategy)) if reconciled.is_matched() => {
+                Some(Match {
+                    run_id: run.id,
+                    transaction_id: ledger.id,
+                    statement_reference: external.reference.clone(),
+                    strategy,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// AgingBucket groups open exceptions by how long they have been outstanding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum AgingBucket {
+    UpToOneDay,
+    OneToThreeDays,
+    ThreeToSevenDays,
+    OverSevenDays,
+}
+
+impl AsRef<str> for AgingBucket {
+    fn as_ref(&self) -> &str {
+        match self {
+            AgingBucket::UpToOneDay => "0-1d",
+            AgingBucket::OneToThreeDays => "1-3d",
+            AgingBucket::ThreeToSevenDays => "3-7d",
+            AgingBucket::OverSevenDays => "7d+",
+        }
+    }
+}
+
+/// Status is where an exception is in its lifecycle. Open and investigating exceptions are
+/// still updated by later runs, resolved and written off ones are closed.
+#[derive(Debug, Clone, Copy, PartialEq, sqlx::Type)]
+#[sqlx(
+    type_name = "reconciliation_exception_status",
+    rename_all = "snake_case"
+)]
+pub enum Status {
+    Open,
+    Investigating,
+    Resolved,
+    WrittenOff,
+}
+
+impl AsRef<str> for Status {
+    fn as_ref(&self) -> &str {
+        match self {
+            Status::Open => "open",
+            Status::Investigating => "investigating",
+            Status::Resolved => "resolved",
+            Status::WrittenOff => "written_off",
+        }
+    }
+}
+
+impl Status {
+    pub fn is_closed(&self) -> bool {
+        matches!(self, Status::Resolved | Status::WrittenOff)
+    }
+
+    /// can_move_to allows open exceptions to be investigated, and open or investigated ones to
+    /// be closed. Closed exceptions stay closed.
+    pub fn can_move_to(&self, to: Status) -> bool {
+        match (self, to) {
+            (Status::Open, Status::Investigating) => true,
+            (Status::Open | Status::Investigating, to) => to.is_closed(),
+            _ => false,
+        }
+    }
+}
+
+/// Exception is a discrepancy someone has to act on. It is keyed by source and reference, so a
+/// ledger transaction the PSP has not reported yet is carried from run to run until a later
+/// statement line settles it.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct Exception {
+    pub id: uuid::Uuid,
+    pub source: String,
+    /// reference is the statement line reference, or the ledger transaction id when the PSP
+    /// reported nothing.
+    pub reference: String,
+    pub ledger_transaction_id: Option<uuid::Uuid>,
+    pub statement_reference: Option<String>,
+    pub discrepancies: Vec<String>,
+    pub ledger_amount_minor: Option<i64>,
+    pub external_amount_minor: Option<i64>,
+    pub currency: String,
+    pub ledger_status: Option<transaction::Status>,
+    pub external_status: Option<String>,
+    pub status: Status,
+    pub first_seen_run_id: uuid::Uuid,
+    pub last_seen_run_id: uuid::Uuid,
+    pub first_seen_at: chrono::DateTime<chrono::Utc>,
+    pub last_seen_at: chrono::DateTime<chrono::Utc>,
+    /// resolved_at is set once the exception is closed, resolved or written off.
+    pub resolved_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub resolved_run_id: Option<uuid::Uuid>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Exception {
+    /// new is the exception for a mismatched pair found by `run`.
+    pub fn new(run: &Run, reconciled: &Reconciled) -> Self {
+        let now = chrono::Utc::now();
+        let ledger = reconciled.ledger.as_ref();
+        let external = reconciled.external.as_ref();
+        let reference = match (ledger, external) {
+            (_, Some(external)) => external.reference.clone(),
+            (Some(ledger), None) => ledger.id.to_string(),
+            (None, None) => String::new(),
+        };
+
+        Exception {
+            id: uuid::Uuid::new_v4(),
+            source: run.source.clone(),
+            reference,
+            ledger_transaction_id: ledger.map(|t| t.id),
+            statement_reference: external.map(|t| t.reference.clone()),
+            discrepancies: reconciled
+                .discrepancies
+                .iter()
+                .map(|d| d.as_ref().to_string())
+                .collect(),
+            ledger_amount_minor: ledger.map(|t| t.amount_minor),
+            external_amount_minor: external.map(|t| t.amount_minor),
+            currency: ledger
+                .map(|t| t.currency.clone())
+                .or(external.map(|t| t.currency.clone()))
+                .unwrap_or_default(),
+            ledger_status: ledger.map(|t| t.status.clone()),
+            external_status: external.map(|t| t.status.as_ref().to_string()),
+            status: Status::Open,
+            first_seen_run_id: run.id,
+            last_seen_run_id: run.id,
+            first_seen_at: now,
+            last_seen_at: now,
+            resolved_at: None,
+            resolved_run_id: None,
+            updated_at: now,
+        }
+    }
+
+    /// correction is the status a ledger transaction can safely be moved to without a human
+    /// looking at it. That is the case when status is the only discrepancy and the PSP has
+    /// settled or failed a transaction which the ledger still has pending past the timeout.
+    pub fn correction(
+        &self,
+        ledger: &Transaction,
+        now: chrono::DateTime<chrono::Utc>,
+        pending_timeout: chrono::Duration,
+    ) -> Option<transaction::Status> {
+        let status_only = self.discrepancies == [Discrepancy::Status.as_ref()];
+        let pending = matches!(
+            ledger.status,
+            transaction::Status::Init | transaction::Status::Pending
+        );
+        if !status_only || !pending || now - ledger.updated_at < pending_timeout {
+            return None;
+        }
+
+        match self
+            .external_status
+            .as_deref()
+            .map(statement::Status::from_str)
+        {
+            Some(Ok(statement::Status::Succeeded)) => Some(transaction::Status::Success),
+            Some(Ok(statement::Status::Failed)) => Some(transaction::Status::Failed),
+            _ => None,
+        }
+    }
+
+    pub fn age(&self, now: chrono::DateTime<chrono::Utc>) -> chrono::Duration {
+        now - self.first_seen_at
+    }
+
+    pub fn aging_bucket(&self, now: chrono::DateTime<chrono::Utc>) -> AgingBucket {
+        match self.age(now) {
+            age if age <= chrono::Duration::days(1) => AgingBucket::UpToOneDay,
+            age if age <= chrono::Duration::days(3) => AgingBucket::OneToThreeDays,
+            age if age <= chrono::Duration::days(7) => AgingBucket::ThreeToSevenDays,
+            _ => AgingBucket::OverSevenDays,
+        }
+    }
+}
+
+/// Note is a comment left when an exception moves along its lifecycle.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct Note {
+    pub id: uuid::Uuid,
+    pub exception_id: uuid::Uuid,
+    pub author: String,
+    pub status: Status,
+    pub text: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Note {
+    pub fn new(
+        exception_id: uuid::Uuid,
+        author: impl Into<String>,
+        status: Status,
+        text: impl Into<String>,
+    ) -> Self {
+        Note {
+            id: uuid::Uuid::new_v4(),
+            exception_id,
+            author: author.into(),
+            status,
+            text: text.into(),
+            created_at: chrono::Utc::now(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExceptionError {
+    NotFound(uuid::Uuid),
+    InvalidTransition(uuid::Uuid, Status, Status),
+}
+
+impl fmt::Display for ExceptionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExceptionError::NotFound(id) => write!(f, "exception {id} not found"),
+            ExceptionError::InvalidTransition(id, from, to) => write!(
+                f,
+                "exception {id} cannot move from {} to {}",
+                from.as_ref(),
+                to.as_ref()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ExceptionError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aging_bucket() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            age: chrono::Duration,
+            expected: AgingBucket,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "seen this hour",
+                age: chrono::Duration::minutes(5),
+                expected: AgingBucket::UpToOneDay,
+            },
+            TestCase {
+                name: "exactly one day",
+                age: chrono::Duration::days(1),
+                expected: AgingBucket::UpToOneDay,
+            },
+            TestCase {
+                name: "two days",
+                age: chrono::Duration::days(2),
+                expected: AgingBucket::OneToThreeDays,
+            },
+            TestCase {
+                name: "five days",
+                age: chrono::Duration::days(5),
+                expected: AgingBucket::ThreeToSevenDays,
+            },
+            TestCase {
+                name: "a month",
+                age: chrono::Duration::days(30),
+                expected: AgingBucket::OverSevenDays,
+            },
+        ];
+
+        let now = chrono::Utc::now();
+        let run = Run::new("psp", now - chrono::Duration::hours(1), now);
+        let reconciled = Reconciled {
+            ledger: None,
+            external: None,
+            discrepancies: vec![],
+            strategy: None,
+        };
+
+        for test_case in test_cases {
+            // arrange
+            let mut exception = Exception::new(&run, &reconciled);
+            exception.first_seen_at = now - test_case.age;
+
+            // act
+            let bucket = exception.aging_bucket(now);
+
+            // assert
+            assert_eq!(bucket, test_case.expected, "{}", test_case.name);
+        }
+    }
+
+    #[test]
+    fn test_can_move_to() {
+        #[derive(Debug)]
+        struct TestCase {
+            from: Status,
+            to: Status,
+            expected: bool,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                from: Status::Open,
+                to: Status::Investigating,
+                expected: true,
+            },
+            TestCase {
+                from: Status::Open,
+                to: Status::WrittenOff,
+                expected: true,
+            },
+            TestCase {
+                from: Status::Investigating,
+                to: Status::Resolved,
+                expected: true,
+            },
+            TestCase {
+                from: Status::Investigating,
+                to: Status::Open,
+                expected: false,
+            },
+            TestCase {
+                from: Status::Investigating,
+                to: Status::Investigating,
+                expected: false,
+            },
+            TestCase {
+                from: Status::Resolved,
+                to: Status::WrittenOff,
+                expected: false,
+            },
+            TestCase {
+                from: Status::WrittenOff,
+                to: Status::Open,
+                expected: false,
+            },
+        ];
+
+        for test_case in test_cases {
+            // act
+            let result = test_case.from.can_move_to(test_case.to);
+
+            // assert
+            assert_eq!(result, test_case.expected, "{test_case:?}");
+        }
+    }
+
+    #[test]
+    fn test_correction() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            discrepancies: Vec<&'static str>,
+            ledger_status: transaction::Status,
+            pending_for: chrono::Duration,
+            external_status: Option<&'static str>,
+            expected: Option<transaction::Status>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "settled at the PSP while pending past the timeout",
+                discrepancies: vec!["status"],
+                ledger_status: transaction::Status::Pending,
+                pending_for: chrono::Duration::hours(2),
+                external_status: Some("succeeded"),
+                expected: Some(transaction::Status::Success),
+            },
+            TestCase {
+                name: "failed at the PSP while still initialised",
+                discrepancies: vec!["status"],
+                ledger_status: transaction::Status::Init,
+                pending_for: chrono::Duration::hours(2),
+                external_status: Some("failed"),
+                expected: Some(transaction::Status::Failed),
+            },
+            TestCase {
+                name: "pending within the timeout",
+                discrepancies: vec!["status"],
+                ledger_status: transaction::Status::Pending,
+                pending_for: chrono::Duration::minutes(5),
+                external_status: Some("succeeded"),
+                expected: None,
+            },
+            TestCase {
+                name: "amount differs too",
+                discrepancies: vec!["status", "amount"],
+                ledger_status: transaction::Status::Pending,
+                pending_for: chrono::Duration::hours(2),
+                external_status: Some("succeeded"),
+                expected: None,
+            },
+            TestCase {
+                name: "ledger already settled",
+                discrepancies: vec!["status"],
+                ledger_status: transaction::Status::Success,
+                pending_for: chrono::Duration::hours(2),
+                external_status: Some("failed"),
+                expected: None,
+            },
+            TestCase {
+                name: "still pending at the PSP",
+                discrepancies: vec!["status"],
+                ledger_status: transaction::Status::Pending,
+                pending_for: chrono::Duration::hours(2),
+                external_status: Some("pending"),
+                expected: None,
+            },
+            TestCase {
+                name: "missing at the PSP",
+                discrepancies: vec!["status"],
+                ledger_status: transaction::Status::Pending,
+                pending_for: chrono::Duration::hours(2),
+                external_status: None,
+                expected: None,
+            },
+        ];
+
+        let now = chrono::Utc::now();
+        let run = Run::new("psp", now - chrono::Duration::hours(1), now);
+        let reconciled = Reconciled {
+            ledger: None,
+            external: None,
+            discrepancies: vec![],
+            strategy: None,
+        };
+
+        for test_case in test_cases {
+            // arrange
+            let mut ledger = Transaction::new(
+                uuid::Uuid::new_v4(),
+                uuid::Uuid::new_v4(),
+                1000,
+                "USD",
+                "key".to_string(),
+                now,
+            );
+            ledger.status = test_case.ledger_status;
+            ledger.updated_at = now - test_case.pending_for;
+            let mut exception = Exception::new(&run, &reconciled);
+            exception.discrepancies = test_case
+                .discrepancies
+                .iter()
+                .map(|d| d.to_string())
+                .collect();
+            exception.external_status = test_case.external_status.map(str::to_string);
+
+            // act
+            let correction = exception.correction(&ledger, now, chrono::Duration::hours(1));
+
+            // assert
+            assert_eq!(correction, test_case.expected, "{}", test_case.name);
+        }
+    }
+}
@@ -0,0 +1,802 @@
+use crate::domain::statement::{self, StatementLine};
+use crate::{DEFAULT_MATCH_DATE_WINDOW_SECONDS, DEFAULT_MAX_SUM_CANDIDATES};
+use ledger::domain::transaction::{self, Transaction};
+use std::collections::HashMap;
+
+/// Discrepancy is a reason for the ledger and the PSP to disagree about a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Discrepancy {
+    /// MissingAtPsp is a ledger transaction the PSP did not report.
+    MissingAtPsp,
+    /// MissingInLedger is a PSP transaction the ledger does not know about.
+    MissingInLedger,
+    /// Duplicate is a second PSP record for a ledger transaction which is already matched.
+    Duplicate,
+    Status,
+    Amount,
+}
+
+impl AsRef<str> for Discrepancy {
+    fn as_ref(&self) -> &str {
+        match self {
+            Discrepancy::MissingAtPsp => "missing_at_psp",
+            Discrepancy::MissingInLedger => "missing_in_ledger",
+            Discrepancy::Duplicate => "duplicate",
+            Discrepancy::Status => "status",
+            Discrepancy::Amount => "amount",
+        }
+    }
+}
+
+/// Strategy is the matching rule which paired a PSP record with a ledger transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, sqlx::Type)]
+#[sqlx(type_name = "reconciliation_match_strategy", rename_all = "snake_case")]
+pub enum Strategy {
+    /// Reference is a match on the echoed transaction id or idempotency key.
+    Reference,
+    /// AmountTolerance is a reference match whose amounts differ within the currency tolerance.
+    AmountTolerance,
+    /// FeeAdjusted is a reference match whose amount is net of the fee the PSP kept.
+    FeeAdjusted,
+    /// DateWindow is a match without reference on amount and currency, close in time.
+    DateWindow,
+    /// Sum is one payout line settling several ledger transactions.
+    Sum,
+}
+
+impl AsRef<str> for Strategy {
+    fn as_ref(&self) -> &str {
+        match self {
+            Strategy::Reference => "reference",
+            Strategy::AmountTolerance => "amount_tolerance",
+            Strategy::FeeAdjusted => "fee_adjusted",
+            Strategy::DateWindow => "date_window",
+            Strategy::Sum => "sum",
+        }
+    }
+}
+
+/// MatchRules are the limits of the matching strategies beyond exact references.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchRules {
+    /// tolerances is the amount difference, in minor units, still accepted per currency.
+    /// Currencies without a tolerance must match exactly.
+    pub tolerances: HashMap<String, i64>,
+    /// date_window is how far apart the ledger transaction and the booking may be for matches
+    /// without a reference.
+    pub date_window: chrono::Duration,
+    /// max_sum_candidates bounds the ledger transactions searched for a sum match of one line.
+    pub max_sum_candidates: usize,
+}
+
+impl Default for MatchRules {
+    fn default() -> Self {
+        MatchRules {
+            tolerances: HashMap::new(),
+            date_window: chrono::Duration::seconds(DEFAULT_MATCH_DATE_WINDOW_SECONDS),
+            max_sum_candidates: DEFAULT_MAX_SUM_CANDIDATES,
+        }
+    }
+}
+
+impl MatchRules {
+    pub fn tolerance(&self, currency: &str) -> i64 {
+        self.tolerances
+            .get(currency.to_uppercase().as_str())
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+/// parse_tolerances reads per-currency tolerances in minor units, e.g. `USD=1,JPY=0`.
+pub fn parse_tolerances(s: &str) -> anyhow::Result<HashMap<String, i64>> {
+    let mut tolerances = HashMap::new();
+    for pair in s.split(',').filter(|pair| !pair.trim().is_empty()) {
+        let (currency, tolerance) = match pair.split_once('=') {
+            Some((currency, tolerance)) => (currency.trim().to_uppercase(), tolerance.trim()),
+            None => anyhow::bail!("invalid tolerance {pair}, expected CURRENCY=minor units"),
+        };
+        match tolerance.parse::<i64>() {
+            Ok(tolerance) if tolerance >= 0 => tolerances.insert(currency, tolerance),
+            _ => anyhow::bail!("invalid tolerance {tolerance} for {currency}"),
+        };
+    }
+
+    Ok(tolerances)
+}
+
+/// Reconciled pairs a ledger transaction with the PSP record for it. Either side is missing
+/// when no counterpart was found. `strategy` is set whenever both sides are present; for a sum
+/// match the same PSP record is paired with each of the ledger transactions it settles.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reconciled {
+    pub ledger: Option<Transaction>,
+    pub external: Option<StatementLine>,
+    pub discrepancies: Vec<Discrepancy>,
+    pub strategy: Option<Strategy>,
+}
+
+impl Reconciled {
+    pub fn is_matched(&self) -> bool {
+        self.discrepancies.is_empty()
+    }
+
+    /// transaction_id is the ledger transaction id, falling back to the best reference the
+    /// PSP gave when the ledger side is missing.
+    pub fn transaction_id(&self) -> String {
+        match (&self.ledger, &self.external) {
+            (Some(ledger), _) => ledger.id.to_string(),
+            (None, Some(external)) => match (&external.transaction_id, &external.idempotency_key) {
+                (Some(id), _) => id.to_string(),
+                (None, Some(key)) => key.clone(),
+                (None, None) => external.reference.clone(),
+            },
+            (None, None) => String::new(),
+        }
+    }
+}
+
+/// settlement_status is the PSP status a ledger transaction should have. Refunds happen after
+/// settlement, so refunded transactions are expected to have succeeded at the PSP.
+pub fn settlement_status(status: &transaction::Status) -> statement::Status {
+    match status {
+        transaction::Status::Init | transaction::Status::Pending => statement::Status::Pending,
+        transaction::Status::Success
+        | transaction::Status::Refund
+        | transaction::Status::Refunded => statement::Status::Succeeded,
+        transaction::Status::Failed | transaction::Status::Fraud => statement::Status::Failed,
+    }
+}
+
+/// reconcile matches PSP records to ledger transactions in three passes:
+///
+/// 1. on transaction id, then idempotency key, comparing status and amount of every pair. The
+///    amount may be net of the PSP fee or differ within the currency tolerance.
+/// 2. records which echo no reference, on status, currency and amount, to the unclaimed ledger
+///    transaction closest in time within the date window.
+/// 3. records still unmatched, to a set of at least two unclaimed ledger transactions within
+///    the date window which add up to the amount, for netted payouts.
+///
+/// PSP records come first in the result, in the order given, followed by the ledger
+/// transactions the PSP did not report.
+pub fn reconcile(
+    ledger: Vec<Transaction>,
+    external: Vec<StatementLine>,
+    rules: &MatchRules,
+) -> Vec<Reconciled> {
+    let by_id: HashMap<uuid::Uuid, usize> = ledger
+        .iter()
+        .enumerate()
+        .map(|(i, transaction)| (transaction.id, i))
+        .collect();
+    let by_key: HashMap<&str, usize> = ledger
+        .iter()
+        .enumerate()
+        .map(|(i, transaction)| (transaction.idempotency_key.as_str(), i))
+        .collect();
+
+    let mut claimed = vec![false; ledger.len()];
+    let mut slots: Vec<Vec<Reconciled>> = vec![Vec::new(); external.len()];
+    let mut unreferenced = Vec::new();
+    for (slot, record) in external.iter().enumerate() {
+        let index = record
+            .transaction_id
+            .and_then(|id| by_id.get(&id))
+            .or_else(|| {
+                record
+                    .idempotency_key
+                    .as_deref()
+                    .and_then(|key| by_key.get(key))
+            })
+            .copied();
+
+        let reconciled_record = match index {
+            Some(i) if claimed[i] => Reconciled {
+                ledger: Some(ledger[i].clone()),
+                external: Some(record.clone()),
+                discrepancies: vec![Discrepancy::Duplicate],
+                strategy: Some(Strategy::Reference),
+            },
+            Some(i) => {
+                claimed[i] = true;
+                let (discrepancies, strategy) = compare(&ledger[i], record, rules);
+                Reconciled {
+                    ledger: Some(ledger[i].clone()),
+                    external: Some(record.clone()),
+                    discrepancies,
+                    strategy: Some(strategy),
+                }
+            }
+            None if record.transaction_id.is_none() && record.idempotency_key.is_none() => {
+                unreferenced.push(slot);
+                continue;
+            }
+            None => Reconciled {
+                ledger: None,
+                external: Some(record.clone()),
+                discrepancies: vec![Discrepancy::MissingInLedger],
+                strategy: None,
+            },
+        };
+        slots[slot].push(reconciled_record);
+    }
+
+    let mut unmatched = Vec::new();
+    for slot in unreferenced {
+        let record = &external[slot];
+        match match_date_window(&ledger, &claimed, record, rules) {
+            Some(i) => {
+                claimed[i] = true;
+                slots[slot].push(Reconciled {
+                    ledger: Some(ledger[i].clone()),
+                    external: Some(record.clone()),
+                    discrepancies: vec![],
+                    strategy: Some(Strategy::DateWindow),
+                });
+            }
+            None => unmatched.push(slot),
+        }
+    }
+
+    for slot in unmatched {
+        let record = &external[slot];
+        match match_sum(&ledger, &claimed, record, rules) {
+            Some(indexes) => {
+                for i in indexes {
+                    claimed[i] = true;
+                    slots[slot].push(Reconciled {
+                        ledger: Some(ledger[i].clone()),
+                        external: Some(record.clone()),
+                        discrepancies: vec![],
+                        strategy: Some(Strategy::Sum),
+                    });
+                }
+            }
+            None => slots[slot].push(Reconciled {
+                ledger: None,
+                external: Some(record.clone()),
+                discrepancies: vec![Discrepancy::MissingInLedger],
+                strategy: None,
+            }),
+        }
+    }
+
+    let mut reconciled: Vec<Reconciled> = slots.into_iter().flatten().collect();
+    for (transaction, claimed) in ledger.into_iter().zip(claimed) {
+        if !claimed {
+            reconciled.push(Reconciled {
+                ledger: Some(transaction),
+                external: None,
+                discrepancies: vec![Discrepancy::MissingAtPsp],
+                strategy: None,
+            });
+        }
+    }
+
+    reconciled
+}
+
+/// compare returns the discrepancies of a pair matched on reference, and how its amounts
+/// matched when they did.
+fn compare(
+    ledger: &Transaction,
+    external: &StatementLine,
+    rules: &MatchRules,
+) -> (Vec<Discrepancy>, Strategy) {
+    let mut discrepancies = Vec::new();
+    if settlement_status(&ledger.status) != external.status {
+        discrepancies.push(Discrepancy::Status);
+    }
+
+    let strategy = match amount_strategy(ledger.amount_minor, &ledger.currency, external, rules) {
+        Some(strategy) => strategy,
+        None => {
+            discrepancies.push(Discrepancy::Amount);
+            Strategy::Reference
+        }
+    };
+
+    (discrepancies, strategy)
+}
+
+/// amount_strategy checks a ledger amount against a PSP record: exactly, net of the fee the PSP
+/// kept, or within the currency tolerance of either.
+fn amount_strategy(
+    amount_minor: i64,
+    currency: &str,
+    external: &StatementLine,
+    rules: &MatchRules,
+) -> Option<Strategy> {
+    if !currency.eq_ignore_ascii_case(external.currency.as_str()) {
+        return None;
+    }
+
+    let net = external.amount_minor;
+    let gross = external.amount_minor + external.fee_minor;
+    let tolerance = rules.tolerance(currency);
+    match amount_minor {
+        _ if amount_minor == net => Some(Strategy::Reference),
+        _ if amount_minor == gross => Some(Strategy::FeeAdjusted),
+        _ if (amount_minor - net).abs() <= tolerance
+            || (amount_minor - gross).abs() <= tolerance =>
+        {
+            Some(Strategy::AmountTolerance)
+        }
+        _ => None,
+    }
+}
+
+/// candidates are the unclaimed ledger transactions a record without reference may settle.
+fn candidates<'a>(
+    ledger: &'a [Transaction],
+    claimed: &'a [bool],
+    external: &'a StatementLine,
+    rules: &'a MatchRules,
+) -> impl Iterator<Item = usize> + 'a {
+    (0..ledger.len()).filter(move |&i| {
+        !claimed[i]
+            && settlement_status(&ledger[i].status) == external.status
+            && ledger[i]
+                .currency
+                .eq_ignore_ascii_case(external.currency.as_str())
+            && (ledger[i].created_at - external.booked_at).abs() <= rules.date_window
+    })
+}
+
+fn match_date_window(
+    ledger: &[Transaction],
+    claimed: &[bool],
+    external: &StatementLine,
+    rules: &MatchRules,
+) -> Option<usize> {
+    candidates(ledger, claimed, external, rules)
+        .filter(|&i| {
+            amount_strategy(ledger[i].amount_minor, &ledger[i].currency, external, rules).is_some()
+        })
+        .min_by_key(|&i| (ledger[i].created_at - external.booked_at).abs())
+}
+
+/// match_sum looks for at least two ledger transactions which add up to the amount of the
+/// record, net of fees or gross. The search is bounded to the `max_sum_candidates` transactions
+/// closest in time.
+fn match_sum(
+    ledger: &[Transaction],
+    claimed: &[bool],
+    external: &StatementLine,
+    rules: &MatchRules,
+) -> Option<Vec<usize>> {
+    let mut indexes: Vec<usize> = candidates(ledger, claimed, external, rules)
+        .filter(|&i| ledger[i].amount_minor > 0)
+        .collect();
+    indexes.sort_by_key(|&i| (ledger[i].created_at - external.booked_at).abs());
+    indexes.truncate(rules.max_sum_candidates);
+    // largest first, so the search can stop as soon as a partial sum overshoots
+    indexes.sort_by_key(|&i| std::cmp::Reverse(ledger[i].amount_minor));
+    let amounts: Vec<i64> = indexes.iter().map(|&i| ledger[i].amount_minor).collect();
+
+    let tolerance = rules.tolerance(&external.currency);
+    let net = external.amount_minor;
+    let gross = external.amount_minor + external.fee_minor;
+    for target in [net, gross] {
+        let mut chosen = Vec::new();
+        if subset_sum(&amounts, 0, target, tolerance, &mut chosen) {
+            return Some(
+                chosen
+                    .into_iter()
+                    .map(|position| indexes[position])
+                    .collect(),
+            );
+        }
+    }
+
+    None
+}
+
+/// subset_sum finds positions in `amounts`, sorted descending, from `start` on whose sum is
+/// within `tolerance` of `target`, picking at least two of them overall.
+fn subset_sum(
+    amounts: &[i64],
+    start: usize,
+    target: i64,
+    tolerance: i64,
+    chosen: &mut Vec<usize>,
+) -> bool {
+    if chosen.len() >= 2 && target.abs() <= tolerance {
+        return true;
+    }
+
+    for position in start..amounts.len() {
+        if amounts[position] > target + tolerance {
+            continue;
+        }
+        chosen.push(position);
+        if subset_sum(
+            amounts,
+            position + 1,
+            target - amounts[position],
+            tolerance,
+            chosen,
+        ) {
+            return true;
+        }
+        chosen.pop();
+    }
+
+    false
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn ledger_transaction(amount_minor: i64, status: transaction::Status) -> Transaction {
+        let mut transaction = Transaction::new(
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+            amount_minor,
+            "USD",
+            uuid::Uuid::new_v4().to_string(),
+            chrono::Utc::now(),
+        );
+        transaction.status = status;
+        transaction
+    }
+
+    pub(crate) fn settled(transaction: &Transaction) -> StatementLine {
+        StatementLine {
+            reference: format!("psp_{}", transaction.id.simple()),
+            transaction_id: Some(transaction.id),
+            idempotency_key: Some(transaction.idempotency_key.clone()),
+            status: statement::Status::Succeeded,
+            amount_minor: transaction.amount_minor,
+            fee_minor: 0,
+            currency: transaction.currency.clone(),
+            booked_at: transaction.created_at,
+        }
+    }
+
+    #[test]
+    fn test_reconcile_pair() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            ledger_status: transaction::Status,
+            external: fn(StatementLine) -> StatementLine,
+            expected: Vec<Discrepancy>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "matched on transaction id",
+                ledger_status: transaction::Status::Success,
+                external: |external| external,
+                expected: vec![],
+            },
+            TestCase {
+                name: "matched on idempotency key",
+                ledger_status: transaction::Status::Success,
+                external: |external| StatementLine {
+                    transaction_id: None,
+                    ..external
+                },
+                expected: vec![],
+            },
+            TestCase {
+                name: "refunded transaction settled at the PSP",
+                ledger_status: transaction::Status::Refunded,
+                external: |external| external,
+                expected: vec![],
+            },
+            TestCase {
+                name: "status mismatch",
+                ledger_status: transaction::Status::Pending,
+                external: |external| external,
+                expected: vec![Discrepancy::Status],
+            },
+            TestCase {
+                name: "amount mismatch",
+                ledger_status: transaction::Status::Success,
+                external: |external| StatementLine {
+                    amount_minor: external.amount_minor + 1,
+                    ..external
+                },
+                expected: vec![Discrepancy::Amount],
+            },
+            TestCase {
+                name: "status and currency mismatch",
+                ledger_status: transaction::Status::Failed,
+                external: |external| StatementLine {
+                    currency: "EUR".to_string(),
+                    ..external
+                },
+                expected: vec![Discrepancy::Status, Discrepancy::Amount],
+            },
+        ];
+
+        for test_case in test_cases {
+            // arrange
+            let ledger = ledger_transaction(1000, test_case.ledger_status.clone());
+            let external = (test_case.external)(settled(&ledger));
+
+            // act
+            let reconciled =
+                reconcile(vec![ledger.clone()], vec![external], &MatchRules::default());
+
+            // assert
+            assert_eq!(reconciled.len(), 1, "{}", test_case.name);
+            assert_eq!(
+                reconciled[0].discrepancies, test_case.expected,
+                "{}",
+                test_case.name
+            );
+            assert_eq!(
+                reconciled[0].transaction_id(),
+                ledger.id.to_string(),
+                "{}",
+                test_case.name
+            );
+        }
+    }
+
+    #[test]
+    fn successfully_report_unmatched_transactions() {
+        // arrange
+        let matched = ledger_transaction(1000, transaction::Status::Success);
+        let missing_at_psp = ledger_transaction(2000, transaction::Status::Success);
+        let missing_in_ledger = StatementLine {
+            transaction_id: None,
+            idempotency_key: Some("unknown".to_string()),
+            ..settled(&ledger_transaction(3000, transaction::Status::Success))
+        };
+
+        // act
+        let reconciled = reconcile(
+            vec![matched.clone(), missing_at_psp.clone()],
+            vec![
+                settled(&matched),
+                missing_in_ledger.clone(),
+                settled(&matched),
+            ],
+            &MatchRules::default(),
+        );
+
+        // assert
+        let discrepancies: Vec<_> = reconciled
+            .iter()
+            .map(|r| (r.transaction_id(), r.discrepancies.clone()))
+            .collect();
+        assert_eq!(
+            discrepancies,
+            vec![
+                (matched.id.to_string(), vec![]),
+                ("unknown".to_string(), vec![Discrepancy::MissingInLedger]),
+                (matched.id.to_string(), vec![Discrepancy::Duplicate]),
+                (
+                    missing_at_psp.id.to_string(),
+                    vec![Discrepancy::MissingAtPsp]
+                ),
+            ]
+        );
+        assert_eq!(reconciled[1].external, Some(missing_in_ledger));
+    }
+
+    #[test]
+    fn test_match_strategy() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            external: fn(StatementLine) -> StatementLine,
+            expected_strategy: Option<Strategy>,
+            expected: Vec<Discrepancy>,
+        }
+
+        fn unreferenced(external: StatementLine) -> StatementLine {
+            StatementLine {
+                transaction_id: None,
+                idempotency_key: None,
+                ..external
+            }
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "exact amount",
+                external: |external| external,
+                expected_strategy: Some(Strategy::Reference),
+                expected: vec![],
+            },
+            TestCase {
+                name: "fee deducted at source",
+                external: |external| StatementLine {
+                    amount_minor: 970,
+                    fee_minor: 30,
+                    ..external
+                },
+                expected_strategy: Some(Strategy::FeeAdjusted),
+                expected: vec![],
+            },
+            TestCase {
+                name: "within tolerance",
+                external: |external| StatementLine {
+                    amount_minor: 1002,
+                    ..external
+                },
+                expected_strategy: Some(Strategy::AmountTolerance),
+                expected: vec![],
+            },
+            TestCase {
+                name: "net of fee within tolerance",
+                external: |external| StatementLine {
+                    amount_minor: 969,
+                    fee_minor: 30,
+                    ..external
+                },
+                expected_strategy: Some(Strategy::AmountTolerance),
+                expected: vec![],
+            },
+            TestCase {
+                name: "outside tolerance",
+                external: |external| StatementLine {
+                    amount_minor: 1003,
+                    ..external
+                },
+                expected_strategy: Some(Strategy::Reference),
+                expected: vec![Discrepancy::Amount],
+            },
+            TestCase {
+                name: "no reference within date window",
+                external: |external| StatementLine {
+                    booked_at: external.booked_at + chrono::Duration::days(2),
+                    ..unreferenced(external)
+                },
+                expected_strategy: Some(Strategy::DateWindow),
+                expected: vec![],
+            },
+            TestCase {
+                name: "no reference net of fee",
+                external: |external| StatementLine {
+                    amount_minor: 970,
+                    fee_minor: 30,
+                    ..unreferenced(external)
+                },
+                expected_strategy: Some(Strategy::DateWindow),
+                expected: vec![],
+            },
+            TestCase {
+                name: "no reference outside date window",
+                external: |external| StatementLine {
+                    booked_at: external.booked_at + chrono::Duration::days(4),
+                    ..unreferenced(external)
+                },
+                expected_strategy: None,
+                expected: vec![Discrepancy::MissingInLedger],
+            },
+            TestCase {
+                name: "no reference with another status",
+                external: |external| StatementLine {
+                    status: statement::Status::Failed,
+                    ..unreferenced(external)
+                },
+                expected_strategy: None,
+                expected: vec![Discrepancy::MissingInLedger],
+            },
+        ];
+
+        let rules = MatchRules {
+            tolerances: parse_tolerances("usd=2").unwrap(),
+            ..MatchRules::default()
+        };
+        for test_case in test_cases {
+            // arrange
+            let ledger = ledger_transaction(1000, transaction::Status::Success);
+            let external = (test_case.external)(settled(&ledger));
+
+            // act
+            let reconciled = reconcile(vec![ledger], vec![external], &rules);
+
+            // assert
+            assert_eq!(
+                reconciled[0].strategy, test_case.expected_strategy,
+                "{}",
+                test_case.name
+            );
+            assert_eq!(
+                reconciled[0].discrepancies, test_case.expected,
+                "{}",
+                test_case.name
+            );
+        }
+    }
+
+    #[test]
+    fn successfully_match_payout_to_sum_of_transactions() {
+        // arrange - a netted payout of two transactions, less the PSP fee
+        let first = ledger_transaction(1000, transaction::Status::Success);
+        let second = ledger_transaction(2500, transaction::Status::Success);
+        let unrelated = ledger_transaction(700, transaction::Status::Success);
+        let mut too_old = ledger_transaction(3500, transaction::Status::Success);
+        too_old.created_at -= chrono::Duration::days(10);
+        let payout = StatementLine {
+            reference: "po_1".to_string(),
+            transaction_id: None,
+            idempotency_key: None,
+            amount_minor: 3400,
+            fee_minor: 100,
+            ..settled(&first)
+        };
+
+        // act
+        let reconciled = reconcile(
+            vec![
+                too_old.clone(),
+                first.clone(),
+                unrelated.clone(),
+                second.clone(),
+            ],
+            vec![payout.clone()],
+            &MatchRules::default(),
+        );
+
+        // assert
+        let results: HashSet<_> = reconciled
+            .iter()
+            .map(|r| (r.transaction_id(), r.strategy, r.discrepancies.clone()))
+            .collect();
+        assert_eq!(
+            results,
+            HashSet::from([
+                (first.id.to_string(), Some(Strategy::Sum), vec![]),
+                (second.id.to_string(), Some(Strategy::Sum), vec![]),
+                (
+                    unrelated.id.to_string(),
+                    None,
+                    vec![Discrepancy::MissingAtPsp]
+                ),
+                (
+                    too_old.id.to_string(),
+                    None,
+                    vec![Discrepancy::MissingAtPsp]
+                ),
+            ])
+        );
+        assert_eq!(reconciled[0].external, Some(payout.clone()));
+        assert_eq!(reconciled[1].external, Some(payout));
+    }
+
+    #[test]
+    fn successfully_match_closest_in_date_window() {
+        // arrange - two transactions of the same amount, a day apart
+        let mut yesterday = ledger_transaction(1000, transaction::Status::Success);
+        yesterday.created_at -= chrono::Duration::days(1);
+        let today = ledger_transaction(1000, transaction::Status::Success);
+        let line = StatementLine {
+            transaction_id: None,
+            idempotency_key: None,
+            ..settled(&today)
+        };
+
+        // act
+        let reconciled = reconcile(
+            vec![yesterday.clone(), today.clone()],
+            vec![line],
+            &MatchRules::default(),
+        );
+
+        // assert
+        assert_eq!(reconciled[0].transaction_id(), today.id.to_string());
+        assert_eq!(reconciled[0].strategy, Some(Strategy::DateWindow));
+        assert_eq!(reconciled[1].transaction_id(), yesterday.id.to_string());
+        assert_eq!(reconciled[1].discrepancies, vec![Discrepancy::MissingAtPsp]);
+    }
+
+    #[test]
+    fn error_when_tolerances_are_invalid() {
+        assert!(parse_tolerances("USD").is_err());
+        assert!(parse_tolerances("USD=-1").is_err());
+        assert_eq!(
+            parse_tolerances("usd=1, EUR=2").unwrap(),
+            HashMap::from([("USD".to_string(), 1), ("EUR".to_string(), 2)])
+        );
+    }
+}
@@ -0,0 +1,4 @@
+pub mod exception;
+pub mod matching;
+pub mod run;
+pub mod statement;
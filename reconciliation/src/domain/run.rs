@@ -0,0 +1,54 @@
+#[derive(Debug, Clone, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "reconciliation_run_status", rename_all = "lowercase")]
+pub enum Status {
+    Running,
+    Completed,
+    Failed,
+}
+
+impl AsRef<str> for Status {
+    fn as_ref(&self) -> &str {
+        match self {
+            Status::Running => "running",
+            Status::Completed => "completed",
+            Status::Failed => "failed",
+        }
+    }
+}
+
+/// Run is the summary of one reconciliation over the window `[window_start, window_end)`.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct Run {
+    pub id: uuid::Uuid,
+    /// source names where the statement lines came from, e.g. `psp` or an imported file.
+    pub source: String,
+    pub window_start: chrono::DateTime<chrono::Utc>,
+    pub window_end: chrono::DateTime<chrono::Utc>,
+    pub status: Status,
+    pub matched_count: i64,
+    pub mismatched_count: i64,
+    pub failure_reason: Option<String>,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl Run {
+    pub fn new(
+        source: impl Into<String>,
+        window_start: chrono::DateTime<chrono::Utc>,
+        window_end: chrono::DateTime<chrono::Utc>,
+    ) -> Self {
+        Run {
+            id: uuid::Uuid::new_v4(),
+            source: source.into(),
+            window_start,
+            window_end,
+            status: Status::Running,
+            matched_count: 0,
+            mismatched_count: 0,
+            failure_reason: None,
+            started_at: chrono::Utc::now(),
+            finished_at: None,
+        }
+    }
+}
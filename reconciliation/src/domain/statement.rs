@@ -0,0 +1,224 @@
+use common::money::Money;
+use std::str::FromStr;
+
+/// Status is the outcome of a transaction as reported by the counterparty.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Status {
+    Pending,
+    Succeeded,
+    Failed,
+}
+
+impl AsRef<str> for Status {
+    fn as_ref(&self) -> &str {
+        match self {
+            Status::Pending => "pending",
+            Status::Succeeded => "succeeded",
+            Status::Failed => "failed",
+        }
+    }
+}
+
+impl FromStr for Status {
+    type Err = anyhow::Error;
+
+    /// from_str accepts the spellings PSPs and banks commonly use in statement exports.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "pending" | "pdng" => Ok(Status::Pending),
+            "succeeded" | "success" | "settled" | "booked" | "book" | "paid" => {
+                Ok(Status::Succeeded)
+            }
+            "failed" | "declined" | "reversed" | "returned" => Ok(Status::Failed),
+            other => anyhow::bail!("unknown statement status {other}"),
+        }
+    }
+}
+
+/// StatementLine is the counterparty's record of a transaction, whether it came from a PSP
+/// API or a statement file. The counterparty echoes the ledger transaction id and idempotency
+/// key it was sent, either of them can be missing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatementLine {
+    /// reference is the counterparty's own identifier for the line.
+    pub reference: String,
+    pub transaction_id: Option<uuid::Uuid>,
+    pub idempotency_key: Option<String>,
+    pub status: Status,
+    pub amount_minor: i64,
+    /// fee_minor is what the counterparty kept back, so `amount_minor + fee_minor` is the gross.
+    pub fee_minor: i64,
+    pub currency: String,
+    pub booked_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Statement is a parsed statement file. `period` is the window the file covers when the file
+/// states it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statement {
+    pub period: Option<(chrono::DateTime<chrono::Utc>, chrono::DateTime<chrono::Utc>)>,
+    pub lines: Vec<StatementLine>,
+}
+
+impl Statement {
+    /// window is the stated period or, failing that, the span of the booking times.
+    pub fn window(&self) -> Option<(chrono::DateTime<chrono::Utc>, chrono::DateTime<chrono::Utc>)> {
+        if self.period.is_some() {
+            return self.period;
+        }
+
+        let from = self.lines.iter().map(|line| line.booked_at).min()?;
+        let to = self.lines.iter().map(|line| line.booked_at).max()?;
+        Some((from, to + chrono::Duration::seconds(1)))
+    }
+}
+
+/// parse_minor_units parses a decimal amount such as `1,050.5` or `-3.00` into minor units of
+/// the currency. Amounts with more decimal places than the currency has are rejected rather
+/// than rounded.
+pub fn parse_minor_units(amount: &str, currency: &str) -> anyhow::Result<i64> {
+    let cleaned: String = amount.trim().chars().filter(|c| *c != ',').collect();
+    let cleaned = cleaned.strip_prefix('+').unwrap_or(&cleaned);
+
+    match Money::parse(cleaned, currency) {
+        Ok(money) => Ok(money.amount_minor()),
+        Err(e) => anyhow::bail!("invalid amount {amount}: {e}"),
+    }
+}
+
+/// parse_reference reads an echoed reference, which is our transaction id when it is a UUID.
+/// The raw value is kept as idempotency key either way.
+pub fn parse_reference(reference: &str) -> (Option<uuid::Uuid>, Option<String>) {
+    let reference = reference.trim();
+    if reference.is_empty() || reference.eq_ignore_ascii_case("NOTPROVIDED") {
+        return (None, None);
+    }
+
+    (
+        uuid::Uuid::parse_str(reference).ok(),
+        Some(reference.to_string()),
+    )
+}
+
+/// parse_timestamp accepts RFC 3339 timestamps, or naive dates and times which are taken as UTC.
+pub fn parse_timestamp(value: &str) -> anyhow::Result<chrono::DateTime<chrono::Utc>> {
+    if let Ok(timestamp) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Ok(timestamp.to_utc());
+    }
+    if let Ok(timestamp) = chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S") {
+        return Ok(timestamp.and_utc());
+    }
+
+    match chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        Ok(date) => Ok(date.and_time(chrono::NaiveTime::MIN).and_utc()),
+        Err(_) => anyhow::bail!("unsupported timestamp {value}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_minor_units() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            amount: &'static str,
+            currency: &'static str,
+            expected: Option<i64>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "two decimal places",
+                amount: "10.50",
+                currency: "USD",
+                expected: Some(1050),
+            },
+            TestCase {
+                name: "one decimal place",
+                amount: "10.5",
+                currency: "USD",
+                expected: Some(1050),
+            },
+            TestCase {
+                name: "whole amount with thousands separator",
+                amount: " 1,000 ",
+                currency: "USD",
+                expected: Some(100000),
+            },
+            TestCase {
+                name: "negative amount",
+                amount: "-0.07",
+                currency: "USD",
+                expected: Some(-7),
+            },
+            TestCase {
+                name: "explicitly positive amount",
+                amount: "+3.00",
+                currency: "EUR",
+                expected: Some(300),
+            },
+            TestCase {
+                name: "currency without minor unit",
+                amount: "1,050",
+                currency: "JPY",
+                expected: Some(1050),
+            },
+            TestCase {
+                name: "currency with three decimal places",
+                amount: "1.005",
+                currency: "KWD",
+                expected: Some(1005),
+            },
+            TestCase {
+                name: "too many decimal places",
+                amount: "1.005",
+                currency: "USD",
+                expected: None,
+            },
+            TestCase {
+                name: "decimals on currency without minor unit",
+                amount: "10.5",
+                currency: "JPY",
+                expected: None,
+            },
+            TestCase {
+                name: "unknown currency",
+                amount: "10.50",
+                currency: "XYZ",
+                expected: None,
+            },
+            TestCase {
+                name: "not a number",
+                amount: "ten",
+                currency: "USD",
+                expected: None,
+            },
+        ];
+
+        for test_case in test_cases {
+            // act
+            let result = parse_minor_units(test_case.amount, test_case.currency);
+
+            // assert
+            assert_eq!(result.ok(), test_case.expected, "{}", test_case.name);
+        }
+    }
+
+    #[test]
+    fn test_parse_reference() {
+        let id = uuid::Uuid::new_v4();
+
+        assert_eq!(
+            parse_reference(id.to_string().as_str()),
+            (Some(id), Some(id.to_string()))
+        );
+        assert_eq!(
+            parse_reference("order-42"),
+            (None, Some("order-42".to_string()))
+        );
+        assert_eq!(parse_reference("NOTPROVIDED"), (None, None));
+    }
+}
@@ -0,0 +1,250 @@
+use crate::domain::statement::{
+    Statement, StatementLine, Status, parse_minor_units, parse_reference, parse_timestamp,
+};
+use roxmltree::Node;
+
+/// parse reads an ISO 20022 camt.053 bank-to-customer statement. Elements are matched on
+/// their local name, so any camt.053.001.xx version is accepted.
+///
+/// Every entry (`Ntry`) becomes a line, or one line per transaction when a batch booking lists
+/// several `TxDtls`. The end-to-end id is the reference we sent, the bank's own reference is
+/// kept as the line reference. Debits are negative, reversals are failed lines and entries
+/// which are not booked yet are pending.
+pub fn parse(content: &str) -> anyhow::Result<Statement> {
+    let document = match roxmltree::Document::parse(content) {
+        Ok(document) => document,
+        Err(e) => anyhow::bail!("failed to parse camt.053 statement: {e}"),
+    };
+
+    let statements: Vec<Node> = document
+        .descendants()
+        .filter(|node| node.has_tag_name("Stmt"))
+        .collect();
+    if statements.is_empty() {
+        anyhow::bail!("camt.053 document has no Stmt element")
+    }
+
+    let mut periods = Vec::new();
+    let mut lines = Vec::new();
+    for statement in statements {
+        let statement_id = text(child_path(statement, &["Id"])).unwrap_or_default();
+        let from = text(child_path(statement, &["FrToDt", "FrDtTm"]));
+        let to = text(child_path(statement, &["FrToDt", "ToDtTm"]));
+        periods.push(match (from, to) {
+            (Some(from), Some(to)) => Some((parse_timestamp(from)?, parse_timestamp(to)?)),
+            _ => None,
+        });
+
+        for (index, entry) in children(statement, "Ntry").enumerate() {
+            match parse_entry(entry) {
+                Ok(entry_lines) => lines.extend(entry_lines),
+                Err(e) => anyhow::bail!(
+                    "invalid entry {} in statement {statement_id}: {e}",
+                    index + 1
+                ),
+            }
+        }
+    }
+
+    // the period is only known when every statement in the file states it
+    let period = match periods.iter().all(Option::is_some) {
+        true => {
+            let from = periods.iter().flatten().map(|(from, _)| *from).min();
+            let to = periods.iter().flatten().map(|(_, to)| *to).max();
+            from.zip(to)
+        }
+        false => None,
+    };
+
+    Ok(Statement { period, lines })
+}
+
+fn parse_entry(entry: Node) -> anyhow::Result<Vec<StatementLine>> {
+    let (amount_minor, currency) = parse_amount(child_path(entry, &["Amt"]))?;
+    let debit = match text(child_path(entry, &["CdtDbtInd"])) {
+        Some("CRDT") => false,
+        Some("DBIT") => true,
+        other => anyhow::bail!("invalid credit/debit indicator {other:?}"),
+    };
+    let reversal = text(child_path(entry, &["RvslInd"])) == Some("true");
+    // Sts is a plain code up to camt.053.001.07 and wrapped in Cd from .08 on
+    let status = match text(child_path(entry, &["Sts", "Cd"]))
+        .or_else(|| text(child_path(entry, &["Sts"])))
+    {
+        _ if reversal => Status::Failed,
+        Some("BOOK") => Status::Succeeded,
+        Some("PDNG") | Some("INFO") => Status::Pending,
+        other => anyhow::bail!("invalid entry status {other:?}"),
+    };
+    let booked_at = match text(child_path(entry, &["BookgDt", "DtTm"]))
+        .or_else(|| text(child_path(entry, &["BookgDt", "Dt"])))
+        .or_else(|| text(child_path(entry, &["ValDt", "Dt"])))
+    {
+        Some(booked_at) => parse_timestamp(booked_at)?,
+        None => anyhow::bail!("entry has no booking date"),
+    };
+    let entry_reference = text(child_path(entry, &["AcctSvcrRef"])).unwrap_or_default();
+    let signed = |amount_minor: i64| if debit { -amount_minor } else { amount_minor };
+
+    let transactions: Vec<Node> = child_path(entry, &["NtryDtls"])
+        .map(|details| children(details, "TxDtls").collect())
+        .unwrap_or_default();
+    if transactions.len() <= 1 {
+        let end_to_end_id = transactions
+            .first()
+            .and_then(|transaction| text(child_path(*transaction, &["Refs", "EndToEndId"])))
+            .unwrap_or_default();
+        let (transaction_id, idempotency_key) = parse_reference(end_to_end_id);
+        let fee_minor = match transactions.first() {
+            Some(transaction) if child_path(*transaction, &["Chrgs"]).is_some() => {
+                parse_charges(*transaction)?
+            }
+            _ => parse_charges(entry)?,
+        };
+
+        return Ok(vec![StatementLine {
+            reference: entry_reference.to_string(),
+            transaction_id,
+            idempotency_key,
+            status,
+            amount_minor: signed(amount_minor),
+            fee_minor,
+            currency,
+            booked_at,
+        }]);
+    }
+
+    // batch booking, the entry amount is the sum of the transactions
+    let mut lines = Vec::with_capacity(transactions.len());
+    for transaction in transactions {
+        let amount = child_path(transaction, &["AmtDtls", "TxAmt", "Amt"])
+            .or_else(|| child_path(transaction, &["Amt"]));
+        let (amount_minor, currency) = parse_amount(amount)?;
+        let (transaction_id, idempotency_key) = parse_reference(
+            text(child_path(transaction, &["Refs", "EndToEndId"])).unwrap_or_default(),
+        );
+        let reference =
+            text(child_path(transaction, &["Refs", "AcctSvcrRef"])).unwrap_or(entry_reference);
+
+        lines.push(StatementLine {
+            reference: reference.to_string(),
+            transaction_id,
+            idempotency_key,
+            status,
+            amount_minor: signed(amount_minor),
+            fee_minor: parse_charges(transaction)?,
+            currency,
+            booked_at,
+        });
+    }
+
+    Ok(lines)
+}
+
+fn parse_amount(amount: Option<Node>) -> anyhow::Result<(i64, String)> {
+    let amount = match amount {
+        Some(amount) => amount,
+        None => anyhow::bail!("missing amount"),
+    };
+    let currency = match amount.attribute("Ccy") {
+        Some(currency) => currency.to_uppercase(),
+        None => anyhow::bail!("amount has no currency"),
+    };
+
+    Ok((
+        parse_minor_units(amount.text().unwrap_or_default(), &currency)?,
+        currency,
+    ))
+}
+
+/// parse_charges reads the charges the bank deducted, stated either on the transaction or on
+/// the entry, as their total or as separate records.
+fn parse_charges(node: Node) -> anyhow::Result<i64> {
+    let charges = match child_path(node, &["Chrgs"]) {
+        Some(charges) => charges,
+        None => return Ok(0),
+    };
+    if let Some(total) = child_path(charges, &["TtlChrgsAndTaxAmt"]) {
+        return Ok(parse_amount(Some(total))?.0);
+    }
+
+    children(charges, "Rcrd").try_fold(0, |fee_minor, record| {
+        Ok(fee_minor + parse_amount(child_path(record, &["Amt"]))?.0)
+    })
+}
+
+fn children<'a, 'input>(
+    node: Node<'a, 'input>,
+    name: &'static str,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children()
+        .filter(move |child| child.has_tag_name(name))
+}
+
+fn child_path<'a, 'input>(
+    node: Node<'a, 'input>,
+    path: &[&'static str],
+) -> Option<Node<'a, 'input>> {
+    path.iter()
+        .try_fold(node, |node, name| children(node, name).next())
+}
+
+fn text<'a>(node: Option<Node<'a, '_>>) -> Option<&'a str> {
+    node.and_then(|node| node.text())
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::import::tests::assert_golden;
+
+    #[test]
+    fn successfully_parse_statement() {
+        // arrange
+        let content = include_str!("../../testdata/statements/bank.camt053.xml");
+
+        // act
+        let statement = parse(content).unwrap();
+
+        // assert
+        assert_golden("bank.camt053.golden", &statement);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            content: &'static str,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "not xml",
+                content: "reference,amount",
+            },
+            TestCase {
+                name: "no statement",
+                content: "<Document><BkToCstmrStmt/></Document>",
+            },
+            TestCase {
+                name: "entry without currency",
+                content: "<Document><BkToCstmrStmt><Stmt><Ntry><Amt>1.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Sts>BOOK</Sts><BookgDt><Dt>2025-10-24</Dt></BookgDt></Ntry></Stmt></BkToCstmrStmt></Document>",
+            },
+            TestCase {
+                name: "entry without booking date",
+                content: "<Document><BkToCstmrStmt><Stmt><Ntry><Amt Ccy=\"EUR\">1.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Sts>BOOK</Sts></Ntry></Stmt></BkToCstmrStmt></Document>",
+            },
+        ];
+
+        for test_case in test_cases {
+            // act
+            let result = parse(test_case.content);
+
+            // assert
+            assert!(result.is_err(), "{}", test_case.name);
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use crate::domain::statement::{
+    Statement, StatementLine, Status, parse_minor_units, parse_timestamp,
+};
+use std::str::FromStr;
+
+/// CsvMapping maps statement-line fields to the columns of a CSV export. Optional columns
+/// which are not mapped, or not in the file, are left empty; a missing status column means
+/// every line is a settled one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvMapping {
+    pub delimiter: u8,
+    pub reference: String,
+    pub transaction_id: Option<String>,
+    pub idempotency_key: Option<String>,
+    pub status: Option<String>,
+    pub amount: String,
+    pub fee: Option<String>,
+    pub currency: String,
+    pub booked_at: String,
+    /// amount_in_minor_units is set when the amount and fee columns hold minor units, e.g. `1050`.
+    pub amount_in_minor_units: bool,
+}
+
+impl Default for CsvMapping {
+    fn default() -> Self {
+        CsvMapping {
+            delimiter: b',',
+            reference: "reference".to_string(),
+            transaction_id: Some("transaction_id".to_string()),
+            idempotency_key: Some("idempotency_key".to_string()),
+            status: Some("status".to_string()),
+            amount: "amount".to_string(),
+            fee: Some("fee".to_string()),
+            currency: "currency".to_string(),
+            booked_at: "booked_at".to_string(),
+            amount_in_minor_units: false,
+        }
+    }
+}
+
+impl FromStr for CsvMapping {
+    type Err = anyhow::Error;
+
+    /// from_str reads overrides of the default mapping as `field=column` pairs separated by
+    /// commas, e.g. `reference=Charge ID,amount=Gross,status=`. An empty column unmaps an
+    /// optional field. The delimiter is a single character, `comma` or `tab`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut mapping = CsvMapping::default();
+        for pair in s.split(',').filter(|pair| !pair.trim().is_empty()) {
+            let (field, column) = match pair.split_once('=') {
+                Some((field, column)) => (field.trim(), column.trim().to_string()),
+                None => anyhow::bail!("invalid column mapping {pair}, expected field=column"),
+            };
+            let optional = (!column.is_empty()).then(|| column.clone());
+
+            match field {
+                "delimiter" => match (column.as_str(), column.as_bytes()) {
+                    ("comma", _) => mapping.delimiter = b',',
+                    ("tab", _) => mapping.delimiter = b'\t',
+                    (_, [delimiter]) => mapping.delimiter = *delimiter,
+                    _ => anyhow::bail!("delimiter must be a single character, got {column}"),
+                },
+                "reference" => mapping.reference = column,
+                "transaction_id" => mapping.transaction_id = optional,
+                "idempotency_key" => mapping.idempotency_key = optional,
+                "status" => mapping.status = optional,
+                "amount" => mapping.amount = column,
+                "fee" => mapping.fee = optional,
+                "currency" => mapping.currency = column,
+                "booked_at" => mapping.booked_at = column,
+                "amount_in_minor_units" => mapping.amount_in_minor_units = column.parse()?,
+                other => anyhow::bail!("unknown statement field {other}"),
+            }
+        }
+
+        Ok(mapping)
+    }
+}
+
+/// parse reads a CSV statement with a header row. CSV exports do not state their period, so
+/// the statement window is derived from the booking times.
+pub fn parse(content: &str, mapping: &CsvMapping) -> anyhow::Result<Statement> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(mapping.delimiter)
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+
+    let headers = match reader.headers() {
+        Ok(headers) => headers.clone(),
+        Err(e) => anyhow::bail!("failed to read CSV header: {e}"),
+    };
+    let required = |column: &str| match headers.iter().position(|h| h == column) {
+        Some(index) => Ok(index),
+        None => anyhow::bail!("CSV statement has no {column} column"),
+    };
+    let optional = |column: &Option<String>| {
+        column
+            .as_deref()
+            .and_then(|column| headers.iter().position(|h| h == column))
+    };
+
+    let reference = required(mapping.reference.as_str())?;
+    let amount = required(mapping.amount.as_str())?;
+    let currency = required(mapping.currency.as_str())?;
+    let booked_at = required(mapping.booked_at.as_str())?;
+    let transaction_id = optional(&mapping.transaction_id);
+    let idempotency_key = optional(&mapping.idempotency_key);
+    let status = optional(&mapping.status);
+    let fee = optional(&mapping.fee);
+
+    let mut lines = Vec::new();
+    for (row, record) in reader.records().enumerate() {
+        // the header is line 1
+        let line = row + 2;
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => anyhow::bail!("failed to read CSV line {line}: {e}"),
+        };
+        let field = |index: usize| record.get(index).unwrap_or_default();
+        let optional_field =
+            |index: Option<usize>| index.map(&field).filter(|value| !value.is_empty());
+
+        let currency = field(currency).to_uppercase();
+        let minor_units = |value: &str| match mapping.amount_in_minor_units {
+            true => value.parse::<i64>().map_err(anyhow::Error::from),
+            false => parse_minor_units(value, &currency),
+        };
+
+        let amount_minor = match minor_units(field(amount)) {
+            Ok(amount_minor) => amount_minor,
+            Err(e) => anyhow::bail!("invalid amount on CSV line {line}: {e}"),
+        };
+        let fee_minor = match optional_field(fee).map(minor_units) {
+            Some(Ok(fee_minor)) => fee_minor,
+            Some(Err(e)) => anyhow::bail!("invalid fee on CSV line {line}: {e}"),
+            None => 0,
+        };
+        let transaction_id = match optional_field(transaction_id).map(uuid::Uuid::parse_str) {
+            Some(Ok(id)) => Some(id),
+            Some(Err(e)) => anyhow::bail!("invalid transaction id on CSV line {line}: {e}"),
+            None => None,
+        };
+        let status = match optional_field(status).map(Status::from_str) {
+            Some(Ok(status)) => status,
+            Some(Err(e)) => anyhow::bail!("invalid status on CSV line {line}: {e}"),
+            None => Status::Succeeded,
+        };
+        let booked_at = match parse_timestamp(field(booked_at)) {
+            Ok(booked_at) => booked_at,
+            Err(e) => anyhow::bail!("invalid booking time on CSV line {line}: {e}"),
+        };
+
+        lines.push(StatementLine {
+            reference: field(reference).to_string(),
+            transaction_id,
+            idempotency_key: optional_field(idempotency_key).map(str::to_string),
+            status,
+            amount_minor,
+            fee_minor,
+            currency,
+            booked_at,
+        });
+    }
+
+    Ok(Statement {
+        period: None,
+        lines,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::import::tests::assert_golden;
+
+    #[test]
+    fn successfully_parse_default_mapping() {
+        // arrange
+        let content = include_str!("../../testdata/statements/psp_export.csv");
+
+        // act
+        let statement = parse(content, &CsvMapping::default()).unwrap();
+
+        // assert
+        assert_golden("psp_export.golden", &statement);
+    }
+
+    #[test]
+    fn successfully_parse_custom_mapping() {
+        // arrange
+        let content = include_str!("../../testdata/statements/bank_export.csv");
+        let mapping: CsvMapping = "delimiter=;,reference=Bank Ref,transaction_id=,idempotency_key=Merchant Ref,status=,amount=Amount (cents),currency=Ccy,booked_at=Value Date,amount_in_minor_units=true"
+            .parse()
+            .unwrap();
+
+        // act
+        let statement = parse(content, &mapping).unwrap();
+
+        // assert
+        assert_golden("bank_export.golden", &statement);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            content: &'static str,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "missing amount column",
+                content: "reference,currency,booked_at\nr1,USD,2025-10-24\n",
+            },
+            TestCase {
+                name: "invalid amount",
+                content: "reference,amount,currency,booked_at\nr1,ten,USD,2025-10-24\n",
+            },
+            TestCase {
+                name: "invalid booking time",
+                content: "reference,amount,currency,booked_at\nr1,10.00,USD,yesterday\n",
+            },
+            TestCase {
+                name: "unknown status",
+                content: "reference,amount,currency,booked_at,status\nr1,10.00,USD,2025-10-24,disputed\n",
+            },
+        ];
+
+        for test_case in test_cases {
+            // act
+            let result = parse(test_case.content, &CsvMapping::default());
+
+            // assert
+            assert!(result.is_err(), "{}", test_case.name);
+        }
+    }
+
+    #[test]
+    fn error_when_mapping_is_invalid() {
+        assert!("amount".parse::<CsvMapping>().is_err());
+        assert!("charges=Fee".parse::<CsvMapping>().is_err());
+        assert!("delimiter=||".parse::<CsvMapping>().is_err());
+        assert_eq!(
+            "delimiter=tab".parse::<CsvMapping>().unwrap().delimiter,
+            b'\t'
+        );
+    }
+}
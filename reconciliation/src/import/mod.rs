@@ -0,0 +1,157 @@
+use crate::domain::statement::{Statement, StatementLine};
+use crate::psp::PaymentServiceProvider;
+use async_trait::async_trait;
+use std::str::FromStr;
+
+pub mod camt053;
+pub mod csv;
+
+/// Format is the layout of a statement file.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Format {
+    Csv(csv::CsvMapping),
+    Camt053,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Format::Csv(csv::CsvMapping::default())),
+            "camt053" | "camt.053" => Ok(Format::Camt053),
+            other => anyhow::bail!("unknown statement format {other}, expected csv or camt053"),
+        }
+    }
+}
+
+impl AsRef<str> for Format {
+    fn as_ref(&self) -> &str {
+        match self {
+            Format::Csv(_) => "csv",
+            Format::Camt053 => "camt053",
+        }
+    }
+}
+
+pub fn parse(content: &str, format: &Format) -> anyhow::Result<Statement> {
+    match format {
+        Format::Csv(mapping) => csv::parse(content, mapping),
+        Format::Camt053 => camt053::parse(content),
+    }
+}
+
+/// StatementFile serves the lines of an imported statement to the reconciliation service,
+/// for counterparties which have no API.
+#[derive(Debug, Clone)]
+pub struct StatementFile {
+    source: String,
+    statement: Statement,
+}
+
+impl StatementFile {
+    pub fn new(source: impl Into<String>, statement: Statement) -> Self {
+        Self {
+            source: source.into(),
+            statement,
+        }
+    }
+
+    pub fn statement(&self) -> &Statement {
+        &self.statement
+    }
+}
+
+#[async_trait]
+impl PaymentServiceProvider for StatementFile {
+    fn source(&self) -> &str {
+        self.source.as_str()
+    }
+
+    async fn settled_transactions(
+        &self,
+        from: chrono::DateTime<chrono::Utc>,
+        to: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<Vec<StatementLine>> {
+        Ok(self
+            .statement
+            .lines
+            .iter()
+            .filter(|line| line.booked_at >= from && line.booked_at < to)
+            .cloned()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    fn render(statement: &Statement) -> String {
+        let mut rendered = match statement.period {
+            Some((from, to)) => format!("period {} {}\n", from.to_rfc3339(), to.to_rfc3339()),
+            None => "period -\n".to_string(),
+        };
+        for line in &statement.lines {
+            rendered.push_str(
+                format!(
+                    "{}|{}|{}|{}|{}|{}|{}|{}\n",
+                    line.reference,
+                    line.transaction_id
+                        .map(|id| id.to_string())
+                        .unwrap_or_default(),
+                    line.idempotency_key.as_deref().unwrap_or_default(),
+                    line.status.as_ref(),
+                    line.amount_minor,
+                    line.fee_minor,
+                    line.currency,
+                    line.booked_at.to_rfc3339(),
+                )
+                .as_str(),
+            );
+        }
+
+        rendered
+    }
+
+    /// assert_golden compares the parsed statement with `testdata/statements/<name>`. Run the
+    /// tests with `UPDATE_GOLDEN=1` to rewrite the golden files after an intended change.
+    pub(crate) fn assert_golden(name: &str, statement: &Statement) {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("testdata/statements")
+            .join(name);
+        let rendered = render(statement);
+
+        if std::env::var("UPDATE_GOLDEN").is_ok() {
+            std::fs::write(&path, rendered).unwrap();
+            return;
+        }
+
+        let golden = std::fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()));
+        assert_eq!(rendered, golden, "{name} is out of date");
+    }
+
+    #[tokio::test]
+    async fn successfully_serve_lines_booked_in_window() {
+        // arrange
+        let content = include_str!("../../testdata/statements/psp_export.csv");
+        let file = StatementFile::new(
+            "csv:psp_export.csv",
+            parse(content, &"csv".parse().unwrap()).unwrap(),
+        );
+        let (from, to) = file.statement().window().unwrap();
+
+        // act
+        let all = file.settled_transactions(from, to).await.unwrap();
+        let none = file
+            .settled_transactions(from - chrono::Duration::days(1), from)
+            .await
+            .unwrap();
+
+        // assert
+        assert_eq!(file.source(), "csv:psp_export.csv");
+        assert_eq!(all.len(), file.statement().lines.len());
+        assert!(none.is_empty());
+    }
+}
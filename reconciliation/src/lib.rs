@@ -0,0 +1,23 @@
+pub mod api;
+pub mod domain;
+pub mod import;
+pub mod psp;
+pub mod repo;
+pub mod service;
+
+pub const DEFAULT_PSP_TIMEOUT_MILLISECONDS: u64 = 10_000;
+/// Runs are scheduled hourly and the first run covers the last hour.
+pub const DEFAULT_RUN_INTERVAL_SECONDS: u64 = 3600;
+pub const DEFAULT_RUN_WINDOW_SECONDS: u64 = 3600;
+/// Windows close 15 minutes in the past so that transactions have time to settle at the PSP.
+pub const DEFAULT_SETTLEMENT_DELAY_SECONDS: u64 = 900;
+/// Matches without a reference accept bookings up to three days from the ledger transaction.
+pub const DEFAULT_MATCH_DATE_WINDOW_SECONDS: i64 = 259_200;
+pub const DEFAULT_MAX_SUM_CANDIDATES: usize = 20;
+/// Unresolved discrepancies are listed as exceptions once they are a day old.
+pub const DEFAULT_EXCEPTION_AFTER_SECONDS: i64 = 86_400;
+/// Ledger transactions still pending an hour after the PSP settled or failed them are corrected.
+pub const DEFAULT_PENDING_TIMEOUT_SECONDS: i64 = 3600;
+pub const DEFAULT_WRITER_MAX_CONN: u32 = 2;
+pub const DEFAULT_READER_MAX_CONN: u32 = 4;
+pub const DEFAULT_TIMEOUT_SECONDS: u64 = 5;
@@ -0,0 +1,251 @@
+use common::{database, kafka, shutdown};
+use ledger::repo::PgLedgerRepository;
+use reconciliation::domain::matching::{MatchRules, parse_tolerances};
+use reconciliation::import::{Format, StatementFile};
+use reconciliation::psp::http::HttpPsp;
+use reconciliation::repo::PgReconciliationRepository;
+use reconciliation::service::ReconciliationService;
+use reconciliation::{
+    DEFAULT_EXCEPTION_AFTER_SECONDS, DEFAULT_MATCH_DATE_WINDOW_SECONDS,
+    DEFAULT_PENDING_TIMEOUT_SECONDS, DEFAULT_PSP_TIMEOUT_MILLISECONDS, DEFAULT_READER_MAX_CONN,
+    DEFAULT_RUN_INTERVAL_SECONDS, DEFAULT_RUN_WINDOW_SECONDS, DEFAULT_SETTLEMENT_DELAY_SECONDS,
+    DEFAULT_TIMEOUT_SECONDS, DEFAULT_WRITER_MAX_CONN,
+};
+use reconciliation_proto::reconciliation_v1::{
+    FILE_DESCRIPTOR_SET, reconciliation_exceptions_server,
+};
+use std::collections::BTreeMap;
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+use tonic::codegen::tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::Server;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    // setup ledger database, runs are recorded next to the transactions they reconcile
+    let database_config = database::Config {
+        reader_url: env::var("READER_DATABASE_URL").expect("READER_DATABASE_URL must be set"),
+        reader_max_connections: DEFAULT_READER_MAX_CONN,
+        writer_url: env::var("WRITER_DATABASE_URL").expect("WRITER_DATABASE_URL must be set"),
+        writer_max_connections: DEFAULT_WRITER_MAX_CONN,
+        timeout_in_secs: DEFAULT_TIMEOUT_SECONDS,
+    };
+    let db = database::Database::new(&database_config)
+        .await
+        .expect("failed to create database");
+
+    // setup repo layer
+    let ledger = PgLedgerRepository::new(db.clone());
+    let repo = PgReconciliationRepository::new(db);
+
+    // setup kafka
+    let kafka_config = kafka::Config {
+        brokers: env::var("KAFKA_BROKERS").expect("KAFKA_BROKERS must be set"),
+        group_id: env::var("KAFKA_GROUP_ID").unwrap_or("reconciliation".to_string()),
+    };
+    let publisher = kafka::KafkaPublisher::new(&kafka_config)?;
+
+    // setup matching rules, e.g. RECONCILIATION_AMOUNT_TOLERANCES=USD=1,JPY=0
+    let rules = MatchRules {
+        tolerances: parse_tolerances(
+            env::var("RECONCILIATION_AMOUNT_TOLERANCES")
+                .unwrap_or_default()
+                .as_str(),
+        )?,
+        date_window: chrono::Duration::seconds(
+            env::var("RECONCILIATION_DATE_WINDOW_SECONDS")
+                .map(|v| v.parse::<i64>())
+                .unwrap_or(Ok(DEFAULT_MATCH_DATE_WINDOW_SECONDS))?,
+        ),
+        ..MatchRules::default()
+    };
+    let exception_after = chrono::Duration::seconds(
+        env::var("RECONCILIATION_EXCEPTION_AFTER_SECONDS")
+            .map(|v| v.parse::<i64>())
+            .unwrap_or(Ok(DEFAULT_EXCEPTION_AFTER_SECONDS))?,
+    );
+    let pending_timeout = chrono::Duration::seconds(
+        env::var("RECONCILIATION_PENDING_TIMEOUT_SECONDS")
+            .map(|v| v.parse::<i64>())
+            .unwrap_or(Ok(DEFAULT_PENDING_TIMEOUT_SECONDS))?,
+    );
+
+    // `reconciliation import <csv|camt053> <path> [column mapping]` reconciles a statement
+    // file once, otherwise the PSP is reconciled on a schedule
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Some("import") = args.first().map(String::as_str) {
+        let (format, path) = match (args.get(1), args.get(2)) {
+            (Some(format), Some(path)) => (format.parse::<Format>()?, path),
+            _ => {
+                anyhow::bail!("usage: reconciliation import <csv|camt053> <path> [column mapping]")
+            }
+        };
+        let format = match (format, args.get(3)) {
+            (Format::Csv(_), Some(mapping)) => Format::Csv(mapping.parse()?),
+            (format, _) => format,
+        };
+
+        let statement = reconciliation::import::parse(&std::fs::read_to_string(path)?, &format)?;
+        let (from, to) = match statement.window() {
+            Some(window) => window,
+            None => anyhow::bail!("statement {path} has no lines"),
+        };
+        let file_name = std::path::Path::new(path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or(path.clone());
+        let source = format!("{}:{file_name}", format.as_ref());
+        let service = ReconciliationService::new(
+            ledger,
+            repo,
+            StatementFile::new(source, statement),
+            publisher,
+        )
+        .with_match_rules(rules)
+        .with_pending_timeout(pending_timeout);
+
+        let run = service.run(from, to).await?;
+        tracing::info!(
+            run_id = %run.id,
+            source = run.source,
+            matched = run.matched_count,
+            mismatched = run.mismatched_count,
+            "completed statement reconciliation"
+        );
+        auto_correct(&service).await;
+        report_exceptions(&service, exception_after).await;
+        return Ok(());
+    }
+
+    // setup psp client
+    let psp = HttpPsp::new(
+        env::var("PSP_URL").expect("PSP_URL must be set"),
+        Duration::from_millis(DEFAULT_PSP_TIMEOUT_MILLISECONDS),
+    )?;
+
+    // setup service
+    let service = Arc::new(
+        ReconciliationService::new(ledger, repo, psp, publisher)
+            .with_match_rules(rules)
+            .with_pending_timeout(pending_timeout),
+    );
+
+    // setup schedule
+    let interval = env::var("RECONCILIATION_INTERVAL_SECONDS")
+        .map(|v| v.parse::<u64>())
+        .unwrap_or(Ok(DEFAULT_RUN_INTERVAL_SECONDS))?;
+    let window = env::var("RECONCILIATION_WINDOW_SECONDS")
+        .map(|v| v.parse::<i64>())
+        .unwrap_or(Ok(DEFAULT_RUN_WINDOW_SECONDS as i64))?;
+    let settlement_delay = env::var("RECONCILIATION_SETTLEMENT_DELAY_SECONDS")
+        .map(|v| v.parse::<i64>())
+        .unwrap_or(Ok(DEFAULT_SETTLEMENT_DELAY_SECONDS as i64))?;
+
+    let scheduled = async {
+        let mut ticker = tokio::time::interval(Duration::from_secs(interval));
+        let shutdown = shutdown::shutdown_signal();
+        tokio::pin!(shutdown);
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => return,
+                _ = ticker.tick() => {
+                    let to = chrono::Utc::now() - chrono::Duration::seconds(settlement_delay);
+                    match service.run_next(to, chrono::Duration::seconds(window)).await {
+                        Ok(Some(run)) => {
+                            tracing::info!(
+                                run_id = %run.id,
+                                window_start = %run.window_start,
+                                window_end = %run.window_end,
+                                matched = run.matched_count,
+                                mismatched = run.mismatched_count,
+                                "completed reconciliation run"
+                            );
+                            auto_correct(service.as_ref()).await;
+                            report_exceptions(service.as_ref(), exception_after).await;
+                        }
+                        Ok(None) => {}
+                        Err(e) => tracing::error!("reconciliation run failed: {e}"),
+                    }
+                }
+            }
+        }
+    };
+
+    // add reflection
+    let reflection_service = tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(FILE_DESCRIPTOR_SET)
+        .build_v1()?;
+
+    // setup grpc server for exception management
+    let port: u32 = env::var("PORT").unwrap_or("8000".to_string()).parse()?;
+    let address = format!("0.0.0.0:{}", port);
+    let listener = tokio::net::TcpListener::bind(address)
+        .await
+        .expect("Failed to create TCP listener ❌");
+    let server = Server::builder()
+        .add_service(reflection_service)
+        // add reconciliation service to reconciliation exceptions server
+        .add_service(
+            reconciliation_exceptions_server::ReconciliationExceptionsServer::from_arc(
+                service.clone(),
+            ),
+        )
+        .serve_with_incoming_shutdown(
+            TcpListenerStream::new(listener),
+            shutdown::shutdown_signal(),
+        );
+
+    let (_, served) = tokio::join!(scheduled, server);
+    served?;
+
+    Ok(())
+}
+
+/// auto_correct applies the corrections which are safe without a human, see
+/// `ReconciliationService::auto_correct`.
+async fn auto_correct<L, R, P, B>(service: &ReconciliationService<L, R, P, B>)
+where
+    L: ledger::repo::LedgerReader,
+    R: reconciliation::repo::ReconciliationRepository,
+    P: reconciliation::psp::PaymentServiceProvider,
+    B: kafka::Publisher,
+{
+    match service.auto_correct(chrono::Utc::now()).await {
+        Ok(0) => {}
+        Ok(corrected) => tracing::info!(corrected, "auto-corrected reconciliation exceptions"),
+        Err(e) => tracing::error!("failed to auto-correct reconciliation exceptions: {e}"),
+    }
+}
+
+/// report_exceptions logs the open exceptions older than `exception_after` per aging bucket.
+async fn report_exceptions<L, R, P, B>(
+    service: &ReconciliationService<L, R, P, B>,
+    exception_after: chrono::Duration,
+) where
+    L: ledger::repo::LedgerReader,
+    R: reconciliation::repo::ReconciliationRepository,
+    P: reconciliation::psp::PaymentServiceProvider,
+    B: kafka::Publisher,
+{
+    let now = chrono::Utc::now();
+    match service.exceptions(now, exception_after).await {
+        Ok(exceptions) if exceptions.is_empty() => {}
+        Ok(exceptions) => {
+            let mut buckets = BTreeMap::new();
+            for exception in &exceptions {
+                *buckets.entry(exception.aging_bucket(now)).or_insert(0) += 1;
+            }
+            for (bucket, count) in buckets {
+                tracing::warn!(
+                    bucket = bucket.as_ref(),
+                    count,
+                    "open reconciliation exceptions"
+                );
+            }
+        }
+        Err(e) => tracing::error!("failed to list reconciliation exceptions: {e}"),
+    }
+}
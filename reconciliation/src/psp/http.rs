@@ -0,0 +1,251 @@
+use crate::domain::statement::{StatementLine, Status};
+use crate::psp::PaymentServiceProvider;
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::time::Duration;
+
+#[derive(Debug, Deserialize)]
+struct HttpSettlementsResponse {
+    transactions: Vec<HttpSettledTransaction>,
+    next_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HttpSettledTransaction {
+    psp_reference: String,
+    transaction_id: Option<uuid::Uuid>,
+    idempotency_key: Option<String>,
+    status: String,
+    amount_minor: i64,
+    #[serde(default)]
+    fee_minor: i64,
+    currency: String,
+    settled_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl TryFrom<HttpSettledTransaction> for StatementLine {
+    type Error = anyhow::Error;
+
+    fn try_from(transaction: HttpSettledTransaction) -> Result<Self, Self::Error> {
+        let status = match transaction.status.as_str() {
+            "pending" => Status::Pending,
+            "succeeded" => Status::Succeeded,
+            "failed" => Status::Failed,
+            other => anyhow::bail!(
+                "unknown PSP status {other} for {}",
+                transaction.psp_reference
+            ),
+        };
+
+        Ok(StatementLine {
+            reference: transaction.psp_reference,
+            transaction_id: transaction.transaction_id,
+            idempotency_key: transaction.idempotency_key,
+            status,
+            amount_minor: transaction.amount_minor,
+            fee_minor: transaction.fee_minor,
+            currency: transaction.currency,
+            booked_at: transaction.settled_at,
+        })
+    }
+}
+
+/// HttpPsp lists settled transactions through the PSP's HTTP API.
+///
+/// The PSP is expected to expose `GET {base_url}/v1/settlements?from=...&to=...[&cursor=...]`
+/// with RFC 3339 timestamps, returning
+/// `{"transactions": [{"psp_reference": "...", "transaction_id": "...", "idempotency_key": "...",
+/// "status": "succeeded", "amount_minor": 100, "fee_minor": 3, "currency": "USD",
+/// "settled_at": "..."}], "next_cursor": "..."}`. Pages are followed until `next_cursor` is
+/// null. `amount_minor` is what the PSP paid out and `fee_minor`, which may be left out, what
+/// it kept.
+#[derive(Debug, Clone)]
+pub struct HttpPsp {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl HttpPsp {
+    pub fn new(base_url: impl Into<String>, timeout: Duration) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder().timeout(timeout).build()?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        })
+    }
+
+    async fn settlements_page(
+        &self,
+        from: chrono::DateTime<chrono::Utc>,
+        to: chrono::DateTime<chrono::Utc>,
+        cursor: Option<&str>,
+    ) -> anyhow::Result<HttpSettlementsResponse> {
+        let mut query = vec![("from", from.to_rfc3339()), ("to", to.to_rfc3339())];
+        if let Some(cursor) = cursor {
+            query.push(("cursor", cursor.to_string()));
+        }
+
+        let response = match self
+            .client
+            .get(format!("{}/v1/settlements", self.base_url))
+            .query(&query)
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => anyhow::bail!("failed to list PSP settlements: {e}"),
+        };
+
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("PSP returned status {status} listing settlements")
+        }
+
+        match response.json::<HttpSettlementsResponse>().await {
+            Ok(page) => Ok(page),
+            Err(e) => anyhow::bail!("failed to decode PSP settlements: {e}"),
+        }
+    }
+}
+
+#[async_trait]
+impl PaymentServiceProvider for HttpPsp {
+    fn source(&self) -> &str {
+        "psp"
+    }
+
+    async fn settled_transactions(
+        &self,
+        from: chrono::DateTime<chrono::Utc>,
+        to: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<Vec<StatementLine>> {
+        let mut transactions = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let page = self.settlements_page(from, to, cursor.as_deref()).await?;
+            for transaction in page.transactions {
+                transactions.push(StatementLine::try_from(transaction)?);
+            }
+
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => return Ok(transactions),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::Json;
+    use axum::Router;
+    use axum::extract::Query;
+    use axum::http::StatusCode;
+    use axum::routing::get;
+    use common::test_util::spawn_stub;
+    use serde_json::{Value, json};
+    use std::collections::HashMap;
+
+    fn settlement(psp_reference: &str, status: &str) -> Value {
+        json!({
+            "psp_reference": psp_reference,
+            "transaction_id": uuid::Uuid::new_v4(),
+            "idempotency_key": null,
+            "status": status,
+            "amount_minor": 1050,
+            "currency": "USD",
+            "settled_at": "2025-10-24T10:00:00Z"
+        })
+    }
+
+    #[tokio::test]
+    async fn successfully_list_settlements_across_pages() {
+        // arrange - the first page points to the second one
+        let router = Router::new().route(
+            "/v1/settlements",
+            get(|Query(query): Query<HashMap<String, String>>| async move {
+                assert!(query.contains_key("from") && query.contains_key("to"));
+                match query.get("cursor").map(String::as_str) {
+                    None => {
+                        let mut paid = settlement("psp_1", "succeeded");
+                        paid["fee_minor"] = json!(32);
+                        Json(json!({
+                            "transactions": [paid],
+                            "next_cursor": "page-2"
+                        }))
+                    }
+                    Some("page-2") => Json(json!({
+                        "transactions": [settlement("psp_2", "failed")],
+                        "next_cursor": null
+                    })),
+                    Some(other) => panic!("unexpected cursor {other}"),
+                }
+            }),
+        );
+        let psp = HttpPsp::new(spawn_stub(router).await, Duration::from_secs(2)).unwrap();
+        let now = chrono::Utc::now();
+
+        // act
+        let transactions = psp
+            .settled_transactions(now - chrono::Duration::hours(1), now)
+            .await
+            .unwrap();
+
+        // assert
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(transactions[0].reference, "psp_1");
+        assert_eq!(transactions[0].status, Status::Succeeded);
+        assert_eq!(transactions[1].status, Status::Failed);
+        assert_eq!(transactions[1].amount_minor, 1050);
+        assert_eq!(transactions[0].fee_minor, 32);
+        assert_eq!(transactions[1].fee_minor, 0);
+        assert!(transactions[1].idempotency_key.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_settlements_errors() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            status: StatusCode,
+            body: Value,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "error when PSP fails",
+                status: StatusCode::BAD_GATEWAY,
+                body: json!({}),
+            },
+            TestCase {
+                name: "error when PSP reports an unknown status",
+                status: StatusCode::OK,
+                body: json!({
+                    "transactions": [settlement("psp_1", "disputed")],
+                    "next_cursor": null
+                }),
+            },
+        ];
+
+        for test_case in test_cases {
+            // arrange
+            let (status, body) = (test_case.status, test_case.body.clone());
+            let router = Router::new().route(
+                "/v1/settlements",
+                get(move || async move { (status, Json(body)) }),
+            );
+            let psp = HttpPsp::new(spawn_stub(router).await, Duration::from_secs(2)).unwrap();
+            let now = chrono::Utc::now();
+
+            // act
+            let result = psp
+                .settled_transactions(now - chrono::Duration::hours(1), now)
+                .await;
+
+            // assert
+            assert!(result.is_err(), "{}", test_case.name);
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use crate::domain::statement::StatementLine;
+use async_trait::async_trait;
+
+pub mod http;
+
+/// PaymentServiceProvider is the counterparty's side of reconciliation, either a PSP API or an
+/// imported statement file.
+#[async_trait]
+pub trait PaymentServiceProvider: 'static + Send + Sync {
+    /// source names the provider in `reconciliation_runs`, runs are scheduled per source.
+    fn source(&self) -> &str;
+    /// settled_transactions returns every statement line booked in `[from, to)`.
+    async fn settled_transactions(
+        &self,
+        from: chrono::DateTime<chrono::Utc>,
+        to: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<Vec<StatementLine>>;
+}
@@ -0,0 +1,401 @@
+use crate::domain::exception::{AUTOMATIC_AUTHOR, Exception, ExceptionError, Match, Note, Status};
+use crate::repo::{ExceptionReader, ExceptionWriter, PgReconciliationRepository};
+use async_trait::async_trait;
+
+const EXCEPTION_COLUMNS: &str = "id, source, reference, ledger_transaction_id, statement_reference, discrepancies, ledger_amount_minor, external_amount_minor, currency, ledger_status, external_status, status, first_seen_run_id, last_seen_run_id, first_seen_at, last_seen_at, resolved_at, resolved_run_id, updated_at";
+const NOTE_COLUMNS: &str = "id, exception_id, author, status, text, created_at";
+
+/// insert_note adds a note as part of an open database transaction.
+async fn insert_note(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    note: &Note,
+) -> anyhow::Result<()> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO reconciliation_exception_notes (id, exception_id, author, status, text, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+    )
+    .bind(note.id)
+    .bind(note.exception_id)
+    .bind(note.author.as_str())
+    .bind(note.status)
+    .bind(note.text.as_str())
+    .bind(note.created_at)
+    .execute(&mut **tx)
+    .await;
+
+    match result {
+        Ok(_) => Ok(()),
+        Err(e) => anyhow::bail!("Failed to insert exception note into database: {e}"),
+    }
+}
+
+#[async_trait]
+impl ExceptionWriter for PgReconciliationRepository {
+    async fn record_matches(&self, matches: &[Match]) -> anyhow::Result<()> {
+        let mut tx = self.db.writer.begin().await?;
+        for m in matches {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO reconciliation_matches (run_id, transaction_id, statement_reference, strategy)
+                VALUES ($1, $2, $3, $4)
+                "#,
+            )
+            .bind(m.run_id)
+            .bind(m.transaction_id)
+            .bind(m.statement_reference.as_str())
+            .bind(m.strategy)
+            .execute(&mut *tx)
+            .await;
+
+            if let Err(e) = result {
+                anyhow::bail!("Failed to insert match into database: {e}")
+            }
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn record_exceptions(&self, exceptions: &[Exception]) -> anyhow::Result<()> {
+        let mut tx = self.db.writer.begin().await?;
+        for exception in exceptions {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO reconciliation_exceptions (id, source, reference, ledger_transaction_id, statement_reference, discrepancies, ledger_amount_minor, external_amount_minor, currency, ledger_status, external_status, status, first_seen_run_id, last_seen_run_id, first_seen_at, last_seen_at, updated_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+                ON CONFLICT (source, reference) WHERE resolved_at IS NULL
+                DO UPDATE SET discrepancies = EXCLUDED.discrepancies,
+                              ledger_amount_minor = EXCLUDED.ledger_amount_minor,
+                              external_amount_minor = EXCLUDED.external_amount_minor,
+                              ledger_status = EXCLUDED.ledger_status,
+                              external_status = EXCLUDED.external_status,
+                              last_seen_run_id = EXCLUDED.last_seen_run_id,
+                              last_seen_at = EXCLUDED.last_seen_at,
+                              updated_at = now()
+                "#,
+            )
+            .bind(exception.id)
+            .bind(exception.source.as_str())
+            .bind(exception.reference.as_str())
+            .bind(exception.ledger_transaction_id)
+            .bind(exception.statement_reference.as_deref())
+            .bind(&exception.discrepancies)
+            .bind(exception.ledger_amount_minor)
+            .bind(exception.external_amount_minor)
+            .bind(exception.currency.as_str())
+            .bind(exception.ledger_status.clone())
+            .bind(exception.external_status.as_deref())
+            .bind(exception.status)
+            .bind(exception.first_seen_run_id)
+            .bind(exception.last_seen_run_id)
+            .bind(exception.first_seen_at)
+            .bind(exception.last_seen_at)
+            .bind(exception.updated_at)
+            .execute(&mut *tx)
+            .await;
+
+            if let Err(e) = result {
+                anyhow::bail!("Failed to record exception in database: {e}")
+            }
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn resolve_exceptions(
+        &self,
+        source: &str,
+        ledger_transaction_ids: &[uuid::Uuid],
+        run_id: uuid::Uuid,
+    ) -> anyhow::Result<u64> {
+        let result = sqlx::query(
+            r#"
+            WITH resolved AS (
+                UPDATE reconciliation_exceptions
+                SET status = 'resolved', resolved_at = now(), resolved_run_id = $3, updated_at = now()
+                WHERE source = $1 AND ledger_transaction_id = ANY($2) AND resolved_at IS NULL
+                RETURNING id
+            )
+            INSERT INTO reconciliation_exception_notes (id, exception_id, author, status, text, created_at)
+            SELECT gen_random_uuid(), id, $4, 'resolved', $5, now() FROM resolved
+            "#,
+        )
+        .bind(source)
+        .bind(ledger_transaction_ids)
+        .bind(run_id)
+        .bind(AUTOMATIC_AUTHOR)
+        .bind(format!("matched by run {run_id}"))
+        .execute(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(result) => Ok(result.rows_affected()),
+            Err(e) => anyhow::bail!("Failed to resolve_exceptions: {e}"),
+        }
+    }
+
+    async fn update_exception_status(
+        &self,
+        id: uuid::Uuid,
+        status: Status,
+        note: &Note,
+    ) -> anyhow::Result<Exception> {
+        let mut tx = self.db.writer.begin().await?;
+
+        let result = sqlx::query_as::<_, Exception>(&format!(
+            "SELECT {EXCEPTION_COLUMNS} FROM reconciliation_exceptions WHERE id = $1 FOR UPDATE"
+        ))
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await;
+        let current = match result {
+            Ok(Some(exception)) => exception,
+            Ok(None) => return Err(ExceptionError::NotFound(id).into()),
+            Err(e) => anyhow::bail!("Failed to update_exception_status: {e}"),
+        };
+        if !current.status.can_move_to(status) {
+            return Err(ExceptionError::InvalidTransition(id, current.status, status).into());
+        }
+
+        let result = sqlx::query_as::<_, Exception>(&format!(
+            r#"
+            UPDATE reconciliation_exceptions
+            SET status = $2, resolved_at = CASE WHEN $3 THEN now() END, updated_at = now()
+            WHERE id = $1
+            RETURNING {EXCEPTION_COLUMNS}
+            "#
+        ))
+        .bind(id)
+        .bind(status)
+        .bind(status.is_closed())
+        .fetch_one(&mut *tx)
+        .await;
+        let updated = match result {
+            Ok(exception) => exception,
+            Err(e) => anyhow::bail!("Failed to update_exception_status: {e}"),
+        };
+        insert_note(&mut tx, note).await?;
+
+        tx.commit().await?;
+
+        Ok(updated)
+    }
+}
+
+#[async_trait]
+impl ExceptionReader for PgReconciliationRepository {
+    async fn get_matches_by_transaction_id(
+        &self,
+        transaction_id: uuid::Uuid,
+    ) -> anyhow::Result<Vec<Match>> {
+        let result = sqlx::query_as::<_, Match>(
+            r#"
+            SELECT run_id, transaction_id, statement_reference, strategy
+            FROM reconciliation_matches
+            WHERE transaction_id = $1
+            ORDER BY created_at
+            "#,
+        )
+        .bind(transaction_id)
+        .fetch_all(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(matches) => Ok(matches),
+            Err(e) => anyhow::bail!("Failed to get_matches_by_transaction_id: {e}"),
+        }
+    }
+
+    async fn get_exception_by_id(&self, id: uuid::Uuid) -> anyhow::Result<Exception> {
+        let result = sqlx::query_as::<_, Exception>(&format!(
+            "SELECT {EXCEPTION_COLUMNS} FROM reconciliation_exceptions WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_one(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(exception) => Ok(exception),
+            Err(sqlx::Error::RowNotFound) => Err(ExceptionError::NotFound(id).into()),
+            Err(e) => anyhow::bail!("Failed to get_exception_by_id: {e}"),
+        }
+    }
+
+    async fn get_exceptions_by_status(
+        &self,
+        status: Status,
+        source: Option<&str>,
+    ) -> anyhow::Result<Vec<Exception>> {
+        let result = sqlx::query_as::<_, Exception>(&format!(
+            r#"
+            SELECT {EXCEPTION_COLUMNS}
+            FROM reconciliation_exceptions
+            WHERE status = $1 AND ($2::TEXT IS NULL OR source = $2)
+            ORDER BY first_seen_at
+            "#
+        ))
+        .bind(status)
+        .bind(source)
+        .fetch_all(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(exceptions) => Ok(exceptions),
+            Err(e) => anyhow::bail!("Failed to get_exceptions_by_status: {e}"),
+        }
+    }
+
+    async fn get_notes_by_exception_id(
+        &self,
+        exception_id: uuid::Uuid,
+    ) -> anyhow::Result<Vec<Note>> {
+        let result = sqlx::query_as::<_, Note>(&format!(
+            "SELECT {NOTE_COLUMNS} FROM reconciliation_exception_notes WHERE exception_id = $1 ORDER BY created_at"
+        ))
+        .bind(exception_id)
+        .fetch_all(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(notes) => Ok(notes),
+            Err(e) => anyhow::bail!("Failed to get_notes_by_exception_id: {e}"),
+        }
+    }
+
+    async fn get_open_exceptions(
+        &self,
+        source: &str,
+        first_seen_before: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<Vec<Exception>> {
+        // read from the writer, a run carries the exceptions the previous run has just recorded
+        let result = sqlx::query_as::<_, Exception>(&format!(
+            r#"
+            SELECT {EXCEPTION_COLUMNS}
+            FROM reconciliation_exceptions
+            WHERE source = $1 AND resolved_at IS NULL AND first_seen_at < $2
+            ORDER BY first_seen_at
+            "#
+        ))
+        .bind(source)
+        .bind(first_seen_before)
+        .fetch_all(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(exceptions) => Ok(exceptions),
+            Err(e) => anyhow::bail!("Failed to get_open_exceptions: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::matching::{Discrepancy, Reconciled};
+    use crate::domain::run::Run;
+    use crate::repo::RunWriter;
+    use ledger::domain::transaction::Transaction;
+
+    async fn repo(pool: sqlx::PgPool) -> PgReconciliationRepository {
+        PgReconciliationRepository::new(common::database::Database::from_pool(pool).await.unwrap())
+    }
+
+    fn missing_at_psp() -> Reconciled {
+        Reconciled {
+            ledger: Some(Transaction::new(
+                uuid::Uuid::new_v4(),
+                uuid::Uuid::new_v4(),
+                1000,
+                "USD",
+                uuid::Uuid::new_v4().to_string(),
+                chrono::Utc::now(),
+            )),
+            external: None,
+            discrepancies: vec![Discrepancy::MissingAtPsp],
+            strategy: None,
+        }
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_carry_exception_until_resolved(pool: sqlx::PgPool) {
+        // arrange - the same ledger transaction is missing in two consecutive runs
+        let repo = repo(pool).await;
+        let now = chrono::Utc::now();
+        let first = repo
+            .create_run(&Run::new("psp", now - chrono::Duration::hours(2), now))
+            .await
+            .unwrap();
+        let second = repo
+            .create_run(&Run::new("psp", now - chrono::Duration::hours(1), now))
+            .await
+            .unwrap();
+        let reconciled = missing_at_psp();
+        let transaction_id = reconciled.ledger.as_ref().unwrap().id;
+
+        // act
+        repo.record_exceptions(&[Exception::new(&first, &reconciled)])
+            .await
+            .unwrap();
+        repo.record_exceptions(&[Exception::new(&second, &reconciled)])
+            .await
+            .unwrap();
+        let open = repo
+            .get_open_exceptions("psp", chrono::Utc::now())
+            .await
+            .unwrap();
+        let other_source = repo
+            .resolve_exceptions("csv:statement.csv", &[transaction_id], second.id)
+            .await
+            .unwrap();
+        let resolved = repo
+            .resolve_exceptions("psp", &[transaction_id], second.id)
+            .await
+            .unwrap();
+        let after = repo
+            .get_open_exceptions("psp", chrono::Utc::now())
+            .await
+            .unwrap();
+
+        // assert
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].reference, transaction_id.to_string());
+        assert_eq!(open[0].first_seen_run_id, first.id);
+        assert_eq!(open[0].last_seen_run_id, second.id);
+        assert_eq!(open[0].discrepancies, vec!["missing_at_psp".to_string()]);
+        assert_eq!(open[0].ledger_amount_minor, Some(1000));
+        assert_eq!(open[0].external_amount_minor, None);
+        assert_eq!(other_source, 0);
+        assert_eq!(resolved, 1);
+        assert!(after.is_empty());
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_list_only_aged_exceptions(pool: sqlx::PgPool) {
+        // arrange
+        let repo = repo(pool).await;
+        let now = chrono::Utc::now();
+        let run = repo
+            .create_run(&Run::new("psp", now - chrono::Duration::hours(1), now))
+            .await
+            .unwrap();
+        let mut aged = Exception::new(&run, &missing_at_psp());
+        aged.first_seen_at = now - chrono::Duration::days(2);
+        let fresh = Exception::new(&run, &missing_at_psp());
+        repo.record_exceptions(&[aged.clone(), fresh])
+            .await
+            .unwrap();
+
+        // act
+        let exceptions = repo
+            .get_open_exceptions("psp", now - chrono::Duration::days(1))
+            .await
+            .unwrap();
+
+        // assert
+        assert_eq!(exceptions.len(), 1);
+        assert_eq!(exceptions[0].id, aged.id);
+    }
+}
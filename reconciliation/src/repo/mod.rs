@@ -0,0 +1,98 @@
+use crate::domain::exception::{Exception, Match, Note, Status};
+use crate::domain::run::Run;
+use async_trait::async_trait;
+use common::database::Database;
+
+mod exception;
+mod run;
+
+#[derive(Debug, Clone)]
+pub struct PgReconciliationRepository {
+    pub db: Database,
+}
+
+impl PgReconciliationRepository {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+pub trait ReconciliationRepository:
+    RunWriter + RunReader + ExceptionWriter + ExceptionReader + 'static + Send + Sync
+{
+}
+
+#[async_trait]
+pub trait RunWriter: 'static + Send + Sync {
+    async fn create_run(&self, run: &Run) -> anyhow::Result<Run>;
+    /// complete_run records the outcome counts of a run which went through the whole window.
+    async fn complete_run(
+        &self,
+        id: uuid::Uuid,
+        matched_count: i64,
+        mismatched_count: i64,
+    ) -> anyhow::Result<Run>;
+    async fn fail_run(&self, id: uuid::Uuid, failure_reason: &str) -> anyhow::Result<Run>;
+}
+
+#[async_trait]
+pub trait RunReader: 'static + Send + Sync {
+    async fn get_run_by_id(&self, id: uuid::Uuid) -> anyhow::Result<Run>;
+    /// get_last_completed_run returns the completed run of the source with the latest window
+    /// end, if any.
+    async fn get_last_completed_run(&self, source: &str) -> anyhow::Result<Option<Run>>;
+}
+
+#[async_trait]
+pub trait ExceptionWriter: 'static + Send + Sync {
+    async fn record_matches(&self, matches: &[Match]) -> anyhow::Result<()>;
+    /// record_exceptions opens an exception per source and reference, or updates the open one
+    /// when the discrepancy was seen by an earlier run.
+    async fn record_exceptions(&self, exceptions: &[Exception]) -> anyhow::Result<()>;
+    /// resolve_exceptions closes the open exceptions of ledger transactions which a statement
+    /// line of `run_id` has now matched, returning how many were closed.
+    async fn resolve_exceptions(
+        &self,
+        source: &str,
+        ledger_transaction_ids: &[uuid::Uuid],
+        run_id: uuid::Uuid,
+    ) -> anyhow::Result<u64>;
+    /// update_exception_status moves an exception along its lifecycle and adds the note. Moves
+    /// out of a closed status are rejected.
+    async fn update_exception_status(
+        &self,
+        id: uuid::Uuid,
+        status: Status,
+        note: &Note,
+    ) -> anyhow::Result<Exception>;
+}
+
+#[async_trait]
+pub trait ExceptionReader: 'static + Send + Sync {
+    async fn get_matches_by_transaction_id(
+        &self,
+        transaction_id: uuid::Uuid,
+    ) -> anyhow::Result<Vec<Match>>;
+    async fn get_exception_by_id(&self, id: uuid::Uuid) -> anyhow::Result<Exception>;
+    /// get_exceptions_by_status returns the exceptions in the status, of every source when
+    /// none is given, oldest first.
+    async fn get_exceptions_by_status(
+        &self,
+        status: Status,
+        source: Option<&str>,
+    ) -> anyhow::Result<Vec<Exception>>;
+    async fn get_notes_by_exception_id(
+        &self,
+        exception_id: uuid::Uuid,
+    ) -> anyhow::Result<Vec<Note>>;
+    /// get_open_exceptions returns the exceptions of the source which are not closed yet and
+    /// were first seen before `first_seen_before`, oldest first.
+    async fn get_open_exceptions(
+        &self,
+        source: &str,
+        first_seen_before: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<Vec<Exception>>;
+}
+
+impl ReconciliationRepository for PgReconciliationRepository {}
@@ -0,0 +1,185 @@
+use crate::domain::run::{Run, Status};
+use crate::repo::{PgReconciliationRepository, RunReader, RunWriter};
+use async_trait::async_trait;
+
+const RUN_COLUMNS: &str = "id, source, window_start, window_end, status, matched_count, mismatched_count, failure_reason, started_at, finished_at";
+
+impl PgReconciliationRepository {
+    async fn finish_run(
+        &self,
+        id: uuid::Uuid,
+        status: Status,
+        matched_count: i64,
+        mismatched_count: i64,
+        failure_reason: Option<&str>,
+    ) -> anyhow::Result<Run> {
+        let result = sqlx::query_as::<_, Run>(&format!(
+            r#"
+            UPDATE reconciliation_runs
+            SET status = $2, matched_count = $3, mismatched_count = $4, failure_reason = $5, finished_at = now()
+            WHERE id = $1 AND status = 'running'
+            RETURNING {RUN_COLUMNS}
+            "#
+        ))
+        .bind(id)
+        .bind(status)
+        .bind(matched_count)
+        .bind(mismatched_count)
+        .bind(failure_reason)
+        .fetch_optional(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(Some(run)) => Ok(run),
+            Ok(None) => anyhow::bail!("Run {id} is not running"),
+            Err(e) => anyhow::bail!("Failed to finish_run: {e}"),
+        }
+    }
+}
+
+#[async_trait]
+impl RunWriter for PgReconciliationRepository {
+    async fn create_run(&self, run: &Run) -> anyhow::Result<Run> {
+        let result = sqlx::query_as::<_, Run>(&format!(
+            r#"
+            INSERT INTO reconciliation_runs (id, source, window_start, window_end, status, started_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING {RUN_COLUMNS}
+            "#
+        ))
+        .bind(run.id)
+        .bind(run.source.as_str())
+        .bind(run.window_start)
+        .bind(run.window_end)
+        .bind(run.status.clone())
+        .bind(run.started_at)
+        .fetch_one(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(run) => Ok(run),
+            Err(e) => anyhow::bail!("Failed to insert run into database: {e}"),
+        }
+    }
+
+    async fn complete_run(
+        &self,
+        id: uuid::Uuid,
+        matched_count: i64,
+        mismatched_count: i64,
+    ) -> anyhow::Result<Run> {
+        self.finish_run(id, Status::Completed, matched_count, mismatched_count, None)
+            .await
+    }
+
+    async fn fail_run(&self, id: uuid::Uuid, failure_reason: &str) -> anyhow::Result<Run> {
+        self.finish_run(id, Status::Failed, 0, 0, Some(failure_reason))
+            .await
+    }
+}
+
+#[async_trait]
+impl RunReader for PgReconciliationRepository {
+    async fn get_run_by_id(&self, id: uuid::Uuid) -> anyhow::Result<Run> {
+        let result = sqlx::query_as::<_, Run>(&format!(
+            "SELECT {RUN_COLUMNS} FROM reconciliation_runs WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_one(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(run) => Ok(run),
+            Err(sqlx::Error::RowNotFound) => anyhow::bail!("Run {id} not found"),
+            Err(e) => anyhow::bail!("Failed to get_run_by_id: {e}"),
+        }
+    }
+
+    async fn get_last_completed_run(&self, source: &str) -> anyhow::Result<Option<Run>> {
+        // read from the writer, the next window must start where the last one ended
+        let result = sqlx::query_as::<_, Run>(&format!(
+            "SELECT {RUN_COLUMNS} FROM reconciliation_runs WHERE source = $1 AND status = 'completed' ORDER BY window_end DESC LIMIT 1"
+        ))
+        .bind(source)
+        .fetch_optional(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(run) => Ok(run),
+            Err(e) => anyhow::bail!("Failed to get_last_completed_run: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn repo(pool: sqlx::PgPool) -> PgReconciliationRepository {
+        PgReconciliationRepository::new(common::database::Database::from_pool(pool).await.unwrap())
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_complete_run_only_once(pool: sqlx::PgPool) {
+        // arrange
+        let repo = repo(pool).await;
+        let now = chrono::Utc::now();
+        let run = repo
+            .create_run(&Run::new("psp", now - chrono::Duration::hours(1), now))
+            .await
+            .unwrap();
+
+        // act
+        let completed = repo.complete_run(run.id, 3, 1).await.unwrap();
+        let failed = repo.fail_run(run.id, "too late").await;
+
+        // assert
+        assert_eq!(run.status, Status::Running);
+        assert_eq!(completed.status, Status::Completed);
+        assert_eq!(completed.matched_count, 3);
+        assert_eq!(completed.mismatched_count, 1);
+        assert!(completed.finished_at.is_some());
+        assert!(failed.is_err());
+        assert_eq!(repo.get_run_by_id(run.id).await.unwrap(), completed);
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_retrieve_last_completed_run(pool: sqlx::PgPool) {
+        // arrange
+        let repo = repo(pool).await;
+        let now = chrono::Utc::now();
+        assert!(repo.get_last_completed_run("psp").await.unwrap().is_none());
+        let first = repo
+            .create_run(&Run::new(
+                "psp",
+                now - chrono::Duration::hours(2),
+                now - chrono::Duration::hours(1),
+            ))
+            .await
+            .unwrap();
+        repo.complete_run(first.id, 0, 0).await.unwrap();
+        let second = repo
+            .create_run(&Run::new("psp", now - chrono::Duration::hours(1), now))
+            .await
+            .unwrap();
+        repo.fail_run(second.id, "PSP unavailable").await.unwrap();
+        let other_source = repo
+            .create_run(&Run::new(
+                "camt053:stmt-1",
+                now,
+                now + chrono::Duration::hours(1),
+            ))
+            .await
+            .unwrap();
+        repo.complete_run(other_source.id, 0, 0).await.unwrap();
+
+        // act
+        let last = repo.get_last_completed_run("psp").await.unwrap();
+
+        // assert
+        assert_eq!(last.unwrap().id, first.id);
+        let failed = repo.get_run_by_id(second.id).await.unwrap();
+        assert_eq!(failed.status, Status::Failed);
+        assert_eq!(failed.failure_reason.as_deref(), Some("PSP unavailable"));
+    }
+}
@@ -0,0 +1,881 @@
+use crate::DEFAULT_PENDING_TIMEOUT_SECONDS;
+use crate::domain::exception::{AUTOMATIC_AUTHOR, Exception, Match, Note, Status};
+use crate::domain::matching::{Discrepancy, MatchRules, Reconciled, Strategy, reconcile};
+use crate::domain::run::Run;
+use crate::domain::statement;
+use crate::psp::PaymentServiceProvider;
+use crate::repo::ReconciliationRepository;
+use common::kafka::{Publisher, RECONCILIATION_EVENTS_TOPIC};
+use events_proto::events_v1;
+use ledger::domain::money::{MoneyError, from_minor_units};
+use ledger::domain::transaction::{self, Transaction};
+use ledger::repo::LedgerReader;
+use prost::Message;
+use prost_types::Timestamp;
+use std::collections::HashSet;
+
+pub struct ReconciliationService<L, R, P, B>
+where
+    L: LedgerReader,
+    R: ReconciliationRepository,
+    P: PaymentServiceProvider,
+    B: Publisher,
+{
+    ledger: L,
+    repo: R,
+    psp: P,
+    publisher: B,
+    rules: MatchRules,
+    pending_timeout: chrono::Duration,
+}
+
+impl<L, R, P, B> ReconciliationService<L, R, P, B>
+where
+    L: LedgerReader,
+    R: ReconciliationRepository,
+    P: PaymentServiceProvider,
+    B: Publisher,
+{
+    pub fn new(ledger: L, repo: R, psp: P, publisher: B) -> Self {
+        Self {
+            ledger,
+            repo,
+            psp,
+            publisher,
+            rules: MatchRules::default(),
+            pending_timeout: chrono::Duration::seconds(DEFAULT_PENDING_TIMEOUT_SECONDS),
+        }
+    }
+
+    pub fn with_match_rules(mut self, rules: MatchRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// with_pending_timeout sets how long a ledger transaction may stay pending before a status
+    /// the PSP reported is applied to it automatically.
+    pub fn with_pending_timeout(mut self, pending_timeout: chrono::Duration) -> Self {
+        self.pending_timeout = pending_timeout;
+        self
+    }
+
+    /// exceptions lists the open exceptions of the source which have been outstanding for
+    /// longer than `older_than`, oldest first.
+    pub async fn exceptions(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        older_than: chrono::Duration,
+    ) -> anyhow::Result<Vec<Exception>> {
+        self.repo
+            .get_open_exceptions(self.psp.source(), now - older_than)
+            .await
+    }
+
+    pub async fn list_exceptions(
+        &self,
+        status: Status,
+        source: Option<&str>,
+    ) -> anyhow::Result<Vec<Exception>> {
+        self.repo.get_exceptions_by_status(status, source).await
+    }
+
+    /// get_exception returns the exception with its notes, oldest first.
+    pub async fn get_exception(&self, id: uuid::Uuid) -> anyhow::Result<(Exception, Vec<Note>)> {
+        let exception = self.repo.get_exception_by_id(id).await?;
+        let notes = self.repo.get_notes_by_exception_id(id).await?;
+
+        Ok((exception, notes))
+    }
+
+    pub async fn investigate_exception(
+        &self,
+        id: uuid::Uuid,
+        author: &str,
+        notes: &str,
+    ) -> anyhow::Result<(Exception, Vec<Note>)> {
+        self.update_exception(id, Status::Investigating, author, notes)
+            .await
+    }
+
+    pub async fn resolve_exception(
+        &self,
+        id: uuid::Uuid,
+        author: &str,
+        notes: &str,
+    ) -> anyhow::Result<(Exception, Vec<Note>)> {
+        self.update_exception(id, Status::Resolved, author, notes)
+            .await
+    }
+
+    pub async fn write_off_exception(
+        &self,
+        id: uuid::Uuid,
+        author: &str,
+        notes: &str,
+    ) -> anyhow::Result<(Exception, Vec<Note>)> {
+        self.update_exception(id, Status::WrittenOff, author, notes)
+            .await
+    }
+
+    async fn update_exception(
+        &self,
+        id: uuid::Uuid,
+        status: Status,
+        author: &str,
+        notes: &str,
+    ) -> anyhow::Result<(Exception, Vec<Note>)> {
+        let exception = self
+            .repo
+            .update_exception_status(id, status, &Note::new(id, author, status, notes))
+            .await?;
+        let notes = self.repo.get_notes_by_exception_id(id).await?;
+
+        Ok((exception, notes))
+    }
+
+    /// auto_correct resolves the open exceptions of the source which are safe to fix without a
+    /// human, see `Exception::correction`. The corrective event for ledger-consumer is published
+    /// before the exception is resolved, so a failure in between publishes it again on the next
+    /// pass; applying it twice is a no-op. Returns how many exceptions were corrected.
+    pub async fn auto_correct(&self, now: chrono::DateTime<chrono::Utc>) -> anyhow::Result<u64> {
+        let exceptions = self
+            .repo
+            .get_exceptions_by_status(Status::Open, Some(self.psp.source()))
+            .await?;
+
+        let mut corrected = 0;
+        for exception in exceptions {
+            let status_only = exception.discrepancies == [Discrepancy::Status.as_ref()];
+            let transaction_id = match exception.ledger_transaction_id {
+                Some(id) if status_only => id,
+                _ => continue,
+            };
+            let ledger = self.ledger.get_transaction_by_id(transaction_id).await?;
+            let to = match exception.correction(&ledger, now, self.pending_timeout) {
+                Some(to) => to,
+                None => continue,
+            };
+
+            self.publish(correction_event(&exception, &ledger, &to)?)
+                .await?;
+            let text = format!(
+                "corrected ledger status from {} to {}",
+                ledger.status.as_ref(),
+                to.as_ref()
+            );
+            self.repo
+                .update_exception_status(
+                    exception.id,
+                    Status::Resolved,
+                    &Note::new(exception.id, AUTOMATIC_AUTHOR, Status::Resolved, text),
+                )
+                .await?;
+            tracing::info!(
+                exception_id = %exception.id,
+                transaction_id = %ledger.id,
+                status = to.as_ref(),
+                "corrected ledger status"
+            );
+            corrected += 1;
+        }
+
+        Ok(corrected)
+    }
+
+    /// run reconciles the window `[from, to)` and records the outcome in `reconciliation_runs`.
+    /// A failed run is recorded as failed and its window is picked up again by `run_next`.
+    pub async fn run(
+        &self,
+        from: chrono::DateTime<chrono::Utc>,
+        to: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<Run> {
+        let run = self
+            .repo
+            .create_run(&Run::new(self.psp.source(), from, to))
+            .await?;
+
+        match self.reconcile_window(&run).await {
+            Ok((matched, mismatched)) => self.repo.complete_run(run.id, matched, mismatched).await,
+            Err(e) => {
+                self.repo.fail_run(run.id, e.to_string().as_str()).await?;
+                Err(e)
+            }
+        }
+    }
+
+    /// run_next reconciles from the end of the last completed run, or `window` before `to` for
+    /// the first run, up to `to`. Nothing is run when the window would be empty.
+    pub async fn run_next(
+        &self,
+        to: chrono::DateTime<chrono::Utc>,
+        window: chrono::Duration,
+    ) -> anyhow::Result<Option<Run>> {
+        let from = match self.repo.get_last_completed_run(self.psp.source()).await? {
+            Some(last) => last.window_end,
+            None => to - window,
+        };
+        if from >= to {
+            return Ok(None);
+        }
+
+        self.run(from, to).await.map(Some)
+    }
+
+    /// reconcile_window publishes one event per reconciled transaction and returns the number
+    /// of matched and mismatched transactions. Ledger transactions are picked by creation time
+    /// and PSP transactions by settlement time, so PSP records for transactions created before
+    /// the window are looked up by reference. Ledger transactions earlier runs could not match
+    /// are carried as candidates until a statement line settles them; while they stay
+    /// unmatched they are not reported again.
+    async fn reconcile_window(&self, run: &Run) -> anyhow::Result<(i64, i64)> {
+        let external = self
+            .psp
+            .settled_transactions(run.window_start, run.window_end)
+            .await?;
+        let mut ledger = self
+            .ledger
+            .get_transactions_created_between(run.window_start, run.window_end)
+            .await?;
+
+        let known_ids: HashSet<uuid::Uuid> = ledger.iter().map(|t| t.id).collect();
+        let known_keys: HashSet<&str> = ledger.iter().map(|t| t.idempotency_key.as_str()).collect();
+        let (mut ids, mut keys) = (Vec::new(), Vec::new());
+        for record in &external {
+            let known = record
+                .transaction_id
+                .is_some_and(|id| known_ids.contains(&id))
+                || record
+                    .idempotency_key
+                    .as_deref()
+                    .is_some_and(|key| known_keys.contains(key));
+            if known {
+                continue;
+            }
+            ids.extend(record.transaction_id);
+            keys.extend(record.idempotency_key.clone());
+        }
+        let carried: HashSet<uuid::Uuid> = self
+            .repo
+            .get_open_exceptions(run.source.as_str(), run.started_at)
+            .await?
+            .into_iter()
+            .filter(|exception| exception.statement_reference.is_none())
+            .filter_map(|exception| exception.ledger_transaction_id)
+            .filter(|id| !known_ids.contains(id))
+            .collect();
+        ids.extend(carried.iter().copied());
+        if !ids.is_empty() || !keys.is_empty() {
+            let earlier = self
+                .ledger
+                .get_transactions_by_references(&ids, &keys)
+                .await?;
+            ledger.extend(earlier.into_iter().filter(|t| !known_ids.contains(&t.id)));
+        }
+
+        let (mut matched, mut mismatched) = (0, 0);
+        let (mut matches, mut exceptions) = (Vec::new(), Vec::new());
+        for reconciled in reconcile(ledger, external, &self.rules) {
+            let still_missing = reconciled.discrepancies == [Discrepancy::MissingAtPsp]
+                && reconciled
+                    .ledger
+                    .as_ref()
+                    .is_some_and(|t| carried.contains(&t.id));
+            if still_missing {
+                continue;
+            }
+
+            match reconciled.is_matched() {
+                true => {
+                    matched += 1;
+                    matches.extend(Match::new(run, &reconciled));
+                }
+                false => {
+                    mismatched += 1;
+                    tracing::warn!(
+                        run_id = %run.id,
+                        transaction_id = reconciled.transaction_id(),
+                        discrepancies = ?reconciled.discrepancies,
+                        "reconciliation mismatch"
+                    );
+                    exceptions.push(Exception::new(run, &reconciled));
+                }
+            }
+            self.publish(reconciliation_event(&reconciled)?).await?;
+        }
+
+        let settled: Vec<uuid::Uuid> = matches.iter().map(|m| m.transaction_id).collect();
+        self.repo.record_matches(&matches).await?;
+        self.repo.record_exceptions(&exceptions).await?;
+        self.repo
+            .resolve_exceptions(run.source.as_str(), &settled, run.id)
+            .await?;
+
+        Ok((matched, mismatched))
+    }
+
+    async fn publish(&self, event: events_v1::Reconciliation) -> anyhow::Result<()> {
+        match self
+            .publisher
+            .publish(
+                RECONCILIATION_EVENTS_TOPIC,
+                event.transaction_id.as_str(),
+                event.encode_to_vec(),
+            )
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(e) => anyhow::bail!("failed to publish reconciliation event: {e}"),
+        }
+    }
+}
+
+/// reconciliation_event maps a reconciled pair to a `Reconciliation` event. The side which is
+/// missing is reported with an unspecified status and a zero amount in the other side's
+/// currency, so both amounts are always populated. Matches carry the strategy which found them.
+pub fn reconciliation_event(
+    reconciled: &Reconciled,
+) -> Result<events_v1::Reconciliation, MoneyError> {
+    let now = chrono::Utc::now();
+    let result = match reconciled.is_matched() {
+        true => events_v1::ReconciliationResult::ResultMatched,
+        false => events_v1::ReconciliationResult::ResultMismatch,
+    };
+    let ledger = reconciled.ledger.as_ref().map(|t| {
+        (
+            ledger_status(&t.status),
+            t.amount_minor,
+            t.currency.as_str(),
+        )
+    });
+    let external = reconciled.external.as_ref().map(|t| {
+        (
+            external_status(t.status),
+            t.amount_minor,
+            t.currency.as_str(),
+        )
+    });
+    let currency = ledger
+        .or(external)
+        .map(|(_, _, currency)| currency)
+        .unwrap_or_default();
+
+    let ledger_amount = match ledger {
+        Some((_, amount, currency)) => money(amount, currency)?,
+        None => money(0, currency)?,
+    };
+    let external_system_amount = match external {
+        Some((_, amount, currency)) => money(amount, currency)?,
+        None => money(0, currency)?,
+    };
+
+    Ok(events_v1::Reconciliation {
+        id: uuid::Uuid::new_v4().to_string(),
+        transaction_id: reconciled.transaction_id(),
+        ledger_status: ledger.map_or(events_v1::Status::Unspecified, |(status, _, _)| status)
+            as i32,
+        external_status: external.map_or(events_v1::Status::Unspecified, |(status, _, _)| status)
+            as i32,
+        ledger_amount: Some(ledger_amount),
+        external_system_amount: Some(external_system_amount),
+        result: result as i32,
+        match_strategy: reconciled
+            .strategy
+            .filter(|_| reconciled.is_matched())
+            .map_or(events_v1::MatchStrategy::Unspecified, match_strategy)
+            as i32,
+        exception_id: String::new(),
+        created_at: Some(Timestamp {
+            seconds: now.timestamp(),
+            nanos: now.timestamp_subsec_nanos() as i32,
+        }),
+    })
+}
+
+/// correction_event asks ledger-consumer to move the ledger transaction of the exception to
+/// `to`, which is reported as the external status.
+pub fn correction_event(
+    exception: &Exception,
+    ledger: &Transaction,
+    to: &transaction::Status,
+) -> Result<events_v1::Reconciliation, MoneyError> {
+    let now = chrono::Utc::now();
+
+    Ok(events_v1::Reconciliation {
+        id: uuid::Uuid::new_v4().to_string(),
+        transaction_id: ledger.id.to_string(),
+        ledger_status: ledger_status(&ledger.status) as i32,
+        external_status: ledger_status(to) as i32,
+        ledger_amount: Some(money(ledger.amount_minor, ledger.currency.as_str())?),
+        external_system_amount: Some(money(
+            exception
+                .external_amount_minor
+                .unwrap_or(ledger.amount_minor),
+            ledger.currency.as_str(),
+        )?),
+        result: events_v1::ReconciliationResult::ResultCorrection as i32,
+        match_strategy: events_v1::MatchStrategy::Unspecified as i32,
+        exception_id: exception.id.to_string(),
+        created_at: Some(Timestamp {
+            seconds: now.timestamp(),
+            nanos: now.timestamp_subsec_nanos() as i32,
+        }),
+    })
+}
+
+/// ledger_status reports refunded transactions as successful, the event status has no refund
+/// states.
+fn ledger_status(status: &transaction::Status) -> events_v1::Status {
+    match status {
+        transaction::Status::Init => events_v1::Status::Init,
+        transaction::Status::Pending => events_v1::Status::Pending,
+        transaction::Status::Success
+        | transaction::Status::Refund
+        | transaction::Status::Refunded => events_v1::Status::Success,
+        transaction::Status::Failed | transaction::Status::Fraud => events_v1::Status::Failed,
+    }
+}
+
+fn external_status(status: statement::Status) -> events_v1::Status {
+    match status {
+        statement::Status::Pending => events_v1::Status::Pending,
+        statement::Status::Succeeded => events_v1::Status::Success,
+        statement::Status::Failed => events_v1::Status::Failed,
+    }
+}
+
+fn match_strategy(strategy: Strategy) -> events_v1::MatchStrategy {
+    match strategy {
+        Strategy::Reference => events_v1::MatchStrategy::Reference,
+        Strategy::AmountTolerance => events_v1::MatchStrategy::AmountTolerance,
+        Strategy::FeeAdjusted => events_v1::MatchStrategy::FeeAdjusted,
+        Strategy::DateWindow => events_v1::MatchStrategy::DateWindow,
+        Strategy::Sum => events_v1::MatchStrategy::Sum,
+    }
+}
+
+fn money(
+    amount_minor: i64,
+    currency: &str,
+) -> Result<events_v1::google::r#type::Money, MoneyError> {
+    let (units, nanos) = from_minor_units(amount_minor, currency)?;
+    Ok(events_v1::google::r#type::Money {
+        currency_code: currency.to_string(),
+        units,
+        nanos,
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::domain::exception::{ExceptionError, Status as ExceptionStatus};
+    use crate::domain::matching::tests::settled;
+    use crate::domain::run::Status;
+    use crate::domain::statement::StatementLine;
+    use crate::repo::{ExceptionReader, PgReconciliationRepository, RunReader};
+    use async_trait::async_trait;
+    use chrono::SubsecRound;
+    use ledger::repo::PgLedgerRepository;
+    use ledger::test_util::seed_transaction;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    pub(crate) struct InMemoryPublisher {
+        pub messages: Mutex<Vec<(String, String, Vec<u8>)>>,
+    }
+
+    #[async_trait]
+    impl Publisher for InMemoryPublisher {
+        async fn publish(&self, topic: &str, key: &str, payload: Vec<u8>) -> anyhow::Result<()> {
+            self.messages
+                .lock()
+                .unwrap()
+                .push((topic.to_string(), key.to_string(), payload));
+            Ok(())
+        }
+    }
+
+    impl InMemoryPublisher {
+        pub(crate) fn events(&self) -> Vec<events_v1::Reconciliation> {
+            self.messages
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(_, _, payload)| {
+                    events_v1::Reconciliation::decode(payload.as_slice()).unwrap()
+                })
+                .collect()
+        }
+    }
+
+    /// StaticPsp reports the same settled transactions for every window, or fails when empty.
+    #[derive(Default)]
+    pub(crate) struct StaticPsp {
+        pub transactions: Mutex<Option<Vec<StatementLine>>>,
+    }
+
+    #[async_trait]
+    impl PaymentServiceProvider for StaticPsp {
+        fn source(&self) -> &str {
+            "psp"
+        }
+
+        async fn settled_transactions(
+            &self,
+            _from: chrono::DateTime<chrono::Utc>,
+            _to: chrono::DateTime<chrono::Utc>,
+        ) -> anyhow::Result<Vec<StatementLine>> {
+            match self.transactions.lock().unwrap().clone() {
+                Some(transactions) => Ok(transactions),
+                None => anyhow::bail!("PSP unavailable"),
+            }
+        }
+    }
+
+    pub(crate) type TestService = ReconciliationService<
+        PgLedgerRepository,
+        PgReconciliationRepository,
+        StaticPsp,
+        InMemoryPublisher,
+    >;
+
+    pub(crate) async fn setup(pool: sqlx::PgPool) -> (TestService, PgLedgerRepository) {
+        let db = common::database::Database::from_pool(pool).await.unwrap();
+        let ledger = PgLedgerRepository::new(db.clone());
+        let service = ReconciliationService::new(
+            ledger.clone(),
+            PgReconciliationRepository::new(db),
+            StaticPsp::default(),
+            InMemoryPublisher::default(),
+        );
+
+        (service, ledger)
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_reconcile_window(pool: sqlx::PgPool) {
+        // arrange - one match, one amount mismatch, one only in the ledger and one only at the PSP
+        let (service, ledger) = setup(pool).await;
+        let earlier = seed_transaction(&ledger, 500, transaction::Status::Success).await;
+        let from = chrono::Utc::now();
+        let matched = seed_transaction(&ledger, 1000, transaction::Status::Success).await;
+        let wrong_amount = seed_transaction(&ledger, 2000, transaction::Status::Success).await;
+        let missing_at_psp = seed_transaction(&ledger, 3000, transaction::Status::Success).await;
+        let to = chrono::Utc::now();
+        *service.psp.transactions.lock().unwrap() = Some(vec![
+            settled(&matched),
+            StatementLine {
+                amount_minor: 1999,
+                ..settled(&wrong_amount)
+            },
+            // created before the window, settled within it
+            StatementLine {
+                transaction_id: None,
+                ..settled(&earlier)
+            },
+            StatementLine {
+                transaction_id: None,
+                idempotency_key: Some("unknown".to_string()),
+                ..settled(&matched)
+            },
+        ]);
+
+        // act
+        let run = service.run(from, to).await.unwrap();
+
+        // assert
+        assert_eq!(run.status, Status::Completed);
+        assert_eq!(run.matched_count, 2);
+        assert_eq!(run.mismatched_count, 3);
+        let events = service.publisher.events();
+        let results: Vec<_> = events
+            .iter()
+            .map(|e| (e.transaction_id.clone(), e.result()))
+            .collect();
+        assert_eq!(
+            results,
+            vec![
+                (
+                    matched.id.to_string(),
+                    events_v1::ReconciliationResult::ResultMatched
+                ),
+                (
+                    wrong_amount.id.to_string(),
+                    events_v1::ReconciliationResult::ResultMismatch
+                ),
+                (
+                    earlier.id.to_string(),
+                    events_v1::ReconciliationResult::ResultMatched
+                ),
+                (
+                    "unknown".to_string(),
+                    events_v1::ReconciliationResult::ResultMismatch
+                ),
+                (
+                    missing_at_psp.id.to_string(),
+                    events_v1::ReconciliationResult::ResultMismatch
+                ),
+            ]
+        );
+        assert_eq!(events[1].ledger_amount.clone().unwrap().units, 20);
+        assert_eq!(
+            events[1].external_system_amount.clone().unwrap().nanos,
+            990_000_000
+        );
+        assert_eq!(events[3].ledger_status(), events_v1::Status::Unspecified);
+        assert_eq!(events[4].external_status(), events_v1::Status::Unspecified);
+        let missing = events[4].external_system_amount.clone().unwrap();
+        assert_eq!((missing.units, missing.currency_code.as_str()), (0, "USD"));
+        let unknown = events[3].ledger_amount.clone().unwrap();
+        assert_eq!((unknown.units, unknown.currency_code.as_str()), (0, "USD"));
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_continue_from_last_completed_run(pool: sqlx::PgPool) {
+        // arrange - the first run fails while the PSP is down
+        let (service, _) = setup(pool).await;
+        let now = chrono::Utc::now().trunc_subsecs(6);
+        let window = chrono::Duration::hours(1);
+        let failed = service.run_next(now, window).await;
+        *service.psp.transactions.lock().unwrap() = Some(vec![]);
+
+        // act
+        let first = service.run_next(now, window).await.unwrap().unwrap();
+        let nothing_to_do = service.run_next(now, window).await.unwrap();
+        let second = service
+            .run_next(now + window, window)
+            .await
+            .unwrap()
+            .unwrap();
+
+        // assert
+        assert!(failed.is_err());
+        let last = service
+            .repo
+            .get_last_completed_run("psp")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(last.id, second.id);
+        assert_eq!(first.window_start, now - window);
+        assert!(nothing_to_do.is_none());
+        assert_eq!(second.window_start, first.window_end);
+        assert_eq!(second.window_end, now + window);
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_reconcile_imported_statement(pool: sqlx::PgPool) {
+        // arrange - the bank booked a different amount and a line we know nothing about
+        let db = common::database::Database::from_pool(pool).await.unwrap();
+        let ledger = PgLedgerRepository::new(db.clone());
+        let transaction = seed_transaction(&ledger, 1000, transaction::Status::Success).await;
+        let content = format!(
+            "reference,idempotency_key,amount,currency,booked_at\n\
+             BR-1,{},9.50,USD,2025-10-24\n\
+             BR-2,,3.00,USD,2025-10-24\n",
+            transaction.idempotency_key
+        );
+        let statement = crate::import::parse(content.as_str(), &"csv".parse().unwrap()).unwrap();
+        let (from, to) = statement.window().unwrap();
+        let service = ReconciliationService::new(
+            ledger,
+            PgReconciliationRepository::new(db),
+            crate::import::StatementFile::new("csv:statement.csv", statement),
+            InMemoryPublisher::default(),
+        );
+
+        // act
+        let run = service.run(from, to).await.unwrap();
+
+        // assert
+        assert_eq!(run.source, "csv:statement.csv");
+        assert_eq!(run.mismatched_count, 2);
+        let events = service.publisher.events();
+        assert_eq!(events.len(), 2);
+        for event in &events {
+            assert_eq!(
+                event.result(),
+                events_v1::ReconciliationResult::ResultMismatch
+            );
+            assert!(event.ledger_amount.is_some() && event.external_system_amount.is_some());
+        }
+        assert_eq!(events[0].transaction_id, transaction.id.to_string());
+        assert_eq!(events[0].ledger_amount.clone().unwrap().units, 10);
+        assert_eq!(events[0].external_system_amount.clone().unwrap().units, 9);
+        assert_eq!(events[1].transaction_id, "BR-2");
+        assert_eq!(events[1].external_system_amount.clone().unwrap().units, 3);
+        // imported statements do not move the PSP schedule
+        assert!(
+            service
+                .repo
+                .get_last_completed_run("psp")
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_match_netted_payout_and_carry_exceptions(pool: sqlx::PgPool) {
+        // arrange - a payout nets two transactions less fees, two more are not settled yet
+        let (service, ledger) = setup(pool).await;
+        let from = chrono::Utc::now();
+        let first = seed_transaction(&ledger, 1000, transaction::Status::Success).await;
+        let second = seed_transaction(&ledger, 2500, transaction::Status::Success).await;
+        let settled_later = seed_transaction(&ledger, 700, transaction::Status::Success).await;
+        let never_settled = seed_transaction(&ledger, 600, transaction::Status::Success).await;
+        let to = chrono::Utc::now();
+        *service.psp.transactions.lock().unwrap() = Some(vec![StatementLine {
+            reference: "po_1".to_string(),
+            transaction_id: None,
+            idempotency_key: None,
+            amount_minor: 3400,
+            fee_minor: 100,
+            ..settled(&first)
+        }]);
+        let first_run = service.run(from, to).await.unwrap();
+        let open = service
+            .exceptions(chrono::Utc::now(), chrono::Duration::zero())
+            .await
+            .unwrap();
+
+        // act - the next window settles one of them without a reference
+        *service.psp.transactions.lock().unwrap() = Some(vec![StatementLine {
+            transaction_id: None,
+            idempotency_key: None,
+            booked_at: chrono::Utc::now(),
+            ..settled(&settled_later)
+        }]);
+        let second_run = service
+            .run(to, to + chrono::Duration::hours(1))
+            .await
+            .unwrap();
+
+        // assert
+        assert_eq!(
+            (first_run.matched_count, first_run.mismatched_count),
+            (2, 2)
+        );
+        let mut missing: Vec<_> = open.iter().map(|e| e.reference.clone()).collect();
+        missing.sort();
+        let mut expected = vec![settled_later.id.to_string(), never_settled.id.to_string()];
+        expected.sort();
+        assert_eq!(missing, expected);
+        let matches = service
+            .repo
+            .get_matches_by_transaction_id(first.id)
+            .await
+            .unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].strategy, Strategy::Sum);
+        assert_eq!(matches[0].statement_reference, "po_1");
+        let matches = service
+            .repo
+            .get_matches_by_transaction_id(second.id)
+            .await
+            .unwrap();
+        assert_eq!(matches[0].statement_reference, "po_1");
+
+        // the carried transaction is matched, the one still missing is not reported again
+        assert_eq!(
+            (second_run.matched_count, second_run.mismatched_count),
+            (1, 0)
+        );
+        let events = service.publisher.events();
+        assert_eq!(events.len(), 5);
+        assert_eq!(events[0].match_strategy(), events_v1::MatchStrategy::Sum);
+        assert_eq!(events[4].transaction_id, settled_later.id.to_string());
+        assert_eq!(
+            events[4].match_strategy(),
+            events_v1::MatchStrategy::DateWindow
+        );
+        let still_open = service
+            .exceptions(chrono::Utc::now(), chrono::Duration::zero())
+            .await
+            .unwrap();
+        assert_eq!(still_open.len(), 1);
+        assert_eq!(still_open[0].ledger_transaction_id, Some(never_settled.id));
+        assert_eq!(still_open[0].first_seen_run_id, first_run.id);
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_auto_correct_and_manually_resolve_exceptions(pool: sqlx::PgPool) {
+        // arrange - the PSP settled a transaction the ledger still has pending, and booked a
+        // different amount for another one
+        let (service, ledger) = setup(pool).await;
+        let service = service.with_pending_timeout(chrono::Duration::zero());
+        let from = chrono::Utc::now();
+        let pending = seed_transaction(&ledger, 1000, transaction::Status::Pending).await;
+        let wrong_amount = seed_transaction(&ledger, 2000, transaction::Status::Pending).await;
+        let to = chrono::Utc::now();
+        *service.psp.transactions.lock().unwrap() = Some(vec![
+            settled(&pending),
+            StatementLine {
+                amount_minor: 1500,
+                ..settled(&wrong_amount)
+            },
+        ]);
+        service.run(from, to).await.unwrap();
+
+        // act
+        let corrected = service.auto_correct(chrono::Utc::now()).await.unwrap();
+        let open = service
+            .list_exceptions(ExceptionStatus::Open, Some("psp"))
+            .await
+            .unwrap();
+        let investigating = service
+            .investigate_exception(open[0].id, "ops@pasys.dev", "asked the PSP")
+            .await
+            .unwrap();
+        let written_off = service
+            .write_off_exception(open[0].id, "ops@pasys.dev", "PSP kept the difference")
+            .await
+            .unwrap();
+        let reopened = service
+            .investigate_exception(open[0].id, "ops@pasys.dev", "one more look")
+            .await;
+
+        // assert
+        assert_eq!(corrected, 1);
+        let events = service.publisher.events();
+        assert_eq!(events.len(), 3);
+        let correction = &events[2];
+        assert_eq!(
+            correction.result(),
+            events_v1::ReconciliationResult::ResultCorrection
+        );
+        assert_eq!(correction.transaction_id, pending.id.to_string());
+        assert_eq!(correction.ledger_status(), events_v1::Status::Pending);
+        assert_eq!(correction.external_status(), events_v1::Status::Success);
+        let resolved = service
+            .list_exceptions(ExceptionStatus::Resolved, None)
+            .await
+            .unwrap();
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(correction.exception_id, resolved[0].id.to_string());
+        let (_, notes) = service.get_exception(resolved[0].id).await.unwrap();
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].author, AUTOMATIC_AUTHOR);
+        assert_eq!(
+            notes[0].text,
+            "corrected ledger status from pending to success"
+        );
+
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].ledger_transaction_id, Some(wrong_amount.id));
+        assert_eq!(investigating.0.status, ExceptionStatus::Investigating);
+        assert!(investigating.0.resolved_at.is_none());
+        assert_eq!(written_off.0.status, ExceptionStatus::WrittenOff);
+        assert!(written_off.0.resolved_at.is_some());
+        let texts: Vec<_> = written_off.1.iter().map(|n| n.text.as_str()).collect();
+        assert_eq!(texts, vec!["asked the PSP", "PSP kept the difference"]);
+        assert_eq!(
+            reopened.unwrap_err().downcast_ref::<ExceptionError>(),
+            Some(&ExceptionError::InvalidTransition(
+                open[0].id,
+                ExceptionStatus::WrittenOff,
+                ExceptionStatus::Investigating
+            ))
+        );
+    }
+}
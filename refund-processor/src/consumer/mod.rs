@@ -0,0 +1,80 @@
+use crate::psp::PaymentServiceProvider;
+use crate::service::RefundService;
+use async_trait::async_trait;
+use common::kafka::{MessageHandler, REFUND_EVENTS_TOPIC};
+use events_proto::events_v1;
+use ledger::repo::LedgerRepository;
+use prost::Message;
+
+#[async_trait]
+impl<R, P> MessageHandler for RefundService<R, P>
+where
+    R: LedgerRepository,
+    P: PaymentServiceProvider,
+{
+    async fn handle(&self, topic: &str, payload: &[u8]) -> anyhow::Result<()> {
+        match topic {
+            REFUND_EVENTS_TOPIC => {
+                let refund = match events_v1::Refund::decode(payload) {
+                    Ok(refund) => refund,
+                    Err(e) => anyhow::bail!("failed to decode refund event: {e}"),
+                };
+
+                let refund = self.process_refund(&refund).await?;
+                tracing::info!(
+                    refund_id = %refund.id,
+                    transaction_id = %refund.transaction_id,
+                    status = refund.status.as_ref(),
+                    "processed refund"
+                );
+            }
+            _ => anyhow::bail!("unexpected topic {topic}"),
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::tests::{ScriptedPsp, refund_event};
+    use ledger::domain::transaction;
+    use ledger::repo::PgLedgerRepository;
+    use ledger::test_util::seed_transaction;
+    use std::time::Duration;
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_handle_refund_event(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let transaction = seed_transaction(&repo, 1000, transaction::Status::Success).await;
+        let service = RefundService::new(repo, ScriptedPsp::default(), 1, Duration::from_millis(1));
+
+        // act
+        let result = service
+            .handle(
+                REFUND_EVENTS_TOPIC,
+                &refund_event(transaction.id, 10).encode_to_vec(),
+            )
+            .await;
+
+        // assert
+        assert!(result.is_ok());
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn error_when_payload_is_not_a_refund(pool: sqlx::PgPool) {
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let service = RefundService::new(repo, ScriptedPsp::default(), 1, Duration::from_millis(1));
+
+        assert!(
+            service
+                .handle(REFUND_EVENTS_TOPIC, b"\xff\xff")
+                .await
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,10 @@
+pub mod consumer;
+pub mod psp;
+pub mod service;
+
+pub const DEFAULT_PSP_TIMEOUT_MILLISECONDS: u64 = 2000;
+pub const DEFAULT_PSP_MAX_ATTEMPTS: u32 = 3;
+pub const DEFAULT_PSP_RETRY_BACKOFF_MILLISECONDS: u64 = 200;
+pub const DEFAULT_WRITER_MAX_CONN: u32 = 4;
+pub const DEFAULT_READER_MAX_CONN: u32 = 4;
+pub const DEFAULT_TIMEOUT_SECONDS: u64 = 5;
@@ -0,0 +1,163 @@
+use crate::psp::{PaymentServiceProvider, PspError, RefundRequest, RefundResponse};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Debug, Serialize)]
+struct HttpRefundRequest<'a> {
+    transaction_id: String,
+    amount_minor: i64,
+    currency: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct HttpRefundResponse {
+    psp_reference: String,
+}
+
+/// HttpPsp issues refunds through the PSP's HTTP API.
+///
+/// The PSP is expected to expose `POST {base_url}/v1/refunds` which accepts
+/// `{"transaction_id": "...", "amount_minor": 100, "currency": "USD"}` with an `Idempotency-Key`
+/// header and returns `{"psp_reference": "..."}`. 4xx responses are declines, anything else is
+/// treated as the PSP being unavailable.
+#[derive(Debug, Clone)]
+pub struct HttpPsp {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl HttpPsp {
+    pub fn new(base_url: impl Into<String>, timeout: Duration) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder().timeout(timeout).build()?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl PaymentServiceProvider for HttpPsp {
+    async fn refund(&self, request: &RefundRequest) -> Result<RefundResponse, PspError> {
+        let response = match self
+            .client
+            .post(format!("{}/v1/refunds", self.base_url))
+            .header("Idempotency-Key", request.refund_id.to_string())
+            .json(&HttpRefundRequest {
+                transaction_id: request.transaction_id.to_string(),
+                amount_minor: request.amount_minor,
+                currency: request.currency.as_str(),
+            })
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => return Err(PspError::Unavailable(e.to_string())),
+        };
+
+        let status = response.status();
+        if status.is_client_error() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(PspError::Declined(format!("{status}: {body}")));
+        }
+        if !status.is_success() {
+            return Err(PspError::Unavailable(format!(
+                "PSP returned status {status}"
+            )));
+        }
+
+        match response.json::<HttpRefundResponse>().await {
+            Ok(response) => Ok(RefundResponse {
+                psp_reference: response.psp_reference,
+            }),
+            Err(e) => Err(PspError::Unavailable(format!(
+                "failed to decode PSP response: {e}"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::Json;
+    use axum::Router;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use common::test_util::spawn_stub;
+    use serde_json::{Value, json};
+
+    fn request() -> RefundRequest {
+        RefundRequest {
+            refund_id: uuid::Uuid::new_v4(),
+            transaction_id: uuid::Uuid::new_v4(),
+            amount_minor: 1050,
+            currency: "USD".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn successfully_refund_with_idempotency_key() {
+        // arrange - stub echoes the idempotency key as reference
+        let router = Router::new().route(
+            "/v1/refunds",
+            post(|headers: HeaderMap, Json(body): Json<Value>| async move {
+                assert_eq!(body["amount_minor"], 1050);
+                let key = headers["Idempotency-Key"].to_str().unwrap().to_string();
+                Json(json!({"psp_reference": key}))
+            }),
+        );
+        let psp = HttpPsp::new(spawn_stub(router).await, Duration::from_secs(2)).unwrap();
+        let request = request();
+
+        // act
+        let response = psp.refund(&request).await;
+
+        // assert
+        assert_eq!(
+            response.unwrap().psp_reference,
+            request.refund_id.to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_refund_errors() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            status: StatusCode,
+            declined: bool,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "declined when PSP rejects the request",
+                status: StatusCode::UNPROCESSABLE_ENTITY,
+                declined: true,
+            },
+            TestCase {
+                name: "unavailable when PSP fails",
+                status: StatusCode::BAD_GATEWAY,
+                declined: false,
+            },
+        ];
+
+        for test_case in test_cases {
+            // arrange
+            let status = test_case.status;
+            let router = Router::new().route("/v1/refunds", post(move || async move { status }));
+            let psp = HttpPsp::new(spawn_stub(router).await, Duration::from_secs(2)).unwrap();
+
+            // act
+            let result = psp.refund(&request()).await;
+
+            // assert
+            match result.unwrap_err() {
+                PspError::Declined(_) => assert!(test_case.declined, "{}", test_case.name),
+                PspError::Unavailable(_) => assert!(!test_case.declined, "{}", test_case.name),
+            }
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+use std::fmt;
+
+pub mod http;
+
+/// RefundRequest asks the PSP to return money for a settled transaction. `refund_id` is sent as
+/// the idempotency key so retries of the same refund are only executed once.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RefundRequest {
+    pub refund_id: uuid::Uuid,
+    pub transaction_id: uuid::Uuid,
+    pub amount_minor: i64,
+    pub currency: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RefundResponse {
+    pub psp_reference: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PspError {
+    /// Declined is a final answer from the PSP, retrying will not help.
+    Declined(String),
+    /// Unavailable is a transient failure, the refund can be retried with the same key.
+    Unavailable(String),
+}
+
+impl fmt::Display for PspError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PspError::Declined(reason) => write!(f, "refund declined by PSP: {reason}"),
+            PspError::Unavailable(reason) => write!(f, "PSP unavailable: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for PspError {}
+
+#[async_trait]
+pub trait PaymentServiceProvider: 'static + Send + Sync {
+    async fn refund(&self, request: &RefundRequest) -> Result<RefundResponse, PspError>;
+}
@@ -0,0 +1,376 @@
+use crate::psp::{PaymentServiceProvider, PspError, RefundRequest};
+use events_proto::events_v1;
+use ledger::domain::money::to_minor_units;
+use ledger::domain::refund::{Refund, Status};
+use ledger::repo::LedgerRepository;
+use std::time::Duration;
+
+pub struct RefundService<R, P>
+where
+    R: LedgerRepository,
+    P: PaymentServiceProvider,
+{
+    repo: R,
+    psp: P,
+    max_attempts: u32,
+    backoff: Duration,
+}
+
+impl<R, P> RefundService<R, P>
+where
+    R: LedgerRepository,
+    P: PaymentServiceProvider,
+{
+    pub fn new(repo: R, psp: P, max_attempts: u32, backoff: Duration) -> Self {
+        Self {
+            repo,
+            psp,
+            max_attempts: max_attempts.max(1),
+            backoff,
+        }
+    }
+
+    /// process_refund reserves the refund in the ledger, asks the PSP to execute it and posts
+    /// the reversing entries once the PSP confirms. Redelivered events are safe: the ledger
+    /// returns the existing refund and the PSP deduplicates on the refund id.
+    ///
+    /// A declined refund is recorded as failed and returned. If the PSP stays unavailable the
+    /// refund is left pending and an error is returned so the event can be replayed.
+    pub async fn process_refund(&self, event: &events_v1::Refund) -> anyhow::Result<Refund> {
+        let refund = parse_refund_event(event)?;
+
+        let refund = self.repo.begin_refund(&refund).await?;
+        if refund.status == Status::Succeeded {
+            tracing::info!(refund_id = %refund.id, "refund already processed, skipping");
+            return Ok(refund);
+        }
+
+        let request = RefundRequest {
+            refund_id: refund.id,
+            transaction_id: refund.transaction_id,
+            amount_minor: refund.amount_minor,
+            currency: refund.currency.clone(),
+        };
+
+        let mut attempt = 1;
+        loop {
+            match self.psp.refund(&request).await {
+                Ok(response) => {
+                    return self
+                        .repo
+                        .complete_refund(refund.id, response.psp_reference.as_str())
+                        .await;
+                }
+                Err(PspError::Declined(reason)) => {
+                    tracing::warn!(refund_id = %refund.id, "refund declined by PSP: {reason}");
+                    return self.repo.fail_refund(refund.id, reason.as_str()).await;
+                }
+                Err(PspError::Unavailable(reason)) if attempt < self.max_attempts => {
+                    tracing::warn!(
+                        refund_id = %refund.id,
+                        attempt,
+                        "PSP unavailable, retrying refund: {reason}"
+                    );
+                    tokio::time::sleep(self.backoff * attempt).await;
+                    attempt += 1;
+                }
+                Err(e) => anyhow::bail!(
+                    "failed to refund {} after {attempt} attempts, left pending: {e}",
+                    refund.id
+                ),
+            }
+        }
+    }
+}
+
+fn parse_refund_event(event: &events_v1::Refund) -> anyhow::Result<Refund> {
+    let id = match uuid::Uuid::parse_str(event.id.as_str()) {
+        Ok(id) => id,
+        Err(e) => anyhow::bail!("failed to parse refund id {}: {e}", event.id),
+    };
+    let transaction_id = match uuid::Uuid::parse_str(event.transaction_id.as_str()) {
+        Ok(transaction_id) => transaction_id,
+        Err(e) => anyhow::bail!(
+            "failed to parse transaction_id {}: {e}",
+            event.transaction_id
+        ),
+    };
+    let amount = match &event.amount {
+        Some(amount) => amount,
+        None => anyhow::bail!("refund {} has no amount", event.id),
+    };
+    let reason = events_v1::RefundReason::try_from(event.reason)
+        .unwrap_or(events_v1::RefundReason::Unspecified);
+
+    let amount_minor = match to_minor_units(amount.units, amount.nanos, &amount.currency_code) {
+        Ok(amount_minor) => amount_minor,
+        Err(e) => anyhow::bail!("refund {} has an invalid amount: {e}", event.id),
+    };
+
+    Ok(Refund::new(
+        id,
+        transaction_id,
+        amount_minor,
+        amount.currency_code.to_uppercase(),
+        reason
+            .as_str_name()
+            .trim_start_matches("REFUND_REASON_")
+            .to_lowercase(),
+    ))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::psp::RefundResponse;
+    use async_trait::async_trait;
+    use events_proto::events_v1::google::r#type::Money;
+    use ledger::domain::refund::RefundError;
+    use ledger::domain::transaction;
+    use ledger::repo::{LedgerReader, PgLedgerRepository, RefundReader, RefundWriter};
+    use ledger::test_util::seed_transaction;
+    use std::sync::Mutex;
+
+    /// ScriptedPsp answers refunds from a script, then succeeds once the script runs out.
+    #[derive(Default)]
+    pub(crate) struct ScriptedPsp {
+        pub script: Mutex<Vec<PspError>>,
+        pub requests: Mutex<Vec<RefundRequest>>,
+    }
+
+    impl ScriptedPsp {
+        pub(crate) fn new(script: Vec<PspError>) -> Self {
+            Self {
+                script: Mutex::new(script),
+                requests: Mutex::new(vec![]),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl PaymentServiceProvider for ScriptedPsp {
+        async fn refund(&self, request: &RefundRequest) -> Result<RefundResponse, PspError> {
+            self.requests.lock().unwrap().push(request.clone());
+            let mut script = self.script.lock().unwrap();
+            match script.is_empty() {
+                true => Ok(RefundResponse {
+                    psp_reference: format!("psp-{}", request.refund_id),
+                }),
+                false => Err(script.remove(0)),
+            }
+        }
+    }
+
+    pub(crate) fn refund_event(transaction_id: uuid::Uuid, units: i64) -> events_v1::Refund {
+        events_v1::Refund {
+            id: uuid::Uuid::new_v4().to_string(),
+            transaction_id: transaction_id.to_string(),
+            amount: Some(Money {
+                currency_code: "USD".to_string(),
+                units,
+                nanos: 0,
+            }),
+            reason: events_v1::RefundReason::RequestedByCustomer as i32,
+            ..Default::default()
+        }
+    }
+
+    async fn setup(
+        pool: sqlx::PgPool,
+        psp: ScriptedPsp,
+    ) -> (
+        RefundService<PgLedgerRepository, ScriptedPsp>,
+        PgLedgerRepository,
+    ) {
+        let repo =
+            PgLedgerRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let service = RefundService::new(repo.clone(), psp, 3, Duration::from_millis(1));
+
+        (service, repo)
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_process_partial_then_full_refund(pool: sqlx::PgPool) {
+        // arrange
+        let (service, repo) = setup(pool, ScriptedPsp::default()).await;
+        let transaction = seed_transaction(&repo, 1000, transaction::Status::Success).await;
+
+        // act
+        let first = service
+            .process_refund(&refund_event(transaction.id, 4))
+            .await
+            .unwrap();
+        let status_after_first = repo
+            .get_transaction_by_id(transaction.id)
+            .await
+            .unwrap()
+            .status;
+        let second = service
+            .process_refund(&refund_event(transaction.id, 6))
+            .await
+            .unwrap();
+
+        // assert
+        assert_eq!(first.status, Status::Succeeded);
+        assert_eq!(first.amount_minor, 400);
+        assert_eq!(first.reason, "requested_by_customer");
+        assert_eq!(status_after_first, transaction::Status::Refund);
+        assert_eq!(second.status, Status::Succeeded);
+        assert_eq!(
+            repo.get_transaction_by_id(transaction.id)
+                .await
+                .unwrap()
+                .status,
+            transaction::Status::Refunded
+        );
+        assert_eq!(
+            repo.get_entries_by_transaction_id(transaction.id)
+                .await
+                .unwrap()
+                .len(),
+            6
+        );
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_skip_redelivered_refund(pool: sqlx::PgPool) {
+        // arrange
+        let (service, repo) = setup(pool, ScriptedPsp::default()).await;
+        let transaction = seed_transaction(&repo, 1000, transaction::Status::Success).await;
+        let event = refund_event(transaction.id, 10);
+        service.process_refund(&event).await.unwrap();
+
+        // act
+        let redelivered = service.process_refund(&event).await.unwrap();
+
+        // assert - the PSP is only called once
+        assert_eq!(redelivered.status, Status::Succeeded);
+        assert_eq!(service.psp.requests.lock().unwrap().len(), 1);
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_retry_with_same_idempotency_key_when_psp_is_unavailable(
+        pool: sqlx::PgPool,
+    ) {
+        // arrange
+        let psp = ScriptedPsp::new(vec![
+            PspError::Unavailable("timeout".to_string()),
+            PspError::Unavailable("timeout".to_string()),
+        ]);
+        let (service, repo) = setup(pool, psp).await;
+        let transaction = seed_transaction(&repo, 1000, transaction::Status::Success).await;
+
+        // act
+        let refund = service
+            .process_refund(&refund_event(transaction.id, 10))
+            .await
+            .unwrap();
+
+        // assert
+        assert_eq!(refund.status, Status::Succeeded);
+        let requests = service.psp.requests.lock().unwrap();
+        assert_eq!(requests.len(), 3);
+        assert!(requests.iter().all(|r| r.refund_id == refund.id));
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_record_declined_refund(pool: sqlx::PgPool) {
+        // arrange
+        let psp = ScriptedPsp::new(vec![PspError::Declined("card closed".to_string())]);
+        let (service, repo) = setup(pool, psp).await;
+        let transaction = seed_transaction(&repo, 1000, transaction::Status::Success).await;
+
+        // act
+        let refund = service
+            .process_refund(&refund_event(transaction.id, 10))
+            .await
+            .unwrap();
+
+        // assert
+        assert_eq!(refund.status, Status::Failed);
+        assert_eq!(refund.failure_reason.as_deref(), Some("card closed"));
+        assert_eq!(
+            repo.get_transaction_by_id(transaction.id)
+                .await
+                .unwrap()
+                .status,
+            transaction::Status::Success
+        );
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn error_when_psp_stays_unavailable(pool: sqlx::PgPool) {
+        // arrange
+        let psp = ScriptedPsp::new(vec![
+            PspError::Unavailable("timeout".to_string()),
+            PspError::Unavailable("timeout".to_string()),
+            PspError::Unavailable("timeout".to_string()),
+        ]);
+        let (service, repo) = setup(pool, psp).await;
+        let transaction = seed_transaction(&repo, 1000, transaction::Status::Success).await;
+        let event = refund_event(transaction.id, 10);
+
+        // act
+        let result = service.process_refund(&event).await;
+
+        // assert - the refund stays pending so a replay picks it up
+        assert!(result.is_err());
+        let refund = repo
+            .get_refund_by_id(uuid::Uuid::parse_str(&event.id).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(refund.status, Status::Pending);
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn error_when_refund_exceeds_original_amount(pool: sqlx::PgPool) {
+        // arrange
+        let (service, repo) = setup(pool, ScriptedPsp::default()).await;
+        let transaction = seed_transaction(&repo, 1000, transaction::Status::Success).await;
+
+        // act
+        let result = service
+            .process_refund(&refund_event(transaction.id, 11))
+            .await;
+
+        // assert
+        assert!(matches!(
+            result.unwrap_err().downcast_ref::<RefundError>(),
+            Some(RefundError::ExceedsOriginal { .. })
+        ));
+        assert!(service.psp.requests.lock().unwrap().is_empty());
+    }
+
+    #[sqlx::test(migrations = "../migrations/ledger")]
+    async fn successfully_process_approved_manual_refund(pool: sqlx::PgPool) {
+        // arrange - a manual refund waiting for its second approver
+        let (service, repo) = setup(pool, ScriptedPsp::default()).await;
+        let transaction = seed_transaction(&repo, 1000, transaction::Status::Success).await;
+        let event = refund_event(transaction.id, 8);
+        let mut manual = Refund::new(
+            uuid::Uuid::parse_str(&event.id).unwrap(),
+            transaction.id,
+            800,
+            "USD",
+            "requested_by_customer",
+        );
+        manual.status = Status::AwaitingApproval;
+        manual.requested_by = Some("alice".to_string());
+        repo.request_refund(&manual).await.unwrap();
+
+        // act
+        let before_approval = service.process_refund(&event).await;
+        repo.decide_refund(manual.id, "bob", true).await.unwrap();
+        let refund = service.process_refund(&event).await.unwrap();
+
+        // assert
+        assert!(matches!(
+            before_approval.unwrap_err().downcast_ref::<RefundError>(),
+            Some(RefundError::NotApproved(_, Status::AwaitingApproval))
+        ));
+        assert_eq!(refund.status, Status::Succeeded);
+        assert_eq!(refund.requested_by.as_deref(), Some("alice"));
+        assert_eq!(refund.approved_by.as_deref(), Some("bob"));
+        assert_eq!(service.psp.requests.lock().unwrap().len(), 1);
+    }
+}
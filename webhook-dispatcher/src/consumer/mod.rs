@@ -0,0 +1,133 @@
+use crate::ledger::TransactionLookup;
+use crate::repo::WebhookRepository;
+use crate::sender::WebhookSender;
+use crate::service::WebhookService;
+use async_trait::async_trait;
+use common::kafka::{
+    FRAUD_DETECTED_EVENTS_TOPIC, MessageHandler, REFUND_EVENTS_TOPIC,
+    SETTLEMENT_RESULT_EVENTS_TOPIC,
+};
+use events_proto::events_v1;
+use prost::Message;
+
+#[async_trait]
+impl<R, S, L> MessageHandler for WebhookService<R, S, L>
+where
+    R: WebhookRepository,
+    S: WebhookSender,
+    L: TransactionLookup,
+{
+    async fn handle(&self, topic: &str, payload: &[u8]) -> anyhow::Result<()> {
+        let deliveries = match topic {
+            SETTLEMENT_RESULT_EVENTS_TOPIC => {
+                let settlement = match events_v1::Settlement::decode(payload) {
+                    Ok(settlement) => settlement,
+                    Err(e) => anyhow::bail!("failed to decode settlement event: {e}"),
+                };
+                self.record_settlement(&settlement).await?
+            }
+            FRAUD_DETECTED_EVENTS_TOPIC => {
+                let fraud = match events_v1::Fraud::decode(payload) {
+                    Ok(fraud) => fraud,
+                    Err(e) => anyhow::bail!("failed to decode fraud event: {e}"),
+                };
+                self.record_fraud(&fraud).await?
+            }
+            REFUND_EVENTS_TOPIC => {
+                let refund = match events_v1::Refund::decode(payload) {
+                    Ok(refund) => refund,
+                    Err(e) => anyhow::bail!("failed to decode refund event: {e}"),
+                };
+                self.record_refund(&refund).await?
+            }
+            _ => anyhow::bail!("unexpected topic {topic}"),
+        };
+
+        if let Some(delivery) = deliveries.first() {
+            tracing::info!(
+                event_id = %delivery.event_id,
+                event_type = delivery.event_type.as_str(),
+                endpoints = deliveries.len(),
+                "queued webhook deliveries"
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::event::tests::settlement_event;
+    use crate::repo::PgWebhookRepository;
+    use crate::service::tests::{ScriptedSender, StaticLookup, policy};
+
+    async fn setup(
+        pool: sqlx::PgPool,
+    ) -> WebhookService<PgWebhookRepository, ScriptedSender, StaticLookup> {
+        let repo =
+            PgWebhookRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        WebhookService::new(
+            repo,
+            ScriptedSender::default(),
+            StaticLookup(None),
+            policy(),
+        )
+    }
+
+    #[sqlx::test(migrations = "../migrations/api")]
+    async fn successfully_handle_settlement_event(pool: sqlx::PgPool) {
+        let service = setup(pool).await;
+        let settlement = settlement_event(
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+            events_v1::SettlementStatus::Settled,
+        );
+
+        let result = service
+            .handle(SETTLEMENT_RESULT_EVENTS_TOPIC, &settlement.encode_to_vec())
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[sqlx::test(migrations = "../migrations/api")]
+    async fn test_handle_errors(pool: sqlx::PgPool) {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            topic: &'static str,
+            payload: Vec<u8>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "error when payload is not a refund",
+                topic: REFUND_EVENTS_TOPIC,
+                payload: b"\xff\xff".to_vec(),
+            },
+            TestCase {
+                name: "error when flagged transaction is unknown to the ledger",
+                topic: FRAUD_DETECTED_EVENTS_TOPIC,
+                payload: events_v1::Fraud {
+                    transaction_id: uuid::Uuid::new_v4().to_string(),
+                    is_fraud: true,
+                    ..Default::default()
+                }
+                .encode_to_vec(),
+            },
+            TestCase {
+                name: "error when topic is unexpected",
+                topic: "transaction.events",
+                payload: vec![],
+            },
+        ];
+
+        let service = setup(pool).await;
+        for test_case in test_cases {
+            let result = service.handle(test_case.topic, &test_case.payload).await;
+            assert!(result.is_err(), "{}", test_case.name);
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use crate::domain::event::WebhookEvent;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "webhook_delivery_status", rename_all = "snake_case")]
+pub enum Status {
+    Pending,
+    Succeeded,
+    /// Failed deliveries ran out of attempts, they are only sent again when redelivered.
+    Failed,
+}
+
+impl AsRef<str> for Status {
+    fn as_ref(&self) -> &str {
+        match self {
+            Status::Pending => "pending",
+            Status::Succeeded => "succeeded",
+            Status::Failed => "failed",
+        }
+    }
+}
+
+impl FromStr for Status {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(Status::Pending),
+            "succeeded" => Ok(Status::Succeeded),
+            "failed" => Ok(Status::Failed),
+            _ => anyhow::bail!("unknown delivery status {s}"),
+        }
+    }
+}
+
+/// RetryPolicy backs off exponentially between attempts of a delivery.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// backoff returns the wait after the given number of failed attempts, doubling from the
+    /// base backoff.
+    pub fn backoff(&self, attempts: u32) -> Duration {
+        let exponent = attempts.saturating_sub(1).min(31);
+        self.base_backoff
+            .saturating_mul(1 << exponent)
+            .min(self.max_backoff)
+    }
+}
+
+/// Delivery is an event to be sent to one endpoint, together with the outcome of its last
+/// attempt.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct Delivery {
+    pub id: uuid::Uuid,
+    pub endpoint_id: uuid::Uuid,
+    pub event_id: uuid::Uuid,
+    pub event_type: String,
+    /// payload is the json body exactly as it is signed and sent.
+    pub payload: String,
+    pub status: Status,
+    pub attempts: i32,
+    pub next_attempt_at: chrono::DateTime<chrono::Utc>,
+    pub last_response_status: Option<i32>,
+    pub last_error: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Attempt is one try at sending a delivery, kept as the delivery log.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct Attempt {
+    pub delivery_id: uuid::Uuid,
+    pub attempt: i32,
+    pub response_status: Option<i32>,
+    pub error: Option<String>,
+    pub attempted_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// DeliveryFilter narrows the delivery log, fields which are not set match every delivery.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeliveryFilter {
+    /// principal limits the log to the deliveries of the principal's endpoints.
+    pub principal: Option<String>,
+    pub endpoint_id: Option<uuid::Uuid>,
+    pub status: Option<Status>,
+    pub limit: i64,
+}
+
+impl Delivery {
+    pub fn new(endpoint_id: uuid::Uuid, event: &WebhookEvent) -> anyhow::Result<Self> {
+        let payload = match serde_json::to_string(event) {
+            Ok(payload) => payload,
+            Err(e) => anyhow::bail!("failed to serialize event {}: {e}", event.id),
+        };
+        let now = chrono::Utc::now();
+
+        Ok(Self {
+            id: uuid::Uuid::new_v4(),
+            endpoint_id,
+            event_id: event.id,
+            event_type: event.event_type.to_string(),
+            payload,
+            status: Status::Pending,
+            attempts: 0,
+            next_attempt_at: now,
+            last_response_status: None,
+            last_error: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// record_attempt applies the outcome of sending the delivery, the response status of the
+    /// endpoint or why no response was received. Anything but a 2xx is retried until the
+    /// policy runs out of attempts.
+    pub fn record_attempt(
+        &mut self,
+        outcome: Result<u16, String>,
+        now: chrono::DateTime<chrono::Utc>,
+        policy: &RetryPolicy,
+    ) -> Attempt {
+        self.attempts += 1;
+        let (response_status, error) = match outcome {
+            Ok(status) if (200..300).contains(&status) => (Some(status as i32), None),
+            Ok(status) => (
+                Some(status as i32),
+                Some(format!("endpoint responded with status {status}")),
+            ),
+            Err(e) => (None, Some(e)),
+        };
+
+        self.status = match (&error, self.attempts as u32 >= policy.max_attempts) {
+            (None, _) => Status::Succeeded,
+            (Some(_), true) => Status::Failed,
+            (Some(_), false) => {
+                let backoff = policy.backoff(self.attempts as u32);
+                self.next_attempt_at =
+                    now + chrono::Duration::from_std(backoff).unwrap_or(chrono::Duration::MAX);
+                Status::Pending
+            }
+        };
+        self.last_response_status = response_status;
+        self.last_error = error.clone();
+        self.updated_at = now;
+
+        Attempt {
+            delivery_id: self.id,
+            attempt: self.attempts,
+            response_status,
+            error,
+            attempted_at: now,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            base_backoff: Duration::from_secs(60),
+            max_backoff: Duration::from_secs(90),
+        }
+    }
+
+    #[test]
+    fn test_backoff() {
+        let policy = policy();
+
+        assert_eq!(policy.backoff(1), Duration::from_secs(60));
+        assert_eq!(policy.backoff(2), Duration::from_secs(90));
+        assert_eq!(policy.backoff(40), Duration::from_secs(90));
+    }
+
+    #[test]
+    fn test_record_attempt() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            attempts: i32,
+            outcome: Result<u16, String>,
+            expected_status: Status,
+            expected_next_attempt_in_seconds: i64,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "succeeded when endpoint responds with 2xx",
+                attempts: 0,
+                outcome: Ok(204),
+                expected_status: Status::Succeeded,
+                expected_next_attempt_in_seconds: 0,
+            },
+            TestCase {
+                name: "retry when endpoint responds with an error",
+                attempts: 0,
+                outcome: Ok(500),
+                expected_status: Status::Pending,
+                expected_next_attempt_in_seconds: 60,
+            },
+            TestCase {
+                name: "retry with backoff when endpoint is unreachable",
+                attempts: 1,
+                outcome: Err("connection refused".to_string()),
+                expected_status: Status::Pending,
+                expected_next_attempt_in_seconds: 90,
+            },
+            TestCase {
+                name: "failed when attempts are exhausted",
+                attempts: 2,
+                outcome: Ok(410),
+                expected_status: Status::Failed,
+                expected_next_attempt_in_seconds: 0,
+            },
+        ];
+
+        for test_case in test_cases {
+            // arrange
+            let now = chrono::Utc::now();
+            let mut delivery = Delivery {
+                id: uuid::Uuid::new_v4(),
+                endpoint_id: uuid::Uuid::new_v4(),
+                event_id: uuid::Uuid::new_v4(),
+                event_type: "transaction.settled".to_string(),
+                payload: "{}".to_string(),
+                status: Status::Pending,
+                attempts: test_case.attempts,
+                next_attempt_at: now,
+                last_response_status: None,
+                last_error: None,
+                created_at: now,
+                updated_at: now,
+            };
+            let is_success = test_case.outcome.as_ref().is_ok_and(|status| *status < 300);
+
+            // act
+            let attempt = delivery.record_attempt(test_case.outcome, now, &policy());
+
+            // assert
+            assert_eq!(
+                delivery.status, test_case.expected_status,
+                "{}",
+                test_case.name
+            );
+            assert_eq!(
+                delivery.next_attempt_at,
+                now + chrono::Duration::seconds(test_case.expected_next_attempt_in_seconds),
+                "{}",
+                test_case.name
+            );
+            assert_eq!(
+                attempt.attempt,
+                test_case.attempts + 1,
+                "{}",
+                test_case.name
+            );
+            assert_eq!(attempt.error.is_none(), is_success, "{}", test_case.name);
+            assert_eq!(attempt.error, delivery.last_error, "{}", test_case.name);
+        }
+    }
+}
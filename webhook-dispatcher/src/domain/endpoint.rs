@@ -0,0 +1,377 @@
+use crate::domain::event::EventType;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use ring::rand::{SecureRandom, SystemRandom};
+use std::fmt;
+use std::net::IpAddr;
+
+/// SECRET_PREFIX makes endpoint secrets recognisable, e.g. by secret scanners.
+pub const SECRET_PREFIX: &str = "whsec_";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EndpointError {
+    InvalidUrl(String),
+    InsecureUrl(String),
+    PrivateHost(String),
+    UnknownEventType(String),
+}
+
+impl fmt::Display for EndpointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EndpointError::InvalidUrl(url) => {
+                write!(f, "url {url} must be an absolute http or https url")
+            }
+            EndpointError::InsecureUrl(url) => write!(f, "url {url} must use https"),
+            EndpointError::PrivateHost(host) => {
+                write!(f, "host {host} is not a public address")
+            }
+            EndpointError::UnknownEventType(event_type) => {
+                write!(f, "unknown event type {event_type}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for EndpointError {}
+
+/// UrlPolicy decides which urls endpoints may be registered with and events sent to.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum UrlPolicy {
+    /// Public only allows https urls of public hosts, so merchants cannot reach internal services.
+    #[default]
+    Public,
+    /// AllowPrivate also allows http and loopback or private hosts, for local development.
+    AllowPrivate,
+}
+
+impl UrlPolicy {
+    /// check parses the url and rejects insecure schemes and hosts which are not public. Host
+    /// names are only resolved when sending, see [`UrlPolicy::allows_ip`].
+    pub fn check(&self, url: &str) -> Result<url::Url, EndpointError> {
+        let parsed = match url::Url::parse(url) {
+            Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => parsed,
+            _ => return Err(EndpointError::InvalidUrl(url.to_string())),
+        };
+        let host = match parsed.host() {
+            Some(host) => host,
+            None => return Err(EndpointError::InvalidUrl(url.to_string())),
+        };
+        if *self == UrlPolicy::AllowPrivate {
+            return Ok(parsed);
+        }
+
+        if parsed.scheme() != "https" {
+            return Err(EndpointError::InsecureUrl(url.to_string()));
+        }
+        let public = match host {
+            url::Host::Domain(domain) => {
+                let domain = domain.trim_end_matches('.').to_lowercase();
+                domain != "localhost" && !domain.ends_with(".localhost")
+            }
+            url::Host::Ipv4(ip) => self.allows_ip(IpAddr::V4(ip)),
+            url::Host::Ipv6(ip) => self.allows_ip(IpAddr::V6(ip)),
+        };
+        if !public {
+            return Err(EndpointError::PrivateHost(host.to_string()));
+        }
+
+        Ok(parsed)
+    }
+
+    /// allows_ip rejects loopback, private, link-local, unspecified and other non public
+    /// addresses unless private hosts are allowed.
+    pub fn allows_ip(&self, ip: IpAddr) -> bool {
+        if *self == UrlPolicy::AllowPrivate {
+            return true;
+        }
+
+        match ip {
+            IpAddr::V4(ip) => {
+                let [a, b, ..] = ip.octets();
+                !(ip.is_loopback()
+                    || ip.is_private()
+                    || ip.is_link_local()
+                    || ip.is_unspecified()
+                    || ip.is_broadcast()
+                    || ip.is_multicast()
+                    || ip.is_documentation()
+                    // shared address space, 100.64.0.0/10
+                    || (a == 100 && (b & 0xc0) == 64)
+                    // this network, 0.0.0.0/8
+                    || a == 0)
+            }
+            IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+                Some(ip) => self.allows_ip(IpAddr::V4(ip)),
+                None => {
+                    !(ip.is_loopback()
+                        || ip.is_unspecified()
+                        || ip.is_multicast()
+                        || ip.is_unique_local()
+                        || ip.is_unicast_link_local())
+                }
+            },
+        }
+    }
+}
+
+/// Endpoint is a merchant url events are POSTed to. It keeps the accounts of the principal
+/// at registration, events on other accounts are not sent to it.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct Endpoint {
+    pub id: uuid::Uuid,
+    pub principal: String,
+    pub url: String,
+    /// secret is shared with the merchant to verify signatures, so unlike api keys it is
+    /// stored as is.
+    pub secret: String,
+    /// event_types the endpoint subscribes to, every event type when empty.
+    pub event_types: Vec<String>,
+    pub account_ids: Vec<uuid::Uuid>,
+    pub all_accounts: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub disabled_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl Endpoint {
+    /// new validates the url against the policy and the event types, and generates the signing
+    /// secret.
+    pub fn new(
+        principal: &str,
+        url: &str,
+        event_types: Vec<String>,
+        account_ids: Vec<uuid::Uuid>,
+        all_accounts: bool,
+        policy: UrlPolicy,
+    ) -> anyhow::Result<Self> {
+        policy.check(url)?;
+        for event_type in &event_types {
+            if event_type.parse::<EventType>().is_err() {
+                return Err(EndpointError::UnknownEventType(event_type.clone()).into());
+            }
+        }
+
+        let mut secret = [0u8; 32];
+        if SystemRandom::new().fill(&mut secret).is_err() {
+            anyhow::bail!("failed to generate endpoint secret");
+        }
+
+        Ok(Self {
+            id: uuid::Uuid::new_v4(),
+            principal: principal.to_string(),
+            url: url.to_string(),
+            secret: format!("{SECRET_PREFIX}{}", URL_SAFE_NO_PAD.encode(secret)),
+            event_types,
+            account_ids,
+            all_accounts,
+            created_at: chrono::Utc::now(),
+            disabled_at: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            url: &'static str,
+            event_types: Vec<&'static str>,
+            policy: UrlPolicy,
+            expected: Result<(), EndpointError>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "successfully create endpoint for every event",
+                url: "https://merchant.example/webhooks",
+                event_types: vec![],
+                policy: UrlPolicy::Public,
+                expected: Ok(()),
+            },
+            TestCase {
+                name: "successfully create endpoint for some events",
+                url: "https://93.184.215.14/hooks",
+                event_types: vec!["transaction.settled", "transaction.refunded"],
+                policy: UrlPolicy::Public,
+                expected: Ok(()),
+            },
+            TestCase {
+                name: "successfully create endpoint on localhost when private hosts are allowed",
+                url: "http://localhost:9000/hooks",
+                event_types: vec![],
+                policy: UrlPolicy::AllowPrivate,
+                expected: Ok(()),
+            },
+            TestCase {
+                name: "error when url is not http",
+                url: "ftp://merchant.example",
+                event_types: vec![],
+                policy: UrlPolicy::AllowPrivate,
+                expected: Err(EndpointError::InvalidUrl(
+                    "ftp://merchant.example".to_string(),
+                )),
+            },
+            TestCase {
+                name: "error when url has no host",
+                url: "https://",
+                event_types: vec![],
+                policy: UrlPolicy::Public,
+                expected: Err(EndpointError::InvalidUrl("https://".to_string())),
+            },
+            TestCase {
+                name: "error when url is http",
+                url: "http://merchant.example",
+                event_types: vec![],
+                policy: UrlPolicy::Public,
+                expected: Err(EndpointError::InsecureUrl(
+                    "http://merchant.example".to_string(),
+                )),
+            },
+            TestCase {
+                name: "error when host is localhost",
+                url: "https://localhost:5432",
+                event_types: vec![],
+                policy: UrlPolicy::Public,
+                expected: Err(EndpointError::PrivateHost("localhost".to_string())),
+            },
+            TestCase {
+                name: "error when host is the metadata service",
+                url: "https://169.254.169.254/latest/meta-data",
+                event_types: vec![],
+                policy: UrlPolicy::Public,
+                expected: Err(EndpointError::PrivateHost("169.254.169.254".to_string())),
+            },
+            TestCase {
+                name: "error when host is a private address",
+                url: "https://10.0.0.7/hooks",
+                event_types: vec![],
+                policy: UrlPolicy::Public,
+                expected: Err(EndpointError::PrivateHost("10.0.0.7".to_string())),
+            },
+            TestCase {
+                name: "error when host is the ipv6 loopback",
+                url: "https://[::1]:8080",
+                event_types: vec![],
+                policy: UrlPolicy::Public,
+                expected: Err(EndpointError::PrivateHost("[::1]".to_string())),
+            },
+            TestCase {
+                name: "error when event type is unknown",
+                url: "https://merchant.example",
+                event_types: vec!["transaction.created"],
+                policy: UrlPolicy::Public,
+                expected: Err(EndpointError::UnknownEventType(
+                    "transaction.created".to_string(),
+                )),
+            },
+        ];
+
+        for test_case in test_cases {
+            // act
+            let result = Endpoint::new(
+                "merchant-a",
+                test_case.url,
+                test_case
+                    .event_types
+                    .iter()
+                    .map(|t| t.to_string())
+                    .collect(),
+                vec![],
+                false,
+                test_case.policy,
+            );
+
+            // assert
+            match test_case.expected {
+                Ok(()) => {
+                    let endpoint = result.unwrap();
+                    assert!(
+                        endpoint.secret.starts_with(SECRET_PREFIX),
+                        "{}",
+                        test_case.name
+                    );
+                }
+                Err(expected) => assert_eq!(
+                    result.unwrap_err().downcast_ref::<EndpointError>(),
+                    Some(&expected),
+                    "{}",
+                    test_case.name
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn test_allows_ip() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            ip: &'static str,
+            expected: bool,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "allow public ipv4 address",
+                ip: "93.184.215.14",
+                expected: true,
+            },
+            TestCase {
+                name: "allow public ipv6 address",
+                ip: "2606:2800:21f:cb07:6820:80da:af6b:8b2c",
+                expected: true,
+            },
+            TestCase {
+                name: "reject loopback",
+                ip: "127.0.0.1",
+                expected: false,
+            },
+            TestCase {
+                name: "reject private address",
+                ip: "192.168.1.10",
+                expected: false,
+            },
+            TestCase {
+                name: "reject link-local address",
+                ip: "169.254.169.254",
+                expected: false,
+            },
+            TestCase {
+                name: "reject unspecified address",
+                ip: "0.0.0.0",
+                expected: false,
+            },
+            TestCase {
+                name: "reject shared address space",
+                ip: "100.64.0.1",
+                expected: false,
+            },
+            TestCase {
+                name: "reject ipv6 unique local address",
+                ip: "fd00::1",
+                expected: false,
+            },
+            TestCase {
+                name: "reject ipv4 mapped loopback",
+                ip: "::ffff:127.0.0.1",
+                expected: false,
+            },
+        ];
+
+        for test_case in test_cases {
+            let ip: IpAddr = test_case.ip.parse().unwrap();
+            assert_eq!(
+                UrlPolicy::Public.allows_ip(ip),
+                test_case.expected,
+                "{}",
+                test_case.name
+            );
+            assert!(UrlPolicy::AllowPrivate.allows_ip(ip), "{}", test_case.name);
+        }
+    }
+}
@@ -0,0 +1,340 @@
+use common::money::Money;
+use events_proto::events_v1;
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
+
+/// EventType is a transaction status change merchants can subscribe to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum EventType {
+    #[serde(rename = "transaction.settled")]
+    TransactionSettled,
+    #[serde(rename = "transaction.failed")]
+    TransactionFailed,
+    #[serde(rename = "transaction.fraud_flagged")]
+    TransactionFraudFlagged,
+    #[serde(rename = "transaction.refunded")]
+    TransactionRefunded,
+}
+
+impl EventType {
+    pub const ALL: [EventType; 4] = [
+        EventType::TransactionSettled,
+        EventType::TransactionFailed,
+        EventType::TransactionFraudFlagged,
+        EventType::TransactionRefunded,
+    ];
+}
+
+impl AsRef<str> for EventType {
+    fn as_ref(&self) -> &str {
+        match self {
+            EventType::TransactionSettled => "transaction.settled",
+            EventType::TransactionFailed => "transaction.failed",
+            EventType::TransactionFraudFlagged => "transaction.fraud_flagged",
+            EventType::TransactionRefunded => "transaction.refunded",
+        }
+    }
+}
+
+impl fmt::Display for EventType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+impl FromStr for EventType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match EventType::ALL
+            .into_iter()
+            .find(|event_type| event_type.as_ref() == s)
+        {
+            Some(event_type) => Ok(event_type),
+            None => anyhow::bail!("unknown event type {s}"),
+        }
+    }
+}
+
+/// TransactionData is the transaction an event is about, as sent to merchants.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TransactionData {
+    pub transaction_id: String,
+    pub debit_account_id: String,
+    pub credit_account_id: String,
+    pub amount_minor: i64,
+    pub currency: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refund_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refund_reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fraud_score: Option<f32>,
+}
+
+/// WebhookEvent is the payload POSTed to merchant endpoints. Its id is derived from the source
+/// event, so a redelivered kafka message results in the same event.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WebhookEvent {
+    pub id: uuid::Uuid,
+    #[serde(rename = "type")]
+    pub event_type: EventType,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub data: TransactionData,
+}
+
+/// TransactionSummary is what the ledger knows of a transaction, used for events which only
+/// carry the transaction id.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionSummary {
+    pub debit_account_id: String,
+    pub credit_account_id: String,
+    pub amount_minor: i64,
+    pub currency: String,
+}
+
+/// parse_amount returns the amount in minor units of its currency, rejecting amounts which are
+/// not whole minor units of an ISO 4217 currency.
+fn parse_amount(
+    amount: Option<&events_v1::google::r#type::Money>,
+) -> anyhow::Result<(i64, String)> {
+    match amount {
+        Some(amount) => {
+            match Money::from_units_nanos(amount.units, amount.nanos, &amount.currency_code) {
+                Ok(money) => Ok((money.amount_minor(), money.currency().code().to_string())),
+                Err(e) => anyhow::bail!("invalid amount: {e}"),
+            }
+        }
+        None => Ok((0, String::new())),
+    }
+}
+
+fn parse_timestamp(timestamp: Option<&prost_types::Timestamp>) -> chrono::DateTime<chrono::Utc> {
+    timestamp
+        .and_then(|timestamp| {
+            chrono::DateTime::from_timestamp(timestamp.seconds, timestamp.nanos as u32)
+        })
+        .unwrap_or_else(chrono::Utc::now)
+}
+
+fn event_id(event_type: EventType, source_id: &str) -> uuid::Uuid {
+    uuid::Uuid::new_v5(
+        &uuid::Uuid::NAMESPACE_URL,
+        format!("pasys:webhook:{event_type}:{source_id}").as_bytes(),
+    )
+}
+
+impl WebhookEvent {
+    /// from_settlement turns settled and failed settlements into events, pending settlements
+    /// are not a status change merchants are told about.
+    pub fn from_settlement(settlement: &events_v1::Settlement) -> anyhow::Result<Option<Self>> {
+        let event_type = match events_v1::SettlementStatus::try_from(settlement.settlement_status) {
+            Ok(events_v1::SettlementStatus::Settled) => EventType::TransactionSettled,
+            Ok(events_v1::SettlementStatus::Failed) => EventType::TransactionFailed,
+            _ => return Ok(None),
+        };
+        let (amount_minor, currency) = parse_amount(settlement.amount.as_ref())?;
+
+        Ok(Some(Self {
+            id: event_id(event_type, settlement.transaction_id.as_str()),
+            event_type,
+            created_at: parse_timestamp(settlement.created_at.as_ref()),
+            data: TransactionData {
+                transaction_id: settlement.transaction_id.clone(),
+                debit_account_id: settlement.debit_account_id.clone(),
+                credit_account_id: settlement.credit_account_id.clone(),
+                amount_minor,
+                currency,
+                refund_id: None,
+                refund_reason: None,
+                fraud_score: None,
+            },
+        }))
+    }
+
+    /// from_fraud turns transactions flagged as fraud into events, the fraud event only
+    /// carries the transaction id so the rest comes from the ledger.
+    pub fn from_fraud(fraud: &events_v1::Fraud, transaction: TransactionSummary) -> Option<Self> {
+        if !fraud.is_fraud {
+            return None;
+        }
+        let event_type = EventType::TransactionFraudFlagged;
+
+        Some(Self {
+            id: event_id(event_type, fraud.transaction_id.as_str()),
+            event_type,
+            created_at: parse_timestamp(fraud.created_at.as_ref()),
+            data: TransactionData {
+                transaction_id: fraud.transaction_id.clone(),
+                debit_account_id: transaction.debit_account_id,
+                credit_account_id: transaction.credit_account_id,
+                amount_minor: transaction.amount_minor,
+                currency: transaction.currency,
+                refund_id: None,
+                refund_reason: None,
+                fraud_score: Some(fraud.score),
+            },
+        })
+    }
+
+    /// from_refund turns an approved refund into an event. Refund events reverse the accounts
+    /// of the transaction, the event reports them as they were on the transaction.
+    pub fn from_refund(refund: &events_v1::Refund) -> anyhow::Result<Self> {
+        let event_type = EventType::TransactionRefunded;
+        let (amount_minor, currency) = parse_amount(refund.amount.as_ref())?;
+        let reason = events_v1::RefundReason::try_from(refund.reason)
+            .unwrap_or(events_v1::RefundReason::Unspecified)
+            .as_str_name()
+            .trim_start_matches("REFUND_REASON_")
+            .to_lowercase();
+
+        Ok(Self {
+            id: event_id(event_type, refund.id.as_str()),
+            event_type,
+            created_at: parse_timestamp(refund.created_at.as_ref()),
+            data: TransactionData {
+                transaction_id: refund.transaction_id.clone(),
+                debit_account_id: refund.credit_account_id.clone(),
+                credit_account_id: refund.debit_account_id.clone(),
+                amount_minor,
+                currency,
+                refund_id: Some(refund.id.clone()),
+                refund_reason: Some(reason),
+                fraud_score: None,
+            },
+        })
+    }
+
+    /// account_ids are the accounts whose endpoints receive the event.
+    pub fn account_ids(&self) -> Vec<uuid::Uuid> {
+        [
+            self.data.debit_account_id.as_str(),
+            self.data.credit_account_id.as_str(),
+        ]
+        .into_iter()
+        .filter_map(|id| uuid::Uuid::parse_str(id).ok())
+        .collect()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) fn settlement_event(
+        debit_account_id: uuid::Uuid,
+        credit_account_id: uuid::Uuid,
+        status: events_v1::SettlementStatus,
+    ) -> events_v1::Settlement {
+        events_v1::Settlement {
+            transaction_id: uuid::Uuid::new_v4().to_string(),
+            idempotency_key: uuid::Uuid::new_v4().to_string(),
+            debit_account_id: debit_account_id.to_string(),
+            credit_account_id: credit_account_id.to_string(),
+            amount: Some(events_v1::google::r#type::Money {
+                currency_code: "USD".to_string(),
+                units: 10,
+                nanos: 500_000_000,
+            }),
+            settlement_status: status as i32,
+            created_at: Some(prost_types::Timestamp {
+                seconds: 1_760_000_000,
+                nanos: 0,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_from_settlement() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            status: events_v1::SettlementStatus,
+            expected: Option<EventType>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "settled transaction",
+                status: events_v1::SettlementStatus::Settled,
+                expected: Some(EventType::TransactionSettled),
+            },
+            TestCase {
+                name: "failed transaction",
+                status: events_v1::SettlementStatus::Failed,
+                expected: Some(EventType::TransactionFailed),
+            },
+            TestCase {
+                name: "no event while settlement is pending",
+                status: events_v1::SettlementStatus::Pending,
+                expected: None,
+            },
+        ];
+
+        for test_case in test_cases {
+            // arrange
+            let settlement =
+                settlement_event(uuid::Uuid::new_v4(), uuid::Uuid::new_v4(), test_case.status);
+
+            // act
+            let event = WebhookEvent::from_settlement(&settlement).unwrap();
+
+            // assert
+            assert_eq!(
+                event.as_ref().map(|event| event.event_type),
+                test_case.expected,
+                "{}",
+                test_case.name
+            );
+            if let Some(event) = event {
+                assert_eq!(event.data.amount_minor, 1050, "{}", test_case.name);
+                assert_eq!(
+                    event,
+                    WebhookEvent::from_settlement(&settlement).unwrap().unwrap(),
+                    "{}",
+                    test_case.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn successfully_serialize_refund_event() {
+        // arrange
+        let debit_account_id = uuid::Uuid::new_v4();
+        let credit_account_id = uuid::Uuid::new_v4();
+        let refund = events_v1::Refund {
+            id: "refund-1".to_string(),
+            transaction_id: "transaction-1".to_string(),
+            idempotency_key: "key".to_string(),
+            debit_account_id: credit_account_id.to_string(),
+            credit_account_id: debit_account_id.to_string(),
+            amount: Some(events_v1::google::r#type::Money {
+                currency_code: "USD".to_string(),
+                units: 2,
+                nanos: 0,
+            }),
+            created_at: None,
+            reason: events_v1::RefundReason::RequestedByCustomer as i32,
+        };
+
+        // act
+        let event = WebhookEvent::from_refund(&refund).unwrap();
+
+        // assert
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "transaction.refunded");
+        assert_eq!(
+            json["data"]["debit_account_id"],
+            debit_account_id.to_string()
+        );
+        assert_eq!(json["data"]["refund_reason"], "requested_by_customer");
+        assert_eq!(json["data"]["amount_minor"], 200);
+        assert!(json["data"].get("fraud_score").is_none());
+        assert_eq!(
+            event.account_ids(),
+            vec![debit_account_id, credit_account_id]
+        );
+    }
+}
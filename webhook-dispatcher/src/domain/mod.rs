@@ -0,0 +1,4 @@
+pub mod delivery;
+pub mod endpoint;
+pub mod event;
+pub mod signature;
@@ -0,0 +1,152 @@
+use ring::hmac;
+use std::fmt;
+
+/// SIGNATURE_HEADER carries `t=<unix seconds>,v1=<hex hmac>`, the HMAC-SHA256 of
+/// `<unix seconds>.<body>` keyed with the endpoint secret.
+pub const SIGNATURE_HEADER: &str = "pasys-signature";
+pub const EVENT_ID_HEADER: &str = "pasys-event-id";
+pub const EVENT_TYPE_HEADER: &str = "pasys-event-type";
+/// DEFAULT_TOLERANCE_SECONDS is how old a signature receivers should accept, so captured
+/// requests cannot be replayed later.
+pub const DEFAULT_TOLERANCE_SECONDS: i64 = 300;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SignatureError {
+    Malformed,
+    Mismatch,
+    Expired,
+}
+
+impl fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SignatureError::Malformed => write!(f, "signature header is malformed"),
+            SignatureError::Mismatch => write!(f, "signature does not match the payload"),
+            SignatureError::Expired => write!(f, "signature timestamp is outside the tolerance"),
+        }
+    }
+}
+
+impl std::error::Error for SignatureError {}
+
+fn key(secret: &str) -> hmac::Key {
+    hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes())
+}
+
+fn signed_payload(timestamp: i64, payload: &str) -> String {
+    format!("{timestamp}.{payload}")
+}
+
+/// sign returns the signature header of the payload sent at the timestamp.
+pub fn sign(secret: &str, timestamp: i64, payload: &str) -> String {
+    let tag = hmac::sign(&key(secret), signed_payload(timestamp, payload).as_bytes());
+    format!("t={timestamp},v1={}", hex::encode(tag.as_ref()))
+}
+
+/// verify checks a signature header the way merchants are expected to, in constant time and
+/// rejecting timestamps more than `tolerance_seconds` away from now.
+pub fn verify(
+    secret: &str,
+    header: &str,
+    payload: &str,
+    now: chrono::DateTime<chrono::Utc>,
+    tolerance_seconds: i64,
+) -> Result<(), SignatureError> {
+    let mut timestamp = None;
+    let mut signatures = vec![];
+    for part in header.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", value)) => timestamp = value.parse::<i64>().ok(),
+            Some(("v1", value)) => signatures.push(value),
+            _ => {}
+        }
+    }
+    let timestamp = match timestamp {
+        Some(timestamp) if !signatures.is_empty() => timestamp,
+        _ => return Err(SignatureError::Malformed),
+    };
+
+    let message = signed_payload(timestamp, payload);
+    let matches = signatures
+        .iter()
+        .any(|signature| match hex::decode(signature) {
+            Ok(tag) => hmac::verify(&key(secret), message.as_bytes(), &tag).is_ok(),
+            Err(_) => false,
+        });
+    if !matches {
+        return Err(SignatureError::Mismatch);
+    }
+    if (now.timestamp() - timestamp).abs() > tolerance_seconds {
+        return Err(SignatureError::Expired);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify() {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            header: String,
+            payload: &'static str,
+            expected: Result<(), SignatureError>,
+        }
+
+        let now = chrono::Utc::now();
+        let payload = r#"{"type":"transaction.settled"}"#;
+        let test_cases = vec![
+            TestCase {
+                name: "successfully verify signature",
+                header: sign("whsec_a", now.timestamp(), payload),
+                payload,
+                expected: Ok(()),
+            },
+            TestCase {
+                name: "successfully verify one of several signatures",
+                header: format!("{},v1=00ff", sign("whsec_a", now.timestamp() - 60, payload)),
+                payload,
+                expected: Ok(()),
+            },
+            TestCase {
+                name: "error when payload was tampered with",
+                header: sign("whsec_a", now.timestamp(), payload),
+                payload: r#"{"type":"transaction.refunded"}"#,
+                expected: Err(SignatureError::Mismatch),
+            },
+            TestCase {
+                name: "error when signed with another secret",
+                header: sign("whsec_b", now.timestamp(), payload),
+                payload,
+                expected: Err(SignatureError::Mismatch),
+            },
+            TestCase {
+                name: "error when signature is too old",
+                header: sign("whsec_a", now.timestamp() - 301, payload),
+                payload,
+                expected: Err(SignatureError::Expired),
+            },
+            TestCase {
+                name: "error when timestamp is missing",
+                header: "v1=00ff".to_string(),
+                payload,
+                expected: Err(SignatureError::Malformed),
+            },
+        ];
+
+        for test_case in test_cases {
+            let result = verify(
+                "whsec_a",
+                test_case.header.as_str(),
+                test_case.payload,
+                now,
+                DEFAULT_TOLERANCE_SECONDS,
+            );
+            assert_eq!(result, test_case.expected, "{}", test_case.name);
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use crate::domain::event::TransactionSummary;
+use async_trait::async_trait;
+use common::money::Money;
+use ledger_proto::ledger_v1;
+use ledger_proto::ledger_v1::ledger_client::LedgerClient;
+use tonic::transport::Channel;
+
+/// TransactionLookup finds the transactions events only carry the id of.
+#[async_trait]
+pub trait TransactionLookup: 'static + Send + Sync {
+    async fn get_transaction(&self, transaction_id: &str) -> anyhow::Result<TransactionSummary>;
+}
+
+#[async_trait]
+impl TransactionLookup for LedgerClient<Channel> {
+    async fn get_transaction(&self, transaction_id: &str) -> anyhow::Result<TransactionSummary> {
+        let request = ledger_v1::GetTransactionRequest {
+            transaction_id: transaction_id.to_string(),
+        };
+        let response = match LedgerClient::get_transaction(&mut self.clone(), request).await {
+            Ok(response) => response.into_inner(),
+            Err(e) => anyhow::bail!("failed to get transaction {transaction_id}: {e}"),
+        };
+
+        let transaction = match response.transaction {
+            Some(transaction) => transaction,
+            None => anyhow::bail!("ledger service returned no transaction {transaction_id}"),
+        };
+        let (amount_minor, currency) = match transaction.amount {
+            Some(amount) => {
+                match Money::from_units_nanos(amount.units, amount.nanos, &amount.currency_code) {
+                    Ok(money) => (money.amount_minor(), money.currency().code().to_string()),
+                    Err(e) => {
+                        anyhow::bail!("transaction {transaction_id} has an invalid amount: {e}")
+                    }
+                }
+            }
+            None => (0, String::new()),
+        };
+
+        Ok(TransactionSummary {
+            debit_account_id: transaction.debit_account_id,
+            credit_account_id: transaction.credit_account_id,
+            amount_minor,
+            currency,
+        })
+    }
+}
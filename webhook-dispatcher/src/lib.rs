@@ -0,0 +1,21 @@
+pub mod consumer;
+pub mod domain;
+pub mod ledger;
+pub mod repo;
+pub mod sender;
+pub mod service;
+
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 8;
+/// DEFAULT_BASE_BACKOFF_SECONDS doubles per failed attempt up to DEFAULT_MAX_BACKOFF_SECONDS,
+/// so the default attempts span about a day.
+pub const DEFAULT_BASE_BACKOFF_SECONDS: u64 = 60;
+pub const DEFAULT_MAX_BACKOFF_SECONDS: u64 = 6 * 60 * 60;
+pub const DEFAULT_SEND_TIMEOUT_MILLISECONDS: u64 = 10_000;
+/// DEFAULT_DELIVERY_LEASE_SECONDS hides a claimed delivery from other workers while it is
+/// sent, it must exceed the send timeout.
+pub const DEFAULT_DELIVERY_LEASE_SECONDS: i64 = 60;
+pub const DEFAULT_DELIVERY_BATCH_SIZE: i64 = 50;
+pub const DEFAULT_DELIVERY_INTERVAL_SECONDS: u64 = 1;
+pub const DEFAULT_WRITER_MAX_CONN: u32 = 4;
+pub const DEFAULT_READER_MAX_CONN: u32 = 2;
+pub const DEFAULT_TIMEOUT_SECONDS: u64 = 5;
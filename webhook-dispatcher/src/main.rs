@@ -0,0 +1,87 @@
+use common::{database, kafka, shutdown};
+use ledger_proto::ledger_v1::ledger_client::LedgerClient;
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+use tonic::transport::Channel;
+use webhook_dispatcher::domain::delivery::RetryPolicy;
+use webhook_dispatcher::domain::endpoint::UrlPolicy;
+use webhook_dispatcher::repo::PgWebhookRepository;
+use webhook_dispatcher::sender::HttpSender;
+use webhook_dispatcher::service::WebhookService;
+use webhook_dispatcher::{
+    DEFAULT_BASE_BACKOFF_SECONDS, DEFAULT_DELIVERY_INTERVAL_SECONDS, DEFAULT_MAX_ATTEMPTS,
+    DEFAULT_MAX_BACKOFF_SECONDS, DEFAULT_READER_MAX_CONN, DEFAULT_SEND_TIMEOUT_MILLISECONDS,
+    DEFAULT_TIMEOUT_SECONDS, DEFAULT_WRITER_MAX_CONN,
+};
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    // setup api database, where endpoints are registered
+    let database_config = database::Config {
+        reader_url: env::var("READER_DATABASE_URL").expect("READER_DATABASE_URL must be set"),
+        reader_max_connections: DEFAULT_READER_MAX_CONN,
+        writer_url: env::var("WRITER_DATABASE_URL").expect("WRITER_DATABASE_URL must be set"),
+        writer_max_connections: DEFAULT_WRITER_MAX_CONN,
+        timeout_in_secs: DEFAULT_TIMEOUT_SECONDS,
+    };
+    let db = database::Database::new(&database_config)
+        .await
+        .expect("failed to create database");
+
+    // setup repo layer
+    let repo = PgWebhookRepository::new(db);
+
+    // setup ledger client, used to look up transactions flagged as fraud
+    let ledger_url = env::var("LEDGER_URL").expect("LEDGER_URL must be set");
+    let ledger = LedgerClient::new(Channel::from_shared(ledger_url)?.connect_lazy());
+
+    // setup sender, only public https endpoints are sent to unless private urls are allowed
+    let url_policy = match env::var("WEBHOOK_ALLOW_PRIVATE_URLS")
+        .map(|v| v.parse::<bool>())
+        .unwrap_or(Ok(false))?
+    {
+        true => UrlPolicy::AllowPrivate,
+        false => UrlPolicy::Public,
+    };
+    let sender = HttpSender::new(
+        Duration::from_millis(DEFAULT_SEND_TIMEOUT_MILLISECONDS),
+        url_policy,
+    )?;
+    let max_attempts = env::var("WEBHOOK_MAX_ATTEMPTS")
+        .map(|v| v.parse::<u32>())
+        .unwrap_or(Ok(DEFAULT_MAX_ATTEMPTS))?;
+    let policy = RetryPolicy {
+        max_attempts: max_attempts.max(1),
+        base_backoff: Duration::from_secs(DEFAULT_BASE_BACKOFF_SECONDS),
+        max_backoff: Duration::from_secs(DEFAULT_MAX_BACKOFF_SECONDS),
+    };
+
+    // setup kafka
+    let kafka_config = kafka::Config {
+        brokers: env::var("KAFKA_BROKERS").expect("KAFKA_BROKERS must be set"),
+        group_id: env::var("KAFKA_GROUP_ID").unwrap_or("webhook-dispatcher".to_string()),
+    };
+    let dead_letters = kafka::DeadLetterQueue::new(&kafka_config)?;
+    let retry_topic = kafka::retry_topic(kafka_config.group_id.as_str());
+    let consumer = kafka::new_consumer(
+        &kafka_config,
+        &[
+            kafka::SETTLEMENT_RESULT_EVENTS_TOPIC,
+            kafka::FRAUD_DETECTED_EVENTS_TOPIC,
+            kafka::REFUND_EVENTS_TOPIC,
+            retry_topic.as_str(),
+        ],
+    )?;
+
+    // setup service
+    let service = Arc::new(WebhookService::new(repo, sender, ledger, policy));
+    service
+        .clone()
+        .spawn_delivery_worker(Duration::from_secs(DEFAULT_DELIVERY_INTERVAL_SECONDS));
+
+    kafka::consume_with_dead_letters(consumer, service, dead_letters, shutdown::shutdown_signal())
+        .await
+}
@@ -0,0 +1,310 @@
+use crate::domain::delivery::{Attempt, Delivery, DeliveryFilter, Status};
+use crate::repo::{DeliveryReader, DeliveryWriter, PgWebhookRepository};
+use async_trait::async_trait;
+
+const DELIVERY_COLUMNS: &str = "id, endpoint_id, event_id, event_type, payload, status, attempts, next_attempt_at, last_response_status, last_error, created_at, updated_at";
+
+#[async_trait]
+impl DeliveryWriter for PgWebhookRepository {
+    async fn record_deliveries(&self, deliveries: &[Delivery]) -> anyhow::Result<()> {
+        let mut tx = self.db.writer.begin().await?;
+        for delivery in deliveries {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO webhook_deliveries (id, endpoint_id, event_id, event_type, payload, status, attempts, next_attempt_at, created_at, updated_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                ON CONFLICT (endpoint_id, event_id) DO NOTHING
+                "#,
+            )
+            .bind(delivery.id)
+            .bind(delivery.endpoint_id)
+            .bind(delivery.event_id)
+            .bind(delivery.event_type.as_str())
+            .bind(delivery.payload.as_str())
+            .bind(delivery.status)
+            .bind(delivery.attempts)
+            .bind(delivery.next_attempt_at)
+            .bind(delivery.created_at)
+            .bind(delivery.updated_at)
+            .execute(&mut *tx)
+            .await;
+
+            if let Err(e) = result {
+                anyhow::bail!("Failed to insert webhook delivery into database: {e}")
+            }
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn claim_due_deliveries(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        lease_until: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<Delivery>> {
+        let result = sqlx::query_as::<_, Delivery>(&format!(
+            r#"
+            UPDATE webhook_deliveries
+            SET next_attempt_at = $2
+            WHERE id IN (
+                SELECT id
+                FROM webhook_deliveries
+                WHERE status = $3 AND next_attempt_at <= $1
+                ORDER BY next_attempt_at
+                LIMIT $4
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING {DELIVERY_COLUMNS}
+            "#
+        ))
+        .bind(now)
+        .bind(lease_until)
+        .bind(Status::Pending)
+        .bind(limit)
+        .fetch_all(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(mut deliveries) => {
+                deliveries.sort_by_key(|delivery| delivery.created_at);
+                Ok(deliveries)
+            }
+            Err(e) => anyhow::bail!("Failed to claim due webhook deliveries: {e}"),
+        }
+    }
+
+    async fn record_attempt(&self, delivery: &Delivery, attempt: &Attempt) -> anyhow::Result<()> {
+        let mut tx = self.db.writer.begin().await?;
+        let result = sqlx::query(
+            r#"
+            INSERT INTO webhook_delivery_attempts (delivery_id, attempt, response_status, error, attempted_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(attempt.delivery_id)
+        .bind(attempt.attempt)
+        .bind(attempt.response_status)
+        .bind(attempt.error.as_deref())
+        .bind(attempt.attempted_at)
+        .execute(&mut *tx)
+        .await;
+        if let Err(e) = result {
+            anyhow::bail!("Failed to insert webhook delivery attempt into database: {e}")
+        }
+
+        let result = sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = $2, attempts = $3, next_attempt_at = $4, last_response_status = $5, last_error = $6, updated_at = $7
+            WHERE id = $1
+            "#,
+        )
+        .bind(delivery.id)
+        .bind(delivery.status)
+        .bind(delivery.attempts)
+        .bind(delivery.next_attempt_at)
+        .bind(delivery.last_response_status)
+        .bind(delivery.last_error.as_deref())
+        .bind(delivery.updated_at)
+        .execute(&mut *tx)
+        .await;
+        if let Err(e) = result {
+            anyhow::bail!("Failed to update webhook delivery: {e}")
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn redeliver_delivery(
+        &self,
+        id: uuid::Uuid,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<Option<Delivery>> {
+        let result = sqlx::query_as::<_, Delivery>(&format!(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = $2, attempts = 0, next_attempt_at = $3, updated_at = $3
+            WHERE id = $1
+            RETURNING {DELIVERY_COLUMNS}
+            "#
+        ))
+        .bind(id)
+        .bind(Status::Pending)
+        .bind(now)
+        .fetch_optional(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(delivery) => Ok(delivery),
+            Err(e) => anyhow::bail!("Failed to redeliver webhook delivery: {e}"),
+        }
+    }
+}
+
+#[async_trait]
+impl DeliveryReader for PgWebhookRepository {
+    async fn get_delivery_by_id(&self, id: uuid::Uuid) -> anyhow::Result<Option<Delivery>> {
+        let result = sqlx::query_as::<_, Delivery>(&format!(
+            r#"
+            SELECT {DELIVERY_COLUMNS}
+            FROM webhook_deliveries
+            WHERE id = $1
+            "#
+        ))
+        .bind(id)
+        .fetch_optional(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(delivery) => Ok(delivery),
+            Err(e) => anyhow::bail!("Failed to get webhook delivery from database: {e}"),
+        }
+    }
+
+    async fn get_deliveries(&self, filter: &DeliveryFilter) -> anyhow::Result<Vec<Delivery>> {
+        let result = sqlx::query_as::<_, Delivery>(
+            r#"
+            SELECT d.id, d.endpoint_id, d.event_id, d.event_type, d.payload, d.status, d.attempts, d.next_attempt_at, d.last_response_status, d.last_error, d.created_at, d.updated_at
+            FROM webhook_deliveries d
+            JOIN webhook_endpoints e ON e.id = d.endpoint_id
+            WHERE ($1::TEXT IS NULL OR e.principal = $1)
+              AND ($2::UUID IS NULL OR d.endpoint_id = $2)
+              AND ($3::webhook_delivery_status IS NULL OR d.status = $3)
+            ORDER BY d.created_at DESC
+            LIMIT $4
+            "#,
+        )
+        .bind(filter.principal.as_deref())
+        .bind(filter.endpoint_id)
+        .bind(filter.status)
+        .bind(filter.limit)
+        .fetch_all(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(deliveries) => Ok(deliveries),
+            Err(e) => anyhow::bail!("Failed to get webhook deliveries from database: {e}"),
+        }
+    }
+
+    async fn get_attempts_by_delivery_id(
+        &self,
+        delivery_id: uuid::Uuid,
+    ) -> anyhow::Result<Vec<Attempt>> {
+        let result = sqlx::query_as::<_, Attempt>(
+            r#"
+            SELECT delivery_id, attempt, response_status, error, attempted_at
+            FROM webhook_delivery_attempts
+            WHERE delivery_id = $1
+            ORDER BY id
+            "#,
+        )
+        .bind(delivery_id)
+        .fetch_all(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(attempts) => Ok(attempts),
+            Err(e) => anyhow::bail!("Failed to get webhook delivery attempts from database: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::delivery::RetryPolicy;
+    use crate::domain::event::WebhookEvent;
+    use crate::domain::event::tests::settlement_event;
+    use crate::repo::endpoint::tests::seed_endpoint;
+    use events_proto::events_v1;
+    use std::time::Duration;
+
+    #[sqlx::test(migrations = "../migrations/api")]
+    async fn successfully_claim_attempt_and_redeliver(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgWebhookRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let account_id = uuid::Uuid::new_v4();
+        let endpoint =
+            seed_endpoint(&repo, "https://a.example", vec![], vec![account_id], false).await;
+        let event = WebhookEvent::from_settlement(&settlement_event(
+            account_id,
+            uuid::Uuid::new_v4(),
+            events_v1::SettlementStatus::Settled,
+        ))
+        .unwrap()
+        .unwrap();
+        let delivery = Delivery::new(endpoint.id, &event).unwrap();
+        repo.record_deliveries(std::slice::from_ref(&delivery))
+            .await
+            .unwrap();
+        // the same event consumed again is skipped
+        repo.record_deliveries(&[Delivery::new(endpoint.id, &event).unwrap()])
+            .await
+            .unwrap();
+        let now = chrono::Utc::now();
+        let lease_until = now + chrono::Duration::seconds(60);
+
+        // act
+        let claimed = repo
+            .claim_due_deliveries(now, lease_until, 10)
+            .await
+            .unwrap();
+        let claimed_again = repo
+            .claim_due_deliveries(now, lease_until, 10)
+            .await
+            .unwrap();
+
+        // assert
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].id, delivery.id);
+        assert!(claimed_again.is_empty());
+
+        let policy = RetryPolicy {
+            max_attempts: 1,
+            base_backoff: Duration::from_secs(60),
+            max_backoff: Duration::from_secs(60),
+        };
+        let mut failed = claimed[0].clone();
+        let attempt = failed.record_attempt(Ok(500), now, &policy);
+        repo.record_attempt(&failed, &attempt).await.unwrap();
+        let found = repo.get_delivery_by_id(delivery.id).await.unwrap().unwrap();
+        assert_eq!(found.status, Status::Failed);
+        assert_eq!(found.last_response_status, Some(500));
+
+        let redelivered = repo
+            .redeliver_delivery(delivery.id, now)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(redelivered.status, Status::Pending);
+        assert_eq!(redelivered.attempts, 0);
+        assert_eq!(
+            repo.get_attempts_by_delivery_id(delivery.id)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        let deliveries = repo
+            .get_deliveries(&DeliveryFilter {
+                principal: Some("merchant-a".to_string()),
+                status: Some(Status::Pending),
+                limit: 10,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(deliveries, vec![redelivered]);
+        assert!(
+            repo.redeliver_delivery(uuid::Uuid::new_v4(), now)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use crate::domain::endpoint::Endpoint;
+use crate::repo::{EndpointReader, EndpointWriter, PgWebhookRepository};
+use async_trait::async_trait;
+
+const ENDPOINT_COLUMNS: &str =
+    "id, principal, url, secret, event_types, account_ids, all_accounts, created_at, disabled_at";
+
+#[async_trait]
+impl EndpointWriter for PgWebhookRepository {
+    async fn create_endpoint(&self, endpoint: &Endpoint) -> anyhow::Result<Endpoint> {
+        let result = sqlx::query_as::<_, Endpoint>(&format!(
+            r#"
+            INSERT INTO webhook_endpoints (id, principal, url, secret, event_types, account_ids, all_accounts, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING {ENDPOINT_COLUMNS}
+            "#
+        ))
+        .bind(endpoint.id)
+        .bind(endpoint.principal.as_str())
+        .bind(endpoint.url.as_str())
+        .bind(endpoint.secret.as_str())
+        .bind(&endpoint.event_types)
+        .bind(&endpoint.account_ids)
+        .bind(endpoint.all_accounts)
+        .bind(endpoint.created_at)
+        .fetch_one(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(endpoint) => Ok(endpoint),
+            Err(e) => anyhow::bail!("Failed to insert webhook endpoint into database: {e}"),
+        }
+    }
+
+    async fn disable_endpoint(&self, id: uuid::Uuid) -> anyhow::Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE webhook_endpoints
+            SET disabled_at = now()
+            WHERE id = $1 AND disabled_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .execute(&self.db.writer)
+        .await;
+
+        match result {
+            Ok(result) => Ok(result.rows_affected() == 1),
+            Err(e) => anyhow::bail!("Failed to disable webhook endpoint: {e}"),
+        }
+    }
+}
+
+#[async_trait]
+impl EndpointReader for PgWebhookRepository {
+    async fn get_endpoint_by_id(&self, id: uuid::Uuid) -> anyhow::Result<Option<Endpoint>> {
+        let result = sqlx::query_as::<_, Endpoint>(&format!(
+            r#"
+            SELECT {ENDPOINT_COLUMNS}
+            FROM webhook_endpoints
+            WHERE id = $1
+            "#
+        ))
+        .bind(id)
+        .fetch_optional(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(endpoint) => Ok(endpoint),
+            Err(e) => anyhow::bail!("Failed to get webhook endpoint from database: {e}"),
+        }
+    }
+
+    async fn get_endpoints(&self, principal: Option<&str>) -> anyhow::Result<Vec<Endpoint>> {
+        let result = sqlx::query_as::<_, Endpoint>(&format!(
+            r#"
+            SELECT {ENDPOINT_COLUMNS}
+            FROM webhook_endpoints
+            WHERE disabled_at IS NULL AND ($1::TEXT IS NULL OR principal = $1)
+            ORDER BY created_at
+            "#
+        ))
+        .bind(principal)
+        .fetch_all(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(endpoints) => Ok(endpoints),
+            Err(e) => anyhow::bail!("Failed to get webhook endpoints from database: {e}"),
+        }
+    }
+
+    async fn get_subscribed_endpoints(
+        &self,
+        event_type: &str,
+        account_ids: &[uuid::Uuid],
+    ) -> anyhow::Result<Vec<Endpoint>> {
+        let result = sqlx::query_as::<_, Endpoint>(&format!(
+            r#"
+            SELECT {ENDPOINT_COLUMNS}
+            FROM webhook_endpoints
+            WHERE disabled_at IS NULL
+              AND (cardinality(event_types) = 0 OR $1 = ANY(event_types))
+              AND (all_accounts OR account_ids && $2)
+            ORDER BY created_at
+            "#
+        ))
+        .bind(event_type)
+        .bind(account_ids)
+        .fetch_all(&self.db.reader)
+        .await;
+
+        match result {
+            Ok(endpoints) => Ok(endpoints),
+            Err(e) => anyhow::bail!("Failed to get subscribed webhook endpoints: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::domain::endpoint::UrlPolicy;
+
+    pub(crate) async fn seed_endpoint(
+        repo: &PgWebhookRepository,
+        url: &str,
+        event_types: Vec<&str>,
+        account_ids: Vec<uuid::Uuid>,
+        all_accounts: bool,
+    ) -> Endpoint {
+        let endpoint = Endpoint::new(
+            "merchant-a",
+            url,
+            event_types.into_iter().map(String::from).collect(),
+            account_ids,
+            all_accounts,
+            UrlPolicy::Public,
+        )
+        .unwrap();
+        repo.create_endpoint(&endpoint).await.unwrap()
+    }
+
+    #[sqlx::test(migrations = "../migrations/api")]
+    async fn successfully_get_subscribed_endpoints(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgWebhookRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let account_id = uuid::Uuid::new_v4();
+        let every_event =
+            seed_endpoint(&repo, "https://a.example", vec![], vec![account_id], false).await;
+        let operator = seed_endpoint(&repo, "https://ops.example", vec![], vec![], true).await;
+        seed_endpoint(
+            &repo,
+            "https://refunds.example",
+            vec!["transaction.refunded"],
+            vec![account_id],
+            false,
+        )
+        .await;
+        seed_endpoint(
+            &repo,
+            "https://other.example",
+            vec![],
+            vec![uuid::Uuid::new_v4()],
+            false,
+        )
+        .await;
+        let disabled = seed_endpoint(
+            &repo,
+            "https://old.example",
+            vec![],
+            vec![account_id],
+            false,
+        )
+        .await;
+        assert!(repo.disable_endpoint(disabled.id).await.unwrap());
+
+        // act
+        let endpoints = repo
+            .get_subscribed_endpoints("transaction.settled", &[uuid::Uuid::new_v4(), account_id])
+            .await
+            .unwrap();
+
+        // assert
+        assert_eq!(endpoints, vec![every_event, operator]);
+        assert!(!repo.disable_endpoint(disabled.id).await.unwrap());
+        assert_eq!(
+            repo.get_endpoints(Some("merchant-a")).await.unwrap().len(),
+            4
+        );
+        assert!(
+            repo.get_endpoints(Some("merchant-b"))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use crate::domain::delivery::{Attempt, Delivery, DeliveryFilter};
+use crate::domain::endpoint::Endpoint;
+use async_trait::async_trait;
+use common::database::Database;
+
+mod delivery;
+mod endpoint;
+
+#[derive(Debug, Clone)]
+pub struct PgWebhookRepository {
+    pub db: Database,
+}
+
+impl PgWebhookRepository {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+pub trait WebhookRepository:
+    EndpointWriter
+    + EndpointReader
+    + DeliveryWriter
+    + DeliveryReader
+    + std::fmt::Debug
+    + 'static
+    + Send
+    + Sync
+{
+}
+
+#[async_trait]
+pub trait EndpointWriter: 'static + Send + Sync {
+    async fn create_endpoint(&self, endpoint: &Endpoint) -> anyhow::Result<Endpoint>;
+    /// disable_endpoint stops deliveries to the endpoint, returning false when it was not
+    /// found or already disabled.
+    async fn disable_endpoint(&self, id: uuid::Uuid) -> anyhow::Result<bool>;
+}
+
+#[async_trait]
+pub trait EndpointReader: 'static + Send + Sync {
+    async fn get_endpoint_by_id(&self, id: uuid::Uuid) -> anyhow::Result<Option<Endpoint>>;
+    /// get_endpoints returns the enabled endpoints of the principal, of every principal when
+    /// none is given, oldest first.
+    async fn get_endpoints(&self, principal: Option<&str>) -> anyhow::Result<Vec<Endpoint>>;
+    /// get_subscribed_endpoints returns the enabled endpoints subscribed to the event type
+    /// which may see any of the accounts.
+    async fn get_subscribed_endpoints(
+        &self,
+        event_type: &str,
+        account_ids: &[uuid::Uuid],
+    ) -> anyhow::Result<Vec<Endpoint>>;
+}
+
+#[async_trait]
+pub trait DeliveryWriter: 'static + Send + Sync {
+    /// record_deliveries stores new deliveries, skipping events already recorded for an
+    /// endpoint so redelivered kafka messages are not sent twice.
+    async fn record_deliveries(&self, deliveries: &[Delivery]) -> anyhow::Result<()>;
+    /// claim_due_deliveries returns pending deliveries due at `now`, oldest first, and pushes
+    /// them back to `lease_until` so other workers skip them while they are sent.
+    async fn claim_due_deliveries(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        lease_until: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<Delivery>>;
+    /// record_attempt adds the attempt to the delivery log and updates the delivery with its
+    /// outcome.
+    async fn record_attempt(&self, delivery: &Delivery, attempt: &Attempt) -> anyhow::Result<()>;
+    /// redeliver_delivery queues the delivery to be sent again with a fresh set of attempts,
+    /// earlier attempts stay in the log.
+    async fn redeliver_delivery(
+        &self,
+        id: uuid::Uuid,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> anyhow::Result<Option<Delivery>>;
+}
+
+#[async_trait]
+pub trait DeliveryReader: 'static + Send + Sync {
+    async fn get_delivery_by_id(&self, id: uuid::Uuid) -> anyhow::Result<Option<Delivery>>;
+    /// get_deliveries returns the deliveries matching the filter, newest first.
+    async fn get_deliveries(&self, filter: &DeliveryFilter) -> anyhow::Result<Vec<Delivery>>;
+    async fn get_attempts_by_delivery_id(
+        &self,
+        delivery_id: uuid::Uuid,
+    ) -> anyhow::Result<Vec<Attempt>>;
+}
+
+impl WebhookRepository for PgWebhookRepository {}
@@ -0,0 +1,138 @@
+use crate::domain::endpoint::UrlPolicy;
+use async_trait::async_trait;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// WebhookSender POSTs a signed payload to an endpoint, returning the response status or why
+/// no response was received.
+#[async_trait]
+pub trait WebhookSender: 'static + Send + Sync {
+    async fn send(
+        &self,
+        url: &str,
+        headers: &[(&'static str, String)],
+        body: &str,
+    ) -> Result<u16, String>;
+}
+
+/// PolicyResolver resolves host names and drops the addresses the policy does not allow, so a
+/// public name pointing at an internal address cannot be sent to.
+#[derive(Debug, Clone, Copy)]
+struct PolicyResolver(UrlPolicy);
+
+impl Resolve for PolicyResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let policy = self.0;
+        Box::pin(async move {
+            let host = name.as_str().to_string();
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .filter(|addr| policy.allows_ip(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("host {host} does not resolve to a public address").into());
+            }
+
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// HttpSender sends webhooks over HTTP. Redirects are not followed, the merchant has to
+/// register the final url. Urls are checked against the policy again before sending, and host
+/// names are resolved to allowed addresses only. Proxies are not used, as they would resolve hosts
+/// past the policy.
+#[derive(Debug, Clone)]
+pub struct HttpSender {
+    client: reqwest::Client,
+    policy: UrlPolicy,
+}
+
+impl HttpSender {
+    pub fn new(timeout: Duration, policy: UrlPolicy) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .redirect(reqwest::redirect::Policy::none())
+            .no_proxy()
+            .dns_resolver(Arc::new(PolicyResolver(policy)))
+            .build()?;
+
+        Ok(Self { client, policy })
+    }
+}
+
+#[async_trait]
+impl WebhookSender for HttpSender {
+    async fn send(
+        &self,
+        url: &str,
+        headers: &[(&'static str, String)],
+        body: &str,
+    ) -> Result<u16, String> {
+        let url = match self.policy.check(url) {
+            Ok(url) => url,
+            Err(e) => return Err(e.to_string()),
+        };
+
+        let mut request = self.client.post(url).body(body.to_string());
+        for (name, value) in headers {
+            request = request.header(*name, value.as_str());
+        }
+
+        match request.send().await {
+            Ok(response) => Ok(response.status().as_u16()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::Router;
+    use axum::http::StatusCode;
+    use axum::routing::post;
+    use common::test_util::spawn_stub;
+    use std::str::FromStr;
+
+    #[tokio::test]
+    async fn error_when_sending_to_private_address() {
+        // arrange - the stub listens on loopback
+        let router = Router::new().route("/hooks", post(|| async { StatusCode::NO_CONTENT }));
+        let url = format!("{}/hooks", spawn_stub(router).await);
+        let sender = HttpSender::new(Duration::from_secs(2), UrlPolicy::Public).unwrap();
+
+        // act
+        let resp = sender.send(url.as_str(), &[], "{}").await;
+
+        // assert
+        assert!(resp.is_err());
+        assert_eq!(
+            HttpSender::new(Duration::from_secs(2), UrlPolicy::AllowPrivate)
+                .unwrap()
+                .send(url.as_str(), &[], "{}")
+                .await,
+            Ok(204)
+        );
+    }
+
+    #[tokio::test]
+    async fn error_when_host_resolves_to_private_address() {
+        // arrange
+        let resolver = PolicyResolver(UrlPolicy::Public);
+
+        // act
+        let resp = resolver.resolve(Name::from_str("localhost").unwrap()).await;
+
+        // assert
+        assert!(resp.is_err());
+        assert!(
+            PolicyResolver(UrlPolicy::AllowPrivate)
+                .resolve(Name::from_str("localhost").unwrap())
+                .await
+                .is_ok()
+        );
+    }
+}
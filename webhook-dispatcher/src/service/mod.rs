@@ -0,0 +1,481 @@
+use crate::domain::delivery::{Delivery, RetryPolicy, Status};
+use crate::domain::endpoint::Endpoint;
+use crate::domain::event::WebhookEvent;
+use crate::domain::signature::{self, EVENT_ID_HEADER, EVENT_TYPE_HEADER, SIGNATURE_HEADER};
+use crate::ledger::TransactionLookup;
+use crate::repo::WebhookRepository;
+use crate::sender::WebhookSender;
+use crate::{DEFAULT_DELIVERY_BATCH_SIZE, DEFAULT_DELIVERY_LEASE_SECONDS};
+use events_proto::events_v1;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::sync::Arc;
+
+pub struct WebhookService<R, S, L>
+where
+    R: WebhookRepository,
+    S: WebhookSender,
+    L: TransactionLookup,
+{
+    repo: R,
+    sender: S,
+    lookup: L,
+    policy: RetryPolicy,
+    batch_size: i64,
+}
+
+impl<R, S, L> WebhookService<R, S, L>
+where
+    R: WebhookRepository,
+    S: WebhookSender,
+    L: TransactionLookup,
+{
+    pub fn new(repo: R, sender: S, lookup: L, policy: RetryPolicy) -> Self {
+        Self {
+            repo,
+            sender,
+            lookup,
+            policy,
+            batch_size: DEFAULT_DELIVERY_BATCH_SIZE,
+        }
+    }
+
+    /// record_event queues a delivery of the event for every endpoint subscribed to it.
+    pub async fn record_event(&self, event: &WebhookEvent) -> anyhow::Result<Vec<Delivery>> {
+        let endpoints = self
+            .repo
+            .get_subscribed_endpoints(event.event_type.as_ref(), &event.account_ids())
+            .await?;
+
+        let mut deliveries = vec![];
+        for endpoint in endpoints {
+            deliveries.push(Delivery::new(endpoint.id, event)?);
+        }
+        if !deliveries.is_empty() {
+            self.repo.record_deliveries(&deliveries).await?;
+        }
+
+        Ok(deliveries)
+    }
+
+    pub async fn record_settlement(
+        &self,
+        settlement: &events_v1::Settlement,
+    ) -> anyhow::Result<Vec<Delivery>> {
+        match WebhookEvent::from_settlement(settlement)? {
+            Some(event) => self.record_event(&event).await,
+            None => Ok(vec![]),
+        }
+    }
+
+    /// record_fraud looks up the flagged transaction in the ledger, transactions which are
+    /// not flagged are skipped without a lookup.
+    pub async fn record_fraud(&self, fraud: &events_v1::Fraud) -> anyhow::Result<Vec<Delivery>> {
+        if !fraud.is_fraud {
+            return Ok(vec![]);
+        }
+        let transaction = self
+            .lookup
+            .get_transaction(fraud.transaction_id.as_str())
+            .await?;
+
+        match WebhookEvent::from_fraud(fraud, transaction) {
+            Some(event) => self.record_event(&event).await,
+            None => Ok(vec![]),
+        }
+    }
+
+    pub async fn record_refund(&self, refund: &events_v1::Refund) -> anyhow::Result<Vec<Delivery>> {
+        self.record_event(&WebhookEvent::from_refund(refund)?).await
+    }
+
+    /// deliver_due sends the deliveries due at `now` concurrently and records the outcome of
+    /// each attempt, returning how many were attempted. Deliveries to endpoints which were
+    /// disabled since are failed without being sent.
+    pub async fn deliver_due(&self, now: chrono::DateTime<chrono::Utc>) -> anyhow::Result<usize> {
+        let lease_until = now + chrono::Duration::seconds(DEFAULT_DELIVERY_LEASE_SECONDS);
+        let deliveries = self
+            .repo
+            .claim_due_deliveries(now, lease_until, self.batch_size)
+            .await?;
+
+        let mut endpoints: HashMap<uuid::Uuid, Option<Endpoint>> = HashMap::new();
+        for delivery in &deliveries {
+            if let Entry::Vacant(entry) = endpoints.entry(delivery.endpoint_id) {
+                entry.insert(self.repo.get_endpoint_by_id(delivery.endpoint_id).await?);
+            }
+        }
+
+        let attempted = deliveries.len();
+        let results = futures_util::future::join_all(deliveries.into_iter().map(|delivery| {
+            let endpoint = endpoints.get(&delivery.endpoint_id).cloned().flatten();
+            self.deliver(delivery, endpoint)
+        }))
+        .await;
+        for result in results {
+            if let Err(e) = result {
+                tracing::error!("failed to record webhook delivery attempt: {e}");
+            }
+        }
+
+        Ok(attempted)
+    }
+
+    async fn deliver(
+        &self,
+        mut delivery: Delivery,
+        endpoint: Option<Endpoint>,
+    ) -> anyhow::Result<()> {
+        let endpoint = endpoint.filter(|endpoint| endpoint.disabled_at.is_none());
+        let now = chrono::Utc::now();
+        let outcome = match &endpoint {
+            Some(endpoint) => {
+                let headers = [
+                    ("content-type", "application/json".to_string()),
+                    (
+                        SIGNATURE_HEADER,
+                        signature::sign(
+                            endpoint.secret.as_str(),
+                            now.timestamp(),
+                            delivery.payload.as_str(),
+                        ),
+                    ),
+                    (EVENT_ID_HEADER, delivery.event_id.to_string()),
+                    (EVENT_TYPE_HEADER, delivery.event_type.clone()),
+                ];
+                self.sender
+                    .send(endpoint.url.as_str(), &headers, delivery.payload.as_str())
+                    .await
+            }
+            None => Err(format!("endpoint {} is disabled", delivery.endpoint_id)),
+        };
+
+        let attempt = delivery.record_attempt(outcome, now, &self.policy);
+        if endpoint.is_none() {
+            delivery.status = Status::Failed;
+        }
+        match delivery.status {
+            Status::Succeeded => {}
+            status => tracing::warn!(
+                delivery_id = %delivery.id,
+                attempt = attempt.attempt,
+                status = status.as_ref(),
+                "webhook delivery failed: {}",
+                attempt.error.as_deref().unwrap_or_default()
+            ),
+        }
+
+        self.repo.record_attempt(&delivery, &attempt).await
+    }
+
+    /// spawn_delivery_worker sends due deliveries on every tick of the interval.
+    pub fn spawn_delivery_worker(
+        self: Arc<Self>,
+        interval: std::time::Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.deliver_due(chrono::Utc::now()).await {
+                    tracing::error!("failed to deliver webhooks: {e}");
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::domain::endpoint::UrlPolicy;
+    use crate::domain::event::TransactionSummary;
+    use crate::domain::event::tests::settlement_event;
+    use crate::repo::{DeliveryReader, EndpointWriter, PgWebhookRepository};
+    use crate::sender::HttpSender;
+    use async_trait::async_trait;
+    use axum::Router;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use common::test_util::spawn_stub;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    /// ScriptedSender answers sends from a script, then responds with 200 once the script
+    /// runs out.
+    #[derive(Default)]
+    pub(crate) struct ScriptedSender {
+        pub script: Mutex<Vec<Result<u16, String>>>,
+        pub urls: Mutex<Vec<String>>,
+    }
+
+    impl ScriptedSender {
+        pub(crate) fn new(script: Vec<Result<u16, String>>) -> Self {
+            Self {
+                script: Mutex::new(script),
+                urls: Mutex::new(vec![]),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl WebhookSender for ScriptedSender {
+        async fn send(
+            &self,
+            url: &str,
+            _headers: &[(&'static str, String)],
+            _body: &str,
+        ) -> Result<u16, String> {
+            self.urls.lock().unwrap().push(url.to_string());
+            let mut script = self.script.lock().unwrap();
+            match script.is_empty() {
+                true => Ok(200),
+                false => script.remove(0),
+            }
+        }
+    }
+
+    /// StaticLookup answers every lookup with the same transaction.
+    pub(crate) struct StaticLookup(pub Option<TransactionSummary>);
+
+    #[async_trait]
+    impl TransactionLookup for StaticLookup {
+        async fn get_transaction(
+            &self,
+            transaction_id: &str,
+        ) -> anyhow::Result<TransactionSummary> {
+            match &self.0 {
+                Some(transaction) => Ok(transaction.clone()),
+                None => anyhow::bail!("transaction {transaction_id} not found"),
+            }
+        }
+    }
+
+    pub(crate) fn policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 2,
+            base_backoff: Duration::from_secs(60),
+            max_backoff: Duration::from_secs(60),
+        }
+    }
+
+    async fn seed_endpoint(
+        repo: &PgWebhookRepository,
+        url: &str,
+        account_id: uuid::Uuid,
+    ) -> Endpoint {
+        let endpoint = Endpoint::new(
+            "merchant-a",
+            url,
+            vec![],
+            vec![account_id],
+            false,
+            UrlPolicy::AllowPrivate,
+        )
+        .unwrap();
+        repo.create_endpoint(&endpoint).await.unwrap()
+    }
+
+    #[sqlx::test(migrations = "../migrations/api")]
+    async fn successfully_deliver_signed_event(pool: sqlx::PgPool) {
+        // arrange - the stub only accepts requests with a valid signature
+        let repo =
+            PgWebhookRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let secret = Arc::new(Mutex::new(String::new()));
+        let stub_secret = secret.clone();
+        let router = Router::new().route(
+            "/hooks",
+            post(move |headers: HeaderMap, body: String| async move {
+                let secret = stub_secret.lock().unwrap().clone();
+                let header = headers[SIGNATURE_HEADER].to_str().unwrap();
+                match signature::verify(
+                    secret.as_str(),
+                    header,
+                    body.as_str(),
+                    chrono::Utc::now(),
+                    signature::DEFAULT_TOLERANCE_SECONDS,
+                ) {
+                    Ok(()) if headers[EVENT_TYPE_HEADER] == "transaction.settled" => {
+                        StatusCode::NO_CONTENT
+                    }
+                    _ => StatusCode::BAD_REQUEST,
+                }
+            }),
+        );
+        let url = format!("{}/hooks", spawn_stub(router).await);
+        let account_id = uuid::Uuid::new_v4();
+        let endpoint = seed_endpoint(&repo, url.as_str(), account_id).await;
+        *secret.lock().unwrap() = endpoint.secret.clone();
+        let service = WebhookService::new(
+            repo.clone(),
+            HttpSender::new(Duration::from_secs(2), UrlPolicy::AllowPrivate).unwrap(),
+            StaticLookup(None),
+            policy(),
+        );
+        let deliveries = service
+            .record_settlement(&settlement_event(
+                account_id,
+                uuid::Uuid::new_v4(),
+                events_v1::SettlementStatus::Settled,
+            ))
+            .await
+            .unwrap();
+
+        // act
+        let attempted = service.deliver_due(chrono::Utc::now()).await.unwrap();
+
+        // assert
+        assert_eq!(attempted, 1);
+        let delivery = repo
+            .get_delivery_by_id(deliveries[0].id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(delivery.status, Status::Succeeded);
+        assert_eq!(delivery.last_response_status, Some(204));
+    }
+
+    #[sqlx::test(migrations = "../migrations/api")]
+    async fn test_deliver_due(pool: sqlx::PgPool) {
+        #[derive(Debug)]
+        struct TestCase {
+            name: &'static str,
+            script: Vec<Result<u16, String>>,
+            disable_endpoint: bool,
+            expected_statuses: Vec<Status>,
+            expected_sends: usize,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                name: "successfully deliver on first attempt",
+                script: vec![],
+                disable_endpoint: false,
+                expected_statuses: vec![Status::Succeeded],
+                expected_sends: 1,
+            },
+            TestCase {
+                name: "successfully deliver after a retry",
+                script: vec![Err("connection refused".to_string())],
+                disable_endpoint: false,
+                expected_statuses: vec![Status::Pending, Status::Succeeded],
+                expected_sends: 2,
+            },
+            TestCase {
+                name: "failed when attempts are exhausted",
+                script: vec![Ok(500), Ok(503)],
+                disable_endpoint: false,
+                expected_statuses: vec![Status::Pending, Status::Failed],
+                expected_sends: 2,
+            },
+            TestCase {
+                name: "failed without sending when endpoint is disabled",
+                script: vec![],
+                disable_endpoint: true,
+                expected_statuses: vec![Status::Failed],
+                expected_sends: 0,
+            },
+        ];
+
+        let repo =
+            PgWebhookRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        for test_case in test_cases {
+            // arrange
+            let account_id = uuid::Uuid::new_v4();
+            let endpoint = seed_endpoint(&repo, "https://merchant.example", account_id).await;
+            let service = WebhookService::new(
+                repo.clone(),
+                ScriptedSender::new(test_case.script),
+                StaticLookup(None),
+                policy(),
+            );
+            let delivery = service
+                .record_settlement(&settlement_event(
+                    uuid::Uuid::new_v4(),
+                    account_id,
+                    events_v1::SettlementStatus::Failed,
+                ))
+                .await
+                .unwrap()
+                .remove(0);
+            if test_case.disable_endpoint {
+                repo.disable_endpoint(endpoint.id).await.unwrap();
+            }
+
+            // act - every round runs once the previous backoff has passed
+            let mut statuses = vec![];
+            let mut now = chrono::Utc::now();
+            for _ in 0..test_case.expected_statuses.len() {
+                service.deliver_due(now).await.unwrap();
+                statuses.push(
+                    repo.get_delivery_by_id(delivery.id)
+                        .await
+                        .unwrap()
+                        .unwrap()
+                        .status,
+                );
+                now += chrono::Duration::seconds(61);
+            }
+
+            // assert
+            assert_eq!(statuses, test_case.expected_statuses, "{}", test_case.name);
+            assert_eq!(
+                service.sender.urls.lock().unwrap().len(),
+                test_case.expected_sends,
+                "{}",
+                test_case.name
+            );
+            assert_eq!(
+                repo.get_attempts_by_delivery_id(delivery.id)
+                    .await
+                    .unwrap()
+                    .len(),
+                test_case.expected_statuses.len(),
+                "{}",
+                test_case.name
+            );
+        }
+    }
+
+    #[sqlx::test(migrations = "../migrations/api")]
+    async fn successfully_record_fraud_for_transaction_accounts(pool: sqlx::PgPool) {
+        // arrange
+        let repo =
+            PgWebhookRepository::new(common::database::Database::from_pool(pool).await.unwrap());
+        let account_id = uuid::Uuid::new_v4();
+        seed_endpoint(&repo, "https://merchant.example", account_id).await;
+        let service = WebhookService::new(
+            repo,
+            ScriptedSender::default(),
+            StaticLookup(Some(TransactionSummary {
+                debit_account_id: uuid::Uuid::new_v4().to_string(),
+                credit_account_id: account_id.to_string(),
+                amount_minor: 1050,
+                currency: "USD".to_string(),
+            })),
+            policy(),
+        );
+        let fraud = events_v1::Fraud {
+            transaction_id: uuid::Uuid::new_v4().to_string(),
+            is_fraud: true,
+            score: 0.93,
+            ..Default::default()
+        };
+
+        // act
+        let deliveries = service.record_fraud(&fraud).await.unwrap();
+        let not_fraud = service
+            .record_fraud(&events_v1::Fraud {
+                is_fraud: false,
+                ..fraud.clone()
+            })
+            .await
+            .unwrap();
+
+        // assert
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].event_type, "transaction.fraud_flagged");
+        assert!(deliveries[0].payload.contains(r#""fraud_score":0.93"#));
+        assert!(not_fraud.is_empty());
+    }
+}